@@ -0,0 +1,121 @@
+# keyer_portable - the platform-independent layer of the keyer, built without ESP-IDF
+#
+# Stream, sample, consumers and fault (keyer_core), the iambic FSM, sidetone/PTT
+# DSP, log buffers, the console parser and output, Morse table, decoder, the pure
+# text keyer logic and the CWNet protocol. Each file also builds inside its
+# ESP-IDF component; here it is compiled without ESP_PLATFORM and with no stub
+# headers on the include path, so an ESP-IDF include slipping into one of these
+# files breaks the host build instead of being papered over.
+#
+# Host tests, the simulator, fuzzers and CI use it with a plain host compiler:
+#
+#   include(${CMAKE_CURRENT_LIST_DIR}/../cmake/keyer_portable.cmake)
+#   target_link_libraries(my_tool PRIVATE keyer_portable)
+#
+# Adding a host-safe source: list it here (there is no glob). Files with ESP-IDF
+# glue stay out (commands.c, console.c, hal_*, *_socket.c, text_keyer.c, ...).
+
+set(KEYER_COMPONENTS ${CMAKE_CURRENT_LIST_DIR}/../components)
+
+set(KEYER_PORTABLE_SOURCES
+    # keyer_core
+    ${KEYER_COMPONENTS}/keyer_core/src/stream.c
+    ${KEYER_COMPONENTS}/keyer_core/src/sample.c
+    ${KEYER_COMPONENTS}/keyer_core/src/fault.c
+    ${KEYER_COMPONENTS}/keyer_core/src/consumer.c
+    ${KEYER_COMPONENTS}/keyer_core/src/consumer_lag.c
+    ${KEYER_COMPONENTS}/keyer_core/src/rt_stats.c
+    ${KEYER_COMPONENTS}/keyer_core/src/cpu_load.c
+    ${KEYER_COMPONENTS}/keyer_core/src/keyer_clock.c
+    ${KEYER_COMPONENTS}/keyer_core/src/stress.c
+    ${KEYER_COMPONENTS}/keyer_core/src/config_audit.c
+    ${KEYER_COMPONENTS}/keyer_core/src/touch_paddle.c
+    ${KEYER_COMPONENTS}/keyer_core/src/straight_key.c
+    ${KEYER_COMPONENTS}/keyer_core/src/idle_sleep.c
+    ${KEYER_COMPONENTS}/keyer_core/src/supply_monitor.c
+    ${KEYER_COMPONENTS}/keyer_core/src/fault_indicator.c
+
+    # keyer_iambic
+    ${KEYER_COMPONENTS}/keyer_iambic/src/iambic.c
+    ${KEYER_COMPONENTS}/keyer_iambic/src/iambic_preset.c
+    ${KEYER_COMPONENTS}/keyer_iambic/src/iambic_stress.c
+
+    # keyer_audio (DSP and PTT only; audio_source.c drives the codec)
+    ${KEYER_COMPONENTS}/keyer_audio/src/sidetone.c
+    ${KEYER_COMPONENTS}/keyer_audio/src/sine_lut.c
+    ${KEYER_COMPONENTS}/keyer_audio/src/audio_buffer.c
+    ${KEYER_COMPONENTS}/keyer_audio/src/ptt.c
+    ${KEYER_COMPONENTS}/keyer_audio/src/sequencer.c
+
+    # keyer_logging (buffers and records; uart_logger.c is ESP-only)
+    ${KEYER_COMPONENTS}/keyer_logging/src/log_stream.c
+    ${KEYER_COMPONENTS}/keyer_logging/src/crash_log.c
+    ${KEYER_COMPONENTS}/keyer_logging/src/log_drain.c
+
+    # keyer_console (parser and output; commands.c, console.c, history.c and
+    # completion.c need the HAL and the USB console)
+    ${KEYER_COMPONENTS}/keyer_console/src/parser.c
+    ${KEYER_COMPONENTS}/keyer_console/src/console_output.c
+    ${KEYER_COMPONENTS}/keyer_console/src/console_lock.c
+    ${KEYER_COMPONENTS}/keyer_console/src/config_profile.c
+    ${KEYER_COMPONENTS}/keyer_console/src/preset_nvs.c
+
+    # keyer_morse, keyer_decoder
+    ${KEYER_COMPONENTS}/keyer_morse/src/morse_table.c
+    ${KEYER_COMPONENTS}/keyer_decoder/src/timing_classifier.c
+    ${KEYER_COMPONENTS}/keyer_decoder/src/edge_filter.c
+    ${KEYER_COMPONENTS}/keyer_decoder/src/decoder.c
+    ${KEYER_COMPONENTS}/keyer_decoder/src/callsign.c
+
+    # keyer_text (pure logic; text_keyer.c and text_memory.c need the config and NVS)
+    ${KEYER_COMPONENTS}/keyer_text/src/memory_buttons.c
+    ${KEYER_COMPONENTS}/keyer_text/src/speed_encoder.c
+    ${KEYER_COMPONENTS}/keyer_text/src/speed_pot.c
+    ${KEYER_COMPONENTS}/keyer_text/src/text_directive.c
+    ${KEYER_COMPONENTS}/keyer_text/src/contest_serial.c
+    ${KEYER_COMPONENTS}/keyer_text/src/type_queue.c
+    ${KEYER_COMPONENTS}/keyer_text/src/abbrev.c
+    ${KEYER_COMPONENTS}/keyer_text/src/trainer.c
+    ${KEYER_COMPONENTS}/keyer_text/src/pause_ptt.c
+
+    # keyer_cwnet (protocol; cwnet_socket.c is the lwIP glue)
+    ${KEYER_COMPONENTS}/keyer_cwnet/src/cwnet_timestamp.c
+    ${KEYER_COMPONENTS}/keyer_cwnet/src/cwnet_frame.c
+    ${KEYER_COMPONENTS}/keyer_cwnet/src/cwnet_ping.c
+    ${KEYER_COMPONENTS}/keyer_cwnet/src/cwnet_client.c
+
+    # keyer_display, keyer_storage (layout and file naming only)
+    ${KEYER_COMPONENTS}/keyer_display/src/display_layout.c
+    ${KEYER_COMPONENTS}/keyer_storage/src/record_format.c
+)
+
+set(KEYER_PORTABLE_INCLUDE_DIRS
+    ${KEYER_COMPONENTS}/keyer_core/include
+    ${KEYER_COMPONENTS}/keyer_iambic/include
+    ${KEYER_COMPONENTS}/keyer_audio/include
+    ${KEYER_COMPONENTS}/keyer_logging/include
+    ${KEYER_COMPONENTS}/keyer_console/include
+    ${KEYER_COMPONENTS}/keyer_config           # Generated headers in root
+    ${KEYER_COMPONENTS}/keyer_morse/include
+    ${KEYER_COMPONENTS}/keyer_decoder/include
+    ${KEYER_COMPONENTS}/keyer_text/include
+    ${KEYER_COMPONENTS}/keyer_cwnet/include
+    ${KEYER_COMPONENTS}/keyer_display/include
+    ${KEYER_COMPONENTS}/keyer_storage/include
+)
+
+add_library(keyer_portable STATIC ${KEYER_PORTABLE_SOURCES})
+target_include_directories(keyer_portable PUBLIC ${KEYER_PORTABLE_INCLUDE_DIRS})
+set_target_properties(keyer_portable PROPERTIES C_STANDARD 11 C_STANDARD_REQUIRED ON)
+target_compile_options(keyer_portable PRIVATE
+    -Wall
+    -Wextra
+    -Werror
+    -Wconversion
+    -Wsign-conversion
+    -Wdouble-promotion
+    -Wformat=2
+    -Wnull-dereference
+    -Wshadow
+    -Wstrict-prototypes
+)
@@ -3,6 +3,14 @@
  * @brief PTT (Push-To-Talk) controller with tail timeout
  *
  * Activates PTT on first key-down, deactivates after silence timeout.
+ *
+ * A PTT input (footswitch, mic PTT) holds PTT on by itself: see
+ * ptt_set_manual(). Precedence, highest first:
+ * 1. Fault: ptt_force_off() drops PTT and the held input with it
+ * 2. TX inhibit (trainer, practice oscillator): the caller does not
+ *    report key-down or the held input
+ * 3. PTT input held: on, regardless of keying
+ * 4. Keying: on from key-down until the tail expires
  */
 
 #ifndef KEYER_PTT_H
@@ -37,6 +45,7 @@ typedef struct {
     uint64_t tail_us;        /**< Tail timeout in microseconds */
     uint64_t last_audio_us;  /**< Timestamp of last audio activity */
     bool audio_active;       /**< Audio currently active */
+    bool manual;             /**< PTT input held (ptt_set_manual()) */
     ptt_pa_callback_t pa_callback;  /**< Optional PA control callback */
 } ptt_controller_t;
 
@@ -68,6 +77,18 @@ void ptt_audio_sample(ptt_controller_t *ptt, uint64_t timestamp_us);
  */
 void ptt_tick(ptt_controller_t *ptt, uint64_t timestamp_us);
 
+/**
+ * @brief Report the PTT input (footswitch) level
+ *
+ * Held turns PTT on at once and keeps it on. On release the tail rule
+ * applies to the last keying only: PTT drops on the next tick unless
+ * CW was sent within the tail.
+ *
+ * @param ptt Controller
+ * @param held true while the input is held
+ */
+void ptt_set_manual(ptt_controller_t *ptt, bool held);
+
 /**
  * @brief Get current PTT state
  *
@@ -91,7 +112,8 @@ static inline bool ptt_is_on(const ptt_controller_t *ptt) {
 /**
  * @brief Force PTT off immediately
  *
- * Used for fault recovery.
+ * Used for fault recovery. Also releases a held PTT input until it is
+ * reported again.
  *
  * @param ptt Controller
  */
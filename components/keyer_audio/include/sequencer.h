@@ -0,0 +1,114 @@
+/**
+ * @file sequencer.h
+ * @brief TX sequencer: antenna relay, amplifier, radio PTT, key
+ *
+ * On TX start the outputs come on in order with a delay between steps:
+ *
+ *   antenna relay -> amp PTT -> radio PTT -> key enable
+ *
+ * and on TX end they go off in reverse order with the same delays, so no
+ * relay ever switches with RF present. The key output is delayed by the
+ * sum of the delays (the lead time) so the first element comes out whole
+ * once the relays have settled; the sidetone is not delayed.
+ *
+ * With all delays 0 every step follows PTT in the same tick and the key
+ * passes straight through. Driven from the PTT state in the RT task.
+ * Pure logic, host-testable.
+ */
+
+#ifndef KEYER_SEQUENCER_H
+#define KEYER_SEQUENCER_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Sequencer steps, in TX start order
+ */
+typedef enum {
+    SEQUENCER_ANTENNA = 0,      /**< Antenna / T-R relay */
+    SEQUENCER_AMP,              /**< Amplifier PTT */
+    SEQUENCER_RADIO,            /**< Radio PTT */
+    SEQUENCER_KEY,              /**< Key output enabled */
+    SEQUENCER_STEPS,
+} sequencer_step_t;
+
+/** Key edges held in the delay line (ample for 100 WPM over the longest lead) */
+#define SEQUENCER_EDGES 16
+
+/**
+ * @brief Sequencer state
+ */
+typedef struct {
+    uint32_t delay_us[SEQUENCER_STEPS - 1]; /**< Gap after step i before step i+1 */
+    uint32_t lead_us;           /**< Key delay (sum of the gaps) */
+    uint8_t level;              /**< Steps on, 0 (idle) to SEQUENCER_STEPS */
+    int64_t step_at_us;         /**< Time of the last step change */
+
+    int64_t edge_at_us[SEQUENCER_EDGES];    /**< Delayed key edges, due time */
+    bool edge_down[SEQUENCER_EDGES];        /**< Key state after each edge */
+    uint8_t edge_head;          /**< Oldest edge */
+    uint8_t edge_count;         /**< Edges pending */
+    bool key_in;                /**< Key as last fed */
+    bool key_out;               /**< Key after the delay line */
+} sequencer_t;
+
+/**
+ * @brief Initialize, all outputs off
+ *
+ * @param seq Sequencer
+ * @param delays_ms Gaps antenna->amp, amp->radio, radio->key
+ */
+void sequencer_init(sequencer_t *seq, const uint32_t delays_ms[SEQUENCER_STEPS - 1]);
+
+/**
+ * @brief Change the delays
+ *
+ * Only while idle (see sequencer_is_active()), so no sequence in
+ * progress is cut short.
+ *
+ * @return false if active (delays unchanged)
+ */
+bool sequencer_set_delays(sequencer_t *seq, const uint32_t delays_ms[SEQUENCER_STEPS - 1]);
+
+/**
+ * @brief Advance the sequencer (call every RT tick)
+ *
+ * @param seq Sequencer
+ * @param ptt PTT state (from the PTT controller)
+ * @param key Key state from the keyer
+ * @param now_us Current time
+ * @return Key output: key delayed by the lead time, only once enabled
+ */
+bool sequencer_tick(sequencer_t *seq, bool ptt, bool key, int64_t now_us);
+
+/**
+ * @brief Drop the key now, including key downs still in the delay line
+ *
+ * For faults: the relays still release in order on the next ticks.
+ */
+void sequencer_key_off(sequencer_t *seq);
+
+/**
+ * @brief Check if a step output is on
+ */
+static inline bool sequencer_is_on(const sequencer_t *seq, sequencer_step_t step) {
+    return seq->level > (uint8_t)step;
+}
+
+/**
+ * @brief Check if any output is on or a key down is pending
+ */
+static inline bool sequencer_is_active(const sequencer_t *seq) {
+    return seq->level > 0 || seq->edge_count > 0 || seq->key_in || seq->key_out;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_SEQUENCER_H */
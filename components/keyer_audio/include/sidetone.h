@@ -26,6 +26,18 @@ extern "C" {
 /** Pre-computed 256-entry sine LUT (signed 16-bit, full scale) */
 extern const int16_t SINE_LUT[SINE_LUT_SIZE];
 
+/** Expected sidetone_lut_checksum() of the shipped table */
+#define SINE_LUT_CHECKSUM 0x9FDB7F5Eu
+
+/**
+ * @brief Checksum of SINE_LUT (FNV-1a over little-endian samples)
+ *
+ * Used by `selftest` to detect a corrupted flash image.
+ *
+ * @return 32-bit checksum, compare against SINE_LUT_CHECKSUM
+ */
+uint32_t sidetone_lut_checksum(void);
+
 /* ============================================================================
  * Fade Envelope
  * ============================================================================ */
@@ -14,6 +14,7 @@ void ptt_init(ptt_controller_t *ptt, uint32_t tail_ms) {
     ptt->tail_us = (uint64_t)tail_ms * 1000;
     ptt->last_audio_us = 0;
     ptt->audio_active = false;
+    ptt->manual = false;
     ptt->pa_callback = NULL;
 }
 
@@ -32,12 +33,25 @@ void ptt_audio_sample(ptt_controller_t *ptt, uint64_t timestamp_us) {
     }
 }
 
+void ptt_set_manual(ptt_controller_t *ptt, bool held) {
+    assert(ptt != NULL);
+
+    ptt->manual = held;
+    if (held && ptt->state == PTT_OFF) {
+        ptt->state = PTT_ON;
+        if (ptt->pa_callback != NULL) {
+            ptt->pa_callback(true);
+        }
+    }
+}
+
 void ptt_tick(ptt_controller_t *ptt, uint64_t timestamp_us) {
     assert(ptt != NULL);
 
     if (ptt->state == PTT_ON) {
         /* Check if tail timeout expired */
-        if (!ptt->audio_active && timestamp_us > ptt->last_audio_us + ptt->tail_us) {
+        if (!ptt->audio_active && !ptt->manual &&
+            timestamp_us > ptt->last_audio_us + ptt->tail_us) {
             ptt->state = PTT_OFF;
             if (ptt->pa_callback != NULL) {
                 ptt->pa_callback(false);
@@ -57,6 +71,7 @@ void ptt_force_off(ptt_controller_t *ptt) {
     }
     ptt->state = PTT_OFF;
     ptt->audio_active = false;
+    ptt->manual = false;
 }
 
 void ptt_set_tail(ptt_controller_t *ptt, uint32_t tail_ms) {
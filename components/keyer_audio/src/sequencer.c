@@ -0,0 +1,113 @@
+/**
+ * @file sequencer.c
+ * @brief TX sequencer implementation
+ */
+
+#include "sequencer.h"
+#include <assert.h>
+#include <stddef.h>
+#include <string.h>
+
+/* ============================================================================
+ * Key Delay Line
+ * ============================================================================ */
+
+static void push_edge(sequencer_t *seq, bool down, int64_t due_us) {
+    if (seq->edge_count == SEQUENCER_EDGES) {
+        /* Full: apply the oldest edge early rather than lose the newest */
+        seq->key_out = seq->edge_down[seq->edge_head];
+        seq->edge_head = (uint8_t)((seq->edge_head + 1U) % SEQUENCER_EDGES);
+        seq->edge_count--;
+    }
+    uint8_t slot = (uint8_t)((seq->edge_head + seq->edge_count) % SEQUENCER_EDGES);
+    seq->edge_at_us[slot] = due_us;
+    seq->edge_down[slot] = down;
+    seq->edge_count++;
+}
+
+static void pop_due_edges(sequencer_t *seq, int64_t now_us) {
+    while (seq->edge_count > 0 && seq->edge_at_us[seq->edge_head] <= now_us) {
+        seq->key_out = seq->edge_down[seq->edge_head];
+        seq->edge_head = (uint8_t)((seq->edge_head + 1U) % SEQUENCER_EDGES);
+        seq->edge_count--;
+    }
+}
+
+/* ============================================================================
+ * Steps
+ * ============================================================================ */
+
+/**
+ * @brief Gap before the next step change from the current level
+ *
+ * Turning on step L waits delay[L-1] after step L-1 came on; turning
+ * off step L-1 waits delay[L-1] after step L went off. The first step
+ * on and the key going off are immediate.
+ */
+static uint32_t step_gap_us(const sequencer_t *seq, bool rising) {
+    if (rising) {
+        return (seq->level == 0) ? 0 : seq->delay_us[seq->level - 1];
+    }
+    return (seq->level == SEQUENCER_STEPS) ? 0 : seq->delay_us[seq->level - 1];
+}
+
+static void advance_steps(sequencer_t *seq, bool demand, int64_t now_us) {
+    uint8_t target = demand ? SEQUENCER_STEPS : 0;
+    while (seq->level != target) {
+        bool rising = seq->level < target;
+        int64_t gap_us = (int64_t)step_gap_us(seq, rising);
+        if (now_us - seq->step_at_us < gap_us) {
+            return;
+        }
+        seq->level = rising ? (uint8_t)(seq->level + 1) : (uint8_t)(seq->level - 1);
+        /* On schedule, not on the tick: rounding must not add up across
+         * steps and fall behind the delayed key */
+        seq->step_at_us = (gap_us == 0) ? now_us : seq->step_at_us + gap_us;
+    }
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void sequencer_init(sequencer_t *seq, const uint32_t delays_ms[SEQUENCER_STEPS - 1]) {
+    assert(seq != NULL);
+    memset(seq, 0, sizeof(*seq));
+    sequencer_set_delays(seq, delays_ms);
+}
+
+bool sequencer_set_delays(sequencer_t *seq, const uint32_t delays_ms[SEQUENCER_STEPS - 1]) {
+    assert(seq != NULL && delays_ms != NULL);
+    if (sequencer_is_active(seq)) {
+        return false;
+    }
+    seq->lead_us = 0;
+    for (size_t i = 0; i < SEQUENCER_STEPS - 1; i++) {
+        seq->delay_us[i] = delays_ms[i] * 1000U;
+        seq->lead_us += seq->delay_us[i];
+    }
+    return true;
+}
+
+bool sequencer_tick(sequencer_t *seq, bool ptt, bool key, int64_t now_us) {
+    assert(seq != NULL);
+
+    if (key != seq->key_in) {
+        seq->key_in = key;
+        push_edge(seq, key, now_us + seq->lead_us);
+    }
+    pop_due_edges(seq, now_us);
+
+    /* Hold the relays until the delayed key is up and nothing is pending */
+    bool demand = ptt || seq->key_in || seq->key_out || seq->edge_count > 0;
+    advance_steps(seq, demand, now_us);
+
+    return seq->key_out && sequencer_is_on(seq, SEQUENCER_KEY);
+}
+
+void sequencer_key_off(sequencer_t *seq) {
+    assert(seq != NULL);
+    seq->edge_count = 0;
+    seq->key_in = false;
+    seq->key_out = false;
+}
@@ -7,6 +7,7 @@
  */
 
 #include "sidetone.h"
+#include <stddef.h>
 
 /* 256-entry sine LUT, signed 16-bit, full scale */
 const int16_t SINE_LUT[SINE_LUT_SIZE] = {
@@ -43,3 +44,15 @@ const int16_t SINE_LUT[SINE_LUT_SIZE] = {
     -12539, -11793, -11039, -10278,  -9512,  -8739,  -7962,  -7179,
      -6393,  -5602,  -4808,  -4011,  -3212,  -2410,  -1608,   -804,
 };
+
+uint32_t sidetone_lut_checksum(void) {
+    uint32_t h = 2166136261u;
+    for (size_t i = 0; i < SINE_LUT_SIZE; i++) {
+        uint16_t v = (uint16_t)SINE_LUT[i];
+        h ^= (uint32_t)(v & 0xFFu);
+        h *= 16777619u;
+        h ^= (uint32_t)(v >> 8);
+        h *= 16777619u;
+    }
+    return h;
+}
@@ -0,0 +1,92 @@
+/**
+ * @file config_profile.h
+ * @brief Named full-configuration profiles
+ *
+ * A profile is a snapshot of every parameter (keyer, audio, network...)
+ * stored in its own NVS namespace "prof_<name>". `profile save home`
+ * captures the running config, `profile load contest` swaps it in and
+ * makes it the boot config. Iambic presets only cover keying timing;
+ * profiles cover the whole station setup.
+ *
+ * Console task only (Core 1), no locking needed.
+ */
+
+#ifndef KEYER_CONFIG_PROFILE_H
+#define KEYER_CONFIG_PROFILE_H
+
+#include "console.h"
+#include <stddef.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Maximum number of stored profiles */
+#define CONFIG_PROFILE_MAX 4
+
+/** Maximum profile name length ("prof_" + name fits NVS's 15 chars) */
+#define CONFIG_PROFILE_NAME_MAX 10
+
+/**
+ * @brief Load the profile list and active name from NVS
+ */
+void config_profile_init(void);
+
+/**
+ * @brief Check a profile name: 1-10 chars of [a-z0-9_]
+ */
+bool config_profile_name_valid(const char *name);
+
+/**
+ * @brief Number of stored profiles
+ */
+size_t config_profile_count(void);
+
+/**
+ * @brief Name of a stored profile
+ * @param index 0..config_profile_count()-1
+ * @return Name, or NULL if out of range
+ */
+const char *config_profile_name(size_t index);
+
+/**
+ * @brief Name of the last saved or loaded profile ("" if none)
+ */
+const char *config_profile_active(void);
+
+/**
+ * @brief Save the running config as a profile (create or overwrite)
+ *
+ * @param name Profile name
+ * @return CONSOLE_OK, CONSOLE_ERR_INVALID_VALUE (bad name),
+ *         CONSOLE_ERR_OUT_OF_RANGE (all slots used) or CONSOLE_ERR_NVS_ERROR
+ */
+console_error_t config_profile_save(const char *name);
+
+/**
+ * @brief Apply a profile to the running config and make it the boot config
+ *
+ * Nothing is applied if the stored memory window start is not below its
+ * end (checked on set, but not on load).
+ *
+ * @param name Profile name
+ * @return CONSOLE_OK, CONSOLE_ERR_INVALID_VALUE (unknown profile or bad
+ *         memory window) or CONSOLE_ERR_NVS_ERROR
+ */
+console_error_t config_profile_load(const char *name);
+
+/**
+ * @brief Delete a profile
+ *
+ * @param name Profile name
+ * @return CONSOLE_OK, CONSOLE_ERR_INVALID_VALUE (unknown profile)
+ *         or CONSOLE_ERR_NVS_ERROR
+ */
+console_error_t config_profile_delete(const char *name);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CONFIG_PROFILE_H */
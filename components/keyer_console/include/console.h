@@ -38,6 +38,7 @@ typedef enum {
     CONSOLE_ERR_OUT_OF_RANGE,   /**< E04: Out of range */
     CONSOLE_ERR_REQUIRES_CONFIRM, /**< E05: Requires 'confirm' */
     CONSOLE_ERR_NVS_ERROR,      /**< E06: NVS error */
+    CONSOLE_ERR_LOCKED,         /**< E07: Console locked */
 } console_error_t;
 
 /**
@@ -60,6 +61,7 @@ const char *console_error_message(console_error_t err);
 typedef struct {
     const char *command;                /**< Command name (first token) */
     const char *args[CONSOLE_MAX_ARGS]; /**< Arguments (up to 3) */
+    const char *rest[CONSOLE_MAX_ARGS]; /**< Unsplit line from args[i] to end (free text) */
     int argc;                           /**< Number of arguments */
 } console_parsed_cmd_t;
 
@@ -144,9 +146,67 @@ bool console_push_char(char c);
 
 /**
  * @brief Print the console prompt
+ *
+ * While a confirmation is pending, prints "Are you sure? [y/N] " instead.
  */
 void console_print_prompt(void);
 
+/**
+ * @brief Ask for interactive confirmation before running a command
+ *
+ * The next input line is treated as the answer: "y" or "yes" executes
+ * @p line, anything else (or Ctrl+C) cancels. Handlers still accept an
+ * explicit "confirm" argument so scripts can skip the prompt.
+ *
+ * @param line Command line to execute on confirmation (e.g. "reboot confirm")
+ * @return CONSOLE_OK, or CONSOLE_ERR_REQUIRES_CONFIRM if line is too long
+ */
+console_error_t console_request_confirm(const char *line);
+
+/**
+ * @brief Check if a confirmation answer is pending
+ * @return true if waiting for y/N
+ */
+bool console_confirm_pending(void);
+
+/* ============================================================================
+ * Live decode
+ * ============================================================================ */
+
+/**
+ * @brief Echo a decoded character to the console if live decode is on
+ *
+ * Called from the background task for every character popped from the
+ * decoder. Enabled with `decode on`. Posted to the console output task,
+ * so a host that stops reading never holds up the caller.
+ *
+ * @param c Decoded character (' ' = word gap)
+ */
+void console_decode_echo(char c);
+
+/* ============================================================================
+ * Keyboard keying
+ * ============================================================================ */
+
+/**
+ * @brief Enter keyboard keying mode (`kb` command)
+ *
+ * Typed characters go to text_keyer_type() instead of the line editor.
+ * Backspace cancels characters not yet keyed, Enter sends a word space,
+ * Ctrl+D leaves once the queue is sent, Ctrl+C leaves and aborts.
+ * The line shows sent text normally and pending text underlined.
+ */
+void console_keyboard_begin(void);
+
+/**
+ * @brief Refresh the sent/pending display (console task loop)
+ *
+ * Leaves keyboard mode if the keyer stopped on its own (paddle abort).
+ *
+ * @return true if keyboard mode ended and the prompt should be printed
+ */
+bool console_keyboard_poll(void);
+
 /* ============================================================================
  * History
  * ============================================================================ */
@@ -0,0 +1,107 @@
+/**
+ * @file console_lock.h
+ * @brief Console lock with passphrase
+ *
+ * Optional protection for keyers at shared remote sites: once a
+ * passphrase is set with `lock set <pass>`, the console starts locked
+ * at boot, can be locked by hand with `lock`, and locks itself after
+ * `system.lock_timeout` idle minutes. While locked only `unlock`,
+ * `help` and `version` are accepted.
+ *
+ * Only a salted FNV-1a hash of the passphrase is stored in NVS. This
+ * keeps casual serial users out; it is not a cryptographic barrier
+ * against someone with flash access.
+ *
+ * Console task only (Core 1), no locking needed.
+ */
+
+#ifndef KEYER_CONSOLE_LOCK_H
+#define KEYER_CONSOLE_LOCK_H
+
+#include "console.h"
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Minimum passphrase length */
+#define CONSOLE_LOCK_PASS_MIN 4
+
+/** Maximum passphrase length */
+#define CONSOLE_LOCK_PASS_MAX 32
+
+/**
+ * @brief Load passphrase hash from NVS
+ *
+ * Starts locked if a passphrase is stored.
+ */
+void console_lock_init(void);
+
+/**
+ * @brief Hash a passphrase
+ * @param pass Passphrase
+ * @return Salted 32-bit FNV-1a hash
+ */
+uint32_t console_lock_hash(const char *pass);
+
+/**
+ * @brief Set or clear the passphrase (persisted to NVS)
+ *
+ * @param pass New passphrase, or NULL/empty to remove the lock
+ * @return CONSOLE_OK, CONSOLE_ERR_OUT_OF_RANGE (bad length),
+ *         or CONSOLE_ERR_NVS_ERROR
+ */
+console_error_t console_lock_set_passphrase(const char *pass);
+
+/**
+ * @brief Check if a passphrase is configured
+ */
+bool console_lock_has_passphrase(void);
+
+/**
+ * @brief Check if the console is locked
+ */
+bool console_lock_is_locked(void);
+
+/**
+ * @brief Lock the console now
+ * @return false if no passphrase is set
+ */
+bool console_lock_engage(void);
+
+/**
+ * @brief Try to unlock the console
+ * @param pass Passphrase
+ * @return true if unlocked
+ */
+bool console_lock_try_unlock(const char *pass);
+
+/**
+ * @brief Check if a command may run while locked
+ * @param command Command name
+ * @return true if allowed (always true when unlocked)
+ */
+bool console_lock_allows(const char *command);
+
+/**
+ * @brief Record user activity (call on every input character)
+ * @param now_ms Current time in milliseconds
+ */
+void console_lock_touch(uint32_t now_ms);
+
+/**
+ * @brief Lock if the console has been idle too long
+ *
+ * @param now_ms Current time in milliseconds
+ * @param timeout_ms Idle timeout (0 = never)
+ * @return true if the console was locked by this call
+ */
+bool console_lock_check_idle(uint32_t now_ms, uint32_t timeout_ms);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CONSOLE_LOCK_H */
@@ -0,0 +1,121 @@
+/**
+ * @file console_output.h
+ * @brief Fan-out writer for console responses and prompt
+ *
+ * Console output (command responses, prompt, completion lists) is written
+ * once and copied to every attached transport: USB CDC, UART, and any
+ * network session. Input echo stays with the transport that received the
+ * character - only output produced by the console is fanned out.
+ *
+ * Sinks are attached at init time from main.c (or by a session when it
+ * connects) and can be detached again. A slot is claimed with a CAS, so
+ * concurrent attaches never share one, and detach waits for writes that
+ * are already inside the sink: once it returns, the sink is not called
+ * again and its transport can be closed.
+ *
+ * Not RT-safe: formatting uses a stack buffer and sinks may block.
+ * Call only from Core 1 tasks. A task that must not wait on a transport
+ * (bg_task) posts its output instead: console_post() copies it into a
+ * ring and the console output task writes it to the sinks.
+ */
+
+#ifndef KEYER_CONSOLE_OUTPUT_H
+#define KEYER_CONSOLE_OUTPUT_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Maximum number of simultaneous output sinks */
+#define CONSOLE_MAX_SINKS 4
+
+/** Maximum formatted length of a single console_printf() call */
+#define CONSOLE_PRINTF_MAX 256
+
+/** Bytes held between console_post() and the console output task (power of 2) */
+#define CONSOLE_POST_RING 512
+
+/**
+ * @brief Sink write function
+ * @param data Bytes to write (not NUL-terminated)
+ * @param len Number of bytes
+ */
+typedef void (*console_sink_write_fn)(const char *data, size_t len);
+
+/**
+ * @brief Attach an output sink
+ *
+ * @param name Sink name (for `stats`/diagnostics, must be static)
+ * @param write Write function
+ * @param crlf true to expand bare '\n' into "\r\n" for this sink
+ * @return Slot index (0..CONSOLE_MAX_SINKS-1), or -1 if table full/invalid
+ */
+int console_output_add_sink(const char *name, console_sink_write_fn write, bool crlf);
+
+/**
+ * @brief Detach an output sink
+ *
+ * Blocks until no console_write() is inside the sink. Must not be called
+ * from a sink write function.
+ *
+ * @param slot Slot returned by console_output_add_sink()
+ */
+void console_output_remove_sink(int slot);
+
+/**
+ * @brief Get number of attached sinks
+ * @return Active sink count
+ */
+size_t console_output_sink_count(void);
+
+/**
+ * @brief Write raw bytes to all attached sinks
+ *
+ * With no sinks attached, output goes to stdout.
+ *
+ * @param data Bytes to write
+ * @param len Number of bytes
+ */
+void console_write(const char *data, size_t len);
+
+/**
+ * @brief Formatted write to all attached sinks
+ *
+ * Output longer than CONSOLE_PRINTF_MAX is truncated.
+ *
+ * @param fmt printf-style format
+ */
+void console_printf(const char *fmt, ...) __attribute__((format(printf, 1, 2)));
+
+/**
+ * @brief Queue output for the console output task, without waiting
+ *
+ * Single producer: call from one task only (bg_task). Output that does
+ * not fit in the ring is dropped whole.
+ *
+ * @param data Bytes to write
+ * @param len Number of bytes
+ * @return false if dropped
+ */
+bool console_post(const char *data, size_t len);
+
+/**
+ * @brief Write posted output to all attached sinks (console output task)
+ */
+void console_output_pump(void);
+
+/**
+ * @brief Console output task: pumps posted output every 20ms (Core 1)
+ * @param arg Unused
+ */
+void console_output_task(void *arg);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CONSOLE_OUTPUT_H */
@@ -0,0 +1,55 @@
+/**
+ * @file preset_nvs.h
+ * @brief NVS persistence for iambic presets
+ *
+ * Stores the 10 iambic preset slots (name, timing, weight and the
+ * sidetone / PTT tail overrides) in NVS namespace "presets", one blob per
+ * slot, tagged with IAMBIC_PRESET_SCHEMA_VERSION. Schema 2 slots are
+ * migrated (new fields get their defaults, no overrides) and rewritten;
+ * other versions are ignored and the built-in presets are kept.
+ *
+ * keyer_iambic stays free of I/O; this module owns the storage side.
+ * Console task only (Core 1).
+ */
+
+#ifndef KEYER_PRESET_NVS_H
+#define KEYER_PRESET_NVS_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Initialize built-in presets, then overlay the stored ones
+ */
+void preset_nvs_init(void);
+
+/**
+ * @brief Save all preset slots and the active index
+ * @return Number of slots saved, or negative on error
+ */
+int preset_nvs_save(void);
+
+/**
+ * @brief Apply one stored slot blob, migrating older layouts
+ *
+ * Used by the loader; exposed so the upgrade path can be tested
+ * without NVS.
+ *
+ * @param index Preset index (0-9)
+ * @param schema Schema version the blob was written with
+ * @param data Blob as read from NVS
+ * @param len Blob length
+ * @return true if applied, false for an unknown schema or wrong length
+ */
+bool preset_nvs_apply_slot(uint32_t index, uint8_t schema, const void *data, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_PRESET_NVS_H */
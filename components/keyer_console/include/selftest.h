@@ -0,0 +1,99 @@
+/**
+ * @file selftest.h
+ * @brief Built-in diagnostic suite for production testing
+ *
+ * Runs a fixed list of checks and reports PASS/FAIL/SKIP per item:
+ * stream push/read, sine LUT checksum, codec probe, NVS read/write. Used
+ * by the `selftest` console command. The key/PTT output loopback through
+ * a test jumper is not part of it: it drives the real outputs, so it
+ * runs only at boot, before the RT task starts.
+ *
+ * Not RT-safe: NVS and I2C access may block. Call only from Core 1.
+ */
+
+#ifndef KEYER_SELFTEST_H
+#define KEYER_SELFTEST_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Number of checks in the suite */
+#define SELFTEST_ITEM_COUNT 4
+
+/** Maximum length of a result detail string */
+#define SELFTEST_DETAIL_MAX 40
+
+/**
+ * @brief Check outcome
+ */
+typedef enum {
+    SELFTEST_PASS = 0,
+    SELFTEST_FAIL,
+    SELFTEST_SKIP,          /**< Not applicable on this build */
+} selftest_status_t;
+
+/**
+ * @brief Result of one check
+ */
+typedef struct {
+    const char *name;                   /**< Check name ("stream", "lut", ...) */
+    selftest_status_t status;           /**< Outcome */
+    char detail[SELFTEST_DETAIL_MAX];   /**< Short explanation (may be empty) */
+} selftest_result_t;
+
+/**
+ * @brief Get status label
+ * @return "PASS", "FAIL" or "SKIP"
+ */
+const char *selftest_status_str(selftest_status_t status);
+
+/**
+ * @brief Stream push/read sanity on a private scratch stream
+ */
+selftest_status_t selftest_stream(char *detail, size_t len);
+
+/**
+ * @brief Verify sine LUT checksum
+ */
+selftest_status_t selftest_audio_lut(char *detail, size_t len);
+
+/**
+ * @brief Probe the audio codec on I2C
+ */
+selftest_status_t selftest_codec(char *detail, size_t len);
+
+/**
+ * @brief Write, read back and erase a scratch NVS key
+ */
+selftest_status_t selftest_nvs(char *detail, size_t len);
+
+/**
+ * @brief Key and PTT outputs through their loopback jumpers
+ *
+ * Times both edges of each output with a jumper configured
+ * (hardware.gpio_loop_tx, hardware.gpio_loop_ptt), see
+ * hal_gpio_output_loopback(). SKIP if neither is. Keys the outputs for
+ * up to a millisecond: run with no rig attached. Boot only (app_main):
+ * nothing else may drive the outputs while it runs.
+ */
+selftest_status_t selftest_loopback(char *detail, size_t len);
+
+/**
+ * @brief Run all checks in order
+ *
+ * @param results Output array
+ * @param max Capacity of results (SELFTEST_ITEM_COUNT is enough)
+ * @return Number of results written
+ */
+size_t selftest_run_all(selftest_result_t *results, size_t max);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_SELFTEST_H */
@@ -5,15 +5,43 @@
  * Implements all console commands: help, version, stats, reboot, etc.
  */
 
+#define RT_LOG_TAG LOG_TAG_CONSOLE
+
 #include "console.h"
+#include "console_output.h"
+#include "console_lock.h"
+#include "selftest.h"
+#include "config_profile.h"
+#include "preset_nvs.h"
+#include "iambic_preset.h"
 #include "config.h"
 #include "config_console.h"
 #include "config_nvs.h"
 #include "rt_log.h"
+#include "crash_log.h"
+#include "log_drain.h"
+#include "rt_stats.h"
+#include "cpu_load.h"
+#include "stream.h"
+#include "iambic_stress.h"
+#include "consumer_lag.h"
+#include "fault.h"
+#include "config_audit.h"
 #include "hal_gpio.h"
+#include "hal_i2c.h"
+#include "hal_audio.h"
 #include "decoder.h"
+#include "callsign.h"
 #include "text_keyer.h"
 #include "text_memory.h"
+#include "contest_serial.h"
+#include "abbrev.h"
+#include "trainer.h"
+#include "copy_practice.h"
+#include "storage.h"
+#include "recorder.h"
+#include "record_format.h"
+#include <stdatomic.h>
 #include <stdio.h>
 #include <stdlib.h>
 #include <string.h>
@@ -21,6 +49,7 @@
 #ifdef ESP_PLATFORM
 #include "driver/gpio.h"
 #include "esp_system.h"
+#include "esp_attr.h"
 #include "esp_timer.h"
 #include "esp_heap_caps.h"
 #include "esp_log.h"
@@ -28,16 +57,16 @@
 #include "freertos/task.h"
 #include "nvs.h"
 #include "nvs_flash.h"
-#include "usb_console.h"
 #include "usb_log.h"
 #include "usb_uf2.h"
 #include "usb_cdc.h"
 #include "tusb_cdc_acm.h"
 #include "wifi.h"
 #include "vpn.h"
-/* Use USB console printf for command output (skip for IDE analyzers) */
+#include "cwnet_socket.h"
+/* Route output through the console fan-out writer (skip for IDE analyzers) */
 #if !defined(__INTELLISENSE__) && !defined(__clang_analyzer__) && !defined(__clangd__)
-#define printf usb_console_printf
+#define printf console_printf
 #endif
 #endif
 
@@ -54,6 +83,7 @@ const char *console_error_code(console_error_t err) {
         case CONSOLE_ERR_OUT_OF_RANGE:    return "E04";
         case CONSOLE_ERR_REQUIRES_CONFIRM: return "E05";
         case CONSOLE_ERR_NVS_ERROR:       return "E06";
+        case CONSOLE_ERR_LOCKED:          return "E07";
         default:                          return "E??";
     }
 }
@@ -67,6 +97,7 @@ const char *console_error_message(console_error_t err) {
         case CONSOLE_ERR_OUT_OF_RANGE:    return "out of range";
         case CONSOLE_ERR_REQUIRES_CONFIRM: return "requires 'confirm'";
         case CONSOLE_ERR_NVS_ERROR:       return "NVS error";
+        case CONSOLE_ERR_LOCKED:          return "console locked (use 'unlock <pass>')";
         default:                          return "unknown error";
     }
 }
@@ -75,6 +106,109 @@ const char *console_error_message(console_error_t err) {
  * Command handlers
  * ============================================================================ */
 
+/* RT loop statistics (defined in main.c, written by rt_task) */
+extern rt_stats_t g_rt_stats;
+
+/* Fault state (defined in main.c) */
+extern fault_state_t g_fault_state;
+
+/* Keying stream (defined in main.c) */
+extern keying_stream_t g_keying_stream;
+
+/* Per-core CPU load (defined in main.c, sampled by bg_task) */
+extern cpu_load_t g_cpu_load;
+
+/* Recent parameter changes (defined in main.c) */
+extern config_audit_t g_config_audit;
+
+/**
+ * @brief Add a console change to the history (secrets masked, no-ops skipped)
+ */
+static void audit_change(const char *path, const char *old_value) {
+    const param_descriptor_t *p = config_find_param(path);
+    char new_value[72];
+    if (p == NULL || config_get_param_str(p->full_path, new_value, sizeof(new_value)) != 0 ||
+        strcmp(old_value, new_value) == 0) {
+        return;
+    }
+    config_audit_record(&g_config_audit, CONFIG_AUDIT_CONSOLE, "", p->full_path,
+                        p->sensitive ? "***" : old_value,
+                        p->sensitive ? "***" : new_value, esp_timer_get_time());
+}
+
+/**
+ * @brief config_set_param_str() that records the change in the history
+ */
+static int audited_set(const char *path, const char *value) {
+    char old_value[72] = "";
+    config_get_param_str(path, old_value, sizeof(old_value));
+    int ret = config_set_param_str(path, value);
+    if (ret == 0) {
+        audit_change(path, old_value);
+    }
+    return ret;
+}
+
+/**
+ * @brief Print RT loop timing statistics
+ */
+static void print_rt_stats(const rt_stats_t *s) {
+    uint32_t count = atomic_load_explicit(&s->period_count, memory_order_relaxed);
+    if (count == 0) {
+        printf("rt: no samples yet\r\n");
+        return;
+    }
+
+    printf("=== RT Loop (nominal %luus) ===\r\n", (unsigned long)s->nominal_us);
+    printf("period min/avg/max: %lu / %lu / %lu us\r\n",
+           (unsigned long)atomic_load_explicit(&s->period_min_us, memory_order_relaxed),
+           (unsigned long)rt_stats_avg_period_us(s),
+           (unsigned long)atomic_load_explicit(&s->period_max_us, memory_order_relaxed));
+    printf("missed ticks:       %lu\r\n",
+           (unsigned long)atomic_load_explicit(&s->missed_ticks, memory_order_relaxed));
+    printf("worst push:         %lu us\r\n",
+           (unsigned long)atomic_load_explicit(&s->push_max_us, memory_order_relaxed));
+    printf("worst consume:      %lu us\r\n",
+           (unsigned long)atomic_load_explicit(&s->consume_max_us, memory_order_relaxed));
+    printf("worst loop body:    %lu us\r\n",
+           (unsigned long)atomic_load_explicit(&s->work_max_us, memory_order_relaxed));
+    if (hal_gpio_isr_enabled()) {
+        printf("worst press wait:   %lu us\r\n",
+               (unsigned long)atomic_load_explicit(&s->press_age_max_us, memory_order_relaxed));
+    }
+
+    printf("jitter histogram:\r\n");
+    uint32_t lower = 0;
+    for (uint32_t i = 0; i < RT_STATS_BUCKETS; i++) {
+        unsigned long n = (unsigned long)atomic_load_explicit(&s->hist[i], memory_order_relaxed);
+        if (i < RT_STATS_BUCKETS - 1) {
+            printf("  %4lu-%-4lu us  %lu\r\n", (unsigned long)lower,
+                   (unsigned long)RT_STATS_BUCKET_LIMITS_US[i] - 1, n);
+            lower = RT_STATS_BUCKET_LIMITS_US[i];
+        } else {
+            printf("  >=%-7lu us  %lu\r\n", (unsigned long)lower, n);
+        }
+    }
+}
+
+/**
+ * @brief Print the stream position and the lag of every registered consumer
+ */
+static void print_stream_stats(void) {
+    printf("stream: %lu samples written\r\n",
+           (unsigned long)stream_write_position(&g_keying_stream));
+    printf("%-10s %8s %8s %8s\r\n", "consumer", "lag max", "mean", "dropped");
+    for (size_t i = 0; i < consumer_lag_count(); i++) {
+        const consumer_lag_t *lag = consumer_lag_get(i);
+        const char *name = atomic_load_explicit(&lag->name, memory_order_acquire);
+        uint32_t mean = consumer_lag_mean_x100(lag);
+        printf("%-10s %8lu %5lu.%02lu %8lu\r\n", name != NULL ? name : "?",
+               (unsigned long)atomic_load_explicit(&lag->lag_max, memory_order_relaxed),
+               (unsigned long)(mean / 100), (unsigned long)(mean % 100),
+               (unsigned long)atomic_load_explicit(&lag->dropped, memory_order_relaxed));
+    }
+}
+
 /**
  * @brief Show detailed help for a command
  */
@@ -123,10 +257,12 @@ static console_error_t cmd_help(const console_parsed_cmd_t *cmd) {
         }
         printf("\r\nFamilies (use 'help <family>'):\r\n");
         for (size_t i = 0; i < FAMILY_COUNT; i++) {
-            printf("  %-14s (%s) %s\r\n",
-                   CONSOLE_FAMILIES[i].name,
-                   CONSOLE_FAMILIES[i].aliases,
-                   CONSOLE_FAMILIES[i].description);
+            const family_descriptor_t *f = &CONSOLE_FAMILIES[i];
+            printf("  %-14s (%s) %s", f->name, f->aliases, f->description);
+            if (!config_family_enabled(f)) {
+                printf(" [off: %s=false]", f->enabled_by);
+            }
+            printf("\r\n");
         }
         printf("\r\nType 'help <cmd>' or 'help <family>' for details\r\n");
     }
@@ -146,6 +282,9 @@ static console_error_t cmd_question(const console_parsed_cmd_t *cmd) {
 static console_error_t cmd_version(const console_parsed_cmd_t *cmd) {
     (void)cmd;
     printf("CW Keyer v0.1.0\r\n");
+    if (CONFIG_GET_STATION_NAME()[0] != '\0') {
+        printf("Station: %s (%s)\r\n", CONFIG_GET_STATION_NAME(), CONFIG_GET_CALLSIGN());
+    }
 #ifdef ESP_PLATFORM
     printf("ESP-IDF: %s\r\n", esp_get_idf_version());
     printf("Target: %s\r\n", CONFIG_IDF_TARGET);
@@ -155,8 +294,133 @@ static console_error_t cmd_version(const console_parsed_cmd_t *cmd) {
     return CONSOLE_OK;
 }
 
+#ifdef ESP_PLATFORM
 /**
- * @brief stats [tasks|heap|stream|rt] - System statistics
+ * @brief Human-readable WiFi state
+ */
+static const char *wifi_state_name(wifi_state_t state) {
+    switch (state) {
+        case WIFI_STATE_DISABLED:   return "disabled";
+        case WIFI_STATE_CONNECTING: return "connecting";
+        case WIFI_STATE_CONNECTED:  return "connected";
+        case WIFI_STATE_FAILED:     return "failed";
+        case WIFI_STATE_AP_MODE:    return "AP mode";
+        default:                    return "unknown";
+    }
+}
+
+/**
+ * @brief Codec, output routing and speaker amp state
+ */
+static void print_audio_stats(void) {
+    if (!hal_audio_is_available()) {
+        printf("codec: not available\r\n");
+        return;
+    }
+    printf("codec: ES8311\r\n");
+    bool headphones = hal_audio_get_output() == HAL_AUDIO_OUTPUT_HEADPHONE;
+    printf("output: %s%s\r\n", headphones ? "headphones" : "speaker",
+           hal_audio_hp_detect_fitted() ? " (jack detect)" : " (no jack detect)");
+    printf("speaker amp: %s\r\n", hal_audio_speaker_on() ? "on" : "off");
+}
+
+/** Stack headroom below which a task is flagged (bytes) */
+#define TASK_STACK_LOW_BYTES 512
+
+/**
+ * @brief Free, minimum ever free and largest block of one heap region
+ */
+static void print_heap_region(const char *name, uint32_t caps) {
+    size_t total = heap_caps_get_total_size(caps);
+    if (total == 0) {
+        printf("%-9s not present\r\n", name);
+        return;
+    }
+    printf("%-9s %7u free %7u min %7u largest %7u total\r\n", name,
+           (unsigned)heap_caps_get_free_size(caps),
+           (unsigned)heap_caps_get_minimum_free_size(caps),
+           (unsigned)heap_caps_get_largest_free_block(caps),
+           (unsigned)total);
+}
+
+static void print_heap_stats(void) {
+    print_heap_region("internal", MALLOC_CAP_INTERNAL | MALLOC_CAP_8BIT);
+    print_heap_region("dma", MALLOC_CAP_DMA);
+    print_heap_region("psram", MALLOC_CAP_SPIRAM);
+}
+
+/** Core a task is pinned to, 2 if it floats (sorts after both cores) */
+static int task_core(const TaskStatus_t *task) {
+    BaseType_t core = xTaskGetCoreID(task->xHandle);
+    return (core == 0 || core == 1) ? (int)core : 2;
+}
+
+static int compare_tasks(const void *a, const void *b) {
+    const TaskStatus_t *ta = a;
+    const TaskStatus_t *tb = b;
+    int diff = task_core(ta) - task_core(tb);
+    return (diff != 0) ? diff : strcmp(ta->pcTaskName, tb->pcTaskName);
+}
+
+static const char *task_state_str(eTaskState state) {
+    switch (state) {
+        case eRunning:   return "RUN";
+        case eReady:     return "RDY";
+        case eBlocked:   return "BLK";
+        case eSuspended: return "SUS";
+        case eDeleted:   return "DEL";
+        default:         return "???";
+    }
+}
+
+/**
+ * @brief Tasks by core with stack headroom, then heap headroom
+ */
+static void print_task_stats(void) {
+    /* A few spare slots for tasks created in between */
+    UBaseType_t capacity = uxTaskGetNumberOfTasks() + 4;
+    TaskStatus_t *tasks = malloc(capacity * sizeof(TaskStatus_t));
+    if (tasks == NULL) {
+        printf("Out of memory\r\n");
+        return;
+    }
+
+    uint32_t total_runtime;
+    UBaseType_t count = uxTaskGetSystemState(tasks, capacity, &total_runtime);
+    qsort(tasks, count, sizeof(TaskStatus_t), compare_tasks);
+
+    printf("=== Tasks (%u) ===\r\n", (unsigned)count);
+    printf("%-16s %4s %4s %5s %6s\r\n", "NAME", "CORE", "PRIO", "STATE", "FREE");
+
+    unsigned low = 0;
+    for (UBaseType_t i = 0; i < count; i++) {
+        int core = task_core(&tasks[i]);
+        /* ESP-IDF stacks are byte arrays: the high-water mark is in bytes */
+        unsigned headroom = (unsigned)tasks[i].usStackHighWaterMark;
+        bool is_low = headroom < TASK_STACK_LOW_BYTES;
+        if (is_low) {
+            low++;
+        }
+        printf("%-16s %4s %4u %5s %6u%s\r\n",
+               tasks[i].pcTaskName,
+               (core == 0) ? "0" : (core == 1) ? "1" : "*",
+               (unsigned)tasks[i].uxCurrentPriority,
+               task_state_str(tasks[i].eCurrentState),
+               headroom,
+               is_low ? " LOW" : "");
+    }
+    free(tasks);
+
+    if (low > 0) {
+        printf("%u task(s) under %u bytes of stack headroom\r\n", low,
+               (unsigned)TASK_STACK_LOW_BYTES);
+    }
+    print_heap_stats();
+}
+#endif
+
+/**
+ * @brief stats [tasks|heap|stream|rt|audio] - System statistics
  */
 static console_error_t cmd_stats(const console_parsed_cmd_t *cmd) {
 #ifdef ESP_PLATFORM
@@ -174,75 +438,93 @@ static console_error_t cmd_stats(const console_parsed_cmd_t *cmd) {
         printf("uptime: %lld:%02lld:%02lld\r\n", hours, mins, secs);
         printf("heap: %lu bytes free (min: %lu)\r\n",
                (unsigned long)heap_free, (unsigned long)heap_min);
+        if (heap_caps_get_total_size(MALLOC_CAP_SPIRAM) > 0) {
+            printf("psram: %u bytes free (min: %u)\r\n",
+                   (unsigned)heap_caps_get_free_size(MALLOC_CAP_SPIRAM),
+                   (unsigned)heap_caps_get_minimum_free_size(MALLOC_CAP_SPIRAM));
+        }
+        if (cpu_load_valid(&g_cpu_load)) {
+            for (uint32_t core = 0; core < CPU_LOAD_CORES; core++) {
+                printf("cpu%lu: %lu%% (%us avg %lu%%, peak %lu%%)\r\n", (unsigned long)core,
+                       (unsigned long)cpu_load_now(&g_cpu_load, core),
+                       (unsigned)CPU_LOAD_WINDOW,
+                       (unsigned long)cpu_load_avg(&g_cpu_load, core),
+                       (unsigned long)cpu_load_peak(&g_cpu_load, core));
+            }
+        }
         printf("stream: ok\r\n");
+        uint32_t raised = fault_get_raised(&g_fault_state);
+        if (raised == 0) {
+            printf("faults: none\r\n");
+        }
+        for (int code = 1; code < FAULT_CODE_COUNT; code++) {
+            if (raised & FAULT_BIT(code)) {
+                printf("fault: %s (%s)\r\n", fault_code_str((fault_code_t)code),
+                       fault_severity_str(fault_code_severity((fault_code_t)code)));
+            }
+        }
+        if (CONFIG_GET_GPIO_SUPPLY() != 0) {
+            uint16_t supply_mv = CONFIG_GET_SUPPLY_MV();
+            const char *state = "ok";
+            if (fault_get_code(&g_fault_state) == FAULT_LOW_VOLTAGE) {
+                state = "critical, TX off";
+            } else if (CONFIG_GET_SUPPLY_LOW_MV() != 0 && supply_mv < CONFIG_GET_SUPPLY_LOW_MV()) {
+                state = "low";
+            }
+            printf("supply: %u.%02u V (%s)\r\n", (unsigned)(supply_mv / 1000),
+                   (unsigned)((supply_mv % 1000) / 10), state);
+        }
+        printf("chip temp: %d C", (int)CONFIG_GET_CHIP_TEMP_C());
+        if (text_keyer_long_tx_blocked()) {
+            printf(" (over %u C: tune and repeats blocked)", (unsigned)CONFIG_GET_THERMAL_LIMIT_C());
+        }
+        printf("\r\n");
+        if (hal_i2c_error_count() > 0) {
+            printf("i2c: %lu failed transactions\r\n", (unsigned long)hal_i2c_error_count());
+        }
+        if (config_nvs_integrity_failed()) {
+            printf("config: NVS CRC mismatch at boot, defaults in use (check, then 'save')\r\n");
+        } else {
+            printf("config: ok\r\n");
+        }
+        config_nvs_stats_t nvs_stats;
+        config_nvs_get_stats(&nvs_stats);
+        printf("nvs: %lu commits (%lu auto), %lu written, %lu unchanged, %lu failed, last %lu us\r\n",
+               (unsigned long)nvs_stats.commits, (unsigned long)nvs_stats.autosaves,
+               (unsigned long)nvs_stats.written, (unsigned long)nvs_stats.unchanged,
+               (unsigned long)nvs_stats.failures, (unsigned long)nvs_stats.last_us);
 
         /* WiFi status */
         wifi_state_t wifi_state = wifi_get_state();
-        const char *state_str;
-        switch (wifi_state) {
-            case WIFI_STATE_DISABLED:   state_str = "disabled"; break;
-            case WIFI_STATE_CONNECTING: state_str = "connecting"; break;
-            case WIFI_STATE_CONNECTED:  state_str = "connected"; break;
-            case WIFI_STATE_FAILED:     state_str = "failed"; break;
-            case WIFI_STATE_AP_MODE:    state_str = "AP mode"; break;
-            default:                    state_str = "unknown"; break;
-        }
-        printf("wifi: %s\r\n", state_str);
+        printf("wifi: %s\r\n", wifi_state_name(wifi_state));
 
         if (wifi_state == WIFI_STATE_CONNECTED || wifi_state == WIFI_STATE_AP_MODE) {
-            printf("ssid: %s\r\n", g_config.wifi.ssid);
+            printf("ssid: %s\r\n", CONFIG_GET_SSID());
             char ip_buf[16];
             if (wifi_get_ip(ip_buf, sizeof(ip_buf))) {
                 printf("ip: %s\r\n", ip_buf);
             }
         }
     } else if (strcmp(cmd->args[0], "heap") == 0) {
-        uint32_t heap_free = esp_get_free_heap_size();
-        uint32_t heap_min = esp_get_minimum_free_heap_size();
-        printf("heap free:    %lu bytes\r\n", (unsigned long)heap_free);
-        printf("heap minimum: %lu bytes\r\n", (unsigned long)heap_min);
+        print_heap_stats();
     } else if (strcmp(cmd->args[0], "tasks") == 0) {
-        /* Get number of tasks */
-        UBaseType_t num_tasks = uxTaskGetNumberOfTasks();
-        TaskStatus_t *tasks = malloc(num_tasks * sizeof(TaskStatus_t));
-        if (tasks == NULL) {
-            printf("Out of memory\r\n");
-            return CONSOLE_OK;
-        }
-
-        uint32_t total_runtime;
-        UBaseType_t actual = uxTaskGetSystemState(tasks, num_tasks, &total_runtime);
-
-        printf("=== Tasks (%u) ===\r\n", (unsigned)actual);
-        printf("%-16s %4s %6s %4s %4s\r\n", "NAME", "CORE", "STACK", "PRIO", "STATE");
-
-        for (UBaseType_t i = 0; i < actual; i++) {
-            const char *state;
-            switch (tasks[i].eCurrentState) {
-                case eRunning:   state = "RUN"; break;
-                case eReady:     state = "RDY"; break;
-                case eBlocked:   state = "BLK"; break;
-                case eSuspended: state = "SUS"; break;
-                case eDeleted:   state = "DEL"; break;
-                default:         state = "???"; break;
-            }
-
-            int core = xTaskGetCoreID(tasks[i].xHandle);
-            const char *core_str = (core == 0) ? "0" : (core == 1) ? "1" : "*";
-
-            printf("%-16s %4s %6u %4u %4s\r\n",
-                   tasks[i].pcTaskName,
-                   core_str,
-                   (unsigned)tasks[i].usStackHighWaterMark,
-                   (unsigned)tasks[i].uxCurrentPriority,
-                   state);
-        }
-
-        free(tasks);
+        print_task_stats();
     } else if (strcmp(cmd->args[0], "stream") == 0) {
-        printf("stream: ok\r\n");
+        if (cmd->argc > 1 && strcmp(cmd->args[1], "reset") == 0) {
+            consumer_lag_request_reset();
+            printf("stream stats reset\r\n");
+        } else {
+            print_stream_stats();
+        }
+    } else if (strcmp(cmd->args[0], "audio") == 0) {
+        print_audio_stats();
     } else if (strcmp(cmd->args[0], "rt") == 0) {
-        printf("rt: ok\r\n");
+        if (cmd->argc > 1 && strcmp(cmd->args[1], "reset") == 0) {
+            rt_stats_request_reset(&g_rt_stats);
+            printf("rt stats reset\r\n");
+        } else {
+            print_rt_stats(&g_rt_stats);
+        }
     } else {
         return CONSOLE_ERR_INVALID_VALUE;
     }
@@ -254,11 +536,19 @@ static console_error_t cmd_stats(const console_parsed_cmd_t *cmd) {
 }
 
 /**
- * @brief reboot confirm - Restart system
+ * @brief reboot [confirm] - Restart system (prompts y/N without 'confirm')
  */
 static console_error_t cmd_reboot(const console_parsed_cmd_t *cmd) {
     if (cmd->argc == 0 || strcmp(cmd->args[0], "confirm") != 0) {
-        return CONSOLE_ERR_REQUIRES_CONFIRM;
+        printf("System will restart.\r\n");
+#ifdef ESP_PLATFORM
+        size_t unsaved = config_nvs_dirty_count();
+        if (unsaved > 0) {
+            printf("Warning: %u unsaved change(s) will be lost ('show changed', 'save')\r\n",
+                   (unsigned)unsaved);
+        }
+#endif
+        return console_request_confirm("reboot confirm");
     }
 #ifdef ESP_PLATFORM
     printf("Rebooting...\r\n");
@@ -276,23 +566,193 @@ static console_error_t cmd_reboot(const console_parsed_cmd_t *cmd) {
 static console_error_t cmd_save(const console_parsed_cmd_t *cmd) {
     (void)cmd;
 #ifdef ESP_PLATFORM
+    config_nvs_stats_t before, after;
+    config_nvs_get_stats(&before);
     int ret = config_save_to_nvs();
     if (ret < 0) {
         return CONSOLE_ERR_NVS_ERROR;
     }
-    printf("Saved %d parameters to NVS\r\n", ret);
+    config_nvs_get_stats(&after);
+    printf("Saved %d parameters to NVS (%lu changed)\r\n",
+           ret, (unsigned long)(after.written - before.written));
+#else
+    printf("NVS not available on host\r\n");
+#endif
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief profile [list|save|load|delete <name>] - Full configuration profiles
+ */
+static console_error_t cmd_profile(const console_parsed_cmd_t *cmd) {
+    const char *sub = (cmd->argc > 0) ? cmd->args[0] : "list";
+
+    if (strcmp(sub, "list") == 0) {
+        size_t count = config_profile_count();
+        if (count == 0) {
+            printf("No profiles (use 'profile save <name>')\r\n");
+            return CONSOLE_OK;
+        }
+        const char *active = config_profile_active();
+        for (size_t i = 0; i < count; i++) {
+            const char *name = config_profile_name(i);
+            printf("%c %s\r\n", (strcmp(name, active) == 0) ? '*' : ' ', name);
+        }
+        return CONSOLE_OK;
+    }
+
+    if (cmd->argc < 2) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+    const char *name = cmd->args[1];
+    console_error_t err;
+
+    if (strcmp(sub, "save") == 0) {
+        err = config_profile_save(name);
+        if (err == CONSOLE_ERR_INVALID_VALUE) {
+            printf("Name: 1-%d chars of a-z, 0-9, _\r\n", CONFIG_PROFILE_NAME_MAX);
+        } else if (err == CONSOLE_ERR_OUT_OF_RANGE) {
+            printf("All %d profiles used, delete one first\r\n", CONFIG_PROFILE_MAX);
+        } else if (err == CONSOLE_OK) {
+            printf("Saved profile '%s'\r\n", name);
+        }
+        return err;
+    }
+
+    if (strcmp(sub, "load") == 0) {
+        err = config_profile_load(name);
+        if (err == CONSOLE_OK) {
+            printf("Loaded profile '%s' (network changes apply after reboot)\r\n", name);
+        }
+        return err;
+    }
+
+    if (strcmp(sub, "delete") == 0) {
+        err = config_profile_delete(name);
+        if (err == CONSOLE_OK) {
+            printf("Deleted profile '%s'\r\n", name);
+        }
+        return err;
+    }
+
+    return CONSOLE_ERR_INVALID_VALUE;
+}
+
+/**
+ * @brief config history - Recent parameter changes, newest first
+ */
+static console_error_t cmd_config(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0 || strcmp(cmd->args[0], "history") != 0) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    size_t count = config_audit_count(&g_config_audit);
+    if (count == 0) {
+        printf("No changes since boot\r\n");
+        return CONSOLE_OK;
+    }
+    int64_t now_us = esp_timer_get_time();
+    for (size_t i = 0; i < count; i++) {
+        config_audit_entry_t e;
+        if (!config_audit_get(&g_config_audit, i, &e)) {
+            continue;  /* Overwritten while reading */
+        }
+        unsigned long age_s = (unsigned long)((now_us - e.time_us) / 1000000);
+        printf("%6lus ago  %-7s %-15s %s: %s -> %s\r\n", age_s,
+               config_audit_source_str(e.source), (e.who[0] != '\0') ? e.who : "-",
+               e.path, e.old_value, e.new_value);
+    }
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief board [name] - Show board variants or select one for the next boot
+ */
+static console_error_t cmd_board(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        const config_board_t *active = config_board_active();
+        for (size_t i = 0; i < CONFIG_BOARD_COUNT; i++) {
+            const config_board_t *b = &CONFIG_BOARDS[i];
+            printf("%c %-10s codec %-3s  psram %-5s  %s\r\n",
+                   (b == active) ? '*' : ' ', b->name, b->has_codec ? "yes" : "no",
+                   config_psram_str(b->psram), b->description);
+        }
+        return CONSOLE_OK;
+    }
+
+    const char *name = cmd->args[0];
+    if (config_board_find(name) < 0) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+#ifdef ESP_PLATFORM
+    if (config_board_save(name) != 0) {
+        return CONSOLE_ERR_NVS_ERROR;
+    }
+    printf("Board set to '%s', reboot to apply\r\n", name);
 #else
     printf("NVS not available on host\r\n");
 #endif
     return CONSOLE_OK;
 }
 
+/**
+ * @brief Check for a value that differs from NVS (always false on host)
+ */
+static bool param_unsaved(const param_descriptor_t *p) {
+#ifdef ESP_PLATFORM
+    return config_nvs_param_dirty(p->full_path);
+#else
+    (void)p;
+    return false;
+#endif
+}
+
+/** Progress of a show listing */
+typedef struct {
+    size_t unsaved;
+    size_t hidden;
+    bool hide_gated;  /**< Skip parameters of gated-off families */
+} show_ctx_t;
+
 /* Visitor callback for show command */
 static void show_param_visitor(const param_descriptor_t *p, void *ctx) {
-    (void)ctx;
+    show_ctx_t *show = (show_ctx_t *)ctx;
+    if (show->hide_gated && !config_param_visible(p)) {
+        show->hidden++;
+        return;
+    }
     char buf[32];
     config_get_param_str(p->full_path, buf, sizeof(buf));
-    printf("%s=%s\r\n", p->full_path, buf);
+    if (param_unsaved(p)) {
+        printf("%s=%s *\r\n", p->full_path, buf);
+        show->unsaved++;
+    } else {
+        printf("%s=%s\r\n", p->full_path, buf);
+    }
+}
+
+/* Visitor callback for "show changed": only non-default or unsaved params */
+static void show_changed_visitor(const param_descriptor_t *p, void *ctx) {
+    size_t *shown = (size_t *)ctx;
+    char buf[32];
+    config_get_param_str(p->full_path, buf, sizeof(buf));
+
+    bool is_default = config_param_is_default(p);
+    bool unsaved = param_unsaved(p);
+
+    if (is_default && !unsaved) {
+        return;
+    }
+
+    printf("%s=%s", p->full_path, buf);
+    if (!is_default) {
+        printf(" (default %s)", config_param_default(p));
+    }
+    if (unsaved) {
+        printf(" *");
+    }
+    printf("\r\n");
+    (*shown)++;
 }
 
 /**
@@ -304,8 +764,22 @@ static void show_param_visitor(const param_descriptor_t *p, void *ctx) {
  *   show keyer.*          All keyer parameters
  *   show keyer.**         Keyer + subfamilies
  *   show hw.*             Alias for hardware.*
+ *   show *gpio*           Bare-name wildcard, any family ("*" = all)
+ *   show changed [pat]    Only params differing from default or NVS
  */
 static console_error_t cmd_show(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc > 0 && strcmp(cmd->args[0], "changed") == 0) {
+        const char *pattern = (cmd->argc > 1) ? cmd->args[1] : "**";
+        size_t shown = 0;
+        config_foreach_matching(pattern, show_changed_visitor, &shown);
+        if (shown == 0) {
+            printf("All parameters at defaults\r\n");
+        } else {
+            printf("(* = not saved to NVS)\r\n");
+        }
+        return CONSOLE_OK;
+    }
+
     const char *pattern = (cmd->argc > 0) ? cmd->args[0] : "**";
 
     /* Handle "show" with no args as "show **" (all params) */
@@ -313,8 +787,21 @@ static console_error_t cmd_show(const console_parsed_cmd_t *cmd) {
         pattern = "**";
     }
 
-    /* Use pattern matching */
-    config_foreach_matching(pattern, show_param_visitor, NULL);
+    /* Use pattern matching; cross-family listings skip gated-off families */
+    bool bare_glob = (strchr(pattern, '*') != NULL && strchr(pattern, '.') == NULL);
+    show_ctx_t show = {
+        .unsaved = 0,
+        .hidden = 0,
+        .hide_gated = (strstr(pattern, "**") != NULL || pattern[0] == '*' || bare_glob),
+    };
+    if (config_foreach_matching(pattern, show_param_visitor, &show) == 0) {
+        printf("No parameters match '%s'\r\n", pattern);
+    } else if (show.unsaved > 0) {
+        printf("(* = not saved to NVS)\r\n");
+    }
+    if (show.hidden > 0) {
+        printf("(%u hidden in disabled groups, see 'help')\r\n", (unsigned)show.hidden);
+    }
 
     return CONSOLE_OK;
 }
@@ -338,6 +825,233 @@ static const char *strip_quotes(const char *s, char *buf, size_t buflen) {
     return s;
 }
 
+/** Progress of a "set <pattern> --default" run */
+typedef struct {
+    size_t reset;
+    size_t failed;
+} reset_ctx_t;
+
+/* Visitor callback for "set <pattern> --default" */
+static void reset_param_visitor(const param_descriptor_t *p, void *ctx) {
+    reset_ctx_t *r = (reset_ctx_t *)ctx;
+    if (config_param_is_default(p)) {
+        return;  /* Nothing to do, don't bump the generation */
+    }
+    int ret = audited_set(p->full_path, config_param_default(p));
+    if (ret == 0) {
+        printf("%s=%s\r\n", p->full_path, config_param_default(p));
+        r->reset++;
+    } else {
+        printf("%s: %s\r\n", p->full_path,
+               (ret == -5) ? config_constraint_error() : "reset failed");
+        r->failed++;
+    }
+}
+
+/* ============================================================================
+ * Batch Set (set begin ... set commit)
+ * ============================================================================ */
+
+/** Maximum parameters staged between "set begin" and "set commit" */
+#define SET_BATCH_MAX 8
+
+/** Staged value size (fits any parameter's string form) */
+#define SET_BATCH_VALUE_LEN (CONFIG_STR_MAX_LEN + 1)
+
+static bool s_batch_open = false;
+static size_t s_batch_count = 0;
+static const param_descriptor_t *s_batch_param[SET_BATCH_MAX];
+static char s_batch_value[SET_BATCH_MAX][SET_BATCH_VALUE_LEN];
+static char s_batch_old[SET_BATCH_MAX][SET_BATCH_VALUE_LEN];
+
+/**
+ * @brief Map a config_set_param_str() result to a console error
+ */
+static console_error_t set_result_error(int ret) {
+    switch (ret) {
+        case 0:
+            return CONSOLE_OK;
+        case -1:
+            return CONSOLE_ERR_UNKNOWN_CMD;
+        case -4:
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        case -5:
+            printf("Rejected: %s\r\n", config_constraint_error());
+            return CONSOLE_ERR_INVALID_VALUE;
+        case -6:
+            printf("Read-only parameter\r\n");
+            return CONSOLE_ERR_INVALID_VALUE;
+        default:
+            return CONSOLE_ERR_INVALID_VALUE;
+    }
+}
+
+/**
+ * @brief Stage one value; a parameter staged twice keeps the last value
+ */
+static console_error_t batch_stage(const char *path, const char *value) {
+    const param_descriptor_t *p = config_find_param(path);
+    if (p == NULL) {
+        return CONSOLE_ERR_UNKNOWN_CMD;
+    }
+    if (p->readonly) {
+        return set_result_error(-6);
+    }
+    if (strlen(value) >= SET_BATCH_VALUE_LEN) {
+        return CONSOLE_ERR_OUT_OF_RANGE;
+    }
+
+    size_t i = 0;
+    while (i < s_batch_count && s_batch_param[i] != p) {
+        i++;
+    }
+    if (i == s_batch_count) {
+        if (s_batch_count >= SET_BATCH_MAX) {
+            printf("Batch full (%d parameters)\r\n", SET_BATCH_MAX);
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+        s_batch_param[s_batch_count++] = p;
+    }
+    strcpy(s_batch_value[i], value);
+    printf("%s=%s (staged %u/%d)\r\n", p->full_path, value,
+           (unsigned)s_batch_count, SET_BATCH_MAX);
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief Apply pending entries, repeating passes until no progress
+ *
+ * A constraint may only hold once a later entry is in (e.g. moving the
+ * memory window start past the old end), so order within a batch
+ * doesn't matter.
+ *
+ * @param params Parameter per entry
+ * @param count Number of entries
+ * @param values Value per entry
+ * @param pending In: entries to apply. Out: entries that failed
+ * @param last_err Result of the last failure (unchanged if none)
+ * @return Number of entries applied
+ */
+static size_t batch_apply(const param_descriptor_t *const *params, size_t count,
+                          char (*values)[SET_BATCH_VALUE_LEN], bool *pending, int *last_err) {
+    size_t applied = 0;
+    bool progress = true;
+    while (progress) {
+        progress = false;
+        for (size_t i = 0; i < count; i++) {
+            if (!pending[i]) {
+                continue;
+            }
+            int ret = config_set_param_str(params[i]->full_path, values[i]);
+            if (ret == 0) {
+                pending[i] = false;
+                applied++;
+                progress = true;
+            } else {
+                *last_err = ret;
+            }
+        }
+    }
+    return applied;
+}
+
+/**
+ * @brief Apply all staged values under one generation, or none of them
+ */
+static console_error_t batch_commit(void) {
+    bool pending[SET_BATCH_MAX];
+    for (size_t i = 0; i < s_batch_count; i++) {
+        config_get_param_str(s_batch_param[i]->full_path, s_batch_old[i], SET_BATCH_VALUE_LEN);
+        pending[i] = true;
+    }
+
+    int err = 0;
+    config_batch_begin(&g_config);
+    size_t applied = batch_apply(s_batch_param, s_batch_count, s_batch_value, pending, &err);
+    if (applied < s_batch_count) {
+        /* Roll back: consumers must never see part of the batch */
+        bool undo[SET_BATCH_MAX];
+        int undo_err = 0;
+        for (size_t i = 0; i < s_batch_count; i++) {
+            if (pending[i]) {
+                printf("%s=%s failed\r\n", s_batch_param[i]->full_path, s_batch_value[i]);
+            }
+            undo[i] = !pending[i];
+        }
+        batch_apply(s_batch_param, s_batch_count, s_batch_old, undo, &undo_err);
+    }
+    config_batch_commit(&g_config);
+
+    if (applied < s_batch_count) {
+        printf("Nothing applied, batch still open ('set abort' to drop)\r\n");
+        return set_result_error(err);
+    }
+    for (size_t i = 0; i < s_batch_count; i++) {
+        audit_change(s_batch_param[i]->full_path, s_batch_old[i]);
+    }
+    printf("Applied %u parameters (generation %u)\r\n", (unsigned)applied,
+           (unsigned)atomic_load_explicit(&g_config.generation, memory_order_relaxed));
+    s_batch_open = false;
+    s_batch_count = 0;
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief set begin | commit | abort
+ */
+static console_error_t batch_command(const char *sub) {
+    if (strcmp(sub, "begin") == 0) {
+        if (s_batch_open) {
+            printf("Batch already open (%u staged)\r\n", (unsigned)s_batch_count);
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        s_batch_open = true;
+        s_batch_count = 0;
+        printf("Batch open: 'set' stages values until 'set commit'\r\n");
+        return CONSOLE_OK;
+    }
+    if (!s_batch_open) {
+        printf("No batch open ('set begin' first)\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    if (strcmp(sub, "commit") == 0) {
+        return batch_commit();
+    }
+    /* abort */
+    s_batch_open = false;
+    s_batch_count = 0;
+    printf("Batch dropped\r\n");
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief Reset one parameter, or every parameter matching a pattern, to default
+ */
+static console_error_t set_to_default(const char *path) {
+    reset_ctx_t r = { 0, 0 };
+    bool pattern = (path[0] == '@') || (strchr(path, '*') != NULL);
+
+    if (pattern) {
+        if (config_foreach_matching(path, reset_param_visitor, &r) == 0) {
+            printf("No parameters match '%s'\r\n", path);
+            return CONSOLE_OK;
+        }
+        printf("%u reset to default\r\n", (unsigned)r.reset);
+    } else {
+        /* Exact name or path only: no prefix matching for a single reset */
+        const param_descriptor_t *p = config_find_param(path);
+        if (p == NULL) {
+            return CONSOLE_ERR_UNKNOWN_CMD;
+        }
+        reset_param_visitor(p, &r);
+        if (r.reset == 0 && r.failed == 0) {
+            printf("%s=%s (already default)\r\n", p->full_path, config_param_default(p));
+        }
+    }
+
+    return (r.failed == 0) ? CONSOLE_OK : CONSOLE_ERR_INVALID_VALUE;
+}
+
 /**
  * @brief set <path> <value> - Set parameter by path
  *
@@ -347,16 +1061,25 @@ static const char *strip_quotes(const char *s, char *buf, size_t buflen) {
  *   set wifi.ssid=MyNetwork
  *   set wifi.ssid = "My Network"
  *   set wpm 25  (legacy, still works)
+ *   set keyer.wpm --default
+ *   set @audio --default
+ *   set begin / set commit / set abort  (apply several at once)
  */
 static console_error_t cmd_set(const console_parsed_cmd_t *cmd) {
     if (cmd->argc < 1) {
         return CONSOLE_ERR_MISSING_ARG;
     }
+    if (cmd->argc == 1 && (strcmp(cmd->args[0], "begin") == 0 ||
+                           strcmp(cmd->args[0], "commit") == 0 ||
+                           strcmp(cmd->args[0], "abort") == 0)) {
+        return batch_command(cmd->args[0]);
+    }
 
     static char path_buf[64];
     static char value_buf[128];
     const char *path = NULL;
     const char *value = NULL;
+    const char *raw = NULL;  /* Value token as typed, quotes included */
 
     /* Check for key=value format in first arg */
     const char *eq = strchr(cmd->args[0], '=');
@@ -367,7 +1090,7 @@ static console_error_t cmd_set(const console_parsed_cmd_t *cmd) {
         memcpy(path_buf, cmd->args[0], path_len);
         path_buf[path_len] = '\0';
         path = path_buf;
-        value = strip_quotes(eq + 1, value_buf, sizeof(value_buf));
+        raw = eq + 1;
     } else if (cmd->argc >= 2) {
         path = cmd->args[0];
         /* Check if args[1] is "=" */
@@ -375,60 +1098,371 @@ static console_error_t cmd_set(const console_parsed_cmd_t *cmd) {
             if (cmd->argc < 3) {
                 return CONSOLE_ERR_MISSING_ARG;
             }
-            value = strip_quotes(cmd->args[2], value_buf, sizeof(value_buf));
+            raw = cmd->args[2];
         } else {
             /* Skip leading = if present */
-            const char *v = cmd->args[1];
-            if (v[0] == '=') v++;
-            value = strip_quotes(v, value_buf, sizeof(value_buf));
+            raw = cmd->args[1];
+            if (raw[0] == '=') raw++;
         }
     } else {
         return CONSOLE_ERR_MISSING_ARG;
     }
 
+    /* An option, not a value: any string, "default" included, can be stored */
+    bool to_default = strcmp(raw, "--default") == 0;
+    value = strip_quotes(raw, value_buf, sizeof(value_buf));
+
+    if (s_batch_open) {
+        if (to_default) {
+            /* Single parameter only: patterns aren't staged */
+            const param_descriptor_t *p = config_find_param(path);
+            if (p == NULL) {
+                return CONSOLE_ERR_UNKNOWN_CMD;
+            }
+            value = config_param_default(p);
+        }
+        return batch_stage(path, value);
+    }
+
+    /* --default restores the schema default (one param or a pattern) */
+    if (to_default) {
+        return set_to_default(path);
+    }
+
     /* Try full path first (config_find_param handles both path and name) */
-    int ret = config_set_param_str(path, value);
+    int ret = audited_set(path, value);
 
     /* If not found and no dot, try legacy name lookup */
     if (ret == -1 && strchr(path, '.') == NULL) {
         /* Search all params for matching name */
         for (size_t i = 0; i < CONSOLE_PARAM_COUNT; i++) {
             if (strcmp(CONSOLE_PARAMS[i].name, path) == 0) {
-                ret = config_set_param_str(CONSOLE_PARAMS[i].full_path, value);
+                ret = audited_set(CONSOLE_PARAMS[i].full_path, value);
                 break;
             }
         }
     }
 
-    switch (ret) {
-        case 0:
-            /* Show confirmation with new value */
-            {
-                char buf[128];
-                if (config_get_param_str(path, buf, sizeof(buf)) == 0) {
-                    printf("%s=%s\r\n", path, buf);
-                }
+    if (ret == 0) {
+        /* Show confirmation with new value */
+        char buf[128];
+        if (config_get_param_str(path, buf, sizeof(buf)) == 0) {
+            printf("%s=%s\r\n", path, buf);
+        }
+    }
+    return set_result_error(ret);
+}
+
+/** Parameters a preset drives (overrides only when set) */
+#define PRESET_PARAM_MAX 9
+
+/**
+ * @brief Copy a preset into the live config as one change
+ *
+ * Keying parameters always follow the preset; sidetone and PTT tail only
+ * when the preset overrides them. Applied like a "set commit" batch, so
+ * the memory window can move past its old bounds in either direction.
+ *
+ * @return Number of parameters that could not be applied
+ */
+static size_t preset_apply(const iambic_preset_t *p) {
+    const param_descriptor_t *param[PRESET_PARAM_MAX];
+    char value[PRESET_PARAM_MAX][SET_BATCH_VALUE_LEN];
+    char old[PRESET_PARAM_MAX][SET_BATCH_VALUE_LEN];
+    bool pending[PRESET_PARAM_MAX];
+    size_t n = 0;
+
+#define PRESET_PARAM(name, v) do { \
+        param[n] = config_find_param(name); \
+        snprintf(value[n], sizeof(value[n]), "%u", (unsigned)(v)); \
+        config_get_param_str((name), old[n], sizeof(old[n])); \
+        pending[n++] = true; \
+    } while (0)
+    PRESET_PARAM("keyer.wpm", iambic_preset_get_wpm(p));
+    PRESET_PARAM("keyer.iambic_mode", iambic_preset_get_mode(p));
+    PRESET_PARAM("keyer.memory_mode", iambic_preset_get_memory_mode(p));
+    PRESET_PARAM("keyer.squeeze_mode", iambic_preset_get_squeeze_mode(p));
+    PRESET_PARAM("keyer.mem_window_start_pct", iambic_preset_get_mem_start(p));
+    PRESET_PARAM("keyer.mem_window_end_pct", iambic_preset_get_mem_end(p));
+    PRESET_PARAM("keyer.weight", iambic_preset_get_weight(p));
+    if (iambic_preset_get_sidetone(p) != IAMBIC_PRESET_NO_OVERRIDE) {
+        PRESET_PARAM("audio.sidetone_freq_hz", iambic_preset_get_sidetone(p));
+    }
+    if (iambic_preset_get_ptt_tail(p) != IAMBIC_PRESET_NO_OVERRIDE) {
+        PRESET_PARAM("timing.ptt_tail_ms", iambic_preset_get_ptt_tail(p));
+    }
+#undef PRESET_PARAM
+
+    int err = 0;
+    config_batch_begin(&g_config);
+    size_t applied = batch_apply(param, n, value, pending, &err);
+    config_batch_commit(&g_config);
+
+    for (size_t i = 0; i < n; i++) {
+        if (pending[i]) {
+            printf("%s=%s failed\r\n", param[i]->full_path, value[i]);
+        } else {
+            audit_change(param[i]->full_path, old[i]);
+        }
+    }
+    return n - applied;
+}
+
+/**
+ * @brief Parse an override argument: a value in [min, max] or "off"
+ */
+static bool parse_override(const char *arg, unsigned long min, unsigned long max,
+                           uint16_t *out) {
+    if (strcmp(arg, "off") == 0) {
+        *out = IAMBIC_PRESET_NO_OVERRIDE;
+        return true;
+    }
+    char *end;
+    unsigned long v = strtoul(arg, &end, 10);
+    if (*end != '\0' || v < min || v > max) {
+        return false;
+    }
+    *out = (uint16_t)v;
+    return true;
+}
+
+/**
+ * @brief preset [list|use|name|tone|ptt|export|import|save] - Iambic presets
+ */
+static console_error_t cmd_preset(const console_parsed_cmd_t *cmd) {
+    static const char *const MODE_NAMES[] = { "A", "B" };
+    const char *sub = (cmd->argc > 0) ? cmd->args[0] : "list";
+
+    if (strcmp(sub, "list") == 0) {
+        uint32_t active = iambic_preset_active_index();
+        for (uint32_t i = 0; i < IAMBIC_PRESET_COUNT; i++) {
+            const iambic_preset_t *p = iambic_preset_get(i);
+            char name[IAMBIC_PRESET_NAME_MAX];
+            iambic_preset_get_name(p, name, sizeof(name));
+            char tone[12] = "-";
+            char ptt[12] = "-";
+            if (iambic_preset_get_sidetone(p) != IAMBIC_PRESET_NO_OVERRIDE) {
+                snprintf(tone, sizeof(tone), "%u Hz", (unsigned)iambic_preset_get_sidetone(p));
             }
-            return CONSOLE_OK;
-        case -1:
-            return CONSOLE_ERR_UNKNOWN_CMD;
-        case -2:
+            if (iambic_preset_get_ptt_tail(p) != IAMBIC_PRESET_NO_OVERRIDE) {
+                snprintf(ptt, sizeof(ptt), "%u ms", (unsigned)iambic_preset_get_ptt_tail(p));
+            }
+            printf("%c %lu %-16s %3lu wpm  mode %s  window %u-%u%%  weight %u  tone %s  ptt %s\r\n",
+                   (i == active) ? '*' : ' ', (unsigned long)i,
+                   (name[0] != '\0') ? name : "(empty)",
+                   (unsigned long)iambic_preset_get_wpm(p),
+                   MODE_NAMES[iambic_preset_get_mode(p) & 1U],
+                   (unsigned)iambic_preset_get_mem_start(p),
+                   (unsigned)iambic_preset_get_mem_end(p),
+                   (unsigned)iambic_preset_get_weight(p), tone, ptt);
+        }
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "save") == 0) {
+        int ret = preset_nvs_save();
+        if (ret < 0) {
+            return CONSOLE_ERR_NVS_ERROR;
+        }
+        printf("Saved %d presets\r\n", ret);
+        return CONSOLE_OK;
+    }
+
+    if (cmd->argc < 2) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+    char *end;
+    unsigned long index = strtoul(cmd->args[1], &end, 10);
+    if (*end != '\0' || index >= IAMBIC_PRESET_COUNT) {
+        return CONSOLE_ERR_OUT_OF_RANGE;
+    }
+
+    if (strcmp(sub, "use") == 0) {
+        iambic_preset_activate((uint32_t)index);
+        if (preset_apply(iambic_preset_get((uint32_t)index)) > 0) {
             return CONSOLE_ERR_INVALID_VALUE;
-        case -4:
+        }
+        printf("Preset %lu active\r\n", index);
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "tone") == 0 || strcmp(sub, "ptt") == 0) {
+        bool tone = (sub[0] == 't');
+        uint16_t v;
+        if (cmd->argc < 3) {
+            return CONSOLE_ERR_MISSING_ARG;
+        }
+        if (!parse_override(cmd->args[2], tone ? 400 : 50, tone ? 800 : 500, &v)) {
             return CONSOLE_ERR_OUT_OF_RANGE;
-        default:
+        }
+        iambic_preset_t *p = iambic_preset_get_mut((uint32_t)index);
+        if (tone) {
+            iambic_preset_set_sidetone(p, v);
+        } else {
+            iambic_preset_set_ptt_tail(p, v);
+        }
+        printf("Preset %lu: %s %s (use 'preset save' to keep)\r\n", index,
+               tone ? "tone" : "ptt", cmd->args[2]);
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "export") == 0) {
+        char code[IAMBIC_PRESET_CODE_MAX];
+        if (iambic_preset_export((uint32_t)index, code, sizeof(code)) == 0) {
             return CONSOLE_ERR_INVALID_VALUE;
+        }
+        printf("preset import %lu %s\r\n", index, code);
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "import") == 0) {
+        if (cmd->argc < 3) {
+            return CONSOLE_ERR_MISSING_ARG;
+        }
+        if (!iambic_preset_import((uint32_t)index, cmd->args[2])) {
+            printf("Invalid preset code\r\n");
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        char name[IAMBIC_PRESET_NAME_MAX];
+        iambic_preset_get_name(iambic_preset_get((uint32_t)index), name, sizeof(name));
+        printf("Preset %lu: '%s' imported (use 'preset save' to keep)\r\n", index, name);
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "name") == 0) {
+        /* Rest of the line, so names may contain spaces; empty clears */
+        const char *name = (cmd->argc > 2) ? cmd->rest[2] : "";
+        if (strlen(name) >= IAMBIC_PRESET_NAME_MAX) {
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+        iambic_preset_set_name((uint32_t)index, name);
+        printf("Preset %lu: '%s' (use 'preset save' to keep)\r\n", index, name);
+        return CONSOLE_OK;
+    }
+
+    return CONSOLE_ERR_INVALID_VALUE;
+}
+
+#ifdef ESP_PLATFORM
+/**
+ * @brief Apply a level to a module tag or to every tag ("*")
+ *
+ * Module tags ("wifi", "rt", ...) filter before formatting; "*" also sets
+ * the USB log drain level.
+ */
+static console_error_t set_log_level(const char *tag, const char *level_str) {
+    log_level_t level;
+    if (!log_level_from_str(level_str, &level)) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    log_tag_t module;
+    if (strcmp(tag, "*") == 0) {
+        for (int t = 0; t < LOG_TAG_COUNT; t++) {
+            log_tag_set_level((log_tag_t)t, level);
+        }
+        usb_log_set_level(level);
+    } else if (log_tag_from_str(tag, &module)) {
+        log_tag_set_level(module, level);
+    } else {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    printf("Log %s = %s\r\n", tag, log_level_str(level));
+    return CONSOLE_OK;
+}
+#endif
+
+/**
+ * @brief Print the stream tail of a crash record, one character per sample
+ *
+ * '#' key down, '.' key up, '~' compressed silence, 50 per row.
+ */
+static void print_crash_samples(const crash_record_t *rec) {
+    printf("--- last %lu stream samples (up to #%lu) ---\r\n",
+           (unsigned long)rec->sample_count, (unsigned long)rec->sample_end);
+    char row[51];
+    size_t n = 0;
+    uint32_t silence_ticks = 0;
+    for (uint32_t i = 0; i < rec->sample_count; i++) {
+        const stream_sample_t *sample = &rec->samples[i];
+        if (sample_is_silence(sample)) {
+            silence_ticks += sample_silence_ticks(sample);
+            row[n++] = '~';
+        } else {
+            row[n++] = sample->local_key ? '#' : '.';
+        }
+        if (n == sizeof(row) - 1 || i + 1 == rec->sample_count) {
+            row[n] = '\0';
+            printf("%s\r\n", row);
+            n = 0;
+        }
+    }
+    if (silence_ticks > 0) {
+        printf("silence: %lu ticks\r\n", (unsigned long)silence_ticks);
+    }
+}
+
+/**
+ * @brief Print the post-mortem record kept in flash
+ */
+static void print_crash_log(void) {
+    static crash_record_t rec;  /* Too large for the console stack */
+    if (!crash_log_read(&rec)) {
+        printf("No crash log\r\n");
+        return;
     }
+
+    printf("Crash log: %s at %lld.%03llds uptime\r\n",
+           crash_cause_str((crash_cause_t)rec.cause),
+           (long long)(rec.uptime_us / 1000000),
+           (long long)((rec.uptime_us / 1000) % 1000));
+    printf("fault: %s%s data=%lu count=%lu\r\n",
+           fault_code_str((fault_code_t)rec.fault_code),
+           rec.fault_active ? " (active)" : "",
+           (unsigned long)rec.fault_data, (unsigned long)rec.fault_count);
+    for (int code = 1; code < FAULT_CODE_COUNT; code++) {
+        if (rec.fault_raised & FAULT_BIT(code)) {
+            printf("raised: %s\r\n", fault_code_str((fault_code_t)code));
+        }
+    }
+    printf("--- last %lu log lines ---\r\n", (unsigned long)rec.line_count);
+    for (uint32_t i = 0; i < rec.line_count; i++) {
+        printf("%s\r\n", rec.lines[i]);
+    }
+    print_crash_samples(&rec);
 }
 
 /**
- * @brief log - Set log level
+ * @brief log - Set log level, show the crash log
  */
 static console_error_t cmd_log(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc >= 1 && strcmp(cmd->args[0], "crash") == 0) {
+        if (cmd->argc >= 2 && strcmp(cmd->args[1], "clear") == 0) {
+            if (!crash_log_clear()) {
+                return CONSOLE_ERR_INVALID_VALUE;
+            }
+            printf("Crash log cleared\r\n");
+            return CONSOLE_OK;
+        }
+        print_crash_log();
+        return CONSOLE_OK;
+    }
+
 #ifdef ESP_PLATFORM
     if (cmd->argc == 0) {
-        /* Show current level */
+        /* Show current levels */
         printf("Log level: %s\r\n", log_level_str(usb_log_get_level()));
+        for (int t = 0; t < LOG_TAG_COUNT; t++) {
+            printf("  %-8s %s\r\n", log_tag_str((log_tag_t)t),
+                   log_level_str(log_tag_get_level((log_tag_t)t)));
+        }
+        for (int slot = 0; slot < LOG_DRAIN_MAX_SINKS; slot++) {
+            const char *name;
+            uint32_t dropped;
+            if (log_drain_sink_info(slot, &name, &dropped)) {
+                printf("Sink %-6s dropped %lu\r\n", name, (unsigned long)dropped);
+            }
+        }
         return CONSOLE_OK;
     }
 
@@ -440,31 +1474,7 @@ static console_error_t cmd_log(const console_parsed_cmd_t *cmd) {
         if (cmd->argc < 3) {
             return CONSOLE_ERR_MISSING_ARG;
         }
-        const char *tag = cmd->args[1];
-        const char *level_str = cmd->args[2];
-
-        log_level_t level;
-        if (strcmp(level_str, "ERROR") == 0 || strcmp(level_str, "E") == 0) {
-            level = LOG_LEVEL_ERROR;
-        } else if (strcmp(level_str, "WARN") == 0 || strcmp(level_str, "W") == 0) {
-            level = LOG_LEVEL_WARN;
-        } else if (strcmp(level_str, "INFO") == 0 || strcmp(level_str, "I") == 0) {
-            level = LOG_LEVEL_INFO;
-        } else if (strcmp(level_str, "DEBUG") == 0 || strcmp(level_str, "D") == 0) {
-            level = LOG_LEVEL_DEBUG;
-        } else if (strcmp(level_str, "TRACE") == 0 || strcmp(level_str, "T") == 0) {
-            level = LOG_LEVEL_TRACE;
-        } else {
-            return CONSOLE_ERR_INVALID_VALUE;
-        }
-
-        if (strcmp(tag, "*") == 0) {
-            usb_log_set_level(level);
-        } else {
-            usb_log_set_tag_level(tag, level);
-        }
-        printf("Log %s = %s\r\n", tag, log_level_str(level));
-        return CONSOLE_OK;
+        return set_log_level(cmd->args[1], cmd->args[2]);
     }
 
     /* Compact style: TAG=L */
@@ -475,26 +1485,7 @@ static console_error_t cmd_log(const console_parsed_cmd_t *cmd) {
     char *eq = strchr(tag_buf, '=');
     if (eq != NULL) {
         *eq = '\0';
-        const char *tag = tag_buf;
-        const char *level_char = eq + 1;
-
-        log_level_t level;
-        switch (*level_char) {
-            case 'E': level = LOG_LEVEL_ERROR; break;
-            case 'W': level = LOG_LEVEL_WARN; break;
-            case 'I': level = LOG_LEVEL_INFO; break;
-            case 'D': level = LOG_LEVEL_DEBUG; break;
-            case 'T': level = LOG_LEVEL_TRACE; break;
-            default: return CONSOLE_ERR_INVALID_VALUE;
-        }
-
-        if (strcmp(tag, "*") == 0) {
-            usb_log_set_level(level);
-        } else {
-            usb_log_set_tag_level(tag, level);
-        }
-        printf("Log %s = %s\r\n", tag, log_level_str(level));
-        return CONSOLE_OK;
+        return set_log_level(tag_buf, eq + 1);
     }
 
     return CONSOLE_ERR_INVALID_VALUE;
@@ -522,11 +1513,12 @@ static console_error_t cmd_uf2(const console_parsed_cmd_t *cmd) {
 }
 
 /**
- * @brief factory-reset confirm - Erase NVS and reboot
+ * @brief factory-reset [confirm] - Erase NVS and reboot (prompts y/N without 'confirm')
  */
 static console_error_t cmd_factory_reset(const console_parsed_cmd_t *cmd) {
     if (cmd->argc == 0 || strcmp(cmd->args[0], "confirm") != 0) {
-        return CONSOLE_ERR_REQUIRES_CONFIRM;
+        printf("All settings will be erased.\r\n");
+        return console_request_confirm("factory-reset confirm");
     }
 #ifdef ESP_PLATFORM
     printf("Erasing all NVS and rebooting...\r\n");
@@ -545,6 +1537,246 @@ static console_error_t cmd_factory_reset(const console_parsed_cmd_t *cmd) {
     return CONSOLE_OK;
 }
 
+/** Codec registers per line in a dump */
+#define CODEC_DUMP_COLUMNS 16
+
+/**
+ * @brief Parse a register address or value (decimal or 0x hex)
+ */
+static bool parse_codec_byte(const char *arg, uint8_t *out) {
+    char *end;
+    unsigned long v = strtoul(arg, &end, 0);
+    if (end == arg || *end != '\0' || v > 0xFF) {
+        return false;
+    }
+    *out = (uint8_t)v;
+    return true;
+}
+
+/**
+ * @brief Print codec registers first..last, CODEC_DUMP_COLUMNS per line
+ */
+static esp_err_t codec_dump_range(uint8_t first, uint8_t last) {
+    for (unsigned reg = first; reg <= last; reg++) {
+        uint8_t value;
+        esp_err_t err = hal_audio_codec_read((uint8_t)reg, &value);
+        if (err != ESP_OK) {
+            printf("\r\n");
+            return err;
+        }
+        if (reg == first || reg % CODEC_DUMP_COLUMNS == 0) {
+            printf("%s%02X:", reg == first ? "" : "\r\n", reg);
+        }
+        printf(" %02X", value);
+    }
+    printf("\r\n");
+    return ESP_OK;
+}
+
+/**
+ * @brief codec dump | codec reg <addr> [value] - ES8311 register access
+ */
+static console_error_t cmd_codec(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+
+    esp_err_t err;
+    if (strcmp(cmd->args[0], "dump") == 0) {
+        err = codec_dump_range(0x00, HAL_AUDIO_CODEC_REG_LAST);
+        if (err == ESP_OK) {
+            err = codec_dump_range(0xFD, 0xFF);  /* Chip ID, version */
+        }
+    } else if (strcmp(cmd->args[0], "reg") == 0) {
+        uint8_t reg;
+        uint8_t value;
+        if (cmd->argc < 2) {
+            return CONSOLE_ERR_MISSING_ARG;
+        }
+        if (!parse_codec_byte(cmd->args[1], &reg)) {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        if (cmd->argc >= 3) {
+            if (!parse_codec_byte(cmd->args[2], &value)) {
+                return CONSOLE_ERR_INVALID_VALUE;
+            }
+            err = hal_audio_codec_write(reg, value);
+        } else {
+            err = ESP_OK;
+        }
+        if (err == ESP_OK) {
+            err = hal_audio_codec_read(reg, &value);
+        }
+        if (err == ESP_OK) {
+            printf("%02X: %02X\r\n", reg, value);
+        }
+    } else {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    if (err == ESP_ERR_INVALID_STATE) {
+        printf("No codec\r\n");
+    } else if (err != ESP_OK) {
+        printf("Codec I2C error: %s\r\n", esp_err_to_name(err));
+    }
+    return CONSOLE_OK;
+}
+
+static void fs_print_file(const storage_file_t *file, void *ctx) {
+    (void)ctx;
+    printf("  %-12s %10lu%s\r\n", file->name, (unsigned long)file->size,
+           recorder_is_open(file->name) ? "  recording" : "");
+}
+
+/**
+ * @brief fs [ls|rm <name>] - SD card files and recordings
+ */
+static console_error_t cmd_fs(const console_parsed_cmd_t *cmd) {
+    if (!storage_is_mounted()) {
+        printf("No SD card\r\n");
+        return CONSOLE_OK;
+    }
+
+    if (cmd->argc == 0) {
+        recorder_status_t rec;
+        uint64_t total = 0;
+        uint64_t free_bytes = 0;
+        recorder_get_status(&rec);
+        storage_usage(&total, &free_bytes);
+        printf("SD card: %llu MB free of %llu MB\r\n",
+               (unsigned long long)(free_bytes >> 20), (unsigned long long)(total >> 20));
+        printf("Session: %04u\r\n", rec.session);
+        printf("Keying: %s  Text: %s  Log: %s\r\n",
+               rec.keying ? "REC" : "off", rec.transcript ? "REC" : "off",
+               rec.log ? "REC" : "off");
+        printf("Written: %lu bytes, %lu errors, %lu samples dropped\r\n",
+               (unsigned long)rec.bytes, (unsigned long)rec.errors,
+               (unsigned long)rec.dropped);
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(cmd->args[0], "ls") == 0) {
+        if (storage_list(fs_print_file, NULL) == 0) {
+            printf("No files\r\n");
+        }
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(cmd->args[0], "rm") == 0) {
+        if (cmd->argc < 2) {
+            return CONSOLE_ERR_MISSING_ARG;
+        }
+        const char *name = cmd->args[1];
+        if (!record_name_is_safe(name)) {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        if (recorder_is_open(name)) {
+            printf("%s is being recorded, switch it off first\r\n", name);
+        } else if (storage_remove(name)) {
+            printf("Deleted %s\r\n", name);
+        } else {
+            printf("Cannot delete %s\r\n", name);
+        }
+        return CONSOLE_OK;
+    }
+
+    return CONSOLE_ERR_INVALID_VALUE;
+}
+
+/**
+ * @brief selftest - Run built-in diagnostic suite
+ */
+static console_error_t cmd_selftest(const console_parsed_cmd_t *cmd) {
+    (void)cmd;
+    selftest_result_t results[SELFTEST_ITEM_COUNT];
+    size_t n = selftest_run_all(results, SELFTEST_ITEM_COUNT);
+
+    size_t failed = 0;
+    for (size_t i = 0; i < n; i++) {
+        printf("  %-8s %s  %s\r\n", results[i].name,
+               selftest_status_str(results[i].status), results[i].detail);
+        if (results[i].status == SELFTEST_FAIL) {
+            failed++;
+        }
+    }
+
+    if (failed == 0) {
+        printf("Self-test PASS\r\n");
+    } else {
+        printf("Self-test FAIL (%u of %u)\r\n", (unsigned)failed, (unsigned)n);
+    }
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief lock [set <pass>|clear|status] - Console lock control
+ */
+static console_error_t cmd_lock(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        if (!console_lock_engage()) {
+            printf("No passphrase set (use 'lock set <pass>')\r\n");
+            return CONSOLE_OK;
+        }
+        printf("Console locked\r\n");
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(cmd->args[0], "status") == 0) {
+        uint16_t timeout = CONFIG_GET_LOCK_TIMEOUT();
+        printf("Passphrase: %s\r\n", console_lock_has_passphrase() ? "set" : "not set");
+        if (timeout > 0) {
+            printf("Auto-lock:  after %u min idle\r\n", (unsigned)timeout);
+        } else {
+            printf("Auto-lock:  off\r\n");
+        }
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(cmd->args[0], "set") == 0) {
+        if (cmd->argc < 2) {
+            return CONSOLE_ERR_MISSING_ARG;
+        }
+        console_error_t err = console_lock_set_passphrase(cmd->args[1]);
+        if (err == CONSOLE_ERR_OUT_OF_RANGE) {
+            printf("Passphrase must be %d-%d characters\r\n",
+                   CONSOLE_LOCK_PASS_MIN, CONSOLE_LOCK_PASS_MAX);
+        }
+        if (err == CONSOLE_OK) {
+            printf("Passphrase set (console locks at boot and with 'lock')\r\n");
+        }
+        return err;
+    }
+
+    if (strcmp(cmd->args[0], "clear") == 0) {
+        console_error_t err = console_lock_set_passphrase(NULL);
+        if (err == CONSOLE_OK) {
+            printf("Passphrase removed, console lock disabled\r\n");
+        }
+        return err;
+    }
+
+    return CONSOLE_ERR_INVALID_VALUE;
+}
+
+/**
+ * @brief unlock <pass> - Unlock the console
+ */
+static console_error_t cmd_unlock(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+    if (!console_lock_try_unlock(cmd->args[0])) {
+#ifdef ESP_PLATFORM
+        /* Slow down guessing */
+        vTaskDelay(pdMS_TO_TICKS(1000));
+#endif
+        printf("Wrong passphrase\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    printf("Console unlocked\r\n");
+    return CONSOLE_OK;
+}
+
 /**
  * @brief debug - Set ESP-IDF log levels
  *
@@ -784,13 +2016,18 @@ static console_error_t cmd_gpio(const console_parsed_cmd_t *cmd) {
 #endif
 }
 
+/** test stress: scratch stream size (power of 2) and run length */
+#define STRESS_STREAM_SIZE 1024
+#define STRESS_DEFAULT_SECONDS 60
+#define STRESS_MAX_SECONDS 600
+
 /**
  * @brief test - Diagnostic test commands
  */
 static console_error_t cmd_test(const console_parsed_cmd_t *cmd) {
 #ifdef ESP_PLATFORM
     if (cmd->argc == 0) {
-        printf("Usage: test cdc1 | test log | test gpio\r\n");
+        printf("Usage: test cdc1 | test log | test gpio | test stress [seed] [seconds]\r\n");
         return CONSOLE_OK;
     }
 
@@ -867,6 +2104,45 @@ static console_error_t cmd_test(const console_parsed_cmd_t *cmd) {
         return CONSOLE_OK;
     }
 
+    if (strcmp(arg, "stress") == 0) {
+        /* Private stream: the live keying path is not touched */
+        static EXT_RAM_BSS_ATTR stream_sample_t s_stress_buffer[STRESS_STREAM_SIZE];
+        static keying_stream_t s_stress_stream;
+
+        stress_config_t config = STRESS_CONFIG_DEFAULT;
+        uint32_t seconds = STRESS_DEFAULT_SECONDS;
+        if (cmd->argc >= 2) {
+            config.seed = (uint32_t)strtoul(cmd->args[1], NULL, 0);
+        }
+        if (cmd->argc >= 3) {
+            seconds = (uint32_t)strtoul(cmd->args[2], NULL, 10);
+            if (seconds == 0 || seconds > STRESS_MAX_SECONDS) {
+                return CONSOLE_ERR_OUT_OF_RANGE;
+            }
+        }
+
+        stream_init(&s_stress_stream, s_stress_buffer, STRESS_STREAM_SIZE);
+        stress_report_t report;
+        int64_t start_us = esp_timer_get_time();
+        iambic_stress_run(&config, seconds * 1000U, &s_stress_stream, 2, &report);
+        int64_t took_us = esp_timer_get_time() - start_us;
+
+        printf("Stress seed %lu, %lu s simulated in %lu ms\r\n",
+               (unsigned long)config.seed, (unsigned long)seconds,
+               (unsigned long)(took_us / 1000));
+        printf("  samples %lu, key edges %lu, speed changes %lu\r\n",
+               (unsigned long)report.samples, (unsigned long)report.key_edges,
+               (unsigned long)report.speed_changes);
+        printf("  stalls %lu, faults %lu latency / %lu overrun, recoveries %lu\r\n",
+               (unsigned long)report.stalls, (unsigned long)report.faults_latency,
+               (unsigned long)report.faults_overrun, (unsigned long)report.recoveries);
+        printf("  unexpected faults %lu, key mismatches %lu, best-effort drops %lu\r\n",
+               (unsigned long)report.faults_unexpected, (unsigned long)report.key_mismatches,
+               (unsigned long)report.best_effort_dropped);
+        printf("%s\r\n", iambic_stress_ok(&report) ? "PASS" : "FAIL");
+        return CONSOLE_OK;
+    }
+
     return CONSOLE_ERR_INVALID_VALUE;
 #else
     (void)cmd;
@@ -939,17 +2215,24 @@ static console_error_t cmd_decoder(const console_parsed_cmd_t *cmd) {
             return CONSOLE_OK;
         }
 
-        decoded_char_t chars[128];
-        size_t n = decoder_get_text_with_timestamps(chars, 128);
-        for (size_t i = 0; i < n; i++) {
-            int64_t ts = chars[i].timestamp_us;
-            int secs = (int)(ts / 1000000);
-            int ms = (int)((ts / 1000) % 1000);
-            char c = chars[i].character;
-            if (c == ' ') {
-                printf("[%d.%03d] (space)\r\n", secs, ms);
-            } else {
-                printf("[%d.%03d] %c\r\n", secs, ms, c);
+        /* Own cursor from the oldest held: bg_task's pop is not disturbed */
+        uint32_t cursor = 0;
+        decoded_char_t chars[16];
+        size_t n;
+        while ((n = decoder_read_since(&cursor, chars, sizeof(chars) / sizeof(chars[0]))) > 0) {
+            for (size_t i = 0; i < n; i++) {
+                int64_t ts = chars[i].timestamp_us;
+                int secs = (int)(ts / 1000000);
+                int ms = (int)((ts / 1000) % 1000);
+                char c = chars[i].character;
+                char sent = chars[i].sent;
+                if (c == ' ') {
+                    printf("[%d.%03d] (space)\r\n", secs, ms);
+                } else if (sent != '\0' && sent != c) {
+                    printf("[%d.%03d] %c  (sent %c)\r\n", secs, ms, c, sent);
+                } else {
+                    printf("[%d.%03d] %c%s\r\n", secs, ms, c, (sent != '\0') ? "  (sent)" : "");
+                }
             }
         }
         return CONSOLE_OK;
@@ -976,6 +2259,10 @@ static console_error_t cmd_decoder(const console_parsed_cmd_t *cmd) {
                (unsigned long)stats.chars_decoded,
                (unsigned long)stats.words_decoded,
                (unsigned long)stats.errors);
+        printf("Glitches: %lu filtered (< %ums)\r\n",
+               (unsigned long)stats.glitches, (unsigned)CONFIG_GET_DECODE_GLITCH_MS());
+        printf("Sent text: %lu decoded differently\r\n",
+               (unsigned long)stats.sent_mismatches);
         printf("Buffer: %u/%u chars\r\n",
                (unsigned)decoder_get_buffer_count(),
                (unsigned)decoder_get_buffer_capacity());
@@ -987,6 +2274,98 @@ static console_error_t cmd_decoder(const console_parsed_cmd_t *cmd) {
     return CONSOLE_ERR_INVALID_VALUE;
 }
 
+/** Live decode echo (set by console, read by background task) */
+static atomic_bool s_decode_live = ATOMIC_VAR_INIT(false);
+
+/** Default and maximum character count for 'decode last' */
+#define DECODE_LAST_DEFAULT 40
+#define DECODE_LAST_MAX     128
+
+void console_decode_echo(char c) {
+    if (!atomic_load_explicit(&s_decode_live, memory_order_relaxed)) {
+        return;
+    }
+    console_post(&c, 1);  /* bg_task: never wait on the USB host */
+}
+
+/**
+ * @brief decode [on|off|last [n]|call [<call>|clear]] - Live CW decode on the console
+ */
+static console_error_t cmd_decode(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        bool live = atomic_load_explicit(&s_decode_live, memory_order_relaxed);
+        printf("Decode: %s", decoder_is_enabled() ? "ON" : "OFF");
+        if (live) {
+            printf(" (live)");
+        }
+        uint32_t wpm = decoder_get_wpm();
+        if (wpm > 0) {
+            printf(", WPM: %lu", (unsigned long)wpm);
+        }
+        printf("\r\n");
+        return CONSOLE_OK;
+    }
+
+    const char *arg = cmd->args[0];
+
+    if (strcmp(arg, "on") == 0) {
+        decoder_set_enabled(true);
+        atomic_store_explicit(&s_decode_live, true, memory_order_relaxed);
+        printf("Decode ON - decoded text follows ('decode off' to stop)\r\n");
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(arg, "off") == 0) {
+        /* Echo only: the WebUI still gets decoded text ('decoder off' stops it) */
+        atomic_store_explicit(&s_decode_live, false, memory_order_relaxed);
+        printf("Live decode OFF\r\n");
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(arg, "last") == 0) {
+        size_t n = DECODE_LAST_DEFAULT;
+        if (cmd->argc >= 2) {
+            char *end = NULL;
+            long v = strtol(cmd->args[1], &end, 10);
+            if (end == cmd->args[1] || *end != '\0' || v < 1) {
+                return CONSOLE_ERR_INVALID_VALUE;
+            }
+            if (v > DECODE_LAST_MAX) {
+                return CONSOLE_ERR_OUT_OF_RANGE;
+            }
+            n = (size_t)v;
+        }
+
+        static char text[DECODE_LAST_MAX + 1];
+        size_t len = decoder_get_text(text, n + 1);
+        if (len == 0) {
+            printf("(empty)\r\n");
+        } else {
+            printf("%s\r\n", text);
+        }
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(arg, "call") == 0) {
+        if (cmd->argc >= 2) {
+            const char *call = (strcmp(cmd->args[1], "clear") == 0) ? "" : cmd->args[1];
+            if (!decoder_set_last_callsign(call)) {
+                printf("Error: not a callsign\r\n");
+                return CONSOLE_ERR_INVALID_VALUE;
+            }
+        }
+        char call[CALLSIGN_MAX_LEN + 1];
+        if (decoder_get_last_callsign(call, sizeof(call)) == 0) {
+            printf("Last call: (none)\r\n");
+        } else {
+            printf("Last call: %s\r\n", call);
+        }
+        return CONSOLE_OK;
+    }
+
+    return CONSOLE_ERR_INVALID_VALUE;
+}
+
 /* ============================================================================
  * Text Keyer Commands
  * ============================================================================ */
@@ -1007,42 +2386,164 @@ static console_error_t cmd_send(const console_parsed_cmd_t *cmd) {
         if (i > 0) {
             strncat(text, " ", sizeof(text) - strlen(text) - 1);
         }
-        strncat(text, cmd->args[i], sizeof(text) - strlen(text) - 1);
+        strncat(text, cmd->args[i], sizeof(text) - strlen(text) - 1);
+    }
+
+    if (text_keyer_send(text) != 0) {
+        printf("Error: already sending or invalid text\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    printf("Sending: %s\r\n", text);
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief m1-m8 - Send memory slot
+ */
+static console_error_t cmd_memory_send(const console_parsed_cmd_t *cmd) {
+    /* Extract slot number from command name (m1 -> 0, m8 -> 7) */
+    const char *name = cmd->command;
+    if (name[0] != 'm' || name[1] < '1' || name[1] > '8') {
+        return CONSOLE_ERR_UNKNOWN_CMD;
+    }
+
+    uint8_t slot = (uint8_t)(name[1] - '1');
+
+    text_memory_slot_t mem;
+    if (text_memory_get(slot, &mem) != 0) {
+        printf("Slot %d is empty\r\n", slot + 1);
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    if (text_keyer_send(mem.text) != 0) {
+        printf("Error: already sending\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    printf("Sending M%d [%s]: %s\r\n", slot + 1, mem.label, mem.text);
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief kb - Keyboard keying mode
+ */
+static console_error_t cmd_kb(const console_parsed_cmd_t *cmd) {
+    (void)cmd;
+    if (text_keyer_get_state() != TEXT_KEYER_IDLE) {
+        printf("Error: already sending\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    printf("Keyboard mode: Backspace cancels unsent text, "
+           "Ctrl+D leaves, Ctrl+C aborts\r\n");
+    console_keyboard_begin();
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief abbrev [<key> [text]] - Keyboard mode shortcuts
+ */
+static console_error_t cmd_abbrev(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        size_t shown = 0;
+        for (size_t i = 0; i < ABBREV_COUNT; i++) {
+            const abbrev_entry_t *e = abbrev_get(i);
+            if (e != NULL) {
+                printf(";%-8s %s\r\n", e->key, e->text);
+                shown++;
+            }
+        }
+        if (shown == 0) {
+            printf("No abbreviations\r\n");
+        }
+        return CONSOLE_OK;
     }
 
-    if (text_keyer_send(text) != 0) {
-        printf("Error: already sending or invalid text\r\n");
+    const char *key = (cmd->args[0][0] == ABBREV_MARK) ? &cmd->args[0][1] : cmd->args[0];
+    const char *text = (cmd->argc >= 2) ? cmd->rest[1] : NULL;
+    if (abbrev_set(key, text) != 0) {
+        printf("Error: key is 1-%d letters/digits, text up to %d chars, at most %d entries\r\n",
+               ABBREV_KEY_MAX, ABBREV_TEXT_MAX, ABBREV_COUNT);
         return CONSOLE_ERR_INVALID_VALUE;
     }
-
-    printf("Sending: %s\r\n", text);
+    if (text == NULL) {
+        printf("Removed ;%s\r\n", key);
+    } else {
+        printf(";%s = %s\r\n", key, abbrev_lookup(key));
+    }
     return CONSOLE_OK;
 }
 
 /**
- * @brief m1-m8 - Send memory slot
+ * @brief cpo [on|off] - Code practice oscillator mode
  */
-static console_error_t cmd_memory_send(const console_parsed_cmd_t *cmd) {
-    /* Extract slot number from command name (m1 -> 0, m8 -> 7) */
-    const char *name = cmd->command;
-    if (name[0] != 'm' || name[1] < '1' || name[1] > '8') {
-        return CONSOLE_ERR_UNKNOWN_CMD;
+static console_error_t cmd_cpo(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc >= 1) {
+        if (strcmp(cmd->args[0], "on") == 0) {
+            CONFIG_SET_PRACTICE_OSC(true);
+            decoder_set_enabled(true);
+        } else if (strcmp(cmd->args[0], "off") == 0) {
+            CONFIG_SET_PRACTICE_OSC(false);
+        } else {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
     }
 
-    uint8_t slot = (uint8_t)(name[1] - '1');
-
-    text_memory_slot_t mem;
-    if (text_memory_get(slot, &mem) != 0) {
-        printf("Slot %d is empty\r\n", slot + 1);
-        return CONSOLE_ERR_INVALID_VALUE;
+    if (CONFIG_GET_PRACTICE_OSC()) {
+        printf("Practice oscillator: ON (sidetone %u Hz, decoder %s, TX/PTT/remote off)\r\n",
+               (unsigned)CONFIG_GET_SIDETONE_FREQ_HZ(), decoder_is_enabled() ? "on" : "off");
+        printf("Trainer: 'train', live decode: 'decode on'\r\n");
+    } else {
+        printf("Practice oscillator: off\r\n");
     }
+    if (cmd->argc >= 1 && CONFIG_GET_AUTOSAVE_SEC() == 0) {
+        printf("Use 'save' to keep it after a reboot\r\n");
+    }
+    return CONSOLE_OK;
+}
 
-    if (text_keyer_send(mem.text) != 0) {
-        printf("Error: already sending\r\n");
-        return CONSOLE_ERR_INVALID_VALUE;
+/**
+ * @brief radio [1|2|swap] - Select the radio keyed (SO2R)
+ */
+static console_error_t cmd_radio(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc >= 1) {
+        uint8_t radio;
+        if (strcmp(cmd->args[0], "swap") == 0) {
+            radio = (CONFIG_GET_RADIO() == 1) ? 2 : 1;
+        } else if (strcmp(cmd->args[0], "1") == 0) {
+            radio = 1;
+        } else if (strcmp(cmd->args[0], "2") == 0) {
+            radio = 2;
+        } else {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        if (radio == 2 && CONFIG_GET_GPIO_TX2() == 0) {
+            printf("Error: no radio 2 (hardware.gpio_tx2 is 0)\r\n");
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        CONFIG_SET_RADIO(radio);
     }
 
-    printf("Sending M%d [%s]: %s\r\n", slot + 1, mem.label, mem.text);
+    unsigned selected = CONFIG_GET_RADIO();
+    printf("Radio %u%s\r\n", selected,
+           (hal_gpio_get_radio() + 1U != selected) ? " (switches when PTT drops)" : "");
+    const unsigned tx[HAL_GPIO_RADIO_MAX] = { CONFIG_GET_GPIO_TX(), CONFIG_GET_GPIO_TX2() };
+    const unsigned ptt[HAL_GPIO_RADIO_MAX] = { CONFIG_GET_GPIO_PTT(), CONFIG_GET_GPIO_PTT2() };
+    for (unsigned i = 0; i < HAL_GPIO_RADIO_MAX; i++) {
+        if (i > 0 && tx[i] == 0) {
+            printf("    2  not fitted\r\n");
+            continue;
+        }
+        char ptt_pin[12] = "none";
+        if (ptt[i] != 0) {
+            snprintf(ptt_pin, sizeof(ptt_pin), "GPIO%u", ptt[i]);
+        }
+        printf("  %c %u  TX GPIO%u  PTT %s\r\n", (i + 1 == selected) ? '*' : ' ',
+               i + 1, tx[i], ptt_pin);
+    }
+    if (cmd->argc >= 1 && CONFIG_GET_AUTOSAVE_SEC() == 0) {
+        printf("Use 'save' to keep it after a reboot\r\n");
+    }
     return CONSOLE_OK;
 }
 
@@ -1052,6 +2553,7 @@ static console_error_t cmd_memory_send(const console_parsed_cmd_t *cmd) {
 static console_error_t cmd_abort(const console_parsed_cmd_t *cmd) {
     (void)cmd;
     text_keyer_abort();
+    copy_practice_stop();
     printf("Aborted\r\n");
     return CONSOLE_OK;
 }
@@ -1061,8 +2563,42 @@ static console_error_t cmd_abort(const console_parsed_cmd_t *cmd) {
  */
 static console_error_t cmd_pause(const console_parsed_cmd_t *cmd) {
     (void)cmd;
+    if (text_keyer_get_state() != TEXT_KEYER_SENDING) {
+        printf("Error: not sending\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
     text_keyer_pause();
-    printf("Paused\r\n");
+    printf("Pausing after this character\r\n");
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief nudge <+n|-n> - Change playback speed mid-message
+ */
+static console_error_t cmd_nudge(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+
+    const char *arg = cmd->args[0];
+    if (arg[0] != '+' && arg[0] != '-') {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    long steps = (arg[0] == '+') ? 1 : -1;
+    if (arg[1] != '\0') {
+        char *end;
+        long n = strtol(&arg[1], &end, 10);
+        if (*end != '\0' || n < 1 || n > 20) {
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+        steps *= n;
+    }
+
+    if (text_keyer_nudge_speed((int)steps) != 0) {
+        printf("Error: not sending\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    printf("Speed %+ld WPM from the next character\r\n", steps);
     return CONSOLE_OK;
 }
 
@@ -1076,6 +2612,104 @@ static console_error_t cmd_resume(const console_parsed_cmd_t *cmd) {
     return CONSOLE_OK;
 }
 
+/**
+ * @brief Parse a 1-based message slot number
+ * @return 0-based slot, or -1 if invalid
+ */
+static int parse_msg_slot(const char *arg) {
+    char *end = NULL;
+    long n = strtol(arg, &end, 10);
+    if (end == arg || *end != '\0' || n < 1 || n > TEXT_MEMORY_SLOTS) {
+        return -1;
+    }
+    return (int)(n - 1);
+}
+
+/**
+ * @brief msg list|set|play [repeat]|stop - Message memory keyer
+ */
+static console_error_t cmd_msg(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0 || strcmp(cmd->args[0], "list") == 0) {
+        for (uint8_t i = 0; i < TEXT_MEMORY_SLOTS; i++) {
+            text_memory_slot_t slot;
+            if (text_memory_get(i, &slot) == 0) {
+                printf("%d: %s\r\n", i + 1, slot.text);
+            } else {
+                printf("%d: (empty)\r\n", i + 1);
+            }
+        }
+        text_keyer_state_t state = text_keyer_get_state();
+        if (state != TEXT_KEYER_IDLE) {
+            size_t sent = 0;
+            size_t total = 0;
+            text_keyer_get_progress(&sent, &total);
+            printf("%s %u/%u\r\n", state == TEXT_KEYER_PAUSED ? "Paused" : "Playing",
+                   (unsigned)sent, (unsigned)total);
+        }
+        return CONSOLE_OK;
+    }
+
+    const char *sub = cmd->args[0];
+
+    if (strcmp(sub, "stop") == 0) {
+        text_keyer_abort();
+        printf("Stopped\r\n");
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "set") != 0 && strcmp(sub, "play") != 0) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    if (cmd->argc < 2) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+    int slot = parse_msg_slot(cmd->args[1]);
+    if (slot < 0) {
+        printf("Error: message must be 1-%d\r\n", TEXT_MEMORY_SLOTS);
+        return CONSOLE_ERR_OUT_OF_RANGE;
+    }
+
+    if (strcmp(sub, "set") == 0) {
+        /* Free text: everything after the slot number */
+        const char *text = (cmd->argc >= 3) ? cmd->rest[2] : NULL;
+        if (text_memory_set((uint8_t)slot, text, NULL) != 0) {
+            return CONSOLE_ERR_NVS_ERROR;
+        }
+        if (text == NULL) {
+            printf("Message %d cleared\r\n", slot + 1);
+        } else {
+            printf("Message %d saved\r\n", slot + 1);
+        }
+        return CONSOLE_OK;
+    }
+
+    /* play [repeat] */
+    bool repeat = false;
+    if (cmd->argc >= 3) {
+        if (strcmp(cmd->args[2], "repeat") != 0) {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        repeat = true;
+    }
+    text_memory_slot_t mem;
+    if (text_memory_get((uint8_t)slot, &mem) != 0) {
+        printf("Message %d is empty\r\n", slot + 1);
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    if (text_keyer_send(mem.text) != 0) {
+        printf("Error: already sending (use 'msg stop')\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+    if (repeat) {
+        text_keyer_repeat();
+        printf("Repeating %d every %us: %s\r\n", slot + 1,
+               (unsigned)CONFIG_GET_MSG_REPEAT_S(), mem.text);
+    } else {
+        printf("Playing %d: %s\r\n", slot + 1, mem.text);
+    }
+    return CONSOLE_OK;
+}
+
 /**
  * @brief mem [slot] [text|clear|label <label>] - Memory slot management
  */
@@ -1144,6 +2778,211 @@ static console_error_t cmd_mem(const console_parsed_cmd_t *cmd) {
     return CONSOLE_OK;
 }
 
+/**
+ * @brief serial [<n>|reset] - Contest serial number for %N
+ */
+static console_error_t cmd_serial(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc > 0) {
+        unsigned long serial = CONTEST_SERIAL_FIRST;
+        if (strcmp(cmd->args[0], "reset") != 0) {
+            char *end;
+            serial = strtoul(cmd->args[0], &end, 10);
+            if (*end != '\0' || serial < CONTEST_SERIAL_FIRST || serial > CONTEST_SERIAL_MAX) {
+                printf("Error: serial must be %d-%d\r\n", CONTEST_SERIAL_FIRST, CONTEST_SERIAL_MAX);
+                return CONSOLE_ERR_OUT_OF_RANGE;
+            }
+        }
+        if (contest_serial_set((uint32_t)serial) != 0) {
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+    }
+    printf("Next serial: %03lu\r\n", (unsigned long)contest_serial_get());
+    return CONSOLE_OK;
+}
+
+/** Last practice text, revealed by `train show` */
+static char s_train_text[TEXT_KEYER_MAX_LEN];
+
+/**
+ * @brief Parse "<wpm>" or "<wpm>/<effective wpm>"
+ */
+static bool parse_train_speed(const char *arg, uint32_t *wpm, uint32_t *eff) {
+    char *end;
+    unsigned long c = strtoul(arg, &end, 10);
+    unsigned long s = 0;
+    if (*end == '/') {
+        s = strtoul(end + 1, &end, 10);
+        if (s < 5 || s > c) {
+            return false;
+        }
+    }
+    if (*end != '\0' || c < 5 || c > 60) {
+        return false;
+    }
+    *wpm = (uint32_t)c;
+    *eff = (uint32_t)s;
+    return true;
+}
+
+/**
+ * @brief Parse the mode, item count and speed shared by all trainer runs
+ *
+ * @param mode_arg "groups", "calls" or "words"
+ * @param count_arg Items (NULL = 10)
+ * @param speed_arg "<wpm>" or "<wpm>/<eff>" (NULL = keyer.wpm)
+ */
+static console_error_t parse_train_args(const char *mode_arg, const char *count_arg,
+                                        const char *speed_arg, trainer_mode_t *mode,
+                                        unsigned long *count, uint32_t *wpm, uint32_t *eff) {
+    if (mode_arg == NULL) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+    if (trainer_mode_from_str(mode_arg, mode) != 0) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    *count = 10;
+    if (count_arg != NULL) {
+        char *end;
+        *count = strtoul(count_arg, &end, 10);
+        if (*end != '\0' || *count < 1 || *count > 50) {
+            printf("Error: count must be 1-50\r\n");
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+    }
+
+    *wpm = 0;  /* keyer.wpm */
+    *eff = 0;
+    if (speed_arg != NULL && !parse_train_speed(speed_arg, wpm, eff)) {
+        printf("Error: speed is <wpm> or <wpm>/<eff>, 5-60, eff <= wpm\r\n");
+        return CONSOLE_ERR_OUT_OF_RANGE;
+    }
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief Print a copy practice result line (called from bg_task)
+ *
+ * Posted like the live decode echo: bg_task must not wait on the console.
+ */
+static void train_echo_report(const char *line) {
+    char buf[CONSOLE_PRINTF_MAX];
+    int len = snprintf(buf, sizeof(buf), "%s\r\n", line);
+    if (len > 0) {
+        console_post(buf, ((size_t)len < sizeof(buf)) ? (size_t)len : sizeof(buf) - 1);
+    }
+}
+
+/**
+ * @brief train echo <mode> [count] [wpm[/eff]] - Copy practice session
+ *
+ * Four words do not fit CONSOLE_MAX_ARGS, so count and speed are split
+ * from the free text after the mode.
+ */
+static console_error_t cmd_train_echo(const console_parsed_cmd_t *cmd) {
+    char opts[32] = "";
+    const char *count_arg = NULL;
+    const char *speed_arg = NULL;
+    if (cmd->argc > 2) {
+        strncpy(opts, cmd->rest[2], sizeof(opts) - 1);
+        count_arg = opts;
+        char *sep = strchr(opts, ' ');
+        if (sep != NULL) {
+            *sep++ = '\0';
+            while (*sep == ' ') {
+                sep++;
+            }
+            speed_arg = sep;
+        }
+    }
+
+    trainer_mode_t mode;
+    unsigned long count;
+    uint32_t wpm;
+    uint32_t eff;
+    console_error_t err = parse_train_args((cmd->argc > 1) ? cmd->args[1] : NULL, count_arg,
+                                           speed_arg, &mode, &count, &wpm, &eff);
+    if (err != CONSOLE_OK) {
+        return err;
+    }
+
+    copy_practice_config_t session = {
+        .mode = mode,
+        .items = (uint32_t)count,
+        .char_wpm = wpm,
+        .effective_wpm = eff,
+        .seed = (uint32_t)esp_timer_get_time() | 1u,
+        .report = train_echo_report,
+    };
+    if (copy_practice_start(&session) != 0) {
+        printf("Error: already sending\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    printf("Copy practice: %lu %s, key each one back on the paddle (TX off).\r\n"
+           "'train stop' ends the session\r\n", count, cmd->args[1]);
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief train <groups|calls|words> [count] [wpm[/eff]] | train echo ... |
+ *        train show | train score | train stop - Morse trainer
+ */
+static console_error_t cmd_train(const console_parsed_cmd_t *cmd) {
+    if (cmd->argc == 0) {
+        return CONSOLE_ERR_MISSING_ARG;
+    }
+
+    if (strcmp(cmd->args[0], "show") == 0) {
+        printf("%s\r\n", (s_train_text[0] != '\0') ? s_train_text : "(nothing sent yet)");
+        return CONSOLE_OK;
+    }
+    if (strcmp(cmd->args[0], "echo") == 0) {
+        return cmd_train_echo(cmd);
+    }
+    if (strcmp(cmd->args[0], "stop") == 0) {
+        copy_practice_stop();
+        text_keyer_abort();
+        return CONSOLE_OK;
+    }
+    if (strcmp(cmd->args[0], "score") == 0) {
+        trainer_score_t score = copy_practice_get_score();
+        if (score.items == 0) {
+            printf("No copy practice scored yet\r\n");
+            return CONSOLE_OK;
+        }
+        printf("%s: %lu/%lu items perfect, %lu/%lu chars (%lu%%)\r\n",
+               copy_practice_is_active() ? "Session" : "Last session",
+               (unsigned long)score.perfect, (unsigned long)score.items,
+               (unsigned long)(score.chars - score.errors), (unsigned long)score.chars,
+               (unsigned long)trainer_score_percent(&score));
+        return CONSOLE_OK;
+    }
+
+    trainer_mode_t mode;
+    unsigned long count;
+    uint32_t wpm;
+    uint32_t eff;
+    console_error_t err = parse_train_args(cmd->args[0], (cmd->argc > 1) ? cmd->args[1] : NULL,
+                                           (cmd->argc > 2) ? cmd->args[2] : NULL,
+                                           &mode, &count, &wpm, &eff);
+    if (err != CONSOLE_OK) {
+        return err;
+    }
+
+    uint32_t seed = (uint32_t)esp_timer_get_time() | 1u;
+    size_t made = trainer_generate(mode, &seed, (size_t)count, s_train_text, sizeof(s_train_text));
+    if (copy_practice_is_active() || text_keyer_send_practice(s_train_text, wpm, eff) != 0) {
+        s_train_text[0] = '\0';
+        printf("Error: already sending\r\n");
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    printf("Sending %u %s (sidetone only, TX off). 'train show' reveals the text\r\n",
+           (unsigned)made, cmd->args[0]);
+    return CONSOLE_OK;
+}
+
 /**
  * @brief vpn - WireGuard VPN control
  */
@@ -1155,9 +2994,9 @@ static console_error_t cmd_vpn(const console_parsed_cmd_t *cmd) {
 
         if (state == VPN_STATE_CONNECTED) {
             printf("  Endpoint: %s:%u\r\n",
-                   g_config.vpn.server_endpoint,
+                   CONFIG_GET_SERVER_ENDPOINT(),
                    (unsigned)atomic_load_explicit(&g_config.vpn.server_port, memory_order_relaxed));
-            printf("  Address:  %s\r\n", g_config.vpn.client_address);
+            printf("  Address:  %s\r\n", CONFIG_GET_CLIENT_ADDRESS());
 
             vpn_stats_t stats;
             if (vpn_get_stats(&stats)) {
@@ -1199,9 +3038,9 @@ static console_error_t cmd_vpn(const console_parsed_cmd_t *cmd) {
         printf("  Enabled:   %s\r\n",
                atomic_load_explicit(&g_config.vpn.enabled, memory_order_relaxed) ? "yes" : "no");
         printf("  Endpoint:  %s:%u\r\n",
-               g_config.vpn.server_endpoint,
+               CONFIG_GET_SERVER_ENDPOINT(),
                (unsigned)atomic_load_explicit(&g_config.vpn.server_port, memory_order_relaxed));
-        printf("  Address:   %s\r\n", g_config.vpn.client_address);
+        printf("  Address:   %s\r\n", CONFIG_GET_CLIENT_ADDRESS());
         printf("  Keepalive: %u s\r\n",
                (unsigned)atomic_load_explicit(&g_config.vpn.persistent_keepalive, memory_order_relaxed));
 
@@ -1219,31 +3058,163 @@ static console_error_t cmd_vpn(const console_parsed_cmd_t *cmd) {
     return CONSOLE_ERR_INVALID_VALUE;
 }
 
+/**
+ * @brief net [status|peer <host:port>|lock [on|off]|ping] - Remote operation
+ */
+static console_error_t cmd_net(const console_parsed_cmd_t *cmd) {
+#ifdef ESP_PLATFORM
+    const char *sub = (cmd->argc > 0) ? cmd->args[0] : "status";
+
+    if (strcmp(sub, "status") == 0) {
+        wifi_state_t ws = wifi_get_state();
+        printf("WiFi:  %s", wifi_state_name(ws));
+        char ip_buf[16];
+        if ((ws == WIFI_STATE_CONNECTED || ws == WIFI_STATE_AP_MODE) &&
+            wifi_get_ip(ip_buf, sizeof(ip_buf))) {
+            printf(" (%s)", ip_buf);
+        }
+        printf("\r\n");
+        printf("VPN:   %s\r\n", vpn_state_str(vpn_get_state()));
+
+        cwnet_socket_state_t cs = cwnet_socket_get_state();
+        printf("CWNet: %s", cwnet_socket_state_str(cs));
+        if (CONFIG_GET_SERVER_HOST()[0] != '\0') {
+            printf(", peer %s:%u", CONFIG_GET_SERVER_HOST(),
+                   (unsigned)g_config.remote.server_port);
+        }
+        printf("\r\n");
+        printf("Lock:  %s\r\n", cwnet_socket_get_tx_lock() ? "on (keying not forwarded)" : "off");
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "peer") == 0) {
+        if (cmd->argc < 2) {
+            return CONSOLE_ERR_MISSING_ARG;
+        }
+
+        /* host[:port] */
+        char host[65];
+        const char *arg = cmd->args[1];
+        const char *colon = strrchr(arg, ':');
+        size_t host_len = (colon != NULL) ? (size_t)(colon - arg) : strlen(arg);
+        if (host_len == 0 || host_len >= sizeof(host)) {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+        memcpy(host, arg, host_len);
+        host[host_len] = '\0';
+
+        /* Check the port before touching either value */
+        if (colon != NULL) {
+            const param_descriptor_t *p = config_find_param("remote.server_port");
+            char *end;
+            unsigned long port = strtoul(colon + 1, &end, 10);
+            if (colon[1] == '\0' || *end != '\0') {
+                printf("Invalid port: %s\r\n", colon + 1);
+                return CONSOLE_ERR_INVALID_VALUE;
+            }
+            if (p == NULL || port < p->min || port > p->max) {
+                printf("Invalid port: %s\r\n", colon + 1);
+                return CONSOLE_ERR_OUT_OF_RANGE;
+            }
+        }
+
+        /* CWNet sees host and port change together */
+        config_batch_begin(&g_config);
+        int ret = audited_set("remote.server_host", host);
+        if (ret == 0 && colon != NULL) {
+            ret = audited_set("remote.server_port", colon + 1);
+        }
+        config_batch_commit(&g_config);
+        if (ret != 0) {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+
+        printf("Peer: %s:%u\r\n", CONFIG_GET_SERVER_HOST(),
+               (unsigned)g_config.remote.server_port);
+        if (!g_config.remote.cwnet_enabled) {
+            printf("CWNet disabled (set remote.cwnet_enabled true)\r\n");
+        } else {
+            cwnet_socket_request_restart();
+            printf("Reconnecting...\r\n");
+        }
+        printf("Use 'save' to persist\r\n");
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "lock") == 0) {
+        bool lock = true;
+        if (cmd->argc >= 2) {
+            if (strcmp(cmd->args[1], "on") == 0) {
+                lock = true;
+            } else if (strcmp(cmd->args[1], "off") == 0) {
+                lock = false;
+            } else {
+                return CONSOLE_ERR_INVALID_VALUE;
+            }
+        }
+        cwnet_socket_set_tx_lock(lock);
+        printf("Net lock %s\r\n", lock ? "ON - local keying not forwarded" : "OFF");
+        return CONSOLE_OK;
+    }
+
+    if (strcmp(sub, "ping") == 0) {
+        if (!cwnet_socket_is_ready()) {
+            printf("CWNet not connected (%s)\r\n",
+                   cwnet_socket_state_str(cwnet_socket_get_state()));
+            return CONSOLE_OK;
+        }
+        int32_t latency = cwnet_socket_get_latency_ms();
+        if (latency < 0) {
+            printf("No RTT measured yet (server pings periodically)\r\n");
+        } else {
+            printf("RTT: %ld ms\r\n", (long)latency);
+        }
+        return CONSOLE_OK;
+    }
+
+    return CONSOLE_ERR_INVALID_VALUE;
+#else
+    (void)cmd;
+    printf("net not available on host\r\n");
+    return CONSOLE_OK;
+#endif
+}
+
 /* ============================================================================
  * Command registry
  * ============================================================================ */
 
 /* Usage strings for commands with non-trivial syntax */
 static const char USAGE_LOG[] =
-    "  log                 Show current level\r\n"
-    "  log level * LEVEL   Set all tags (ERROR/WARN/INFO/DEBUG/TRACE)\r\n"
-    "  log level TAG LEVEL Set specific tag\r\n"
+    "  log                 Show current levels per tag and the log sinks\r\n"
+    "  log level * LEVEL   Set all tags (error/warn/info/debug/trace)\r\n"
+    "  log level TAG LEVEL Set one tag (main rt bg wifi vpn cwnet power console)\r\n"
     "  log *=L             Compact: set all (E/W/I/D/T)\r\n"
-    "  log TAG=L           Compact: set tag";
+    "  log TAG=L           Compact: set tag\r\n"
+    "  log crash           Post-mortem of the last panic or critical fault\r\n"
+    "  log crash clear     Erase the post-mortem";
 
 static const char USAGE_STATS[] =
-    "  stats               Overview (uptime, heap, stream)\r\n"
-    "  stats heap          Heap memory details\r\n"
-    "  stats tasks         Task list by core\r\n"
-    "  stats stream        Stream buffer status\r\n"
-    "  stats rt            RT task statistics";
+    "  stats               Overview (uptime, heap, CPU load, stream, supply, temperature)\r\n"
+    "  stats heap          Free, minimum and largest block: internal, DMA, PSRAM\r\n"
+    "  stats tasks         Tasks by core with stack headroom, then heap\r\n"
+    "  stats stream        Lag (max, mean) and drops per stream consumer\r\n"
+    "  stats stream reset  Clear consumer lag statistics\r\n"
+    "  stats rt            RT loop period, jitter histogram, worst-case timings\r\n"
+    "  stats rt reset      Clear RT statistics\r\n"
+    "  stats audio         Codec output routing (speaker/headphones)";
 
 static const char USAGE_SHOW[] =
-    "  show                  All parameters\r\n"
+    "  show                  All parameters (* = not saved to NVS)\r\n"
     "  show keyer.*          All keyer parameters\r\n"
     "  show keyer.**         Keyer + subfamilies\r\n"
     "  show keyer.wpm        Single parameter\r\n"
     "  show hw.*             Alias for hardware.*\r\n"
+    "  show *.gpio_*         Wildcard within a path segment\r\n"
+    "  show @audio           Whole family (name or alias)\r\n"
+    "  show keyer.w          Prefix match (path or name)\r\n"
+    "  show changed          Params differing from default (* = unsaved)\r\n"
+    "  show changed keyer.*  Same, filtered by pattern\r\n"
     "\r\n"
     "Families:\r\n"
     "  keyer (k)      Keying behavior\r\n"
@@ -1258,7 +3229,14 @@ static const char USAGE_SET[] =
     "Examples:\r\n"
     "  set keyer.wpm 25\r\n"
     "  set audio.sidetone_freq_hz 700\r\n"
-    "  set wpm 25              (legacy shorthand)";
+    "  set wpm 25              (legacy shorthand)\r\n"
+    "  set keyer.wpm --default Reset one parameter\r\n"
+    "  set audio.* --default   Reset a group (show patterns)\r\n"
+    "\r\n"
+    "Batch (applied together, all or nothing):\r\n"
+    "  set begin               Stage following sets\r\n"
+    "  set commit              Apply staged values at once\r\n"
+    "  set abort               Drop staged values";
 
 static const char USAGE_DEBUG[] =
     "  debug               Show RT log status\r\n"
@@ -1274,7 +3252,7 @@ static const char USAGE_DIAG[] =
 static const char USAGE_DECODER[] =
     "  decoder             Show status and last decoded text\r\n"
     "  decoder on|off      Enable/disable decoder\r\n"
-    "  decoder text        Show buffer with timestamps\r\n"
+    "  decoder text        Show buffer with timestamps (sent text marked)\r\n"
     "  decoder stats       Show timing statistics\r\n"
     "  decoder clear       Clear buffer and reset timing";
 
@@ -1282,18 +3260,152 @@ static const char USAGE_SEND[] =
     "  send <text>         Send text as CW\r\n"
     "\r\n"
     "Supports A-Z, 0-9, punctuation, spaces, and prosigns.\r\n"
-    "Prosigns: <SK>, <AR>, <BT>, <KN>, <AS>, <SN>, <KA>\r\n"
+    "Prosigns: <AR>, <SK>, <BK>, <KN>, <BT>, <AS>, <SN>, <KA>,\r\n"
+    "or any 2-4 characters in <> sent run together\r\n"
+    "Commands: |S30| speed (WPM), |P2| pause (s), |R| repeat from start,\r\n"
+    "|M3| continue with memory 3 (at the end; 8 jumps at most)\r\n"
+    "+ / - 1 WPM faster/slower; send <AR> for '+', <DU> for '-'\r\n"
+    "%N contest serial, %C last decoded callsign\r\n"
     "\r\n"
     "Examples:\r\n"
     "  send CQ CQ DE IU3QEZ K\r\n"
-    "  send 73 <SK>";
+    "  send %C 5NN %N\r\n"
+    "  send 73 <SK>\r\n"
+    "  send |S35|CQ TEST IU3QEZ|P3||R|";
+
+static const char USAGE_NUDGE[] =
+    "  nudge +            1 WPM faster from the next character\r\n"
+    "  nudge -3           3 WPM slower\r\n"
+    "\r\n"
+    "Only for the message playing (keyer.wpm is unchanged).\r\n"
+    "In messages, + and - do the same ('send ++5NN-- TU').";
+
+static const char USAGE_KB[] =
+    "  kb                  Key characters as they are typed\r\n"
+    "\r\n"
+    "Pending text is underlined until it has been keyed.\r\n"
+    "Backspace    Cancel the last unsent character\r\n"
+    "Enter        Word space\r\n"
+    "Ctrl+D       Leave (pending text is still sent)\r\n"
+    "Ctrl+C       Leave and abort\r\n"
+    "Prosigns: type <AR>, <BK>...; sent once '>' is typed.\r\n"
+    "Shortcuts: ;TU then space sends its abbreviation (see 'abbrev').\r\n"
+    "|S|P|R| commands need 'send'.";
 
 static const char USAGE_MEM[] =
     "  mem                 List all slots\r\n"
     "  mem <slot>          Show slot (1-8)\r\n"
     "  mem <slot> <text>   Save text to slot\r\n"
     "  mem <slot> clear    Clear slot\r\n"
-    "  mem <slot> label X  Set slot label";
+    "  mem <slot> label X  Set slot label\r\n"
+    "\r\n"
+    "%N sends the contest serial (see 'help serial'),\r\n"
+    "%C the last decoded callsign (see 'help decode')";
+
+static const char USAGE_TRAIN[] =
+    "  train groups [n] [wpm]  Random 5-character groups\r\n"
+    "  train calls [n] [wpm]   Callsign-like strings\r\n"
+    "  train words [n] [wpm]   Common words and abbreviations\r\n"
+    "  train show              Show the last practice text\r\n"
+    "  train echo <kind> [n] [wpm]\r\n"
+    "                          Copy practice: key each item back,\r\n"
+    "                          scored per item and per session\r\n"
+    "  train score             Score of the current/last session\r\n"
+    "  train stop              End a copy practice session\r\n"
+    "\r\n"
+    "n = items (1-50, default 10), wpm = 25 or 25/15 (Farnsworth).\r\n"
+    "Sidetone only: TX is off until done or 'abort'.";
+
+static const char USAGE_SERIAL[] =
+    "  serial              Show next serial number\r\n"
+    "  serial <n>          Set next serial (1-9999)\r\n"
+    "  serial reset        Restart at 001\r\n"
+    "\r\n"
+    "In messages: %N sends and advances, %N- repeats the\r\n"
+    "last one, %N+ skips ahead first. Kept across reboots.";
+
+static const char USAGE_LOCK[] =
+    "  lock                Lock console now\r\n"
+    "  lock set <pass>     Set passphrase (4-32 chars)\r\n"
+    "  lock clear          Remove passphrase\r\n"
+    "  lock status         Show lock settings\r\n"
+    "\r\n"
+    "Auto-lock after idle: set system.lock_timeout <min>";
+
+static const char USAGE_DECODE[] =
+    "  decode              Show decode status\r\n"
+    "  decode on           Enable decoder, print text live\r\n"
+    "  decode off          Stop live output (decoder keeps running)\r\n"
+    "  decode last [n]     Last n decoded chars (default 40, max 128)\r\n"
+    "  decode call         Last decoded callsign (sent by %C)\r\n"
+    "  decode call <call>  Correct it by hand\r\n"
+    "  decode call clear   Forget it";
+
+static const char USAGE_CPO[] =
+    "  cpo                 Show practice oscillator mode\r\n"
+    "  cpo on              Sidetone + decoder only: TX, PTT and remote off\r\n"
+    "  cpo off             Back to normal keying\r\n"
+    "Same as system.practice_osc; safe with no radio connected.";
+
+static const char USAGE_RADIO[] =
+    "  radio               Show the selected radio and its outputs\r\n"
+    "  radio 1|2           Key radio 1 or 2\r\n"
+    "  radio swap          Switch to the other radio\r\n"
+    "Same as keyer.radio; takes effect once PTT drops.";
+
+static const char USAGE_ABBREV[] =
+    "  abbrev              List keyboard mode shortcuts\r\n"
+    "  abbrev <key> <text> Define ;key (saved to NVS), e.g. abbrev tu TU 73 EE\r\n"
+    "  abbrev <key>        Remove ;key";
+
+static const char USAGE_MSG[] =
+    "  msg list            List messages\r\n"
+    "  msg set <n> <text>  Store message n (1-8), saved to NVS\r\n"
+    "  msg set <n>         Clear message n\r\n"
+    "  msg play <n>        Send message n as CW\r\n"
+    "  msg play <n> repeat Call it every keyer.msg_repeat_s until keying is heard\r\n"
+    "  msg stop            Stop sending";
+
+static const char USAGE_PROFILE[] =
+    "  profile [list]      List profiles (* = active)\r\n"
+    "  profile save <name> Save running config as profile\r\n"
+    "  profile load <name> Switch to profile (also saved as boot config)\r\n"
+    "  profile delete <name> Remove profile";
+
+static const char USAGE_CONFIG[] =
+    "  config history      Recent parameter changes (age, source, old -> new)";
+
+static const char USAGE_BOARD[] =
+    "  board               List board variants (* = active)\r\n"
+    "  board <name>        Use board from next boot (pin and codec defaults)";
+
+static const char USAGE_CODEC[] =
+    "  codec dump          Print every ES8311 register (hex)\r\n"
+    "  codec reg <addr>    Read one register (decimal or 0x hex)\r\n"
+    "  codec reg <addr> <value> Write one register and read it back\r\n"
+    "Writes are not saved and bypass the codec driver.";
+
+static const char USAGE_FS[] =
+    "  fs                  SD card space and recording state\r\n"
+    "  fs ls               List files (size in bytes)\r\n"
+    "  fs rm <name>        Delete a file (not one being recorded)\r\n"
+    "Recordings: system.sd_record, sd_transcript, sd_log";
+
+static const char USAGE_PRESET[] =
+    "  preset [list]       List iambic presets (* = active)\r\n"
+    "  preset use <n>      Activate preset 0-9 and apply it to the config\r\n"
+    "  preset name <n> <text> Rename preset (empty clears)\r\n"
+    "  preset tone <n> <hz|off> Sidetone override (400-800)\r\n"
+    "  preset ptt <n> <ms|off>  PTT tail override (50-500)\r\n"
+    "  preset export <n>   Print a share code for preset n\r\n"
+    "  preset import <n> <code> Load a share code into slot n\r\n"
+    "  preset save         Store presets in NVS";
+
+static const char USAGE_NET[] =
+    "  net [status]        WiFi, VPN and CWNet state\r\n"
+    "  net peer <host:port> Set CWNet server and reconnect\r\n"
+    "  net lock [on|off]   Stop forwarding local keying to peer\r\n"
+    "  net ping            Last measured round-trip time";
 
 static const char USAGE_VPN[] =
     "  vpn                 Show VPN status\r\n"
@@ -1315,16 +3427,26 @@ static const console_cmd_t s_commands[] = {
     { "show",          "Show parameters",              USAGE_SHOW,  cmd_show },
     { "set",           "Set parameter value",          USAGE_SET,   cmd_set },
     { "save",          "Persist to NVS",               NULL,        cmd_save },
+    { "config",        "Configuration change history", USAGE_CONFIG, cmd_config },
+    { "profile",       "Full configuration profiles",  USAGE_PROFILE, cmd_profile },
+    { "preset",        "Iambic presets",               USAGE_PRESET, cmd_preset },
+    { "board",         "Hardware board variant",       USAGE_BOARD, cmd_board },
     { "reboot",        "Restart system",               NULL,        cmd_reboot },
-    { "log",           "Set log level",                USAGE_LOG,   cmd_log },
+    { "log",           "Log levels, crash log",        USAGE_LOG,   cmd_log },
     { "debug",         "Set ESP-IDF log levels",       USAGE_DEBUG, cmd_debug },
     { "uf2",           "Enter UF2 bootloader",         NULL,        cmd_uf2 },
     { "flash",         "Enter bootloader mode",        NULL,        cmd_uf2 },
     { "factory-reset", "Erase NVS and reboot",         NULL,        cmd_factory_reset },
+    { "lock",          "Lock console with passphrase", USAGE_LOCK,  cmd_lock },
+    { "unlock",        "Unlock console",               NULL,        cmd_unlock },
     { "diag",          "RT diagnostic logging",        USAGE_DIAG,  cmd_diag },
     { "decoder",       "CW decoder control",           USAGE_DECODER, cmd_decoder },
+    { "decode",        "Live CW decode",               USAGE_DECODE, cmd_decode },
     { "test",          "Diagnostic tests",             NULL,        cmd_test },
     { "gpio",          "Read raw GPIO state",          NULL,        cmd_gpio },
+    { "selftest",      "Run production self-test",     NULL,        cmd_selftest },
+    { "codec",         "ES8311 register access",       USAGE_CODEC, cmd_codec },
+    { "fs",            "SD card files and recordings", USAGE_FS,    cmd_fs },
     { "send",          "Send text as CW",              USAGE_SEND,  cmd_send },
     { "m1",            "Send memory slot 1",           NULL,        cmd_memory_send },
     { "m2",            "Send memory slot 2",           NULL,        cmd_memory_send },
@@ -1334,11 +3456,20 @@ static const console_cmd_t s_commands[] = {
     { "m6",            "Send memory slot 6",           NULL,        cmd_memory_send },
     { "m7",            "Send memory slot 7",           NULL,        cmd_memory_send },
     { "m8",            "Send memory slot 8",           NULL,        cmd_memory_send },
+    { "kb",            "Keyboard keying mode",         USAGE_KB,    cmd_kb },
+    { "abbrev",        "Keyboard mode shortcuts",      USAGE_ABBREV, cmd_abbrev },
     { "abort",         "Abort CW transmission",        NULL,        cmd_abort },
     { "pause",         "Pause CW transmission",        NULL,        cmd_pause },
     { "resume",        "Resume CW transmission",       NULL,        cmd_resume },
+    { "nudge",         "Change speed while sending",   USAGE_NUDGE, cmd_nudge },
     { "mem",           "Memory slot management",       USAGE_MEM,   cmd_mem },
+    { "serial",        "Contest serial number",        USAGE_SERIAL, cmd_serial },
+    { "train",         "Morse trainer (no TX)",        USAGE_TRAIN, cmd_train },
+    { "cpo",           "Practice oscillator mode",     USAGE_CPO,   cmd_cpo },
+    { "radio",         "Select radio (SO2R)",          USAGE_RADIO, cmd_radio },
+    { "msg",           "Message memory keyer",         USAGE_MSG,   cmd_msg },
     { "vpn",           "WireGuard VPN control",        USAGE_VPN,   cmd_vpn },
+    { "net",           "Network / remote operation",   USAGE_NET,   cmd_net },
 };
 
 #define NUM_COMMANDS (sizeof(s_commands) / sizeof(s_commands[0]))
@@ -1367,6 +3498,10 @@ console_error_t console_execute(const console_parsed_cmd_t *cmd) {
         return CONSOLE_OK; /* Empty line, do nothing */
     }
 
+    if (!console_lock_allows(cmd->command)) {
+        return CONSOLE_ERR_LOCKED;
+    }
+
     const console_cmd_t *c = console_find_command(cmd->command);
     if (c == NULL) {
         return CONSOLE_ERR_UNKNOWN_CMD;
@@ -6,6 +6,7 @@
  */
 
 #include "console.h"
+#include "console_output.h"
 #include "config_console.h"
 #include "log_tags.h"
 #include <stdio.h>
@@ -13,10 +14,9 @@
 #include <stddef.h>
 
 #ifdef ESP_PLATFORM
-#include "usb_console.h"
-/* Use USB console printf for console output (skip for IDE analyzers) */
+/* Route output through the console fan-out writer (skip for IDE analyzers) */
 #if !defined(__INTELLISENSE__) && !defined(__clang_analyzer__) && !defined(__clangd__)
-#define printf usb_console_printf
+#define printf console_printf
 #endif
 #endif
 
@@ -0,0 +1,311 @@
+/**
+ * @file config_profile.c
+ * @brief Configuration profiles implementation (NVS persistence)
+ */
+
+#include "config_profile.h"
+#include <stdio.h>
+#include <string.h>
+
+#ifdef ESP_PLATFORM
+#include "config.h"
+#include "config_nvs.h"
+#include "nvs.h"
+#include "esp_log.h"
+static const char *TAG = "profile";
+#define NVS_NAMESPACE   "profiles"
+#define NVS_KEY_NAMES   "names"
+#define NVS_KEY_ACTIVE  "active"
+#endif
+
+/** NVS namespace prefix of a profile's parameter store */
+#define PROFILE_NS_PREFIX "prof_"
+
+/** Comma-joined profile list (registry value in NVS) */
+#define NAMES_BUF_LEN (CONFIG_PROFILE_MAX * (CONFIG_PROFILE_NAME_MAX + 1))
+
+/* ============================================================================
+ * Module State
+ * ============================================================================ */
+
+static char s_names[CONFIG_PROFILE_MAX][CONFIG_PROFILE_NAME_MAX + 1];
+static size_t s_count = 0;
+static char s_active[CONFIG_PROFILE_NAME_MAX + 1] = "";
+
+/* ============================================================================
+ * Registry Helpers
+ * ============================================================================ */
+
+static int find_profile(const char *name) {
+    for (size_t i = 0; i < s_count; i++) {
+        if (strcmp(s_names[i], name) == 0) {
+            return (int)i;
+        }
+    }
+    return -1;
+}
+
+/* ============================================================================
+ * NVS Helpers
+ * ============================================================================ */
+
+#ifdef ESP_PLATFORM
+static void names_from_list(const char *list) {
+    s_count = 0;
+    const char *p = list;
+    while (*p != '\0' && s_count < CONFIG_PROFILE_MAX) {
+        const char *comma = strchr(p, ',');
+        size_t len = (comma != NULL) ? (size_t)(comma - p) : strlen(p);
+        if (len > 0 && len <= CONFIG_PROFILE_NAME_MAX) {
+            memcpy(s_names[s_count], p, len);
+            s_names[s_count][len] = '\0';
+            if (config_profile_name_valid(s_names[s_count])) {
+                s_count++;
+            }
+        }
+        if (comma == NULL) {
+            break;
+        }
+        p = comma + 1;
+    }
+}
+
+static void names_to_list(char *buf, size_t len) {
+    buf[0] = '\0';
+    size_t pos = 0;
+    for (size_t i = 0; i < s_count; i++) {
+        int n = snprintf(buf + pos, len - pos, "%s%s", (i > 0) ? "," : "", s_names[i]);
+        if (n < 0 || (size_t)n >= len - pos) {
+            break;
+        }
+        pos += (size_t)n;
+    }
+}
+
+static void profile_ns(const char *name, char *buf, size_t len) {
+    snprintf(buf, len, PROFILE_NS_PREFIX "%s", name);
+}
+
+static void load_registry(void) {
+    nvs_handle_t handle;
+    if (nvs_open(NVS_NAMESPACE, NVS_READONLY, &handle) != ESP_OK) {
+        return; /* No profiles saved yet */
+    }
+    char list[NAMES_BUF_LEN];
+    size_t len = sizeof(list);
+    if (nvs_get_str(handle, NVS_KEY_NAMES, list, &len) == ESP_OK) {
+        names_from_list(list);
+    }
+    len = sizeof(s_active);
+    if (nvs_get_str(handle, NVS_KEY_ACTIVE, s_active, &len) != ESP_OK) {
+        s_active[0] = '\0';
+    }
+    nvs_close(handle);
+}
+
+static int save_registry(void) {
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to open NVS: %s", esp_err_to_name(err));
+        return -1;
+    }
+    char list[NAMES_BUF_LEN];
+    names_to_list(list, sizeof(list));
+    err = nvs_set_str(handle, NVS_KEY_NAMES, list);
+    if (err == ESP_OK) {
+        err = nvs_set_str(handle, NVS_KEY_ACTIVE, s_active);
+    }
+    if (err == ESP_OK) {
+        err = nvs_commit(handle);
+    }
+    nvs_close(handle);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to save profile list: %s", esp_err_to_name(err));
+        return -1;
+    }
+    return 0;
+}
+
+static int store_params(const char *name) {
+    char ns[16];
+    profile_ns(name, ns, sizeof(ns));
+    return (config_save_to_nvs_ns(ns) < 0) ? -1 : 0;
+}
+
+/**
+ * @brief Check the memory window a profile would load
+ *
+ * Loading bypasses the set-time constraints, so a profile stored before
+ * they existed could put start at or past end. Keys the profile lacks
+ * keep the running value.
+ */
+static bool window_valid(const char *name) {
+    char ns[16];
+    profile_ns(name, ns, sizeof(ns));
+    uint8_t start = CONFIG_GET_MEM_WINDOW_START_PCT();
+    uint8_t end = CONFIG_GET_MEM_WINDOW_END_PCT();
+    nvs_handle_t handle;
+    if (nvs_open(ns, NVS_READONLY, &handle) == ESP_OK) {
+        nvs_get_u8(handle, NVS_KEYER_MEM_WINDOW_START_PCT, &start);
+        nvs_get_u8(handle, NVS_KEYER_MEM_WINDOW_END_PCT, &end);
+        nvs_close(handle);
+    }
+    if (start >= end) {
+        ESP_LOGE(TAG, "Profile %s: memory window %u-%u%%, start must be below end",
+                 name, (unsigned)start, (unsigned)end);
+        return false;
+    }
+    return true;
+}
+
+static int apply_params(const char *name) {
+    char ns[16];
+    profile_ns(name, ns, sizeof(ns));
+
+    /* One generation for the whole profile: readers never see it half-loaded */
+    config_batch_begin(&g_config);
+    int loaded = config_load_from_nvs_ns(ns);
+    if (loaded > 0) {
+        /* Every parameter may have changed */
+        for (size_t id = 0; id < CONFIG_ID_COUNT; id++) {
+            config_mark_changed(&g_config, (config_param_id_t)id);
+        }
+    }
+    config_batch_commit(&g_config);
+    if (loaded <= 0) {
+        return -1;
+    }
+
+    /* Make it the boot config too */
+    return (config_save_to_nvs() < 0) ? -1 : 0;
+}
+
+static int erase_params(const char *name) {
+    char ns[16];
+    profile_ns(name, ns, sizeof(ns));
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(ns, NVS_READWRITE, &handle);
+    if (err == ESP_ERR_NVS_NOT_FOUND) {
+        return 0;
+    }
+    if (err == ESP_OK) {
+        err = nvs_erase_all(handle);
+        if (err == ESP_OK) {
+            err = nvs_commit(handle);
+        }
+        nvs_close(handle);
+    }
+    return (err == ESP_OK) ? 0 : -1;
+}
+#else
+/* Host stubs: registry lives in RAM only */
+static void load_registry(void) {}
+static int save_registry(void) { return 0; }
+static int store_params(const char *name) { (void)name; return 0; }
+static bool window_valid(const char *name) { (void)name; return true; }
+static int apply_params(const char *name) { (void)name; return 0; }
+static int erase_params(const char *name) { (void)name; return 0; }
+#endif
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void config_profile_init(void) {
+    s_count = 0;
+    s_active[0] = '\0';
+    load_registry();
+}
+
+bool config_profile_name_valid(const char *name) {
+    if (name == NULL) {
+        return false;
+    }
+    size_t len = strlen(name);
+    if (len == 0 || len > CONFIG_PROFILE_NAME_MAX) {
+        return false;
+    }
+    for (size_t i = 0; i < len; i++) {
+        char c = name[i];
+        if (!((c >= 'a' && c <= 'z') || (c >= '0' && c <= '9') || c == '_')) {
+            return false;
+        }
+    }
+    return true;
+}
+
+size_t config_profile_count(void) {
+    return s_count;
+}
+
+const char *config_profile_name(size_t index) {
+    return (index < s_count) ? s_names[index] : NULL;
+}
+
+const char *config_profile_active(void) {
+    return s_active;
+}
+
+console_error_t config_profile_save(const char *name) {
+    if (!config_profile_name_valid(name)) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    bool is_new = (find_profile(name) < 0);
+    if (is_new && s_count >= CONFIG_PROFILE_MAX) {
+        return CONSOLE_ERR_OUT_OF_RANGE;
+    }
+
+    if (store_params(name) != 0) {
+        return CONSOLE_ERR_NVS_ERROR;
+    }
+
+    if (is_new) {
+        strcpy(s_names[s_count++], name);
+    }
+    strcpy(s_active, name);
+    return (save_registry() == 0) ? CONSOLE_OK : CONSOLE_ERR_NVS_ERROR;
+}
+
+console_error_t config_profile_load(const char *name) {
+    if (name == NULL || find_profile(name) < 0) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    if (!window_valid(name)) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    if (apply_params(name) != 0) {
+        return CONSOLE_ERR_NVS_ERROR;
+    }
+
+    strcpy(s_active, name);
+    return (save_registry() == 0) ? CONSOLE_OK : CONSOLE_ERR_NVS_ERROR;
+}
+
+console_error_t config_profile_delete(const char *name) {
+    int idx = (name != NULL) ? find_profile(name) : -1;
+    if (idx < 0) {
+        return CONSOLE_ERR_INVALID_VALUE;
+    }
+
+    /* name may point into s_names, which is shifted below */
+    char victim[CONFIG_PROFILE_NAME_MAX + 1];
+    strcpy(victim, name);
+    name = victim;
+
+    if (erase_params(name) != 0) {
+        return CONSOLE_ERR_NVS_ERROR;
+    }
+
+    for (size_t i = (size_t)idx; i + 1 < s_count; i++) {
+        strcpy(s_names[i], s_names[i + 1]);
+    }
+    s_count--;
+    if (strcmp(s_active, name) == 0) {
+        s_active[0] = '\0';
+    }
+    return (save_registry() == 0) ? CONSOLE_OK : CONSOLE_ERR_NVS_ERROR;
+}
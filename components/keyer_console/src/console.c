@@ -7,6 +7,11 @@
  */
 
 #include "console.h"
+#include "console_output.h"
+#include "console_lock.h"
+#include "config_profile.h"
+#include "preset_nvs.h"
+#include "text_keyer.h"
 #include "config.h"
 #include <stdio.h>
 #include <string.h>
@@ -15,10 +20,10 @@
 #include "freertos/FreeRTOS.h"
 #include "freertos/task.h"
 #include "driver/usb_serial_jtag.h"
-#include "usb_console.h"
-/* Use USB console printf for console output (skip for IDE analyzers) */
+#include "esp_timer.h"
+/* Route output through the console fan-out writer (skip for IDE analyzers) */
 #if !defined(__INTELLISENSE__) && !defined(__clang_analyzer__) && !defined(__clangd__)
-#define printf usb_console_printf
+#define printf console_printf
 #endif
 #endif
 
@@ -38,15 +43,127 @@ typedef enum {
 
 static escape_state_t s_escape_state = ESC_NONE;
 
+/** Command line awaiting y/N confirmation (empty = none pending) */
+static char s_confirm_line[CONSOLE_LINE_MAX];
+
+/** Last character was CR: an LF right after it is the same Enter (CRLF) */
+static bool s_after_cr = false;
+
+/** Typed characters shown in keyboard mode (newest at the end) */
+#define KEYBOARD_SHOW_MAX 48
+
+/** Keyboard keying mode state */
+static bool s_keyboard = false;
+static bool s_keyboard_started = false;     /**< First character accepted */
+static size_t s_keyboard_shown = 0;         /**< Chars on the last redraw */
+static size_t s_keyboard_pending = 0;       /**< Pending chars on the last redraw */
+
 void console_init(void) {
     s_line_pos = 0;
     memset(s_line_buf, 0, sizeof(s_line_buf));
     s_escape_state = ESC_NONE;
+    s_confirm_line[0] = '\0';
     console_history_init();
+    console_lock_init();
+    config_profile_init();
+    preset_nvs_init();
+}
+
+/**
+ * @brief Milliseconds since boot (for idle auto-lock)
+ */
+static uint32_t console_now_ms(void) {
+#ifdef ESP_PLATFORM
+    return (uint32_t)(esp_timer_get_time() / 1000);
+#else
+    return 0;
+#endif
+}
+
+/**
+ * @brief Auto-lock if idle longer than system.lock_timeout
+ *
+ * Discards any partial input and pending confirmation on lock.
+ */
+static void console_idle_lock_check(uint32_t now_ms) {
+    uint32_t timeout_ms = (uint32_t)CONFIG_GET_LOCK_TIMEOUT() * 60u * 1000u;
+    if (console_lock_check_idle(now_ms, timeout_ms)) {
+        s_line_pos = 0;
+        s_saved_pos = 0;
+        s_escape_state = ESC_NONE;
+        s_confirm_line[0] = '\0';
+        if (s_keyboard) {
+            text_keyer_type_end();
+            s_keyboard = false;
+        }
+        printf("\r\nConsole locked (idle)\r\n");
+        console_print_prompt();
+    }
+}
+
+/**
+ * @brief Check if a line carries a passphrase (kept out of history)
+ */
+static bool line_has_secret(const char *line) {
+    return strncmp(line, "unlock", 6) == 0 || strncmp(line, "lock set", 8) == 0;
+}
+
+console_error_t console_request_confirm(const char *line) {
+    if (line == NULL || strlen(line) >= sizeof(s_confirm_line)) {
+        return CONSOLE_ERR_REQUIRES_CONFIRM;
+    }
+    strcpy(s_confirm_line, line);
+    return CONSOLE_OK;
+}
+
+bool console_confirm_pending(void) {
+    return s_confirm_line[0] != '\0';
+}
+
+/**
+ * @brief Handle the answer to a pending confirmation
+ */
+static void console_handle_confirm(const char *answer) {
+    char line[CONSOLE_LINE_MAX];
+    strcpy(line, s_confirm_line);
+    s_confirm_line[0] = '\0';
+
+    if (strcmp(answer, "y") != 0 && strcmp(answer, "Y") != 0 &&
+        strcmp(answer, "yes") != 0 && strcmp(answer, "YES") != 0) {
+        printf("Cancelled\r\n");
+        return;
+    }
+
+    console_parsed_cmd_t cmd;
+    console_parse_line(line, &cmd);
+    console_error_t err = console_execute(&cmd);
+    if (err != CONSOLE_OK) {
+        printf("%s: %s\r\n",
+               console_error_code(err),
+               console_error_message(err));
+    }
 }
 
 void console_print_prompt(void) {
-    const char *callsign = g_config.system.callsign;
+    if (s_keyboard) {
+        printf("KB> ");
+        fflush(stdout);
+        return;
+    }
+
+    if (console_confirm_pending()) {
+        printf("Are you sure? [y/N] ");
+        fflush(stdout);
+        return;
+    }
+
+    if (console_lock_is_locked()) {
+        printf("locked> ");
+        fflush(stdout);
+        return;
+    }
+
+    const char *callsign = CONFIG_GET_CALLSIGN();
     if (callsign[0] != '\0') {
         printf("%s> ", callsign);
     } else {
@@ -55,7 +172,118 @@ void console_print_prompt(void) {
     fflush(stdout);
 }
 
+/* ============================================================================
+ * Keyboard keying
+ * ============================================================================ */
+
+/**
+ * @brief Redraw the keyboard line: sent text, then pending text underlined
+ *
+ * @param force Redraw even if nothing was sent or typed since last time
+ */
+static void keyboard_redraw(bool force) {
+    char buf[KEYBOARD_SHOW_MAX + 1];
+    size_t pending = 0;
+    size_t n = text_keyer_get_typed(buf, sizeof(buf), &pending);
+    if (!force && n == s_keyboard_shown && pending == s_keyboard_pending) {
+        return;
+    }
+    s_keyboard_shown = n;
+    s_keyboard_pending = pending;
+
+    printf("\rKB> %.*s\033[4m%s\033[0m\033[K",
+           (int)(n - pending), buf, &buf[n - pending]);
+    fflush(stdout);
+}
+
+static void keyboard_leave(const char *reason) {
+    s_keyboard = false;
+    printf("\r\nKeyboard mode %s\r\n", reason);
+}
+
+void console_keyboard_begin(void) {
+    s_keyboard = true;
+    s_keyboard_started = false;
+    s_keyboard_shown = 0;
+    s_keyboard_pending = 0;
+}
+
+bool console_keyboard_poll(void) {
+    if (!s_keyboard || !s_keyboard_started) {
+        return false;
+    }
+    if (!text_keyer_is_typing()) {
+        keyboard_leave("ended (keyer stopped)");
+        return true;
+    }
+    keyboard_redraw(false);
+    return false;
+}
+
+/**
+ * @brief Handle one character in keyboard mode
+ * @return true if keyboard mode was left
+ */
+static bool keyboard_push_char(char c) {
+    /* Swallow escape sequences (arrow keys) */
+    if (s_escape_state == ESC_RECEIVED) {
+        s_escape_state = (c == '[') ? ESC_BRACKET_RECEIVED : ESC_NONE;
+        return false;
+    }
+    if (s_escape_state == ESC_BRACKET_RECEIVED) {
+        s_escape_state = ESC_NONE;
+        return false;
+    }
+
+    if (c == 0x1B) {
+        s_escape_state = ESC_RECEIVED;
+        return false;
+    } else if (c == 0x03) {
+        /* Ctrl+C - drop what is not sent yet */
+        text_keyer_abort();
+        keyboard_leave("aborted");
+        return true;
+    } else if (c == 0x04) {
+        /* Ctrl+D - leave, the queue is still sent */
+        text_keyer_type_end();
+        keyboard_leave("off");
+        return true;
+    } else if (c == '\b' || c == 0x7F) {
+        text_keyer_backspace();
+    } else if (c == '\r' || c == '\n' || (c >= 0x20 && c <= 0x7E)) {
+        char key = (c == '\r' || c == '\n') ? ' ' : c;
+        if (text_keyer_type(key) == 0) {
+            s_keyboard_started = true;
+        } else {
+            printf("\a");  /* Queue full or a message is playing */
+        }
+    }
+
+    keyboard_redraw(true);
+    return false;
+}
+
+/* ============================================================================
+ * Line editor
+ * ============================================================================ */
+
 bool console_push_char(char c) {
+    uint32_t now_ms = console_now_ms();
+    console_idle_lock_check(now_ms);
+    console_lock_touch(now_ms);
+
+    if (s_keyboard) {
+        return keyboard_push_char(c);
+    }
+
+    /* A CRLF terminal would otherwise enter an empty line, which answers
+     * (and cancels) a confirmation asked by the line just entered */
+    bool after_cr = s_after_cr;
+    s_after_cr = (c == '\r');
+    if (c == '\n' && after_cr) {
+        return false;
+    }
+
     /* Handle escape sequences for arrow keys */
     if (s_escape_state == ESC_BRACKET_RECEIVED) {
         s_escape_state = ESC_NONE;
@@ -76,7 +304,7 @@ bool console_push_char(char c) {
                 s_line_pos = strlen(s_line_buf);
 
                 /* Clear and redraw line */
-                const char *call1 = g_config.system.callsign;
+                const char *call1 = CONFIG_GET_CALLSIGN();
                 printf("\r%s> %s\033[K", call1[0] ? call1 : "", s_line_buf);
                 fflush(stdout);
             }
@@ -101,7 +329,7 @@ bool console_push_char(char c) {
             }
 
             /* Clear and redraw line */
-            const char *call2 = g_config.system.callsign;
+            const char *call2 = CONFIG_GET_CALLSIGN();
             printf("\r%s> %s\033[K", call2[0] ? call2 : "", s_line_buf);
             fflush(stdout);
             return false;
@@ -118,6 +346,11 @@ bool console_push_char(char c) {
         return false;
     }
 
+    /* No history or completion while answering y/N */
+    if (console_confirm_pending() && (c == 0x1B || c == 0x09)) {
+        return false;
+    }
+
     if (c == 0x1B) {
         /* ESC character - start escape sequence */
         s_escape_state = ESC_RECEIVED;
@@ -129,7 +362,7 @@ bool console_push_char(char c) {
         /* Tab character - try to complete */
         if (console_complete(s_line_buf, &s_line_pos, CONSOLE_LINE_MAX)) {
             /* Completion succeeded - redraw line */
-            const char *call3 = g_config.system.callsign;
+            const char *call3 = CONFIG_GET_CALLSIGN();
             printf("\r%s> %s", call3[0] ? call3 : "", s_line_buf);
             fflush(stdout);
         }
@@ -142,11 +375,16 @@ bool console_push_char(char c) {
 
     if (c == '\r' || c == '\n') {
         printf("\r\n");
-        if (s_line_pos > 0) {
+        if (console_confirm_pending()) {
+            s_line_buf[s_line_pos] = '\0';
+            console_handle_confirm(s_line_buf);
+        } else if (s_line_pos > 0) {
             s_line_buf[s_line_pos] = '\0';
 
-            /* Add to history */
-            console_history_push(s_line_buf);
+            /* Add to history (never passphrases) */
+            if (!line_has_secret(s_line_buf)) {
+                console_history_push(s_line_buf);
+            }
 
             /* Parse and execute command */
             console_parsed_cmd_t cmd;
@@ -168,7 +406,11 @@ bool console_push_char(char c) {
             s_line_pos--;
         }
     } else if (c == 0x03) {
-        /* Ctrl+C - cancel current line */
+        /* Ctrl+C - cancel current line (and any pending confirmation) */
+        if (console_confirm_pending()) {
+            s_confirm_line[0] = '\0';
+            printf("Cancelled\r\n");
+        }
         s_line_pos = 0;
         s_saved_pos = 0;
         return true;
@@ -217,6 +459,10 @@ void console_task(void *arg) {
                 console_print_prompt();
             }
         }
+        console_idle_lock_check(console_now_ms());
+        if (console_keyboard_poll()) {
+            console_print_prompt();
+        }
         vTaskDelay(pdMS_TO_TICKS(10));
     }
 }
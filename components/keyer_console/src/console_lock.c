@@ -0,0 +1,194 @@
+/**
+ * @file console_lock.c
+ * @brief Console lock implementation (NVS persistence)
+ */
+
+#include "console_lock.h"
+#include <string.h>
+
+#ifdef ESP_PLATFORM
+#include "nvs.h"
+#include "esp_log.h"
+static const char *TAG = "con_lock";
+#define NVS_NAMESPACE "console"
+#define NVS_KEY_HASH  "lock_hash"
+#endif
+
+/* ============================================================================
+ * Module State
+ * ============================================================================ */
+
+static bool s_has_pass = false;
+static uint32_t s_hash = 0;
+static bool s_locked = false;
+static uint32_t s_last_activity_ms = 0;
+
+/** Commands accepted while locked */
+static const char *const ALLOWED_LOCKED[] = {
+    "unlock", "help", "?", "version", "v",
+};
+
+#define NUM_ALLOWED (sizeof(ALLOWED_LOCKED) / sizeof(ALLOWED_LOCKED[0]))
+
+/* ============================================================================
+ * NVS Helpers
+ * ============================================================================ */
+
+#ifdef ESP_PLATFORM
+static void load_from_nvs(void) {
+    nvs_handle_t handle;
+    if (nvs_open(NVS_NAMESPACE, NVS_READONLY, &handle) != ESP_OK) {
+        return; /* Namespace doesn't exist yet: no passphrase */
+    }
+    uint32_t hash = 0;
+    if (nvs_get_u32(handle, NVS_KEY_HASH, &hash) == ESP_OK) {
+        s_hash = hash;
+        s_has_pass = true;
+    }
+    nvs_close(handle);
+}
+
+static int save_to_nvs(void) {
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to open NVS: %s", esp_err_to_name(err));
+        return -1;
+    }
+
+    if (s_has_pass) {
+        err = nvs_set_u32(handle, NVS_KEY_HASH, s_hash);
+    } else {
+        err = nvs_erase_key(handle, NVS_KEY_HASH);
+        if (err == ESP_ERR_NVS_NOT_FOUND) {
+            err = ESP_OK;
+        }
+    }
+    if (err == ESP_OK) {
+        err = nvs_commit(handle);
+    }
+    nvs_close(handle);
+
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to save lock: %s", esp_err_to_name(err));
+        return -1;
+    }
+    return 0;
+}
+#else
+/* Host stubs */
+static void load_from_nvs(void) {}
+static int save_to_nvs(void) { return 0; }
+#endif
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void console_lock_init(void) {
+    s_has_pass = false;
+    s_hash = 0;
+    s_last_activity_ms = 0;
+
+    load_from_nvs();
+
+    s_locked = s_has_pass;
+}
+
+uint32_t console_lock_hash(const char *pass) {
+    /* FNV-1a, salted so a bare FNV table lookup doesn't apply */
+    static const char SALT[] = "cwkeyer:";
+    uint32_t h = 2166136261u;
+    for (const char *p = SALT; *p != '\0'; p++) {
+        h ^= (uint8_t)*p;
+        h *= 16777619u;
+    }
+    for (const char *p = pass; *p != '\0'; p++) {
+        h ^= (uint8_t)*p;
+        h *= 16777619u;
+    }
+    return h;
+}
+
+console_error_t console_lock_set_passphrase(const char *pass) {
+    bool had_pass = s_has_pass;
+    uint32_t old_hash = s_hash;
+
+    if (pass == NULL || pass[0] == '\0') {
+        s_has_pass = false;
+        s_hash = 0;
+        s_locked = false;
+    } else {
+        size_t len = strlen(pass);
+        if (len < CONSOLE_LOCK_PASS_MIN || len > CONSOLE_LOCK_PASS_MAX) {
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+        s_hash = console_lock_hash(pass);
+        s_has_pass = true;
+    }
+
+    if (save_to_nvs() != 0) {
+        s_has_pass = had_pass;
+        s_hash = old_hash;
+        return CONSOLE_ERR_NVS_ERROR;
+    }
+    return CONSOLE_OK;
+}
+
+bool console_lock_has_passphrase(void) {
+    return s_has_pass;
+}
+
+bool console_lock_is_locked(void) {
+    return s_locked;
+}
+
+bool console_lock_engage(void) {
+    if (!s_has_pass) {
+        return false;
+    }
+    s_locked = true;
+    return true;
+}
+
+bool console_lock_try_unlock(const char *pass) {
+    if (!s_has_pass) {
+        s_locked = false;
+        return true;
+    }
+    if (pass == NULL || console_lock_hash(pass) != s_hash) {
+        return false;
+    }
+    s_locked = false;
+    return true;
+}
+
+bool console_lock_allows(const char *command) {
+    if (!s_locked) {
+        return true;
+    }
+    if (command == NULL) {
+        return false;
+    }
+    for (size_t i = 0; i < NUM_ALLOWED; i++) {
+        if (strcmp(command, ALLOWED_LOCKED[i]) == 0) {
+            return true;
+        }
+    }
+    return false;
+}
+
+void console_lock_touch(uint32_t now_ms) {
+    s_last_activity_ms = now_ms;
+}
+
+bool console_lock_check_idle(uint32_t now_ms, uint32_t timeout_ms) {
+    if (!s_has_pass || s_locked || timeout_ms == 0) {
+        return false;
+    }
+    if (now_ms - s_last_activity_ms < timeout_ms) {
+        return false;
+    }
+    s_locked = true;
+    return true;
+}
@@ -0,0 +1,211 @@
+/**
+ * @file console_output.c
+ * @brief Fan-out writer for console responses and prompt
+ */
+
+#include "console_output.h"
+#include <stdatomic.h>
+#include <stdarg.h>
+#include <stdio.h>
+#include <string.h>
+
+#ifdef ESP_PLATFORM
+#include "freertos/FreeRTOS.h"
+#include "freertos/task.h"
+#else
+#include <sched.h>
+#endif
+
+/* ============================================================================
+ * Sink table
+ * ============================================================================ */
+
+/** Slot lifecycle: FREE -> CLAIMED (fields being set) -> ACTIVE -> FREE */
+enum {
+    SINK_FREE = 0,
+    SINK_CLAIMED,
+    SINK_ACTIVE,
+};
+
+typedef struct {
+    const char *name;
+    console_sink_write_fn write;
+    bool crlf;
+    atomic_uchar state;
+    atomic_uint busy;   /**< Writers currently inside (or checking) this slot */
+} console_sink_t;
+
+static console_sink_t s_sinks[CONSOLE_MAX_SINKS];
+
+int console_output_add_sink(const char *name, console_sink_write_fn write, bool crlf) {
+    if (write == NULL) {
+        return -1;
+    }
+
+    for (int i = 0; i < CONSOLE_MAX_SINKS; i++) {
+        console_sink_t *s = &s_sinks[i];
+        unsigned char expected = SINK_FREE;
+        if (atomic_compare_exchange_strong_explicit(&s->state, &expected, SINK_CLAIMED,
+                                                    memory_order_acquire,
+                                                    memory_order_relaxed)) {
+            s->name = (name != NULL) ? name : "?";
+            s->write = write;
+            s->crlf = crlf;
+            /* Publish only after fields are written */
+            atomic_store_explicit(&s->state, SINK_ACTIVE, memory_order_release);
+            return i;
+        }
+    }
+    return -1;
+}
+
+void console_output_remove_sink(int slot) {
+    if (slot < 0 || slot >= CONSOLE_MAX_SINKS) {
+        return;
+    }
+    console_sink_t *s = &s_sinks[slot];
+    unsigned char expected = SINK_ACTIVE;
+    if (!atomic_compare_exchange_strong(&s->state, &expected, SINK_CLAIMED)) {
+        return;
+    }
+
+    /* A writer that got in before the state change finishes first */
+    while (atomic_load(&s->busy) != 0) {
+#ifdef ESP_PLATFORM
+        vTaskDelay(1);
+#else
+        sched_yield();
+#endif
+    }
+    atomic_store_explicit(&s->state, SINK_FREE, memory_order_release);
+}
+
+size_t console_output_sink_count(void) {
+    size_t count = 0;
+    for (int i = 0; i < CONSOLE_MAX_SINKS; i++) {
+        if (atomic_load_explicit(&s_sinks[i].state, memory_order_acquire) == SINK_ACTIVE) {
+            count++;
+        }
+    }
+    return count;
+}
+
+/* ============================================================================
+ * Writers
+ * ============================================================================ */
+
+/**
+ * @brief Write to one sink, expanding bare LF to CRLF if requested
+ */
+static void sink_write(const console_sink_t *s, const char *data, size_t len) {
+    if (!s->crlf) {
+        s->write(data, len);
+        return;
+    }
+
+    size_t start = 0;
+    for (size_t i = 0; i < len; i++) {
+        if (data[i] == '\n' && (i == 0 || data[i - 1] != '\r')) {
+            if (i > start) {
+                s->write(&data[start], i - start);
+            }
+            s->write("\r\n", 2);
+            start = i + 1;
+        }
+    }
+    if (start < len) {
+        s->write(&data[start], len - start);
+    }
+}
+
+void console_write(const char *data, size_t len) {
+    if (data == NULL || len == 0) {
+        return;
+    }
+
+    bool written = false;
+    for (int i = 0; i < CONSOLE_MAX_SINKS; i++) {
+        console_sink_t *s = &s_sinks[i];
+        /* Announce before checking: remove either sees busy or we see its state */
+        atomic_fetch_add(&s->busy, 1);
+        if (atomic_load(&s->state) == SINK_ACTIVE) {
+            sink_write(s, data, len);
+            written = true;
+        }
+        atomic_fetch_sub_explicit(&s->busy, 1, memory_order_release);
+    }
+
+    if (!written) {
+        fwrite(data, 1, len, stdout);
+    }
+}
+
+void console_printf(const char *fmt, ...) {
+    char buf[CONSOLE_PRINTF_MAX];
+    va_list args;
+    va_start(args, fmt);
+    int len = vsnprintf(buf, sizeof(buf), fmt, args);
+    va_end(args);
+
+    if (len <= 0) {
+        return;
+    }
+    if ((size_t)len >= sizeof(buf)) {
+        len = (int)sizeof(buf) - 1;
+    }
+    console_write(buf, (size_t)len);
+}
+
+/* ============================================================================
+ * Posted output (single producer, console output task consumes)
+ * ============================================================================ */
+
+static char s_post[CONSOLE_POST_RING];
+static atomic_uint s_post_head = 0;  /* Written by the producer */
+static atomic_uint s_post_tail = 0;  /* Written by the pump */
+
+bool console_post(const char *data, size_t len) {
+    if (data == NULL || len == 0) {
+        return true;
+    }
+    unsigned head = atomic_load_explicit(&s_post_head, memory_order_relaxed);
+    unsigned tail = atomic_load_explicit(&s_post_tail, memory_order_acquire);
+    if (len > CONSOLE_POST_RING - (head - tail)) {
+        return false;
+    }
+    for (size_t i = 0; i < len; i++) {
+        s_post[(head + i) & (CONSOLE_POST_RING - 1)] = data[i];
+    }
+    atomic_store_explicit(&s_post_head, head + (unsigned)len, memory_order_release);
+    return true;
+}
+
+void console_output_pump(void) {
+    unsigned head = atomic_load_explicit(&s_post_head, memory_order_acquire);
+    unsigned tail = atomic_load_explicit(&s_post_tail, memory_order_relaxed);
+    while (tail != head) {
+        /* Up to the end of the ring, the rest on the next pass */
+        size_t start = tail & (CONSOLE_POST_RING - 1);
+        size_t len = head - tail;
+        if (len > CONSOLE_POST_RING - start) {
+            len = CONSOLE_POST_RING - start;
+        }
+        console_write(&s_post[start], len);
+        tail += (unsigned)len;
+        atomic_store_explicit(&s_post_tail, tail, memory_order_release);
+    }
+}
+
+#ifdef ESP_PLATFORM
+void console_output_task(void *arg) {
+    (void)arg;
+    for (;;) {
+        console_output_pump();
+        vTaskDelay(pdMS_TO_TICKS(20));
+    }
+}
+#else
+void console_output_task(void *arg) {
+    (void)arg;
+}
+#endif
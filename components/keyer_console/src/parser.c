@@ -40,6 +40,7 @@ void console_parse_line(const char *line, console_parsed_cmd_t *out) {
     out->argc = 0;
     for (int i = 0; i < CONSOLE_MAX_ARGS; i++) {
         out->args[i] = NULL;
+        out->rest[i] = NULL;
     }
 
     if (line == NULL || *line == '\0') {
@@ -51,6 +52,14 @@ void console_parse_line(const char *line, console_parsed_cmd_t *out) {
     strncpy(s_parse_buf, line, CONSOLE_LINE_MAX - 1);
     s_parse_buf[CONSOLE_LINE_MAX - 1] = '\0';
 
+    /* Untouched copy for rest[] (free-text arguments), trailing space trimmed */
+    static char s_rest_buf[CONSOLE_LINE_MAX];
+    memcpy(s_rest_buf, s_parse_buf, CONSOLE_LINE_MAX);
+    size_t rest_len = strlen(s_rest_buf);
+    while (rest_len > 0 && isspace((unsigned char)s_rest_buf[rest_len - 1])) {
+        s_rest_buf[--rest_len] = '\0';
+    }
+
     char *p = s_parse_buf;
 
     /* Skip leading whitespace */
@@ -85,6 +94,7 @@ void console_parse_line(const char *line, console_parsed_cmd_t *out) {
         }
 
         out->args[i] = p;
+        out->rest[i] = s_rest_buf + (p - s_parse_buf);
         out->argc++;
 
         /* Find end of argument */
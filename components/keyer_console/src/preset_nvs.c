@@ -0,0 +1,226 @@
+/**
+ * @file preset_nvs.c
+ * @brief Iambic preset persistence implementation
+ */
+
+#include "preset_nvs.h"
+#include "iambic_preset.h"
+#include <stdio.h>
+#include <string.h>
+
+#ifdef ESP_PLATFORM
+#include "nvs.h"
+#include "esp_log.h"
+static const char *TAG = "preset";
+#define NVS_NAMESPACE   "presets"
+#define NVS_KEY_SCHEMA  "schema"
+#define NVS_KEY_ACTIVE  "active"
+#endif
+
+/**
+ * @brief Stored layout of one slot (schema 3)
+ */
+typedef struct __attribute__((packed)) {
+    char name[IAMBIC_PRESET_NAME_MAX];
+    uint16_t speed_wpm;
+    uint8_t iambic_mode;
+    uint8_t memory_mode;
+    uint8_t squeeze_mode;
+    uint8_t mem_window_start_pct;
+    uint8_t mem_window_end_pct;
+    uint16_t sidetone_freq_hz;  /**< 0 = no override */
+    uint8_t weight;
+    uint16_t ptt_tail_ms;       /**< 0 = no override */
+} preset_blob_t;
+
+/**
+ * @brief Stored layout of one slot (schema 2, no sidetone/weight)
+ */
+typedef struct __attribute__((packed)) {
+    char name[IAMBIC_PRESET_NAME_MAX];
+    uint16_t speed_wpm;
+    uint8_t iambic_mode;
+    uint8_t memory_mode;
+    uint8_t squeeze_mode;
+    uint8_t mem_window_start_pct;
+    uint8_t mem_window_end_pct;
+} preset_blob_v2_t;
+
+/* ============================================================================
+ * Blob Conversion
+ * ============================================================================ */
+
+static void blob_to_preset(uint32_t index, const preset_blob_t *blob) {
+    iambic_preset_t *p = iambic_preset_get_mut(index);
+    char name[IAMBIC_PRESET_NAME_MAX];
+    memcpy(name, blob->name, sizeof(name));
+    name[sizeof(name) - 1] = '\0';
+
+    iambic_preset_set_name(index, name);
+    iambic_preset_set_wpm(p, blob->speed_wpm);  /* Range-checked by setters */
+    if (blob->iambic_mode <= IAMBIC_MODE_B) {
+        iambic_preset_set_mode(p, (iambic_mode_t)blob->iambic_mode);
+    }
+    if (blob->memory_mode <= MEMORY_MODE_DOT_AND_DAH) {
+        iambic_preset_set_memory_mode(p, (memory_mode_t)blob->memory_mode);
+    }
+    if (blob->squeeze_mode <= SQUEEZE_MODE_LATCH_ON) {
+        iambic_preset_set_squeeze_mode(p, (squeeze_mode_t)blob->squeeze_mode);
+    }
+    iambic_preset_set_mem_start(p, blob->mem_window_start_pct);
+    iambic_preset_set_mem_end(p, blob->mem_window_end_pct);
+    iambic_preset_set_sidetone(p, blob->sidetone_freq_hz);
+    iambic_preset_set_weight(p, blob->weight);
+    iambic_preset_set_ptt_tail(p, blob->ptt_tail_ms);
+}
+
+/**
+ * @brief Upgrade a schema 2 slot: values carry over, new fields get defaults
+ */
+static void migrate_v2(const preset_blob_v2_t *old, preset_blob_t *blob) {
+    memset(blob, 0, sizeof(*blob));
+    memcpy(blob->name, old->name, sizeof(blob->name));
+    blob->speed_wpm = old->speed_wpm;
+    blob->iambic_mode = old->iambic_mode;
+    blob->memory_mode = old->memory_mode;
+    blob->squeeze_mode = old->squeeze_mode;
+    blob->mem_window_start_pct = old->mem_window_start_pct;
+    blob->mem_window_end_pct = old->mem_window_end_pct;
+    blob->sidetone_freq_hz = IAMBIC_PRESET_NO_OVERRIDE;
+    blob->weight = IAMBIC_PRESET_WEIGHT_DEFAULT;
+    blob->ptt_tail_ms = IAMBIC_PRESET_NO_OVERRIDE;
+}
+
+bool preset_nvs_apply_slot(uint32_t index, uint8_t schema, const void *data, size_t len) {
+    if (index >= IAMBIC_PRESET_COUNT || data == NULL) {
+        return false;
+    }
+
+    preset_blob_t blob;
+    if (schema == IAMBIC_PRESET_SCHEMA_VERSION && len == sizeof(preset_blob_t)) {
+        memcpy(&blob, data, sizeof(blob));
+    } else if (schema == 2 && len == sizeof(preset_blob_v2_t)) {
+        preset_blob_v2_t old;
+        memcpy(&old, data, sizeof(old));
+        migrate_v2(&old, &blob);
+    } else {
+        return false;
+    }
+    blob_to_preset(index, &blob);
+    return true;
+}
+
+/* ============================================================================
+ * NVS Helpers
+ * ============================================================================ */
+
+#ifdef ESP_PLATFORM
+static void slot_key(uint32_t index, char *buf, size_t len) {
+    snprintf(buf, len, "p%lu", (unsigned long)index);
+}
+
+static void blob_from_preset(const iambic_preset_t *p, preset_blob_t *blob) {
+    memset(blob, 0, sizeof(*blob));
+    iambic_preset_get_name(p, blob->name, sizeof(blob->name));
+    blob->speed_wpm = (uint16_t)iambic_preset_get_wpm(p);
+    blob->iambic_mode = (uint8_t)iambic_preset_get_mode(p);
+    blob->memory_mode = (uint8_t)iambic_preset_get_memory_mode(p);
+    blob->squeeze_mode = (uint8_t)iambic_preset_get_squeeze_mode(p);
+    blob->mem_window_start_pct = iambic_preset_get_mem_start(p);
+    blob->mem_window_end_pct = iambic_preset_get_mem_end(p);
+    blob->sidetone_freq_hz = iambic_preset_get_sidetone(p);
+    blob->weight = iambic_preset_get_weight(p);
+    blob->ptt_tail_ms = iambic_preset_get_ptt_tail(p);
+}
+
+static int save_to_nvs(void) {
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to open NVS: %s", esp_err_to_name(err));
+        return -1;
+    }
+
+    int saved = 0;
+    err = nvs_set_u8(handle, NVS_KEY_SCHEMA, IAMBIC_PRESET_SCHEMA_VERSION);
+    for (uint32_t i = 0; i < IAMBIC_PRESET_COUNT && err == ESP_OK; i++) {
+        char key[8];
+        preset_blob_t blob;
+        slot_key(i, key, sizeof(key));
+        blob_from_preset(iambic_preset_get(i), &blob);
+        err = nvs_set_blob(handle, key, &blob, sizeof(blob));
+        if (err == ESP_OK) {
+            saved++;
+        }
+    }
+    if (err == ESP_OK) {
+        err = nvs_set_u8(handle, NVS_KEY_ACTIVE, (uint8_t)iambic_preset_active_index());
+    }
+    if (err == ESP_OK) {
+        err = nvs_commit(handle);
+    }
+    nvs_close(handle);
+
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to save presets: %s", esp_err_to_name(err));
+        return -1;
+    }
+    return saved;
+}
+
+static void load_from_nvs(void) {
+    nvs_handle_t handle;
+    if (nvs_open(NVS_NAMESPACE, NVS_READONLY, &handle) != ESP_OK) {
+        return; /* Nothing saved yet: keep built-in presets */
+    }
+
+    uint8_t schema = 0;
+    nvs_get_u8(handle, NVS_KEY_SCHEMA, &schema);
+    if (schema != IAMBIC_PRESET_SCHEMA_VERSION && schema != 2) {
+        ESP_LOGW(TAG, "schema %u != %u, ignored",
+                 (unsigned)schema, (unsigned)IAMBIC_PRESET_SCHEMA_VERSION);
+        nvs_close(handle);
+        return;
+    }
+
+    for (uint32_t i = 0; i < IAMBIC_PRESET_COUNT; i++) {
+        char key[8];
+        preset_blob_t blob;  /* Largest layout: older ones fit */
+        size_t len = sizeof(blob);
+        slot_key(i, key, sizeof(key));
+        if (nvs_get_blob(handle, key, &blob, &len) == ESP_OK) {
+            preset_nvs_apply_slot(i, schema, &blob, len);
+        }
+    }
+
+    uint8_t active = 0;
+    if (nvs_get_u8(handle, NVS_KEY_ACTIVE, &active) == ESP_OK) {
+        iambic_preset_activate(active);  /* Rejects out-of-range indices */
+    }
+    nvs_close(handle);
+
+    /* Store in the current layout so the old one is read only once */
+    if (schema != IAMBIC_PRESET_SCHEMA_VERSION) {
+        ESP_LOGI(TAG, "migrating schema %u -> %u",
+                 (unsigned)schema, (unsigned)IAMBIC_PRESET_SCHEMA_VERSION);
+        save_to_nvs();
+    }
+}
+#else
+/* Host stubs */
+static void load_from_nvs(void) {}
+static int save_to_nvs(void) { return IAMBIC_PRESET_COUNT; }
+#endif
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void preset_nvs_init(void) {
+    iambic_preset_init();
+    load_from_nvs();
+}
+
+int preset_nvs_save(void) {
+    return save_to_nvs();
+}
@@ -0,0 +1,247 @@
+/**
+ * @file selftest.c
+ * @brief Built-in diagnostic suite implementation
+ */
+
+#include "selftest.h"
+#include "stream.h"
+#include "sidetone.h"
+#include "hal_audio.h"
+#include "hal_gpio.h"
+#include "config.h"
+#include <stdio.h>
+#include <string.h>
+
+#ifdef ESP_PLATFORM
+#include "nvs.h"
+#define NVS_NAMESPACE "selftest"
+#define NVS_KEY_PROBE "probe"
+#endif
+
+/** Scratch stream size (power of 2) */
+#define SCRATCH_STREAM_SIZE 16
+
+/** Samples pushed and read back in the stream check */
+#define SCRATCH_SAMPLES 8
+
+/** Longest key output edge propagation (transistor or optocoupler stage) */
+#define LOOPBACK_TIMEOUT_US 1000
+
+/* ============================================================================
+ * Individual checks
+ * ============================================================================ */
+
+const char *selftest_status_str(selftest_status_t status) {
+    switch (status) {
+        case SELFTEST_PASS: return "PASS";
+        case SELFTEST_FAIL: return "FAIL";
+        case SELFTEST_SKIP: return "SKIP";
+        default:            return "????";
+    }
+}
+
+selftest_status_t selftest_stream(char *detail, size_t len) {
+    /* Private stream: never touches the live keying stream */
+    static stream_sample_t s_buf[SCRATCH_STREAM_SIZE];
+    keying_stream_t stream;
+    stream_consumer_t consumer;
+
+    stream_init(&stream, s_buf, SCRATCH_STREAM_SIZE);
+    consumer_init(&consumer, &stream);
+
+    for (uint32_t i = 0; i < SCRATCH_SAMPLES; i++) {
+        stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+        sample.local_key = (uint8_t)(i & 1U);
+        sample.audio_level = (uint8_t)(i * 17U + 1U);
+        if (!stream_push_raw(&stream, sample)) {
+            snprintf(detail, len, "push %lu failed", (unsigned long)i);
+            return SELFTEST_FAIL;
+        }
+    }
+
+    for (uint32_t i = 0; i < SCRATCH_SAMPLES; i++) {
+        stream_sample_t out;
+        if (!consumer_next(&consumer, &out)) {
+            snprintf(detail, len, "read %lu failed", (unsigned long)i);
+            return SELFTEST_FAIL;
+        }
+        if (out.local_key != (uint8_t)(i & 1U) ||
+            out.audio_level != (uint8_t)(i * 17U + 1U)) {
+            snprintf(detail, len, "read %lu mismatch", (unsigned long)i);
+            return SELFTEST_FAIL;
+        }
+    }
+
+    stream_sample_t extra;
+    if (consumer_next(&consumer, &extra)) {
+        snprintf(detail, len, "read past write position");
+        return SELFTEST_FAIL;
+    }
+
+    /* Lap the consumer: overrun must be detected */
+    for (uint32_t i = 0; i <= SCRATCH_STREAM_SIZE; i++) {
+        stream_push_raw(&stream, STREAM_SAMPLE_EMPTY);
+    }
+    if (!consumer_is_overrun(&consumer)) {
+        snprintf(detail, len, "overrun not detected");
+        return SELFTEST_FAIL;
+    }
+
+    snprintf(detail, len, "%d samples, overrun ok", SCRATCH_SAMPLES);
+    return SELFTEST_PASS;
+}
+
+selftest_status_t selftest_audio_lut(char *detail, size_t len) {
+    uint32_t sum = sidetone_lut_checksum();
+    snprintf(detail, len, "0x%08lX", (unsigned long)sum);
+    if (sum != SINE_LUT_CHECKSUM) {
+        snprintf(detail, len, "0x%08lX != 0x%08lX",
+                 (unsigned long)sum, (unsigned long)SINE_LUT_CHECKSUM);
+        return SELFTEST_FAIL;
+    }
+    return SELFTEST_PASS;
+}
+
+selftest_status_t selftest_codec(char *detail, size_t len) {
+#ifdef ESP_PLATFORM
+    const config_board_t *board = config_board_active();
+    if (!board->has_codec) {
+        snprintf(detail, len, "none on %s", board->name);
+        return SELFTEST_SKIP;
+    }
+    esp_err_t err = hal_audio_probe();
+    if (err != ESP_OK) {
+        snprintf(detail, len, "%s", esp_err_to_name(err));
+        return SELFTEST_FAIL;
+    }
+    snprintf(detail, len, "ES8311 ACK");
+    return SELFTEST_PASS;
+#else
+    snprintf(detail, len, "no codec on host");
+    return SELFTEST_SKIP;
+#endif
+}
+
+selftest_status_t selftest_nvs(char *detail, size_t len) {
+#ifdef ESP_PLATFORM
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err != ESP_OK) {
+        snprintf(detail, len, "open: %s", esp_err_to_name(err));
+        return SELFTEST_FAIL;
+    }
+
+    const uint32_t pattern = 0xA5C3965Au;
+    uint32_t readback = 0;
+    err = nvs_set_u32(handle, NVS_KEY_PROBE, pattern);
+    if (err == ESP_OK) {
+        err = nvs_commit(handle);
+    }
+    if (err == ESP_OK) {
+        err = nvs_get_u32(handle, NVS_KEY_PROBE, &readback);
+    }
+    if (err != ESP_OK) {
+        nvs_close(handle);
+        snprintf(detail, len, "r/w: %s", esp_err_to_name(err));
+        return SELFTEST_FAIL;
+    }
+
+    /* Leave NVS as we found it */
+    nvs_erase_key(handle, NVS_KEY_PROBE);
+    nvs_commit(handle);
+    nvs_close(handle);
+
+    if (readback != pattern) {
+        snprintf(detail, len, "readback 0x%08lX", (unsigned long)readback);
+        return SELFTEST_FAIL;
+    }
+    snprintf(detail, len, "write/read/erase ok");
+    return SELFTEST_PASS;
+#else
+    snprintf(detail, len, "no NVS on host");
+    return SELFTEST_SKIP;
+#endif
+}
+
+#ifdef ESP_PLATFORM
+/**
+ * @brief Loopback one output, append "NAME a/r us" or the failure
+ *
+ * @return false on failure (detail then holds only the failure)
+ */
+static bool loop_one(const char *name, hal_gpio_loop_output_t output, uint8_t sense_pin,
+                     char *detail, size_t len) {
+    hal_gpio_loop_result_t r;
+    bool ok = hal_gpio_output_loopback(output, sense_pin, CONFIG_GET_LOOP_ACTIVE_LOW(),
+                                       LOOPBACK_TIMEOUT_US, &r);
+    if (!r.idle_ok) {
+        snprintf(detail, len, "%s stuck keyed (GPIO%u)", name, (unsigned)sense_pin);
+    } else if (!r.asserted) {
+        snprintf(detail, len, "%s no key down in %dus", name, LOOPBACK_TIMEOUT_US);
+    } else if (!r.released) {
+        snprintf(detail, len, "%s no key up in %dus", name, LOOPBACK_TIMEOUT_US);
+    } else {
+        size_t used = strlen(detail);
+        snprintf(detail + used, len - used, "%s%s %lu/%luus", used > 0 ? " " : "", name,
+                 (unsigned long)r.assert_us, (unsigned long)r.release_us);
+    }
+    return ok;
+}
+#endif
+
+selftest_status_t selftest_loopback(char *detail, size_t len) {
+#ifdef ESP_PLATFORM
+    uint8_t tx_sense = CONFIG_GET_GPIO_LOOP_TX();
+    uint8_t ptt_sense = CONFIG_GET_GPIO_LOOP_PTT();
+    if (hal_gpio_get_config().ptt_pin == 0) {
+        ptt_sense = 0;  /* No PTT output to check */
+    }
+    if (tx_sense == 0 && ptt_sense == 0) {
+        snprintf(detail, len, "no loopback jumper");
+        return SELFTEST_SKIP;
+    }
+
+    detail[0] = '\0';
+    if (tx_sense != 0 && !loop_one("TX", HAL_GPIO_LOOP_TX, tx_sense, detail, len)) {
+        return SELFTEST_FAIL;
+    }
+    if (ptt_sense != 0 && !loop_one("PTT", HAL_GPIO_LOOP_PTT, ptt_sense, detail, len)) {
+        return SELFTEST_FAIL;
+    }
+    return SELFTEST_PASS;
+#else
+    snprintf(detail, len, "no GPIO on host");
+    return SELFTEST_SKIP;
+#endif
+}
+
+/* ============================================================================
+ * Suite
+ * ============================================================================ */
+
+typedef selftest_status_t (*selftest_fn)(char *detail, size_t len);
+
+static const struct {
+    const char *name;
+    selftest_fn run;
+} SELFTEST_ITEMS[SELFTEST_ITEM_COUNT] = {
+    { "stream", selftest_stream },
+    { "lut",    selftest_audio_lut },
+    { "codec",  selftest_codec },
+    { "nvs",    selftest_nvs },
+};
+
+size_t selftest_run_all(selftest_result_t *results, size_t max) {
+    if (results == NULL) {
+        return 0;
+    }
+
+    size_t n = 0;
+    for (size_t i = 0; i < SELFTEST_ITEM_COUNT && n < max; i++) {
+        selftest_result_t *r = &results[n++];
+        r->name = SELFTEST_ITEMS[i].name;
+        r->detail[0] = '\0';
+        r->status = SELFTEST_ITEMS[i].run(r->detail, sizeof(r->detail));
+    }
+    return n;
+}
@@ -0,0 +1,115 @@
+/**
+ * @file config_audit.h
+ * @brief Ring of recent configuration changes
+ *
+ * Keeps the last CONFIG_AUDIT_SIZE parameter changes (source, old value,
+ * new value, timestamp) so an unexpected behavior change on an unattended
+ * keyer can be traced with `config history`.
+ *
+ * Writers are the console task and the HTTP server task, so a slot is
+ * claimed with fetch_add and published through its sequence number.
+ * Readers copy a slot and discard it if the sequence moved meanwhile.
+ * Never used from the RT path.
+ */
+
+#ifndef KEYER_CONFIG_AUDIT_H
+#define KEYER_CONFIG_AUDIT_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdatomic.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Number of changes kept (older ones are overwritten) */
+#define CONFIG_AUDIT_SIZE 16
+
+/** Stored lengths, including terminator (longer text is truncated) */
+#define CONFIG_AUDIT_WHO_MAX   16
+#define CONFIG_AUDIT_PATH_MAX  32
+#define CONFIG_AUDIT_VALUE_MAX 24
+
+/**
+ * @brief Where a change came from
+ */
+typedef enum {
+    CONFIG_AUDIT_CONSOLE = 0,    /**< Serial/USB console */
+    CONFIG_AUDIT_WEB = 1,        /**< REST API (WebUI) */
+} config_audit_source_t;
+
+/**
+ * @brief One recorded change
+ */
+typedef struct {
+    int64_t time_us;                        /**< esp_timer time of the change */
+    config_audit_source_t source;
+    char who[CONFIG_AUDIT_WHO_MAX];         /**< Client address, "" if unknown */
+    char path[CONFIG_AUDIT_PATH_MAX];       /**< "keyer.wpm" */
+    char old_value[CONFIG_AUDIT_VALUE_MAX];
+    char new_value[CONFIG_AUDIT_VALUE_MAX];
+} config_audit_entry_t;
+
+/**
+ * @brief Ring slot: entry plus publication sequence
+ */
+typedef struct {
+    atomic_uint seq;                 /**< 2n+1 while record n is written, 2n+2 once done */
+    config_audit_entry_t entry;
+} config_audit_slot_t;
+
+/**
+ * @brief Change ring
+ */
+typedef struct {
+    atomic_uint head;                /**< Records ever claimed */
+    config_audit_slot_t slots[CONFIG_AUDIT_SIZE];
+} config_audit_t;
+
+/**
+ * @brief Empty the ring
+ */
+void config_audit_init(config_audit_t *audit);
+
+/**
+ * @brief Record one change
+ *
+ * @param audit Ring
+ * @param source Transport the change came from
+ * @param who Client address (NULL or "" if not applicable)
+ * @param path Parameter path
+ * @param old_value Value before the change
+ * @param new_value Value after the change
+ * @param now_us Current time
+ */
+void config_audit_record(config_audit_t *audit, config_audit_source_t source,
+                         const char *who, const char *path,
+                         const char *old_value, const char *new_value, int64_t now_us);
+
+/**
+ * @brief Number of changes currently held (at most CONFIG_AUDIT_SIZE)
+ */
+size_t config_audit_count(const config_audit_t *audit);
+
+/**
+ * @brief Copy a recorded change
+ *
+ * @param audit Ring
+ * @param index 0 = most recent
+ * @param out Copy of the entry
+ * @return false if out of range or overwritten while copying
+ */
+bool config_audit_get(const config_audit_t *audit, size_t index, config_audit_entry_t *out);
+
+/**
+ * @brief Source as text ("console", "web")
+ */
+const char *config_audit_source_str(config_audit_source_t source);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CONFIG_AUDIT_H */
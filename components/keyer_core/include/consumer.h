@@ -19,6 +19,7 @@
 #include <stdbool.h>
 #include "stream.h"
 #include "fault.h"
+#include "consumer_lag.h"
 
 #ifdef __cplusplus
 extern "C" {
@@ -41,6 +42,7 @@ typedef struct {
     fault_state_t *fault;           /**< Fault state (shared with RT loop) */
     size_t read_idx;                /**< Current read position */
     size_t max_lag;                 /**< Maximum allowed lag before FAULT */
+    consumer_lag_t *lag;            /**< Lag telemetry (NULL = not tracked) */
 } hard_rt_consumer_t;
 
 /**
@@ -95,6 +97,18 @@ void hard_rt_consumer_resync(hard_rt_consumer_t *consumer);
  */
 size_t hard_rt_consumer_lag(const hard_rt_consumer_t *consumer);
 
+/**
+ * @brief Report lag telemetry to a registry slot
+ *
+ * Call after init (init detaches).
+ *
+ * @param consumer Consumer handle
+ * @param lag Slot from consumer_lag_register() (NULL = stop tracking)
+ */
+static inline void hard_rt_consumer_track(hard_rt_consumer_t *consumer, consumer_lag_t *lag) {
+    consumer->lag = lag;
+}
+
 /* ============================================================================
  * Best Effort Consumer
  * ============================================================================ */
@@ -111,6 +125,7 @@ typedef struct {
     size_t read_idx;                /**< Current read position */
     size_t dropped;                 /**< Counter of skipped samples */
     size_t skip_threshold;          /**< Lag threshold for auto-skip */
+    consumer_lag_t *lag;            /**< Lag telemetry (NULL = not tracked) */
 } best_effort_consumer_t;
 
 /**
@@ -164,6 +179,19 @@ static inline void best_effort_consumer_reset_dropped(best_effort_consumer_t *co
  */
 size_t best_effort_consumer_lag(const best_effort_consumer_t *consumer);
 
+/**
+ * @brief Report lag and drops to a registry slot
+ *
+ * Call after init (init detaches).
+ *
+ * @param consumer Consumer handle
+ * @param lag Slot from consumer_lag_register() (NULL = stop tracking)
+ */
+static inline void best_effort_consumer_track(best_effort_consumer_t *consumer,
+                                              consumer_lag_t *lag) {
+    consumer->lag = lag;
+}
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,103 @@
+/**
+ * @file consumer_lag.h
+ * @brief Per-consumer stream lag telemetry
+ *
+ * Each stream consumer (audio/TX, remote, decoder, recorder, display)
+ * registers a named slot once and attaches it to its consumer; every
+ * tick that finds data records the lag, skips record the samples
+ * dropped. "stats stream" lists the slots, so the consumer that falls
+ * behind when a fault occurs is visible.
+ *
+ * Same model as rt_stats: one writer per slot (the consumer's task),
+ * any number of readers, relaxed atomics. Reset is requested by the
+ * reader and performed by the writer on its next record.
+ */
+
+#ifndef KEYER_CONSUMER_LAG_H
+#define KEYER_CONSUMER_LAG_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <stdbool.h>
+#include <stdatomic.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Registry slots */
+#define CONSUMER_LAG_MAX 8
+
+/** Halve lag sum/count when count reaches this value (keeps sum in 32 bits) */
+#define CONSUMER_LAG_AVG_WINDOW (1u << 20)
+
+/**
+ * @brief Lag statistics of one consumer
+ */
+typedef struct {
+    _Atomic(const char *) name;     /**< Registered name (NULL = free slot) */
+    atomic_uint lag_max;            /**< Worst lag seen (samples) */
+    atomic_uint lag_sum;            /**< Sum of lags (for the mean) */
+    atomic_uint lag_count;          /**< Number of lags in sum */
+    atomic_uint dropped;            /**< Samples skipped or lost */
+    atomic_bool reset_request;      /**< Set by reader, cleared by writer */
+} consumer_lag_t;
+
+/**
+ * @brief Get the slot of a consumer, registering it on first use
+ *
+ * Call at init, not per tick. Registering a name again returns the same
+ * slot (a consumer re-initialized keeps its history).
+ *
+ * @param name Consumer name (static string)
+ * @return Slot, NULL if the registry is full
+ */
+consumer_lag_t *consumer_lag_register(const char *name);
+
+/**
+ * @brief Number of registered slots
+ */
+size_t consumer_lag_count(void);
+
+/**
+ * @brief Slot by index
+ *
+ * @param index 0..consumer_lag_count()-1
+ * @return Slot, NULL if out of range
+ */
+const consumer_lag_t *consumer_lag_get(size_t index);
+
+/**
+ * @brief Record the lag seen by one tick (writer only, NULL is a no-op)
+ *
+ * @param lag Slot
+ * @param samples Lag in samples
+ */
+void consumer_lag_record(consumer_lag_t *lag, size_t samples);
+
+/**
+ * @brief Record skipped or lost samples (writer only, NULL is a no-op)
+ *
+ * @param lag Slot
+ * @param samples Samples dropped
+ */
+void consumer_lag_drop(consumer_lag_t *lag, size_t samples);
+
+/**
+ * @brief Ask every writer to reset its counters
+ */
+void consumer_lag_request_reset(void);
+
+/**
+ * @brief Mean lag
+ *
+ * @param lag Slot
+ * @return Mean lag in hundredths of a sample (0 if no records)
+ */
+uint32_t consumer_lag_mean_x100(const consumer_lag_t *lag);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CONSUMER_LAG_H */
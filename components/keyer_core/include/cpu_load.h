@@ -0,0 +1,85 @@
+/**
+ * @file cpu_load.h
+ * @brief Per-core CPU load over a rolling window
+ *
+ * Load is derived from how long each core's idle task ran since the
+ * previous sample: busy = elapsed - idle. Light sleep counts as idle.
+ * bg_task takes one sample per second and keeps the last
+ * CPU_LOAD_WINDOW of them per core.
+ *
+ * Single writer (bg_task), any number of readers (console, web UI).
+ * Published values are atomics; the window itself is writer-only.
+ */
+
+#ifndef KEYER_CPU_LOAD_H
+#define KEYER_CPU_LOAD_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stdatomic.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Cores measured */
+#define CPU_LOAD_CORES 2
+
+/** Samples in the rolling window (one per second) */
+#define CPU_LOAD_WINDOW 10
+
+/**
+ * @brief CPU load state
+ */
+typedef struct {
+    int64_t     last_at_us;                     /**< Previous sample (0 = none yet) */
+    uint32_t    last_idle_us[CPU_LOAD_CORES];   /**< Idle run time at the previous sample */
+    uint8_t     window[CPU_LOAD_CORES][CPU_LOAD_WINDOW]; /**< Load %, writer only */
+    atomic_uint samples;                        /**< Samples taken (window fill) */
+    atomic_uint now_pct[CPU_LOAD_CORES];        /**< Last sample */
+    atomic_uint avg_pct[CPU_LOAD_CORES];        /**< Window average */
+    atomic_uint peak_pct[CPU_LOAD_CORES];       /**< Window maximum */
+} cpu_load_t;
+
+/**
+ * @brief Initialize (no samples)
+ */
+void cpu_load_init(cpu_load_t *load);
+
+/**
+ * @brief Take one sample (writer only)
+ *
+ * The first call only sets the reference point.
+ *
+ * @param load CPU load state
+ * @param now_us Current time
+ * @param idle_us Run time of each core's idle task in µs (may wrap)
+ */
+void cpu_load_update(cpu_load_t *load, int64_t now_us,
+                     const uint32_t idle_us[CPU_LOAD_CORES]);
+
+/**
+ * @brief Load of the last sample (percent)
+ */
+uint32_t cpu_load_now(const cpu_load_t *load, uint32_t core);
+
+/**
+ * @brief Average load over the window (percent)
+ */
+uint32_t cpu_load_avg(const cpu_load_t *load, uint32_t core);
+
+/**
+ * @brief Highest sample in the window (percent)
+ */
+uint32_t cpu_load_peak(const cpu_load_t *load, uint32_t core);
+
+/**
+ * @brief Whether at least one sample has been taken
+ */
+bool cpu_load_valid(const cpu_load_t *load);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CPU_LOAD_H */
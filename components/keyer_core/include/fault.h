@@ -35,8 +35,34 @@ typedef enum {
     FAULT_LATENCY_EXCEEDED = 2,  /**< Hard RT latency deadline missed */
     FAULT_PRODUCER_OVERRUN = 3,  /**< Stream buffer full */
     FAULT_HARDWARE = 4,          /**< Hardware failure detected */
+    FAULT_LOW_VOLTAGE = 5,       /**< Supply below the critical threshold (data = mV) */
+    FAULT_LINK_LOSS = 6,         /**< CWNet link dropped while in use */
+    FAULT_AUDIO_UNDERRUN = 7,    /**< Sidetone write to the codec failed */
+    FAULT_NVS = 8,               /**< Stored config unreadable or a commit failed */
+    FAULT_WATCHDOG = 9,          /**< Previous boot ended in a watchdog reset */
+    FAULT_BROWNOUT = 10,         /**< Previous boot ended in a brownout reset */
+    FAULT_CODEC = 11,            /**< Codec did not initialize (data = esp_err_t) */
+    FAULT_GPIO_SELFTEST = 12,    /**< TX/PTT output loopback failed, may be stuck keyed */
+    FAULT_PIN_CONFLICT = 13,     /**< GPIO assigned twice, later user not started (data = GPIO) */
+    FAULT_CODE_COUNT             /**< Number of codes (not a code) */
 } fault_code_t;
 
+/**
+ * @brief How bad a fault is
+ *
+ * Only CRITICAL stops TX and audio (fault_is_active()). The others are
+ * recorded and shown, and keying goes on.
+ */
+typedef enum {
+    FAULT_SEVERITY_NONE = 0,     /**< FAULT_NONE */
+    FAULT_SEVERITY_INFO = 1,     /**< Past event (reset cause), nothing to do now */
+    FAULT_SEVERITY_WARNING = 2,  /**< Degraded (no link, no sidetone), keying works */
+    FAULT_SEVERITY_CRITICAL = 3, /**< Timing or power unsafe: output stopped */
+} fault_severity_t;
+
+/** Bit of a code in fault_get_raised() */
+#define FAULT_BIT(code) (1U << (unsigned)(code))
+
 /* ============================================================================
  * Fault State
  * ============================================================================ */
@@ -44,13 +70,17 @@ typedef enum {
 /**
  * @brief Atomic fault state
  *
- * All fields are atomic for lock-free access.
+ * All fields are atomic for lock-free access. code and data describe
+ * the latest fault, except that a lower severity never replaces an
+ * active critical one; raised keeps every code seen since the last
+ * clear.
  */
 typedef struct {
-    atomic_bool  active;   /**< Fault active flag */
+    atomic_bool  active;   /**< Critical fault active: output stopped */
     atomic_uchar code;     /**< Fault code (fault_code_t) */
     atomic_uint  data;     /**< Additional fault data (e.g., lag value) */
     atomic_uint  count;    /**< Fault occurrence counter */
+    atomic_uint  raised;   /**< FAULT_BIT() of every code set since the last clear */
 } fault_state_t;
 
 /**
@@ -60,7 +90,8 @@ typedef struct {
     .active = ATOMIC_VAR_INIT(false), \
     .code = ATOMIC_VAR_INIT(0), \
     .data = ATOMIC_VAR_INIT(0), \
-    .count = ATOMIC_VAR_INIT(0) \
+    .count = ATOMIC_VAR_INIT(0), \
+    .raised = ATOMIC_VAR_INIT(0) \
 }
 
 /**
@@ -73,8 +104,9 @@ void fault_init(fault_state_t *fault);
 /**
  * @brief Set fault state
  *
- * Called when a fault condition is detected. Sets active flag,
- * stores code and data, increments counter.
+ * Called when a fault condition is detected. Stores code and data,
+ * increments counter, and for a CRITICAL code sets the active flag.
+ * RT-safe.
  *
  * @param fault Fault state
  * @param code Fault code
@@ -83,7 +115,7 @@ void fault_init(fault_state_t *fault);
 void fault_set(fault_state_t *fault, fault_code_t code, uint32_t data);
 
 /**
- * @brief Check if fault is active
+ * @brief Check if a critical fault is active (TX and audio stopped)
  *
  * @param fault Fault state
  * @return true if fault is active
@@ -122,6 +154,26 @@ static inline uint32_t fault_get_count(const fault_state_t *fault) {
     return atomic_load_explicit(&fault->count, memory_order_relaxed);
 }
 
+/**
+ * @brief Get every code set since the last clear
+ *
+ * @param fault Fault state
+ * @return FAULT_BIT() mask
+ */
+static inline uint32_t fault_get_raised(const fault_state_t *fault) {
+    return atomic_load_explicit(&fault->raised, memory_order_relaxed);
+}
+
+/**
+ * @brief Check if a code was set since the last clear
+ *
+ * @param fault Fault state
+ * @param code Fault code
+ */
+static inline bool fault_has(const fault_state_t *fault, fault_code_t code) {
+    return (fault_get_raised(fault) & FAULT_BIT(code)) != 0;
+}
+
 /**
  * @brief Clear fault state
  *
@@ -131,6 +183,34 @@ static inline uint32_t fault_get_count(const fault_state_t *fault) {
  */
 void fault_clear(fault_state_t *fault);
 
+/**
+ * @brief Mark one non-critical condition as gone (link back up)
+ *
+ * Drops it from the raised mask, and from code/data if it is the
+ * latest. A critical fault is only cleared by fault_clear().
+ *
+ * @param fault Fault state
+ * @param code Fault code
+ */
+void fault_resolve(fault_state_t *fault, fault_code_t code);
+
+/**
+ * @brief Severity of a fault code
+ *
+ * @param code Fault code
+ * @return Severity (FAULT_SEVERITY_NONE for FAULT_NONE or unknown codes)
+ */
+fault_severity_t fault_code_severity(fault_code_t code);
+
+/**
+ * @brief Severity of the latest fault
+ *
+ * @param fault Fault state
+ */
+static inline fault_severity_t fault_get_severity(const fault_state_t *fault) {
+    return fault_code_severity(fault_get_code(fault));
+}
+
 /**
  * @brief Get fault code as string
  *
@@ -139,6 +219,14 @@ void fault_clear(fault_state_t *fault);
  */
 const char *fault_code_str(fault_code_t code);
 
+/**
+ * @brief Get severity as string
+ *
+ * @param severity Severity
+ * @return "INFO", "WARNING", ...
+ */
+const char *fault_severity_str(fault_severity_t severity);
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,106 @@
+/**
+ * @file fault_indicator.h
+ * @brief Fault state to operator indicators
+ *
+ * Picks the fault worth showing and turns it into what the operator
+ * sees and hears without a serial console:
+ * - Status LED blink code: the code number in blinks, then a pause
+ *   (warnings and critical faults only)
+ * - Buzzer chirp when a new warning or critical fault is raised
+ * - Short banner text for the OLED and the network status
+ *
+ * A critical fault (TX stopped) always wins; otherwise the raised fault
+ * of highest severity, lowest code first. Info faults reach the banner
+ * only. A resolved warning that comes back chirps again.
+ *
+ * Pure logic, host-testable. Fed every bg_task tick; the chirp flag is
+ * atomic because the RT task drives the buzzer.
+ */
+
+#ifndef KEYER_FAULT_INDICATOR_H
+#define KEYER_FAULT_INDICATOR_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdatomic.h>
+#include "fault.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Blink code timing */
+#define FAULT_BLINK_ON_US   250000
+#define FAULT_BLINK_OFF_US  250000
+#define FAULT_BLINK_GAP_US  1500000
+
+/** Buzzer chirp length */
+#define FAULT_CHIRP_US      80000
+
+/**
+ * @brief Indicator state
+ */
+typedef struct {
+    fault_code_t code;          /**< Fault shown (FAULT_NONE if none) */
+    fault_severity_t severity;  /**< Its severity */
+    uint32_t announced;         /**< Raised mask already chirped for */
+    int64_t cycle_start_us;     /**< Start of the blink code cycle */
+    int64_t chirp_until_us;     /**< End of the running chirp */
+    atomic_bool chirp;          /**< Buzzer on (read by the RT task) */
+} fault_indicator_t;
+
+/**
+ * @brief Reset the indicator (nothing shown, nothing announced)
+ */
+void fault_indicator_init(fault_indicator_t *ind);
+
+/**
+ * @brief Fault to show for a fault state
+ *
+ * @param fault Fault state
+ * @return Active critical code, else the most severe raised code, else FAULT_NONE
+ */
+fault_code_t fault_indicator_pick(const fault_state_t *fault);
+
+/**
+ * @brief Follow the fault state
+ *
+ * @param ind Indicator
+ * @param fault Fault state
+ * @param now_us Current time
+ * @param chirp_enabled Chirp on newly raised faults (audio.fault_chirp)
+ * @return true if the shown fault changed
+ */
+bool fault_indicator_update(fault_indicator_t *ind, const fault_state_t *fault,
+                            int64_t now_us, bool chirp_enabled);
+
+/**
+ * @brief Status LED phase of the blink code
+ *
+ * @param ind Indicator
+ * @param now_us Current time
+ * @return true while the LED is lit (always false for info faults)
+ */
+bool fault_indicator_blink(const fault_indicator_t *ind, int64_t now_us);
+
+/**
+ * @brief Buzzer chirp in progress (RT-safe)
+ */
+static inline bool fault_indicator_chirping(const fault_indicator_t *ind) {
+    return atomic_load_explicit(&ind->chirp, memory_order_relaxed);
+}
+
+/**
+ * @brief Banner text of the shown fault
+ *
+ * @param ind Indicator
+ * @return Fault name, NULL if none
+ */
+const char *fault_indicator_banner(const fault_indicator_t *ind);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_FAULT_INDICATOR_H */
@@ -0,0 +1,71 @@
+/**
+ * @file idle_sleep.h
+ * @brief Idle timer deciding when to enter and leave light sleep
+ *
+ * Any activity (paddles, console, network) restarts the timer. After
+ * timeout_s without activity the caller is told to go to sleep, and on
+ * the first activity after that to wake up. Entering and leaving sleep
+ * is up to the caller (main/power.c).
+ *
+ * Pure logic, no hardware access.
+ */
+
+#ifndef KEYER_IDLE_SLEEP_H
+#define KEYER_IDLE_SLEEP_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief What the caller has to do
+ */
+typedef enum {
+    IDLE_SLEEP_NONE = 0,    /**< Stay as is */
+    IDLE_SLEEP_ENTER,       /**< Idle for the timeout: go to sleep */
+    IDLE_SLEEP_WAKE,        /**< Activity while asleep: wake up */
+} idle_sleep_action_t;
+
+/**
+ * @brief Idle timer state
+ */
+typedef struct {
+    int64_t last_activity_us;   /**< Time of the last activity */
+    bool asleep;                /**< ENTER reported, no WAKE since */
+} idle_sleep_t;
+
+/**
+ * @brief Initialize (awake, activity now)
+ *
+ * @param idle Timer state
+ * @param now_us Current time
+ */
+void idle_sleep_init(idle_sleep_t *idle, int64_t now_us);
+
+/**
+ * @brief Feed the activity seen since the last call
+ *
+ * @param idle Timer state
+ * @param active Any activity
+ * @param timeout_s Idle time before sleep (0 = never sleep)
+ * @param now_us Current time
+ * @return Action for the caller
+ */
+idle_sleep_action_t idle_sleep_update(idle_sleep_t *idle, bool active,
+                                      uint32_t timeout_s, int64_t now_us);
+
+/**
+ * @brief Check if asleep
+ */
+static inline bool idle_sleep_is_asleep(const idle_sleep_t *idle) {
+    return idle->asleep;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_IDLE_SLEEP_H */
@@ -0,0 +1,60 @@
+/**
+ * @file keyer_clock.h
+ * @brief Monotonic time source for code that reads the time itself
+ *
+ * The keying path does not read a clock: iambic_tick(), ptt_tick() and
+ * the consumers take the time from their caller (rt_task on the target,
+ * the test or the simulator on host), so they run the same on both.
+ * Modules that need "now" on their own (decoder inactivity timeout,
+ * CWNet timers) call keyer_clock_now_us() instead of esp_timer directly.
+ *
+ * On the target main installs esp_timer_get_time() at boot. Without a
+ * source the clock is simulated: it starts at 0 and only moves when
+ * set or advanced, which keeps host tests deterministic.
+ *
+ * The source is set once at boot, before the tasks start.
+ */
+
+#ifndef KEYER_CLOCK_H
+#define KEYER_CLOCK_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Clock source (monotonic, microseconds)
+ */
+typedef int64_t (*keyer_clock_fn)(void);
+
+/**
+ * @brief Install the clock source
+ *
+ * @param source Source, NULL for the simulated clock
+ */
+void keyer_clock_set_source(keyer_clock_fn source);
+
+/**
+ * @brief Current time from the installed source
+ *
+ * @return Microseconds (simulated time when no source is installed)
+ */
+int64_t keyer_clock_now_us(void);
+
+/**
+ * @brief Set the simulated time
+ */
+void keyer_clock_sim_set(int64_t now_us);
+
+/**
+ * @brief Move the simulated time forward
+ */
+void keyer_clock_sim_advance(int64_t delta_us);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CLOCK_H */
@@ -2,7 +2,8 @@
  * @file keyer_core.h
  * @brief Main include file for keyer_core component
  *
- * Includes all core types: stream, sample, consumer, fault.
+ * Includes all core types: stream, sample, consumer (and its lag
+ * telemetry), fault, fault indicator, RT stats, clock source, stress input.
  */
 
 #ifndef KEYER_CORE_H
@@ -11,6 +12,13 @@
 #include "sample.h"
 #include "stream.h"
 #include "consumer.h"
+#include "consumer_lag.h"
 #include "fault.h"
+#include "fault_indicator.h"
+#include "rt_stats.h"
+#include "cpu_load.h"
+#include "keyer_clock.h"
+#include "stress.h"
+#include "config_audit.h"
 
 #endif /* KEYER_CORE_H */
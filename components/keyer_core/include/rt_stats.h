@@ -0,0 +1,125 @@
+/**
+ * @file rt_stats.h
+ * @brief RT loop timing statistics
+ *
+ * Lightweight instrumentation for the hard RT loop: period min/avg/max,
+ * jitter histogram, worst-case push/consume/work durations, missed ticks.
+ *
+ * Single writer (RT task on Core 0), any number of readers (console).
+ * All fields are atomics with relaxed ordering - a reader may see a
+ * snapshot that is a few ticks out of step between fields, which is
+ * fine for diagnostics.
+ *
+ * Reset is requested by the reader and performed by the writer on its
+ * next period record, so the RT task stays the only writer.
+ */
+
+#ifndef KEYER_RT_STATS_H
+#define KEYER_RT_STATS_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stdatomic.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Number of jitter histogram buckets */
+#define RT_STATS_BUCKETS 8
+
+/**
+ * @brief Upper bounds (exclusive, µs) of jitter buckets 0..RT_STATS_BUCKETS-2
+ *
+ * The last bucket collects everything >= the last bound.
+ */
+extern const uint32_t RT_STATS_BUCKET_LIMITS_US[RT_STATS_BUCKETS - 1];
+
+/** Halve period sum/count when count reaches this value (keeps the average recent) */
+#define RT_STATS_AVG_WINDOW (1u << 20)
+
+/**
+ * @brief RT loop statistics
+ */
+typedef struct {
+    uint32_t    nominal_us;                 /**< Nominal loop period (set at init) */
+    atomic_uint period_min_us;              /**< Shortest observed period */
+    atomic_uint period_max_us;              /**< Longest observed period */
+    _Atomic uint64_t period_sum_us;         /**< Sum of periods (for average, 64-bit: no wrap) */
+    atomic_uint period_count;               /**< Number of periods in sum */
+    atomic_uint hist[RT_STATS_BUCKETS];     /**< Jitter histogram (|period - nominal|) */
+    atomic_uint missed_ticks;               /**< Periods >= 1.5x nominal */
+    atomic_uint push_max_us;                /**< Worst-case stream_push duration */
+    atomic_uint consume_max_us;             /**< Worst-case consumer tick duration */
+    atomic_uint work_max_us;                /**< Worst-case loop body duration */
+    atomic_uint press_age_max_us;           /**< Worst-case paddle edge to RT tick */
+    atomic_bool reset_request;              /**< Set by reader, cleared by writer */
+} rt_stats_t;
+
+/**
+ * @brief Initialize statistics
+ *
+ * @param stats Statistics to initialize
+ * @param nominal_us Nominal loop period in microseconds
+ */
+void rt_stats_init(rt_stats_t *stats, uint32_t nominal_us);
+
+/**
+ * @brief Map jitter to histogram bucket
+ *
+ * @param jitter_us Absolute deviation from nominal period
+ * @return Bucket index (0..RT_STATS_BUCKETS-1)
+ */
+uint32_t rt_stats_bucket(uint32_t jitter_us);
+
+/**
+ * @brief Record one loop period (writer only)
+ *
+ * Applies a pending reset first, then updates min/max/avg,
+ * histogram and missed-tick count.
+ *
+ * @param stats Statistics
+ * @param period_us Measured time since previous loop start
+ */
+void rt_stats_record_period(rt_stats_t *stats, uint32_t period_us);
+
+/**
+ * @brief Record stream push duration (writer only)
+ */
+void rt_stats_record_push(rt_stats_t *stats, uint32_t duration_us);
+
+/**
+ * @brief Record consumer tick duration (writer only)
+ */
+void rt_stats_record_consume(rt_stats_t *stats, uint32_t duration_us);
+
+/**
+ * @brief Record loop body duration (writer only)
+ */
+void rt_stats_record_work(rt_stats_t *stats, uint32_t duration_us);
+
+/**
+ * @brief Record how long an ISR-latched paddle press waited (writer only)
+ */
+void rt_stats_record_press_age(rt_stats_t *stats, uint32_t age_us);
+
+/**
+ * @brief Ask the writer to reset all counters
+ *
+ * @param stats Statistics
+ */
+void rt_stats_request_reset(rt_stats_t *stats);
+
+/**
+ * @brief Average loop period
+ *
+ * @param stats Statistics
+ * @return Average period in microseconds (0 if no samples)
+ */
+uint32_t rt_stats_avg_period_us(const rt_stats_t *stats);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_RT_STATS_H */
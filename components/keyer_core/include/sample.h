@@ -25,7 +25,7 @@ extern "C" {
  * ============================================================================ */
 
 /**
- * @brief GPIO paddle state (1 byte)
+ * @brief GPIO paddle and key input state (1 byte)
  */
 typedef struct {
     uint8_t bits;
@@ -33,6 +33,7 @@ typedef struct {
 
 #define GPIO_DIT_BIT  0x01
 #define GPIO_DAH_BIT  0x02
+#define GPIO_KEY_BIT  0x04  /**< Straight key / external keyer input */
 
 /** Idle state - no paddles pressed */
 #define GPIO_IDLE     ((gpio_state_t){.bits = 0})
@@ -50,7 +51,12 @@ static inline bool gpio_dah(gpio_state_t gs) {
     return (gs.bits & GPIO_DAH_BIT) != 0;
 }
 
-/** Check if no paddles pressed */
+/** Check if the straight key input is down */
+static inline bool gpio_key(gpio_state_t gs) {
+    return (gs.bits & GPIO_KEY_BIT) != 0;
+}
+
+/** Check if no paddles pressed and the straight key is up */
 static inline bool gpio_is_idle(gpio_state_t gs) {
     return gs.bits == 0;
 }
@@ -68,6 +74,30 @@ static inline gpio_state_t gpio_from_paddles(bool dit, bool dah) {
     return gs;
 }
 
+/**
+ * @brief Create GPIO state from one snapshot of the input levels
+ *
+ * Both paddles and the key input come from the same read, so a squeeze
+ * is never seen with only one side applied.
+ *
+ * @param levels Pin levels, bit n = GPIOn
+ * @param active_low Bit n set if GPIOn reads 0 when closed
+ * @param dit_pin DIT paddle GPIO
+ * @param dah_pin DAH paddle GPIO
+ * @param key_pin Straight key GPIO (0 = not fitted)
+ */
+static inline gpio_state_t gpio_from_levels(uint64_t levels, uint64_t active_low,
+                                            uint8_t dit_pin, uint8_t dah_pin,
+                                            uint8_t key_pin) {
+    uint64_t closed = levels ^ active_low;
+    gpio_state_t gs = gpio_from_paddles(((closed >> dit_pin) & 1U) != 0,
+                                        ((closed >> dah_pin) & 1U) != 0);
+    if (key_pin != 0 && ((closed >> key_pin) & 1U) != 0) {
+        gs.bits |= GPIO_KEY_BIT;
+    }
+    return gs;
+}
+
 /* ============================================================================
  * Sample Flags
  * ============================================================================ */
@@ -90,6 +120,9 @@ static inline gpio_state_t gpio_from_paddles(bool dit, bool dah) {
 /** Local key state edge (on/off transition) */
 #define FLAG_LOCAL_EDGE     0x20
 
+/** PTT input (footswitch) held: a level, set on every sample while held */
+#define FLAG_PTT_IN         0x40
+
 /* ============================================================================
  * Stream Sample (6 bytes packed)
  * ============================================================================ */
@@ -153,16 +186,35 @@ static inline bool sample_has_local_edge(const stream_sample_t *s) {
     return (s->flags & FLAG_LOCAL_EDGE) != 0;
 }
 
+/** Check if the PTT input was held for this sample */
+static inline bool sample_has_ptt_in(const stream_sample_t *s) {
+    return (s->flags & (FLAG_PTT_IN | FLAG_SILENCE)) == FLAG_PTT_IN;
+}
+
+/**
+ * @brief Check if sample carries a config change
+ *
+ * Set by the stream on the first sample stamped with a new config
+ * generation. Never set on silence markers (config_gen holds ticks there).
+ */
+static inline bool sample_has_config_change(const stream_sample_t *s) {
+    return (s->flags & (FLAG_CONFIG_CHANGE | FLAG_SILENCE)) == FLAG_CONFIG_CHANGE;
+}
+
 /**
  * @brief Check if sample has changed from another
  *
  * Used for silence compression - if no change, increment idle counter.
+ * A new config generation counts as a change so it is never compressed away,
+ * and so does the PTT input level (FLAG_PTT_IN).
  */
 static inline bool sample_has_change_from(const stream_sample_t *a,
                                           const stream_sample_t *b) {
     return a->gpio.bits != b->gpio.bits ||
            a->local_key != b->local_key ||
-           a->audio_level != b->audio_level;
+           a->audio_level != b->audio_level ||
+           a->config_gen != b->config_gen ||
+           ((a->flags ^ b->flags) & FLAG_PTT_IN) != 0;
 }
 
 /**
@@ -0,0 +1,54 @@
+/**
+ * @file straight_key.h
+ * @brief Debounce for the straight key / external keyer input
+ *
+ * The input keys TX directly, so an edge is taken at once and the
+ * contact bounce after it is ignored: for debounce_us after a change the
+ * state holds, then it follows the pin again. A key released before
+ * the lockout ends goes up when the lockout ends.
+ *
+ * Pure logic, RT-safe (no allocation, no locks).
+ */
+
+#ifndef KEYER_STRAIGHT_KEY_H
+#define KEYER_STRAIGHT_KEY_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Straight key input state
+ */
+typedef struct {
+    uint32_t debounce_us;   /**< Lockout after an edge (0 = none) */
+    int64_t changed_at_us;  /**< Time of the last accepted edge */
+    bool down;              /**< Debounced state */
+} straight_key_t;
+
+/**
+ * @brief Initialize (key up)
+ *
+ * @param key Input state
+ * @param debounce_us Lockout after an edge
+ */
+void straight_key_init(straight_key_t *key, uint32_t debounce_us);
+
+/**
+ * @brief Feed the raw pin state
+ *
+ * @param key Input state
+ * @param raw_down Pin reads key down
+ * @param now_us Current time
+ * @return Debounced key state
+ */
+bool straight_key_update(straight_key_t *key, bool raw_down, int64_t now_us);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_STRAIGHT_KEY_H */
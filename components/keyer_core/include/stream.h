@@ -30,6 +30,25 @@
 extern "C" {
 #endif
 
+/* ============================================================================
+ * Sent-Text Markers
+ * ============================================================================ */
+
+/** Sent-text markers kept by the stream (power of 2) */
+#define STREAM_MARKER_CAPACITY 64
+
+/**
+ * @brief Character the text keyer started at a sample
+ *
+ * Side-channel next to the samples: consumers match sample_idx against
+ * their read position to tell text-keyed characters (memories, keyboard)
+ * from hand-keyed ones, and what was meant from what was decoded.
+ */
+typedef struct {
+    size_t sample_idx;      /**< Stream index of the character's first key-down */
+    char   character;       /**< Character being sent */
+} stream_marker_t;
+
 /* ============================================================================
  * KeyingStream - Lock-free SPMC Ring Buffer
  * ============================================================================ */
@@ -41,8 +60,10 @@ extern "C" {
  * All coordination through atomic operations.
  *
  * Memory ordering:
- * - Producer uses memory_order_acq_rel for write_idx.fetch_add()
- * - Consumer uses memory_order_acquire for write_idx.load()
+ * - Producer writes the slot, then stores write_idx with memory_order_release
+ * - Consumer uses memory_order_acquire for write_idx.load(), and rechecks
+ *   write_idx after copying a slot (the producer may lap a slow consumer)
+ * - A slot is readable while it is fewer than capacity samples behind
  *
  * Buffer must be power of 2 for fast modulo via mask.
  */
@@ -53,6 +74,8 @@ typedef struct {
     atomic_size_t    write_idx;   /**< Producer write index (monotonic) */
     atomic_uint_fast32_t idle_ticks; /**< Silence compression counter */
     stream_sample_t  last_sample; /**< Last sample for change detection */
+    stream_marker_t  markers[STREAM_MARKER_CAPACITY]; /**< Sent-text side-channel */
+    atomic_size_t    marker_idx;  /**< Markers written (monotonic) */
 } keying_stream_t;
 
 /**
@@ -140,6 +163,36 @@ size_t stream_lag(const keying_stream_t *stream, size_t read_idx);
  */
 bool stream_is_overrun(const keying_stream_t *stream, size_t read_idx);
 
+/**
+ * @brief Mark the last written sample with a sent-text character
+ *
+ * Producer only (RT thread), right after the push that wrote the
+ * character's first key-down. Never blocks; the oldest marker is
+ * overwritten when consumers fall behind.
+ *
+ * @param stream Stream to mark
+ * @param c Character being sent
+ */
+void stream_mark_text(keying_stream_t *stream, char c);
+
+/**
+ * @brief Read sent-text marker number seq
+ *
+ * @param stream Stream to read from
+ * @param seq Marker sequence number
+ * @param out Output marker (written on success)
+ * @return true if available, false if not yet written or overwritten
+ */
+bool stream_read_marker(const keying_stream_t *stream, size_t seq, stream_marker_t *out);
+
+/**
+ * @brief Get the sequence number the next marker will get
+ *
+ * @param stream Stream to query
+ * @return Markers written since init
+ */
+size_t stream_marker_position(const keying_stream_t *stream);
+
 /**
  * @brief Get buffer capacity
  *
@@ -0,0 +1,87 @@
+/**
+ * @file stress.h
+ * @brief Reproducible stress input for the keying path
+ *
+ * A seeded generator producing, one 1 ms tick at a time, randomized
+ * paddle patterns (including contact glitches), speed changes and
+ * consumer stalls. The same seed always gives the same sequence, on
+ * host and target, so a failing run can be replayed exactly.
+ *
+ * No allocation, no libc beyond integer arithmetic: the generator is a
+ * xorshift32 over a small state struct.
+ */
+
+#ifndef KEYER_STRESS_H
+#define KEYER_STRESS_H
+
+#include <stdint.h>
+#include "sample.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Stress generator configuration
+ */
+typedef struct {
+    uint32_t seed;              /**< RNG seed (0 is replaced by a fixed value) */
+    uint32_t wpm_min;           /**< Speed change range */
+    uint32_t wpm_max;
+    uint32_t speed_change_ms;   /**< Mean time between speed changes, 0 = never */
+    uint32_t stall_permille;    /**< Chance per tick of a consumer stall (0-1000) */
+    uint32_t stall_max_ticks;   /**< Longest stall */
+} stress_config_t;
+
+/**
+ * @brief Default configuration
+ */
+#define STRESS_CONFIG_DEFAULT { \
+    .seed = 1, \
+    .wpm_min = 10, \
+    .wpm_max = 50, \
+    .speed_change_ms = 2000, \
+    .stall_permille = 2, \
+    .stall_max_ticks = 8 \
+}
+
+/**
+ * @brief What happens on one tick
+ */
+typedef struct {
+    gpio_state_t paddles;       /**< Paddle state for this tick */
+    uint32_t wpm;               /**< New speed, 0 = unchanged */
+    uint32_t stall_ticks;       /**< Consumer stall starting now, 0 = none */
+} stress_event_t;
+
+/**
+ * @brief Generator state
+ */
+typedef struct {
+    stress_config_t config;
+    uint32_t rng;               /**< xorshift32 state (never 0) */
+    gpio_state_t paddles;       /**< Current paddle pattern */
+    uint32_t hold_ticks;        /**< Ticks left in the current pattern */
+    uint32_t speed_ticks;       /**< Ticks left to the next speed change */
+} stress_gen_t;
+
+/**
+ * @brief Initialize the generator
+ */
+void stress_init(stress_gen_t *gen, const stress_config_t *config);
+
+/**
+ * @brief Next pseudo-random number (xorshift32)
+ */
+uint32_t stress_random(stress_gen_t *gen);
+
+/**
+ * @brief Produce the next tick
+ */
+void stress_next(stress_gen_t *gen, stress_event_t *event);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_STRESS_H */
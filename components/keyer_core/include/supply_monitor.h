@@ -0,0 +1,84 @@
+/**
+ * @file supply_monitor.h
+ * @brief Supply voltage filtering and low-voltage levels
+ *
+ * Turns divider tap readings into the supply voltage, smooths it (a TX
+ * load can pull a battery down for a moment) and classifies it against
+ * two thresholds: LOW warns, CRITICAL raises FAULT_LOW_VOLTAGE so TX
+ * stops before the rig or the keyer browns out. Leaving a level needs
+ * SUPPLY_MONITOR_HYSTERESIS_MV of margin, so a rail sitting on a
+ * threshold does not flap.
+ *
+ * Pure logic, host-testable. Fed once a second from bg_task.
+ */
+
+#ifndef KEYER_SUPPLY_MONITOR_H
+#define KEYER_SUPPLY_MONITOR_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Margin over a threshold needed to leave its level */
+#define SUPPLY_MONITOR_HYSTERESIS_MV 100
+
+/**
+ * @brief Supply level
+ */
+typedef enum {
+    SUPPLY_OK = 0,          /**< Above both thresholds */
+    SUPPLY_LOW,             /**< Below the warning threshold */
+    SUPPLY_CRITICAL,        /**< Below the critical threshold */
+} supply_level_t;
+
+/**
+ * @brief Monitor state
+ */
+typedef struct {
+    uint32_t filtered_mv;   /**< Smoothed supply voltage */
+    bool primed;            /**< First reading taken */
+    supply_level_t level;   /**< Current level */
+} supply_monitor_t;
+
+/**
+ * @brief Reset the monitor
+ */
+void supply_monitor_init(supply_monitor_t *mon);
+
+/**
+ * @brief Supply voltage from the divider tap voltage
+ *
+ * @param pin_mv Millivolts at the ADC pin
+ * @param divider_x100 Divider ratio x100 (200 = halved)
+ * @return Supply millivolts
+ */
+uint32_t supply_monitor_scale(uint32_t pin_mv, uint16_t divider_x100);
+
+/**
+ * @brief Feed a supply reading and classify it
+ *
+ * The first reading primes the filter, so a supply that is already low
+ * at boot is reported at once.
+ *
+ * @param mon Monitor
+ * @param supply_mv Supply millivolts (supply_monitor_scale())
+ * @param low_mv Warning threshold (0 = off)
+ * @param critical_mv Critical threshold (0 = off)
+ * @return Level after this reading
+ */
+supply_level_t supply_monitor_update(supply_monitor_t *mon, uint32_t supply_mv,
+                                     uint16_t low_mv, uint16_t critical_mv);
+
+/**
+ * @brief Level name for logs and the console
+ */
+const char *supply_level_str(supply_level_t level);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_SUPPLY_MONITOR_H */
@@ -0,0 +1,60 @@
+/**
+ * @file touch_paddle.h
+ * @brief Touch pad detection for capacitive paddles
+ *
+ * Turns raw touch sensor readings into pressed/released. The baseline is
+ * measured once with the pads untouched; a reading that rises by the
+ * sensitivity (percent of the baseline) is a touch, and it is released
+ * again below half that rise, so a finger resting at the edge of the
+ * threshold does not chatter. Readings rise on touch on the ESP32-S2/S3.
+ *
+ * Pure logic, RT-safe (no allocation, no locks).
+ */
+
+#ifndef KEYER_TOUCH_PADDLE_H
+#define KEYER_TOUCH_PADDLE_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Sensitivity range (percent of the baseline) */
+#define TOUCH_PADDLE_SENSITIVITY_MIN 5
+#define TOUCH_PADDLE_SENSITIVITY_MAX 100
+
+/**
+ * @brief One touch pad
+ */
+typedef struct {
+    uint32_t baseline;      /**< Untouched reading */
+    uint32_t on_delta;      /**< Rise over the baseline that is a touch */
+    bool touched;           /**< Current state */
+} touch_paddle_t;
+
+/**
+ * @brief Set the untouched level and the sensitivity
+ *
+ * @param pad Pad
+ * @param baseline Average reading with the pad untouched
+ * @param sensitivity_pct Rise that counts as a touch, percent of the
+ *        baseline (clamped to TOUCH_PADDLE_SENSITIVITY_MIN..MAX)
+ */
+void touch_paddle_calibrate(touch_paddle_t *pad, uint32_t baseline, uint8_t sensitivity_pct);
+
+/**
+ * @brief Feed a raw reading
+ *
+ * @param pad Pad
+ * @param raw Raw touch sensor reading
+ * @return true while touched
+ */
+bool touch_paddle_update(touch_paddle_t *pad, uint32_t raw);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_TOUCH_PADDLE_H */
@@ -0,0 +1,72 @@
+/**
+ * @file config_audit.c
+ * @brief Ring of recent configuration changes implementation
+ */
+
+#include "config_audit.h"
+#include <string.h>
+
+static void copy_str(char *dst, size_t len, const char *src) {
+    strncpy(dst, (src != NULL) ? src : "", len - 1);
+    dst[len - 1] = '\0';
+}
+
+void config_audit_init(config_audit_t *audit) {
+    atomic_init(&audit->head, 0);
+    for (size_t i = 0; i < CONFIG_AUDIT_SIZE; i++) {
+        atomic_init(&audit->slots[i].seq, 0);
+        memset(&audit->slots[i].entry, 0, sizeof(audit->slots[i].entry));
+    }
+}
+
+void config_audit_record(config_audit_t *audit, config_audit_source_t source,
+                         const char *who, const char *path,
+                         const char *old_value, const char *new_value, int64_t now_us) {
+    unsigned n = atomic_fetch_add_explicit(&audit->head, 1, memory_order_relaxed);
+    config_audit_slot_t *slot = &audit->slots[n % CONFIG_AUDIT_SIZE];
+
+    /* Odd sequence: readers drop the slot until the entry is complete */
+    atomic_store_explicit(&slot->seq, 2U * n + 1U, memory_order_relaxed);
+    atomic_thread_fence(memory_order_release);
+
+    config_audit_entry_t *e = &slot->entry;
+    e->time_us = now_us;
+    e->source = source;
+    copy_str(e->who, sizeof(e->who), who);
+    copy_str(e->path, sizeof(e->path), path);
+    copy_str(e->old_value, sizeof(e->old_value), old_value);
+    copy_str(e->new_value, sizeof(e->new_value), new_value);
+
+    atomic_store_explicit(&slot->seq, 2U * n + 2U, memory_order_release);
+}
+
+size_t config_audit_count(const config_audit_t *audit) {
+    unsigned head = atomic_load_explicit(&audit->head, memory_order_acquire);
+    return (head < CONFIG_AUDIT_SIZE) ? head : CONFIG_AUDIT_SIZE;
+}
+
+bool config_audit_get(const config_audit_t *audit, size_t index, config_audit_entry_t *out) {
+    unsigned head = atomic_load_explicit(&audit->head, memory_order_acquire);
+    size_t held = (head < CONFIG_AUDIT_SIZE) ? head : CONFIG_AUDIT_SIZE;
+    if (out == NULL || index >= held) {
+        return false;
+    }
+
+    unsigned n = head - 1U - (unsigned)index;
+    const config_audit_slot_t *slot = &audit->slots[n % CONFIG_AUDIT_SIZE];
+    unsigned before = atomic_load_explicit(&slot->seq, memory_order_acquire);
+    if (before != 2U * n + 2U) {
+        return false;  /* Still being written, or already reused */
+    }
+    memcpy(out, &slot->entry, sizeof(*out));
+    atomic_thread_fence(memory_order_acquire);
+    return atomic_load_explicit(&slot->seq, memory_order_relaxed) == before;
+}
+
+const char *config_audit_source_str(config_audit_source_t source) {
+    switch (source) {
+        case CONFIG_AUDIT_CONSOLE: return "console";
+        case CONFIG_AUDIT_WEB:     return "web";
+        default:                   return "?";
+    }
+}
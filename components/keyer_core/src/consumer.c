@@ -27,6 +27,7 @@ void hard_rt_consumer_init(hard_rt_consumer_t *consumer,
     consumer->fault = fault;
     consumer->read_idx = stream_write_position(stream);
     consumer->max_lag = max_lag;
+    consumer->lag = NULL;
 }
 
 hard_rt_result_t hard_rt_consumer_tick(hard_rt_consumer_t *consumer,
@@ -41,6 +42,9 @@ hard_rt_result_t hard_rt_consumer_tick(hard_rt_consumer_t *consumer,
 
     /* Check lag */
     size_t lag = stream_lag(consumer->stream, consumer->read_idx);
+    if (lag > 0) {
+        consumer_lag_record(consumer->lag, lag);
+    }
 
     if (lag > consumer->max_lag) {
         /* FAULT: latency exceeded */
@@ -95,6 +99,7 @@ void best_effort_consumer_init(best_effort_consumer_t *consumer,
     consumer->read_idx = stream_write_position(stream);
     consumer->dropped = 0;
     consumer->skip_threshold = skip_threshold;
+    consumer->lag = NULL;
 }
 
 bool best_effort_consumer_tick(best_effort_consumer_t *consumer,
@@ -109,6 +114,7 @@ bool best_effort_consumer_tick(best_effort_consumer_t *consumer,
         /* Caught up - no new data */
         return false;
     }
+    consumer_lag_record(consumer->lag, lag);
 
     /* Check for overrun or lag threshold exceeded */
     if (stream_is_overrun(consumer->stream, consumer->read_idx) ||
@@ -122,6 +128,7 @@ bool best_effort_consumer_tick(best_effort_consumer_t *consumer,
         size_t skipped = skip_to - consumer->read_idx;
         consumer->dropped += skipped;
         consumer->read_idx = skip_to;
+        consumer_lag_drop(consumer->lag, skipped);
 
         /* Recalculate lag after skip */
         lag = stream_lag(consumer->stream, consumer->read_idx);
@@ -135,6 +142,7 @@ bool best_effort_consumer_tick(best_effort_consumer_t *consumer,
         /* Buffer might have wrapped during our calculations - skip again */
         consumer->read_idx = stream_write_position(consumer->stream);
         consumer->dropped++;
+        consumer_lag_drop(consumer->lag, 1);
         return false;
     }
 
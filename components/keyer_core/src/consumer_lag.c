@@ -0,0 +1,107 @@
+/**
+ * @file consumer_lag.c
+ * @brief Per-consumer lag telemetry implementation
+ */
+
+#include "consumer_lag.h"
+#include <string.h>
+
+static consumer_lag_t s_slots[CONSUMER_LAG_MAX];
+static atomic_uint s_count = 0;
+
+static void clear(consumer_lag_t *lag) {
+    atomic_store_explicit(&lag->lag_max, 0, memory_order_relaxed);
+    atomic_store_explicit(&lag->lag_sum, 0, memory_order_relaxed);
+    atomic_store_explicit(&lag->lag_count, 0, memory_order_relaxed);
+    atomic_store_explicit(&lag->dropped, 0, memory_order_relaxed);
+}
+
+/** Apply a pending reset (writer side) */
+static void check_reset(consumer_lag_t *lag) {
+    if (atomic_load_explicit(&lag->reset_request, memory_order_acquire)) {
+        clear(lag);
+        atomic_store_explicit(&lag->reset_request, false, memory_order_release);
+    }
+}
+
+static uint32_t clamp_u32(size_t value) {
+    return (value > UINT32_MAX) ? UINT32_MAX : (uint32_t)value;
+}
+
+consumer_lag_t *consumer_lag_register(const char *name) {
+    size_t count = consumer_lag_count();
+    for (size_t i = 0; i < count; i++) {
+        const char *slot_name = atomic_load_explicit(&s_slots[i].name, memory_order_acquire);
+        if (slot_name != NULL && strcmp(slot_name, name) == 0) {
+            return &s_slots[i];
+        }
+    }
+
+    unsigned index = atomic_fetch_add_explicit(&s_count, 1, memory_order_relaxed);
+    if (index >= CONSUMER_LAG_MAX) {
+        atomic_store_explicit(&s_count, CONSUMER_LAG_MAX, memory_order_relaxed);
+        return NULL;
+    }
+    consumer_lag_t *lag = &s_slots[index];
+    clear(lag);
+    atomic_store_explicit(&lag->reset_request, false, memory_order_relaxed);
+    atomic_store_explicit(&lag->name, name, memory_order_release);
+    return lag;
+}
+
+size_t consumer_lag_count(void) {
+    unsigned count = atomic_load_explicit(&s_count, memory_order_relaxed);
+    return (count > CONSUMER_LAG_MAX) ? CONSUMER_LAG_MAX : count;
+}
+
+const consumer_lag_t *consumer_lag_get(size_t index) {
+    if (index >= consumer_lag_count()) {
+        return NULL;
+    }
+    return &s_slots[index];
+}
+
+void consumer_lag_record(consumer_lag_t *lag, size_t samples) {
+    if (lag == NULL) {
+        return;
+    }
+    check_reset(lag);
+
+    uint32_t value = clamp_u32(samples);
+    if (value > atomic_load_explicit(&lag->lag_max, memory_order_relaxed)) {
+        atomic_store_explicit(&lag->lag_max, value, memory_order_relaxed);
+    }
+
+    uint32_t sum = atomic_load_explicit(&lag->lag_sum, memory_order_relaxed);
+    uint32_t count = atomic_load_explicit(&lag->lag_count, memory_order_relaxed);
+    if (count >= CONSUMER_LAG_AVG_WINDOW) {
+        sum /= 2;
+        count /= 2;
+    }
+    atomic_store_explicit(&lag->lag_sum, sum + value, memory_order_relaxed);
+    atomic_store_explicit(&lag->lag_count, count + 1, memory_order_relaxed);
+}
+
+void consumer_lag_drop(consumer_lag_t *lag, size_t samples) {
+    if (lag == NULL) {
+        return;
+    }
+    check_reset(lag);
+    atomic_fetch_add_explicit(&lag->dropped, clamp_u32(samples), memory_order_relaxed);
+}
+
+void consumer_lag_request_reset(void) {
+    size_t count = consumer_lag_count();
+    for (size_t i = 0; i < count; i++) {
+        atomic_store_explicit(&s_slots[i].reset_request, true, memory_order_release);
+    }
+}
+
+uint32_t consumer_lag_mean_x100(const consumer_lag_t *lag) {
+    uint32_t count = atomic_load_explicit(&lag->lag_count, memory_order_relaxed);
+    if (count == 0) {
+        return 0;
+    }
+    uint64_t sum = atomic_load_explicit(&lag->lag_sum, memory_order_relaxed);
+    return (uint32_t)(sum * 100U / count);
+}
@@ -0,0 +1,86 @@
+/**
+ * @file cpu_load.c
+ * @brief Per-core CPU load implementation
+ */
+
+#include "cpu_load.h"
+#include <string.h>
+
+void cpu_load_init(cpu_load_t *load) {
+    load->last_at_us = 0;
+    atomic_init(&load->samples, 0);
+    memset(load->last_idle_us, 0, sizeof(load->last_idle_us));
+    memset(load->window, 0, sizeof(load->window));
+    for (uint32_t core = 0; core < CPU_LOAD_CORES; core++) {
+        atomic_init(&load->now_pct[core], 0);
+        atomic_init(&load->avg_pct[core], 0);
+        atomic_init(&load->peak_pct[core], 0);
+    }
+}
+
+/**
+ * @brief Busy share of an interval, in percent
+ */
+static uint8_t busy_percent(uint32_t idle_us, uint32_t elapsed_us) {
+    if (elapsed_us == 0 || idle_us >= elapsed_us) {
+        return 0;
+    }
+    return (uint8_t)((uint64_t)(elapsed_us - idle_us) * 100U / elapsed_us);
+}
+
+void cpu_load_update(cpu_load_t *load, int64_t now_us,
+                     const uint32_t idle_us[CPU_LOAD_CORES]) {
+    if (load->last_at_us == 0 || now_us <= load->last_at_us) {
+        load->last_at_us = now_us;
+        memcpy(load->last_idle_us, idle_us, sizeof(load->last_idle_us));
+        return;
+    }
+
+    uint32_t elapsed_us = (uint32_t)(now_us - load->last_at_us);
+    uint32_t samples = atomic_load_explicit(&load->samples, memory_order_relaxed);
+    uint32_t slot = samples % CPU_LOAD_WINDOW;
+    samples++;
+    uint32_t filled = (samples < CPU_LOAD_WINDOW) ? samples : CPU_LOAD_WINDOW;
+
+    for (uint32_t core = 0; core < CPU_LOAD_CORES; core++) {
+        /* Run-time counters wrap: unsigned difference */
+        uint32_t idle = idle_us[core] - load->last_idle_us[core];
+        uint8_t pct = busy_percent(idle, elapsed_us);
+        load->window[core][slot] = pct;
+
+        uint32_t sum = 0;
+        uint32_t peak = 0;
+        for (uint32_t i = 0; i < filled; i++) {
+            sum += load->window[core][i];
+            if (load->window[core][i] > peak) {
+                peak = load->window[core][i];
+            }
+        }
+        atomic_store_explicit(&load->now_pct[core], pct, memory_order_relaxed);
+        atomic_store_explicit(&load->avg_pct[core], sum / filled, memory_order_relaxed);
+        atomic_store_explicit(&load->peak_pct[core], peak, memory_order_relaxed);
+    }
+
+    load->last_at_us = now_us;
+    memcpy(load->last_idle_us, idle_us, sizeof(load->last_idle_us));
+    atomic_store_explicit(&load->samples, samples, memory_order_relaxed);
+}
+
+uint32_t cpu_load_now(const cpu_load_t *load, uint32_t core) {
+    return (core < CPU_LOAD_CORES)
+               ? atomic_load_explicit(&load->now_pct[core], memory_order_relaxed) : 0;
+}
+
+uint32_t cpu_load_avg(const cpu_load_t *load, uint32_t core) {
+    return (core < CPU_LOAD_CORES)
+               ? atomic_load_explicit(&load->avg_pct[core], memory_order_relaxed) : 0;
+}
+
+uint32_t cpu_load_peak(const cpu_load_t *load, uint32_t core) {
+    return (core < CPU_LOAD_CORES)
+               ? atomic_load_explicit(&load->peak_pct[core], memory_order_relaxed) : 0;
+}
+
+bool cpu_load_valid(const cpu_load_t *load) {
+    return atomic_load_explicit(&load->samples, memory_order_relaxed) > 0;
+}
@@ -14,18 +14,29 @@ void fault_init(fault_state_t *fault) {
     atomic_init(&fault->code, (unsigned char)FAULT_NONE);
     atomic_init(&fault->data, 0);
     atomic_init(&fault->count, 0);
+    atomic_init(&fault->raised, 0);
 }
 
 void fault_set(fault_state_t *fault, fault_code_t code, uint32_t data) {
-    /* Store fault info */
-    atomic_store_explicit(&fault->code, (unsigned char)code, memory_order_relaxed);
-    atomic_store_explicit(&fault->data, data, memory_order_relaxed);
+    atomic_fetch_or_explicit(&fault->raised, FAULT_BIT(code), memory_order_relaxed);
 
     /* Increment counter */
     atomic_fetch_add_explicit(&fault->count, 1, memory_order_relaxed);
 
+    /* A warning never hides the critical fault that stopped output */
+    bool critical = fault_code_severity(code) == FAULT_SEVERITY_CRITICAL;
+    if (!critical && fault_is_active(fault)) {
+        return;
+    }
+
+    /* Store fault info */
+    atomic_store_explicit(&fault->code, (unsigned char)code, memory_order_relaxed);
+    atomic_store_explicit(&fault->data, data, memory_order_relaxed);
+
     /* Set active flag last (with release to ensure visibility) */
-    atomic_store_explicit(&fault->active, true, memory_order_release);
+    if (critical) {
+        atomic_store_explicit(&fault->active, true, memory_order_release);
+    }
 }
 
 void fault_clear(fault_state_t *fault) {
@@ -35,6 +46,45 @@ void fault_clear(fault_state_t *fault) {
     /* Clear code and data */
     atomic_store_explicit(&fault->code, (unsigned char)FAULT_NONE, memory_order_relaxed);
     atomic_store_explicit(&fault->data, 0, memory_order_relaxed);
+    atomic_store_explicit(&fault->raised, 0, memory_order_relaxed);
+}
+
+void fault_resolve(fault_state_t *fault, fault_code_t code) {
+    if (fault_code_severity(code) == FAULT_SEVERITY_CRITICAL) {
+        return;
+    }
+    atomic_fetch_and_explicit(&fault->raised, ~FAULT_BIT(code), memory_order_relaxed);
+
+    unsigned char expected = (unsigned char)code;
+    if (atomic_compare_exchange_strong_explicit(&fault->code, &expected,
+                                                (unsigned char)FAULT_NONE,
+                                                memory_order_relaxed,
+                                                memory_order_relaxed)) {
+        atomic_store_explicit(&fault->data, 0, memory_order_relaxed);
+    }
+}
+
+fault_severity_t fault_code_severity(fault_code_t code) {
+    switch (code) {
+        case FAULT_OVERRUN:
+        case FAULT_LATENCY_EXCEEDED:
+        case FAULT_PRODUCER_OVERRUN:
+        case FAULT_HARDWARE:
+        case FAULT_LOW_VOLTAGE:
+        case FAULT_GPIO_SELFTEST:
+            return FAULT_SEVERITY_CRITICAL;
+        case FAULT_LINK_LOSS:
+        case FAULT_AUDIO_UNDERRUN:
+        case FAULT_NVS:
+        case FAULT_CODEC:
+        case FAULT_PIN_CONFLICT:
+            return FAULT_SEVERITY_WARNING;
+        case FAULT_WATCHDOG:
+        case FAULT_BROWNOUT:
+            return FAULT_SEVERITY_INFO;
+        default:
+            return FAULT_SEVERITY_NONE;
+    }
 }
 
 const char *fault_code_str(fault_code_t code) {
@@ -44,6 +94,25 @@ const char *fault_code_str(fault_code_t code) {
         case FAULT_LATENCY_EXCEEDED: return "LATENCY_EXCEEDED";
         case FAULT_PRODUCER_OVERRUN: return "PRODUCER_OVERRUN";
         case FAULT_HARDWARE:         return "HARDWARE";
+        case FAULT_LOW_VOLTAGE:      return "LOW_VOLTAGE";
+        case FAULT_LINK_LOSS:        return "LINK_LOSS";
+        case FAULT_AUDIO_UNDERRUN:   return "AUDIO_UNDERRUN";
+        case FAULT_NVS:              return "NVS";
+        case FAULT_WATCHDOG:         return "WATCHDOG";
+        case FAULT_BROWNOUT:         return "BROWNOUT";
+        case FAULT_CODEC:            return "CODEC";
+        case FAULT_GPIO_SELFTEST:    return "GPIO_SELFTEST";
+        case FAULT_PIN_CONFLICT:     return "PIN_CONFLICT";
         default:                     return "UNKNOWN";
     }
 }
+
+const char *fault_severity_str(fault_severity_t severity) {
+    switch (severity) {
+        case FAULT_SEVERITY_NONE:     return "NONE";
+        case FAULT_SEVERITY_INFO:     return "INFO";
+        case FAULT_SEVERITY_WARNING:  return "WARNING";
+        case FAULT_SEVERITY_CRITICAL: return "CRITICAL";
+        default:                      return "UNKNOWN";
+    }
+}
@@ -0,0 +1,79 @@
+/**
+ * @file fault_indicator.c
+ * @brief Fault indicator implementation
+ */
+
+#include "fault_indicator.h"
+
+/** Raised codes that chirp and blink */
+static uint32_t noticeable(uint32_t raised) {
+    uint32_t mask = 0;
+    for (int code = 1; code < FAULT_CODE_COUNT; code++) {
+        if ((raised & FAULT_BIT(code)) &&
+            fault_code_severity((fault_code_t)code) >= FAULT_SEVERITY_WARNING) {
+            mask |= FAULT_BIT(code);
+        }
+    }
+    return mask;
+}
+
+void fault_indicator_init(fault_indicator_t *ind) {
+    ind->code = FAULT_NONE;
+    ind->severity = FAULT_SEVERITY_NONE;
+    ind->announced = 0;
+    ind->cycle_start_us = 0;
+    ind->chirp_until_us = 0;
+    atomic_init(&ind->chirp, false);
+}
+
+fault_code_t fault_indicator_pick(const fault_state_t *fault) {
+    if (fault_is_active(fault)) {
+        return fault_get_code(fault);
+    }
+
+    uint32_t raised = fault_get_raised(fault);
+    fault_code_t best = FAULT_NONE;
+    fault_severity_t best_severity = FAULT_SEVERITY_NONE;
+    for (int code = 1; code < FAULT_CODE_COUNT; code++) {
+        fault_severity_t severity = fault_code_severity((fault_code_t)code);
+        if ((raised & FAULT_BIT(code)) && severity > best_severity) {
+            best = (fault_code_t)code;
+            best_severity = severity;
+        }
+    }
+    return best;
+}
+
+bool fault_indicator_update(fault_indicator_t *ind, const fault_state_t *fault,
+                            int64_t now_us, bool chirp_enabled) {
+    uint32_t raised = noticeable(fault_get_raised(fault));
+    if ((raised & ~ind->announced) != 0 && chirp_enabled) {
+        ind->chirp_until_us = now_us + FAULT_CHIRP_US;
+    }
+    ind->announced = raised;  /* Resolved codes chirp again when raised again */
+    atomic_store_explicit(&ind->chirp, now_us < ind->chirp_until_us, memory_order_relaxed);
+
+    fault_code_t code = fault_indicator_pick(fault);
+    if (code == ind->code) {
+        return false;
+    }
+    ind->code = code;
+    ind->severity = fault_code_severity(code);
+    ind->cycle_start_us = now_us;
+    return true;
+}
+
+bool fault_indicator_blink(const fault_indicator_t *ind, int64_t now_us) {
+    if (ind->severity < FAULT_SEVERITY_WARNING) {
+        return false;
+    }
+
+    int64_t blink_us = FAULT_BLINK_ON_US + FAULT_BLINK_OFF_US;
+    int64_t code_us = (int64_t)ind->code * blink_us;
+    int64_t phase = (now_us - ind->cycle_start_us) % (code_us + FAULT_BLINK_GAP_US);
+    return phase < code_us && (phase % blink_us) < FAULT_BLINK_ON_US;
+}
+
+const char *fault_indicator_banner(const fault_indicator_t *ind) {
+    return (ind->code != FAULT_NONE) ? fault_code_str(ind->code) : NULL;
+}
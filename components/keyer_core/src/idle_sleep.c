@@ -0,0 +1,30 @@
+/**
+ * @file idle_sleep.c
+ * @brief Idle timer implementation
+ */
+
+#include "idle_sleep.h"
+
+void idle_sleep_init(idle_sleep_t *idle, int64_t now_us) {
+    idle->last_activity_us = now_us;
+    idle->asleep = false;
+}
+
+idle_sleep_action_t idle_sleep_update(idle_sleep_t *idle, bool active,
+                                      uint32_t timeout_s, int64_t now_us) {
+    if (active) {
+        idle->last_activity_us = now_us;
+        if (idle->asleep) {
+            idle->asleep = false;
+            return IDLE_SLEEP_WAKE;
+        }
+        return IDLE_SLEEP_NONE;
+    }
+
+    if (!idle->asleep && timeout_s > 0 &&
+        now_us - idle->last_activity_us >= (int64_t)timeout_s * 1000000) {
+        idle->asleep = true;
+        return IDLE_SLEEP_ENTER;
+    }
+    return IDLE_SLEEP_NONE;
+}
@@ -0,0 +1,26 @@
+/**
+ * @file keyer_clock.c
+ * @brief Monotonic time source implementation
+ */
+
+#include "keyer_clock.h"
+#include <stddef.h>
+
+static keyer_clock_fn s_source = NULL;
+static int64_t s_sim_us = 0;
+
+void keyer_clock_set_source(keyer_clock_fn source) {
+    s_source = source;
+}
+
+int64_t keyer_clock_now_us(void) {
+    return (s_source != NULL) ? s_source() : s_sim_us;
+}
+
+void keyer_clock_sim_set(int64_t now_us) {
+    s_sim_us = now_us;
+}
+
+void keyer_clock_sim_advance(int64_t delta_us) {
+    s_sim_us += delta_us;
+}
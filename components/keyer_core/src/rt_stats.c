@@ -0,0 +1,114 @@
+/**
+ * @file rt_stats.c
+ * @brief RT loop timing statistics implementation
+ *
+ * All updates are plain loads/stores: the RT task is the only writer,
+ * so no read-modify-write atomics are needed.
+ */
+
+#include "rt_stats.h"
+
+const uint32_t RT_STATS_BUCKET_LIMITS_US[RT_STATS_BUCKETS - 1] = {
+    10, 25, 50, 100, 250, 500, 1000
+};
+
+static void rt_stats_clear(rt_stats_t *stats) {
+    atomic_store_explicit(&stats->period_min_us, UINT32_MAX, memory_order_relaxed);
+    atomic_store_explicit(&stats->period_max_us, 0, memory_order_relaxed);
+    atomic_store_explicit(&stats->period_sum_us, 0, memory_order_relaxed);
+    atomic_store_explicit(&stats->period_count, 0, memory_order_relaxed);
+    for (uint32_t i = 0; i < RT_STATS_BUCKETS; i++) {
+        atomic_store_explicit(&stats->hist[i], 0, memory_order_relaxed);
+    }
+    atomic_store_explicit(&stats->missed_ticks, 0, memory_order_relaxed);
+    atomic_store_explicit(&stats->push_max_us, 0, memory_order_relaxed);
+    atomic_store_explicit(&stats->consume_max_us, 0, memory_order_relaxed);
+    atomic_store_explicit(&stats->work_max_us, 0, memory_order_relaxed);
+    atomic_store_explicit(&stats->press_age_max_us, 0, memory_order_relaxed);
+}
+
+void rt_stats_init(rt_stats_t *stats, uint32_t nominal_us) {
+    stats->nominal_us = nominal_us;
+    atomic_init(&stats->reset_request, false);
+    rt_stats_clear(stats);
+}
+
+uint32_t rt_stats_bucket(uint32_t jitter_us) {
+    for (uint32_t i = 0; i < RT_STATS_BUCKETS - 1; i++) {
+        if (jitter_us < RT_STATS_BUCKET_LIMITS_US[i]) {
+            return i;
+        }
+    }
+    return RT_STATS_BUCKETS - 1;
+}
+
+/* Single-writer increment (no fetch_add needed) */
+static inline void bump(atomic_uint *v) {
+    atomic_store_explicit(v, atomic_load_explicit(v, memory_order_relaxed) + 1,
+                          memory_order_relaxed);
+}
+
+/* Single-writer max update */
+static inline void update_max(atomic_uint *v, uint32_t value) {
+    if (value > atomic_load_explicit(v, memory_order_relaxed)) {
+        atomic_store_explicit(v, value, memory_order_relaxed);
+    }
+}
+
+void rt_stats_record_period(rt_stats_t *stats, uint32_t period_us) {
+    if (atomic_load_explicit(&stats->reset_request, memory_order_acquire)) {
+        rt_stats_clear(stats);
+        atomic_store_explicit(&stats->reset_request, false, memory_order_release);
+    }
+
+    if (period_us < atomic_load_explicit(&stats->period_min_us, memory_order_relaxed)) {
+        atomic_store_explicit(&stats->period_min_us, period_us, memory_order_relaxed);
+    }
+    update_max(&stats->period_max_us, period_us);
+
+    uint64_t sum = atomic_load_explicit(&stats->period_sum_us, memory_order_relaxed);
+    uint32_t count = atomic_load_explicit(&stats->period_count, memory_order_relaxed);
+    if (count >= RT_STATS_AVG_WINDOW) {
+        sum /= 2;
+        count /= 2;
+    }
+    atomic_store_explicit(&stats->period_sum_us, sum + period_us, memory_order_relaxed);
+    atomic_store_explicit(&stats->period_count, count + 1, memory_order_relaxed);
+
+    uint32_t jitter = (period_us > stats->nominal_us)
+                      ? period_us - stats->nominal_us
+                      : stats->nominal_us - period_us;
+    bump(&stats->hist[rt_stats_bucket(jitter)]);
+
+    if (period_us >= stats->nominal_us + stats->nominal_us / 2) {
+        bump(&stats->missed_ticks);
+    }
+}
+
+void rt_stats_record_push(rt_stats_t *stats, uint32_t duration_us) {
+    update_max(&stats->push_max_us, duration_us);
+}
+
+void rt_stats_record_consume(rt_stats_t *stats, uint32_t duration_us) {
+    update_max(&stats->consume_max_us, duration_us);
+}
+
+void rt_stats_record_work(rt_stats_t *stats, uint32_t duration_us) {
+    update_max(&stats->work_max_us, duration_us);
+}
+
+void rt_stats_record_press_age(rt_stats_t *stats, uint32_t age_us) {
+    update_max(&stats->press_age_max_us, age_us);
+}
+
+void rt_stats_request_reset(rt_stats_t *stats) {
+    atomic_store_explicit(&stats->reset_request, true, memory_order_release);
+}
+
+uint32_t rt_stats_avg_period_us(const rt_stats_t *stats) {
+    uint32_t count = atomic_load_explicit(&stats->period_count, memory_order_relaxed);
+    if (count == 0) {
+        return 0;
+    }
+    return (uint32_t)(atomic_load_explicit(&stats->period_sum_us, memory_order_relaxed) / count);
+}
@@ -19,6 +19,11 @@ stream_sample_t sample_with_edges_from(stream_sample_t current,
         flags |= FLAG_LOCAL_EDGE;
     }
 
+    /* Check for config generation change */
+    if (current.config_gen != previous->config_gen) {
+        flags |= FLAG_CONFIG_CHANGE;
+    }
+
     current.flags = flags;
     return current;
 }
@@ -0,0 +1,21 @@
+/**
+ * @file straight_key.c
+ * @brief Straight key debounce implementation
+ */
+
+#include "straight_key.h"
+
+void straight_key_init(straight_key_t *key, uint32_t debounce_us) {
+    key->debounce_us = debounce_us;
+    key->changed_at_us = 0;
+    key->down = false;
+}
+
+bool straight_key_update(straight_key_t *key, bool raw_down, int64_t now_us) {
+    if (raw_down != key->down &&
+        (key->changed_at_us == 0 || now_us - key->changed_at_us >= (int64_t)key->debounce_us)) {
+        key->down = raw_down;
+        key->changed_at_us = now_us;
+    }
+    return key->down;
+}
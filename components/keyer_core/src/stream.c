@@ -4,7 +4,7 @@
  *
  * ARCHITECTURE.md compliance:
  * - RULE 3.1.1: Only atomic operations for synchronization
- * - RULE 3.1.2: AcqRel for read-modify-write
+ * - RULE 3.1.2: Slot written before write_idx is published (Release)
  * - RULE 3.1.3: Acquire for read operations
  * - RULE 3.1.4: No operation shall block
  */
@@ -37,6 +37,8 @@ void stream_init(keying_stream_t *stream, stream_sample_t *buffer, size_t capaci
     atomic_init(&stream->write_idx, 0);
     atomic_init(&stream->idle_ticks, 0);
     stream->last_sample = STREAM_SAMPLE_EMPTY;
+    memset(stream->markers, 0, sizeof(stream->markers));
+    atomic_init(&stream->marker_idx, 0);
 
     /* Zero the buffer */
     memset(buffer, 0, capacity * sizeof(stream_sample_t));
@@ -48,13 +50,16 @@ void stream_init(keying_stream_t *stream, stream_sample_t *buffer, size_t capaci
  * Internal function - always writes, no compression.
  */
 static inline bool stream_write_slot(keying_stream_t *stream, stream_sample_t sample) {
-    /* RULE 3.1.2: AcqRel for read-modify-write */
-    size_t idx = atomic_fetch_add_explicit(&stream->write_idx, 1, memory_order_acq_rel);
+    /* Single producer: nobody else moves write_idx */
+    size_t idx = atomic_load_explicit(&stream->write_idx, memory_order_relaxed);
     size_t slot_idx = idx & stream->mask;
 
     /* Write sample to slot */
     stream->buffer[slot_idx] = sample;
 
+    /* RULE 3.1.2: publish only once the slot holds the sample */
+    atomic_store_explicit(&stream->write_idx, idx + 1, memory_order_release);
+
     return true;
 }
 
@@ -116,15 +121,18 @@ bool stream_read(const keying_stream_t *stream, size_t idx, stream_sample_t *out
         /* Not yet written */
         return false;
     }
-    if (behind > stream->capacity) {
-        /* Overwritten (consumer too slow) */
+    if (behind >= stream->capacity) {
+        /* Overwritten, or being overwritten by the next write (consumer too slow) */
         return false;
     }
 
     size_t slot_idx = idx & stream->mask;
     *out = stream->buffer[slot_idx];
 
-    return true;
+    /* The producer may have lapped the slot while we copied it */
+    atomic_thread_fence(memory_order_acquire);
+    write = atomic_load_explicit(&stream->write_idx, memory_order_relaxed);
+    return write - idx < stream->capacity;
 }
 
 size_t stream_write_position(const keying_stream_t *stream) {
@@ -140,7 +148,53 @@ size_t stream_lag(const keying_stream_t *stream, size_t read_idx) {
 
 bool stream_is_overrun(const keying_stream_t *stream, size_t read_idx) {
     assert(stream != NULL);
-    return stream_lag(stream, read_idx) > stream->capacity;
+    /* At lag == capacity the producer's next write reuses the unread slot */
+    return stream_lag(stream, read_idx) >= stream->capacity;
+}
+
+/* ============================================================================
+ * Sent-Text Markers
+ * ============================================================================ */
+
+void stream_mark_text(keying_stream_t *stream, char c) {
+    assert(stream != NULL);
+
+    size_t write = atomic_load_explicit(&stream->write_idx, memory_order_relaxed);
+    if (write == 0) {
+        return;  /* Nothing written to mark */
+    }
+
+    size_t n = atomic_load_explicit(&stream->marker_idx, memory_order_relaxed);
+    stream_marker_t *marker = &stream->markers[n & (STREAM_MARKER_CAPACITY - 1)];
+    marker->sample_idx = write - 1;
+    marker->character = c;
+
+    /* Publish after the slot is written */
+    atomic_store_explicit(&stream->marker_idx, n + 1, memory_order_release);
+}
+
+bool stream_read_marker(const keying_stream_t *stream, size_t seq, stream_marker_t *out) {
+    assert(stream != NULL);
+    assert(out != NULL);
+
+    /* RULE 3.1.3: Acquire for read */
+    size_t written = atomic_load_explicit(&stream->marker_idx, memory_order_acquire);
+    size_t behind = written - seq;  /* Wrapping subtraction is OK */
+    if (behind == 0 || behind >= STREAM_MARKER_CAPACITY) {
+        return false;
+    }
+
+    *out = stream->markers[seq & (STREAM_MARKER_CAPACITY - 1)];
+
+    /* The producer may have lapped the slot while we copied it */
+    atomic_thread_fence(memory_order_acquire);
+    written = atomic_load_explicit(&stream->marker_idx, memory_order_relaxed);
+    return written - seq < STREAM_MARKER_CAPACITY;
+}
+
+size_t stream_marker_position(const keying_stream_t *stream) {
+    assert(stream != NULL);
+    return atomic_load_explicit(&stream->marker_idx, memory_order_acquire);
 }
 
 /* ============================================================================
@@ -207,9 +261,9 @@ void consumer_resync(stream_consumer_t *consumer) {
     size_t write_pos = stream_write_position(consumer->stream);
     size_t capacity = stream_capacity(consumer->stream);
 
-    /* Move to oldest valid position */
+    /* Move to oldest valid position (the slot at write_pos - capacity is next to be reused) */
     if (write_pos >= capacity) {
-        consumer->read_idx = write_pos - capacity;
+        consumer->read_idx = write_pos - capacity + 1;
     } else {
         consumer->read_idx = 0;
     }
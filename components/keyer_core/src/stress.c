@@ -0,0 +1,82 @@
+/**
+ * @file stress.c
+ * @brief Reproducible stress input implementation
+ */
+
+#include "stress.h"
+
+/** Replaces a zero seed (xorshift state must never be 0) */
+#define STRESS_SEED_FALLBACK 0x9E3779B9u
+
+/** Pattern lengths: mostly element-length holds, some contact glitches */
+#define STRESS_GLITCH_PERCENT 10
+#define STRESS_GLITCH_MAX_TICKS 4
+#define STRESS_HOLD_MIN_TICKS 20
+#define STRESS_HOLD_MAX_TICKS 400
+
+void stress_init(stress_gen_t *gen, const stress_config_t *config) {
+    gen->config = *config;
+    gen->rng = (config->seed != 0) ? config->seed : STRESS_SEED_FALLBACK;
+    gen->paddles = GPIO_IDLE;
+    gen->hold_ticks = 0;
+    gen->speed_ticks = config->speed_change_ms;
+}
+
+uint32_t stress_random(stress_gen_t *gen) {
+    uint32_t x = gen->rng;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    gen->rng = x;
+    return x;
+}
+
+/** Uniform in [lo, hi] */
+static uint32_t random_range(stress_gen_t *gen, uint32_t lo, uint32_t hi) {
+    if (hi <= lo) {
+        return lo;
+    }
+    return lo + stress_random(gen) % (hi - lo + 1U);
+}
+
+static void next_pattern(stress_gen_t *gen) {
+    /* 40% released, 25% dit, 20% dah, 15% squeeze */
+    uint32_t pick = stress_random(gen) % 100U;
+    if (pick < 40U) {
+        gen->paddles = GPIO_IDLE;
+    } else if (pick < 65U) {
+        gen->paddles = gpio_from_paddles(true, false);
+    } else if (pick < 85U) {
+        gen->paddles = gpio_from_paddles(false, true);
+    } else {
+        gen->paddles = GPIO_BOTH;
+    }
+
+    if (stress_random(gen) % 100U < STRESS_GLITCH_PERCENT) {
+        gen->hold_ticks = random_range(gen, 1, STRESS_GLITCH_MAX_TICKS);
+    } else {
+        gen->hold_ticks = random_range(gen, STRESS_HOLD_MIN_TICKS, STRESS_HOLD_MAX_TICKS);
+    }
+}
+
+void stress_next(stress_gen_t *gen, stress_event_t *event) {
+    if (gen->hold_ticks == 0) {
+        next_pattern(gen);
+    }
+    gen->hold_ticks--;
+    event->paddles = gen->paddles;
+
+    event->wpm = 0;
+    if (gen->config.speed_change_ms > 0 && --gen->speed_ticks == 0) {
+        event->wpm = random_range(gen, gen->config.wpm_min, gen->config.wpm_max);
+        /* Next change between half and one and a half mean intervals */
+        gen->speed_ticks = random_range(gen, gen->config.speed_change_ms / 2U + 1U,
+                                        gen->config.speed_change_ms * 3U / 2U);
+    }
+
+    event->stall_ticks = 0;
+    if (gen->config.stall_permille > 0 &&
+        stress_random(gen) % 1000U < gen->config.stall_permille) {
+        event->stall_ticks = random_range(gen, 1, gen->config.stall_max_ticks);
+    }
+}
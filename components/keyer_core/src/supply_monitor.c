@@ -0,0 +1,65 @@
+/**
+ * @file supply_monitor.c
+ * @brief Supply voltage monitor implementation
+ */
+
+#include "supply_monitor.h"
+
+/** Filter weight of a new reading: 1/4 (a few seconds at 1 Hz) */
+#define FILTER_SHIFT 2
+
+/**
+ * @brief Check a threshold with hysteresis
+ *
+ * @param below Currently below the threshold
+ */
+static bool is_below(uint32_t mv, uint16_t threshold_mv, bool below) {
+    if (threshold_mv == 0) {
+        return false;
+    }
+    if (below) {
+        return mv < (uint32_t)threshold_mv + SUPPLY_MONITOR_HYSTERESIS_MV;
+    }
+    return mv < threshold_mv;
+}
+
+void supply_monitor_init(supply_monitor_t *mon) {
+    mon->filtered_mv = 0;
+    mon->primed = false;
+    mon->level = SUPPLY_OK;
+}
+
+uint32_t supply_monitor_scale(uint32_t pin_mv, uint16_t divider_x100) {
+    return (uint32_t)(((uint64_t)pin_mv * divider_x100 + 50U) / 100U);
+}
+
+supply_level_t supply_monitor_update(supply_monitor_t *mon, uint32_t supply_mv,
+                                     uint16_t low_mv, uint16_t critical_mv) {
+    if (!mon->primed) {
+        mon->filtered_mv = supply_mv;
+        mon->primed = true;
+    } else if (supply_mv >= mon->filtered_mv) {
+        mon->filtered_mv += (supply_mv - mon->filtered_mv) >> FILTER_SHIFT;
+    } else {
+        mon->filtered_mv -= (mon->filtered_mv - supply_mv) >> FILTER_SHIFT;
+    }
+
+    uint32_t mv = mon->filtered_mv;
+    if (is_below(mv, critical_mv, mon->level == SUPPLY_CRITICAL)) {
+        mon->level = SUPPLY_CRITICAL;
+    } else if (is_below(mv, low_mv, mon->level != SUPPLY_OK)) {
+        mon->level = SUPPLY_LOW;
+    } else {
+        mon->level = SUPPLY_OK;
+    }
+    return mon->level;
+}
+
+const char *supply_level_str(supply_level_t level) {
+    switch (level) {
+        case SUPPLY_OK:       return "ok";
+        case SUPPLY_LOW:      return "low";
+        case SUPPLY_CRITICAL: return "critical";
+        default:              return "?";
+    }
+}
@@ -0,0 +1,32 @@
+/**
+ * @file touch_paddle.c
+ * @brief Touch pad detection implementation
+ */
+
+#include "touch_paddle.h"
+
+void touch_paddle_calibrate(touch_paddle_t *pad, uint32_t baseline, uint8_t sensitivity_pct) {
+    uint32_t pct = sensitivity_pct;
+    if (pct < TOUCH_PADDLE_SENSITIVITY_MIN) {
+        pct = TOUCH_PADDLE_SENSITIVITY_MIN;
+    } else if (pct > TOUCH_PADDLE_SENSITIVITY_MAX) {
+        pct = TOUCH_PADDLE_SENSITIVITY_MAX;
+    }
+
+    pad->baseline = baseline;
+    pad->on_delta = (uint32_t)(((uint64_t)baseline * pct) / 100U);
+    if (pad->on_delta < 2U) {
+        pad->on_delta = 2U;  /* Keeps the release level above the baseline */
+    }
+    pad->touched = false;
+}
+
+bool touch_paddle_update(touch_paddle_t *pad, uint32_t raw) {
+    uint32_t rise = (raw > pad->baseline) ? raw - pad->baseline : 0U;
+    if (pad->touched) {
+        pad->touched = rise >= pad->on_delta / 2U;
+    } else {
+        pad->touched = rise >= pad->on_delta;
+    }
+    return pad->touched;
+}
@@ -71,6 +71,28 @@ bool cwnet_socket_is_ready(void);
  */
 int32_t cwnet_socket_get_latency_ms(void);
 
+/**
+ * @brief Ask bg_task to drop the connection and re-read remote.* config
+ *
+ * Safe to call from any task; applied on the next cwnet_socket_process().
+ */
+void cwnet_socket_request_restart(void);
+
+/**
+ * @brief Inhibit forwarding of local key-down events to the peer
+ *
+ * The connection stays up; key-up events are still sent so the
+ * remote transmitter is never left keyed.
+ *
+ * @param locked true to inhibit
+ */
+void cwnet_socket_set_tx_lock(bool locked);
+
+/**
+ * @brief Check if key forwarding is inhibited
+ */
+bool cwnet_socket_get_tx_lock(void);
+
 /**
  * @brief Get state as string (for logging)
  */
@@ -3,6 +3,8 @@
  * @brief CWNet TCP client state machine implementation
  */
 
+#define RT_LOG_TAG LOG_TAG_CWNET
+
 #include "cwnet_client.h"
 #include <string.h>
 #include <stdio.h>
@@ -10,7 +12,7 @@
 
 /* Diagnostic logging - uses RT-safe logging from bg task context */
 #include "rt_log.h"
-#include "esp_timer.h"
+#include "keyer_clock.h"
 extern log_stream_t g_bg_log_stream;
 
 /*===========================================================================*/
@@ -94,7 +96,7 @@ static cwnet_client_err_t send_connect(cwnet_client_t *client) {
 
     /* Permissions field (4 bytes) - leave as zero */
 
-    int64_t now_us = esp_timer_get_time();
+    int64_t now_us = keyer_clock_now_us();
     RT_DEBUG(&g_bg_log_stream, now_us,
              "CONNECT: cmd=0x%02X user=\"%s\"",
              frame[0], client->username);
@@ -132,12 +134,12 @@ static cwnet_client_err_t send_ping_response(cwnet_client_t *client,
 
     int sent = send_frame(client, frame, sizeof(frame));
     if (sent < 0 || (size_t)sent != sizeof(frame)) {
-        int64_t now_us = esp_timer_get_time();
+        int64_t now_us = keyer_clock_now_us();
         RT_ERROR(&g_bg_log_stream, now_us, "PING RSP1 send failed: %d", sent);
         return CWNET_CLIENT_ERR_SEND_FAILED;
     }
 
-    int64_t now_us = esp_timer_get_time();
+    int64_t now_us = keyer_clock_now_us();
     RT_DEBUG(&g_bg_log_stream, now_us,
              "PING RSP1 sent: id=%u t0=%" PRId32 " t1=%" PRId32,
              request->id, request->t0_ms, our_time);
@@ -200,12 +202,12 @@ static void handle_ping(cwnet_client_t *client,
                         size_t len) {
     cwnet_ping_t ping;
     if (!cwnet_ping_parse(&ping, payload, len)) {
-        int64_t now_us = esp_timer_get_time();
+        int64_t now_us = keyer_clock_now_us();
         RT_WARN(&g_bg_log_stream, now_us, "CWNet PING: parse failed (len=%zu)", len);
         return;  /* Invalid PING, ignore */
     }
 
-    int64_t now_us = esp_timer_get_time();
+    int64_t now_us = keyer_clock_now_us();
 
     switch (ping.type) {
         case CWNET_PING_REQUEST:
@@ -419,7 +421,7 @@ void cwnet_client_on_data(cwnet_client_t *client,
         return;
     }
 
-    int64_t now_us = esp_timer_get_time();
+    int64_t now_us = keyer_clock_now_us();
     (void)now_us;  /* Used by RT_* macros below */
 
     /* Process all frames in buffer */
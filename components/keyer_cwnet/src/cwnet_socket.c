@@ -3,11 +3,14 @@
  * @brief CWNet TCP socket integration for ESP-IDF
  */
 
+#define RT_LOG_TAG LOG_TAG_CWNET
+
 #include "cwnet_socket.h"
 #include "config.h"
 #include "rt_log.h"
 
 #include <string.h>
+#include <stdatomic.h>
 #include <errno.h>
 #include <sys/socket.h>
 #include <netdb.h>
@@ -47,6 +50,10 @@ static struct {
     bool enabled;
 } s_ctx;
 
+/* Cross-task requests (set from console, consumed by bg_task) */
+static atomic_bool s_restart_request = ATOMIC_VAR_INIT(false);
+static atomic_bool s_tx_lock = ATOMIC_VAR_INIT(false);
+
 /*===========================================================================*/
 /* Callbacks for cwnet_client                                                */
 /*===========================================================================*/
@@ -254,9 +261,9 @@ void cwnet_socket_init(void) {
     }
 
     /* Copy config values */
-    strncpy(s_ctx.host, g_config.remote.server_host, sizeof(s_ctx.host) - 1);
+    CONFIG_COPY_SERVER_HOST(s_ctx.host, sizeof(s_ctx.host));
     s_ctx.port = g_config.remote.server_port;
-    strncpy(s_ctx.username, g_config.remote.username, sizeof(s_ctx.username) - 1);
+    CONFIG_COPY_USERNAME(s_ctx.username, sizeof(s_ctx.username));
 
     /* Validate */
     if (s_ctx.host[0] == '\0') {
@@ -294,6 +301,11 @@ void cwnet_socket_init(void) {
 }
 
 void cwnet_socket_process(void) {
+    if (atomic_exchange_explicit(&s_restart_request, false, memory_order_acquire)) {
+        close_socket();
+        cwnet_socket_init();  /* Re-reads remote.* from g_config */
+    }
+
     if (!s_ctx.enabled) {
         return;
     }
@@ -345,6 +357,11 @@ bool cwnet_socket_send_key_event(bool key_down) {
         return false;
     }
 
+    /* Locked: drop key-down, still forward key-up so the peer never sticks */
+    if (key_down && atomic_load_explicit(&s_tx_lock, memory_order_relaxed)) {
+        return false;
+    }
+
     cwnet_client_err_t err = cwnet_client_send_key_event(&s_ctx.client, key_down);
     if (err == CWNET_CLIENT_OK) {
         int64_t now_us = esp_timer_get_time();
@@ -366,6 +383,18 @@ int32_t cwnet_socket_get_latency_ms(void) {
     return cwnet_client_get_latency_ms(&s_ctx.client);
 }
 
+void cwnet_socket_request_restart(void) {
+    atomic_store_explicit(&s_restart_request, true, memory_order_release);
+}
+
+void cwnet_socket_set_tx_lock(bool locked) {
+    atomic_store_explicit(&s_tx_lock, locked, memory_order_relaxed);
+}
+
+bool cwnet_socket_get_tx_lock(void) {
+    return atomic_load_explicit(&s_tx_lock, memory_order_relaxed);
+}
+
 const char *cwnet_socket_state_str(cwnet_socket_state_t state) {
     switch (state) {
         case CWNET_SOCK_DISABLED:     return "DISABLED";
@@ -0,0 +1,62 @@
+/**
+ * @file callsign.h
+ * @brief Callsign recognition and %C message substitution
+ *
+ * The decoder checks every decoded word with callsign_is_plausible() and
+ * keeps the most recent match (see decoder_get_last_callsign()), so a
+ * message such as "%C 5NN %N" answers the station just copied.
+ *
+ * A callsign is a prefix of 1-3 characters with at least one letter,
+ * a digit, and a suffix of 1-4 letters (K1ABC, IU3QEZ, 2E0ABC, 4U1ITU),
+ * optionally with /P, DL/ and similar parts. Exchange words (5NN, 599,
+ * TU, 73) never match. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_CALLSIGN_H
+#define KEYER_CALLSIGN_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Longest callsign kept, without terminator (as system.callsign) */
+#define CALLSIGN_MAX_LEN 12
+
+/**
+ * @brief Check if a word looks like a callsign
+ *
+ * @param word Decoded word, uppercase, no spaces
+ * @return true if callsign-shaped
+ */
+bool callsign_is_plausible(const char *word);
+
+/**
+ * @brief Check if two callsigns are the same station
+ *
+ * Compares the base calls case-insensitively, ignoring /P and prefix
+ * parts (IU3QEZ/P is IU3QEZ).
+ */
+bool callsign_same_station(const char *a, const char *b);
+
+/**
+ * @brief Replace %C in a message with a callsign
+ *
+ * %c works too. With no callsign, %C is dropped. A callsign that does
+ * not fit in out is dropped rather than cut.
+ *
+ * @param in Message text
+ * @param call Callsign to insert ("" if none)
+ * @param out Output, NUL-terminated
+ * @param out_len Size of out
+ * @return true if the message contained %C
+ */
+bool callsign_expand(const char *in, const char *call, char *out, size_t out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CALLSIGN_H */
@@ -7,6 +7,14 @@
  *
  * Architecture:
  *   keying_stream_t → decoder_consumer → timing_classifier → pattern_decoder → text buffer
+ *
+ * The text buffer keeps the last few hundred characters. Each reader
+ * (console, WebUI, network clients) keeps its own cursor and calls
+ * decoder_read_since(), so readers never steal characters from each
+ * other; decoder_pop_char() is just one such cursor, kept internally.
+ *
+ * Every decoded word is also checked for a callsign (see callsign.h);
+ * the last one is kept for %C message substitution.
  */
 
 #ifndef KEYER_DECODER_H
@@ -31,6 +39,7 @@ extern "C" {
 typedef struct {
     char character;         /**< ASCII character (or ' ' for space) */
     int64_t timestamp_us;   /**< When character was decoded */
+    char sent;              /**< Character the text keyer sent, '\0' if hand keyed */
 } decoded_char_t;
 
 /**
@@ -50,6 +59,8 @@ typedef struct {
     uint32_t errors;            /**< Unrecognized patterns */
     uint32_t samples_processed; /**< Stream samples processed */
     uint32_t samples_dropped;   /**< Samples dropped (lag) */
+    uint32_t glitches;          /**< Marks/spaces dropped by the glitch filter */
+    uint32_t sent_mismatches;   /**< Text keyer characters decoded as something else */
 } decoder_stats_t;
 
 /* ============================================================================
@@ -86,6 +97,28 @@ void decoder_set_enabled(bool enabled);
  */
 bool decoder_is_enabled(void);
 
+/**
+ * @brief Read decoded characters after a cursor
+ *
+ * Safe from any task. A cursor is a character sequence number: start
+ * from 0 for everything still buffered, or from decoder_text_seq() for
+ * new text only. If the reader fell more than the buffer capacity
+ * behind, it skips ahead to the oldest buffered character.
+ *
+ * @param cursor In: next character wanted. Out: advanced past the copy
+ * @param buf Output buffer
+ * @param max_count Maximum entries to return
+ * @return Number of entries written (0 if nothing new)
+ */
+size_t decoder_read_since(uint32_t *cursor, decoded_char_t *buf, size_t max_count);
+
+/**
+ * @brief Sequence number the next decoded character will get
+ *
+ * @return Characters decoded since init/reset
+ */
+uint32_t decoder_text_seq(void);
+
 /**
  * @brief Get decoded text (simple, for console)
  *
@@ -117,6 +150,8 @@ decoded_char_t decoder_get_last_char(void);
  * @brief Pop next unread decoded character
  *
  * Returns characters in FIFO order. Each character is returned only once.
+ * Uses a single internal cursor, so only one task may pop; other
+ * readers use decoder_read_since().
  *
  * @return Next unread character (character='\0' if none available)
  */
@@ -138,6 +173,45 @@ uint32_t decoder_get_wpm(void);
  */
 size_t decoder_get_current_pattern(char *buf, size_t max_len);
 
+/**
+ * @brief Set the station's own callsign (never captured)
+ *
+ * Messages sent by this keyer are decoded too; without this a CQ would
+ * replace the other station's call with our own. Decoder task only.
+ *
+ * @param call system.callsign
+ */
+void decoder_set_own_callsign(const char *call);
+
+/**
+ * @brief Get the last callsign-shaped word decoded
+ *
+ * Safe from any task. Used for %C in messages.
+ *
+ * @param buf Output buffer
+ * @param max_len Buffer size (including null terminator)
+ * @return Callsign length (0 if none captured yet)
+ */
+size_t decoder_get_last_callsign(char *buf, size_t max_len);
+
+/**
+ * @brief Override the last callsign (correct a miscopied call)
+ *
+ * @param call Callsign (any case), "" to clear
+ * @return false if not callsign-shaped, too long, or a capture was in progress
+ */
+bool decoder_set_last_callsign(const char *call);
+
+/**
+ * @brief Set the glitch filter (see edge_filter.h)
+ *
+ * Marks and spaces shorter than this are merged into the surrounding
+ * element before classification. Call from the decoder task.
+ *
+ * @param ms Shortest accepted mark or space (0 = off)
+ */
+void decoder_set_glitch_filter_ms(uint32_t ms);
+
 /**
  * @brief Get decoder state
  *
@@ -0,0 +1,74 @@
+/**
+ * @file edge_filter.h
+ * @brief Key glitch filter in front of the timing classifier
+ *
+ * A level change is only accepted once the new level has held for the
+ * minimum time. Shorter marks or spaces (contact bounce on a straight
+ * key, dropouts on a noisy remote link) are merged into the surrounding
+ * element and counted. With a minimum of 0 every change is accepted.
+ *
+ * Fed one level per stream sample by decoder_process(); pure logic,
+ * host-testable.
+ */
+
+#ifndef KEYER_EDGE_FILTER_H
+#define KEYER_EDGE_FILTER_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Filter state
+ */
+typedef struct {
+    int64_t min_us;         /**< Shortest accepted mark or space */
+    bool level;             /**< Accepted level (true = mark) */
+    int64_t since_us;       /**< When the accepted level started */
+    bool pending;           /**< Level differs, not held long enough yet */
+    int64_t pending_us;     /**< When the pending change started */
+    uint32_t glitches;      /**< Changes dropped as too short */
+} edge_filter_t;
+
+/**
+ * @brief Accepted edge
+ */
+typedef struct {
+    bool was_mark;          /**< Level that ended (true = mark) */
+    int64_t duration_us;    /**< How long it lasted */
+    int64_t at_us;          /**< When it ended */
+} edge_filter_edge_t;
+
+/**
+ * @brief Reset to key up, no glitches counted
+ *
+ * @param filter Filter state
+ * @param min_us Shortest accepted mark or space (0 = no filtering)
+ */
+void edge_filter_init(edge_filter_t *filter, int64_t min_us);
+
+/**
+ * @brief Change the minimum (takes effect on the next sample)
+ */
+void edge_filter_set_min(edge_filter_t *filter, int64_t min_us);
+
+/**
+ * @brief Feed the key level at a point in time
+ *
+ * @param filter Filter state
+ * @param mark Raw level (true = key down)
+ * @param now_us Sample time, never decreasing
+ * @param edge Output: the accepted edge, timed where the change started
+ * @return true if an edge was accepted
+ */
+bool edge_filter_update(edge_filter_t *filter, bool mark, int64_t now_us,
+                        edge_filter_edge_t *edge);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_EDGE_FILTER_H */
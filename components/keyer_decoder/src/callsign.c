@@ -0,0 +1,181 @@
+/**
+ * @file callsign.c
+ * @brief Callsign recognition and %C substitution implementation
+ */
+
+#include "callsign.h"
+#include <ctype.h>
+#include <string.h>
+
+/** Most '/' separated parts in a callsign (DL/K1ABC/P) */
+#define MAX_PARTS 3
+
+/** Longest non-base part (/QRP, /MM, DL/) */
+#define MAX_EXTRA_LEN 4
+
+/* ============================================================================
+ * Shape Helpers
+ * ============================================================================ */
+
+static bool is_letter(char c) {
+    return c >= 'A' && c <= 'Z';
+}
+
+static bool is_digit(char c) {
+    return c >= '0' && c <= '9';
+}
+
+/**
+ * @brief Base call: prefix (1-3, one letter at least), digit, 1-4 letters
+ */
+static bool is_base_call(const char *s, size_t len) {
+    if (len < 3 || len > 7) {
+        return false;
+    }
+
+    size_t digit = len;
+    for (size_t i = len; i-- > 0;) {
+        if (is_digit(s[i])) {
+            digit = i;
+            break;
+        }
+    }
+    if (digit == len || digit == 0 || digit > 3) {
+        return false;
+    }
+
+    size_t suffix = len - digit - 1;
+    if (suffix < 1 || suffix > 4) {
+        return false;
+    }
+
+    bool letter = false;
+    for (size_t i = 0; i < digit; i++) {
+        if (is_letter(s[i])) {
+            letter = true;
+        } else if (!is_digit(s[i])) {
+            return false;
+        }
+    }
+    for (size_t i = digit + 1; i < len; i++) {
+        if (!is_letter(s[i])) {
+            return false;
+        }
+    }
+    return letter;
+}
+
+/**
+ * @brief Find the base call among the '/' separated parts
+ *
+ * @param word Word to check
+ * @param base_len Length of the base call
+ * @return Start of the base call, NULL if the word is not a callsign
+ */
+static const char *find_base(const char *word, size_t *base_len) {
+    size_t len = strlen(word);
+    if (len < 3 || len > CALLSIGN_MAX_LEN) {
+        return NULL;
+    }
+
+    const char *base = NULL;
+    size_t parts = 0;
+    const char *p = word;
+    for (;;) {
+        const char *slash = strchr(p, '/');
+        size_t n = (slash != NULL) ? (size_t)(slash - p) : strlen(p);
+        if (n == 0 || ++parts > MAX_PARTS) {
+            return NULL;
+        }
+
+        if (base == NULL && is_base_call(p, n)) {
+            base = p;
+            *base_len = n;
+        } else {
+            if (n > MAX_EXTRA_LEN) {
+                return NULL;
+            }
+            for (size_t i = 0; i < n; i++) {
+                if (!is_letter(p[i]) && !is_digit(p[i])) {
+                    return NULL;
+                }
+            }
+        }
+
+        if (slash == NULL) {
+            break;
+        }
+        p = slash + 1;
+    }
+    return base;
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+bool callsign_is_plausible(const char *word) {
+    size_t base_len;
+    return word != NULL && find_base(word, &base_len) != NULL;
+}
+
+bool callsign_same_station(const char *a, const char *b) {
+    if (a == NULL || b == NULL) {
+        return false;
+    }
+
+    char up_a[CALLSIGN_MAX_LEN + 1];
+    char up_b[CALLSIGN_MAX_LEN + 1];
+    size_t i;
+    for (i = 0; a[i] != '\0' && i < CALLSIGN_MAX_LEN; i++) {
+        up_a[i] = (char)toupper((unsigned char)a[i]);
+    }
+    up_a[i] = '\0';
+    for (i = 0; b[i] != '\0' && i < CALLSIGN_MAX_LEN; i++) {
+        up_b[i] = (char)toupper((unsigned char)b[i]);
+    }
+    up_b[i] = '\0';
+
+    size_t len_a;
+    size_t len_b;
+    const char *base_a = find_base(up_a, &len_a);
+    const char *base_b = find_base(up_b, &len_b);
+    if (base_a == NULL || base_b == NULL) {
+        return strcmp(up_a, up_b) == 0;
+    }
+    return len_a == len_b && memcmp(base_a, base_b, len_a) == 0;
+}
+
+bool callsign_expand(const char *in, const char *call, char *out, size_t out_len) {
+    if (out == NULL || out_len == 0) {
+        return false;
+    }
+    out[0] = '\0';
+    if (in == NULL) {
+        return false;
+    }
+    if (call == NULL) {
+        call = "";
+    }
+
+    size_t call_len = strlen(call);
+    bool found = false;
+    size_t pos = 0;
+
+    while (*in != '\0' && pos + 1 < out_len) {
+        if (in[0] != '%' || (in[1] != 'C' && in[1] != 'c')) {
+            out[pos++] = *in++;
+            continue;
+        }
+
+        in += 2;
+        found = true;
+        if (pos + call_len >= out_len) {
+            break;  /* Does not fit: drop the partial call */
+        }
+        memcpy(&out[pos], call, call_len);
+        pos += call_len;
+    }
+    out[pos] = '\0';
+    return found;
+}
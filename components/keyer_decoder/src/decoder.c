@@ -7,31 +7,33 @@
  */
 
 #include "decoder.h"
+#include "callsign.h"
 #include "morse_table.h"
 #include "timing_classifier.h"
+#include "edge_filter.h"
 #include "consumer.h"
 #include "sample.h"
 
+#include <ctype.h>
 #include <string.h>
 #include <stdatomic.h>
 
+#include "keyer_clock.h"
+
 #ifdef ESP_PLATFORM
-#include "esp_timer.h"
 #include "esp_log.h"
 static const char *TAG = "decoder";
-#else
-/* Host stub for esp_timer_get_time */
-static int64_t s_mock_time_us = 0;
-static inline int64_t esp_timer_get_time(void) { return s_mock_time_us; }
-void decoder_set_mock_time(int64_t time_us) { s_mock_time_us = time_us; }
 #endif
 
 /* ============================================================================
  * Configuration
  * ============================================================================ */
 
-/** Decoded text buffer size */
-#define DECODER_BUFFER_SIZE 128
+/** Decoded text buffer size (a few lines of QSO for late readers) */
+#define DECODER_BUFFER_SIZE 256
+
+/** Ring slots: one spare for the character being written */
+#define RING_SLOTS (DECODER_BUFFER_SIZE + 1)
 
 /** Maximum pattern length (ITU max is 6 for some punctuation) */
 #define MAX_PATTERN_LEN 8
@@ -58,11 +60,16 @@ void decoder_set_test_stream(keying_stream_t *stream) { s_test_stream = stream;
  * Static state
  * ============================================================================ */
 
-/** Decoded text circular buffer */
-static decoded_char_t s_decoded_buffer[DECODER_BUFFER_SIZE];
-static size_t s_buffer_head = 0;   /* Next write position */
-static size_t s_buffer_count = 0;  /* Characters in buffer */
-static size_t s_buffer_read = 0;   /* Next read position (for pop) */
+/**
+ * Decoded text ring. Character n lives in slot n % RING_SLOTS;
+ * s_written counts characters ever decoded and is published after the
+ * slot is written, so readers on other tasks can detect overwrites.
+ */
+static decoded_char_t s_decoded_buffer[RING_SLOTS];
+static atomic_uint_fast32_t s_written = 0;
+
+/** Cursor of decoder_pop_char() */
+static uint32_t s_pop_cursor = 0;
 
 /** Current pattern being accumulated */
 static char s_pattern[MAX_PATTERN_LEN + 1];
@@ -84,9 +91,12 @@ static bool s_consumer_initialized = false;
 /** Enable flag */
 static atomic_bool s_enabled = false;
 
-/** Edge tracking */
+/** Edge tracking, after the glitch filter */
+static edge_filter_t s_filter;
 static int64_t s_last_edge_us = 0;
-static bool s_last_was_mark = false;
+
+/** Raw level of the last regular sample (silence keeps it) */
+static bool s_raw_mark = false;
 
 /** Statistics */
 static decoder_stats_t s_stats;
@@ -100,6 +110,28 @@ static int64_t s_last_event_wall_us = 0;
 /** Sample-based time tracking (1 sample = 1ms = 1000us) */
 static int64_t s_sample_time_us = 0;
 
+/** Word being decoded, checked for a callsign when it ends */
+static char s_word[CALLSIGN_MAX_LEN + 1];
+static size_t s_word_len = 0;
+static bool s_word_too_long = false;
+
+/** Station's own call (decoder task only), never captured */
+static char s_own_call[CALLSIGN_MAX_LEN + 1];
+
+/**
+ * Last captured callsign. Written by the decoder task or the console,
+ * read from any task: s_call_seq is odd while a writer copies, and a
+ * writer that finds it odd gives up instead of waiting.
+ */
+static char s_last_call[CALLSIGN_MAX_LEN + 1];
+static atomic_uint s_call_seq = 0;
+
+/* Sent-text markers: next to read, the one waiting for its first
+ * element, and the one for the pattern being received */
+static size_t s_marker_seq = 0;
+static char s_sent_next = '\0';
+static char s_sent = '\0';
+
 /* ============================================================================
  * Internal helpers
  * ============================================================================ */
@@ -107,16 +139,70 @@ static int64_t s_sample_time_us = 0;
 /**
  * @brief Add character to decoded buffer
  */
-static void buffer_push(char c, int64_t timestamp_us) {
-    s_decoded_buffer[s_buffer_head].character = c;
-    s_decoded_buffer[s_buffer_head].timestamp_us = timestamp_us;
+static void buffer_push(char c, char sent, int64_t timestamp_us) {
+    uint32_t n = (uint32_t)atomic_load_explicit(&s_written, memory_order_relaxed);
+    decoded_char_t *slot = &s_decoded_buffer[n % RING_SLOTS];
+    slot->character = c;
+    slot->timestamp_us = timestamp_us;
+    slot->sent = sent;
+    atomic_store_explicit(&s_written, n + 1U, memory_order_release);
+}
+
+/**
+ * @brief Characters currently held (at most DECODER_BUFFER_SIZE)
+ */
+static uint32_t buffer_held(uint32_t written) {
+    return (written < DECODER_BUFFER_SIZE) ? written : DECODER_BUFFER_SIZE;
+}
+
+/**
+ * @brief Clear the text ring (decoder task only)
+ */
+static void buffer_clear(void) {
+    memset(s_decoded_buffer, 0, sizeof(s_decoded_buffer));
+    atomic_store_explicit(&s_written, 0, memory_order_release);
+    s_pop_cursor = 0;
+}
+
+/**
+ * @brief Publish the last callsign
+ * @return false if another writer is in the middle of an update
+ */
+static bool store_last_call(const char *call) {
+    unsigned seq = atomic_load_explicit(&s_call_seq, memory_order_relaxed);
+    if ((seq & 1U) != 0 ||
+        !atomic_compare_exchange_strong_explicit(&s_call_seq, &seq, seq + 1U,
+                                                 memory_order_acquire, memory_order_relaxed)) {
+        return false;
+    }
+    atomic_thread_fence(memory_order_release);
+    strncpy(s_last_call, call, CALLSIGN_MAX_LEN);
+    s_last_call[CALLSIGN_MAX_LEN] = '\0';
+    atomic_store_explicit(&s_call_seq, seq + 2U, memory_order_release);
+    return true;
+}
 
-    s_buffer_head = (s_buffer_head + 1) % DECODER_BUFFER_SIZE;
-    if (s_buffer_count < DECODER_BUFFER_SIZE) {
-        s_buffer_count++;
+static void word_append(char c) {
+    if (s_word_len < CALLSIGN_MAX_LEN) {
+        s_word[s_word_len++] = c;
+    } else {
+        s_word_too_long = true;
     }
 }
 
+/**
+ * @brief Word complete: keep it if it is someone else's callsign
+ */
+static void word_end(void) {
+    s_word[s_word_len] = '\0';
+    if (s_word_len > 0 && !s_word_too_long && callsign_is_plausible(s_word) &&
+        !callsign_same_station(s_word, s_own_call)) {
+        store_last_call(s_word);
+    }
+    s_word_len = 0;
+    s_word_too_long = false;
+}
+
 /**
  * @brief Finalize current pattern and decode
  */
@@ -131,9 +217,13 @@ static void finalize_pattern(int64_t timestamp_us) {
     memcpy(s_last_pattern, s_pattern, s_pattern_len + 1);
 
     char decoded = morse_table_lookup(s_pattern);
+    if (s_sent != '\0' && decoded != s_sent) {
+        s_stats.sent_mismatches++;
+    }
 
     if (decoded != '\0') {
-        buffer_push(decoded, timestamp_us);
+        buffer_push(decoded, s_sent, timestamp_us);
+        word_append(decoded);
         s_stats.chars_decoded++;
 #ifdef ESP_PLATFORM
         ESP_LOGD(TAG, "Decoded: '%s' -> '%c'", s_pattern, decoded);
@@ -148,9 +238,38 @@ static void finalize_pattern(int64_t timestamp_us) {
 
     /* Reset pattern */
     s_pattern_len = 0;
+    s_sent = '\0';
     s_state = DECODER_STATE_IDLE;
 }
 
+/**
+ * @brief Pick up the sent-text markers up to the sample just read
+ *
+ * The marker of a character arrives with its first key-down, before the
+ * gap that ends the previous character is classified, so it waits in
+ * s_sent_next until the character's first element.
+ */
+static void read_markers(size_t sample_idx) {
+    const keying_stream_t *stream = s_consumer.stream;
+    stream_marker_t marker;
+    for (;;) {
+        if (!stream_read_marker(stream, s_marker_seq, &marker)) {
+            size_t end = stream_marker_position(stream);
+            if (end - s_marker_seq < STREAM_MARKER_CAPACITY) {
+                return;  /* Caught up */
+            }
+            s_marker_seq = end - STREAM_MARKER_CAPACITY + 1;  /* Lapped: skip ahead */
+            continue;
+        }
+        size_t ahead = marker.sample_idx - sample_idx;  /* Wrapping */
+        if (ahead != 0 && ahead < SIZE_MAX / 2) {
+            return;  /* Marks a later sample */
+        }
+        s_sent_next = marker.character;
+        s_marker_seq++;
+    }
+}
+
 /**
  * @brief Check for inactivity timeout (uses wall clock)
  */
@@ -159,14 +278,16 @@ static void check_inactivity(void) {
         return;
     }
 
-    int64_t now_us = esp_timer_get_time();
+    int64_t now_us = keyer_clock_now_us();
     int64_t elapsed_us = now_us - s_last_event_wall_us;
     int64_t timeout_us = s_timing.dit_avg_us * INACTIVITY_DIT_UNITS;
 
     if (elapsed_us > timeout_us) {
         /* Force finalization */
         finalize_pattern(s_sample_time_us);
-        /* No word space - just character gap */
+        /* No word space yet (added once keying resumes), but the word
+         * is over: a callsign sent last is captured now */
+        word_end();
     }
 }
 
@@ -184,17 +305,17 @@ void decoder_init(void) {
     timing_classifier_init(&s_timing, DEFAULT_INITIAL_WPM);
 
     /* Reset state */
-    s_buffer_head = 0;
-    s_buffer_count = 0;
-    s_buffer_read = 0;
+    buffer_clear();
     s_pattern_len = 0;
     s_state = DECODER_STATE_IDLE;
     s_last_edge_us = 0;
-    s_last_was_mark = false;
+    s_raw_mark = false;
+    edge_filter_init(&s_filter, s_filter.min_us);
     s_last_event_us = 0;
+    s_word_len = 0;
+    s_word_too_long = false;
 
     memset(&s_stats, 0, sizeof(s_stats));
-    memset(s_decoded_buffer, 0, sizeof(s_decoded_buffer));
     memset(s_pattern, 0, sizeof(s_pattern));
     memset(s_last_pattern, 0, sizeof(s_last_pattern));
 
@@ -208,6 +329,12 @@ void decoder_init(void) {
         s_consumer_initialized = true;
     }
 #endif
+    if (s_consumer_initialized) {
+        best_effort_consumer_track(&s_consumer, consumer_lag_register("decoder"));
+        s_marker_seq = stream_marker_position(s_consumer.stream);
+    }
+    s_sent_next = '\0';
+    s_sent = '\0';
 
     atomic_store(&s_enabled, true);
 }
@@ -221,53 +348,54 @@ void decoder_process(void) {
     stream_sample_t sample;
     while (best_effort_consumer_tick(&s_consumer, &sample)) {
         s_stats.samples_processed++;
+        read_markers(s_consumer.read_idx - 1);
 
         /* Advance sample time based on sample type:
          * - Regular sample: 1ms (1000us)
          * - Silence marker: config_gen ms (ticks * 1000us)
+         * Silence doesn't change the key state, but it can confirm a
+         * change the glitch filter is still holding back.
          */
         if (sample_is_silence(&sample)) {
             s_sample_time_us += (int64_t)sample_silence_ticks(&sample) * 1000;
-            continue;  /* Silence doesn't change key state */
+        } else {
+            s_sample_time_us += 1000;  /* 1 sample = 1ms */
+            /* We're interested in local_key transitions (mark/space) */
+            s_raw_mark = (sample.local_key != 0);
         }
-        s_sample_time_us += 1000;  /* 1 sample = 1ms */
 
-        /* We're interested in local_key transitions (mark/space) */
-        bool is_mark = (sample.local_key != 0);
-
-        /* Detect edge */
-        if (is_mark != s_last_was_mark) {
-            if (s_last_edge_us > 0) {
-                /* Edge detected - classify the duration */
-                int64_t duration_us = s_sample_time_us - s_last_edge_us;
+        edge_filter_edge_t edge;
+        if (!edge_filter_update(&s_filter, s_raw_mark, s_sample_time_us, &edge)) {
+            continue;
+        }
 
-                /* s_last_was_mark tells us what just ended:
-                 * - true: a mark just ended (key went up) → classify as dit/dah
-                 * - false: a space just ended (key went down) → classify as gap
-                 */
-                key_event_t event = timing_classifier_classify(
-                    &s_timing, duration_us, s_last_was_mark);
+        if (s_last_edge_us > 0) {
+            /* edge.was_mark tells us what just ended:
+             * - true: a mark just ended (key went up) → classify as dit/dah
+             * - false: a space just ended (key went down) → classify as gap
+             */
+            key_event_t event = timing_classifier_classify(
+                &s_timing, edge.duration_us, edge.was_mark);
 
 #ifdef ESP_PLATFORM
-                ESP_LOGD(TAG, "Edge: %s->%s dur=%lldus event=%d dit_avg=%lld",
-                         s_last_was_mark ? "MARK" : "SPACE",
-                         is_mark ? "MARK" : "SPACE",
-                         (long long)duration_us, (int)event,
-                         (long long)s_timing.dit_avg_us);
+            ESP_LOGD(TAG, "Edge: %s->%s dur=%lldus event=%d dit_avg=%lld",
+                     edge.was_mark ? "MARK" : "SPACE",
+                     edge.was_mark ? "SPACE" : "MARK",
+                     (long long)edge.duration_us, (int)event,
+                     (long long)s_timing.dit_avg_us);
 #endif
 
-                decoder_handle_event(event, s_sample_time_us);
-                s_last_event_us = s_sample_time_us;
-                s_last_event_wall_us = esp_timer_get_time();
-            }
-
-            /* Update edge tracking only on transitions */
-            s_last_edge_us = s_sample_time_us;
-            s_last_was_mark = is_mark;
+            decoder_handle_event(event, edge.at_us);
+            s_last_event_us = edge.at_us;
+            s_last_event_wall_us = keyer_clock_now_us();
         }
+
+        /* Update edge tracking only on transitions */
+        s_last_edge_us = edge.at_us;
     }
 
-    /* Update dropped count */
+    /* Update dropped count and glitches */
+    s_stats.glitches = s_filter.glitches;
     s_stats.samples_dropped = (uint32_t)best_effort_consumer_dropped(&s_consumer);
 
     /* Check for inactivity timeout (uses wall clock) */
@@ -280,6 +408,11 @@ void decoder_handle_event(key_event_t event, int64_t timestamp_us) {
              (int)event, (int)s_pattern_len, s_pattern, s_pattern_len);
 #endif
 
+    if ((event == KEY_EVENT_DIT || event == KEY_EVENT_DAH) && s_pattern_len == 0) {
+        s_sent = s_sent_next;  /* First element: the marker belongs to it */
+        s_sent_next = '\0';
+    }
+
     switch (event) {
         case KEY_EVENT_DIT:
             if (s_pattern_len < MAX_PATTERN_LEN) {
@@ -323,7 +456,8 @@ void decoder_handle_event(key_event_t event, int64_t timestamp_us) {
                      (int)s_pattern_len, s_pattern);
 #endif
             finalize_pattern(timestamp_us);
-            buffer_push(' ', timestamp_us);
+            buffer_push(' ', '\0', timestamp_us);
+            word_end();
             s_stats.words_decoded++;
             break;
 
@@ -342,83 +476,87 @@ bool decoder_is_enabled(void) {
     return atomic_load(&s_enabled);
 }
 
-size_t decoder_get_text(char *buf, size_t max_len) {
-    if (buf == NULL || max_len == 0) {
+size_t decoder_read_since(uint32_t *cursor, decoded_char_t *buf, size_t max_count) {
+    if (cursor == NULL || buf == NULL || max_count == 0) {
         return 0;
     }
 
-    size_t count = (s_buffer_count < max_len - 1) ? s_buffer_count : max_len - 1;
-    if (count == 0) {
-        buf[0] = '\0';
-        return 0;
+    uint32_t written = (uint32_t)atomic_load_explicit(&s_written, memory_order_acquire);
+    uint32_t held = buffer_held(written);
+    if (written - *cursor > held) {
+        *cursor = written - held;  /* Lapped, or cursor from before a reset */
     }
 
-    /* Calculate start position in circular buffer */
-    size_t start;
-    if (s_buffer_count >= DECODER_BUFFER_SIZE) {
-        start = s_buffer_head;  /* Buffer wrapped, head is oldest */
-    } else {
-        start = 0;
+    uint32_t avail = written - *cursor;
+    size_t count = (avail < max_count) ? avail : max_count;
+    for (size_t i = 0; i < count; i++) {
+        buf[i] = s_decoded_buffer[(*cursor + (uint32_t)i) % RING_SLOTS];
     }
 
-    /* Copy characters */
-    for (size_t i = 0; i < count; i++) {
-        size_t idx = (start + s_buffer_count - count + i) % DECODER_BUFFER_SIZE;
-        buf[i] = s_decoded_buffer[idx].character;
+    /* The writer may have reused the oldest slots while we copied. Up to
+     * character `now` (possibly in progress) that reaches now - RING_SLOTS,
+     * so everything from the oldest still held is intact */
+    atomic_thread_fence(memory_order_acquire);
+    uint32_t now = (uint32_t)atomic_load_explicit(&s_written, memory_order_relaxed);
+    uint32_t first_safe = now - buffer_held(now);
+    if ((int32_t)(first_safe - *cursor) > 0) {
+        uint32_t lost = first_safe - *cursor;
+        if (lost >= count) {
+            *cursor = first_safe;
+            return 0;
+        }
+        memmove(buf, buf + lost, (count - lost) * sizeof(*buf));
+        count -= lost;
+        *cursor += lost;
     }
-    buf[count] = '\0';
 
+    *cursor += (uint32_t)count;
     return count;
 }
 
-size_t decoder_get_text_with_timestamps(decoded_char_t *buf, size_t max_count) {
-    if (buf == NULL || max_count == 0) {
-        return 0;
-    }
+uint32_t decoder_text_seq(void) {
+    return (uint32_t)atomic_load_explicit(&s_written, memory_order_acquire);
+}
 
-    size_t count = (s_buffer_count < max_count) ? s_buffer_count : max_count;
-    if (count == 0) {
+size_t decoder_get_text(char *buf, size_t max_len) {
+    if (buf == NULL || max_len == 0) {
         return 0;
     }
 
-    /* Calculate start position */
-    size_t start;
-    if (s_buffer_count >= DECODER_BUFFER_SIZE) {
-        start = s_buffer_head;
-    } else {
-        start = 0;
+    /* Start max_len - 1 characters back from the newest */
+    uint32_t cursor = decoder_text_seq() - (uint32_t)(max_len - 1);
+    size_t len = 0;
+    decoded_char_t chunk[16];
+    size_t n;
+    while (len < max_len - 1 &&
+           (n = decoder_read_since(&cursor, chunk, sizeof(chunk) / sizeof(chunk[0]))) > 0) {
+        for (size_t i = 0; i < n && len < max_len - 1; i++) {
+            buf[len++] = chunk[i].character;
+        }
     }
+    buf[len] = '\0';
+    return len;
+}
 
-    /* Copy entries */
-    for (size_t i = 0; i < count; i++) {
-        size_t idx = (start + s_buffer_count - count + i) % DECODER_BUFFER_SIZE;
-        buf[i] = s_decoded_buffer[idx];
+size_t decoder_get_text_with_timestamps(decoded_char_t *buf, size_t max_count) {
+    if (buf == NULL || max_count == 0) {
+        return 0;
     }
 
-    return count;
+    uint32_t cursor = decoder_text_seq() - (uint32_t)max_count;
+    return decoder_read_since(&cursor, buf, max_count);
 }
 
 decoded_char_t decoder_get_last_char(void) {
-    if (s_buffer_count == 0) {
-        return (decoded_char_t){ .character = '\0', .timestamp_us = 0 };
-    }
-
-    size_t idx = (s_buffer_head + DECODER_BUFFER_SIZE - 1) % DECODER_BUFFER_SIZE;
-    return s_decoded_buffer[idx];
+    decoded_char_t last = { .character = '\0', .timestamp_us = 0, .sent = '\0' };
+    uint32_t cursor = decoder_text_seq() - 1U;
+    decoder_read_since(&cursor, &last, 1);
+    return last;
 }
 
 decoded_char_t decoder_pop_char(void) {
-    /* Check if there are unread characters */
-    if (s_buffer_read == s_buffer_head) {
-        return (decoded_char_t){ .character = '\0', .timestamp_us = 0 };
-    }
-
-    /* Get character at read position */
-    decoded_char_t result = s_decoded_buffer[s_buffer_read];
-
-    /* Advance read pointer */
-    s_buffer_read = (s_buffer_read + 1) % DECODER_BUFFER_SIZE;
-
+    decoded_char_t result = { .character = '\0', .timestamp_us = 0, .sent = '\0' };
+    decoder_read_since(&s_pop_cursor, &result, 1);
     return result;
 }
 
@@ -442,6 +580,57 @@ size_t decoder_get_current_pattern(char *buf, size_t max_len) {
     return len;
 }
 
+void decoder_set_own_callsign(const char *call) {
+    strncpy(s_own_call, (call != NULL) ? call : "", CALLSIGN_MAX_LEN);
+    s_own_call[CALLSIGN_MAX_LEN] = '\0';
+}
+
+size_t decoder_get_last_callsign(char *buf, size_t max_len) {
+    if (buf == NULL || max_len == 0) {
+        return 0;
+    }
+
+    for (int attempt = 0; attempt < 3; attempt++) {
+        unsigned before = atomic_load_explicit(&s_call_seq, memory_order_acquire);
+        if ((before & 1U) != 0) {
+            continue;  /* Being written */
+        }
+        char call[CALLSIGN_MAX_LEN + 1];
+        memcpy(call, s_last_call, sizeof(call));
+        atomic_thread_fence(memory_order_acquire);
+        if (atomic_load_explicit(&s_call_seq, memory_order_relaxed) == before) {
+            call[CALLSIGN_MAX_LEN] = '\0';
+            strncpy(buf, call, max_len - 1);
+            buf[max_len - 1] = '\0';
+            return strlen(buf);
+        }
+    }
+    buf[0] = '\0';
+    return 0;
+}
+
+bool decoder_set_last_callsign(const char *call) {
+    if (call == NULL) {
+        call = "";
+    }
+    char up[CALLSIGN_MAX_LEN + 1];
+    size_t len = strlen(call);
+    if (len > CALLSIGN_MAX_LEN) {
+        return false;
+    }
+    for (size_t i = 0; i <= len; i++) {
+        up[i] = (char)toupper((unsigned char)call[i]);
+    }
+    if (len > 0 && !callsign_is_plausible(up)) {
+        return false;
+    }
+    return store_last_call(up);
+}
+
+void decoder_set_glitch_filter_ms(uint32_t ms) {
+    edge_filter_set_min(&s_filter, (int64_t)ms * 1000);
+}
+
 decoder_state_t decoder_get_state(void) {
     return s_state;
 }
@@ -457,19 +646,21 @@ const timing_classifier_t *decoder_get_timing(void) {
 }
 
 void decoder_reset(void) {
-    s_buffer_head = 0;
-    s_buffer_count = 0;
-    s_buffer_read = 0;
+    buffer_clear();
     s_pattern_len = 0;
     s_state = DECODER_STATE_IDLE;
     s_last_edge_us = 0;
-    s_last_was_mark = false;
+    s_raw_mark = false;
+    edge_filter_init(&s_filter, s_filter.min_us);
     s_last_event_us = 0;
     s_last_event_wall_us = 0;
     s_sample_time_us = 0;
+    s_word_len = 0;
+    s_word_too_long = false;
+    s_sent_next = '\0';
+    s_sent = '\0';
 
     memset(&s_stats, 0, sizeof(s_stats));
-    memset(s_decoded_buffer, 0, sizeof(s_decoded_buffer));
     memset(s_pattern, 0, sizeof(s_pattern));
     memset(s_last_pattern, 0, sizeof(s_last_pattern));
 
@@ -477,7 +668,7 @@ void decoder_reset(void) {
 }
 
 size_t decoder_get_buffer_count(void) {
-    return s_buffer_count;
+    return buffer_held(decoder_text_seq());
 }
 
 size_t decoder_get_buffer_capacity(void) {
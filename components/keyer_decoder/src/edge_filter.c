@@ -0,0 +1,47 @@
+/**
+ * @file edge_filter.c
+ * @brief Key glitch filter implementation
+ */
+
+#include "edge_filter.h"
+
+void edge_filter_init(edge_filter_t *filter, int64_t min_us) {
+    filter->min_us = (min_us > 0) ? min_us : 0;
+    filter->level = false;
+    filter->since_us = 0;
+    filter->pending = false;
+    filter->pending_us = 0;
+    filter->glitches = 0;
+}
+
+void edge_filter_set_min(edge_filter_t *filter, int64_t min_us) {
+    filter->min_us = (min_us > 0) ? min_us : 0;
+}
+
+bool edge_filter_update(edge_filter_t *filter, bool mark, int64_t now_us,
+                        edge_filter_edge_t *edge) {
+    if (mark == filter->level) {
+        if (filter->pending) {
+            filter->pending = false;  /* Back before min_us: a glitch */
+            filter->glitches++;
+        }
+        return false;
+    }
+
+    if (!filter->pending) {
+        filter->pending = true;
+        filter->pending_us = now_us;
+    }
+    if (now_us - filter->pending_us < filter->min_us) {
+        return false;
+    }
+
+    edge->was_mark = filter->level;
+    edge->duration_us = filter->pending_us - filter->since_us;
+    edge->at_us = filter->pending_us;
+
+    filter->level = mark;
+    filter->since_us = filter->pending_us;
+    filter->pending = false;
+    return true;
+}
@@ -0,0 +1,76 @@
+/**
+ * @file display.h
+ * @brief SSD1306 OLED status display
+ *
+ * 128x64 SSD1306 on the I2C bus shared with the audio codec. Shows
+ * speed, active preset, iambic mode, TX/PTT, faults and the decoded
+ * text tail (see display_layout.h).
+ *
+ * TX and PTT come from the keying stream through a best-effort consumer:
+ * the display never slows down or faults the RT path. Only the pages
+ * that changed are sent.
+ */
+
+#ifndef KEYER_DISPLAY_H
+#define KEYER_DISPLAY_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include "esp_err.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Display configuration
+ */
+typedef struct {
+    uint8_t i2c_addr;   /**< 7-bit address (0x3C or 0x3D) */
+    bool flip;          /**< Rotate 180 degrees */
+} display_config_t;
+
+/**
+ * @brief Default display configuration
+ */
+#define DISPLAY_CONFIG_DEFAULT { \
+    .i2c_addr = 0x3C, \
+    .flip = false \
+}
+
+/**
+ * @brief Initialize the display
+ *
+ * Call once the shared I2C bus is up (hal_audio_init() brings it up).
+ *
+ * @param config Display configuration
+ * @return ESP_OK on success, ESP_ERR_INVALID_STATE without an I2C bus,
+ *         or the error of the controller setup
+ *
+ * @note Non-fatal: keyer continues if init fails
+ */
+esp_err_t display_init(const display_config_t *config);
+
+/**
+ * @brief Check if the display is running
+ */
+bool display_is_available(void);
+
+/**
+ * @brief Refresh the display (io_task, every pass)
+ *
+ * Drains the stream and the decoder every call, redraws at most every
+ * DISPLAY_REFRESH_US.
+ *
+ * @param now_us Current timestamp in microseconds
+ */
+void display_tick(int64_t now_us);
+
+/** Redraw interval */
+#define DISPLAY_REFRESH_US (100 * 1000)
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_DISPLAY_H */
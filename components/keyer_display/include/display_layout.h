@@ -0,0 +1,78 @@
+/**
+ * @file display_layout.h
+ * @brief OLED status screen layout (128x64, SSD1306 page format)
+ *
+ * Screen:
+ *   rows 0-1  speed at double size, "TX" while the key is down
+ *   row  2    active preset and iambic mode
+ *   row  3    PTT state, fault banner (right aligned)
+ *   rows 5-7  last decoded characters, oldest first
+ *
+ * The framebuffer is laid out as the controller expects it: 8 pages of
+ * 128 column bytes, bit 0 at the top. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_DISPLAY_LAYOUT_H
+#define KEYER_DISPLAY_LAYOUT_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define DISPLAY_WIDTH       128
+#define DISPLAY_PAGES       8
+#define DISPLAY_FB_SIZE     (DISPLAY_WIDTH * DISPLAY_PAGES)
+
+/** Characters per row (6 pixel cells) */
+#define DISPLAY_COLS        21
+
+/** Rows of decoded text at the bottom */
+#define DISPLAY_TEXT_ROWS   3
+
+/** Decoded characters kept for the text rows */
+#define DISPLAY_TEXT_LEN    (DISPLAY_COLS * DISPLAY_TEXT_ROWS)
+
+/** Fault banner characters (row 3 after the PTT state) */
+#define DISPLAY_FAULT_LEN   13
+
+/**
+ * @brief Everything shown on the screen
+ */
+typedef struct {
+    uint16_t wpm;                       /**< keyer.wpm */
+    uint8_t preset_index;               /**< Active preset */
+    const char *preset_name;            /**< Its name ("" if unnamed) */
+    char iambic_mode;                   /**< 'A' or 'B' */
+    bool tx;                            /**< Key down */
+    bool ptt;                           /**< PTT asserted */
+    const char *fault;                  /**< Fault banner, NULL if none */
+    char text[DISPLAY_TEXT_LEN + 1];    /**< Decoded text tail (see display_text_append()) */
+} display_status_t;
+
+/**
+ * @brief Add a decoded character to the text tail
+ *
+ * Once full, the oldest character is dropped (the text scrolls left).
+ *
+ * @param text Tail, NUL-terminated, DISPLAY_TEXT_LEN + 1 bytes
+ * @param c Character to add
+ */
+void display_text_append(char *text, char c);
+
+/**
+ * @brief Draw the status screen
+ *
+ * @param status What to show
+ * @param fb Framebuffer, DISPLAY_FB_SIZE bytes (fully overwritten)
+ */
+void display_render(const display_status_t *status, uint8_t *fb);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_DISPLAY_LAYOUT_H */
@@ -0,0 +1,243 @@
+/**
+ * @file display.c
+ * @brief SSD1306 OLED status display implementation
+ */
+
+#include "display.h"
+#include "display_layout.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "consumer.h"
+#include "fault_indicator.h"
+#include "decoder.h"
+#include "iambic_preset.h"
+#include "config.h"
+#include "hal_i2c.h"
+#include "esp_log.h"
+#include <string.h>
+
+static const char *TAG = "display";
+
+extern keying_stream_t g_keying_stream;
+extern fault_indicator_t g_fault_indicator;
+
+#define OLED_I2C_HZ         400000
+
+/** Control byte: a command stream or a data stream follows */
+#define OLED_CTRL_CMD       0x00
+#define OLED_CTRL_DATA      0x40
+
+/** Stream lag before the consumer skips ahead */
+#define DISPLAY_SKIP_THRESHOLD 1000
+
+static hal_i2c_dev_t s_dev = NULL;
+static best_effort_consumer_t s_consumer;
+static uint32_t s_text_cursor = 0;
+
+static display_status_t s_status;
+static char s_preset_name[IAMBIC_PRESET_NAME_MAX];  /**< s_status.preset_name points here */
+static int64_t s_last_key_down_us = 0;
+static int64_t s_last_refresh_us = 0;
+
+static uint8_t s_fb[DISPLAY_FB_SIZE];
+static uint8_t s_shown[DISPLAY_FB_SIZE];
+static bool s_shown_valid = false;
+
+/* ============================================================================
+ * SSD1306
+ * ============================================================================ */
+
+static esp_err_t oled_commands(const uint8_t *cmds, size_t len) {
+    uint8_t buf[32];
+    if (len + 1 > sizeof(buf)) {
+        return ESP_ERR_INVALID_SIZE;
+    }
+    buf[0] = OLED_CTRL_CMD;
+    memcpy(&buf[1], cmds, len);
+    return hal_i2c_write(s_dev, buf, len + 1);
+}
+
+static esp_err_t oled_setup(bool flip) {
+    const uint8_t init[] = {
+        0xAE,                   /* Display off */
+        0xD5, 0x80,             /* Clock divide */
+        0xA8, 0x3F,             /* Multiplex 64 */
+        0xD3, 0x00,             /* No offset */
+        0x40,                   /* Start line 0 */
+        0x8D, 0x14,             /* Charge pump on */
+        0x20, 0x02,             /* Page addressing */
+        flip ? 0xA0 : 0xA1,     /* Segment remap */
+        flip ? 0xC0 : 0xC8,     /* COM scan direction */
+        0xDA, 0x12,             /* COM pins */
+        0x81, 0xCF,             /* Contrast */
+        0xD9, 0xF1,             /* Precharge */
+        0xDB, 0x40,             /* VCOMH */
+        0xA4,                   /* Follow RAM */
+        0xA6,                   /* Normal, not inverted */
+        0xAF,                   /* Display on */
+    };
+    return oled_commands(init, sizeof(init));
+}
+
+/**
+ * @brief Send one page (128 columns) of the framebuffer
+ */
+static esp_err_t oled_write_page(int page, const uint8_t *data) {
+    const uint8_t addr[] = {
+        (uint8_t)(0xB0 | page),     /* Page */
+        0x00, 0x10,                 /* Column 0 */
+    };
+    esp_err_t err = oled_commands(addr, sizeof(addr));
+    if (err != ESP_OK) {
+        return err;
+    }
+
+    uint8_t buf[DISPLAY_WIDTH + 1];
+    buf[0] = OLED_CTRL_DATA;
+    memcpy(&buf[1], data, DISPLAY_WIDTH);
+    return hal_i2c_write(s_dev, buf, sizeof(buf));
+}
+
+/**
+ * @brief Send the pages that differ from what the panel shows
+ *
+ * A full screen takes ~25 ms at 400 kHz; a changed WPM or text row
+ * is one or two pages.
+ */
+static void oled_flush(void) {
+    for (int page = 0; page < DISPLAY_PAGES; page++) {
+        const uint8_t *data = &s_fb[page * DISPLAY_WIDTH];
+        uint8_t *shown = &s_shown[page * DISPLAY_WIDTH];
+        if (s_shown_valid && memcmp(data, shown, DISPLAY_WIDTH) == 0) {
+            continue;
+        }
+        if (oled_write_page(page, data) != ESP_OK) {
+            s_shown_valid = false;  /* Resend everything next time */
+            return;
+        }
+        memcpy(shown, data, DISPLAY_WIDTH);
+    }
+    s_shown_valid = true;
+}
+
+/* ============================================================================
+ * Status
+ * ============================================================================ */
+
+/**
+ * @brief Follow the key through the stream
+ *
+ * PTT is derived the way the RT task drives it: on with the key, off
+ * timing.ptt_tail_ms after the last key down.
+ */
+static void update_keying(int64_t now_us) {
+    stream_sample_t sample;
+    bool key_down = false;
+    bool seen = false;
+    while (best_effort_consumer_tick(&s_consumer, &sample)) {
+        seen = true;
+        key_down = (sample.local_key != 0);
+        if (key_down) {
+            s_last_key_down_us = now_us;
+        }
+    }
+    if (seen) {
+        s_status.tx = key_down;
+    }
+
+    int64_t tail_us = (int64_t)CONFIG_GET_PTT_TAIL_MS() * 1000;
+    s_status.ptt = s_status.tx ||
+                   (s_last_key_down_us != 0 && now_us - s_last_key_down_us < tail_us);
+}
+
+static void update_text(void) {
+    decoded_char_t chars[8];
+    size_t n;
+    while ((n = decoder_read_since(&s_text_cursor, chars, sizeof(chars) / sizeof(chars[0]))) > 0) {
+        for (size_t i = 0; i < n; i++) {
+            display_text_append(s_status.text, chars[i].character);
+        }
+    }
+}
+
+static void update_status(void) {
+    const iambic_preset_t *preset = iambic_preset_active();
+    s_status.wpm = CONFIG_GET_WPM();
+    s_status.iambic_mode = (CONFIG_GET_IAMBIC_MODE() == IAMBIC_MODE_A) ? 'A' : 'B';
+    s_status.preset_index = (uint8_t)iambic_preset_active_index();
+    iambic_preset_get_name(preset, s_preset_name, sizeof(s_preset_name));
+    s_status.preset_name = s_preset_name;
+    s_status.fault = fault_indicator_banner(&g_fault_indicator);
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+esp_err_t display_init(const display_config_t *config) {
+    esp_err_t err = hal_i2c_probe(config->i2c_addr);  /* INVALID_STATE: no bus yet */
+    if (err != ESP_OK) {
+        return err;
+    }
+
+    err = hal_i2c_add_device(config->i2c_addr, OLED_I2C_HZ, &s_dev);
+    if (err != ESP_OK) {
+        return err;
+    }
+
+    err = oled_setup(config->flip);
+    if (err != ESP_OK) {
+        hal_i2c_remove_device(s_dev);
+        s_dev = NULL;
+        return err;
+    }
+
+    best_effort_consumer_init(&s_consumer, &g_keying_stream, DISPLAY_SKIP_THRESHOLD);
+    best_effort_consumer_track(&s_consumer, consumer_lag_register("display"));
+    s_text_cursor = decoder_text_seq();
+    s_shown_valid = false;
+    ESP_LOGI(TAG, "SSD1306 at 0x%02X", config->i2c_addr);
+    return ESP_OK;
+}
+
+bool display_is_available(void) {
+    return s_dev != NULL;
+}
+
+void display_tick(int64_t now_us) {
+    if (s_dev == NULL) {
+        return;
+    }
+
+    update_keying(now_us);
+    update_text();
+    if (now_us - s_last_refresh_us < DISPLAY_REFRESH_US) {
+        return;
+    }
+    s_last_refresh_us = now_us;
+
+    update_status();
+    display_render(&s_status, s_fb);
+    oled_flush();
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+esp_err_t display_init(const display_config_t *config) {
+    (void)config;
+    return ESP_ERR_NOT_SUPPORTED;
+}
+
+bool display_is_available(void) {
+    return false;
+}
+
+void display_tick(int64_t now_us) {
+    (void)now_us;
+}
+
+#endif /* ESP_PLATFORM */
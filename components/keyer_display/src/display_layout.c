@@ -0,0 +1,199 @@
+/**
+ * @file display_layout.c
+ * @brief OLED status screen layout implementation
+ */
+
+#include "display_layout.h"
+#include <stdio.h>
+#include <string.h>
+
+/** Glyph width in pixels (plus one blank column per cell) */
+#define GLYPH_WIDTH 5
+#define CELL_WIDTH  (GLYPH_WIDTH + 1)
+
+/** Row of the first decoded text line */
+#define TEXT_ROW    (DISPLAY_PAGES - DISPLAY_TEXT_ROWS)
+
+/* ============================================================================
+ * Font
+ * ============================================================================ */
+
+/**
+ * 5x7 font, ' ' to '_', one byte per column, bit 0 at the top.
+ * Lower case is drawn as upper case: CW has no case.
+ */
+static const uint8_t FONT[][GLYPH_WIDTH] = {
+    {0x00, 0x00, 0x00, 0x00, 0x00},  /* ' ' */
+    {0x00, 0x00, 0x5F, 0x00, 0x00},  /* ! */
+    {0x00, 0x07, 0x00, 0x07, 0x00},  /* " */
+    {0x14, 0x7F, 0x14, 0x7F, 0x14},  /* # */
+    {0x24, 0x2A, 0x7F, 0x2A, 0x12},  /* $ */
+    {0x23, 0x13, 0x08, 0x64, 0x62},  /* % */
+    {0x36, 0x49, 0x55, 0x22, 0x50},  /* & */
+    {0x00, 0x05, 0x03, 0x00, 0x00},  /* ' */
+    {0x00, 0x1C, 0x22, 0x41, 0x00},  /* ( */
+    {0x00, 0x41, 0x22, 0x1C, 0x00},  /* ) */
+    {0x08, 0x2A, 0x1C, 0x2A, 0x08},  /* * */
+    {0x08, 0x08, 0x3E, 0x08, 0x08},  /* + */
+    {0x00, 0x50, 0x30, 0x00, 0x00},  /* , */
+    {0x08, 0x08, 0x08, 0x08, 0x08},  /* - */
+    {0x00, 0x60, 0x60, 0x00, 0x00},  /* . */
+    {0x20, 0x10, 0x08, 0x04, 0x02},  /* / */
+    {0x3E, 0x51, 0x49, 0x45, 0x3E},  /* 0 */
+    {0x00, 0x42, 0x7F, 0x40, 0x00},  /* 1 */
+    {0x42, 0x61, 0x51, 0x49, 0x46},  /* 2 */
+    {0x21, 0x41, 0x45, 0x4B, 0x31},  /* 3 */
+    {0x18, 0x14, 0x12, 0x7F, 0x10},  /* 4 */
+    {0x27, 0x45, 0x45, 0x45, 0x39},  /* 5 */
+    {0x3C, 0x4A, 0x49, 0x49, 0x30},  /* 6 */
+    {0x01, 0x71, 0x09, 0x05, 0x03},  /* 7 */
+    {0x36, 0x49, 0x49, 0x49, 0x36},  /* 8 */
+    {0x06, 0x49, 0x49, 0x29, 0x1E},  /* 9 */
+    {0x00, 0x36, 0x36, 0x00, 0x00},  /* : */
+    {0x00, 0x56, 0x36, 0x00, 0x00},  /* ; */
+    {0x08, 0x14, 0x22, 0x41, 0x00},  /* < */
+    {0x14, 0x14, 0x14, 0x14, 0x14},  /* = */
+    {0x00, 0x41, 0x22, 0x14, 0x08},  /* > */
+    {0x02, 0x01, 0x51, 0x09, 0x06},  /* ? */
+    {0x32, 0x49, 0x79, 0x41, 0x3E},  /* @ */
+    {0x7E, 0x11, 0x11, 0x11, 0x7E},  /* A */
+    {0x7F, 0x49, 0x49, 0x49, 0x36},  /* B */
+    {0x3E, 0x41, 0x41, 0x41, 0x22},  /* C */
+    {0x7F, 0x41, 0x41, 0x22, 0x1C},  /* D */
+    {0x7F, 0x49, 0x49, 0x49, 0x41},  /* E */
+    {0x7F, 0x09, 0x09, 0x09, 0x01},  /* F */
+    {0x3E, 0x41, 0x49, 0x49, 0x7A},  /* G */
+    {0x7F, 0x08, 0x08, 0x08, 0x7F},  /* H */
+    {0x00, 0x41, 0x7F, 0x41, 0x00},  /* I */
+    {0x20, 0x40, 0x41, 0x3F, 0x01},  /* J */
+    {0x7F, 0x08, 0x14, 0x22, 0x41},  /* K */
+    {0x7F, 0x40, 0x40, 0x40, 0x40},  /* L */
+    {0x7F, 0x02, 0x0C, 0x02, 0x7F},  /* M */
+    {0x7F, 0x04, 0x08, 0x10, 0x7F},  /* N */
+    {0x3E, 0x41, 0x41, 0x41, 0x3E},  /* O */
+    {0x7F, 0x09, 0x09, 0x09, 0x06},  /* P */
+    {0x3E, 0x41, 0x51, 0x21, 0x5E},  /* Q */
+    {0x7F, 0x09, 0x19, 0x29, 0x46},  /* R */
+    {0x46, 0x49, 0x49, 0x49, 0x31},  /* S */
+    {0x01, 0x01, 0x7F, 0x01, 0x01},  /* T */
+    {0x3F, 0x40, 0x40, 0x40, 0x3F},  /* U */
+    {0x1F, 0x20, 0x40, 0x20, 0x1F},  /* V */
+    {0x3F, 0x40, 0x38, 0x40, 0x3F},  /* W */
+    {0x63, 0x14, 0x08, 0x14, 0x63},  /* X */
+    {0x07, 0x08, 0x70, 0x08, 0x07},  /* Y */
+    {0x61, 0x51, 0x49, 0x45, 0x43},  /* Z */
+    {0x00, 0x7F, 0x41, 0x41, 0x00},  /* [ */
+    {0x02, 0x04, 0x08, 0x10, 0x20},  /* \ */
+    {0x00, 0x41, 0x41, 0x7F, 0x00},  /* ] */
+    {0x04, 0x02, 0x01, 0x02, 0x04},  /* ^ */
+    {0x40, 0x40, 0x40, 0x40, 0x40},  /* _ */
+};
+
+#define FONT_FIRST ' '
+#define FONT_LAST  '_'
+
+static const uint8_t *glyph(char c) {
+    if (c >= 'a' && c <= 'z') {
+        c = (char)(c - 'a' + 'A');
+    }
+    if (c < FONT_FIRST || c > FONT_LAST) {
+        c = '?';
+    }
+    return FONT[c - FONT_FIRST];
+}
+
+/* ============================================================================
+ * Drawing
+ * ============================================================================ */
+
+/**
+ * @brief Draw text on one page row, clipped at the right edge
+ */
+static void draw_text(uint8_t *fb, int row, int col, const char *s) {
+    uint8_t *line = &fb[row * DISPLAY_WIDTH];
+    for (int x = col * CELL_WIDTH; *s != '\0' && x + GLYPH_WIDTH <= DISPLAY_WIDTH; s++) {
+        memcpy(&line[x], glyph(*s), GLYPH_WIDTH);
+        x += CELL_WIDTH;
+    }
+}
+
+/**
+ * @brief Spread the low 4 bits of a column over 8 (each pixel doubled)
+ */
+static uint8_t stretch(uint8_t bits) {
+    uint8_t out = 0;
+    for (int i = 0; i < 4; i++) {
+        if (bits & (1U << i)) {
+            out |= (uint8_t)(3U << (2 * i));
+        }
+    }
+    return out;
+}
+
+/**
+ * @brief Draw double-size text over two page rows
+ *
+ * @param x Left edge in pixels
+ */
+static void draw_text_2x(uint8_t *fb, int row, int x, const char *s) {
+    uint8_t *top = &fb[row * DISPLAY_WIDTH];
+    uint8_t *bottom = &fb[(row + 1) * DISPLAY_WIDTH];
+    for (; *s != '\0' && x + 2 * GLYPH_WIDTH <= DISPLAY_WIDTH; s++) {
+        const uint8_t *g = glyph(*s);
+        for (int i = 0; i < GLYPH_WIDTH; i++) {
+            uint8_t hi = stretch(g[i]);
+            uint8_t lo = stretch((uint8_t)(g[i] >> 4));
+            top[x + 2 * i] = top[x + 2 * i + 1] = hi;
+            bottom[x + 2 * i] = bottom[x + 2 * i + 1] = lo;
+        }
+        x += 2 * CELL_WIDTH;
+    }
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void display_text_append(char *text, char c) {
+    size_t len = strlen(text);
+    if (len == DISPLAY_TEXT_LEN) {
+        memmove(text, text + 1, len - 1);
+        len--;
+    }
+    text[len] = c;
+    text[len + 1] = '\0';
+}
+
+void display_render(const display_status_t *status, uint8_t *fb) {
+    memset(fb, 0, DISPLAY_FB_SIZE);
+    char line[32];  /* Longer than a row: draw_text() clips */
+
+    snprintf(line, sizeof(line), "%u WPM", (unsigned)status->wpm);
+    draw_text_2x(fb, 0, 0, line);
+    if (status->tx) {
+        draw_text_2x(fb, 0, DISPLAY_WIDTH - 2 * (CELL_WIDTH * 2) + 1, "TX");
+    }
+
+    const char *name = (status->preset_name != NULL) ? status->preset_name : "";
+    snprintf(line, sizeof(line), "P%u %-11.11s Mode %c",
+             (unsigned)status->preset_index, name, status->iambic_mode);
+    draw_text(fb, 2, 0, line);
+
+    draw_text(fb, 3, 0, status->ptt ? "PTT ON" : "PTT off");
+    if (status->fault != NULL) {
+        snprintf(line, sizeof(line), "%.*s", DISPLAY_FAULT_LEN, status->fault);
+        draw_text(fb, 3, DISPLAY_COLS - (int)strlen(line), line);
+    }
+
+    const char *text = status->text;
+    for (int row = TEXT_ROW; row < DISPLAY_PAGES && *text != '\0'; row++) {
+        size_t n = strlen(text);
+        if (n > DISPLAY_COLS) {
+            n = DISPLAY_COLS;
+        }
+        memcpy(line, text, n);
+        line[n] = '\0';
+        draw_text(fb, row, 0, line);
+        text += n;
+    }
+}
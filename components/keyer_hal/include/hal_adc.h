@@ -0,0 +1,68 @@
+/**
+ * @file hal_adc.h
+ * @brief Shared ADC1 one-shot inputs (speed pot, supply voltage)
+ *
+ * The ADC1 unit can only be opened once, so every analog input takes a
+ * channel on the same unit through here. ADC2 is avoided: it is shared
+ * with WiFi.
+ */
+
+#ifndef KEYER_HAL_ADC_H
+#define KEYER_HAL_ADC_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief One ADC1 input
+ */
+typedef struct {
+    int channel;        /**< ADC1 channel, -1 if not set up */
+    void *cali;         /**< Calibration handle (NULL = uncalibrated) */
+} hal_adc_input_t;
+
+/** Input not set up */
+#define HAL_ADC_INPUT_NONE { .channel = -1, .cali = NULL }
+
+/**
+ * @brief Set up the ADC1 channel behind a GPIO
+ *
+ * 12 bits, full-scale attenuation (about 0..3.1 V at the pin).
+ *
+ * @param in Input to fill
+ * @param pin GPIO (ADC1-capable), 0 = not fitted
+ * @param name Name for the log
+ * @return true if ready, false if pin is 0, not an ADC1 pin, or the ADC
+ *         could not be set up
+ */
+bool hal_adc_input_init(hal_adc_input_t *in, uint8_t pin, const char *name);
+
+/**
+ * @brief Raw reading
+ *
+ * Takes a few microseconds. Call from bg_task.
+ *
+ * @return 0..4095, -1 if not set up or the read failed
+ */
+int hal_adc_read_raw(const hal_adc_input_t *in);
+
+/**
+ * @brief Reading in millivolts at the pin
+ *
+ * Uses the eFuse calibration when the chip has one, a nominal full
+ * scale otherwise.
+ *
+ * @return Millivolts, -1 if not set up or the read failed
+ */
+int hal_adc_read_mv(const hal_adc_input_t *in);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_ADC_H */
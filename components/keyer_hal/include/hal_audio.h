@@ -15,6 +15,29 @@
 extern "C" {
 #endif
 
+/**
+ * @brief Codec ADC input (same order as audio.input_source)
+ */
+typedef enum {
+    HAL_AUDIO_INPUT_NONE = 0,       /**< No input selected */
+    HAL_AUDIO_INPUT_ANALOG = 1,     /**< MIC1P/MIC1N, mic or line level */
+    HAL_AUDIO_INPUT_DIGITAL = 2,    /**< PDM microphone */
+} hal_audio_input_t;
+
+/**
+ * @brief Codec output routing
+ */
+typedef enum {
+    HAL_AUDIO_OUTPUT_SPEAKER = 0,   /**< Line drive into the speaker amp */
+    HAL_AUDIO_OUTPUT_HEADPHONE = 1, /**< Headphone drive (jack plugged) */
+} hal_audio_output_t;
+
+/** Highest input PGA gain (3 dB steps) */
+#define HAL_AUDIO_INPUT_GAIN_MAX_DB 30
+
+/** Last ES8311 control register (chip ID and version sit at 0xFD-0xFF) */
+#define HAL_AUDIO_CODEC_REG_LAST 0x45
+
 /**
  * @brief Audio HAL configuration
  */
@@ -29,6 +52,7 @@ typedef struct {
     int i2s_bclk_pin;
     int i2s_lrck_pin;
     int i2s_dout_pin;
+    int i2s_din_pin;         /**< Codec ADC data (-1 = input not wired) */
 
     /* Audio parameters */
     uint32_t sample_rate;    /**< Sample rate in Hz (typically 8000) */
@@ -38,6 +62,13 @@ typedef struct {
     bool pa_via_io_expander; /**< true = TCA9555, false = direct GPIO */
     int pa_pin;              /**< TCA9555 pin or GPIO number */
     bool pa_active_high;     /**< PA enable polarity */
+
+    /* Input */
+    int mic_bias_pin;        /**< GPIO switching the mic bias supply (-1 = none) */
+
+    /* Headphone jack switch (internal pull-up) */
+    int hp_detect_pin;       /**< GPIO reading the jack switch (-1 = none) */
+    bool hp_detect_high;     /**< Reads high with headphones plugged */
 } hal_audio_config_t;
 
 /**
@@ -51,11 +82,15 @@ typedef struct {
     .i2s_bclk_pin = 13, \
     .i2s_lrck_pin = 14, \
     .i2s_dout_pin = 16, \
+    .i2s_din_pin = 15, \
     .sample_rate = 8000, \
     .volume_percent = 70, \
     .pa_via_io_expander = true, \
     .pa_pin = 8, \
     .pa_active_high = true, \
+    .mic_bias_pin = -1, \
+    .hp_detect_pin = -1, \
+    .hp_detect_high = true, \
 }
 
 /**
@@ -90,6 +125,70 @@ esp_err_t hal_audio_set_volume(uint8_t volume_percent);
  */
 esp_err_t hal_audio_set_pa(bool enable);
 
+/**
+ * @brief Select the codec ADC input
+ * @param input Input (NONE leaves the ADC unconnected)
+ * @return ESP_OK on success, ESP_ERR_INVALID_STATE without a codec,
+ *         ESP_ERR_NOT_SUPPORTED if i2s_din_pin is not wired (ADC off)
+ * @note NOT RT-safe (I2C transaction, skipped if unchanged)
+ */
+esp_err_t hal_audio_set_input(hal_audio_input_t input);
+
+/**
+ * @brief Set the analog input PGA gain
+ * @param gain_db 0-30 dB, rounded down to a 3 dB step
+ * @return ESP_OK on success, ESP_ERR_INVALID_STATE without a codec
+ * @note NOT RT-safe (I2C transaction, skipped if unchanged)
+ */
+esp_err_t hal_audio_set_input_gain(uint8_t gain_db);
+
+/**
+ * @brief Switch the microphone bias supply
+ *
+ * The ES8311 has no bias output: the board switches it with
+ * mic_bias_pin.
+ *
+ * @param on true = bias on
+ * @return ESP_OK on success, ESP_ERR_NOT_SUPPORTED to turn it on
+ *         without a bias pin
+ */
+esp_err_t hal_audio_set_mic_bias(bool on);
+
+/**
+ * @brief Read the headphone jack switch (raw, not debounced)
+ * @return true if headphones are plugged (false without a detect pin)
+ */
+bool hal_audio_read_hp_detect(void);
+
+/**
+ * @brief Check if a headphone detect pin is fitted
+ */
+bool hal_audio_hp_detect_fitted(void);
+
+/**
+ * @brief Route the codec output
+ *
+ * Headphones switch the ES8311 output stage to headphone drive; with
+ * mute_speaker the speaker amp is also held off while they are
+ * plugged (the codec stays unmuted, unlike hal_audio_set_pa(false)).
+ *
+ * @param output Speaker or headphones
+ * @param mute_speaker Hold the speaker amp off on headphones
+ * @return ESP_OK on success, ESP_ERR_INVALID_STATE without a codec
+ * @note NOT RT-safe (I2C transactions, skipped if unchanged)
+ */
+esp_err_t hal_audio_set_output(hal_audio_output_t output, bool mute_speaker);
+
+/**
+ * @brief Get the current output routing
+ */
+hal_audio_output_t hal_audio_get_output(void);
+
+/**
+ * @brief Check if the speaker amp is powered (enabled and not muted)
+ */
+bool hal_audio_speaker_on(void);
+
 /**
  * @brief Start I2S output
  */
@@ -106,6 +205,39 @@ void hal_audio_stop(void);
  */
 bool hal_audio_is_available(void);
 
+/**
+ * @brief Probe the ES8311 codec on the I2C bus
+ *
+ * Checks that the codec still ACKs its address. Safe to call while
+ * audio is running (I2C only, no I2S traffic).
+ *
+ * @return ESP_OK if the codec responds, ESP_ERR_INVALID_STATE if the
+ *         I2C bus is not initialized, ESP_ERR_NOT_FOUND if no ACK
+ */
+esp_err_t hal_audio_probe(void);
+
+/**
+ * @brief Read an ES8311 register (bring-up and debugging)
+ * @param reg Register address
+ * @param value Register contents
+ * @return ESP_OK on success, ESP_ERR_INVALID_STATE without a codec
+ * @note NOT RT-safe (I2C transaction)
+ */
+esp_err_t hal_audio_codec_read(uint8_t reg, uint8_t *value);
+
+/**
+ * @brief Write an ES8311 register (bring-up and debugging)
+ *
+ * Goes around the codec driver: the change lasts until the driver or
+ * the input settings write the register again.
+ *
+ * @param reg Register address
+ * @param value New contents
+ * @return ESP_OK on success, ESP_ERR_INVALID_STATE without a codec
+ * @note NOT RT-safe (I2C transaction)
+ */
+esp_err_t hal_audio_codec_write(uint8_t reg, uint8_t value);
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,54 @@
+/**
+ * @file hal_buzzer.h
+ * @brief Piezo buzzer sidetone (LEDC square wave)
+ *
+ * Second sidetone channel, independent of the codec: a key-down
+ * indicator that works with no headphones plugged in, or on boards
+ * without a codec. Fixed 50% duty, no volume control.
+ */
+
+#ifndef KEYER_HAL_BUZZER_H
+#define KEYER_HAL_BUZZER_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Set up the LEDC timer and channel (silent)
+ *
+ * @param pin Buzzer GPIO (0 = not fitted)
+ * @param freq_hz Tone frequency
+ * @return true if fitted and ready
+ */
+bool hal_buzzer_init(uint8_t pin, uint32_t freq_hz);
+
+/**
+ * @brief Sound or silence the buzzer
+ *
+ * Registers are written only on change (LEDC spinlock, no mutex), so it
+ * can be called every RT tick. No-op if not fitted.
+ *
+ * @param on true to sound
+ */
+void hal_buzzer_set(bool on);
+
+/**
+ * @brief Change the tone frequency
+ *
+ * Call on a config change only, and not from the RT task: reprogramming
+ * the LEDC timer recomputes the divider and may log. bg_task applies
+ * audio.sidetone_freq_hz.
+ *
+ * @param freq_hz Tone frequency
+ */
+void hal_buzzer_set_frequency(uint32_t freq_hz);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_BUZZER_H */
@@ -0,0 +1,52 @@
+/**
+ * @file hal_encoder.h
+ * @brief Rotary encoder (quadrature, PCNT peripheral) for speed control
+ */
+
+#ifndef KEYER_HAL_ENCODER_H
+#define KEYER_HAL_ENCODER_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Quadrature counts per detent (typical mechanical encoder) */
+#define HAL_ENCODER_COUNTS_PER_DETENT 4
+
+/**
+ * @brief Start counting on two GPIOs (internal pull-ups)
+ *
+ * @param pin_a Encoder A output
+ * @param pin_b Encoder B output
+ * @return true if counting, false if a pin is 0 (not fitted) or the
+ *         PCNT unit could not be set up
+ */
+bool hal_encoder_init(uint8_t pin_a, uint8_t pin_b);
+
+/**
+ * @brief Take the detents turned since the last call
+ *
+ * Partial detents are kept for the next call. Call from bg_task.
+ *
+ * @return Detents, positive clockwise (0 if not fitted)
+ */
+int hal_encoder_take_detents(void);
+
+/**
+ * @brief Stop counting for light sleep, or start again after it
+ *
+ * The PCNT unit holds a PM lock while counting. Turns during sleep are
+ * not counted and do not wake the chip.
+ *
+ * @param sleep true before sleep, false after wakeup
+ */
+void hal_encoder_sleep(bool sleep);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_ENCODER_H */
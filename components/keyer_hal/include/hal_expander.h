@@ -0,0 +1,63 @@
+/**
+ * @file hal_expander.h
+ * @brief I2C GPIO expander for extra front panel inputs
+ *
+ * An MCP23017 (16 inputs) or PCF8574/PCF8574A (8 inputs) on the shared
+ * I2C bus, every pin an input with a pull-up and closed to ground. The
+ * pins are read in one transaction, so bg_task can poll them every
+ * tick. Core 1 only (hal_i2c).
+ *
+ * The TCA9555 on codec boards sits at 0x20: give the expander another
+ * address there.
+ */
+
+#ifndef KEYER_HAL_EXPANDER_H
+#define KEYER_HAL_EXPANDER_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include "esp_err.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Expander chips (hardware.expander_type values)
+ */
+typedef enum {
+    HAL_EXPANDER_NONE = 0,
+    HAL_EXPANDER_PCF8574 = 1,   /**< 8 quasi-bidirectional pins */
+    HAL_EXPANDER_MCP23017 = 2,  /**< 16 pins, ports A then B */
+} hal_expander_type_t;
+
+/**
+ * @brief Find the chip and make every pin a pulled-up input
+ *
+ * Needs the I2C bus up (hal_i2c_init()).
+ *
+ * @param type Chip, HAL_EXPANDER_NONE for no expander
+ * @param addr 7-bit address
+ * @return ESP_OK, ESP_ERR_NOT_FOUND if it does not answer,
+ *         ESP_ERR_INVALID_STATE without a bus, or the I2C error
+ */
+esp_err_t hal_expander_init(hal_expander_type_t type, uint8_t addr);
+
+/**
+ * @brief Number of inputs (0 if no expander was set up)
+ */
+uint8_t hal_expander_input_count(void);
+
+/**
+ * @brief Read every input in one transaction
+ *
+ * @param closed Bit n set while input n is pulled to ground (0 on error)
+ * @return ESP_OK, ESP_ERR_INVALID_STATE if not set up, or the I2C error
+ */
+esp_err_t hal_expander_read(uint16_t *closed);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_EXPANDER_H */
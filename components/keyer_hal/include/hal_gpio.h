@@ -8,6 +8,7 @@
 
 #include <stdint.h>
 #include <stdbool.h>
+#include <stddef.h>
 #include "sample.h"
 
 #ifdef __cplusplus
@@ -20,12 +21,28 @@ extern "C" {
 typedef struct {
     uint8_t dit_pin;       /**< DIT paddle GPIO pin */
     uint8_t dah_pin;       /**< DAH paddle GPIO pin */
-    uint8_t tx_pin;        /**< TX output GPIO pin */
-    bool active_low;       /**< Paddle inputs are active low */
+    uint8_t tx_pin;        /**< TX output GPIO pin (radio 1) */
+    uint8_t ptt_pin;       /**< PTT output GPIO pin (radio 1, 0 = not fitted) */
+    uint8_t tx2_pin;       /**< TX output GPIO pin (radio 2, 0 = no second radio) */
+    uint8_t ptt2_pin;      /**< PTT output GPIO pin (radio 2, 0 = not fitted) */
+    uint8_t antenna_pin;   /**< Antenna / T-R relay output GPIO pin (0 = not fitted) */
+    uint8_t amp_pin;       /**< Amplifier PTT output GPIO pin (0 = not fitted) */
+    bool active_low;       /**< Paddle inputs are active low (internal pull-up, else pull-down) */
+    bool touch_paddles;    /**< Paddle pins are touch pads (hal_touch.h), no GPIO inputs or ISR */
     bool tx_active_high;   /**< TX output is active high */
+    bool tx_open_drain;    /**< TX output is open drain (internal pull-up) instead of push-pull */
+    uint32_t tx_min_key_up_us; /**< Shortest key-up on the TX output in µs (0 = off) */
     uint32_t isr_blanking_us; /**< ISR blanking period in µs (0 = disable ISR, use polling only) */
+    uint16_t glitch_filter_ns; /**< Paddle hardware glitch filter window in ns (0 = off) */
+    uint8_t key_in_pin;    /**< Straight key / external keyer input GPIO pin (0 = not fitted) */
+    bool key_in_active_low; /**< Straight key input is active low (internal pull-up) */
+    uint8_t ptt_in_pin;    /**< PTT input GPIO pin, footswitch or mic PTT (0 = not fitted) */
+    bool ptt_in_active_low; /**< PTT input is active low (internal pull-up) */
 } hal_gpio_config_t;
 
+/** Maximum number of message memory buttons */
+#define HAL_GPIO_BUTTON_MAX 4
+
 /**
  * @brief Default GPIO configuration
  */
@@ -33,9 +50,22 @@ typedef struct {
     .dit_pin = 4, \
     .dah_pin = 5, \
     .tx_pin = 6, \
+    .ptt_pin = 0, \
+    .tx2_pin = 0, \
+    .ptt2_pin = 0, \
+    .antenna_pin = 0, \
+    .amp_pin = 0, \
     .active_low = true, \
+    .touch_paddles = false, \
     .tx_active_high = true, \
-    .isr_blanking_us = 1500 \
+    .tx_open_drain = false, \
+    .tx_min_key_up_us = 0, \
+    .isr_blanking_us = 1500, \
+    .glitch_filter_ns = 0, \
+    .key_in_pin = 0, \
+    .key_in_active_low = true, \
+    .ptt_in_pin = 0, \
+    .ptt_in_active_low = true \
 }
 
 /**
@@ -46,22 +76,119 @@ void hal_gpio_init(const hal_gpio_config_t *config);
 
 /**
  * @brief Read paddle state
+ *
+ * Both paddles and the straight key come from one snapshot of the input
+ * register, inverted per pin for active low inputs. With touch_paddles
+ * set, reads the touch pads instead (hal_touch_read_paddles()). The
+ * straight key input, if fitted, is GPIO_KEY_BIT (raw, debounced by the
+ * caller: straight_key.h). RT-safe: register reads only.
+ *
  * @return Current paddle GPIO state
  */
 gpio_state_t hal_gpio_read_paddles(void);
 
+/**
+ * @brief Read the PTT input (footswitch, mic PTT)
+ *
+ * @return true while held (raw, debounced by the caller); false if not fitted
+ */
+bool hal_gpio_read_ptt_in(void);
+
 /**
  * @brief Set TX output
+ *
+ * With tx_min_key_up_us set, a key down that follows a key up too
+ * closely is held back until the key-up time has passed (the element
+ * starts late rather than the rig missing the break). Call every tick:
+ * a held-back key down goes out on a later call.
+ *
  * @param on true to key TX, false to unkey
  */
 void hal_gpio_set_tx(bool on);
 
 /**
  * @brief Get TX state
- * @return true if TX is keyed
+ * @return true if TX is keyed (false while a key down is held back)
  */
 bool hal_gpio_get_tx(void);
 
+/**
+ * @brief Key TX for an exact time (RMT key output)
+ *
+ * The peripheral times the key down and the key up that ends it, so the
+ * element length does not depend on RT loop scheduling. A
+ * hal_gpio_set_tx(false) before the pulse ends waits for it. Needs
+ * CONFIG_KEYER_TX_RMT and radio 1; otherwise, while TX is keyed or
+ * within tx_min_key_up_us, returns false and the caller keys with
+ * hal_gpio_set_tx().
+ *
+ * @param duration_us Key-down time (up to about 1 s)
+ * @return true if the pulse was started
+ */
+bool hal_gpio_tx_pulse(uint32_t duration_us);
+
+/**
+ * @brief Unkey TX at once (fault path)
+ *
+ * Unlike hal_gpio_set_tx(false), also cuts a pulse in progress.
+ */
+void hal_gpio_tx_stop(void);
+
+/**
+ * @brief Release every key, PTT and amplifier output (panic path)
+ *
+ * IRAM, register writes only: safe with the other core stalled and the
+ * flash cache off. Takes the radio 1 key line back from the RMT channel.
+ * Leaves the HAL state as is: the chip restarts right after.
+ */
+void hal_gpio_panic_off(void);
+
+/**
+ * @brief Set PTT output of the selected radio
+ *
+ * Same polarity and drive as the TX output. No-op without a PTT pin.
+ * Fits ptt_set_pa_callback().
+ *
+ * @param on true to assert PTT
+ */
+void hal_gpio_set_ptt(bool on);
+
+/**
+ * @brief Set the antenna relay output
+ *
+ * Shared by both radios. Same polarity and drive as the TX output.
+ * No-op without a pin. Driven by the TX sequencer (sequencer.h).
+ */
+void hal_gpio_set_antenna(bool on);
+
+/**
+ * @brief Set the amplifier PTT output
+ *
+ * Shared by both radios. Same polarity and drive as the TX output.
+ * No-op without a pin. Driven by the TX sequencer (sequencer.h).
+ */
+void hal_gpio_set_amp(bool on);
+
+/** Radios (key + PTT output pairs) */
+#define HAL_GPIO_RADIO_MAX 2
+
+/**
+ * @brief Route TX and PTT to another radio
+ *
+ * Releases both outputs of the previous radio. Call from the RT task,
+ * with TX and PTT off, so no transmission moves between rigs.
+ *
+ * @param radio 0 or 1
+ * @return false if radio 1 has no TX pin (selection unchanged)
+ */
+bool hal_gpio_select_radio(uint8_t radio);
+
+/**
+ * @brief Radio the outputs are routed to
+ * @return 0 or 1 (safe from any task)
+ */
+uint8_t hal_gpio_get_radio(void);
+
 /**
  * @brief Get current GPIO configuration
  * @return Current configuration
@@ -69,18 +196,94 @@ bool hal_gpio_get_tx(void);
 hal_gpio_config_t hal_gpio_get_config(void);
 
 /**
- * @brief Check and consume pending DIT press from ISR
- * @return true if DIT was pressed (ISR triggered), false otherwise
- * @note RT-safe, lock-free (atomic exchange)
+ * @brief TX pin loopback test
+ *
+ * Drives the TX pin to both levels and reads its own pad back, detecting
+ * a keying line that is shorted or clamped keyed. Briefly asserts TX (a
+ * few µs): call at boot, before the RT task runs. Leaves TX released.
+ *
+ * @return true if the pad followed both levels
+ */
+bool hal_gpio_tx_loopback(void);
+
+/**
+ * @brief Key output checked through a loopback jumper
+ */
+typedef enum {
+    HAL_GPIO_LOOP_TX = 0,   /**< Radio 1 TX */
+    HAL_GPIO_LOOP_PTT,      /**< Radio 1 PTT */
+} hal_gpio_loop_output_t;
+
+/**
+ * @brief Loopback jumper check result
+ */
+typedef struct {
+    bool idle_ok;           /**< Sense input released before keying */
+    bool asserted;          /**< Followed key down within the timeout */
+    bool released;          /**< Followed key up within the timeout */
+    uint32_t assert_us;     /**< Key down propagation delay */
+    uint32_t release_us;    /**< Key up propagation delay */
+} hal_gpio_loop_result_t;
+
+/**
+ * @brief Key an output and time its edges on a sense input
+ *
+ * The sense input is wired by a test jumper to the keyed side of the
+ * output stage, so a dead transistor or optocoupler shows up as an
+ * edge that never arrives. Keys the output for up to timeout_us: call
+ * at boot, before the RT task runs, with no rig attached. Restores the
+ * output's last commanded state.
+ *
+ * @param output TX or PTT
+ * @param sense_pin Input GPIO wired to the output stage
+ * @param sense_active_low true if the keyed line reads low
+ * @param timeout_us Longest wait for each edge
+ * @param result Filled in
+ * @return true if both edges arrived in time from an idle line
  */
-bool hal_gpio_consume_dit_press(void);
+bool hal_gpio_output_loopback(hal_gpio_loop_output_t output, uint8_t sense_pin,
+                              bool sense_active_low, uint32_t timeout_us,
+                              hal_gpio_loop_result_t *result);
 
 /**
- * @brief Check and consume pending DAH press from ISR
- * @return true if DAH was pressed (ISR triggered), false otherwise
+ * @brief Configure message memory buttons (active low, internal pull-up)
+ *
+ * A button on an invalid GPIO, or on one of the keying pins given to
+ * hal_gpio_init(), is not started (reads as never pressed).
+ *
+ * @param pins GPIO per button, 0 = button not fitted
+ * @param count Number of entries (at most HAL_GPIO_BUTTON_MAX are used)
+ * @return 0 if every fitted button started, else the first GPIO refused
+ */
+uint8_t hal_gpio_buttons_init(const uint8_t *pins, size_t count);
+
+/**
+ * @brief Read message memory buttons
+ * @return Bit i set while button i is held (raw, not debounced)
+ */
+uint8_t hal_gpio_read_buttons(void);
+
+/**
+ * @brief Paddle presses latched by the edge ISR since the last take
+ */
+typedef struct {
+    gpio_state_t paddles;   /**< Paddles pressed (even if already released) */
+    uint32_t age_us;        /**< Time since the earliest of those presses */
+} hal_gpio_presses_t;
+
+/**
+ * @brief Take the presses latched by the edge ISR
+ *
+ * The ISR stores each press with its timestamp in a per-paddle atomic
+ * mailbox; taking empties it. A press and release between two RT ticks,
+ * or during a slipped tick, is still reported.
+ *
+ * @param now_us Current timestamp from esp_timer_get_time()
+ * @param out Presses and their age (written on success)
+ * @return true if any paddle was pressed
  * @note RT-safe, lock-free (atomic exchange)
  */
-bool hal_gpio_consume_dah_press(void);
+bool hal_gpio_take_presses(int64_t now_us, hal_gpio_presses_t *out);
 
 /**
  * @brief Check if ISR mode is enabled
@@ -100,6 +303,35 @@ bool hal_gpio_isr_enabled(void);
  */
 void hal_gpio_isr_tick(int64_t now_us);
 
+/**
+ * @brief Arm the inputs as light sleep wakeup sources (hal_power.h)
+ *
+ * Paddles (not touch pads), straight key, PTT input and memory buttons
+ * wake the chip at their active level. While armed the paddle ISR does
+ * not queue presses: the first closure calls the wake callback once
+ * (if one is set) and masks the paddle interrupts. The RMT key output
+ * is stopped while armed (it holds a PM lock). Call with TX off and the
+ * RT task parked; disarm before it runs again.
+ *
+ * @param enable true to arm before sleep, false after wakeup
+ */
+void hal_gpio_sleep_wakeup(bool enable);
+
+/**
+ * @brief Paddle closure while armed for sleep (GPIO ISR context, IRAM)
+ */
+typedef void (*hal_gpio_wake_fn)(void);
+
+/**
+ * @brief Set the function called on a paddle closure while armed
+ *
+ * Needs the paddle ISR (isr_blanking_us > 0). Without it, and for the
+ * other wakeup inputs, only polling sees the closure.
+ *
+ * @param fn ISR-safe function in IRAM, NULL for none
+ */
+void hal_gpio_set_wake_callback(hal_gpio_wake_fn fn);
+
 #ifdef __cplusplus
 }
 #endif
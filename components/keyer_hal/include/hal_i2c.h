@@ -0,0 +1,122 @@
+/**
+ * @file hal_i2c.h
+ * @brief Shared I2C bus (ES8311 codec, TCA9555, OLED, input expander)
+ *
+ * One bus owner for every I2C device on the board. Drivers register
+ * their device here and send through hal_i2c_write(), hal_i2c_read()
+ * and hal_i2c_write_read(): each call is one transaction, serialized with
+ * the others by the bus lock, capped at HAL_I2C_XFER_MAX bytes and
+ * HAL_I2C_TIMEOUT_MS, so no device can hold the bus for long.
+ *
+ * Core 1 tasks and app_main only: never from the RT task.
+ */
+
+#ifndef KEYER_HAL_I2C_H
+#define KEYER_HAL_I2C_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include "esp_err.h"
+
+#ifdef ESP_PLATFORM
+#include "driver/i2c_master.h"
+#endif
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Longest wait for a transaction, bus lock included */
+#define HAL_I2C_TIMEOUT_MS  50
+
+/** Largest transfer per transaction (~3.3 ms at 400 kHz) */
+#define HAL_I2C_XFER_MAX    132
+
+/** Device on the shared bus */
+typedef struct hal_i2c_dev *hal_i2c_dev_t;
+
+/**
+ * @brief Bring up the bus
+ *
+ * Later calls return ESP_OK and keep the first pins.
+ *
+ * @param sda_pin SDA GPIO
+ * @param scl_pin SCL GPIO
+ * @return ESP_OK or the driver error
+ */
+esp_err_t hal_i2c_init(int sda_pin, int scl_pin);
+
+/**
+ * @brief Check if the bus is up
+ */
+bool hal_i2c_is_ready(void);
+
+/**
+ * @brief Check for an ACK at an address
+ *
+ * @param addr 7-bit address
+ * @return ESP_OK if present, ESP_ERR_NOT_FOUND if no ACK,
+ *         ESP_ERR_INVALID_STATE if the bus is not up
+ */
+esp_err_t hal_i2c_probe(uint8_t addr);
+
+/**
+ * @brief Register a device
+ *
+ * @param addr 7-bit address
+ * @param scl_hz Clock for this device
+ * @param dev Device handle
+ * @return ESP_OK, ESP_ERR_INVALID_STATE if the bus is not up
+ */
+esp_err_t hal_i2c_add_device(uint8_t addr, uint32_t scl_hz, hal_i2c_dev_t *dev);
+
+/**
+ * @brief Unregister a device (NULL is ignored)
+ */
+void hal_i2c_remove_device(hal_i2c_dev_t dev);
+
+/**
+ * @brief Write to a device
+ *
+ * @return ESP_OK, ESP_ERR_INVALID_SIZE over HAL_I2C_XFER_MAX,
+ *         ESP_ERR_TIMEOUT or the driver error
+ */
+esp_err_t hal_i2c_write(hal_i2c_dev_t dev, const uint8_t *data, size_t len);
+
+/**
+ * @brief Read from a device with no register address (PCF8574)
+ *
+ * Same limits as hal_i2c_write().
+ */
+esp_err_t hal_i2c_read(hal_i2c_dev_t dev, uint8_t *data, size_t len);
+
+/**
+ * @brief Write then read with a repeated start (register reads)
+ *
+ * Same limits as hal_i2c_write(), for each direction.
+ */
+esp_err_t hal_i2c_write_read(hal_i2c_dev_t dev, const uint8_t *out, size_t out_len,
+                             uint8_t *in, size_t in_len);
+
+/**
+ * @brief Failed transactions since boot (NACK, timeout)
+ */
+uint32_t hal_i2c_error_count(void);
+
+#ifdef ESP_PLATFORM
+/**
+ * @brief Bus handle for libraries that take one (codec, IO expander)
+ *
+ * Their transactions go through the same bus lock.
+ *
+ * @return Handle, NULL if the bus is not up
+ */
+i2c_master_bus_handle_t hal_i2c_bus(void);
+#endif
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_I2C_H */
@@ -0,0 +1,38 @@
+/**
+ * @file hal_pot.h
+ * @brief Speed potentiometer (ADC one-shot)
+ */
+
+#ifndef KEYER_HAL_POT_H
+#define KEYER_HAL_POT_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Set up the ADC channel behind a GPIO
+ *
+ * @param pin Pot wiper GPIO (ADC1-capable)
+ * @return true if ready, false if pin is 0 (not fitted), not an ADC1
+ *         pin, or the ADC could not be set up
+ */
+bool hal_pot_init(uint8_t pin);
+
+/**
+ * @brief Read the pot
+ *
+ * Takes a few microseconds. Call from bg_task.
+ *
+ * @return Raw reading 0..4095, -1 if not fitted or the read failed
+ */
+int hal_pot_read(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_POT_H */
@@ -0,0 +1,54 @@
+/**
+ * @file hal_power.h
+ * @brief Light sleep (ESP-IDF power management)
+ *
+ * The chip stays awake while a "keyer awake" PM lock is held, which is
+ * always the case after hal_power_init(). Releasing it with
+ * hal_power_allow_sleep(true) lets FreeRTOS tickless idle enter light
+ * sleep whenever every task is blocked. GPIO (armed by
+ * hal_gpio_sleep_wakeup()) and WiFi wake it up; WiFi stays associated
+ * through modem sleep.
+ *
+ * Any driver holding its own PM lock (I2S, RMT, PCNT) keeps the chip
+ * awake: their owners stop them before allowing sleep.
+ *
+ * Needs CONFIG_PM_ENABLE and CONFIG_FREERTOS_USE_TICKLESS_IDLE.
+ */
+
+#ifndef KEYER_HAL_POWER_H
+#define KEYER_HAL_POWER_H
+
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Configure power management and take the awake lock
+ *
+ * CPU frequency stays fixed: only light sleep is enabled.
+ *
+ * @return true if light sleep can be used
+ */
+bool hal_power_init(void);
+
+/**
+ * @brief Check if light sleep can be used
+ */
+bool hal_power_is_available(void);
+
+/**
+ * @brief Release or take back the awake lock
+ *
+ * No-op without hal_power_init() success. Core 1 only.
+ *
+ * @param allow true to let the chip light sleep when idle
+ */
+void hal_power_allow_sleep(bool allow);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_POWER_H */
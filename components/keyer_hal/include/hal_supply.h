@@ -0,0 +1,39 @@
+/**
+ * @file hal_supply.h
+ * @brief Supply voltage input (ADC one-shot through a resistor divider)
+ */
+
+#ifndef KEYER_HAL_SUPPLY_H
+#define KEYER_HAL_SUPPLY_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Set up the ADC channel behind a GPIO
+ *
+ * @param pin Divider tap GPIO (ADC1-capable)
+ * @return true if ready, false if pin is 0 (not fitted), not an ADC1
+ *         pin, or the ADC could not be set up
+ */
+bool hal_supply_init(uint8_t pin);
+
+/**
+ * @brief Read the divider tap
+ *
+ * Takes a few microseconds. Call from bg_task.
+ *
+ * @return Millivolts at the pin (before the divider ratio), -1 if not
+ *         fitted or the read failed
+ */
+int hal_supply_read_mv(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_SUPPLY_H */
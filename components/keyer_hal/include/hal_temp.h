@@ -0,0 +1,37 @@
+/**
+ * @file hal_temp.h
+ * @brief Chip temperature sensor
+ */
+
+#ifndef KEYER_HAL_TEMP_H
+#define KEYER_HAL_TEMP_H
+
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Install and enable the internal temperature sensor
+ *
+ * @return true if ready
+ */
+bool hal_temp_init(void);
+
+/**
+ * @brief Read the die temperature
+ *
+ * Takes a few hundred microseconds. Call from bg_task. The die runs
+ * warmer than the enclosure, more so with WiFi busy.
+ *
+ * @param celsius Temperature in °C
+ * @return true on success, false if not set up or the read failed
+ */
+bool hal_temp_read(float *celsius);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_TEMP_H */
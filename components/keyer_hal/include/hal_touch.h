@@ -0,0 +1,48 @@
+/**
+ * @file hal_touch.h
+ * @brief Capacitive touch paddles (touch sensor peripheral)
+ *
+ * Alternative paddle source for builds without a mechanical paddle:
+ * the DIT and DAH pins are read as touch pads and mapped into a
+ * gpio_state_t (see touch_paddle.h for the detection).
+ */
+
+#ifndef KEYER_HAL_TOUCH_H
+#define KEYER_HAL_TOUCH_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include "sample.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Set up both pads and calibrate them
+ *
+ * Measures the untouched baseline, so keep fingers off the pads at boot.
+ * Blocks for about 100 ms. Call once from app_main.
+ *
+ * @param dit_pin DIT pad GPIO (a touch channel: GPIO1..14 on the S3)
+ * @param dah_pin DAH pad GPIO
+ * @param sensitivity_pct Rise over the baseline that is a touch (percent)
+ * @return true if ready, false if a pin is not a touch channel or the
+ *         touch sensor could not be set up
+ */
+bool hal_touch_init(uint8_t dit_pin, uint8_t dah_pin, uint8_t sensitivity_pct);
+
+/**
+ * @brief Read both pads
+ *
+ * Reads the latest measurement, no waiting. RT-safe.
+ *
+ * @return Paddle state (idle if not set up)
+ */
+gpio_state_t hal_touch_read_paddles(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_HAL_TOUCH_H */
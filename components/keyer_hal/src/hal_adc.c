@@ -0,0 +1,146 @@
+/**
+ * @file hal_adc.c
+ * @brief Shared ADC1 one-shot implementation
+ */
+
+#include "hal_adc.h"
+
+/** Nominal full scale at 12 dB attenuation, used without calibration */
+#define NOMINAL_FULL_SCALE_MV 3100
+
+/** Full-scale raw reading at 12 bits */
+#define RAW_MAX 4095
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "esp_adc/adc_oneshot.h"
+#include "esp_adc/adc_cali.h"
+#include "esp_adc/adc_cali_scheme.h"
+#include "esp_log.h"
+
+static const char *TAG = "hal_adc";
+
+static adc_oneshot_unit_handle_t s_adc = NULL;
+
+/**
+ * @brief Open ADC1 on first use
+ */
+static bool open_unit(void) {
+    if (s_adc != NULL) {
+        return true;
+    }
+    adc_oneshot_unit_init_cfg_t unit_conf = {
+        .unit_id = ADC_UNIT_1,
+    };
+    esp_err_t err = adc_oneshot_new_unit(&unit_conf, &s_adc);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to create ADC unit: %s", esp_err_to_name(err));
+        s_adc = NULL;
+        return false;
+    }
+    return true;
+}
+
+static void *create_cali(adc_channel_t channel) {
+    adc_cali_handle_t cali = NULL;
+    esp_err_t err = ESP_ERR_NOT_SUPPORTED;
+#if ADC_CALI_SCHEME_CURVE_FITTING_SUPPORTED
+    adc_cali_curve_fitting_config_t conf = {
+        .unit_id = ADC_UNIT_1,
+        .chan = channel,
+        .atten = ADC_ATTEN_DB_12,
+        .bitwidth = ADC_BITWIDTH_12,
+    };
+    err = adc_cali_create_scheme_curve_fitting(&conf, &cali);
+#elif ADC_CALI_SCHEME_LINE_FITTING_SUPPORTED
+    (void)channel;
+    adc_cali_line_fitting_config_t conf = {
+        .unit_id = ADC_UNIT_1,
+        .atten = ADC_ATTEN_DB_12,
+        .bitwidth = ADC_BITWIDTH_12,
+    };
+    err = adc_cali_create_scheme_line_fitting(&conf, &cali);
+#else
+    (void)channel;
+#endif
+    return (err == ESP_OK) ? cali : NULL;
+}
+
+bool hal_adc_input_init(hal_adc_input_t *in, uint8_t pin, const char *name) {
+    in->channel = -1;
+    in->cali = NULL;
+    if (pin == 0) {
+        return false;
+    }
+
+    adc_unit_t unit;
+    adc_channel_t channel;
+    if (adc_oneshot_io_to_channel(pin, &unit, &channel) != ESP_OK || unit != ADC_UNIT_1) {
+        ESP_LOGE(TAG, "%s: GPIO%d is not an ADC1 pin", name, pin);
+        return false;
+    }
+    if (!open_unit()) {
+        return false;
+    }
+
+    adc_oneshot_chan_cfg_t chan_conf = {
+        .atten = ADC_ATTEN_DB_12,
+        .bitwidth = ADC_BITWIDTH_12,
+    };
+    esp_err_t err = adc_oneshot_config_channel(s_adc, channel, &chan_conf);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "%s: failed to configure ADC channel: %s", name, esp_err_to_name(err));
+        return false;
+    }
+
+    in->channel = (int)channel;
+    in->cali = create_cali(channel);
+    ESP_LOGI(TAG, "%s on GPIO%d (%s)", name, pin,
+             (in->cali != NULL) ? "calibrated" : "uncalibrated");
+    return true;
+}
+
+int hal_adc_read_raw(const hal_adc_input_t *in) {
+    int raw = 0;
+    if (in->channel < 0 ||
+        adc_oneshot_read(s_adc, (adc_channel_t)in->channel, &raw) != ESP_OK) {
+        return -1;
+    }
+    return raw;
+}
+
+int hal_adc_read_mv(const hal_adc_input_t *in) {
+    int raw = hal_adc_read_raw(in);
+    if (raw < 0) {
+        return -1;
+    }
+    int mv = 0;
+    if (in->cali != NULL &&
+        adc_cali_raw_to_voltage((adc_cali_handle_t)in->cali, raw, &mv) == ESP_OK) {
+        return mv;
+    }
+    return (raw * NOMINAL_FULL_SCALE_MV) / RAW_MAX;
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+bool hal_adc_input_init(hal_adc_input_t *in, uint8_t pin, const char *name) {
+    (void)name;
+    in->channel = (pin != 0) ? (int)pin : -1;
+    in->cali = NULL;
+    return pin != 0;
+}
+
+int hal_adc_read_raw(const hal_adc_input_t *in) {
+    return (in->channel < 0) ? -1 : 0;
+}
+
+int hal_adc_read_mv(const hal_adc_input_t *in) {
+    int raw = hal_adc_read_raw(in);
+    return (raw < 0) ? -1 : (raw * NOMINAL_FULL_SCALE_MV) / RAW_MAX;
+}
+
+#endif /* ESP_PLATFORM */
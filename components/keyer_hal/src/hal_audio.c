@@ -4,11 +4,12 @@
  */
 
 #include "hal_audio.h"
+#include "hal_i2c.h"
 
 #ifdef ESP_PLATFORM
 
-#include "driver/i2c_master.h"
 #include "driver/i2s_std.h"
+#include "driver/gpio.h"
 #include "esp_log.h"
 #include "esp_io_expander.h"
 #include "esp_io_expander_tca95xx_16bit.h"
@@ -19,10 +20,10 @@ static const char *TAG = "hal_audio";
 
 /* State */
 static hal_audio_config_t s_config;
-static i2c_master_bus_handle_t s_i2c_bus = NULL;
 static esp_io_expander_handle_t s_io_expander = NULL;
 static bool s_pa_enabled = false;
 static i2s_chan_handle_t s_i2s_tx = NULL;
+static i2s_chan_handle_t s_i2s_rx = NULL;   /* Codec ADC, NULL if DIN not wired */
 static esp_codec_dev_handle_t s_codec_dev = NULL;
 static const audio_codec_ctrl_if_t *s_ctrl_if = NULL;
 static const audio_codec_data_if_t *s_data_if = NULL;
@@ -30,33 +31,13 @@ static const audio_codec_gpio_if_t *s_gpio_if = NULL;
 static const audio_codec_if_t *s_codec_if = NULL;
 static bool s_audio_available = false;
 
-/**
- * @brief Initialize I2C bus
- */
-static esp_err_t init_i2c(void) {
-    i2c_master_bus_config_t bus_cfg = {
-        .i2c_port = I2C_NUM_0,
-        .sda_io_num = s_config.i2c_sda_pin,
-        .scl_io_num = s_config.i2c_scl_pin,
-        .clk_source = I2C_CLK_SRC_DEFAULT,
-        .glitch_ignore_cnt = 7,
-        .intr_priority = 0,
-        .trans_queue_depth = 0,
-        .flags = {
-            .enable_internal_pullup = true,
-        },
-    };
-
-    esp_err_t ret = i2c_new_master_bus(&bus_cfg, &s_i2c_bus);
-    if (ret != ESP_OK) {
-        ESP_LOGE(TAG, "I2C bus init failed: %s", esp_err_to_name(ret));
-        return ret;
-    }
-
-    ESP_LOGI(TAG, "I2C bus initialized (SDA=%d, SCL=%d)",
-             s_config.i2c_sda_pin, s_config.i2c_scl_pin);
-    return ESP_OK;
-}
+/* Codec input and routing: registers written directly (esp_codec_dev has no PGA/LINSEL control) */
+static hal_i2c_dev_t s_codec_i2c = NULL;
+static uint8_t s_reg13 = 0;         /* Last value written to ES8311 REG13 */
+static uint8_t s_reg14 = 0;         /* Last value written to ES8311 REG14 */
+static hal_audio_output_t s_output = HAL_AUDIO_OUTPUT_SPEAKER;
+static bool s_speaker_muted = false; /* PA held off for headphones */
+static int s_mic_bias = -1;         /* Bias pin state, -1 = not set yet */
 
 /**
  * @brief Initialize TCA9555 IO expander for PA control
@@ -68,7 +49,7 @@ static esp_err_t init_io_expander(void) {
     }
 
     esp_err_t ret = esp_io_expander_new_i2c_tca95xx_16bit(
-        s_i2c_bus,
+        hal_i2c_bus(),
         ESP_IO_EXPANDER_I2C_TCA9555_ADDRESS_000,  /* 0x20 */
         &s_io_expander
     );
@@ -96,13 +77,26 @@ static esp_err_t init_io_expander(void) {
 }
 
 /**
- * @brief Initialize I2S for audio output
+ * @brief Delete the I2S channels after a failed init
+ */
+static void del_i2s(void) {
+    i2s_del_channel(s_i2s_tx);
+    s_i2s_tx = NULL;
+    if (s_i2s_rx != NULL) {
+        i2s_del_channel(s_i2s_rx);
+        s_i2s_rx = NULL;
+    }
+}
+
+/**
+ * @brief Initialize I2S: output to the DAC, input from the ADC if DIN is wired
  */
 static esp_err_t init_i2s(void) {
     i2s_chan_config_t chan_cfg = I2S_CHANNEL_DEFAULT_CONFIG(I2S_NUM_0, I2S_ROLE_MASTER);
     chan_cfg.auto_clear = true;
 
-    esp_err_t ret = i2s_new_channel(&chan_cfg, &s_i2s_tx, NULL);
+    bool duplex = (s_config.i2s_din_pin >= 0);
+    esp_err_t ret = i2s_new_channel(&chan_cfg, &s_i2s_tx, duplex ? &s_i2s_rx : NULL);
     if (ret != ESP_OK) {
         ESP_LOGE(TAG, "I2S channel create failed: %s", esp_err_to_name(ret));
         return ret;
@@ -116,7 +110,7 @@ static esp_err_t init_i2s(void) {
             .bclk = s_config.i2s_bclk_pin,
             .ws = s_config.i2s_lrck_pin,
             .dout = s_config.i2s_dout_pin,
-            .din = GPIO_NUM_NC,
+            .din = duplex ? s_config.i2s_din_pin : GPIO_NUM_NC,
             .invert_flags = {
                 .mclk_inv = false,
                 .bclk_inv = false,
@@ -127,30 +121,46 @@ static esp_err_t init_i2s(void) {
     std_cfg.clk_cfg.mclk_multiple = I2S_MCLK_MULTIPLE_256;
 
     ret = i2s_channel_init_std_mode(s_i2s_tx, &std_cfg);
+    if (ret == ESP_OK && duplex) {
+        ret = i2s_channel_init_std_mode(s_i2s_rx, &std_cfg);
+    }
     if (ret != ESP_OK) {
         ESP_LOGE(TAG, "I2S std mode init failed: %s", esp_err_to_name(ret));
-        i2s_del_channel(s_i2s_tx);
-        s_i2s_tx = NULL;
+        del_i2s();
         return ret;
     }
 
     ret = i2s_channel_enable(s_i2s_tx);
+    if (ret == ESP_OK && duplex) {
+        ret = i2s_channel_enable(s_i2s_rx);
+    }
     if (ret != ESP_OK) {
         ESP_LOGE(TAG, "I2S enable failed: %s", esp_err_to_name(ret));
-        i2s_del_channel(s_i2s_tx);
-        s_i2s_tx = NULL;
+        i2s_channel_disable(s_i2s_tx);
+        del_i2s();
         return ret;
     }
 
-    ESP_LOGI(TAG, "I2S initialized (MCLK=%d, BCLK=%d, LRCK=%d, DOUT=%d, rate=%lu)",
+    ESP_LOGI(TAG, "I2S initialized (MCLK=%d, BCLK=%d, LRCK=%d, DOUT=%d, DIN=%d, rate=%lu)",
              s_config.i2s_mclk_pin, s_config.i2s_bclk_pin,
-             s_config.i2s_lrck_pin, s_config.i2s_dout_pin,
+             s_config.i2s_lrck_pin, s_config.i2s_dout_pin, s_config.i2s_din_pin,
              (unsigned long)s_config.sample_rate);
     return ESP_OK;
 }
 
 #define ES8311_I2C_ADDR 0x18
 
+/* ES8311 REG13: output stage */
+#define ES8311_REG_SYSTEM_13    0x13
+#define ES8311_HPSW             0x10    /* Headphone drive instead of line out */
+
+/* ES8311 REG14: input selection and PGA gain */
+#define ES8311_REG_SYSTEM_14    0x14
+#define ES8311_DMIC_ON          0x40
+#define ES8311_LINSEL           0x10    /* MIC1P/MIC1N to the PGA */
+#define ES8311_PGA_MASK         0x0F    /* 0-10, 3 dB steps */
+#define ES8311_PGA_STEP_DB      3
+
 /**
  * @brief Initialize ES8311 codec via esp_codec_dev
  */
@@ -159,7 +169,7 @@ static esp_err_t init_codec(void) {
     audio_codec_i2c_cfg_t i2c_cfg = {
         .port = I2C_NUM_0,
         .addr = ES8311_I2C_ADDR << 1,  /* 7-bit addr shifted to 8-bit format */
-        .bus_handle = s_i2c_bus,
+        .bus_handle = hal_i2c_bus(),
     };
     s_ctrl_if = audio_codec_new_i2c_ctrl(&i2c_cfg);
     if (s_ctrl_if == NULL) {
@@ -170,7 +180,7 @@ static esp_err_t init_codec(void) {
     /* Create I2S data interface */
     audio_codec_i2s_cfg_t i2s_cfg = {
         .port = I2S_NUM_0,
-        .rx_handle = NULL,
+        .rx_handle = s_i2s_rx,
         .tx_handle = s_i2s_tx,
     };
     s_data_if = audio_codec_new_i2s_data(&i2s_cfg);
@@ -190,7 +200,8 @@ static esp_err_t init_codec(void) {
     es8311_codec_cfg_t es_cfg = {
         .ctrl_if = s_ctrl_if,
         .gpio_if = s_gpio_if,
-        .codec_mode = ESP_CODEC_DEV_WORK_MODE_DAC,
+        /* ADC powered only with an input to read it from */
+        .codec_mode = (s_i2s_rx != NULL) ? ESP_CODEC_DEV_WORK_MODE_BOTH : ESP_CODEC_DEV_WORK_MODE_DAC,
         .pa_pin = -1,  /* PA managed separately via TCA9555 */
         .pa_reverted = false,
         .master_mode = false,
@@ -214,7 +225,7 @@ static esp_err_t init_codec(void) {
 
     /* Create codec device */
     esp_codec_dev_cfg_t dev_cfg = {
-        .dev_type = ESP_CODEC_DEV_TYPE_OUT,
+        .dev_type = (s_i2s_rx != NULL) ? ESP_CODEC_DEV_TYPE_IN_OUT : ESP_CODEC_DEV_TYPE_OUT,
         .codec_if = s_codec_if,
         .data_if = s_data_if,
     };
@@ -245,6 +256,80 @@ static esp_err_t init_codec(void) {
     return ESP_OK;
 }
 
+/**
+ * @brief Set up the codec input registers and the mic bias pin
+ *
+ * Not fatal: output works without them.
+ */
+static void init_input(void) {
+    if (s_config.mic_bias_pin >= 0) {
+        gpio_config_t io_conf = {
+            .pin_bit_mask = 1ULL << s_config.mic_bias_pin,
+            .mode = GPIO_MODE_OUTPUT,
+            .pull_up_en = GPIO_PULLUP_DISABLE,
+            .pull_down_en = GPIO_PULLDOWN_DISABLE,
+            .intr_type = GPIO_INTR_DISABLE,
+        };
+        gpio_config(&io_conf);
+        gpio_set_level((gpio_num_t)s_config.mic_bias_pin, 0);
+        s_mic_bias = 0;
+    }
+    if (s_config.hp_detect_pin >= 0) {
+        gpio_config_t io_conf = {
+            .pin_bit_mask = 1ULL << s_config.hp_detect_pin,
+            .mode = GPIO_MODE_INPUT,
+            .pull_up_en = GPIO_PULLUP_ENABLE,
+            .pull_down_en = GPIO_PULLDOWN_DISABLE,
+            .intr_type = GPIO_INTR_DISABLE,
+        };
+        gpio_config(&io_conf);
+    }
+
+    /* Start from what the codec driver wrote on open */
+    const uint8_t reg13 = ES8311_REG_SYSTEM_13;
+    const uint8_t reg14 = ES8311_REG_SYSTEM_14;
+    esp_err_t err = hal_i2c_add_device(ES8311_I2C_ADDR, s_config.i2c_freq_hz, &s_codec_i2c);
+    if (err == ESP_OK) {
+        err = hal_i2c_write_read(s_codec_i2c, &reg13, 1, &s_reg13, 1);
+    }
+    if (err == ESP_OK) {
+        err = hal_i2c_write_read(s_codec_i2c, &reg14, 1, &s_reg14, 1);
+    }
+    if (err != ESP_OK) {
+        ESP_LOGW(TAG, "Codec input control unavailable: %s", esp_err_to_name(err));
+        hal_i2c_remove_device(s_codec_i2c);
+        s_codec_i2c = NULL;
+    }
+}
+
+/**
+ * @brief Update cached register bits (no I2C if unchanged)
+ */
+static esp_err_t update_reg(uint8_t reg, uint8_t *cache, uint8_t mask, uint8_t bits) {
+    if (s_codec_i2c == NULL) {
+        return ESP_ERR_INVALID_STATE;
+    }
+    uint8_t value = (uint8_t)((*cache & ~mask) | (bits & mask));
+    if (value == *cache) {
+        return ESP_OK;
+    }
+    const uint8_t buf[2] = { reg, value };
+    esp_err_t err = hal_i2c_write(s_codec_i2c, buf, sizeof(buf));
+    if (err == ESP_OK) {
+        *cache = value;
+    }
+    return err;
+}
+
+/**
+ * @brief Drive the speaker amp enable pin on the IO expander
+ */
+static esp_err_t write_pa_pin(bool on) {
+    uint32_t pa_mask = (1u << (uint32_t)s_config.pa_pin);
+    uint8_t level = (on == s_config.pa_active_high) ? 1 : 0;
+    return esp_io_expander_set_level(s_io_expander, pa_mask, level);
+}
+
 esp_err_t hal_audio_init(const hal_audio_config_t *config) {
     if (config == NULL) {
         return ESP_ERR_INVALID_ARG;
@@ -253,8 +338,8 @@ esp_err_t hal_audio_init(const hal_audio_config_t *config) {
     s_config = *config;
     s_audio_available = false;
 
-    /* Step 1: Initialize I2C bus */
-    esp_err_t ret = init_i2c();
+    /* Step 1: Shared I2C bus (codec, IO expander, OLED) */
+    esp_err_t ret = hal_i2c_init(s_config.i2c_sda_pin, s_config.i2c_scl_pin);
     if (ret != ESP_OK) {
         ESP_LOGE(TAG, "Audio init failed at I2C, continuing without audio");
         return ESP_OK;  /* Don't block boot */
@@ -281,6 +366,8 @@ esp_err_t hal_audio_init(const hal_audio_config_t *config) {
         return ESP_OK;  /* Don't block boot */
     }
 
+    init_input();
+
     s_audio_available = true;
     ESP_LOGI(TAG, "Audio HAL initialized (sample_rate=%lu)",
              (unsigned long)s_config.sample_rate);
@@ -357,10 +444,8 @@ esp_err_t hal_audio_set_pa(bool enable) {
 
     /* Enable PA first, THEN unmute codec (reverse order when disabling) */
     if (enable) {
-        /* Step 1: Enable PA */
-        uint32_t pa_mask = (1u << (uint32_t)s_config.pa_pin);
-        uint8_t level = s_config.pa_active_high ? 1 : 0;
-        esp_err_t ret = esp_io_expander_set_level(s_io_expander, pa_mask, level);
+        /* Step 1: Enable PA (stays off while headphones mute it) */
+        esp_err_t ret = write_pa_pin(!s_speaker_muted);
         if (ret != ESP_OK) {
             ESP_LOGE(TAG, "Failed to enable PA: %s", esp_err_to_name(ret));
             return ret;
@@ -378,9 +463,7 @@ esp_err_t hal_audio_set_pa(bool enable) {
         }
 
         /* Step 2: Disable PA */
-        uint32_t pa_mask = (1u << (uint32_t)s_config.pa_pin);
-        uint8_t level = s_config.pa_active_high ? 0 : 1;
-        esp_err_t ret = esp_io_expander_set_level(s_io_expander, pa_mask, level);
+        esp_err_t ret = write_pa_pin(false);
         if (ret != ESP_OK) {
             ESP_LOGE(TAG, "Failed to disable PA: %s", esp_err_to_name(ret));
             return ret;
@@ -408,10 +491,118 @@ bool hal_audio_is_available(void) {
     return s_audio_available;
 }
 
+esp_err_t hal_audio_probe(void) {
+    return hal_i2c_probe(ES8311_I2C_ADDR);
+}
+
+esp_err_t hal_audio_codec_read(uint8_t reg, uint8_t *value) {
+    if (s_codec_i2c == NULL) {
+        return ESP_ERR_INVALID_STATE;
+    }
+    return hal_i2c_write_read(s_codec_i2c, &reg, 1, value, 1);
+}
+
+esp_err_t hal_audio_codec_write(uint8_t reg, uint8_t value) {
+    if (s_codec_i2c == NULL) {
+        return ESP_ERR_INVALID_STATE;
+    }
+    const uint8_t buf[2] = { reg, value };
+    esp_err_t err = hal_i2c_write(s_codec_i2c, buf, sizeof(buf));
+    if (err == ESP_OK && reg == ES8311_REG_SYSTEM_13) {
+        s_reg13 = value;  /* Keep the routing cache in step */
+    }
+    if (err == ESP_OK && reg == ES8311_REG_SYSTEM_14) {
+        s_reg14 = value;  /* Keep the input settings cache in step */
+    }
+    return err;
+}
+
+esp_err_t hal_audio_set_input(hal_audio_input_t input) {
+    if (input != HAL_AUDIO_INPUT_NONE && s_i2s_rx == NULL) {
+        return ESP_ERR_NOT_SUPPORTED;  /* ADC off: DIN not wired */
+    }
+    uint8_t bits = 0;
+    switch (input) {
+        case HAL_AUDIO_INPUT_ANALOG:
+            bits = ES8311_LINSEL;
+            break;
+        case HAL_AUDIO_INPUT_DIGITAL:
+            bits = ES8311_DMIC_ON;
+            break;
+        default:
+            break;
+    }
+    return update_reg(ES8311_REG_SYSTEM_14, &s_reg14, ES8311_DMIC_ON | ES8311_LINSEL, bits);
+}
+
+esp_err_t hal_audio_set_input_gain(uint8_t gain_db) {
+    if (gain_db > HAL_AUDIO_INPUT_GAIN_MAX_DB) {
+        gain_db = HAL_AUDIO_INPUT_GAIN_MAX_DB;
+    }
+    return update_reg(ES8311_REG_SYSTEM_14, &s_reg14, ES8311_PGA_MASK,
+                      (uint8_t)(gain_db / ES8311_PGA_STEP_DB));
+}
+
+esp_err_t hal_audio_set_mic_bias(bool on) {
+    if (s_config.mic_bias_pin < 0) {
+        return on ? ESP_ERR_NOT_SUPPORTED : ESP_OK;
+    }
+    if (s_mic_bias != (int)on) {
+        gpio_set_level((gpio_num_t)s_config.mic_bias_pin, on ? 1U : 0U);
+        s_mic_bias = on ? 1 : 0;
+    }
+    return ESP_OK;
+}
+
+bool hal_audio_read_hp_detect(void) {
+    if (s_config.hp_detect_pin < 0) {
+        return false;
+    }
+    bool high = gpio_get_level((gpio_num_t)s_config.hp_detect_pin) != 0;
+    return high == s_config.hp_detect_high;
+}
+
+bool hal_audio_hp_detect_fitted(void) {
+    return s_config.hp_detect_pin >= 0;
+}
+
+esp_err_t hal_audio_set_output(hal_audio_output_t output, bool mute_speaker) {
+    bool headphones = (output == HAL_AUDIO_OUTPUT_HEADPHONE);
+    esp_err_t err = update_reg(ES8311_REG_SYSTEM_13, &s_reg13, ES8311_HPSW,
+                               headphones ? ES8311_HPSW : 0);
+    if (err != ESP_OK) {
+        return err;
+    }
+    if (output != s_output) {
+        ESP_LOGI(TAG, "Output: %s", headphones ? "headphones" : "speaker");
+        s_output = output;
+    }
+
+    bool muted = headphones && mute_speaker;
+    if (muted != s_speaker_muted) {
+        if (s_pa_enabled && s_io_expander != NULL) {
+            err = write_pa_pin(!muted);
+        }
+        if (err == ESP_OK) {
+            s_speaker_muted = muted;
+        }
+    }
+    return err;
+}
+
+hal_audio_output_t hal_audio_get_output(void) {
+    return s_output;
+}
+
+bool hal_audio_speaker_on(void) {
+    return s_pa_enabled && !s_speaker_muted;
+}
+
 #else
 /* Host stub */
 
 static bool s_available = false;
+static uint8_t s_codec_regs[256];
 
 esp_err_t hal_audio_init(const hal_audio_config_t *config) {
     (void)config;
@@ -437,5 +628,67 @@ esp_err_t hal_audio_set_pa(bool enable) {
 void hal_audio_start(void) {}
 void hal_audio_stop(void) {}
 bool hal_audio_is_available(void) { return s_available; }
+esp_err_t hal_audio_probe(void) { return s_available ? ESP_OK : ESP_ERR_INVALID_STATE; }
+
+esp_err_t hal_audio_codec_read(uint8_t reg, uint8_t *value) {
+    *value = s_codec_regs[reg];
+    return s_available ? ESP_OK : ESP_ERR_INVALID_STATE;
+}
+
+esp_err_t hal_audio_codec_write(uint8_t reg, uint8_t value) {
+    if (!s_available) {
+        return ESP_ERR_INVALID_STATE;
+    }
+    s_codec_regs[reg] = value;
+    return ESP_OK;
+}
+
+esp_err_t hal_audio_set_input(hal_audio_input_t input) {
+    (void)input;
+    return s_available ? ESP_OK : ESP_ERR_INVALID_STATE;
+}
+
+esp_err_t hal_audio_set_input_gain(uint8_t gain_db) {
+    (void)gain_db;
+    return s_available ? ESP_OK : ESP_ERR_INVALID_STATE;
+}
+
+esp_err_t hal_audio_set_mic_bias(bool on) {
+    return on ? ESP_ERR_NOT_SUPPORTED : ESP_OK;
+}
+
+static bool s_hp_plugged = false;
+static hal_audio_output_t s_output = HAL_AUDIO_OUTPUT_SPEAKER;
+static bool s_speaker_muted = false;
+
+bool hal_audio_read_hp_detect(void) {
+    return s_hp_plugged;
+}
+
+bool hal_audio_hp_detect_fitted(void) {
+    return true;
+}
+
+esp_err_t hal_audio_set_output(hal_audio_output_t output, bool mute_speaker) {
+    if (!s_available) {
+        return ESP_ERR_INVALID_STATE;
+    }
+    s_output = output;
+    s_speaker_muted = (output == HAL_AUDIO_OUTPUT_HEADPHONE) && mute_speaker;
+    return ESP_OK;
+}
+
+hal_audio_output_t hal_audio_get_output(void) {
+    return s_output;
+}
+
+bool hal_audio_speaker_on(void) {
+    return !s_speaker_muted;
+}
+
+/* Test helper */
+void hal_audio_test_set_hp_detect(bool plugged) {
+    s_hp_plugged = plugged;
+}
 
 #endif /* ESP_PLATFORM */
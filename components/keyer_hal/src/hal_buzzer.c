@@ -0,0 +1,112 @@
+/**
+ * @file hal_buzzer.c
+ * @brief Piezo buzzer HAL implementation
+ */
+
+#include "hal_buzzer.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "driver/ledc.h"
+#include "esp_log.h"
+
+static const char *TAG = "hal_buzzer";
+
+#define BUZZER_MODE     LEDC_LOW_SPEED_MODE
+#define BUZZER_TIMER    LEDC_TIMER_0
+#define BUZZER_CHANNEL  LEDC_CHANNEL_0
+
+/** 10 bits: fine for audio tones on an 80 MHz source */
+#define BUZZER_DUTY_RES LEDC_TIMER_10_BIT
+
+/** Square wave: loudest for a piezo */
+#define BUZZER_DUTY_ON  (1U << 9)
+
+static bool s_fitted = false;
+static bool s_on = false;
+
+bool hal_buzzer_init(uint8_t pin, uint32_t freq_hz) {
+    if (pin == 0) {
+        return false;
+    }
+
+    ledc_timer_config_t timer = {
+        .speed_mode = BUZZER_MODE,
+        .duty_resolution = BUZZER_DUTY_RES,
+        .timer_num = BUZZER_TIMER,
+        .freq_hz = freq_hz,
+        .clk_cfg = LEDC_AUTO_CLK,
+    };
+    ledc_channel_config_t channel = {
+        .gpio_num = pin,
+        .speed_mode = BUZZER_MODE,
+        .channel = BUZZER_CHANNEL,
+        .intr_type = LEDC_INTR_DISABLE,
+        .timer_sel = BUZZER_TIMER,
+        .duty = 0,
+        .hpoint = 0,
+    };
+    esp_err_t err = ledc_timer_config(&timer);
+    if (err == ESP_OK) {
+        err = ledc_channel_config(&channel);
+    }
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to set up buzzer on GPIO%d: %s", pin, esp_err_to_name(err));
+        return false;
+    }
+
+    s_fitted = true;
+    s_on = false;
+    ESP_LOGI(TAG, "Buzzer on GPIO%d (%lu Hz)", pin, (unsigned long)freq_hz);
+    return true;
+}
+
+void hal_buzzer_set(bool on) {
+    if (!s_fitted || on == s_on) {
+        return;
+    }
+    ledc_set_duty(BUZZER_MODE, BUZZER_CHANNEL, on ? BUZZER_DUTY_ON : 0);
+    ledc_update_duty(BUZZER_MODE, BUZZER_CHANNEL);
+    s_on = on;
+}
+
+void hal_buzzer_set_frequency(uint32_t freq_hz) {
+    if (s_fitted) {
+        ledc_set_freq(BUZZER_MODE, BUZZER_TIMER, freq_hz);
+    }
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static bool s_fitted = false;
+static bool s_on = false;
+static uint32_t s_freq_hz = 0;
+
+bool hal_buzzer_init(uint8_t pin, uint32_t freq_hz) {
+    s_fitted = (pin != 0);
+    s_on = false;
+    s_freq_hz = freq_hz;
+    return s_fitted;
+}
+
+void hal_buzzer_set(bool on) {
+    s_on = s_fitted && on;
+}
+
+void hal_buzzer_set_frequency(uint32_t freq_hz) {
+    s_freq_hz = freq_hz;
+}
+
+/* Test helpers */
+bool hal_buzzer_test_is_on(void) {
+    return s_on;
+}
+
+uint32_t hal_buzzer_test_frequency(void) {
+    return s_freq_hz;
+}
+
+#endif /* ESP_PLATFORM */
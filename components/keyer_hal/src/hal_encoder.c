@@ -0,0 +1,162 @@
+/**
+ * @file hal_encoder.c
+ * @brief Rotary encoder HAL implementation
+ *
+ * One PCNT unit with two channels decodes the quadrature signal in
+ * hardware (4 counts per cycle), so no edge is lost however busy Core 1
+ * is. The count accumulates across the unit limits; bg_task takes whole
+ * detents and leaves the remainder counted.
+ */
+
+#include "hal_encoder.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "driver/pulse_cnt.h"
+#include "driver/gpio.h"
+#include "esp_log.h"
+
+static const char *TAG = "hal_encoder";
+
+/** PCNT limits: the count is accumulated on each crossing */
+#define ENC_LIMIT 1000
+
+/** Contact bounce shorter than this is ignored by the PCNT filter */
+#define ENC_GLITCH_NS 1000
+
+static pcnt_unit_handle_t s_unit = NULL;
+static int s_taken = 0;  /* Counts already returned as detents */
+
+/**
+ * @brief Set up both channels: each counts the edges of one input,
+ *        direction from the level of the other
+ */
+static esp_err_t add_channels(uint8_t pin_a, uint8_t pin_b) {
+    pcnt_chan_config_t a_conf = {
+        .edge_gpio_num = pin_a,
+        .level_gpio_num = pin_b,
+    };
+    pcnt_channel_handle_t chan_a = NULL;
+    esp_err_t err = pcnt_new_channel(s_unit, &a_conf, &chan_a);
+    if (err != ESP_OK) {
+        return err;
+    }
+
+    pcnt_chan_config_t b_conf = {
+        .edge_gpio_num = pin_b,
+        .level_gpio_num = pin_a,
+    };
+    pcnt_channel_handle_t chan_b = NULL;
+    err = pcnt_new_channel(s_unit, &b_conf, &chan_b);
+    if (err != ESP_OK) {
+        return err;
+    }
+
+    pcnt_channel_set_edge_action(chan_a, PCNT_CHANNEL_EDGE_ACTION_DECREASE,
+                                 PCNT_CHANNEL_EDGE_ACTION_INCREASE);
+    pcnt_channel_set_level_action(chan_a, PCNT_CHANNEL_LEVEL_ACTION_KEEP,
+                                  PCNT_CHANNEL_LEVEL_ACTION_INVERSE);
+    pcnt_channel_set_edge_action(chan_b, PCNT_CHANNEL_EDGE_ACTION_INCREASE,
+                                 PCNT_CHANNEL_EDGE_ACTION_DECREASE);
+    pcnt_channel_set_level_action(chan_b, PCNT_CHANNEL_LEVEL_ACTION_KEEP,
+                                  PCNT_CHANNEL_LEVEL_ACTION_INVERSE);
+    return ESP_OK;
+}
+
+bool hal_encoder_init(uint8_t pin_a, uint8_t pin_b) {
+    if (pin_a == 0 || pin_b == 0) {
+        return false;
+    }
+
+    pcnt_unit_config_t unit_conf = {
+        .high_limit = ENC_LIMIT,
+        .low_limit = -ENC_LIMIT,
+        .flags.accum_count = true,
+    };
+    esp_err_t err = pcnt_new_unit(&unit_conf, &s_unit);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to create PCNT unit: %s", esp_err_to_name(err));
+        s_unit = NULL;
+        return false;
+    }
+
+    pcnt_glitch_filter_config_t filter = { .max_glitch_ns = ENC_GLITCH_NS };
+    pcnt_unit_set_glitch_filter(s_unit, &filter);
+
+    err = add_channels(pin_a, pin_b);
+    if (err == ESP_OK) {
+        pcnt_unit_add_watch_point(s_unit, ENC_LIMIT);
+        pcnt_unit_add_watch_point(s_unit, -ENC_LIMIT);
+        err = pcnt_unit_enable(s_unit);
+    }
+    if (err == ESP_OK) {
+        pcnt_unit_clear_count(s_unit);
+        err = pcnt_unit_start(s_unit);
+    }
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to start encoder: %s", esp_err_to_name(err));
+        s_unit = NULL;  /* Unit left allocated: init runs once at boot */
+        return false;
+    }
+
+    /* PCNT leaves the inputs floating: the encoder switches to ground */
+    gpio_set_pull_mode((gpio_num_t)pin_a, GPIO_PULLUP_ONLY);
+    gpio_set_pull_mode((gpio_num_t)pin_b, GPIO_PULLUP_ONLY);
+
+    s_taken = 0;
+    ESP_LOGI(TAG, "Encoder on GPIO%d/GPIO%d", pin_a, pin_b);
+    return true;
+}
+
+int hal_encoder_take_detents(void) {
+    int count = 0;
+    if (s_unit == NULL || pcnt_unit_get_count(s_unit, &count) != ESP_OK) {
+        return 0;
+    }
+    int detents = (count - s_taken) / HAL_ENCODER_COUNTS_PER_DETENT;
+    s_taken += detents * HAL_ENCODER_COUNTS_PER_DETENT;
+    return detents;
+}
+
+void hal_encoder_sleep(bool sleep) {
+    if (s_unit == NULL) {
+        return;
+    }
+    if (sleep) {
+        pcnt_unit_stop(s_unit);
+        pcnt_unit_disable(s_unit);
+    } else {
+        pcnt_unit_enable(s_unit);
+        pcnt_unit_start(s_unit);  /* Count kept: partial detents too */
+    }
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static bool s_fitted = false;
+static int s_detents = 0;
+
+bool hal_encoder_init(uint8_t pin_a, uint8_t pin_b) {
+    s_fitted = (pin_a != 0 && pin_b != 0);
+    return s_fitted;
+}
+
+int hal_encoder_take_detents(void) {
+    int detents = s_detents;
+    s_detents = 0;
+    return s_fitted ? detents : 0;
+}
+
+void hal_encoder_sleep(bool sleep) {
+    (void)sleep;
+}
+
+/* Test helper */
+void hal_encoder_test_turn(int detents) {
+    s_detents += detents;
+}
+
+#endif /* ESP_PLATFORM */
@@ -0,0 +1,135 @@
+/**
+ * @file hal_expander.c
+ * @brief I2C GPIO expander HAL implementation
+ */
+
+#include "hal_expander.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "hal_i2c.h"
+#include "esp_log.h"
+
+static const char *TAG = "hal_expander";
+
+#define EXPANDER_I2C_HZ     100000
+
+/* MCP23017 registers (IOCON.BANK = 0, the reset value: A/B pairs) */
+#define MCP_IODIRA          0x00
+#define MCP_GPPUA           0x0C
+#define MCP_GPIOA           0x12
+
+static hal_i2c_dev_t s_dev = NULL;
+static hal_expander_type_t s_type = HAL_EXPANDER_NONE;
+
+static esp_err_t setup_pins(hal_expander_type_t type) {
+    if (type == HAL_EXPANDER_PCF8574) {
+        /* Writing 1 turns a pin into a weakly pulled-up input */
+        const uint8_t inputs = 0xFF;
+        return hal_i2c_write(s_dev, &inputs, 1);
+    }
+
+    const uint8_t iodir[] = { MCP_IODIRA, 0xFF, 0xFF };
+    const uint8_t gppu[] = { MCP_GPPUA, 0xFF, 0xFF };
+    esp_err_t err = hal_i2c_write(s_dev, iodir, sizeof(iodir));
+    if (err == ESP_OK) {
+        err = hal_i2c_write(s_dev, gppu, sizeof(gppu));
+    }
+    return err;
+}
+
+esp_err_t hal_expander_init(hal_expander_type_t type, uint8_t addr) {
+    if (type == HAL_EXPANDER_NONE) {
+        return ESP_OK;
+    }
+
+    esp_err_t err = hal_i2c_probe(addr);
+    if (err == ESP_OK) {
+        err = hal_i2c_add_device(addr, EXPANDER_I2C_HZ, &s_dev);
+    }
+    if (err == ESP_OK) {
+        err = setup_pins(type);
+        if (err != ESP_OK) {
+            hal_i2c_remove_device(s_dev);
+            s_dev = NULL;
+        }
+    }
+    if (err != ESP_OK) {
+        ESP_LOGW(TAG, "No expander at 0x%02X: %s", addr, esp_err_to_name(err));
+        return err;
+    }
+
+    s_type = type;
+    ESP_LOGI(TAG, "%s at 0x%02X, %u inputs",
+             type == HAL_EXPANDER_PCF8574 ? "PCF8574" : "MCP23017",
+             addr, hal_expander_input_count());
+    return ESP_OK;
+}
+
+uint8_t hal_expander_input_count(void) {
+    switch (s_type) {
+        case HAL_EXPANDER_PCF8574: return 8;
+        case HAL_EXPANDER_MCP23017: return 16;
+        default: return 0;
+    }
+}
+
+esp_err_t hal_expander_read(uint16_t *closed) {
+    *closed = 0;
+    if (s_dev == NULL) {
+        return ESP_ERR_INVALID_STATE;
+    }
+
+    uint8_t levels[2] = { 0xFF, 0xFF };
+    esp_err_t err;
+    if (s_type == HAL_EXPANDER_PCF8574) {
+        err = hal_i2c_read(s_dev, levels, 1);
+    } else {
+        const uint8_t reg = MCP_GPIOA;
+        err = hal_i2c_write_read(s_dev, &reg, 1, levels, 2);
+    }
+    if (err != ESP_OK) {
+        return err;
+    }
+
+    /* Pulled up: a closed contact reads 0 */
+    *closed = (uint16_t)~(levels[0] | (levels[1] << 8));
+    if (s_type == HAL_EXPANDER_PCF8574) {
+        *closed &= 0x00FF;
+    }
+    return ESP_OK;
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static hal_expander_type_t s_type = HAL_EXPANDER_NONE;
+static uint16_t s_closed = 0;
+
+esp_err_t hal_expander_init(hal_expander_type_t type, uint8_t addr) {
+    (void)addr;
+    s_type = type;
+    return ESP_OK;
+}
+
+uint8_t hal_expander_input_count(void) {
+    switch (s_type) {
+        case HAL_EXPANDER_PCF8574: return 8;
+        case HAL_EXPANDER_MCP23017: return 16;
+        default: return 0;
+    }
+}
+
+esp_err_t hal_expander_read(uint16_t *closed) {
+    *closed = (s_type != HAL_EXPANDER_NONE) ? s_closed : 0;
+    return (s_type != HAL_EXPANDER_NONE) ? ESP_OK : ESP_ERR_INVALID_STATE;
+}
+
+/* Test helper */
+void hal_expander_test_set(uint16_t closed) {
+    s_closed = closed;
+}
+
+#endif /* ESP_PLATFORM */
@@ -4,43 +4,111 @@
  *
  * ISR + Blanking Strategy (ISR-safe design):
  * 1. GPIO interrupt triggers on falling edge (paddle press)
- * 2. ISR sets atomic flags and disables interrupt (ISR-safe ops only)
+ * 2. ISR stores the press time in an atomic mailbox and disables the
+ *    interrupt (ISR-safe ops only)
  * 3. RT task (via hal_gpio_isr_tick) starts blanking timer (task context)
  * 4. Blanking timer callback re-enables interrupt after blanking period
- * 5. RT task reads atomic flag to detect press
+ * 5. RT task takes the mailbox (hal_gpio_take_presses) to detect press
  *
  * Key insight: esp_timer_start_once() is NOT ISR-safe (uses spinlocks).
  * Solution: ISR sets a flag, RT task starts the timer from task context.
  * This adds ~1ms latency to blanking start but avoids crashes.
+ *
+ * With CONFIG_KEYER_TX_RMT the radio 1 key line is driven by an RMT
+ * channel instead of GPIO writes, so a whole element can be timed by
+ * the peripheral (hal_gpio_tx_pulse()).
  */
 
 #include "hal_gpio.h"
+#include "hal_touch.h"
+
+/* ============================================================================
+ * Press Mailbox
+ *
+ * One 32-bit word per paddle, so the flag and the timestamp are taken
+ * together by a single exchange: (time_us << 1) | 1 for a press, 0 when
+ * empty. Time wraps every ~35 minutes; only differences are used.
+ * ============================================================================ */
+
+/* Always inlined: also called from the IRAM ISRs */
+static inline __attribute__((always_inline)) uint32_t mailbox_pack(int64_t at_us) {
+    return ((uint32_t)at_us << 1) | 1U;
+}
+
+/**
+ * @brief Age of a mailbox press (0 if empty)
+ */
+static inline uint32_t mailbox_age(uint32_t mailbox, int64_t now_us) {
+    if (mailbox == 0) {
+        return 0;
+    }
+    return (mailbox_pack(now_us) - mailbox) >> 1;  /* Wrapping subtraction */
+}
+
+/**
+ * @brief Merge both paddle mailboxes into the take result
+ */
+static bool mailbox_take(uint32_t dit, uint32_t dah, int64_t now_us,
+                         hal_gpio_presses_t *out) {
+    out->paddles = gpio_from_paddles(dit != 0, dah != 0);
+    uint32_t dit_age = mailbox_age(dit, now_us);
+    uint32_t dah_age = mailbox_age(dah, now_us);
+    out->age_us = (dit_age > dah_age) ? dit_age : dah_age;
+    return !gpio_is_idle(out->paddles);
+}
 
 #ifdef ESP_PLATFORM
 /* ESP-IDF target build */
 #include "driver/gpio.h"
+#include "driver/gpio_filter.h"
 #include "esp_private/gpio.h"
+#include "soc/soc_caps.h"
+#include "soc/gpio_reg.h"
+#include "soc/gpio_sig_map.h"
 #include "esp_log.h"
 #include "esp_timer.h"
+#include "esp_rom_sys.h"
+#include "esp_rom_gpio.h"
+#include "esp_attr.h"
 #include <stdatomic.h>
+#if CONFIG_KEYER_TX_RMT
+#include "driver/rmt_tx.h"
+#endif
 
 static const char *TAG = "hal_gpio";
 static hal_gpio_config_t s_config = HAL_GPIO_CONFIG_DEFAULT;
 static bool s_tx_state = false;
+static bool s_ptt_state = false;
+static int64_t s_tx_off_at_us = 0;  /* Last key up on the line (0 = never keyed) */
+static int64_t s_pulse_end_us = 0;  /* End of the last queued RMT pulse */
 static bool s_isr_enabled = false;
 
+/* Outputs of the selected radio (written by the RT task) */
+static gpio_num_t s_tx_pin;
+static gpio_num_t s_ptt_pin;  /* GPIO_NUM_NC if not fitted */
+static atomic_uint s_radio = ATOMIC_VAR_INIT(0);
+static uint8_t s_button_pins[HAL_GPIO_BUTTON_MAX];
+
+/* Paddle and key input snapshot (see read_input_levels()) */
+static uint64_t s_input_active_low = 0;  /* Bit n set: GPIOn reads 0 when closed */
+static bool s_input_high_bank = false;   /* An input above GPIO31 */
+
 /* ============================================================================
  * ISR State (atomic communication with RT task)
  * ============================================================================ */
 
-/* Pending press flags (set by ISR, consumed by RT task) */
-static volatile atomic_bool s_dit_pending = ATOMIC_VAR_INIT(false);
-static volatile atomic_bool s_dah_pending = ATOMIC_VAR_INIT(false);
+/* Press mailboxes (set by ISR, taken by RT task) */
+static volatile atomic_uint_fast32_t s_dit_mailbox = ATOMIC_VAR_INIT(0);
+static volatile atomic_uint_fast32_t s_dah_mailbox = ATOMIC_VAR_INIT(0);
 
 /* Flags to signal RT task to start blanking timers (ISR-safe handoff) */
 static volatile atomic_bool s_dit_needs_blanking = ATOMIC_VAR_INIT(false);
 static volatile atomic_bool s_dah_needs_blanking = ATOMIC_VAR_INIT(false);
 
+/* Light sleep: the paddle ISR reports a closure instead (see hal_gpio_sleep_wakeup) */
+static volatile atomic_bool s_wake_armed = ATOMIC_VAR_INIT(false);
+static hal_gpio_wake_fn s_wake_fn = NULL;
+
 /* Timestamps when interrupts were disabled (for watchdog) */
 static volatile int64_t s_dit_disabled_at_us = 0;
 static volatile int64_t s_dah_disabled_at_us = 0;
@@ -62,17 +130,30 @@ static esp_timer_handle_t s_dah_blanking_timer = NULL;
  * - ESP_LOGx (uses locks)
  * ============================================================================ */
 
+/* Level-triggered while armed: mask both paddles until disarmed */
+static void IRAM_ATTR wake_isr(void) {
+    gpio_intr_disable((gpio_num_t)s_config.dit_pin);
+    gpio_intr_disable((gpio_num_t)s_config.dah_pin);
+    atomic_store_explicit(&s_wake_armed, false, memory_order_relaxed);
+    s_wake_fn();
+}
+
 static void IRAM_ATTR dit_isr_handler(void *arg) {
     (void)arg;
+    if (atomic_load_explicit(&s_wake_armed, memory_order_relaxed)) {
+        wake_isr();
+        return;
+    }
 
-    /* Set pending flag for RT task */
-    atomic_store_explicit(&s_dit_pending, true, memory_order_release);
+    /* Press time for RT task */
+    int64_t now_us = esp_timer_get_time();
+    atomic_store_explicit(&s_dit_mailbox, mailbox_pack(now_us), memory_order_release);
 
     /* Disable interrupt to prevent bounce storms */
     gpio_intr_disable((gpio_num_t)s_config.dit_pin);
 
     /* Record when we disabled (for watchdog) */
-    s_dit_disabled_at_us = esp_timer_get_time();
+    s_dit_disabled_at_us = now_us;
 
     /* Signal RT task to start blanking timer */
     atomic_store_explicit(&s_dit_needs_blanking, true, memory_order_release);
@@ -80,10 +161,15 @@ static void IRAM_ATTR dit_isr_handler(void *arg) {
 
 static void IRAM_ATTR dah_isr_handler(void *arg) {
     (void)arg;
+    if (atomic_load_explicit(&s_wake_armed, memory_order_relaxed)) {
+        wake_isr();
+        return;
+    }
 
-    atomic_store_explicit(&s_dah_pending, true, memory_order_release);
+    int64_t now_us = esp_timer_get_time();
+    atomic_store_explicit(&s_dah_mailbox, mailbox_pack(now_us), memory_order_release);
     gpio_intr_disable((gpio_num_t)s_config.dah_pin);
-    s_dah_disabled_at_us = esp_timer_get_time();
+    s_dah_disabled_at_us = now_us;
     atomic_store_explicit(&s_dah_needs_blanking, true, memory_order_release);
 }
 
@@ -165,6 +251,53 @@ static esp_err_t init_isr(void) {
     return ESP_OK;
 }
 
+/* ============================================================================
+ * Hardware Glitch Filter
+ *
+ * Pulses shorter than the window never reach the GPIO matrix, so an ESD
+ * spike cannot start a dit or trigger the ISR. Chips with flex filters
+ * take the configured window; the ESP32-S3 only has the per-pin filter,
+ * a fixed window of 2 clock cycles. A longer window than the chip can
+ * filter is refused (filter left off) rather than silently shortened.
+ * ============================================================================ */
+
+/** Per-pin filter window: 2 cycles of the 80 MHz APB clock */
+#define PIN_GLITCH_WINDOW_NS 25
+
+static void init_glitch_filter(gpio_num_t pin, uint16_t window_ns) {
+    gpio_glitch_filter_handle_t filter = NULL;
+    esp_err_t err;
+
+#if SOC_GPIO_FLEX_GLITCH_FILTER_NUM > 0
+    gpio_flex_glitch_filter_config_t conf = {
+        .clk_src = GLITCH_FILTER_CLK_SRC_DEFAULT,
+        .gpio_num = pin,
+        .window_width_ns = window_ns,
+        .window_thres_ns = window_ns,
+    };
+    err = gpio_new_flex_glitch_filter(&conf, &filter);
+#elif SOC_GPIO_SUPPORT_PIN_GLITCH_FILTER
+    if (window_ns > PIN_GLITCH_WINDOW_NS) {
+        ESP_LOGE(TAG, "GPIO%d glitch filter: %u ns refused, this chip filters %d ns only",
+                 pin, (unsigned)window_ns, PIN_GLITCH_WINDOW_NS);
+        return;
+    }
+    gpio_pin_glitch_filter_config_t conf = {
+        .clk_src = GLITCH_FILTER_CLK_SRC_DEFAULT,
+        .gpio_num = pin,
+    };
+    err = gpio_new_pin_glitch_filter(&conf, &filter);
+#else
+    (void)window_ns;
+    err = ESP_ERR_NOT_SUPPORTED;
+#endif
+
+    if (err == ESP_OK) {
+        err = gpio_glitch_filter_enable(filter);
+    }
+    ESP_LOGI(TAG, "GPIO%d glitch filter: %s", pin, esp_err_to_name(err));
+}
+
 /* ============================================================================
  * GPIO Reset Helper
  * ============================================================================ */
@@ -178,16 +311,10 @@ static void force_gpio_reset(gpio_num_t pin) {
 }
 
 /* ============================================================================
- * Public API
+ * Paddle Inputs
  * ============================================================================ */
 
-void hal_gpio_init(const hal_gpio_config_t *config) {
-    s_config = *config;
-
-    ESP_LOGI(TAG, "Configuring GPIO: DIT=%d, DAH=%d, TX=%d, active_low=%d, isr_blanking=%luus",
-             config->dit_pin, config->dah_pin, config->tx_pin, config->active_low,
-             (unsigned long)config->isr_blanking_us);
-
+static void init_paddle_inputs(const hal_gpio_config_t *config) {
     /* Force reset paddle pins */
     force_gpio_reset((gpio_num_t)config->dit_pin);
     force_gpio_reset((gpio_num_t)config->dah_pin);
@@ -198,12 +325,16 @@ void hal_gpio_init(const hal_gpio_config_t *config) {
         intr_type = config->active_low ? GPIO_INTR_NEGEDGE : GPIO_INTR_POSEDGE;
     }
 
+    /* Pull toward the open level: up for contacts to ground, down otherwise */
+    gpio_pullup_t pull_up = config->active_low ? GPIO_PULLUP_ENABLE : GPIO_PULLUP_DISABLE;
+    gpio_pulldown_t pull_down = config->active_low ? GPIO_PULLDOWN_DISABLE : GPIO_PULLDOWN_ENABLE;
+
     /* Configure DIT input */
     gpio_config_t dit_conf = {
         .pin_bit_mask = (1ULL << config->dit_pin),
         .mode = GPIO_MODE_INPUT,
-        .pull_up_en = GPIO_PULLUP_ENABLE,
-        .pull_down_en = GPIO_PULLDOWN_DISABLE,
+        .pull_up_en = pull_up,
+        .pull_down_en = pull_down,
         .intr_type = intr_type,
     };
     esp_err_t err = gpio_config(&dit_conf);
@@ -213,71 +344,643 @@ void hal_gpio_init(const hal_gpio_config_t *config) {
     gpio_config_t dah_conf = {
         .pin_bit_mask = (1ULL << config->dah_pin),
         .mode = GPIO_MODE_INPUT,
-        .pull_up_en = GPIO_PULLUP_ENABLE,
-        .pull_down_en = GPIO_PULLDOWN_DISABLE,
+        .pull_up_en = pull_up,
+        .pull_down_en = pull_down,
         .intr_type = intr_type,
     };
     err = gpio_config(&dah_conf);
-    ESP_LOGI(TAG, "DAH GPIO%d config: %s", config->dah_pin, esp_err_to_name(err));
+    ESP_LOGI(TAG, "DAH GPIO%d config: %s (active %s)", config->dah_pin,
+             esp_err_to_name(err), config->active_low ? "low" : "high");
+
+    if (config->glitch_filter_ns > 0) {
+        init_glitch_filter((gpio_num_t)config->dit_pin, config->glitch_filter_ns);
+        init_glitch_filter((gpio_num_t)config->dah_pin, config->glitch_filter_ns);
+    }
+
+    int dit_level = gpio_get_level(config->dit_pin);
+    int dah_level = gpio_get_level(config->dah_pin);
+    ESP_LOGI(TAG, "Initial levels: DIT=%d, DAH=%d", dit_level, dah_level);
+}
+
+/**
+ * @brief Work out the snapshot masks for the paddle and key inputs
+ */
+static void init_input_snapshot(const hal_gpio_config_t *config) {
+    s_input_active_low = 0;
+    s_input_high_bank = false;
+    if (!config->touch_paddles) {
+        if (config->active_low) {
+            s_input_active_low |= (1ULL << config->dit_pin) | (1ULL << config->dah_pin);
+        }
+        s_input_high_bank = config->dit_pin >= 32 || config->dah_pin >= 32;
+    }
+    if (config->key_in_pin != 0) {
+        if (config->key_in_active_low) {
+            s_input_active_low |= 1ULL << config->key_in_pin;
+        }
+        s_input_high_bank = s_input_high_bank || config->key_in_pin >= 32;
+    }
+}
+
+/**
+ * @brief Every input level in one go (bit n = GPIOn)
+ *
+ * One register read covers GPIO0-31; the second bank is read only if an
+ * input lives there.
+ */
+static inline uint64_t read_input_levels(void) {
+    uint64_t levels = REG_READ(GPIO_IN_REG);
+    if (s_input_high_bank) {
+        levels |= (uint64_t)REG_READ(GPIO_IN1_REG) << 32;
+    }
+    return levels;
+}
+
+/**
+ * @brief Straight key or PTT input: polled, no ISR (debounced in the RT task)
+ */
+static void init_polled_input(const char *what, uint8_t pin, bool active_low) {
+    gpio_config_t conf = {
+        .pin_bit_mask = (1ULL << pin),
+        .mode = GPIO_MODE_INPUT,
+        .pull_up_en = active_low ? GPIO_PULLUP_ENABLE : GPIO_PULLUP_DISABLE,
+        .pull_down_en = active_low ? GPIO_PULLDOWN_DISABLE : GPIO_PULLDOWN_ENABLE,
+        .intr_type = GPIO_INTR_DISABLE,
+    };
+    esp_err_t err = gpio_config(&conf);
+    ESP_LOGI(TAG, "%s input GPIO%d config: %s (active %s)", what, pin,
+             esp_err_to_name(err), active_low ? "low" : "high");
+}
 
-    /* Configure TX output */
-    gpio_config_t tx_conf = {
-        .pin_bit_mask = (1ULL << config->tx_pin),
-        .mode = GPIO_MODE_OUTPUT,
-        .pull_up_en = GPIO_PULLUP_DISABLE,
+/* ============================================================================
+ * Key Outputs
+ * ============================================================================ */
+
+/**
+ * @brief Pin level for an output state (TX polarity applies to PTT too)
+ */
+static uint32_t output_level(bool on) {
+    return (s_config.tx_active_high == on) ? 1U : 0U;
+}
+
+/* ============================================================================
+ * RMT Key Output (radio 1)
+ *
+ * Every TX change is an RMT transaction: a level change is a 2 µs
+ * symbol that leaves the line at the new level (eot_level), a pulse is
+ * the whole element followed by key up. Transactions queue, so a key-up
+ * written by the RT task while a pulse runs takes effect after it: the
+ * element keeps its exact length. Polarity and open drain are set on
+ * the channel, so symbol levels are logical (1 = key down).
+ *
+ * The RT task never waits on the queue: a transaction that finds it full
+ * is refused and the caller leaves the TX state as it was, so the edge
+ * goes out on a later tick.
+ * ============================================================================ */
+
+#if CONFIG_KEYER_TX_RMT
+
+/** 1 tick = 1 µs */
+#define RMT_RESOLUTION_HZ   1000000
+
+/** Longest half symbol (15-bit duration) */
+#define RMT_HALF_MAX_US     32767U
+
+/** Symbols in a pulse buffer: ~1 s, longer than a 5 WPM dah */
+#define RMT_PULSE_SYMBOLS   16
+
+/** Channel RAM (one block on every target) */
+#define RMT_MEM_SYMBOLS     48
+
+/** Transactions queued at once (level changes + one pulse) */
+#define RMT_QUEUE_DEPTH     4
+
+static rmt_channel_handle_t s_rmt = NULL;
+static rmt_encoder_handle_t s_rmt_encoder = NULL;
+
+/* Pulse buffers must stay valid until sent: two, used in turn */
+static rmt_symbol_word_t s_pulse_symbols[2][RMT_PULSE_SYMBOLS];
+static uint8_t s_pulse_buf = 0;
+
+static const rmt_symbol_word_t RMT_LEVEL_SYMBOL[2] = {
+    { .level0 = 0, .duration0 = 1, .level1 = 0, .duration1 = 1 },
+    { .level0 = 1, .duration0 = 1, .level1 = 1, .duration1 = 1 },
+};
+
+static void init_rmt(gpio_num_t pin) {
+    rmt_tx_channel_config_t conf = {
+        .gpio_num = pin,
+        .clk_src = RMT_CLK_SRC_DEFAULT,
+        .resolution_hz = RMT_RESOLUTION_HZ,
+        .mem_block_symbols = RMT_MEM_SYMBOLS,
+        .trans_queue_depth = RMT_QUEUE_DEPTH,
+        .flags.invert_out = !s_config.tx_active_high,
+        .flags.io_loop_back = true,  /* Input kept for the loopback test */
+        .flags.io_od_mode = s_config.tx_open_drain,
+    };
+    rmt_copy_encoder_config_t enc_conf = {};
+    esp_err_t err = rmt_new_tx_channel(&conf, &s_rmt);
+    if (err == ESP_OK) {
+        err = rmt_new_copy_encoder(&enc_conf, &s_rmt_encoder);
+    }
+    if (err == ESP_OK) {
+        err = rmt_enable(s_rmt);
+    }
+    if (err != ESP_OK) {
+        ESP_LOGW(TAG, "RMT key output unavailable (%s), using GPIO writes",
+                 esp_err_to_name(err));
+        if (s_rmt_encoder != NULL) {
+            rmt_del_encoder(s_rmt_encoder);
+            s_rmt_encoder = NULL;
+        }
+        if (s_rmt != NULL) {
+            rmt_del_channel(s_rmt);
+            s_rmt = NULL;
+        }
+        return;
+    }
+    if (s_config.tx_open_drain) {
+        gpio_set_pull_mode(pin, GPIO_PULLUP_ONLY);
+    }
+    ESP_LOGI(TAG, "TX GPIO%d driven by RMT", pin);
+}
+
+/**
+ * @brief Check if the selected radio's key line is on the RMT channel
+ */
+static bool rmt_drives_tx(void) {
+    return s_rmt != NULL && atomic_load_explicit(&s_radio, memory_order_relaxed) == 0;
+}
+
+static bool rmt_send(const rmt_symbol_word_t *symbols, size_t count, bool end_on) {
+    rmt_transmit_config_t conf = {
+        .loop_count = 0,
+        .flags.eot_level = end_on ? 1U : 0U,
+        .flags.queue_nonblocking = 1,  /* Full queue: fail, don't block the RT task */
+    };
+    return rmt_transmit(s_rmt, s_rmt_encoder, symbols,
+                        count * sizeof(rmt_symbol_word_t), &conf) == ESP_OK;
+}
+
+/**
+ * @brief Build a key-down pulse of an exact length, then key up
+ *
+ * @return Symbols used, 0 if too long for the buffer
+ */
+static size_t rmt_build_pulse(rmt_symbol_word_t *symbols, uint32_t duration_us) {
+    size_t halves = (duration_us + RMT_HALF_MAX_US - 1U) / RMT_HALF_MAX_US;
+    size_t count = halves / 2U + 1U;  /* Key down halves + a 1 µs key-up half */
+    if (halves == 0 || count > RMT_PULSE_SYMBOLS) {
+        return 0;
+    }
+
+    uint32_t left = duration_us;
+    for (size_t i = 0; i < count * 2U; i++) {
+        uint32_t half = (left > RMT_HALF_MAX_US) ? RMT_HALF_MAX_US : left;
+        bool down = half > 0;
+        left -= half;
+        if (!down) {
+            half = 1;  /* Key up (a zero duration would end the transaction) */
+        }
+        if ((i & 1U) == 0) {
+            symbols[i / 2U].level0 = down ? 1U : 0U;
+            symbols[i / 2U].duration0 = half & RMT_HALF_MAX_US;
+        } else {
+            symbols[i / 2U].level1 = down ? 1U : 0U;
+            symbols[i / 2U].duration1 = half & RMT_HALF_MAX_US;
+        }
+    }
+    return count;
+}
+
+#else
+
+static void init_rmt(gpio_num_t pin) {
+    (void)pin;
+}
+
+static bool rmt_drives_tx(void) {
+    return false;
+}
+
+#endif /* CONFIG_KEYER_TX_RMT */
+
+/**
+ * @brief Drive the selected radio's key line
+ *
+ * @return false if the RMT queue was full (nothing written)
+ */
+static bool write_tx(bool on) {
+#if CONFIG_KEYER_TX_RMT
+    if (rmt_drives_tx()) {
+        return rmt_send(&RMT_LEVEL_SYMBOL[on ? 1 : 0], 1, on);
+    }
+#endif
+    gpio_set_level(s_tx_pin, output_level(on));
+    return true;
+}
+
+/**
+ * @brief Configure a key or PTT output, released
+ *
+ * Input buffer enabled for selftest readback. Open drain keeps the
+ * pull-up so the released level is defined (and readable) with nothing
+ * attached. The idle level is latched before the driver is enabled, so
+ * the pin never glitches to keyed at boot.
+ */
+static void init_output(uint8_t pin, const char *name) {
+    gpio_set_level((gpio_num_t)pin, output_level(false));
+    gpio_config_t conf = {
+        .pin_bit_mask = (1ULL << pin),
+        .mode = s_config.tx_open_drain ? GPIO_MODE_INPUT_OUTPUT_OD : GPIO_MODE_INPUT_OUTPUT,
+        .pull_up_en = s_config.tx_open_drain ? GPIO_PULLUP_ENABLE : GPIO_PULLUP_DISABLE,
         .pull_down_en = GPIO_PULLDOWN_DISABLE,
         .intr_type = GPIO_INTR_DISABLE,
     };
-    err = gpio_config(&tx_conf);
-    ESP_LOGI(TAG, "TX GPIO%d config: %s", config->tx_pin, esp_err_to_name(err));
+    esp_err_t err = gpio_config(&conf);
+    ESP_LOGI(TAG, "%s GPIO%d config: %s", name, pin, esp_err_to_name(err));
+}
+
+static gpio_num_t optional_pin(uint8_t pin) {
+    return (pin != 0) ? (gpio_num_t)pin : GPIO_NUM_NC;
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void hal_gpio_init(const hal_gpio_config_t *config) {
+    s_config = *config;
+
+    ESP_LOGI(TAG, "Configuring GPIO: DIT=%d, DAH=%d, TX=%d, active_low=%d, isr_blanking=%luus",
+             config->dit_pin, config->dah_pin, config->tx_pin, config->active_low,
+             (unsigned long)config->isr_blanking_us);
+
+    if (!config->touch_paddles) {
+        init_paddle_inputs(config);
+    }
+    if (config->key_in_pin != 0) {
+        init_polled_input("Key", config->key_in_pin, config->key_in_active_low);
+    }
+    if (config->ptt_in_pin != 0) {
+        init_polled_input("PTT", config->ptt_in_pin, config->ptt_in_active_low);
+    }
+    init_input_snapshot(config);
+
+    /* Configure key and PTT outputs, all released, radio 1 selected */
+    ESP_LOGI(TAG, "Key outputs: active %s, %s, min key-up %luus",
+             config->tx_active_high ? "high" : "low",
+             config->tx_open_drain ? "open drain" : "push-pull",
+             (unsigned long)config->tx_min_key_up_us);
+    init_output(config->tx_pin, "TX");
+    if (config->ptt_pin != 0) {
+        init_output(config->ptt_pin, "PTT");
+    }
+    if (config->tx2_pin != 0) {
+        init_output(config->tx2_pin, "TX2");
+    }
+    if (config->ptt2_pin != 0) {
+        init_output(config->ptt2_pin, "PTT2");
+    }
+    if (config->antenna_pin != 0) {
+        init_output(config->antenna_pin, "Antenna relay");
+    }
+    if (config->amp_pin != 0) {
+        init_output(config->amp_pin, "Amp PTT");
+    }
+    s_tx_pin = (gpio_num_t)config->tx_pin;
+    s_ptt_pin = optional_pin(config->ptt_pin);
+    atomic_store_explicit(&s_radio, 0, memory_order_relaxed);
+    init_rmt(s_tx_pin);
 
+    s_tx_state = true;  /* Forces the first write */
     hal_gpio_set_tx(false);
 
     /* Initialize ISR if configured */
-    if (config->isr_blanking_us > 0) {
-        err = init_isr();
+    if (config->touch_paddles) {
+        ESP_LOGI(TAG, "Touch paddles, ISR not used");
+    } else if (config->isr_blanking_us > 0) {
+        esp_err_t err = init_isr();
         if (err != ESP_OK) {
             ESP_LOGW(TAG, "ISR init failed, using polling only");
         }
     } else {
         ESP_LOGI(TAG, "ISR disabled, using polling only");
     }
-
-    int dit_level = gpio_get_level(config->dit_pin);
-    int dah_level = gpio_get_level(config->dah_pin);
-    ESP_LOGI(TAG, "Initial levels: DIT=%d, DAH=%d", dit_level, dah_level);
 }
 
 gpio_state_t hal_gpio_read_paddles(void) {
-    int dit_level = gpio_get_level((gpio_num_t)s_config.dit_pin);
-    int dah_level = gpio_get_level((gpio_num_t)s_config.dah_pin);
+    gpio_state_t state = gpio_from_levels(read_input_levels(), s_input_active_low,
+                                          s_config.dit_pin, s_config.dah_pin,
+                                          s_config.key_in_pin);
+    if (s_config.touch_paddles) {
+        gpio_state_t touch = hal_touch_read_paddles();
+        state.bits = (uint8_t)((state.bits & GPIO_KEY_BIT) | touch.bits);
+    }
+    return state;
+}
 
-    bool dit_pressed = s_config.active_low ? (dit_level == 0) : (dit_level != 0);
-    bool dah_pressed = s_config.active_low ? (dah_level == 0) : (dah_level != 0);
+bool hal_gpio_read_ptt_in(void) {
+    if (s_config.ptt_in_pin == 0) {
+        return false;
+    }
+    int level = gpio_get_level((gpio_num_t)s_config.ptt_in_pin);
+    return s_config.ptt_in_active_low ? (level == 0) : (level != 0);
+}
 
-    return gpio_from_paddles(dit_pressed, dah_pressed);
+/**
+ * @brief Check the minimum key-up time before a key down
+ *
+ * @return false if the key down has to wait
+ */
+static bool key_up_done(void) {
+    return s_config.tx_min_key_up_us == 0 || s_tx_off_at_us == 0 ||
+           esp_timer_get_time() - s_tx_off_at_us >= (int64_t)s_config.tx_min_key_up_us;
+}
+
+/**
+ * @brief When the line goes idle: now, or after the pulse still queued
+ */
+static int64_t tx_idle_at_us(void) {
+    int64_t now_us = esp_timer_get_time();
+    return (s_pulse_end_us > now_us) ? s_pulse_end_us : now_us;
 }
 
 void hal_gpio_set_tx(bool on) {
+    if (on == s_tx_state) {
+        return;
+    }
+    if (on && !key_up_done()) {
+        return;  /* Key-up too short: key down goes out later */
+    }
+    if (!write_tx(on)) {
+        return;  /* RMT queue full: retried on the next call */
+    }
+    if (!on) {
+        s_tx_off_at_us = tx_idle_at_us();  /* A key up queued behind a pulse */
+    }
     s_tx_state = on;
-    uint32_t level = s_config.tx_active_high ? (on ? 1U : 0U) : (on ? 0U : 1U);
-    gpio_set_level(s_config.tx_pin, level);
+}
+
+bool hal_gpio_tx_pulse(uint32_t duration_us) {
+#if CONFIG_KEYER_TX_RMT
+    if (s_tx_state || !rmt_drives_tx() || !key_up_done()) {
+        return false;
+    }
+    rmt_symbol_word_t *symbols = s_pulse_symbols[s_pulse_buf];
+    size_t count = rmt_build_pulse(symbols, duration_us);
+    int64_t start_us = tx_idle_at_us();
+    if (count == 0 || !rmt_send(symbols, count, false)) {
+        return false;
+    }
+    s_pulse_end_us = start_us + (int64_t)duration_us;
+    s_pulse_buf ^= 1U;
+    s_tx_state = true;
+    return true;
+#else
+    (void)duration_us;
+    return false;
+#endif
+}
+
+void hal_gpio_tx_stop(void) {
+#if CONFIG_KEYER_TX_RMT
+    if (s_rmt != NULL) {
+        /* Drops a pulse in progress and anything queued behind it */
+        rmt_disable(s_rmt);
+        rmt_enable(s_rmt);
+        if (rmt_drives_tx()) {
+            rmt_send(&RMT_LEVEL_SYMBOL[0], 1, false);
+        }
+    }
+#endif
+    if (s_tx_state) {
+        s_tx_off_at_us = esp_timer_get_time();
+    }
+    s_pulse_end_us = 0;
+    s_tx_state = false;
+    gpio_set_level(s_tx_pin, output_level(false));
+}
+
+/**
+ * @brief Drive one output to its released level from the GPIO matrix
+ */
+static void IRAM_ATTR panic_release(uint8_t pin) {
+    if (pin == 0) {
+        return;
+    }
+    /* Plain GPIO output again, in case RMT owned the pin */
+    esp_rom_gpio_connect_out_signal(pin, SIG_GPIO_OUT_IDX, false, false);
+
+    bool high = !s_config.tx_active_high;
+    if (pin < 32) {
+        REG_WRITE(high ? GPIO_OUT_W1TS_REG : GPIO_OUT_W1TC_REG, 1UL << pin);
+    } else {
+        REG_WRITE(high ? GPIO_OUT1_W1TS_REG : GPIO_OUT1_W1TC_REG, 1UL << (pin - 32));
+    }
+}
+
+void IRAM_ATTR hal_gpio_panic_off(void) {
+    panic_release(s_config.tx_pin);
+    panic_release(s_config.tx2_pin);
+    panic_release(s_config.ptt_pin);
+    panic_release(s_config.ptt2_pin);
+    panic_release(s_config.amp_pin);
 }
 
 bool hal_gpio_get_tx(void) {
     return s_tx_state;
 }
 
+void hal_gpio_set_ptt(bool on) {
+    s_ptt_state = on;
+    if (s_ptt_pin != GPIO_NUM_NC) {
+        gpio_set_level(s_ptt_pin, output_level(on));
+    }
+}
+
+void hal_gpio_set_antenna(bool on) {
+    if (s_config.antenna_pin != 0) {
+        gpio_set_level((gpio_num_t)s_config.antenna_pin, output_level(on));
+    }
+}
+
+void hal_gpio_set_amp(bool on) {
+    if (s_config.amp_pin != 0) {
+        gpio_set_level((gpio_num_t)s_config.amp_pin, output_level(on));
+    }
+}
+
+bool hal_gpio_select_radio(uint8_t radio) {
+    if (radio >= HAL_GPIO_RADIO_MAX || (radio == 1 && s_config.tx2_pin == 0)) {
+        return false;
+    }
+
+    /* Release the outputs of the radio being left */
+    write_tx(false);
+    if (s_ptt_pin != GPIO_NUM_NC) {
+        gpio_set_level(s_ptt_pin, output_level(false));
+    }
+
+    s_tx_pin = (gpio_num_t)((radio == 0) ? s_config.tx_pin : s_config.tx2_pin);
+    s_ptt_pin = optional_pin((radio == 0) ? s_config.ptt_pin : s_config.ptt2_pin);
+    atomic_store_explicit(&s_radio, radio, memory_order_relaxed);
+
+    /* The new radio follows the current state */
+    write_tx(s_tx_state);
+    hal_gpio_set_ptt(s_ptt_state);
+    return true;
+}
+
+uint8_t hal_gpio_get_radio(void) {
+    return (uint8_t)atomic_load_explicit(&s_radio, memory_order_relaxed);
+}
+
 hal_gpio_config_t hal_gpio_get_config(void) {
     return s_config;
 }
 
-bool hal_gpio_consume_dit_press(void) {
-    return atomic_exchange_explicit(&s_dit_pending, false, memory_order_acquire);
+/**
+ * @brief Drive a key output for the loopback check (state not recorded)
+ */
+static void write_loop_output(hal_gpio_loop_output_t output, bool on) {
+    if (output == HAL_GPIO_LOOP_TX) {
+        write_tx(on);
+    } else if (s_ptt_pin != GPIO_NUM_NC) {
+        gpio_set_level(s_ptt_pin, output_level(on));
+    }
+}
+
+/**
+ * @brief Poll the sense input until it reaches a level
+ *
+ * @return true if reached within timeout_us, elapsed time in *elapsed_us
+ */
+static bool wait_sense(gpio_num_t pin, int level, uint32_t timeout_us, uint32_t *elapsed_us) {
+    int64_t start = esp_timer_get_time();
+    for (;;) {
+        uint32_t elapsed = (uint32_t)(esp_timer_get_time() - start);
+        if (gpio_get_level(pin) == level) {
+            *elapsed_us = elapsed;
+            return true;
+        }
+        if (elapsed >= timeout_us) {
+            *elapsed_us = elapsed;
+            return false;
+        }
+    }
+}
+
+bool hal_gpio_output_loopback(hal_gpio_loop_output_t output, uint8_t sense_pin,
+                              bool sense_active_low, uint32_t timeout_us,
+                              hal_gpio_loop_result_t *result) {
+    gpio_num_t pin = (gpio_num_t)sense_pin;
+    int keyed = sense_active_low ? 0 : 1;
+    bool restore = (output == HAL_GPIO_LOOP_TX) ? s_tx_state : s_ptt_state;
+
+    *result = (hal_gpio_loop_result_t){ 0 };
+    init_polled_input("Loopback", sense_pin, sense_active_low);
+
+    write_loop_output(output, false);
+    esp_rom_delay_us(10);
+    result->idle_ok = (gpio_get_level(pin) != keyed);
+
+    if (result->idle_ok) {
+        write_loop_output(output, true);
+        result->asserted = wait_sense(pin, keyed, timeout_us, &result->assert_us);
+        write_loop_output(output, false);
+        result->released = wait_sense(pin, keyed ^ 1, timeout_us, &result->release_us);
+    }
+
+    /* Back to the level last commanded, sense pin left as an input */
+    write_loop_output(output, restore);
+    return result->idle_ok && result->asserted && result->released;
+}
+
+/** Longest the TX pad may take to follow a write (RMT queue included) */
+#define TX_LOOPBACK_TIMEOUT_US 200
+
+bool hal_gpio_tx_loopback(void) {
+    int active = (int)output_level(true);
+    uint32_t elapsed_us;
+
+    /* Released before the test: a pad reading keyed is clamped */
+    write_tx(false);
+    bool ok = wait_sense(s_tx_pin, active ^ 1, TX_LOOPBACK_TIMEOUT_US, &elapsed_us);
+
+    write_tx(true);
+    ok = wait_sense(s_tx_pin, active, TX_LOOPBACK_TIMEOUT_US, &elapsed_us) && ok;
+
+    /* Must follow the release too: stuck keyed is the case that matters */
+    write_tx(false);
+    ok = wait_sense(s_tx_pin, active ^ 1, TX_LOOPBACK_TIMEOUT_US, &elapsed_us) && ok;
+    return ok;
 }
 
-bool hal_gpio_consume_dah_press(void) {
-    return atomic_exchange_explicit(&s_dah_pending, false, memory_order_acquire);
+/**
+ * @brief Check whether a GPIO is already used by the keying pins
+ */
+static bool keying_pin(uint8_t pin) {
+    const uint8_t used[] = {
+        s_config.dit_pin, s_config.dah_pin, s_config.tx_pin, s_config.ptt_pin,
+        s_config.tx2_pin, s_config.ptt2_pin, s_config.antenna_pin, s_config.amp_pin,
+        s_config.key_in_pin, s_config.ptt_in_pin,
+    };
+    for (size_t i = 0; i < sizeof(used); i++) {
+        if (used[i] == pin) {
+            return true;
+        }
+    }
+    return false;
+}
+
+uint8_t hal_gpio_buttons_init(const uint8_t *pins, size_t count) {
+    uint8_t refused = 0;
+    for (size_t i = 0; i < HAL_GPIO_BUTTON_MAX; i++) {
+        uint8_t pin = (i < count) ? pins[i] : 0;
+        s_button_pins[i] = 0;
+        if (pin == 0) {
+            continue;
+        }
+        esp_err_t err = ESP_ERR_INVALID_ARG;
+        if (pin < GPIO_NUM_MAX && !keying_pin(pin)) {
+            gpio_config_t conf = {
+                .pin_bit_mask = (1ULL << pin),
+                .mode = GPIO_MODE_INPUT,
+                .pull_up_en = GPIO_PULLUP_ENABLE,
+                .pull_down_en = GPIO_PULLDOWN_DISABLE,
+                .intr_type = GPIO_INTR_DISABLE,
+            };
+            err = gpio_config(&conf);
+        }
+        if (err != ESP_OK) {
+            ESP_LOGE(TAG, "Memory button %u GPIO%d refused: %s", (unsigned)(i + 1),
+                     pin, esp_err_to_name(err));
+            if (refused == 0) {
+                refused = pin;
+            }
+            continue;
+        }
+        s_button_pins[i] = pin;
+        ESP_LOGI(TAG, "Memory button %u on GPIO%d", (unsigned)(i + 1), pin);
+    }
+    return refused;
+}
+
+uint8_t hal_gpio_read_buttons(void) {
+    uint8_t mask = 0;
+    for (size_t i = 0; i < HAL_GPIO_BUTTON_MAX; i++) {
+        if (s_button_pins[i] != 0 && gpio_get_level((gpio_num_t)s_button_pins[i]) == 0) {
+            mask |= (uint8_t)(1U << i);
+        }
+    }
+    return mask;
+}
+
+bool hal_gpio_take_presses(int64_t now_us, hal_gpio_presses_t *out) {
+    uint32_t dit = (uint32_t)atomic_exchange_explicit(&s_dit_mailbox, 0, memory_order_acquire);
+    uint32_t dah = (uint32_t)atomic_exchange_explicit(&s_dah_mailbox, 0, memory_order_acquire);
+    return mailbox_take(dit, dah, now_us, out);
 }
 
 bool hal_gpio_isr_enabled(void) {
@@ -316,6 +1019,69 @@ void hal_gpio_isr_tick(int64_t now_us) {
     }
 }
 
+/* ============================================================================
+ * Light Sleep Wakeup
+ * ============================================================================ */
+
+static void set_wakeup(uint8_t pin, bool active_low, bool enable) {
+    if (pin == 0) {
+        return;
+    }
+    if (enable) {
+        gpio_wakeup_enable((gpio_num_t)pin, active_low ? GPIO_INTR_LOW_LEVEL : GPIO_INTR_HIGH_LEVEL);
+    } else {
+        gpio_wakeup_disable((gpio_num_t)pin);
+        gpio_set_intr_type((gpio_num_t)pin, GPIO_INTR_DISABLE);
+    }
+}
+
+void hal_gpio_set_wake_callback(hal_gpio_wake_fn fn) {
+    s_wake_fn = fn;
+}
+
+void hal_gpio_sleep_wakeup(bool enable) {
+    if (!s_config.touch_paddles) {
+        if (s_isr_enabled) {
+            /* Level wakeup would retrigger the edge ISR while held */
+            gpio_intr_disable((gpio_num_t)s_config.dit_pin);
+            gpio_intr_disable((gpio_num_t)s_config.dah_pin);
+            atomic_store(&s_wake_armed, false);
+        }
+        set_wakeup(s_config.dit_pin, s_config.active_low, enable);
+        set_wakeup(s_config.dah_pin, s_config.active_low, enable);
+        if (enable && s_isr_enabled && s_wake_fn != NULL) {
+            /* One level interrupt at the first closure (wake_isr masks it) */
+            atomic_store(&s_wake_armed, true);
+            gpio_intr_enable((gpio_num_t)s_config.dit_pin);
+            gpio_intr_enable((gpio_num_t)s_config.dah_pin);
+        }
+        if (!enable && s_isr_enabled) {
+            gpio_int_type_t edge = s_config.active_low ? GPIO_INTR_NEGEDGE : GPIO_INTR_POSEDGE;
+            gpio_set_intr_type((gpio_num_t)s_config.dit_pin, edge);
+            gpio_set_intr_type((gpio_num_t)s_config.dah_pin, edge);
+            s_dit_disabled_at_us = 0;
+            s_dah_disabled_at_us = 0;
+            gpio_intr_enable((gpio_num_t)s_config.dit_pin);
+            gpio_intr_enable((gpio_num_t)s_config.dah_pin);
+        }
+    }
+    set_wakeup(s_config.key_in_pin, s_config.key_in_active_low, enable);
+    set_wakeup(s_config.ptt_in_pin, s_config.ptt_in_active_low, enable);
+    for (size_t i = 0; i < HAL_GPIO_BUTTON_MAX; i++) {
+        set_wakeup(s_button_pins[i], true, enable);
+    }
+
+#if CONFIG_KEYER_TX_RMT
+    if (s_rmt != NULL) {
+        if (enable) {
+            rmt_disable(s_rmt);
+        } else {
+            rmt_enable(s_rmt);
+        }
+    }
+#endif
+}
+
 #else
 /* ============================================================================
  * Host Stub Implementation
@@ -326,53 +1092,146 @@ void hal_gpio_isr_tick(int64_t now_us) {
 static hal_gpio_config_t s_config = HAL_GPIO_CONFIG_DEFAULT;
 static gpio_state_t s_paddle_state = {0};
 static bool s_tx_state = false;
-static atomic_bool s_dit_pending = ATOMIC_VAR_INIT(false);
-static atomic_bool s_dah_pending = ATOMIC_VAR_INIT(false);
+static bool s_ptt_state = false;
+static bool s_ptt_in = false;
+static uint8_t s_radio = 0;
+static uint8_t s_button_state = 0;
+static atomic_uint_fast32_t s_dit_mailbox = ATOMIC_VAR_INIT(0);
+static atomic_uint_fast32_t s_dah_mailbox = ATOMIC_VAR_INIT(0);
 
 void hal_gpio_init(const hal_gpio_config_t *config) {
     s_config = *config;
 }
 
 gpio_state_t hal_gpio_read_paddles(void) {
-    return s_paddle_state;
+    gpio_state_t state = s_config.touch_paddles ? hal_touch_read_paddles() : s_paddle_state;
+    state.bits |= (uint8_t)(s_paddle_state.bits & GPIO_KEY_BIT);
+    return state;
+}
+
+bool hal_gpio_read_ptt_in(void) {
+    return s_ptt_in;
 }
 
 void hal_gpio_set_tx(bool on) {
     s_tx_state = on;
 }
 
+bool hal_gpio_tx_pulse(uint32_t duration_us) {
+    (void)duration_us;
+    return false;
+}
+
+void hal_gpio_tx_stop(void) {
+    s_tx_state = false;
+}
+
+void hal_gpio_panic_off(void) {
+    s_tx_state = false;
+    s_ptt_state = false;
+}
+
 bool hal_gpio_get_tx(void) {
     return s_tx_state;
 }
 
+void hal_gpio_set_ptt(bool on) {
+    s_ptt_state = on;
+}
+
+void hal_gpio_set_antenna(bool on) {
+    (void)on;
+}
+
+void hal_gpio_set_amp(bool on) {
+    (void)on;
+}
+
+bool hal_gpio_select_radio(uint8_t radio) {
+    if (radio >= HAL_GPIO_RADIO_MAX || (radio == 1 && s_config.tx2_pin == 0)) {
+        return false;
+    }
+    s_radio = radio;
+    return true;
+}
+
+uint8_t hal_gpio_get_radio(void) {
+    return s_radio;
+}
+
 hal_gpio_config_t hal_gpio_get_config(void) {
     return s_config;
 }
 
-bool hal_gpio_consume_dit_press(void) {
-    return atomic_exchange(&s_dit_pending, false);
+bool hal_gpio_tx_loopback(void) {
+    return true;
+}
+
+bool hal_gpio_output_loopback(hal_gpio_loop_output_t output, uint8_t sense_pin,
+                              bool sense_active_low, uint32_t timeout_us,
+                              hal_gpio_loop_result_t *result) {
+    (void)output;
+    (void)sense_pin;
+    (void)sense_active_low;
+    (void)timeout_us;
+    *result = (hal_gpio_loop_result_t){
+        .idle_ok = true, .asserted = true, .released = true,
+    };
+    return true;
+}
+
+uint8_t hal_gpio_buttons_init(const uint8_t *pins, size_t count) {
+    (void)pins;
+    (void)count;
+    return 0;
+}
+
+uint8_t hal_gpio_read_buttons(void) {
+    return s_button_state;
 }
 
-bool hal_gpio_consume_dah_press(void) {
-    return atomic_exchange(&s_dah_pending, false);
+bool hal_gpio_take_presses(int64_t now_us, hal_gpio_presses_t *out) {
+    uint32_t dit = (uint32_t)atomic_exchange(&s_dit_mailbox, 0);
+    uint32_t dah = (uint32_t)atomic_exchange(&s_dah_mailbox, 0);
+    return mailbox_take(dit, dah, now_us, out);
 }
 
 bool hal_gpio_isr_enabled(void) {
-    return s_config.isr_blanking_us > 0;
+    return s_config.isr_blanking_us > 0 && !s_config.touch_paddles;
 }
 
 void hal_gpio_isr_tick(int64_t now_us) {
     (void)now_us;
 }
 
+void hal_gpio_set_wake_callback(hal_gpio_wake_fn fn) {
+    (void)fn;
+}
+
+void hal_gpio_sleep_wakeup(bool enable) {
+    (void)enable;
+}
+
 /* Test helpers */
 void hal_gpio_test_set_paddles(bool dit, bool dah) {
     s_paddle_state = gpio_from_paddles(dit, dah);
 }
 
-void hal_gpio_test_inject_isr_press(bool dit, bool dah) {
-    if (dit) atomic_store(&s_dit_pending, true);
-    if (dah) atomic_store(&s_dah_pending, true);
+void hal_gpio_test_set_key(bool down) {
+    s_paddle_state.bits = (uint8_t)((s_paddle_state.bits & ~GPIO_KEY_BIT) | (down ? GPIO_KEY_BIT : 0));
+}
+
+void hal_gpio_test_set_ptt_in(bool held) {
+    s_ptt_in = held;
+}
+
+void hal_gpio_test_set_buttons(uint8_t mask) {
+    s_button_state = mask;
+}
+
+void hal_gpio_test_inject_isr_press(bool dit, bool dah, int64_t at_us) {
+    if (dit) atomic_store(&s_dit_mailbox, mailbox_pack(at_us));
+    if (dah) atomic_store(&s_dah_mailbox, mailbox_pack(at_us));
 }
 
 #endif /* ESP_PLATFORM */
@@ -0,0 +1,201 @@
+/**
+ * @file hal_i2c.c
+ * @brief Shared I2C bus implementation
+ */
+
+#include "hal_i2c.h"
+#include <stdatomic.h>
+
+static atomic_uint s_errors = ATOMIC_VAR_INIT(0);
+
+#ifdef ESP_PLATFORM
+/* ============================================================================
+ * ESP-IDF Implementation
+ * ============================================================================ */
+
+#include "esp_log.h"
+
+static const char *TAG = "hal_i2c";
+
+#define I2C_PORT I2C_NUM_0
+
+static i2c_master_bus_handle_t s_bus = NULL;
+
+static esp_err_t count_error(esp_err_t err) {
+    if (err != ESP_OK) {
+        atomic_fetch_add_explicit(&s_errors, 1, memory_order_relaxed);
+    }
+    return err;
+}
+
+esp_err_t hal_i2c_init(int sda_pin, int scl_pin) {
+    if (s_bus != NULL) {
+        return ESP_OK;
+    }
+
+    i2c_master_bus_config_t bus_cfg = {
+        .i2c_port = I2C_PORT,
+        .sda_io_num = sda_pin,
+        .scl_io_num = scl_pin,
+        .clk_source = I2C_CLK_SRC_DEFAULT,
+        .glitch_ignore_cnt = 7,
+        .intr_priority = 0,
+        .trans_queue_depth = 0,  /* Synchronous: a call returns with its transaction done */
+        .flags = {
+            .enable_internal_pullup = true,
+        },
+    };
+
+    esp_err_t ret = i2c_new_master_bus(&bus_cfg, &s_bus);
+    if (ret != ESP_OK) {
+        ESP_LOGE(TAG, "I2C bus init failed: %s", esp_err_to_name(ret));
+        s_bus = NULL;
+        return ret;
+    }
+
+    ESP_LOGI(TAG, "I2C bus initialized (SDA=%d, SCL=%d)", sda_pin, scl_pin);
+    return ESP_OK;
+}
+
+bool hal_i2c_is_ready(void) {
+    return s_bus != NULL;
+}
+
+i2c_master_bus_handle_t hal_i2c_bus(void) {
+    return s_bus;
+}
+
+esp_err_t hal_i2c_probe(uint8_t addr) {
+    if (s_bus == NULL) {
+        return ESP_ERR_INVALID_STATE;
+    }
+    return i2c_master_probe(s_bus, addr, HAL_I2C_TIMEOUT_MS);
+}
+
+esp_err_t hal_i2c_add_device(uint8_t addr, uint32_t scl_hz, hal_i2c_dev_t *dev) {
+    if (dev == NULL) {
+        return ESP_ERR_INVALID_ARG;
+    }
+    *dev = NULL;
+    if (s_bus == NULL) {
+        return ESP_ERR_INVALID_STATE;
+    }
+
+    i2c_device_config_t dev_cfg = {
+        .dev_addr_length = I2C_ADDR_BIT_LEN_7,
+        .device_address = addr,
+        .scl_speed_hz = scl_hz,
+    };
+    i2c_master_dev_handle_t handle = NULL;
+    esp_err_t err = i2c_master_bus_add_device(s_bus, &dev_cfg, &handle);
+    if (err == ESP_OK) {
+        *dev = (hal_i2c_dev_t)handle;
+    }
+    return err;
+}
+
+void hal_i2c_remove_device(hal_i2c_dev_t dev) {
+    if (dev != NULL) {
+        i2c_master_bus_rm_device((i2c_master_dev_handle_t)dev);
+    }
+}
+
+esp_err_t hal_i2c_write(hal_i2c_dev_t dev, const uint8_t *data, size_t len) {
+    if (dev == NULL || data == NULL) {
+        return ESP_ERR_INVALID_ARG;
+    }
+    if (len > HAL_I2C_XFER_MAX) {
+        return ESP_ERR_INVALID_SIZE;
+    }
+    return count_error(i2c_master_transmit((i2c_master_dev_handle_t)dev, data, len,
+                                           HAL_I2C_TIMEOUT_MS));
+}
+
+esp_err_t hal_i2c_read(hal_i2c_dev_t dev, uint8_t *data, size_t len) {
+    if (dev == NULL || data == NULL) {
+        return ESP_ERR_INVALID_ARG;
+    }
+    if (len > HAL_I2C_XFER_MAX) {
+        return ESP_ERR_INVALID_SIZE;
+    }
+    return count_error(i2c_master_receive((i2c_master_dev_handle_t)dev, data, len,
+                                          HAL_I2C_TIMEOUT_MS));
+}
+
+esp_err_t hal_i2c_write_read(hal_i2c_dev_t dev, const uint8_t *out, size_t out_len,
+                             uint8_t *in, size_t in_len) {
+    if (dev == NULL || out == NULL || in == NULL) {
+        return ESP_ERR_INVALID_ARG;
+    }
+    if (out_len > HAL_I2C_XFER_MAX || in_len > HAL_I2C_XFER_MAX) {
+        return ESP_ERR_INVALID_SIZE;
+    }
+    return count_error(i2c_master_transmit_receive((i2c_master_dev_handle_t)dev, out, out_len,
+                                                   in, in_len, HAL_I2C_TIMEOUT_MS));
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static bool s_ready = false;
+
+esp_err_t hal_i2c_init(int sda_pin, int scl_pin) {
+    (void)sda_pin;
+    (void)scl_pin;
+    s_ready = true;
+    return ESP_OK;
+}
+
+bool hal_i2c_is_ready(void) {
+    return s_ready;
+}
+
+esp_err_t hal_i2c_probe(uint8_t addr) {
+    (void)addr;
+    return s_ready ? ESP_ERR_NOT_FOUND : ESP_ERR_INVALID_STATE;
+}
+
+esp_err_t hal_i2c_add_device(uint8_t addr, uint32_t scl_hz, hal_i2c_dev_t *dev) {
+    (void)addr;
+    (void)scl_hz;
+    if (dev != NULL) {
+        *dev = NULL;
+    }
+    return ESP_ERR_NOT_SUPPORTED;
+}
+
+void hal_i2c_remove_device(hal_i2c_dev_t dev) {
+    (void)dev;
+}
+
+esp_err_t hal_i2c_write(hal_i2c_dev_t dev, const uint8_t *data, size_t len) {
+    (void)dev;
+    (void)data;
+    (void)len;
+    return ESP_ERR_NOT_SUPPORTED;
+}
+
+esp_err_t hal_i2c_read(hal_i2c_dev_t dev, uint8_t *data, size_t len) {
+    (void)dev;
+    (void)data;
+    (void)len;
+    return ESP_ERR_NOT_SUPPORTED;
+}
+
+esp_err_t hal_i2c_write_read(hal_i2c_dev_t dev, const uint8_t *out, size_t out_len,
+                             uint8_t *in, size_t in_len) {
+    (void)dev;
+    (void)out;
+    (void)out_len;
+    (void)in;
+    (void)in_len;
+    return ESP_ERR_NOT_SUPPORTED;
+}
+
+#endif /* ESP_PLATFORM */
+
+uint32_t hal_i2c_error_count(void) {
+    return atomic_load_explicit(&s_errors, memory_order_relaxed);
+}
@@ -0,0 +1,47 @@
+/**
+ * @file hal_pot.c
+ * @brief Speed potentiometer HAL implementation
+ *
+ * ADC1 one-shot reads through hal_adc, 12 bits, full-scale attenuation
+ * so the wiper can swing rail to rail.
+ */
+
+#include "hal_pot.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "hal_adc.h"
+
+static hal_adc_input_t s_input = HAL_ADC_INPUT_NONE;
+
+bool hal_pot_init(uint8_t pin) {
+    return hal_adc_input_init(&s_input, pin, "Speed pot");
+}
+
+int hal_pot_read(void) {
+    return hal_adc_read_raw(&s_input);
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static bool s_fitted = false;
+static int s_raw = 0;
+
+bool hal_pot_init(uint8_t pin) {
+    s_fitted = (pin != 0);
+    return s_fitted;
+}
+
+int hal_pot_read(void) {
+    return s_fitted ? s_raw : -1;
+}
+
+/* Test helper */
+void hal_pot_test_set(int raw) {
+    s_raw = raw;
+}
+
+#endif /* ESP_PLATFORM */
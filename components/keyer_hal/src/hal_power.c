@@ -0,0 +1,116 @@
+/**
+ * @file hal_power.c
+ * @brief Light sleep HAL implementation
+ */
+
+#include "hal_power.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "sdkconfig.h"
+#include "esp_log.h"
+#include "esp_pm.h"
+#include "esp_sleep.h"
+#include "soc/soc_caps.h"
+
+static const char *TAG = "hal_power";
+
+#if CONFIG_PM_ENABLE && CONFIG_FREERTOS_USE_TICKLESS_IDLE
+
+static esp_pm_lock_handle_t s_awake = NULL;
+static bool s_sleep_allowed = false;
+
+bool hal_power_init(void) {
+    esp_pm_config_t pm = {
+        .max_freq_mhz = CONFIG_ESP_DEFAULT_CPU_FREQ_MHZ,
+        .min_freq_mhz = CONFIG_ESP_DEFAULT_CPU_FREQ_MHZ,
+        .light_sleep_enable = true,
+    };
+    esp_err_t err = esp_pm_lock_create(ESP_PM_NO_LIGHT_SLEEP, 0, "keyer_awake", &s_awake);
+    if (err == ESP_OK) {
+        err = esp_pm_lock_acquire(s_awake);  /* Before sleep is enabled */
+    }
+    if (err == ESP_OK) {
+        err = esp_sleep_enable_gpio_wakeup();
+    }
+#if SOC_PM_SUPPORT_WIFI_WAKEUP
+    if (err == ESP_OK) {
+        err = esp_sleep_enable_wifi_wakeup();
+    }
+#endif
+    if (err == ESP_OK) {
+        err = esp_pm_configure(&pm);  /* Last: sleep is enabled from here */
+    }
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Light sleep unavailable: %s", esp_err_to_name(err));
+        if (s_awake != NULL) {
+            esp_pm_lock_release(s_awake);  /* INVALID_STATE if never taken */
+            esp_pm_lock_delete(s_awake);
+        }
+        s_awake = NULL;
+        return false;
+    }
+    ESP_LOGI(TAG, "Light sleep ready (CPU %d MHz)", CONFIG_ESP_DEFAULT_CPU_FREQ_MHZ);
+    return true;
+}
+
+bool hal_power_is_available(void) {
+    return s_awake != NULL;
+}
+
+void hal_power_allow_sleep(bool allow) {
+    if (s_awake == NULL || allow == s_sleep_allowed) {
+        return;
+    }
+    if (allow) {
+        esp_pm_lock_release(s_awake);
+    } else {
+        esp_pm_lock_acquire(s_awake);
+    }
+    s_sleep_allowed = allow;
+}
+
+#else
+
+bool hal_power_init(void) {
+    ESP_LOGW(TAG, "Light sleep needs CONFIG_PM_ENABLE and CONFIG_FREERTOS_USE_TICKLESS_IDLE");
+    return false;
+}
+
+bool hal_power_is_available(void) {
+    return false;
+}
+
+void hal_power_allow_sleep(bool allow) {
+    (void)allow;
+}
+
+#endif /* CONFIG_PM_ENABLE && CONFIG_FREERTOS_USE_TICKLESS_IDLE */
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static bool s_ready = false;
+static bool s_sleep_allowed = false;
+
+bool hal_power_init(void) {
+    s_ready = true;
+    return true;
+}
+
+bool hal_power_is_available(void) {
+    return s_ready;
+}
+
+void hal_power_allow_sleep(bool allow) {
+    s_sleep_allowed = s_ready && allow;
+}
+
+/* Test helper */
+bool hal_power_test_sleep_allowed(void) {
+    return s_sleep_allowed;
+}
+
+#endif /* ESP_PLATFORM */
@@ -0,0 +1,47 @@
+/**
+ * @file hal_supply.c
+ * @brief Supply voltage HAL implementation
+ *
+ * ADC1 one-shot reads through hal_adc, calibrated to millivolts. The
+ * divider ratio is applied by the caller (supply_monitor.h).
+ */
+
+#include "hal_supply.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "hal_adc.h"
+
+static hal_adc_input_t s_input = HAL_ADC_INPUT_NONE;
+
+bool hal_supply_init(uint8_t pin) {
+    return hal_adc_input_init(&s_input, pin, "Supply voltage");
+}
+
+int hal_supply_read_mv(void) {
+    return hal_adc_read_mv(&s_input);
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static bool s_fitted = false;
+static int s_mv = 0;
+
+bool hal_supply_init(uint8_t pin) {
+    s_fitted = (pin != 0);
+    return s_fitted;
+}
+
+int hal_supply_read_mv(void) {
+    return s_fitted ? s_mv : -1;
+}
+
+/* Test helper */
+void hal_supply_test_set(int mv) {
+    s_mv = mv;
+}
+
+#endif /* ESP_PLATFORM */
@@ -0,0 +1,64 @@
+/**
+ * @file hal_temp.c
+ * @brief Chip temperature sensor HAL implementation
+ *
+ * Measured range -10..80 °C, the most accurate one that still covers
+ * a keyer cooking in the sun next to a PA.
+ */
+
+#include "hal_temp.h"
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "driver/temperature_sensor.h"
+#include "esp_log.h"
+
+static const char *TAG = "hal_temp";
+
+static temperature_sensor_handle_t s_sensor = NULL;
+
+bool hal_temp_init(void) {
+    temperature_sensor_config_t conf = TEMPERATURE_SENSOR_CONFIG_DEFAULT(-10, 80);
+    esp_err_t err = temperature_sensor_install(&conf, &s_sensor);
+    if (err == ESP_OK) {
+        err = temperature_sensor_enable(s_sensor);
+    }
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to set up temperature sensor: %s", esp_err_to_name(err));
+        s_sensor = NULL;
+        return false;
+    }
+    return true;
+}
+
+bool hal_temp_read(float *celsius) {
+    return s_sensor != NULL && temperature_sensor_get_celsius(s_sensor, celsius) == ESP_OK;
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static bool s_ready = false;
+static float s_celsius = 25.0f;
+
+bool hal_temp_init(void) {
+    s_ready = true;
+    return true;
+}
+
+bool hal_temp_read(float *celsius) {
+    if (!s_ready) {
+        return false;
+    }
+    *celsius = s_celsius;
+    return true;
+}
+
+/* Test helper */
+void hal_temp_test_set(float celsius) {
+    s_celsius = celsius;
+}
+
+#endif /* ESP_PLATFORM */
@@ -0,0 +1,136 @@
+/**
+ * @file hal_touch.c
+ * @brief Capacitive touch paddle HAL implementation
+ *
+ * The touch FSM measures both channels continuously in the background;
+ * a read only fetches the last raw value, so polling from the RT loop
+ * costs a register read per pad.
+ */
+
+#include "hal_touch.h"
+#include "touch_paddle.h"
+
+/** Readings averaged for the untouched baseline */
+#define CALIBRATION_READS 8
+
+static touch_paddle_t s_dit;
+static touch_paddle_t s_dah;
+static bool s_ready = false;
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include "driver/touch_pad.h"
+#include "soc/soc_caps.h"  /* SOC_TOUCH_SENSOR_NUM */
+#include "freertos/FreeRTOS.h"
+#include "freertos/task.h"
+#include "esp_log.h"
+
+static const char *TAG = "hal_touch";
+
+static touch_pad_t s_dit_pad;
+static touch_pad_t s_dah_pad;
+
+/**
+ * @brief Touch channel behind a GPIO (GPIO n is channel n on the S2/S3)
+ */
+static bool pad_from_pin(uint8_t pin, touch_pad_t *pad) {
+    if (pin == 0 || pin >= SOC_TOUCH_SENSOR_NUM) {
+        return false;
+    }
+    *pad = (touch_pad_t)pin;
+    return true;
+}
+
+static uint32_t read_baseline(touch_pad_t pad) {
+    uint64_t sum = 0;
+    for (int i = 0; i < CALIBRATION_READS; i++) {
+        uint32_t raw = 0;
+        touch_pad_read_raw_data(pad, &raw);
+        sum += raw;
+        vTaskDelay(pdMS_TO_TICKS(5));
+    }
+    return (uint32_t)(sum / CALIBRATION_READS);
+}
+
+bool hal_touch_init(uint8_t dit_pin, uint8_t dah_pin, uint8_t sensitivity_pct) {
+    if (!pad_from_pin(dit_pin, &s_dit_pad) || !pad_from_pin(dah_pin, &s_dah_pad)) {
+        ESP_LOGE(TAG, "GPIO%d/GPIO%d are not both touch pins", dit_pin, dah_pin);
+        return false;
+    }
+
+    esp_err_t err = touch_pad_init();
+    if (err == ESP_OK) {
+        err = touch_pad_config(s_dit_pad);
+    }
+    if (err == ESP_OK) {
+        err = touch_pad_config(s_dah_pad);
+    }
+    if (err == ESP_OK) {
+        err = touch_pad_set_fsm_mode(TOUCH_FSM_MODE_TIMER);
+    }
+    if (err == ESP_OK) {
+        err = touch_pad_fsm_start();
+    }
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to set up touch sensor: %s", esp_err_to_name(err));
+        return false;
+    }
+
+    /* First measurements need a few FSM cycles */
+    vTaskDelay(pdMS_TO_TICKS(50));
+
+    touch_paddle_calibrate(&s_dit, read_baseline(s_dit_pad), sensitivity_pct);
+    touch_paddle_calibrate(&s_dah, read_baseline(s_dah_pad), sensitivity_pct);
+    s_ready = true;
+
+    ESP_LOGI(TAG, "Touch paddles: DIT GPIO%d base %lu, DAH GPIO%d base %lu, +%u%%",
+             dit_pin, (unsigned long)s_dit.baseline, dah_pin,
+             (unsigned long)s_dah.baseline, (unsigned)sensitivity_pct);
+    return true;
+}
+
+gpio_state_t hal_touch_read_paddles(void) {
+    if (!s_ready) {
+        return gpio_from_paddles(false, false);
+    }
+
+    uint32_t dit_raw = 0;
+    uint32_t dah_raw = 0;
+    touch_pad_read_raw_data(s_dit_pad, &dit_raw);
+    touch_pad_read_raw_data(s_dah_pad, &dah_raw);
+    return gpio_from_paddles(touch_paddle_update(&s_dit, dit_raw),
+                             touch_paddle_update(&s_dah, dah_raw));
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation
+ * ============================================================================ */
+
+static uint32_t s_dit_raw = 0;
+static uint32_t s_dah_raw = 0;
+
+bool hal_touch_init(uint8_t dit_pin, uint8_t dah_pin, uint8_t sensitivity_pct) {
+    (void)dit_pin;
+    (void)dah_pin;
+    touch_paddle_calibrate(&s_dit, s_dit_raw, sensitivity_pct);
+    touch_paddle_calibrate(&s_dah, s_dah_raw, sensitivity_pct);
+    s_ready = true;
+    return true;
+}
+
+gpio_state_t hal_touch_read_paddles(void) {
+    if (!s_ready) {
+        return gpio_from_paddles(false, false);
+    }
+    return gpio_from_paddles(touch_paddle_update(&s_dit, s_dit_raw),
+                             touch_paddle_update(&s_dah, s_dah_raw));
+}
+
+/* Test helper */
+void hal_touch_test_set(uint32_t dit_raw, uint32_t dah_raw) {
+    s_dit_raw = dit_raw;
+    s_dah_raw = dah_raw;
+}
+
+#endif /* ESP_PLATFORM */
@@ -12,6 +12,8 @@
  * @deprecated This preset system is deprecated in favor of unified g_config.
  *             Enum types (iambic_mode_t, memory_mode_t, squeeze_mode_t) still used.
  *             Future: NVS-backed presets integrated with g_config.
+ *
+ * Persistence lives outside this I/O-free component (preset_nvs.h).
  */
 
 #ifndef KEYER_IAMBIC_PRESET_H
@@ -19,6 +21,7 @@
 
 #include <stdint.h>
 #include <stdbool.h>
+#include <stddef.h>
 #include <stdatomic.h>
 
 #ifdef __cplusplus
@@ -35,8 +38,21 @@ extern "C" {
 /** Maximum preset name length (including null terminator) */
 #define IAMBIC_PRESET_NAME_MAX 32
 
-/** NVS schema version for migration support */
-#define IAMBIC_PRESET_SCHEMA_VERSION 1
+/**
+ * NVS schema version for migration support
+ *
+ * 1: values only, never persisted
+ * 2: names stored with values (one blob per slot)
+ * 3: adds weight and the optional sidetone / PTT tail overrides
+ *    (schema 2 slots are migrated on load)
+ */
+#define IAMBIC_PRESET_SCHEMA_VERSION 3
+
+/** Sidetone / PTT tail value meaning "keep the global setting" */
+#define IAMBIC_PRESET_NO_OVERRIDE 0
+
+/** Default dit/dah weight (50 = standard 1:3), same as keyer.weight */
+#define IAMBIC_PRESET_WEIGHT_DEFAULT 50
 
 /* ============================================================================
  * Enums
@@ -72,6 +88,22 @@ typedef enum {
  * Preset Structure
  * ============================================================================ */
 
+/**
+ * @brief Preset name, copied out without locks
+ *
+ * Double-buffered with a sequence count, like the config strings: a
+ * rename makes `seq` odd, fills the inactive buffer, publishes it by
+ * flipping `active`, then makes `seq` even again. Readers copy the name
+ * into their own buffer with iambic_preset_get_name() and retry if
+ * `seq` moved meanwhile, so no caller holds a pointer a rename reuses.
+ * Renames come from the console task only.
+ */
+typedef struct {
+    char buf[2][IAMBIC_PRESET_NAME_MAX];
+    atomic_uint_fast8_t active;
+    atomic_uint seq;
+} iambic_preset_name_t;
+
 /**
  * @brief Single iambic preset configuration
  *
@@ -88,7 +120,7 @@ typedef enum {
  * - After D%: Paddle input ignored (too late, next element)
  */
 typedef struct {
-    char name[IAMBIC_PRESET_NAME_MAX];  /**< User-defined name (empty = unused slot) */
+    iambic_preset_name_t name;          /**< User-defined name (empty = unused slot) */
 
     atomic_uint_fast32_t speed_wpm;     /**< Speed in WPM (5-100) */
     atomic_uint_fast8_t iambic_mode;    /**< iambic_mode_t value */
@@ -97,19 +129,26 @@ typedef struct {
 
     atomic_uint_fast8_t mem_window_start_pct;  /**< Memory window start (0-100%) */
     atomic_uint_fast8_t mem_window_end_pct;    /**< Memory window end (0-100%) */
+
+    atomic_uint_fast16_t sidetone_freq_hz;  /**< Sidetone override (400-800 Hz, 0 = none) */
+    atomic_uint_fast8_t weight;             /**< Dit/dah weight (33-67, 50 = standard) */
+    atomic_uint_fast16_t ptt_tail_ms;       /**< PTT tail override (50-500 ms, 0 = none) */
 } iambic_preset_t;
 
 /**
  * @brief Default preset values
  */
 #define IAMBIC_PRESET_DEFAULT { \
-    .name = "", \
+    .name = { .buf = { "", "" }, .active = 0, .seq = 0 }, \
     .speed_wpm = 25, \
     .iambic_mode = IAMBIC_MODE_B, \
     .memory_mode = MEMORY_MODE_DOT_AND_DAH, \
     .squeeze_mode = SQUEEZE_MODE_LATCH_OFF, \
     .mem_window_start_pct = 0, \
-    .mem_window_end_pct = 100 \
+    .mem_window_end_pct = 100, \
+    .sidetone_freq_hz = IAMBIC_PRESET_NO_OVERRIDE, \
+    .weight = IAMBIC_PRESET_WEIGHT_DEFAULT, \
+    .ptt_tail_ms = IAMBIC_PRESET_NO_OVERRIDE \
 }
 
 /* ============================================================================
@@ -200,6 +239,53 @@ bool iambic_preset_reset(uint32_t index);
  */
 bool iambic_preset_set_name(uint32_t index, const char* name);
 
+/**
+ * @brief Copy a preset's name (empty = unused slot)
+ *
+ * Never waits: a rename in progress only writes the buffer not being
+ * read, and a copy overlapping a completed rename is retried.
+ *
+ * @param preset Preset
+ * @param buf Output buffer (IAMBIC_PRESET_NAME_MAX holds any name)
+ * @param len Buffer size (truncated to fit)
+ */
+void iambic_preset_get_name(const iambic_preset_t* preset, char* buf, size_t len);
+
+/* ============================================================================
+ * Share Codes
+ * ============================================================================ */
+
+/** Share code buffer size: base64url of 42 bytes + terminator */
+#define IAMBIC_PRESET_CODE_MAX 57
+
+/**
+ * @brief Encode a preset as a short printable share code
+ *
+ * Packs name, speed, modes, memory window, weight and the sidetone / PTT
+ * tail overrides with a format version and CRC-8, then base64url-encodes
+ * it (no padding), e.g. "AiMNPGMyAAAAAENvbnRlc3..".
+ * The code can be pasted on another unit with iambic_preset_import().
+ *
+ * @param index Preset index (0-9)
+ * @param buf Output buffer (IAMBIC_PRESET_CODE_MAX is enough)
+ * @param len Buffer size
+ * @return Code length, or 0 if index invalid or buffer too small
+ */
+size_t iambic_preset_export(uint32_t index, char* buf, size_t len);
+
+/**
+ * @brief Overwrite a preset from a share code
+ *
+ * Nothing is changed unless the whole code is valid (format, CRC,
+ * value ranges, memory window start below its end). Codes from format 1 (before weight and overrides) are
+ * accepted and get the standard weight and no overrides.
+ *
+ * @param index Preset index (0-9)
+ * @param code Code from iambic_preset_export()
+ * @return true if imported
+ */
+bool iambic_preset_import(uint32_t index, const char* code);
+
 /* ============================================================================
  * Preset Value Accessors (RT-safe)
  * ============================================================================ */
@@ -246,6 +332,27 @@ static inline uint8_t iambic_preset_get_mem_end(const iambic_preset_t* preset) {
     return (uint8_t)atomic_load_explicit(&preset->mem_window_end_pct, memory_order_relaxed);
 }
 
+/**
+ * @brief Get sidetone override in Hz (IAMBIC_PRESET_NO_OVERRIDE if none)
+ */
+static inline uint16_t iambic_preset_get_sidetone(const iambic_preset_t* preset) {
+    return (uint16_t)atomic_load_explicit(&preset->sidetone_freq_hz, memory_order_relaxed);
+}
+
+/**
+ * @brief Get dit/dah weight
+ */
+static inline uint8_t iambic_preset_get_weight(const iambic_preset_t* preset) {
+    return (uint8_t)atomic_load_explicit(&preset->weight, memory_order_relaxed);
+}
+
+/**
+ * @brief Get PTT tail override in ms (IAMBIC_PRESET_NO_OVERRIDE if none)
+ */
+static inline uint16_t iambic_preset_get_ptt_tail(const iambic_preset_t* preset) {
+    return (uint16_t)atomic_load_explicit(&preset->ptt_tail_ms, memory_order_relaxed);
+}
+
 /* ============================================================================
  * Preset Value Setters
  * ============================================================================ */
@@ -298,6 +405,33 @@ static inline void iambic_preset_set_mem_end(iambic_preset_t* preset, uint8_t pc
     }
 }
 
+/**
+ * @brief Set sidetone override in Hz (400-800, or IAMBIC_PRESET_NO_OVERRIDE)
+ */
+static inline void iambic_preset_set_sidetone(iambic_preset_t* preset, uint16_t hz) {
+    if (hz == IAMBIC_PRESET_NO_OVERRIDE || (hz >= 400 && hz <= 800)) {
+        atomic_store_explicit(&preset->sidetone_freq_hz, hz, memory_order_relaxed);
+    }
+}
+
+/**
+ * @brief Set dit/dah weight
+ */
+static inline void iambic_preset_set_weight(iambic_preset_t* preset, uint8_t weight) {
+    if (weight >= 33 && weight <= 67) {
+        atomic_store_explicit(&preset->weight, weight, memory_order_relaxed);
+    }
+}
+
+/**
+ * @brief Set PTT tail override in ms (50-500, or IAMBIC_PRESET_NO_OVERRIDE)
+ */
+static inline void iambic_preset_set_ptt_tail(iambic_preset_t* preset, uint16_t ms) {
+    if (ms == IAMBIC_PRESET_NO_OVERRIDE || (ms >= 50 && ms <= 500)) {
+        atomic_store_explicit(&preset->ptt_tail_ms, ms, memory_order_relaxed);
+    }
+}
+
 /* ============================================================================
  * Timing Calculation Helpers
  * ============================================================================ */
@@ -0,0 +1,71 @@
+/**
+ * @file iambic_stress.h
+ * @brief Stress run of the keying path: FSM → stream → consumers
+ *
+ * Feeds stress_next() input (stress.h) through the iambic FSM into a
+ * KeyingStream read by a hard RT consumer (ticked every 1 ms, like
+ * rt_task) and a best-effort consumer (drained every 10 ms, like
+ * bg_task). Injected stalls hold both consumers back. Hard RT faults are
+ * recovered the way rt_task does (clear, resync) and checked against
+ * the stalls that should explain them.
+ *
+ * Runs on simulated time as fast as the CPU allows: host tests and the
+ * `test stress` console command use the same code. No allocation; the
+ * caller owns the stream buffer.
+ */
+
+#ifndef KEYER_IAMBIC_STRESS_H
+#define KEYER_IAMBIC_STRESS_H
+
+#include <stdint.h>
+#include <stddef.h>
+#include "stress.h"
+#include "stream.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Outcome of a stress run
+ */
+typedef struct {
+    uint32_t ticks;             /**< Ticks run */
+    uint32_t samples;           /**< Stream slots written */
+    uint32_t key_edges;         /**< Key output changes */
+    uint32_t speed_changes;     /**< Speed changes applied */
+    uint32_t stalls;            /**< Consumer stalls injected */
+    uint32_t faults_latency;    /**< Hard RT lag over max_lag */
+    uint32_t faults_overrun;    /**< Hard RT consumer lapped by the producer */
+    uint32_t faults_unexpected; /**< Faults with no stall since the last resync */
+    uint32_t recoveries;        /**< Fault clear + resync */
+    uint32_t key_mismatches;    /**< Caught-up hard RT output differing from the FSM */
+    uint32_t best_effort_dropped; /**< Samples skipped by the best-effort consumer */
+} stress_report_t;
+
+/**
+ * @brief Run the keying path under stress
+ *
+ * @param config Stress input (seed, speeds, stalls)
+ * @param ticks Run length in 1 ms ticks
+ * @param stream Stream to use (initialized by the caller, reused from the start)
+ * @param max_lag Hard RT consumer lag limit (> 0)
+ * @param report Filled in
+ */
+void iambic_stress_run(const stress_config_t *config, uint32_t ticks,
+                       keying_stream_t *stream, size_t max_lag,
+                       stress_report_t *report);
+
+/**
+ * @brief Whether a report shows only explained faults and consistent output
+ */
+static inline bool iambic_stress_ok(const stress_report_t *report) {
+    return report->faults_unexpected == 0 && report->key_mismatches == 0 &&
+           report->recoveries == report->faults_latency + report->faults_overrun;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_IAMBIC_STRESS_H */
@@ -43,6 +43,22 @@ static const uint32_t DEFAULT_PRESET_WPM[IAMBIC_PRESET_COUNT] = {
  */
 iambic_preset_system_t g_iambic_presets;
 
+/**
+ * @brief Publish a new name (truncated to IAMBIC_PRESET_NAME_MAX - 1)
+ */
+static void preset_name_store(iambic_preset_name_t* name, const char* value) {
+    unsigned seq = atomic_load_explicit(&name->seq, memory_order_relaxed);
+    atomic_store_explicit(&name->seq, seq + 1U, memory_order_relaxed);
+    /* Odd seq is visible before the buffer changes */
+    atomic_thread_fence(memory_order_release);
+
+    uint_fast8_t next = (atomic_load_explicit(&name->active, memory_order_relaxed) + 1U) & 1U;
+    strncpy(name->buf[next], value, IAMBIC_PRESET_NAME_MAX - 1);
+    name->buf[next][IAMBIC_PRESET_NAME_MAX - 1] = '\0';
+    atomic_store_explicit(&name->active, next, memory_order_release);
+    atomic_store_explicit(&name->seq, seq + 2U, memory_order_release);
+}
+
 /* ============================================================================
  * Initialization
  * ============================================================================ */
@@ -52,8 +68,7 @@ void iambic_preset_init(void) {
         iambic_preset_t* preset = &g_iambic_presets.presets[i];
 
         /* Set name */
-        strncpy(preset->name, DEFAULT_PRESET_NAMES[i], IAMBIC_PRESET_NAME_MAX - 1);
-        preset->name[IAMBIC_PRESET_NAME_MAX - 1] = '\0';
+        preset_name_store(&preset->name, DEFAULT_PRESET_NAMES[i]);
 
         /* Set speed */
         atomic_store_explicit(&preset->speed_wpm, DEFAULT_PRESET_WPM[i], memory_order_relaxed);
@@ -66,6 +81,11 @@ void iambic_preset_init(void) {
         /* Set default memory window (0% - 100% = full window, compatible with legacy) */
         atomic_store_explicit(&preset->mem_window_start_pct, 0, memory_order_relaxed);
         atomic_store_explicit(&preset->mem_window_end_pct, 100, memory_order_relaxed);
+
+        /* Standard weight, sidetone and PTT tail follow the global config */
+        atomic_store_explicit(&preset->sidetone_freq_hz, IAMBIC_PRESET_NO_OVERRIDE, memory_order_relaxed);
+        atomic_store_explicit(&preset->weight, IAMBIC_PRESET_WEIGHT_DEFAULT, memory_order_relaxed);
+        atomic_store_explicit(&preset->ptt_tail_ms, IAMBIC_PRESET_NO_OVERRIDE, memory_order_relaxed);
     }
 
     /* Start with first preset active */
@@ -126,7 +146,9 @@ bool iambic_preset_copy(uint32_t src_index, uint32_t dst_index) {
     iambic_preset_t* dst = &g_iambic_presets.presets[dst_index];
 
     /* Copy name */
-    strncpy(dst->name, src->name, IAMBIC_PRESET_NAME_MAX);
+    char name[IAMBIC_PRESET_NAME_MAX];
+    iambic_preset_get_name(src, name, sizeof(name));
+    preset_name_store(&dst->name, name);
 
     /* Copy atomic values */
     atomic_store_explicit(&dst->speed_wpm,
@@ -153,6 +175,18 @@ bool iambic_preset_copy(uint32_t src_index, uint32_t dst_index) {
         atomic_load_explicit(&src->mem_window_end_pct, memory_order_relaxed),
         memory_order_relaxed);
 
+    atomic_store_explicit(&dst->sidetone_freq_hz,
+        atomic_load_explicit(&src->sidetone_freq_hz, memory_order_relaxed),
+        memory_order_relaxed);
+
+    atomic_store_explicit(&dst->weight,
+        atomic_load_explicit(&src->weight, memory_order_relaxed),
+        memory_order_relaxed);
+
+    atomic_store_explicit(&dst->ptt_tail_ms,
+        atomic_load_explicit(&src->ptt_tail_ms, memory_order_relaxed),
+        memory_order_relaxed);
+
     return true;
 }
 
@@ -164,8 +198,7 @@ bool iambic_preset_reset(uint32_t index) {
     iambic_preset_t* preset = &g_iambic_presets.presets[index];
 
     /* Reset name */
-    strncpy(preset->name, DEFAULT_PRESET_NAMES[index], IAMBIC_PRESET_NAME_MAX - 1);
-    preset->name[IAMBIC_PRESET_NAME_MAX - 1] = '\0';
+    preset_name_store(&preset->name, DEFAULT_PRESET_NAMES[index]);
 
     /* Reset values to defaults */
     atomic_store_explicit(&preset->speed_wpm, DEFAULT_PRESET_WPM[index], memory_order_relaxed);
@@ -174,6 +207,9 @@ bool iambic_preset_reset(uint32_t index) {
     atomic_store_explicit(&preset->squeeze_mode, SQUEEZE_MODE_LATCH_ON, memory_order_relaxed);
     atomic_store_explicit(&preset->mem_window_start_pct, 60, memory_order_relaxed);
     atomic_store_explicit(&preset->mem_window_end_pct, 99, memory_order_relaxed);
+    atomic_store_explicit(&preset->sidetone_freq_hz, IAMBIC_PRESET_NO_OVERRIDE, memory_order_relaxed);
+    atomic_store_explicit(&preset->weight, IAMBIC_PRESET_WEIGHT_DEFAULT, memory_order_relaxed);
+    atomic_store_explicit(&preset->ptt_tail_ms, IAMBIC_PRESET_NO_OVERRIDE, memory_order_relaxed);
 
     return true;
 }
@@ -183,9 +219,197 @@ bool iambic_preset_set_name(uint32_t index, const char* name) {
         return false;
     }
 
-    iambic_preset_t* preset = &g_iambic_presets.presets[index];
-    strncpy(preset->name, name, IAMBIC_PRESET_NAME_MAX - 1);
-    preset->name[IAMBIC_PRESET_NAME_MAX - 1] = '\0';
+    preset_name_store(&g_iambic_presets.presets[index].name, name);
+
+    return true;
+}
+
+void iambic_preset_get_name(const iambic_preset_t* preset, char* buf, size_t len) {
+    if (preset == NULL || buf == NULL || len == 0) {
+        return;
+    }
+
+    size_t n = (len < IAMBIC_PRESET_NAME_MAX) ? len : IAMBIC_PRESET_NAME_MAX;
+    unsigned before;
+    do {
+        before = atomic_load_explicit(&preset->name.seq, memory_order_acquire);
+        uint_fast8_t idx = atomic_load_explicit(&preset->name.active, memory_order_acquire) & 1U;
+        memcpy(buf, preset->name.buf[idx], n);
+        atomic_thread_fence(memory_order_acquire);
+    } while (atomic_load_explicit(&preset->name.seq, memory_order_relaxed) != before);
+    buf[n - 1] = '\0';
+}
+
+/* ============================================================================
+ * Share Codes
+ * ============================================================================ */
+
+/** Share code format version (first packed byte) */
+#define CODE_FORMAT 2
+
+/** Format 1 (no weight or overrides), still accepted on import */
+#define CODE_FORMAT_V1 1
+
+/** Format 1 header: format, wpm, modes, window start, window end */
+#define CODE_HEADER_LEN_V1 5
+
+/** Packed header: format 1 header, weight, sidetone, PTT tail (big-endian) */
+#define CODE_HEADER_LEN 10
+
+/** Longest packed code: header + name + CRC */
+#define CODE_PACKED_MAX (CODE_HEADER_LEN + IAMBIC_PRESET_NAME_MAX - 1 + 1)
+
+static const char BASE64URL[] =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/**
+ * @brief CRC-8 (poly 0x07)
+ */
+static uint8_t code_crc8(const uint8_t* data, size_t len) {
+    uint8_t crc = 0;
+    for (size_t i = 0; i < len; i++) {
+        crc ^= data[i];
+        for (int bit = 0; bit < 8; bit++) {
+            unsigned shifted = (unsigned)crc << 1;
+            crc = (uint8_t)((crc & 0x80U) ? (shifted ^ 0x07U) : shifted);
+        }
+    }
+    return crc;
+}
+
+static int base64url_value(char c) {
+    const char* p = strchr(BASE64URL, c);
+    return (c != '\0' && p != NULL) ? (int)(p - BASE64URL) : -1;
+}
+
+size_t iambic_preset_export(uint32_t index, char* buf, size_t len) {
+    const iambic_preset_t* preset = iambic_preset_get(index);
+    if (preset == NULL || buf == NULL) {
+        return 0;
+    }
+
+    uint8_t packed[CODE_PACKED_MAX];
+    char name[IAMBIC_PRESET_NAME_MAX];
+    iambic_preset_get_name(preset, name, sizeof(name));
+    size_t name_len = strlen(name);
+
+    packed[0] = CODE_FORMAT;
+    packed[1] = (uint8_t)iambic_preset_get_wpm(preset);
+    packed[2] = (uint8_t)(((uint32_t)iambic_preset_get_mode(preset) & 0x01U) |
+                          (((uint32_t)iambic_preset_get_memory_mode(preset) & 0x03U) << 1) |
+                          (((uint32_t)iambic_preset_get_squeeze_mode(preset) & 0x01U) << 3));
+    packed[3] = iambic_preset_get_mem_start(preset);
+    packed[4] = iambic_preset_get_mem_end(preset);
+    packed[5] = iambic_preset_get_weight(preset);
+    packed[6] = (uint8_t)(iambic_preset_get_sidetone(preset) >> 8);
+    packed[7] = (uint8_t)(iambic_preset_get_sidetone(preset) & 0xFFU);
+    packed[8] = (uint8_t)(iambic_preset_get_ptt_tail(preset) >> 8);
+    packed[9] = (uint8_t)(iambic_preset_get_ptt_tail(preset) & 0xFFU);
+    memcpy(&packed[CODE_HEADER_LEN], name, name_len);
+    size_t n = CODE_HEADER_LEN + name_len;
+    packed[n] = code_crc8(packed, n);
+    n++;
+
+    size_t out_len = (n * 8U + 5U) / 6U;
+    if (len < out_len + 1U) {
+        return 0;
+    }
+
+    uint32_t acc = 0;
+    unsigned bits = 0;
+    size_t pos = 0;
+    for (size_t i = 0; i < n; i++) {
+        acc = (acc << 8) | packed[i];
+        bits += 8U;
+        while (bits >= 6U) {
+            bits -= 6U;
+            buf[pos++] = BASE64URL[(acc >> bits) & 0x3FU];
+        }
+    }
+    if (bits > 0U) {
+        buf[pos++] = BASE64URL[(acc << (6U - bits)) & 0x3FU];
+    }
+    buf[pos] = '\0';
+    return pos;
+}
+
+bool iambic_preset_import(uint32_t index, const char* code) {
+    iambic_preset_t* preset = iambic_preset_get_mut(index);
+    if (preset == NULL || code == NULL) {
+        return false;
+    }
+
+    uint8_t packed[CODE_PACKED_MAX];
+    size_t n = 0;
+    uint32_t acc = 0;
+    unsigned bits = 0;
+    for (const char* c = code; *c != '\0'; c++) {
+        int v = base64url_value(*c);
+        if (v < 0) {
+            return false;
+        }
+        acc = (acc << 6) | (uint32_t)v;
+        bits += 6U;
+        if (bits >= 8U) {
+            bits -= 8U;
+            if (n >= CODE_PACKED_MAX) {
+                return false;
+            }
+            packed[n++] = (uint8_t)(acc >> bits);
+        }
+    }
+
+    /* Header + CRC at least, CRC over everything before it */
+    size_t header_len = (n > 0U && packed[0] == CODE_FORMAT_V1) ? CODE_HEADER_LEN_V1
+                                                                 : CODE_HEADER_LEN;
+    if (n < header_len + 1U ||
+        (packed[0] != CODE_FORMAT && packed[0] != CODE_FORMAT_V1) ||
+        code_crc8(packed, n - 1U) != packed[n - 1U]) {
+        return false;
+    }
 
+    uint8_t wpm = packed[1];
+    uint8_t modes = packed[2];
+    uint8_t start = packed[3];
+    uint8_t end = packed[4];
+    /* Same rule as keyer.mem_window_start_pct < keyer.mem_window_end_pct */
+    if (wpm < 5U || wpm > 100U || modes > 0x0FU || start > 100U || end > 100U ||
+        start >= end) {
+        return false;
+    }
+
+    /* Format 1 codes predate these: standard weight, no overrides */
+    uint8_t weight = IAMBIC_PRESET_WEIGHT_DEFAULT;
+    uint16_t sidetone = IAMBIC_PRESET_NO_OVERRIDE;
+    uint16_t ptt_tail = IAMBIC_PRESET_NO_OVERRIDE;
+    if (packed[0] == CODE_FORMAT) {
+        weight = packed[5];
+        sidetone = (uint16_t)(((unsigned)packed[6] << 8) | packed[7]);
+        ptt_tail = (uint16_t)(((unsigned)packed[8] << 8) | packed[9]);
+    }
+    if (weight < 33U || weight > 67U ||
+        (sidetone != IAMBIC_PRESET_NO_OVERRIDE && (sidetone < 400U || sidetone > 800U)) ||
+        (ptt_tail != IAMBIC_PRESET_NO_OVERRIDE && (ptt_tail < 50U || ptt_tail > 500U))) {
+        return false;
+    }
+
+    char name[IAMBIC_PRESET_NAME_MAX];
+    size_t name_len = n - 1U - header_len;
+    if (name_len >= sizeof(name)) {
+        return false;
+    }
+    memcpy(name, &packed[header_len], name_len);
+    name[name_len] = '\0';
+
+    preset_name_store(&preset->name, name);
+    iambic_preset_set_wpm(preset, wpm);
+    iambic_preset_set_mode(preset, (iambic_mode_t)(modes & 0x01U));
+    iambic_preset_set_memory_mode(preset, (memory_mode_t)((modes >> 1) & 0x03U));
+    iambic_preset_set_squeeze_mode(preset, (squeeze_mode_t)((modes >> 3) & 0x01U));
+    iambic_preset_set_mem_start(preset, start);
+    iambic_preset_set_mem_end(preset, end);
+    iambic_preset_set_weight(preset, weight);
+    iambic_preset_set_sidetone(preset, sidetone);
+    iambic_preset_set_ptt_tail(preset, ptt_tail);
     return true;
 }
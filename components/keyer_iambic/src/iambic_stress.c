@@ -0,0 +1,104 @@
+/**
+ * @file iambic_stress.c
+ * @brief Stress run implementation
+ */
+
+#include "iambic_stress.h"
+#include "iambic.h"
+#include "consumer.h"
+#include "fault.h"
+#include <string.h>
+
+/** Best-effort consumer period (bg_task) */
+#define STRESS_BEST_EFFORT_PERIOD 10
+
+void iambic_stress_run(const stress_config_t *config, uint32_t ticks,
+                       keying_stream_t *stream, size_t max_lag,
+                       stress_report_t *report) {
+    memset(report, 0, sizeof(*report));
+
+    stress_gen_t gen;
+    stress_init(&gen, config);
+
+    iambic_config_t keyer = IAMBIC_CONFIG_DEFAULT;
+    iambic_processor_t proc;
+    iambic_init(&proc, &keyer);
+
+    fault_state_t fault;
+    fault_init(&fault);
+    stream_init(stream, stream->buffer, stream->capacity);
+
+    hard_rt_consumer_t hard_rt;
+    hard_rt_consumer_init(&hard_rt, stream, &fault, max_lag);
+    best_effort_consumer_t best_effort;
+    best_effort_consumer_init(&best_effort, stream, max_lag * 4U);
+
+    uint32_t stall_left = 0;
+    bool stalled_since_resync = false;
+    bool fsm_key = false;
+    bool out_key = false;
+
+    for (uint32_t t = 0; t < ticks; t++) {
+        stress_event_t event;
+        stress_next(&gen, &event);
+
+        if (event.wpm != 0) {
+            keyer.wpm = event.wpm;
+            iambic_set_config(&proc, &keyer);
+            report->speed_changes++;
+        }
+        if (event.stall_ticks > stall_left) {
+            stall_left = event.stall_ticks;
+            stalled_since_resync = true;
+            report->stalls++;
+        }
+
+        int64_t now_us = (int64_t)t * 1000;
+        stream_sample_t sample = iambic_tick(&proc, now_us, event.paddles);
+        size_t before = stream_write_position(stream);
+        stream_push(stream, sample);
+        report->samples += (uint32_t)(stream_write_position(stream) - before);
+        if ((sample.local_key != 0) != fsm_key) {
+            fsm_key = sample.local_key != 0;
+            report->key_edges++;
+        }
+
+        if (stall_left > 0) {
+            stall_left--;
+            continue;
+        }
+
+        /* Hard RT consumer: one sample per tick, as rt_task */
+        stream_sample_t out;
+        hard_rt_result_t result = hard_rt_consumer_tick(&hard_rt, &out);
+        if (result == HARD_RT_OK && !sample_is_silence(&out)) {
+            out_key = out.local_key != 0;
+        } else if (result == HARD_RT_FAULT) {
+            if (fault_get_code(&fault) == FAULT_OVERRUN) {
+                report->faults_overrun++;
+            } else {
+                report->faults_latency++;
+            }
+            if (!stalled_since_resync) {
+                report->faults_unexpected++;
+            }
+            fault_clear(&fault);
+            hard_rt_consumer_resync(&hard_rt);
+            out_key = fsm_key;
+            stalled_since_resync = false;
+            report->recoveries++;
+        }
+        if (hard_rt_consumer_lag(&hard_rt) == 0 && out_key != fsm_key) {
+            report->key_mismatches++;
+        }
+
+        /* Best-effort consumer: drained every bg_task period */
+        if (t % STRESS_BEST_EFFORT_PERIOD == 0) {
+            while (best_effort_consumer_tick(&best_effort, &out)) {
+            }
+        }
+    }
+
+    report->ticks = ticks;
+    report->best_effort_dropped = (uint32_t)best_effort_consumer_dropped(&best_effort);
+}
@@ -8,6 +8,7 @@
  * - AP mode: alternating orange/blue
  * - Degraded: dim yellow
  * - Keying: DIT/DAH/squeeze indication
+ * - Fault blink code: over the state, first LED (warning) or all (critical)
  */
 
 #ifndef KEYER_LED_H
@@ -36,6 +37,15 @@ typedef enum {
     LED_STATE_IDLE,             /**< Dim green steady */
 } led_state_t;
 
+/**
+ * @brief Fault overlay level
+ */
+typedef enum {
+    LED_FAULT_NONE = 0,         /**< No overlay */
+    LED_FAULT_WARNING,          /**< Yellow blink on the first LED */
+    LED_FAULT_CRITICAL,         /**< Red blink on all LEDs (TX stopped) */
+} led_fault_t;
+
 /**
  * @brief LED configuration
  */
@@ -98,6 +108,17 @@ led_state_t led_get_state(void);
  */
 void led_tick(int64_t now_us, bool dit, bool dah);
 
+/**
+ * @brief Set the fault overlay
+ *
+ * Drawn over every state by the next led_tick(). The caller times the
+ * blink code and passes the current phase.
+ *
+ * @param fault Overlay level
+ * @param lit Blink phase: true while the fault LEDs are on
+ */
+void led_set_fault(led_fault_t fault, bool lit);
+
 /**
  * @brief Update brightness from config
  *
@@ -63,6 +63,8 @@ static struct {
     int64_t state_start_us;
     _Atomic uint8_t brightness;
     _Atomic uint8_t brightness_dim;
+    _Atomic led_fault_t fault;
+    _Atomic bool fault_lit;
 } s_led;
 
 /* Forward declarations */
@@ -267,6 +269,24 @@ static void transmit_leds(void)
     }
 }
 
+/**
+ * @brief Draw the fault overlay over the rendered state
+ */
+static void draw_fault(uint8_t brightness)
+{
+    led_fault_t fault = atomic_load_explicit(&s_led.fault, memory_order_relaxed);
+    if (fault == LED_FAULT_NONE) {
+        return;
+    }
+
+    bool lit = atomic_load_explicit(&s_led.fault_lit, memory_order_relaxed);
+    if (fault == LED_FAULT_CRITICAL) {
+        set_all_leds(lit ? apply_brightness(COLOR_RED, brightness) : COLOR_OFF);
+    } else {
+        set_led(0, lit ? apply_brightness(COLOR_YELLOW, brightness) : COLOR_OFF);
+    }
+}
+
 /**
  * @brief Calculate breathing brightness (triangle wave)
  */
@@ -510,9 +530,16 @@ void led_tick(int64_t now_us, bool dit, bool dah)
         break;
     }
 
+    draw_fault(brightness);
     transmit_leds();
 }
 
+void led_set_fault(led_fault_t fault, bool lit)
+{
+    atomic_store_explicit(&s_led.fault, fault, memory_order_relaxed);
+    atomic_store_explicit(&s_led.fault_lit, lit, memory_order_relaxed);
+}
+
 void led_set_brightness(uint8_t brightness, uint8_t brightness_dim)
 {
     atomic_store_explicit(&s_led.brightness, brightness, memory_order_relaxed);
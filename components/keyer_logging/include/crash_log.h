@@ -0,0 +1,160 @@
+/**
+ * @file crash_log.h
+ * @brief Post-mortem log kept in flash across reboots
+ *
+ * A log drain sink keeps the formatted lines in a small ring of recent
+ * lines. On a panic the key, PTT and amplifier outputs are released
+ * first, then the ring, the last keying stream samples and the fault
+ * state are frozen into a record in RTC memory (kept through the panic
+ * reset), and the next boot moves that record to the "crashlog" flash
+ * partition. A critical fault
+ * (TX stopped) is saved to flash directly from bg_task. "log crash"
+ * shows the last record after any number of reboots.
+ *
+ * Writing flash stalls both cores for a few milliseconds: it only
+ * happens at boot or once TX is already stopped by a critical fault.
+ */
+
+#ifndef KEYER_CRASH_LOG_H
+#define KEYER_CRASH_LOG_H
+
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "fault.h"
+#include "stream.h"
+#include "rt_log.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Lines kept in the record */
+#define CRASH_LOG_LINES 16
+
+/** Length of a kept line (longer lines are truncated) */
+#define CRASH_LOG_LINE_LEN 100
+
+/** Keying stream samples kept in the record (the tail before the snapshot) */
+#define CRASH_LOG_SAMPLES 200
+
+/**
+ * @brief What ended the run the record describes
+ */
+typedef enum {
+    CRASH_CAUSE_NONE = 0,
+    CRASH_CAUSE_PANIC,      /**< CPU exception, abort, assert */
+    CRASH_CAUSE_FAULT,      /**< Critical fault (TX stopped) */
+} crash_cause_t;
+
+/**
+ * @brief Post-mortem record (stored as is in flash)
+ */
+typedef struct {
+    uint32_t magic;                 /**< CRASH_LOG_MAGIC when written */
+    uint32_t cause;                 /**< crash_cause_t */
+    int64_t uptime_us;              /**< Time of the snapshot */
+    uint32_t fault_code;            /**< FaultState at the snapshot */
+    uint32_t fault_data;
+    uint32_t fault_count;
+    uint32_t fault_raised;
+    uint32_t fault_active;
+    uint32_t line_count;            /**< Valid lines, oldest first */
+    char lines[CRASH_LOG_LINES][CRASH_LOG_LINE_LEN];
+    uint32_t sample_end;            /**< Stream write position at the snapshot */
+    uint32_t sample_count;          /**< Valid samples, oldest first */
+    stream_sample_t samples[CRASH_LOG_SAMPLES];
+    uint32_t checksum;              /**< Over everything above */
+} crash_record_t;
+
+/** Record marker */
+#define CRASH_LOG_MAGIC 0x43524153u  /* "CRAS" */
+
+/**
+ * @brief Output release hook run first by the panic handler
+ *
+ * Must be IRAM and touch registers only (see hal_gpio_panic_off()).
+ */
+typedef void (*crash_log_outputs_off_fn)(void);
+
+/**
+ * @brief Set what the panic handler secures and keeps (call once at boot)
+ *
+ * @param stream Keying stream whose tail goes into records, NULL for none
+ * @param outputs_off Output release hook, NULL for none
+ */
+void crash_log_panic_setup(const keying_stream_t *stream, crash_log_outputs_off_fn outputs_off);
+
+/**
+ * @brief Keep a log line in the ring of recent lines
+ *
+ * Called by the log drain task (single writer). Trailing CR/LF are dropped.
+ *
+ * @param line Formatted line
+ * @param len Length
+ */
+void crash_log_capture(const char *line, size_t len);
+
+/**
+ * @brief Log drain sink feeding crash_log_capture() (see log_drain.h)
+ */
+bool crash_log_sink(const log_entry_t *entry, const char *stream);
+
+/**
+ * @brief Freeze the recent lines, stream tail and fault state into a record
+ *
+ * @param rec Filled in (checksum included)
+ * @param cause What ended the run
+ * @param fault Fault state
+ * @param now_us Current time
+ */
+void crash_log_snapshot(crash_record_t *rec, crash_cause_t cause,
+                        const fault_state_t *fault, int64_t now_us);
+
+/**
+ * @brief Check the marker and checksum of a record
+ */
+bool crash_log_valid(const crash_record_t *rec);
+
+/**
+ * @brief Get the cause name ("panic")
+ */
+const char *crash_cause_str(crash_cause_t cause);
+
+/**
+ * @brief Move a record left by a panic to flash (call once at boot)
+ *
+ * @return true if a panic record was saved
+ */
+bool crash_log_boot(void);
+
+/**
+ * @brief Save a record to flash now (bg context, not RT-safe)
+ *
+ * @param cause What ended the run
+ * @param fault Fault state
+ * @param now_us Current time
+ * @return true if written
+ */
+bool crash_log_save(crash_cause_t cause, const fault_state_t *fault, int64_t now_us);
+
+/**
+ * @brief Read the record kept in flash
+ *
+ * @param rec Filled in
+ * @return true if a valid record is stored
+ */
+bool crash_log_read(crash_record_t *rec);
+
+/**
+ * @brief Erase the record kept in flash
+ *
+ * @return true on success
+ */
+bool crash_log_clear(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CRASH_LOG_H */
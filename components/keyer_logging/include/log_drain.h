@@ -0,0 +1,114 @@
+/**
+ * @file log_drain.h
+ * @brief Log drain: one task, many sinks
+ *
+ * A single Core 1 task drains the RT and BG log streams and hands every
+ * entry to each attached sink (UART, USB CDC, crash log, ...). Entries
+ * pass through a small ring with one read position per sink, the same
+ * model as the keying stream consumers: a sink that reports busy keeps
+ * its place and gets the entry again on the next poll, a sink that
+ * falls more than LOG_DRAIN_RING entries behind skips ahead and counts
+ * the entries it lost. A slow sink never holds back the others or the
+ * log streams.
+ *
+ * Sinks are attached from Core 1 tasks at any time. A slot is claimed
+ * with a CAS, and detaching waits until the drain is out of the sink, so
+ * its transport can be closed as soon as log_drain_remove_sink() returns.
+ * A new sink first receives the entries still in the ring, so a late USB
+ * connection sees the tail of the boot log.
+ */
+
+#ifndef KEYER_LOG_DRAIN_H
+#define KEYER_LOG_DRAIN_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include "rt_log.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Maximum number of attached sinks */
+#define LOG_DRAIN_MAX_SINKS 6
+
+/** Entries held for sinks that are behind (power of 2) */
+#define LOG_DRAIN_RING 32
+
+/**
+ * @brief Sink write function
+ * @param entry Entry (text, see log_entry_format())
+ * @param stream Stream it came from ("RT" or "BG")
+ * @return false if the sink is busy (the entry is offered again later)
+ */
+typedef bool (*log_sink_write_fn)(const log_entry_t *entry, const char *stream);
+
+/**
+ * @brief Sink flush function, called after each batch (optional)
+ */
+typedef void (*log_sink_flush_fn)(void);
+
+/**
+ * @brief Attach a sink
+ *
+ * @param name Sink name (for `log`, must be static)
+ * @param write Write function
+ * @param flush Flush function, NULL if none
+ * @return Slot index (0..LOG_DRAIN_MAX_SINKS-1), or -1 if table full/invalid
+ */
+int log_drain_add_sink(const char *name, log_sink_write_fn write, log_sink_flush_fn flush);
+
+/**
+ * @brief Detach a sink
+ *
+ * Returns once the drain task is no longer inside the sink's write or
+ * flush function. Not from a sink function (it would wait for itself).
+ *
+ * @param slot Slot returned by log_drain_add_sink()
+ */
+void log_drain_remove_sink(int slot);
+
+/**
+ * @brief Get the state of a sink
+ *
+ * @param slot Slot index
+ * @param name Sink name (written if attached)
+ * @param dropped Entries lost by the sink (written if attached)
+ * @return true if a sink is attached to the slot
+ */
+bool log_drain_sink_info(int slot, const char **name, uint32_t *dropped);
+
+/**
+ * @brief Move the stream entries to the sinks (drain task only)
+ *
+ * @return Number of entries taken from the log streams
+ */
+size_t log_drain_poll(void);
+
+/**
+ * @brief Queue a line from the drain itself (drain task only)
+ *
+ * Used for the dropped-message reports.
+ *
+ * @param timestamp_us Timestamp in microseconds
+ * @param level Log level
+ * @param msg Message text
+ */
+void log_drain_note(int64_t timestamp_us, log_level_t level, const char *msg);
+
+/**
+ * @brief Log drain task
+ *
+ * Polls the streams every millisecond and reports dropped messages
+ * every 10 seconds. Runs on Core 1.
+ *
+ * @param arg Unused
+ */
+void log_drain_task(void *arg);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_LOG_DRAIN_H */
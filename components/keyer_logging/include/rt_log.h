@@ -3,7 +3,18 @@
  * @brief RT-safe non-blocking logging system
  *
  * Lock-free log stream with ~100-200ns push latency.
- * UART drain task runs on Core 1.
+ * The drain task (log_drain.h) runs on Core 1.
+ *
+ * Every message belongs to a module tag with its own runtime level
+ * ("log level wifi debug"). The macros check the level before
+ * formatting, so a suppressed message costs one atomic load. A source
+ * file picks its tag by defining RT_LOG_TAG before including this
+ * header (default LOG_TAG_MAIN); RT_LOG_T() names the tag per call.
+ *
+ * Hot-path messages can use the binary variants (RT_LOG_BIN,
+ * RT_DIAG_BIN): the producer pushes a message id from rt_log_msgs.h and
+ * its raw arguments, and log_stream_drain() formats the text on the
+ * consumer side. No snprintf runs on the producer.
  *
  * ARCHITECTURE.md compliance:
  * - RULE 3.1.4: No operation shall block
@@ -18,6 +29,7 @@
 #include <stdbool.h>
 #include <stdatomic.h>
 #include <stdio.h>
+#include "rt_log_msgs.h"
 
 #ifdef __cplusplus
 extern "C" {
@@ -33,6 +45,12 @@ extern "C" {
 /** Log buffer size (number of entries, must be power of 2) */
 #define LOG_BUFFER_SIZE 256
 
+/** Buffer size for a formatted log line (log_entry_format) */
+#define LOG_LINE_MAX 208
+
+/** Maximum arguments of a binary log message */
+#define LOG_BIN_MAX_ARGS 4
+
 /* ============================================================================
  * Types
  * ============================================================================ */
@@ -48,22 +66,59 @@ typedef enum {
     LOG_LEVEL_TRACE = 4,
 } log_level_t;
 
+/**
+ * @brief Module tag (runtime level per tag)
+ */
+typedef enum {
+    LOG_TAG_MAIN = 0,   /**< Default: files without a tag */
+    LOG_TAG_RT,         /**< RT task (keying, audio, TX) */
+    LOG_TAG_BG,         /**< Background task */
+    LOG_TAG_WIFI,       /**< WiFi state */
+    LOG_TAG_VPN,        /**< WireGuard VPN state */
+    LOG_TAG_CWNET,      /**< CWNet remote keying */
+    LOG_TAG_POWER,      /**< Sleep and power */
+    LOG_TAG_CONSOLE,    /**< Console commands */
+    LOG_TAG_COUNT
+} log_tag_t;
+
+/** Level of every tag at boot */
+#define LOG_TAG_DEFAULT_LEVEL LOG_LEVEL_INFO
+
+/**
+ * @brief Binary log message id (format string registry, rt_log_msgs.h)
+ */
+typedef enum {
+    LOG_MSG_NONE = 0,   /**< Text entry (msg holds the formatted text) */
+#define RT_LOG_MSG_ID(id, fmt) LOG_MSG_##id,
+    RT_LOG_MESSAGES(RT_LOG_MSG_ID)
+#undef RT_LOG_MSG_ID
+    LOG_MSG_COUNT
+} log_msg_t;
+
 /**
  * @brief Log entry
  */
 typedef struct {
     int64_t timestamp_us;              /**< Timestamp in microseconds */
     log_level_t level;                 /**< Log level */
+    uint8_t tag;                       /**< Source module (log_tag_t) */
     uint8_t len;                       /**< Message length */
-    char msg[LOG_MAX_MSG_LEN];         /**< Message buffer */
+    uint16_t msg_id;                   /**< Deferred format (log_msg_t), 0 = text */
+    char msg[LOG_MAX_MSG_LEN];         /**< Message buffer (raw args if msg_id) */
 } log_entry_t;
 
 /**
  * @brief Lock-free log stream
+ *
+ * Any number of producers, one consumer (the log drain task). A producer
+ * reserves a slot by advancing write_idx (CAS), fills it, then publishes
+ * it through seq[slot]; the consumer only takes a slot once it is
+ * published, so a slow producer never hands out a half-written entry.
  */
 typedef struct {
     log_entry_t entries[LOG_BUFFER_SIZE]; /**< Entry ring buffer */
-    atomic_uint write_idx;                 /**< Producer write index */
+    atomic_uint seq[LOG_BUFFER_SIZE];     /**< Per slot: index + 1 once filled */
+    atomic_uint write_idx;                 /**< Next index to reserve */
     atomic_uint read_idx;                  /**< Consumer read index */
     atomic_uint dropped;                   /**< Dropped message counter */
 } log_stream_t;
@@ -90,6 +145,9 @@ extern log_stream_t g_bg_log_stream;
 /** Diagnostic logging enable flag (atomic for RT-safe access) */
 extern atomic_bool g_rt_diag_enabled;
 
+/** Highest level logged per tag (log_level_t values) */
+extern atomic_uchar g_log_tag_levels[LOG_TAG_COUNT];
+
 /* ============================================================================
  * Functions
  * ============================================================================ */
@@ -108,16 +166,38 @@ void log_stream_init(log_stream_t *stream);
  * @param stream Stream to push to
  * @param timestamp_us Timestamp in microseconds
  * @param level Log level
+ * @param tag Source module
  * @param msg Message string
  * @param len Message length (max LOG_MAX_MSG_LEN)
  * @return true if pushed, false if dropped
  */
 bool log_stream_push(log_stream_t *stream, int64_t timestamp_us,
-                     log_level_t level, const char *msg, size_t len);
+                     log_level_t level, log_tag_t tag, const char *msg, size_t len);
+
+/**
+ * @brief Push a binary log entry (RT-safe, non-blocking)
+ *
+ * Stores the message id and the raw arguments; no formatting.
+ *
+ * @param stream Stream to push to
+ * @param timestamp_us Timestamp in microseconds
+ * @param level Log level
+ * @param tag Source module
+ * @param id Message id
+ * @param args Arguments
+ * @param nargs Number of arguments (max LOG_BIN_MAX_ARGS)
+ * @return true if pushed, false if dropped
+ */
+bool log_stream_push_bin(log_stream_t *stream, int64_t timestamp_us,
+                         log_level_t level, log_tag_t tag, log_msg_t id,
+                         const uint32_t *args, size_t nargs);
 
 /**
  * @brief Drain log entry (consumer side)
  *
+ * Binary entries are formatted here: out always holds text
+ * (msg_id LOG_MSG_NONE).
+ *
  * @param stream Stream to drain from
  * @param out Output entry (written on success)
  * @return true if entry available
@@ -158,46 +238,204 @@ uint32_t log_stream_count(const log_stream_t *stream);
  */
 const char *log_level_str(log_level_t level);
 
+/**
+ * @brief Format an entry as "[timestamp_us] LEVEL tag: message\r\n"
+ *
+ * Once the wall clock is known (log_clock_set_boot_epoch()) the time
+ * becomes "[YYYY-MM-DD hh:mm:ss.uuuuuuZ timestamp_us]" in UTC, also for
+ * entries logged before the sync.
+ *
+ * @param entry Text entry
+ * @param buf Output buffer (LOG_LINE_MAX holds any entry)
+ * @param size Buffer size
+ * @return Line length (truncated to size - 1)
+ */
+size_t log_entry_format(const log_entry_t *entry, char *buf, size_t size);
+
+/** Earliest wall clock taken as synced (2020-01-01, seconds since the epoch) */
+#define LOG_CLOCK_VALID_EPOCH 1577836800
+
+/**
+ * @brief Anchor the log timestamps to the wall clock
+ *
+ * Entry timestamps count from boot, so the wall-clock time of boot
+ * converts every entry, including those logged before the sync.
+ *
+ * @param boot_epoch_us Wall-clock time of boot (us since the epoch), 0 to forget
+ */
+void log_clock_set_boot_epoch(int64_t boot_epoch_us);
+
+/**
+ * @brief Get the wall-clock time of boot
+ * @return us since the epoch, 0 if the clock was never synced
+ */
+int64_t log_clock_boot_epoch(void);
+
+/** ANSI sequence ending a colored line */
+#define LOG_COLOR_RESET "\033[0m"
+
+/**
+ * @brief Get the ANSI color of a level
+ * @param level Log level
+ * @return Escape sequence, "" for INFO or when colors are off
+ */
+const char *log_level_color(log_level_t level);
+
+/**
+ * @brief Color the UART and USB log lines by level (system.log_color)
+ * @param on false for plain text
+ */
+void log_set_color(bool on);
+
+/**
+ * @brief Get the format string of a binary message
+ * @param id Message id
+ * @return Format string, NULL for LOG_MSG_NONE or an unknown id
+ */
+const char *log_msg_format(log_msg_t id);
+
+/**
+ * @brief Get tag name ("wifi")
+ * @param tag Tag
+ * @return Tag name string
+ */
+const char *log_tag_str(log_tag_t tag);
+
+/**
+ * @brief Find a tag by name (case-insensitive)
+ * @param name Tag name
+ * @param out Tag (written on success)
+ * @return true if found
+ */
+bool log_tag_from_str(const char *name, log_tag_t *out);
+
+/**
+ * @brief Parse a level name ("debug", "DEBUG" or "D")
+ * @param name Level name
+ * @param out Level (written on success)
+ * @return true if valid
+ */
+bool log_level_from_str(const char *name, log_level_t *out);
+
+/**
+ * @brief Set the highest level logged for a tag
+ * @param tag Tag
+ * @param level Level (messages above it are dropped before formatting)
+ */
+void log_tag_set_level(log_tag_t tag, log_level_t level);
+
+/**
+ * @brief Get the highest level logged for a tag
+ * @param tag Tag
+ * @return Level
+ */
+log_level_t log_tag_get_level(log_tag_t tag);
+
+/**
+ * @brief Check a message against its tag level (RT-safe, one atomic load)
+ * @param tag Tag
+ * @param level Message level
+ * @return true if the message is to be logged
+ */
+static inline bool log_tag_enabled(log_tag_t tag, log_level_t level) {
+    return (unsigned)level <=
+           atomic_load_explicit(&g_log_tag_levels[tag], memory_order_relaxed);
+}
+
 /* ============================================================================
  * UART Logger
  * ============================================================================ */
 
 /**
- * @brief Initialize UART logger (UART1 on GPIO6)
+ * @brief Initialize UART logger (UART1, TX on Kconfig KEYER_LOG_UART_TX_GPIO)
  *
- * Configures UART1 at 115200 baud for log output.
- * Must be called before starting uart_logger_task.
+ * Configures UART1 at 115200 baud for log output and attaches the
+ * "uart" log drain sink.
  */
 void uart_logger_init(void);
 
 /**
- * @brief UART logger task
+ * @brief Detach the UART from the log drain (console on USB from now on)
+ */
+void uart_logger_detach(void);
+
+/**
+ * @brief Write raw bytes to the log UART
  *
- * Drains RT and BG log streams to UART.
- * Runs on Core 1, priority normal.
+ * Used as a console output sink so command responses also appear
+ * on the UART. Not RT-safe (may block on the UART TX FIFO).
  *
- * @param arg Unused
+ * @param data Bytes to write
+ * @param len Number of bytes
  */
-void uart_logger_task(void *arg);
+void uart_logger_write(const char *data, size_t len);
+
 
 /* ============================================================================
  * RT-Safe Logging Macros
  * ============================================================================ */
 
+/** Tag of the RT_* macros in this file (define before including) */
+#ifndef RT_LOG_TAG
+#define RT_LOG_TAG LOG_TAG_MAIN
+#endif
+
 /**
- * @brief RT-safe log macro (internal)
+ * @brief Format and push, no level check (internal)
  *
  * Uses snprintf to format message, then pushes to stream.
  */
-#define RT_LOG(stream, level, ts, fmt, ...) do { \
+#define RT_LOG_PUSH(stream, tag, level, ts, fmt, ...) do { \
     char _rt_log_buf[LOG_MAX_MSG_LEN]; \
     int _rt_log_len = snprintf(_rt_log_buf, sizeof(_rt_log_buf), fmt, ##__VA_ARGS__); \
     if (_rt_log_len > 0) { \
-        log_stream_push((stream), (ts), (level), _rt_log_buf, \
+        log_stream_push((stream), (ts), (level), (tag), _rt_log_buf, \
             (_rt_log_len > LOG_MAX_MSG_LEN) ? LOG_MAX_MSG_LEN : (size_t)_rt_log_len); \
     } \
 } while(0)
 
+/**
+ * @brief RT-safe log macro with an explicit tag
+ *
+ * Checks the tag level first: a suppressed message is never formatted.
+ */
+#define RT_LOG_T(stream, tag, level, ts, fmt, ...) do { \
+    if (log_tag_enabled((tag), (level))) { \
+        RT_LOG_PUSH(stream, tag, level, ts, fmt, ##__VA_ARGS__); \
+    } \
+} while(0)
+
+/**
+ * @brief RT-safe log macro (internal), tag RT_LOG_TAG
+ */
+#define RT_LOG(stream, level, ts, fmt, ...) \
+    RT_LOG_T(stream, RT_LOG_TAG, level, ts, fmt, ##__VA_ARGS__)
+
+/**
+ * @brief Push a binary message, no level check (internal)
+ *
+ * Arguments are converted to uint32_t; the count is checked at compile time.
+ */
+#define RT_LOG_BIN_PUSH(stream, tag, level, ts, id, ...) do { \
+    const uint32_t _rt_log_args[] = { 0, ##__VA_ARGS__ }; \
+    _Static_assert(sizeof(_rt_log_args) <= (LOG_BIN_MAX_ARGS + 1) * sizeof(uint32_t), \
+                   "too many binary log arguments"); \
+    log_stream_push_bin((stream), (ts), (level), (tag), LOG_MSG_##id, &_rt_log_args[1], \
+        sizeof(_rt_log_args) / sizeof(_rt_log_args[0]) - 1U); \
+} while(0)
+
+/**
+ * @brief Binary log macro, tag RT_LOG_TAG
+ *
+ * RT_LOG_BIN(stream, LOG_LEVEL_INFO, ts, RADIO_SELECT, radio) logs
+ * LOG_MSG_RADIO_SELECT, formatted when drained.
+ */
+#define RT_LOG_BIN(stream, level, ts, id, ...) do { \
+    if (log_tag_enabled(RT_LOG_TAG, (level))) { \
+        RT_LOG_BIN_PUSH(stream, RT_LOG_TAG, level, ts, id, ##__VA_ARGS__); \
+    } \
+} while(0)
+
 /** Log error (critical) */
 #define RT_ERROR(stream, ts, fmt, ...) \
     RT_LOG(stream, LOG_LEVEL_ERROR, ts, fmt, ##__VA_ARGS__)
@@ -226,10 +464,20 @@ void uart_logger_task(void *arg);
  * @brief Diagnostic log macro (internal)
  *
  * Only logs if g_rt_diag_enabled is true. Single atomic load (~1 cycle).
+ * The diag switch is its own opt-in: tag levels do not apply.
  */
 #define RT_DIAG_LOG(stream, level, ts, fmt, ...) do { \
     if (atomic_load_explicit(&g_rt_diag_enabled, memory_order_relaxed)) { \
-        RT_LOG(stream, level, ts, fmt, ##__VA_ARGS__); \
+        RT_LOG_PUSH(stream, RT_LOG_TAG, level, ts, fmt, ##__VA_ARGS__); \
+    } \
+} while(0)
+
+/**
+ * @brief Binary diagnostic log macro (see RT_LOG_BIN)
+ */
+#define RT_DIAG_BIN(stream, level, ts, id, ...) do { \
+    if (atomic_load_explicit(&g_rt_diag_enabled, memory_order_relaxed)) { \
+        RT_LOG_BIN_PUSH(stream, RT_LOG_TAG, level, ts, id, ##__VA_ARGS__); \
     } \
 } while(0)
 
@@ -0,0 +1,26 @@
+/**
+ * @file rt_log_msgs.h
+ * @brief Format strings of the binary (deferred-formatting) log messages
+ *
+ * One line per message: X(ID, format). The list expands at compile time
+ * into the log_msg_t ids (LOG_MSG_ID) and the format table the drain
+ * side uses. Append new messages at the end so ids stay stable.
+ *
+ * Arguments are 32-bit integers: use %d, %u, %x or %c only, at most
+ * LOG_BIN_MAX_ARGS per message.
+ */
+
+#ifndef KEYER_RT_LOG_MSGS_H
+#define KEYER_RT_LOG_MSGS_H
+
+#define RT_LOG_MESSAGES(X) \
+    X(KEY_DIT_DOWN,  "KEY DIT down") \
+    X(KEY_DAH_DOWN,  "KEY DAH down") \
+    X(KEY_UP,        "KEY up %uus") \
+    X(DRIFT,         "DRIFT %u%% (exp=%u act=%u)") \
+    X(MEM_DIT,       "MEM DIT") \
+    X(MEM_DAH,       "MEM DAH") \
+    X(RADIO_SELECT,  "Radio %u selected") \
+    X(RADIO_NO_TX,   "Radio %u has no TX pin")
+
+#endif /* KEYER_RT_LOG_MSGS_H */
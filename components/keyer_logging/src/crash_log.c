@@ -0,0 +1,242 @@
+/**
+ * @file crash_log.c
+ * @brief Post-mortem log implementation
+ */
+
+#include "crash_log.h"
+#include <string.h>
+
+#ifdef ESP_PLATFORM
+#include "esp_attr.h"
+#include "esp_partition.h"
+#include "esp_timer.h"
+#include "esp_private/panic_internal.h"
+#include "esp_private/cache_utils.h"
+
+/** Custom data subtype of the "crashlog" partition (partitions.csv) */
+#define CRASH_LOG_PARTITION_SUBTYPE 0x40
+#define CRASH_LOG_SECTOR 4096
+
+/* The panic handler may run with the flash cache disabled */
+#define CRASH_LOG_IRAM IRAM_ATTR
+
+/* The stream buffer is in PSRAM, behind the same cache */
+#define CRASH_LOG_PSRAM_READABLE() spi_flash_cache_enabled()
+#else
+#define CRASH_LOG_IRAM
+#define CRASH_LOG_PSRAM_READABLE() true
+#endif
+
+/* ============================================================================
+ * Panic Setup
+ * ============================================================================ */
+
+static const keying_stream_t *s_stream = NULL;
+static crash_log_outputs_off_fn s_outputs_off = NULL;
+
+void crash_log_panic_setup(const keying_stream_t *stream, crash_log_outputs_off_fn outputs_off) {
+    s_stream = stream;
+    s_outputs_off = outputs_off;
+}
+
+/* ============================================================================
+ * Recent Lines (written by the log drain task)
+ * ============================================================================ */
+
+static char s_lines[CRASH_LOG_LINES][CRASH_LOG_LINE_LEN];
+static uint32_t s_line_total = 0;
+
+void crash_log_capture(const char *line, size_t len) {
+    while (len > 0 && (line[len - 1] == '\n' || line[len - 1] == '\r')) {
+        len--;
+    }
+    if (len >= CRASH_LOG_LINE_LEN) {
+        len = CRASH_LOG_LINE_LEN - 1;
+    }
+
+    char *slot = s_lines[s_line_total % CRASH_LOG_LINES];
+    memcpy(slot, line, len);
+    slot[len] = '\0';
+    s_line_total++;
+}
+
+bool crash_log_sink(const log_entry_t *entry, const char *stream) {
+    (void)stream;
+    static char line[LOG_LINE_MAX];
+    crash_log_capture(line, log_entry_format(entry, line, sizeof(line)));
+    return true;
+}
+
+/* ============================================================================
+ * Records
+ * ============================================================================ */
+
+static CRASH_LOG_IRAM uint32_t record_checksum(const crash_record_t *rec) {
+    /* FNV-1a */
+    const uint8_t *bytes = (const uint8_t *)rec;
+    uint32_t hash = 2166136261u;
+    for (size_t i = 0; i < offsetof(crash_record_t, checksum); i++) {
+        hash = (hash ^ bytes[i]) * 16777619u;
+    }
+    return hash;
+}
+
+/**
+ * @brief Copy the stream tail (oldest first)
+ *
+ * Reads the buffer directly: stream_read() lives in flash.
+ */
+static CRASH_LOG_IRAM void snapshot_samples(crash_record_t *rec) {
+    const keying_stream_t *stream = s_stream;
+    if (stream == NULL || stream->buffer == NULL || !CRASH_LOG_PSRAM_READABLE()) {
+        return;
+    }
+    size_t end = atomic_load_explicit(&stream->write_idx, memory_order_acquire);
+    size_t count = (end < CRASH_LOG_SAMPLES) ? end : CRASH_LOG_SAMPLES;
+    if (count > stream->capacity) {
+        count = stream->capacity;
+    }
+    for (size_t i = 0; i < count; i++) {
+        rec->samples[i] = stream->buffer[(end - count + i) & stream->mask];
+    }
+    rec->sample_end = (uint32_t)end;
+    rec->sample_count = (uint32_t)count;
+}
+
+CRASH_LOG_IRAM void crash_log_snapshot(crash_record_t *rec, crash_cause_t cause,
+                                       const fault_state_t *fault, int64_t now_us) {
+    memset(rec, 0, sizeof(*rec));
+    rec->magic = CRASH_LOG_MAGIC;
+    rec->cause = (uint32_t)cause;
+    rec->uptime_us = now_us;
+    rec->fault_code = (uint32_t)fault_get_code(fault);
+    rec->fault_data = fault_get_data(fault);
+    rec->fault_count = fault_get_count(fault);
+    rec->fault_raised = fault_get_raised(fault);
+    rec->fault_active = fault_is_active(fault) ? 1U : 0U;
+
+    /* Oldest line first */
+    uint32_t total = s_line_total;
+    uint32_t count = (total < CRASH_LOG_LINES) ? total : CRASH_LOG_LINES;
+    for (uint32_t i = 0; i < count; i++) {
+        memcpy(rec->lines[i], s_lines[(total - count + i) % CRASH_LOG_LINES],
+               CRASH_LOG_LINE_LEN);
+        rec->lines[i][CRASH_LOG_LINE_LEN - 1] = '\0';
+    }
+    rec->line_count = count;
+    snapshot_samples(rec);
+    rec->checksum = record_checksum(rec);
+}
+
+bool crash_log_valid(const crash_record_t *rec) {
+    return rec->magic == CRASH_LOG_MAGIC &&
+           rec->line_count <= CRASH_LOG_LINES &&
+           rec->sample_count <= CRASH_LOG_SAMPLES &&
+           rec->checksum == record_checksum(rec);
+}
+
+const char *crash_cause_str(crash_cause_t cause) {
+    switch (cause) {
+        case CRASH_CAUSE_PANIC: return "panic";
+        case CRASH_CAUSE_FAULT: return "fault";
+        default:                return "none";
+    }
+}
+
+/* ============================================================================
+ * Flash Storage
+ * ============================================================================ */
+
+#ifdef ESP_PLATFORM
+
+/* Global fault state (main.c) */
+extern fault_state_t g_fault_state;
+
+/* Left by the panic handler, kept through the panic reset */
+static RTC_NOINIT_ATTR crash_record_t s_pending;
+
+static const esp_partition_t *find_partition(void) {
+    return esp_partition_find_first(ESP_PARTITION_TYPE_DATA,
+                                    (esp_partition_subtype_t)CRASH_LOG_PARTITION_SUBTYPE,
+                                    "crashlog");
+}
+
+/** Erase size covering one record */
+static size_t record_erase_size(void) {
+    return (sizeof(crash_record_t) + CRASH_LOG_SECTOR - 1) / CRASH_LOG_SECTOR * CRASH_LOG_SECTOR;
+}
+
+static bool write_record(const crash_record_t *rec) {
+    const esp_partition_t *part = find_partition();
+    if (part == NULL) {
+        return false;
+    }
+    return esp_partition_erase_range(part, 0, record_erase_size()) == ESP_OK &&
+           esp_partition_write(part, 0, rec, sizeof(*rec)) == ESP_OK;
+}
+
+/* Linked in place of esp_panic_handler (-Wl,--wrap, see CMakeLists.txt) */
+void __real_esp_panic_handler(panic_info_t *info);
+
+void IRAM_ATTR __wrap_esp_panic_handler(panic_info_t *info) {
+    /* Never leave the transmitter keyed, whatever comes next */
+    if (s_outputs_off != NULL) {
+        s_outputs_off();
+    }
+    crash_log_snapshot(&s_pending, CRASH_CAUSE_PANIC, &g_fault_state, esp_timer_get_time());
+    __real_esp_panic_handler(info);
+}
+
+bool crash_log_boot(void) {
+    if (!crash_log_valid(&s_pending)) {
+        return false;
+    }
+    bool saved = write_record(&s_pending);
+    s_pending.magic = 0;
+    return saved;
+}
+
+bool crash_log_save(crash_cause_t cause, const fault_state_t *fault, int64_t now_us) {
+    static crash_record_t rec;  /* Too large for the bg_task stack */
+    crash_log_snapshot(&rec, cause, fault, now_us);
+    return write_record(&rec);
+}
+
+bool crash_log_read(crash_record_t *rec) {
+    const esp_partition_t *part = find_partition();
+    if (part == NULL || esp_partition_read(part, 0, rec, sizeof(*rec)) != ESP_OK) {
+        return false;
+    }
+    return crash_log_valid(rec);
+}
+
+bool crash_log_clear(void) {
+    const esp_partition_t *part = find_partition();
+    return part != NULL && esp_partition_erase_range(part, 0, record_erase_size()) == ESP_OK;
+}
+
+#else
+/* Host: the flash partition is a RAM copy */
+
+static crash_record_t s_flash;
+
+bool crash_log_boot(void) {
+    return false;
+}
+
+bool crash_log_save(crash_cause_t cause, const fault_state_t *fault, int64_t now_us) {
+    crash_log_snapshot(&s_flash, cause, fault, now_us);
+    return true;
+}
+
+bool crash_log_read(crash_record_t *rec) {
+    *rec = s_flash;
+    return crash_log_valid(rec);
+}
+
+bool crash_log_clear(void) {
+    memset(&s_flash, 0xFF, sizeof(s_flash));
+    return true;
+}
+
+#endif /* ESP_PLATFORM */
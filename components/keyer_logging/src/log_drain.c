@@ -0,0 +1,289 @@
+/**
+ * @file log_drain.c
+ * @brief Log drain implementation
+ */
+
+#include "log_drain.h"
+#include <stdatomic.h>
+#include <stdio.h>
+#include <string.h>
+
+#ifdef ESP_PLATFORM
+#include "freertos/FreeRTOS.h"
+#include "freertos/task.h"
+#include "esp_timer.h"
+#else
+#include <sched.h>
+#endif
+
+/* Entries taken from the streams between two deliveries */
+#define LOG_DRAIN_BATCH (LOG_DRAIN_RING / 2)
+
+/* ============================================================================
+ * Ring and sink table (drain task only, except attach/detach/info)
+ * ============================================================================ */
+
+/** Sink slot states; a slot is reused only once it is back to FREE */
+enum {
+    SINK_FREE = 0,
+    SINK_CLAIMED,   /**< Being filled in, or being detached */
+    SINK_ACTIVE,
+};
+
+typedef struct {
+    log_entry_t entry;
+    const char *stream;
+} drain_slot_t;
+
+typedef struct {
+    const char *name;
+    log_sink_write_fn write;
+    log_sink_flush_fn flush;
+    uint32_t next;              /**< Next ring position to deliver */
+    atomic_uint dropped;        /**< Entries lost (fell behind) */
+    uint32_t reported;          /**< dropped at the last report */
+    atomic_uchar state;
+    atomic_uint busy;           /**< Tasks using the slot (see sink_enter) */
+} log_sink_t;
+
+static drain_slot_t s_ring[LOG_DRAIN_RING];
+static atomic_uint s_head = 0;
+static log_sink_t s_sinks[LOG_DRAIN_MAX_SINKS];
+
+int log_drain_add_sink(const char *name, log_sink_write_fn write, log_sink_flush_fn flush) {
+    if (write == NULL) {
+        return -1;
+    }
+
+    for (int i = 0; i < LOG_DRAIN_MAX_SINKS; i++) {
+        log_sink_t *s = &s_sinks[i];
+        unsigned char expected = SINK_FREE;
+        if (atomic_compare_exchange_strong_explicit(&s->state, &expected, SINK_CLAIMED,
+                                                    memory_order_acquire,
+                                                    memory_order_relaxed)) {
+            uint32_t head = atomic_load_explicit(&s_head, memory_order_acquire);
+            s->name = (name != NULL) ? name : "?";
+            s->write = write;
+            s->flush = flush;
+            s->next = (head > LOG_DRAIN_RING) ? head - LOG_DRAIN_RING : 0;
+            atomic_store_explicit(&s->dropped, 0, memory_order_relaxed);
+            s->reported = 0;
+            /* Publish only after fields are written */
+            atomic_store_explicit(&s->state, SINK_ACTIVE, memory_order_release);
+            return i;
+        }
+    }
+    return -1;
+}
+
+/**
+ * @brief Start using a slot; true if a sink is attached (then call sink_leave)
+ *
+ * busy is raised before the state is checked, and remove changes the
+ * state before it reads busy (both seq_cst): either remove waits for us
+ * or we see the slot detached.
+ */
+static bool sink_enter(log_sink_t *s) {
+    atomic_fetch_add(&s->busy, 1);
+    if (atomic_load(&s->state) != SINK_ACTIVE) {
+        atomic_fetch_sub_explicit(&s->busy, 1, memory_order_release);
+        return false;
+    }
+    return true;
+}
+
+static void sink_leave(log_sink_t *s) {
+    atomic_fetch_sub_explicit(&s->busy, 1, memory_order_release);
+}
+
+void log_drain_remove_sink(int slot) {
+    if (slot < 0 || slot >= LOG_DRAIN_MAX_SINKS) {
+        return;
+    }
+    log_sink_t *s = &s_sinks[slot];
+    unsigned char expected = SINK_ACTIVE;
+    if (!atomic_compare_exchange_strong(&s->state, &expected, SINK_CLAIMED)) {
+        return;
+    }
+
+    /* The drain may be inside write()/flush() right now */
+    while (atomic_load(&s->busy) != 0) {
+#ifdef ESP_PLATFORM
+        vTaskDelay(1);
+#else
+        sched_yield();
+#endif
+    }
+    atomic_store_explicit(&s->state, SINK_FREE, memory_order_release);
+}
+
+bool log_drain_sink_info(int slot, const char **name, uint32_t *dropped) {
+    if (slot < 0 || slot >= LOG_DRAIN_MAX_SINKS || !sink_enter(&s_sinks[slot])) {
+        return false;
+    }
+    *name = s_sinks[slot].name;
+    *dropped = atomic_load_explicit(&s_sinks[slot].dropped, memory_order_relaxed);
+    sink_leave(&s_sinks[slot]);
+    return true;
+}
+
+/* ============================================================================
+ * Drain
+ * ============================================================================ */
+
+/**
+ * @brief Take up to max entries from the streams into the ring (RT first)
+ */
+static size_t collect(size_t max) {
+    uint32_t head = atomic_load_explicit(&s_head, memory_order_relaxed);
+    size_t count = 0;
+    while (count < max) {
+        drain_slot_t *slot = &s_ring[head & (LOG_DRAIN_RING - 1)];
+        if (log_stream_drain(&g_rt_log_stream, &slot->entry)) {
+            slot->stream = "RT";
+        } else if (log_stream_drain(&g_bg_log_stream, &slot->entry)) {
+            slot->stream = "BG";
+        } else {
+            break;
+        }
+        head++;
+        count++;
+    }
+    atomic_store_explicit(&s_head, head, memory_order_release);
+    return count;
+}
+
+/**
+ * @brief Offer one sink what it has not seen yet
+ */
+static void deliver(log_sink_t *s, uint32_t head) {
+    if (head - s->next > LOG_DRAIN_RING) {
+        /* Fell behind: the oldest entries were overwritten */
+        atomic_fetch_add_explicit(&s->dropped, head - s->next - LOG_DRAIN_RING,
+                                  memory_order_relaxed);
+        s->next = head - LOG_DRAIN_RING;
+    }
+
+    bool sent = false;
+    while (s->next != head) {
+        const drain_slot_t *slot = &s_ring[s->next & (LOG_DRAIN_RING - 1)];
+        if (!s->write(&slot->entry, slot->stream)) {
+            break;  /* Busy: keep the place, retry on the next poll */
+        }
+        s->next++;
+        sent = true;
+    }
+
+    if (sent && s->flush != NULL) {
+        s->flush();
+    }
+}
+
+static void deliver_all(void) {
+    uint32_t head = atomic_load_explicit(&s_head, memory_order_relaxed);
+    for (int i = 0; i < LOG_DRAIN_MAX_SINKS; i++) {
+        log_sink_t *s = &s_sinks[i];
+        if (sink_enter(s)) {
+            deliver(s, head);
+            sink_leave(s);
+        }
+    }
+}
+
+size_t log_drain_poll(void) {
+    size_t total = 0;
+    size_t count;
+    do {
+        count = collect(LOG_DRAIN_BATCH);
+        total += count;
+        deliver_all();
+    } while (count == LOG_DRAIN_BATCH);
+    return total;
+}
+
+void log_drain_note(int64_t timestamp_us, log_level_t level, const char *msg) {
+    uint32_t head = atomic_load_explicit(&s_head, memory_order_relaxed);
+    drain_slot_t *slot = &s_ring[head & (LOG_DRAIN_RING - 1)];
+
+    size_t len = strlen(msg);
+    if (len > LOG_MAX_MSG_LEN) {
+        len = LOG_MAX_MSG_LEN;
+    }
+    memset(&slot->entry, 0, sizeof(slot->entry));
+    slot->entry.timestamp_us = timestamp_us;
+    slot->entry.level = level;
+    slot->entry.tag = LOG_TAG_MAIN;
+    slot->entry.len = (uint8_t)len;
+    memcpy(slot->entry.msg, msg, len);
+    slot->stream = "BG";
+
+    atomic_store_explicit(&s_head, head + 1, memory_order_release);
+    deliver_all();
+}
+
+#ifdef ESP_PLATFORM
+
+/**
+ * @brief Report stream drops and sink drops since the last report
+ */
+static void report_dropped(int64_t now_us) {
+    char msg[LOG_MAX_MSG_LEN];
+    uint32_t rt_dropped = log_stream_dropped(&g_rt_log_stream);
+    uint32_t bg_dropped = log_stream_dropped(&g_bg_log_stream);
+    if (rt_dropped > 0 || bg_dropped > 0) {
+        snprintf(msg, sizeof(msg), "Dropped logs: RT=%lu BG=%lu",
+                 (unsigned long)rt_dropped, (unsigned long)bg_dropped);
+        log_drain_note(now_us, LOG_LEVEL_WARN, msg);
+        log_stream_reset_dropped(&g_rt_log_stream);
+        log_stream_reset_dropped(&g_bg_log_stream);
+    }
+
+    for (int i = 0; i < LOG_DRAIN_MAX_SINKS; i++) {
+        log_sink_t *s = &s_sinks[i];
+        if (!sink_enter(s)) {
+            continue;
+        }
+        uint32_t dropped = atomic_load_explicit(&s->dropped, memory_order_relaxed);
+        bool report = (dropped != s->reported);
+        if (report) {
+            snprintf(msg, sizeof(msg), "Log sink %s dropped %lu",
+                     s->name, (unsigned long)(dropped - s->reported));
+            s->reported = dropped;
+        }
+        sink_leave(s);
+        if (report) {
+            log_drain_note(now_us, LOG_LEVEL_WARN, msg);
+        }
+    }
+}
+
+void log_drain_task(void *arg) {
+    (void)arg;
+
+    int64_t last_report_us = 0;
+
+    for (;;) {
+        size_t count = log_drain_poll();
+
+        int64_t now_us = esp_timer_get_time();
+        if (now_us - last_report_us >= 10000000) {
+            report_dropped(now_us);
+            last_report_us = now_us;
+        }
+
+        /* Sleep if no entries */
+        if (count == 0) {
+            vTaskDelay(pdMS_TO_TICKS(1));
+        }
+    }
+}
+
+#else
+/* Host stub */
+
+void log_drain_task(void *arg) {
+    (void)arg;
+    /* No-op on host */
+}
+
+#endif /* ESP_PLATFORM */
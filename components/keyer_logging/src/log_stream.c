@@ -4,7 +4,10 @@
  */
 
 #include "rt_log.h"
+#include <ctype.h>
 #include <string.h>
+#include <strings.h>
+#include <time.h>
 
 /* Global log stream instances */
 log_stream_t g_rt_log_stream = LOG_STREAM_INIT;
@@ -13,22 +16,91 @@ log_stream_t g_bg_log_stream = LOG_STREAM_INIT;
 /* Diagnostic logging enable flag (default: off) */
 atomic_bool g_rt_diag_enabled = false;
 
+/* Level colors in the log drains (system.log_color) */
+static atomic_bool s_color = false;
+
+/* Wall-clock time of boot in us, 0 until synced (bg_task writes, drain reads) */
+static _Atomic int64_t s_boot_epoch_us = 0;
+
+/* Per-tag levels (default: LOG_TAG_DEFAULT_LEVEL) */
+atomic_uchar g_log_tag_levels[LOG_TAG_COUNT] = {
+    [LOG_TAG_MAIN] = LOG_TAG_DEFAULT_LEVEL,
+    [LOG_TAG_RT] = LOG_TAG_DEFAULT_LEVEL,
+    [LOG_TAG_BG] = LOG_TAG_DEFAULT_LEVEL,
+    [LOG_TAG_WIFI] = LOG_TAG_DEFAULT_LEVEL,
+    [LOG_TAG_VPN] = LOG_TAG_DEFAULT_LEVEL,
+    [LOG_TAG_CWNET] = LOG_TAG_DEFAULT_LEVEL,
+    [LOG_TAG_POWER] = LOG_TAG_DEFAULT_LEVEL,
+    [LOG_TAG_CONSOLE] = LOG_TAG_DEFAULT_LEVEL,
+};
+
+static const char *const TAG_NAMES[LOG_TAG_COUNT] = {
+    [LOG_TAG_MAIN] = "main",
+    [LOG_TAG_RT] = "rt",
+    [LOG_TAG_BG] = "bg",
+    [LOG_TAG_WIFI] = "wifi",
+    [LOG_TAG_VPN] = "vpn",
+    [LOG_TAG_CWNET] = "cwnet",
+    [LOG_TAG_POWER] = "power",
+    [LOG_TAG_CONSOLE] = "console",
+};
+
+_Static_assert(LOG_BIN_MAX_ARGS == 4, "format_bin() passes four arguments");
+
+/* Binary message formats, indexed by log_msg_t */
+static const char *const MSG_FORMATS[LOG_MSG_COUNT] = {
+    [LOG_MSG_NONE] = NULL,
+#define RT_LOG_MSG_FORMAT(id, fmt) [LOG_MSG_##id] = fmt,
+    RT_LOG_MESSAGES(RT_LOG_MSG_FORMAT)
+#undef RT_LOG_MSG_FORMAT
+};
+
 void log_stream_init(log_stream_t *stream) {
     atomic_init(&stream->write_idx, 0);
     atomic_init(&stream->read_idx, 0);
     atomic_init(&stream->dropped, 0);
     memset(stream->entries, 0, sizeof(stream->entries));
+    for (uint32_t i = 0; i < LOG_BUFFER_SIZE; i++) {
+        atomic_init(&stream->seq[i], 0);
+    }
 }
 
-bool log_stream_push(log_stream_t *stream, int64_t timestamp_us,
-                     log_level_t level, const char *msg, size_t len) {
-    /* Check if buffer is full */
-    uint32_t write = atomic_load_explicit(&stream->write_idx, memory_order_relaxed);
-    uint32_t read = atomic_load_explicit(&stream->read_idx, memory_order_relaxed);
+/**
+ * @brief Reserve the next slot (any producer)
+ *
+ * @return false if the buffer is full (message dropped)
+ */
+static bool reserve_slot(log_stream_t *stream, uint32_t *index) {
+    uint32_t write;
+    do {
+        /* Acquire: the consumer has finished copying every slot below read */
+        uint32_t read = atomic_load_explicit(&stream->read_idx, memory_order_acquire);
+        write = atomic_load_explicit(&stream->write_idx, memory_order_relaxed);
 
-    if (write - read >= LOG_BUFFER_SIZE) {
-        /* Buffer full - drop message */
-        atomic_fetch_add_explicit(&stream->dropped, 1, memory_order_relaxed);
+        if (write - read >= LOG_BUFFER_SIZE) {
+            /* Buffer full - drop message */
+            atomic_fetch_add_explicit(&stream->dropped, 1, memory_order_relaxed);
+            return false;
+        }
+    } while (!atomic_compare_exchange_weak_explicit(&stream->write_idx, &write, write + 1,
+                                                    memory_order_relaxed,
+                                                    memory_order_relaxed));
+    *index = write;
+    return true;
+}
+
+/**
+ * @brief Hand a filled slot to the consumer
+ */
+static void publish_slot(log_stream_t *stream, uint32_t index) {
+    atomic_store_explicit(&stream->seq[index & (LOG_BUFFER_SIZE - 1)], index + 1,
+                          memory_order_release);
+}
+
+bool log_stream_push(log_stream_t *stream, int64_t timestamp_us,
+                     log_level_t level, log_tag_t tag, const char *msg, size_t len) {
+    uint32_t write;
+    if (!reserve_slot(stream, &write)) {
         return false;
     }
 
@@ -39,6 +111,8 @@ bool log_stream_push(log_stream_t *stream, int64_t timestamp_us,
     /* Fill entry */
     entry->timestamp_us = timestamp_us;
     entry->level = level;
+    entry->tag = (uint8_t)tag;
+    entry->msg_id = LOG_MSG_NONE;
 
     /* Copy message (truncate if needed) */
     size_t copy_len = (len > LOG_MAX_MSG_LEN) ? LOG_MAX_MSG_LEN : len;
@@ -46,29 +120,91 @@ bool log_stream_push(log_stream_t *stream, int64_t timestamp_us,
     entry->len = (uint8_t)copy_len;
 
     /* Publish entry */
-    atomic_store_explicit(&stream->write_idx, write + 1, memory_order_release);
+    publish_slot(stream, write);
 
     return true;
 }
 
-bool log_stream_drain(log_stream_t *stream, log_entry_t *out) {
-    uint32_t read = atomic_load_explicit(&stream->read_idx, memory_order_relaxed);
-    uint32_t write = atomic_load_explicit(&stream->write_idx, memory_order_acquire);
-
-    if (read == write) {
-        /* Empty */
+bool log_stream_push_bin(log_stream_t *stream, int64_t timestamp_us,
+                         log_level_t level, log_tag_t tag, log_msg_t id,
+                         const uint32_t *args, size_t nargs) {
+    uint32_t write;
+    if (!reserve_slot(stream, &write)) {
         return false;
     }
 
+    uint32_t slot = write & (LOG_BUFFER_SIZE - 1);
+    log_entry_t *entry = &stream->entries[slot];
+
+    entry->timestamp_us = timestamp_us;
+    entry->level = level;
+    entry->tag = (uint8_t)tag;
+    entry->msg_id = (uint16_t)id;
+
+    /* Raw arguments, formatted by the consumer */
+    size_t count = (nargs > LOG_BIN_MAX_ARGS) ? LOG_BIN_MAX_ARGS : nargs;
+    memcpy(entry->msg, args, count * sizeof(uint32_t));
+    entry->len = (uint8_t)count;
+
+    publish_slot(stream, write);
+
+    return true;
+}
+
+/**
+ * @brief Turn a binary entry into a text entry (consumer side)
+ */
+static void format_bin(log_entry_t *entry) {
+    uint32_t args[LOG_BIN_MAX_ARGS] = {0};
+    size_t count = (entry->len > LOG_BIN_MAX_ARGS) ? LOG_BIN_MAX_ARGS : entry->len;
+    memcpy(args, entry->msg, count * sizeof(uint32_t));
+
+    const char *fmt = log_msg_format((log_msg_t)entry->msg_id);
+    int len;
+    if (fmt != NULL) {
+        /* Formats come from rt_log_msgs.h only; unused arguments are ignored */
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wformat-nonliteral"
+        len = snprintf(entry->msg, sizeof(entry->msg), fmt,
+                       (unsigned)args[0], (unsigned)args[1],
+                       (unsigned)args[2], (unsigned)args[3]);
+#pragma GCC diagnostic pop
+    } else {
+        len = snprintf(entry->msg, sizeof(entry->msg), "unknown log message %u",
+                       (unsigned)entry->msg_id);
+    }
+
+    if (len < 0) {
+        len = 0;
+    } else if (len >= LOG_MAX_MSG_LEN) {
+        len = LOG_MAX_MSG_LEN - 1;  /* Truncated */
+    }
+    entry->len = (uint8_t)len;
+    entry->msg_id = LOG_MSG_NONE;
+}
+
+bool log_stream_drain(log_stream_t *stream, log_entry_t *out) {
+    uint32_t read = atomic_load_explicit(&stream->read_idx, memory_order_relaxed);
+
     /* Get slot */
     uint32_t slot = read & (LOG_BUFFER_SIZE - 1);
 
+    /* Acquire: pairs with publish_slot() of the producer that filled it */
+    if (atomic_load_explicit(&stream->seq[slot], memory_order_acquire) != read + 1) {
+        /* Empty, or reserved but still being filled */
+        return false;
+    }
+
     /* Copy entry */
     *out = stream->entries[slot];
 
     /* Advance read index */
     atomic_store_explicit(&stream->read_idx, read + 1, memory_order_release);
 
+    if (out->msg_id != LOG_MSG_NONE) {
+        format_bin(out);
+    }
+
     return true;
 }
 
@@ -78,8 +214,8 @@ uint32_t log_stream_dropped(const log_stream_t *stream) {
 
 bool log_stream_has_entries(const log_stream_t *stream) {
     uint32_t read = atomic_load_explicit(&stream->read_idx, memory_order_relaxed);
-    uint32_t write = atomic_load_explicit(&stream->write_idx, memory_order_acquire);
-    return read != write;
+    uint32_t slot = read & (LOG_BUFFER_SIZE - 1);
+    return atomic_load_explicit(&stream->seq[slot], memory_order_acquire) == read + 1;
 }
 
 void log_stream_reset_dropped(log_stream_t *stream) {
@@ -102,3 +238,109 @@ const char *log_level_str(log_level_t level) {
         default:              return "?????";
     }
 }
+
+void log_clock_set_boot_epoch(int64_t boot_epoch_us) {
+    atomic_store_explicit(&s_boot_epoch_us, boot_epoch_us, memory_order_relaxed);
+}
+
+int64_t log_clock_boot_epoch(void) {
+    return atomic_load_explicit(&s_boot_epoch_us, memory_order_relaxed);
+}
+
+/**
+ * @brief Time field of a log line: UTC wall clock and uptime, or uptime only
+ *
+ * No time zone is configured on the device, so the wall clock is always
+ * printed as UTC and marked "Z".
+ */
+static void format_time(int64_t timestamp_us, char *buf, size_t size) {
+    int64_t boot_us = log_clock_boot_epoch();
+    if (boot_us != 0) {
+        int64_t wall_us = boot_us + timestamp_us;
+        time_t secs = (time_t)(wall_us / 1000000);
+        struct tm tm;
+        if (gmtime_r(&secs, &tm) != NULL) {
+            snprintf(buf, size, "%04d-%02d-%02d %02d:%02d:%02d.%06ldZ %lld",
+                     tm.tm_year + 1900, tm.tm_mon + 1, tm.tm_mday,
+                     tm.tm_hour, tm.tm_min, tm.tm_sec,
+                     (long)(wall_us % 1000000), (long long)timestamp_us);
+            return;
+        }
+    }
+    snprintf(buf, size, "%lld", (long long)timestamp_us);
+}
+
+size_t log_entry_format(const log_entry_t *entry, char *buf, size_t size) {
+    char time_buf[48];
+    format_time(entry->timestamp_us, time_buf, sizeof(time_buf));
+    int len = snprintf(buf, size, "[%s] %s %s: %.*s\r\n",
+                       time_buf,
+                       log_level_str(entry->level),
+                       log_tag_str((log_tag_t)entry->tag),
+                       (int)entry->len,
+                       entry->msg);
+    if (len < 0 || size == 0) {
+        return 0;
+    }
+    return ((size_t)len < size) ? (size_t)len : size - 1;
+}
+
+const char *log_level_color(log_level_t level) {
+    if (!atomic_load_explicit(&s_color, memory_order_relaxed)) {
+        return "";
+    }
+    switch (level) {
+        case LOG_LEVEL_ERROR: return "\033[1;31m";   /* Bold red */
+        case LOG_LEVEL_WARN:  return "\033[33m";     /* Yellow */
+        case LOG_LEVEL_DEBUG: return "\033[36m";     /* Cyan */
+        case LOG_LEVEL_TRACE: return "\033[90m";     /* Grey */
+        default:              return "";
+    }
+}
+
+void log_set_color(bool on) {
+    atomic_store_explicit(&s_color, on, memory_order_relaxed);
+}
+
+const char *log_msg_format(log_msg_t id) {
+    return ((unsigned)id < LOG_MSG_COUNT) ? MSG_FORMATS[id] : NULL;
+}
+
+const char *log_tag_str(log_tag_t tag) {
+    return ((unsigned)tag < LOG_TAG_COUNT) ? TAG_NAMES[tag] : "?";
+}
+
+bool log_tag_from_str(const char *name, log_tag_t *out) {
+    for (int i = 0; i < LOG_TAG_COUNT; i++) {
+        if (strcasecmp(name, TAG_NAMES[i]) == 0) {
+            *out = (log_tag_t)i;
+            return true;
+        }
+    }
+    return false;
+}
+
+bool log_level_from_str(const char *name, log_level_t *out) {
+    for (int level = LOG_LEVEL_ERROR; level <= LOG_LEVEL_TRACE; level++) {
+        const char *full = log_level_str((log_level_t)level);
+        if (strcasecmp(name, full) == 0 ||
+            (name[0] != '\0' && name[1] == '\0' && toupper((unsigned char)name[0]) == full[0])) {
+            *out = (log_level_t)level;
+            return true;
+        }
+    }
+    return false;
+}
+
+void log_tag_set_level(log_tag_t tag, log_level_t level) {
+    if ((unsigned)tag < LOG_TAG_COUNT) {
+        atomic_store_explicit(&g_log_tag_levels[tag], (unsigned char)level, memory_order_relaxed);
+    }
+}
+
+log_level_t log_tag_get_level(log_tag_t tag) {
+    if ((unsigned)tag >= LOG_TAG_COUNT) {
+        return LOG_TAG_DEFAULT_LEVEL;
+    }
+    return (log_level_t)atomic_load_explicit(&g_log_tag_levels[tag], memory_order_relaxed);
+}
@@ -1,29 +1,68 @@
 /**
  * @file uart_logger.c
- * @brief UART log drain task
+ * @brief UART log sink
  *
- * Runs on Core 1, drains log streams to UART on GPIO6.
+ * Sends the log drain entries to UART1 (TX pin from Kconfig
+ * KEYER_LOG_UART_TX_GPIO), colored by level.
  */
 
 #include "rt_log.h"
+#include "log_drain.h"
 #include <stdio.h>
 #include <string.h>
-#include <inttypes.h>
 
 #ifdef ESP_PLATFORM
 /* ESP-IDF includes */
+#include "sdkconfig.h"
 #include "driver/uart.h"
 #include "driver/gpio.h"
-#include "freertos/FreeRTOS.h"
-#include "freertos/task.h"
-#include "esp_timer.h"
 
 #define UART_LOG_PORT    UART_NUM_1
-#define UART_LOG_TX_PIN  GPIO_NUM_6
+#if CONFIG_KEYER_LOG_UART_TX_GPIO
+#define UART_LOG_TX_PIN  CONFIG_KEYER_LOG_UART_TX_GPIO
+#else
+#define UART_LOG_TX_PIN  UART_PIN_NO_CHANGE
+#endif
 #define UART_LOG_BAUD    115200
 #define UART_BUF_SIZE    256
+#define UART_TX_BUF_SIZE 2048  /* Writes only copy into the ring */
+
+static char s_format_buf[LOG_LINE_MAX];
+static int s_sink = -1;
 
-static char s_format_buf[256];
+/**
+ * @brief Format and send a log entry to the UART (colored by level)
+ *
+ * Busy while the TX ring has no room for the whole line: at 115200 baud
+ * the drain must not wait for the wire.
+ */
+static bool uart_sink_write(const log_entry_t *entry, const char *stream) {
+    (void)stream;
+    size_t len = log_entry_format(entry, s_format_buf, sizeof(s_format_buf));
+    if (len < 2) {
+        return true;
+    }
+
+    const char *color = log_level_color(entry->level);
+    size_t needed = len;
+    if (color[0] != '\0') {
+        needed += strlen(color) + sizeof(LOG_COLOR_RESET) - 1;
+    }
+    size_t room = 0;
+    if (uart_get_tx_buffer_free_size(UART_LOG_PORT, &room) != ESP_OK || room < needed) {
+        return false;
+    }
+
+    if (color[0] != '\0') {
+        /* Reset before the CR/LF so the next line starts plain */
+        uart_write_bytes(UART_LOG_PORT, color, strlen(color));
+        uart_write_bytes(UART_LOG_PORT, s_format_buf, len - 2);
+        uart_write_bytes(UART_LOG_PORT, LOG_COLOR_RESET "\r\n", sizeof(LOG_COLOR_RESET "\r\n") - 1);
+    } else {
+        uart_write_bytes(UART_LOG_PORT, s_format_buf, len);
+    }
+    return true;
+}
 
 /**
  * @brief Initialize UART logger
@@ -39,82 +78,24 @@ void uart_logger_init(void) {
     };
 
     /* Configure UART */
-    uart_driver_install(UART_LOG_PORT, UART_BUF_SIZE, 0, 0, NULL, 0);
+    uart_driver_install(UART_LOG_PORT, UART_BUF_SIZE, UART_TX_BUF_SIZE, 0, NULL, 0);
     uart_param_config(UART_LOG_PORT, &uart_config);
     uart_set_pin(UART_LOG_PORT, UART_LOG_TX_PIN, UART_PIN_NO_CHANGE,
                  UART_PIN_NO_CHANGE, UART_PIN_NO_CHANGE);
+
+    s_sink = log_drain_add_sink("uart", uart_sink_write, NULL);
 }
 
-/**
- * @brief Format and send log entry to UART
- */
-static void send_entry(const log_entry_t *entry) {
-    /* Format: [timestamp_us] LEVEL: message\r\n */
-    int len = snprintf(s_format_buf, sizeof(s_format_buf),
-                       "[%lld] %s: %.*s\r\n",
-                       (long long)entry->timestamp_us,
-                       log_level_str(entry->level),
-                       (int)entry->len,
-                       entry->msg);
-
-    if (len > 0) {
-        uart_write_bytes(UART_LOG_PORT, s_format_buf, (size_t)len);
-    }
+void uart_logger_detach(void) {
+    log_drain_remove_sink(s_sink);
+    s_sink = -1;
 }
 
-/**
- * @brief UART logger task
- *
- * Drains RT and BG log streams to UART.
- * Runs on Core 1, priority normal.
- */
-void uart_logger_task(void *arg) {
-    (void)arg;
-
-    log_entry_t entry;
-    uint32_t last_dropped_report_ms = 0;
-
-    for (;;) {
-        bool had_entry = false;
-
-        /* Drain RT log stream (higher priority) */
-        while (log_stream_drain(&g_rt_log_stream, &entry)) {
-            send_entry(&entry);
-            had_entry = true;
-        }
-
-        /* Drain BG log stream */
-        while (log_stream_drain(&g_bg_log_stream, &entry)) {
-            send_entry(&entry);
-            had_entry = true;
-        }
-
-        /* Report dropped messages periodically */
-        uint32_t now_ms = (uint32_t)(esp_timer_get_time() / 1000);
-        if (now_ms - last_dropped_report_ms >= 10000) {
-            uint32_t rt_dropped = log_stream_dropped(&g_rt_log_stream);
-            uint32_t bg_dropped = log_stream_dropped(&g_bg_log_stream);
-
-            if (rt_dropped > 0 || bg_dropped > 0) {
-                int len = snprintf(s_format_buf, sizeof(s_format_buf),
-                                   "[%" PRIu32 "] WARN: Dropped logs: RT=%" PRIu32 " BG=%" PRIu32 "\r\n",
-                                   now_ms, rt_dropped, bg_dropped);
-                if (len > 0) {
-                    uart_write_bytes(UART_LOG_PORT, s_format_buf, (size_t)len);
-                }
-
-                log_stream_reset_dropped(&g_rt_log_stream);
-                log_stream_reset_dropped(&g_bg_log_stream);
-            }
-
-            last_dropped_report_ms = now_ms;
-        }
-
-        /* Sleep if no entries */
-        if (!had_entry) {
-            vTaskDelay(pdMS_TO_TICKS(1));
-        }
+void uart_logger_write(const char *data, size_t len) {
+    if (data == NULL || len == 0) {
+        return;
     }
+    uart_write_bytes(UART_LOG_PORT, data, len);
 }
 
 #else
@@ -124,8 +105,13 @@ void uart_logger_init(void) {
     /* No-op on host */
 }
 
-void uart_logger_task(void *arg) {
-    (void)arg;
+void uart_logger_detach(void) {
+    /* No-op on host */
+}
+
+void uart_logger_write(const char *data, size_t len) {
+    (void)data;
+    (void)len;
     /* No-op on host */
 }
 
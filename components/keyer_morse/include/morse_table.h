@@ -1,9 +1,12 @@
 /**
  * @file morse_table.h
- * @brief ITU Morse code pattern to character lookup
+ * @brief ITU Morse code table, lookup in both directions
  *
- * Static lookup table for decoding morse patterns (e.g., ".-" -> 'A').
- * Uses linear search over ~50 entries (<5us lookup time).
+ * One table of letters, digits, punctuation and prosigns shared by the
+ * decoder (pattern -> character) and the text keyer (character ->
+ * pattern). Both directions are generated at compile time from the same
+ * list: decoding is a linear search over ~50 entries (<5us), encoding
+ * an array index.
  */
 
 #ifndef KEYER_MORSE_TABLE_H
@@ -47,6 +50,15 @@ const char *morse_table_reverse(char c);
  */
 unsigned morse_table_count(void);
 
+/**
+ * @brief Get a table entry by index
+ *
+ * @param index Entry index, 0 to morse_table_count() - 1
+ * @param pattern_out Output: entry pattern (can be NULL)
+ * @return Entry character, or '\0' if index is out of range
+ */
+char morse_table_at(unsigned index, const char **pattern_out);
+
 /**
  * @brief Check if text starts with a prosign tag
  *
@@ -61,6 +73,30 @@ unsigned morse_table_count(void);
  */
 size_t morse_match_prosign(const char *text, const char **pattern_out);
 
+/** Most characters run together by morse_build_prosign() */
+#define MORSE_PROSIGN_CHARS_MAX 4
+
+/** Pattern buffer large enough for any morse_build_prosign() result */
+#define MORSE_PROSIGN_PATTERN_MAX 32
+
+/**
+ * @brief Build a run-together character from "<XY>" text
+ *
+ * Any 2 to MORSE_PROSIGN_CHARS_MAX characters in angle brackets are sent
+ * as one character, without the gaps between them. Covers the named
+ * prosigns and anything else the operator runs together.
+ *
+ * @param text Text starting with '<'
+ * @param pattern Output pattern
+ * @param pattern_len Size of pattern (MORSE_PROSIGN_PATTERN_MAX is enough)
+ * @return Characters consumed including both brackets, 0 if not valid
+ *
+ * Example:
+ *   morse_build_prosign("<BK> TU", buf, len) -> 4, buf = "-...-.-"
+ *   morse_build_prosign("<HELLO>", buf, len) -> 0 (too long)
+ */
+size_t morse_build_prosign(const char *text, char *pattern, size_t pattern_len);
+
 /**
  * @brief Get prosign display name for pattern
  *
@@ -0,0 +1,232 @@
+/**
+ * @file morse_table.c
+ * @brief ITU Morse code table, lookup in both directions
+ */
+
+#include "morse_table.h"
+#include <stddef.h>
+#include <string.h>
+
+/* ============================================================================
+ * Morse Code Table (ITU Standard)
+ * ============================================================================ */
+
+/**
+ * ITU International Morse Code table, X(character, pattern)
+ *
+ * Letters: A-Z (26)
+ * Numbers: 0-9 (10)
+ * Punctuation and prosigns
+ *
+ * Both lookup directions are generated from this one list.
+ */
+#define MORSE_CHARACTERS(X) \
+    /* Letters A-Z */ \
+    X('A',  ".-")     \
+    X('B',  "-...")   \
+    X('C',  "-.-.")   \
+    X('D',  "-..")    \
+    X('E',  ".")      \
+    X('F',  "..-.")   \
+    X('G',  "--.")    \
+    X('H',  "....")   \
+    X('I',  "..")     \
+    X('J',  ".---")   \
+    X('K',  "-.-")    \
+    X('L',  ".-..")   \
+    X('M',  "--")     \
+    X('N',  "-.")     \
+    X('O',  "---")    \
+    X('P',  ".--.")   \
+    X('Q',  "--.-")   \
+    X('R',  ".-.")    \
+    X('S',  "...")    \
+    X('T',  "-")      \
+    X('U',  "..-")    \
+    X('V',  "...-")   \
+    X('W',  ".--")    \
+    X('X',  "-..-")   \
+    X('Y',  "-.--")   \
+    X('Z',  "--..")   \
+    /* Numbers 0-9 */ \
+    X('0',  "-----")  \
+    X('1',  ".----")  \
+    X('2',  "..---")  \
+    X('3',  "...--")  \
+    X('4',  "....-")  \
+    X('5',  ".....")  \
+    X('6',  "-....")  \
+    X('7',  "--...")  \
+    X('8',  "---..")  \
+    X('9',  "----.")  \
+    /* Punctuation */ \
+    X('.',  ".-.-.-") /* Period */ \
+    X(',',  "--..--") /* Comma */ \
+    X('?',  "..--..") /* Question mark */ \
+    X('\'', ".----.") /* Apostrophe */ \
+    X('!',  "-.-.--") /* Exclamation (KW) */ \
+    X('/',  "-..-.")  /* Slash */ \
+    X('(',  "-.--.")  /* Open parenthesis */ \
+    X(')',  "-.--.-") /* Close parenthesis */ \
+    X('&',  ".-...")  /* Ampersand (AS) */ \
+    X(':',  "---...") /* Colon */ \
+    X(';',  "-.-.-.") /* Semicolon */ \
+    X('=',  "-...-")  /* Equals / BT prosign */ \
+    X('+',  ".-.-.")  /* Plus / AR prosign */ \
+    X('-',  "-....-") /* Hyphen */ \
+    X('_',  "..--.-") /* Underscore */ \
+    X('"',  ".-..-.") /* Quotation mark */ \
+    X('$',  "...-..-") /* Dollar sign */ \
+    X('@',  ".--.-.") /* At sign */ \
+    /* Prosigns (mapped to printable characters) */ \
+    X('*',  "...-.-") /* SK (end of contact) */ \
+    X('<',  "-.-.-")  /* CT (commence transmission) / KA */ \
+    X('#',  "........") /* Error signal (8 dots) */
+
+typedef struct {
+    const char *pattern;
+    char character;
+} morse_entry_t;
+
+/** Pattern -> character, searched in table order */
+static const morse_entry_t MORSE_TABLE[] = {
+#define MORSE_ENTRY(c, p) { p, c },
+    MORSE_CHARACTERS(MORSE_ENTRY)
+#undef MORSE_ENTRY
+};
+
+#define MORSE_TABLE_SIZE (sizeof(MORSE_TABLE) / sizeof(MORSE_TABLE[0]))
+
+/** Character -> pattern, indexed by ASCII code (NULL = no pattern) */
+static const char *const PATTERN_BY_CHAR[128] = {
+#define MORSE_REVERSE(c, p) [(unsigned char)(c)] = p,
+    MORSE_CHARACTERS(MORSE_REVERSE)
+#undef MORSE_REVERSE
+};
+
+/* ============================================================================
+ * Prosign Table for Text Keyer
+ * ============================================================================ */
+
+typedef struct {
+    const char *tag;      /* e.g., "<SK>" */
+    const char *pattern;  /* e.g., "...-.-" */
+} prosign_entry_t;
+
+static const prosign_entry_t PROSIGN_TABLE[] = {
+    { "<SK>", "...-.-" },   /* End of contact */
+    { "<AR>", ".-.-." },    /* End of message */
+    { "<BT>", "-...-" },    /* Break/Pause */
+    { "<KN>", "-.--." },    /* Specific station only */
+    { "<AS>", ".-..." },    /* Wait */
+    { "<SN>", "...-." },    /* Understood (also VE) */
+    { "<KA>", "-.-.-" },    /* Starting signal */
+    { "<BK>", "-...-.-" },  /* Break-in */
+};
+
+#define PROSIGN_TABLE_SIZE (sizeof(PROSIGN_TABLE) / sizeof(PROSIGN_TABLE[0]))
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+char morse_table_lookup(const char *pattern) {
+    if (pattern == NULL || pattern[0] == '\0') {
+        return '\0';
+    }
+
+    for (size_t i = 0; i < MORSE_TABLE_SIZE; i++) {
+        if (strcmp(MORSE_TABLE[i].pattern, pattern) == 0) {
+            return MORSE_TABLE[i].character;
+        }
+    }
+
+    return '\0';  /* Not found */
+}
+
+const char *morse_table_reverse(char c) {
+    /* Convert lowercase to uppercase for lookup */
+    if (c >= 'a' && c <= 'z') {
+        c = (char)(c - 'a' + 'A');
+    }
+
+    unsigned char index = (unsigned char)c;
+    if (index >= sizeof(PATTERN_BY_CHAR) / sizeof(PATTERN_BY_CHAR[0])) {
+        return NULL;
+    }
+    return PATTERN_BY_CHAR[index];
+}
+
+char morse_table_at(unsigned index, const char **pattern_out) {
+    if (index >= MORSE_TABLE_SIZE) {
+        return '\0';
+    }
+    if (pattern_out != NULL) {
+        *pattern_out = MORSE_TABLE[index].pattern;
+    }
+    return MORSE_TABLE[index].character;
+}
+
+unsigned morse_table_count(void) {
+    return (unsigned)MORSE_TABLE_SIZE;
+}
+
+size_t morse_match_prosign(const char *text, const char **pattern_out) {
+    if (text == NULL || text[0] != '<') {
+        return 0;
+    }
+
+    for (size_t i = 0; i < PROSIGN_TABLE_SIZE; i++) {
+        size_t len = strlen(PROSIGN_TABLE[i].tag);
+        if (strncmp(text, PROSIGN_TABLE[i].tag, len) == 0) {
+            if (pattern_out != NULL) {
+                *pattern_out = PROSIGN_TABLE[i].pattern;
+            }
+            return len;
+        }
+    }
+
+    return 0;
+}
+
+size_t morse_build_prosign(const char *text, char *pattern, size_t pattern_len) {
+    if (text == NULL || text[0] != '<' || pattern == NULL || pattern_len == 0) {
+        return 0;
+    }
+
+    size_t pos = 0;
+    size_t chars = 0;
+    for (const char *p = text + 1; *p != '>'; p++) {
+        const char *part = (*p != '\0' && *p != '<' && *p != ' ')
+                               ? morse_table_reverse(*p) : NULL;
+        if (part == NULL || ++chars > MORSE_PROSIGN_CHARS_MAX) {
+            return 0;
+        }
+        size_t len = strlen(part);
+        if (pos + len >= pattern_len) {
+            return 0;
+        }
+        memcpy(&pattern[pos], part, len);
+        pos += len;
+    }
+    if (chars < 2) {
+        return 0;
+    }
+
+    pattern[pos] = '\0';
+    return chars + 2;  /* Brackets included */
+}
+
+const char *morse_get_prosign_tag(const char *pattern) {
+    if (pattern == NULL) {
+        return NULL;
+    }
+
+    for (size_t i = 0; i < PROSIGN_TABLE_SIZE; i++) {
+        if (strcmp(PROSIGN_TABLE[i].pattern, pattern) == 0) {
+            return PROSIGN_TABLE[i].tag;
+        }
+    }
+
+    return NULL;
+}
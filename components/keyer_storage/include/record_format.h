@@ -0,0 +1,95 @@
+/**
+ * @file record_format.h
+ * @brief SD card recording file names and line format
+ *
+ * Each boot is one session: the recorder writes KEYnnnn.CSV (keying
+ * stream), TXTnnnn.TXT (decoded text) and LOGnnnn.TXT (log lines) with
+ * the same nnnn, one past the highest found on the card. 8.3 names, so
+ * the card reads anywhere without long file name support.
+ *
+ * Keying file, one line per stream sample that is not a silence marker:
+ *
+ *   ms,gpio,key,audio,flags
+ *   1520,1,1,0,21
+ *
+ * ms counts RT ticks from the start of the recording (silence markers
+ * are expanded), gpio is the paddle/key bit mask, flags the sample flags,
+ * all decimal. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_RECORD_FORMAT_H
+#define KEYER_RECORD_FORMAT_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include "sample.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** "KEY0001.CSV" plus the terminator */
+#define RECORD_NAME_LEN     12
+
+/** Highest session number (four digits) */
+#define RECORD_SESSION_MAX  9999
+
+/** First line of a keying file */
+#define RECORD_KEYING_HEADER "ms,gpio,key,audio,flags\n"
+
+/** Longest keying line, terminator included */
+#define RECORD_LINE_MAX     40
+
+/**
+ * @brief Build a session file name
+ *
+ * @param buf Output, at least RECORD_NAME_LEN bytes
+ * @param len Size of buf
+ * @param prefix Three letters ("KEY", "TXT", "LOG")
+ * @param ext Three letters ("CSV", "TXT")
+ * @param session Session number (1-9999)
+ * @return true if it fit
+ */
+bool record_session_name(char *buf, size_t len, const char *prefix,
+                         const char *ext, uint16_t session);
+
+/**
+ * @brief Session number of a recording file name
+ *
+ * Accepts the three letter prefix, four digits and any three letter
+ * extension, in either case.
+ *
+ * @param name File name (no directory)
+ * @return Session number, 0 if not a recording
+ */
+uint16_t record_session_from_name(const char *name);
+
+/**
+ * @brief Check a name given on the console before deleting it
+ *
+ * A plain file name on the card root: no directories, no "..", at most
+ * 8.3 characters.
+ *
+ * @param name File name
+ * @return true if safe to join to the mount point
+ */
+bool record_name_is_safe(const char *name);
+
+/**
+ * @brief Format one keying stream sample
+ *
+ * @param buf Output, RECORD_LINE_MAX bytes is always enough
+ * @param len Size of buf
+ * @param ms Ticks since the recording started
+ * @param sample Sample (not a silence marker)
+ * @return Characters written (newline included), 0 if it did not fit
+ */
+size_t record_format_sample(char *buf, size_t len, uint32_t ms,
+                            const stream_sample_t *sample);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_RECORD_FORMAT_H */
@@ -0,0 +1,94 @@
+/**
+ * @file recorder.h
+ * @brief Keying, decoded text and log recording to the SD card
+ *
+ * Three independent files per boot session (see record_format.h), each
+ * switched on and off at runtime:
+ * - system.sd_record: keying stream, best-effort consumer (KEYnnnn.CSV)
+ * - system.sd_transcript: decoded text while the decoder is on, decoder
+ *   cursor (TXTnnnn.TXT)
+ * - system.sd_log: RT and BG log lines, a log drain sink
+ *   (LOGnnnn.TXT)
+ *
+ * Switching a recording off and on again appends to the same file.
+ * Writes go through stdio buffers and are flushed to the card once per
+ * RECORDER_FLUSH_MS, so at most that much is lost on power off. Every
+ * file operation runs in io_task: a stalled card delays only that task,
+ * never bg_task or the RT task.
+ */
+
+#ifndef KEYER_RECORDER_H
+#define KEYER_RECORDER_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include "stream.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Interval between flushes to the card */
+#define RECORDER_FLUSH_MS 1000
+
+/**
+ * @brief Recorder state, for the console
+ */
+typedef struct {
+    uint16_t session;       /**< Session number, 0 if no card */
+    bool keying;            /**< KEYnnnn.CSV open */
+    bool transcript;        /**< TXTnnnn.TXT open */
+    bool log;               /**< LOGnnnn.TXT open */
+    uint32_t bytes;         /**< Bytes written this boot */
+    uint32_t errors;        /**< Failed opens and writes */
+    uint32_t dropped;       /**< Keying samples skipped (consumer lag) */
+} recorder_status_t;
+
+/**
+ * @brief Pick the session number and attach to the keying stream
+ *
+ * Call from app_main after storage_init(). No-op without a card.
+ *
+ * @param stream Keying stream to record
+ */
+void recorder_init(const keying_stream_t *stream);
+
+/**
+ * @brief Open and close files on config changes, write, flush (io_task)
+ *
+ * @param now_us Current time
+ */
+void recorder_tick(int64_t now_us);
+
+/**
+ * @brief Append formatted log text (SD log drain sink)
+ *
+ * Dropped unless system.sd_log is on and the file is open. Never waits:
+ * while the file is being flushed the call returns false.
+ *
+ * @param data Bytes to write
+ * @param len Number of bytes
+ * @return false if busy (nothing written, try again)
+ */
+bool recorder_log_write(const char *data, size_t len);
+
+/**
+ * @brief Check if a file is one the recorder has open
+ *
+ * @param name File name (no directory)
+ */
+bool recorder_is_open(const char *name);
+
+/**
+ * @brief Get the recorder state
+ *
+ * @param status Filled in
+ */
+void recorder_get_status(recorder_status_t *status);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_RECORDER_H */
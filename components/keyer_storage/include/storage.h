@@ -0,0 +1,105 @@
+/**
+ * @file storage.h
+ * @brief SD card on SPI, FAT mounted at STORAGE_MOUNT_POINT
+ *
+ * Optional: with any of the four hardware.gpio_sd_* pins at 0 there is
+ * no card and every call reports not mounted. Files live on the card
+ * root. Card access blocks for milliseconds, so only Core 1 tasks (never
+ * the RT task) may touch it.
+ */
+
+#ifndef KEYER_STORAGE_H
+#define KEYER_STORAGE_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** VFS mount point of the card */
+#define STORAGE_MOUNT_POINT "/sdcard"
+
+/** Mount point, '/', 8.3 name and terminator */
+#define STORAGE_PATH_MAX    (sizeof(STORAGE_MOUNT_POINT) + 13)
+
+/**
+ * @brief SD card SPI pins (GPIO numbers, 0 = not fitted)
+ */
+typedef struct {
+    uint8_t sck;
+    uint8_t mosi;
+    uint8_t miso;
+    uint8_t cs;
+} storage_pins_t;
+
+/**
+ * @brief One file on the card, passed to the storage_list() callback
+ */
+typedef struct {
+    const char *name;   /**< File name (no directory) */
+    uint32_t size;      /**< Bytes */
+} storage_file_t;
+
+/** storage_list() callback */
+typedef void (*storage_list_fn)(const storage_file_t *file, void *ctx);
+
+/**
+ * @brief Set up the SPI bus and mount the card (app_main)
+ *
+ * A missing or unreadable card is logged and left unmounted; the card
+ * is never formatted.
+ *
+ * @param pins SPI pins
+ * @return true if mounted
+ */
+bool storage_init(const storage_pins_t *pins);
+
+/**
+ * @brief Check if a card is mounted
+ */
+bool storage_is_mounted(void);
+
+/**
+ * @brief Card capacity and free space
+ *
+ * @param total_bytes Filled with the FAT volume size
+ * @param free_bytes Filled with the free space
+ * @return true on success
+ */
+bool storage_usage(uint64_t *total_bytes, uint64_t *free_bytes);
+
+/**
+ * @brief Build the full path of a file on the card root
+ *
+ * @param buf Output, STORAGE_PATH_MAX bytes
+ * @param len Size of buf
+ * @param name File name (no directory)
+ * @return true if it fit
+ */
+bool storage_path(char *buf, size_t len, const char *name);
+
+/**
+ * @brief Call fn for every file on the card root (directories skipped)
+ *
+ * @param fn Callback
+ * @param ctx Passed to fn
+ * @return Number of files, 0 if not mounted
+ */
+size_t storage_list(storage_list_fn fn, void *ctx);
+
+/**
+ * @brief Delete a file from the card root
+ *
+ * @param name File name (no directory)
+ * @return true if deleted
+ */
+bool storage_remove(const char *name);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_STORAGE_H */
@@ -0,0 +1,88 @@
+/**
+ * @file record_format.c
+ * @brief SD card recording file names and line format implementation
+ */
+
+#include "record_format.h"
+#include <ctype.h>
+#include <stdio.h>
+#include <string.h>
+
+/** "KEY0001" */
+#define BASE_LEN 7
+
+static bool is_letters(const char *s, size_t n) {
+    for (size_t i = 0; i < n; i++) {
+        if (!isalpha((unsigned char)s[i])) {
+            return false;
+        }
+    }
+    return true;
+}
+
+bool record_session_name(char *buf, size_t len, const char *prefix,
+                         const char *ext, uint16_t session) {
+    if (buf == NULL || len < RECORD_NAME_LEN ||
+        session == 0 || session > RECORD_SESSION_MAX) {
+        return false;
+    }
+    int n = snprintf(buf, len, "%.3s%04u.%.3s", prefix, (unsigned)session, ext);
+    return n == RECORD_NAME_LEN - 1;
+}
+
+uint16_t record_session_from_name(const char *name) {
+    if (name == NULL || strlen(name) != RECORD_NAME_LEN - 1 ||
+        !is_letters(name, 3) || name[BASE_LEN] != '.' ||
+        !is_letters(name + BASE_LEN + 1, 3)) {
+        return 0;
+    }
+
+    unsigned session = 0;
+    for (size_t i = 3; i < BASE_LEN; i++) {
+        if (!isdigit((unsigned char)name[i])) {
+            return 0;
+        }
+        session = session * 10U + (unsigned)(name[i] - '0');
+    }
+    return (uint16_t)session;
+}
+
+bool record_name_is_safe(const char *name) {
+    if (name == NULL || name[0] == '\0' || name[0] == '.') {
+        return false;
+    }
+
+    size_t base = 0;
+    size_t ext = 0;
+    bool dot = false;
+    for (const char *p = name; *p != '\0'; p++) {
+        if (*p == '/' || *p == '\\' || *p == ':') {
+            return false;
+        }
+        if (*p == '.') {
+            if (dot) {
+                return false;
+            }
+            dot = true;
+        } else if (dot) {
+            ext++;
+        } else {
+            base++;
+        }
+    }
+    return base <= 8 && ext <= 3;
+}
+
+size_t record_format_sample(char *buf, size_t len, uint32_t ms,
+                            const stream_sample_t *sample) {
+    int n = snprintf(buf, len, "%lu,%u,%u,%u,%u\n",
+                     (unsigned long)ms,
+                     (unsigned)sample->gpio.bits,
+                     (unsigned)sample->local_key,
+                     (unsigned)sample->audio_level,
+                     (unsigned)sample->flags);
+    if (n < 0 || (size_t)n >= len) {
+        return 0;
+    }
+    return (size_t)n;
+}
@@ -0,0 +1,243 @@
+/**
+ * @file recorder.c
+ * @brief Keying, decoded text and log recording to the SD card
+ */
+
+#include "recorder.h"
+#include "record_format.h"
+#include "storage.h"
+#include "consumer.h"
+#include "decoder.h"
+#include "config.h"
+
+#include <stdatomic.h>
+#include <stdio.h>
+#include <string.h>
+#include <strings.h>
+
+#include "freertos/FreeRTOS.h"
+#include "freertos/semphr.h"
+#include "esp_log.h"
+
+static const char *TAG = "recorder";
+
+/** Decoded characters read per call */
+#define TEXT_CHUNK 16
+
+/**
+ * @brief One recording file
+ */
+typedef struct {
+    const char *prefix;
+    const char *ext;
+    FILE *file;
+    bool failed;    /**< Open failed: no retry until switched off */
+    char name[RECORD_NAME_LEN];
+} rec_file_t;
+
+static rec_file_t s_keying = { .prefix = "KEY", .ext = "CSV" };
+static rec_file_t s_text = { .prefix = "TXT", .ext = "TXT" };
+static rec_file_t s_log = { .prefix = "LOG", .ext = "TXT" };
+
+static uint16_t s_session = 0;
+static const keying_stream_t *s_stream = NULL;
+
+/* Keying: consumer and ticks since the recording started */
+static best_effort_consumer_t s_consumer;
+static uint32_t s_keying_ms = 0;
+
+/* Transcript: next decoded character to write */
+static uint32_t s_text_cursor = 0;
+
+/* The log file is written from the log drain task */
+static SemaphoreHandle_t s_log_lock = NULL;
+
+static int64_t s_last_flush_us = 0;
+static atomic_uint s_bytes = 0;
+static atomic_uint s_errors = 0;
+
+/* ============================================================================
+ * Helpers
+ * ============================================================================ */
+
+static void find_session(const storage_file_t *file, void *ctx) {
+    uint16_t *highest = ctx;
+    uint16_t session = record_session_from_name(file->name);
+    if (session > *highest) {
+        *highest = session;
+    }
+}
+
+static bool rec_open(rec_file_t *rec) {
+    char path[STORAGE_PATH_MAX];
+    if (!record_session_name(rec->name, sizeof(rec->name), rec->prefix, rec->ext, s_session) ||
+        !storage_path(path, sizeof(path), rec->name)) {
+        return false;
+    }
+
+    rec->file = fopen(path, "a");
+    if (rec->file == NULL) {
+        ESP_LOGW(TAG, "Cannot open %s", rec->name);
+        atomic_fetch_add(&s_errors, 1);
+        return false;
+    }
+    ESP_LOGI(TAG, "Recording to %s", rec->name);
+    return true;
+}
+
+static void rec_close(rec_file_t *rec) {
+    if (rec->file != NULL) {
+        fclose(rec->file);
+        rec->file = NULL;
+        ESP_LOGI(TAG, "Closed %s", rec->name);
+    }
+}
+
+static void rec_write(rec_file_t *rec, const char *data, size_t len) {
+    if (fwrite(data, 1, len, rec->file) == len) {
+        atomic_fetch_add(&s_bytes, (unsigned)len);
+    } else {
+        atomic_fetch_add(&s_errors, 1);
+    }
+}
+
+static void rec_flush(rec_file_t *rec) {
+    if (rec->file != NULL && fflush(rec->file) != 0) {
+        atomic_fetch_add(&s_errors, 1);
+    }
+}
+
+/** Open or close to follow the config toggle, true when just opened */
+static bool rec_follow(rec_file_t *rec, bool enabled) {
+    if (enabled && rec->file == NULL && !rec->failed) {
+        rec->failed = !rec_open(rec);
+        return !rec->failed;
+    }
+    if (!enabled) {
+        rec_close(rec);
+        rec->failed = false;
+    }
+    return false;
+}
+
+static void record_keying(void) {
+    if (rec_follow(&s_keying, CONFIG_GET_SD_RECORD())) {
+        /* New or resumed recording: start from the live position */
+        best_effort_consumer_init(&s_consumer, s_stream, 0);
+        best_effort_consumer_track(&s_consumer, consumer_lag_register("recorder"));
+        s_keying_ms = 0;
+        if (ftell(s_keying.file) == 0) {
+            rec_write(&s_keying, RECORD_KEYING_HEADER, strlen(RECORD_KEYING_HEADER));
+        }
+    }
+    if (s_keying.file == NULL) {
+        return;
+    }
+
+    stream_sample_t sample;
+    while (best_effort_consumer_tick(&s_consumer, &sample)) {
+        if (sample_is_silence(&sample)) {
+            s_keying_ms += sample_silence_ticks(&sample);
+            continue;
+        }
+        char line[RECORD_LINE_MAX];
+        size_t len = record_format_sample(line, sizeof(line), s_keying_ms, &sample);
+        rec_write(&s_keying, line, len);
+        s_keying_ms++;
+    }
+}
+
+static void record_transcript(void) {
+    if (rec_follow(&s_text, CONFIG_GET_SD_TRANSCRIPT())) {
+        s_text_cursor = decoder_text_seq();
+    }
+    if (s_text.file == NULL) {
+        return;
+    }
+
+    decoded_char_t chars[TEXT_CHUNK];
+    size_t n;
+    while ((n = decoder_read_since(&s_text_cursor, chars, TEXT_CHUNK)) > 0) {
+        char text[TEXT_CHUNK];
+        for (size_t i = 0; i < n; i++) {
+            text[i] = chars[i].character;
+        }
+        rec_write(&s_text, text, n);
+    }
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void recorder_init(const keying_stream_t *stream) {
+    if (!storage_is_mounted()) {
+        return;
+    }
+
+    uint16_t highest = 0;
+    storage_list(find_session, &highest);
+    s_session = highest < RECORD_SESSION_MAX ? (uint16_t)(highest + 1) : RECORD_SESSION_MAX;
+    s_stream = stream;
+    s_log_lock = xSemaphoreCreateMutex();
+    ESP_LOGI(TAG, "Session %04u", s_session);
+}
+
+void recorder_tick(int64_t now_us) {
+    if (s_session == 0) {
+        return;
+    }
+
+    record_keying();
+    record_transcript();
+
+    xSemaphoreTake(s_log_lock, portMAX_DELAY);
+    rec_follow(&s_log, CONFIG_GET_SD_LOG());
+    xSemaphoreGive(s_log_lock);
+
+    if (now_us - s_last_flush_us >= (int64_t)RECORDER_FLUSH_MS * 1000) {
+        rec_flush(&s_keying);
+        rec_flush(&s_text);
+        xSemaphoreTake(s_log_lock, portMAX_DELAY);
+        rec_flush(&s_log);
+        xSemaphoreGive(s_log_lock);
+        s_last_flush_us = now_us;
+    }
+}
+
+bool recorder_log_write(const char *data, size_t len) {
+    if (s_session == 0 || s_log.file == NULL) {
+        return true;
+    }
+    /* The lock is held across a flush: report busy rather than wait */
+    if (xSemaphoreTake(s_log_lock, 0) != pdTRUE) {
+        return false;
+    }
+    if (s_log.file != NULL) {
+        rec_write(&s_log, data, len);
+    }
+    xSemaphoreGive(s_log_lock);
+    return true;
+}
+
+bool recorder_is_open(const char *name) {
+    const rec_file_t *recs[] = { &s_keying, &s_text, &s_log };
+    for (size_t i = 0; i < sizeof(recs) / sizeof(recs[0]); i++) {
+        if (recs[i]->file != NULL && strcasecmp(recs[i]->name, name) == 0) {
+            return true;
+        }
+    }
+    return false;
+}
+
+void recorder_get_status(recorder_status_t *status) {
+    *status = (recorder_status_t){
+        .session = s_session,
+        .keying = s_keying.file != NULL,
+        .transcript = s_text.file != NULL,
+        .log = s_log.file != NULL,
+        .bytes = atomic_load(&s_bytes),
+        .errors = atomic_load(&s_errors),
+        .dropped = s_keying.file != NULL ? (uint32_t)best_effort_consumer_dropped(&s_consumer) : 0,
+    };
+}
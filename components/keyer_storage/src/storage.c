@@ -0,0 +1,163 @@
+/**
+ * @file storage.c
+ * @brief SD card on SPI implementation
+ */
+
+#include "storage.h"
+#include <stdio.h>
+#include <string.h>
+
+bool storage_path(char *buf, size_t len, const char *name) {
+    int n = snprintf(buf, len, "%s/%s", STORAGE_MOUNT_POINT, name);
+    return n > 0 && (size_t)n < len;
+}
+
+#ifdef ESP_PLATFORM
+/* ESP-IDF target build */
+#include <dirent.h>
+#include <sys/stat.h>
+#include <unistd.h>
+#include "esp_vfs_fat.h"
+#include "sdmmc_cmd.h"
+#include "driver/sdspi_host.h"
+#include "driver/spi_common.h"
+#include "esp_log.h"
+
+static const char *TAG = "storage";
+
+/** SPI2 (FSPI): nothing else on the board uses SPI */
+#define STORAGE_SPI_HOST    SPI2_HOST
+
+/** Files open at once: the three recordings plus one spare */
+#define STORAGE_MAX_FILES   4
+
+static sdmmc_card_t *s_card = NULL;
+
+bool storage_init(const storage_pins_t *pins) {
+    if (pins->sck == 0 || pins->mosi == 0 || pins->miso == 0 || pins->cs == 0) {
+        ESP_LOGI(TAG, "No SD card pins, storage disabled");
+        return false;
+    }
+
+    spi_bus_config_t bus = {
+        .mosi_io_num = pins->mosi,
+        .miso_io_num = pins->miso,
+        .sclk_io_num = pins->sck,
+        .quadwp_io_num = -1,
+        .quadhd_io_num = -1,
+        .max_transfer_sz = 4000,
+    };
+    esp_err_t err = spi_bus_initialize(STORAGE_SPI_HOST, &bus, SDSPI_DEFAULT_DMA);
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "SPI bus init failed: %s", esp_err_to_name(err));
+        return false;
+    }
+
+    sdmmc_host_t host = SDSPI_HOST_DEFAULT();
+    host.slot = STORAGE_SPI_HOST;
+
+    sdspi_device_config_t slot = SDSPI_DEVICE_CONFIG_DEFAULT();
+    slot.gpio_cs = pins->cs;
+    slot.host_id = STORAGE_SPI_HOST;
+
+    esp_vfs_fat_mount_config_t mount = {
+        .format_if_mount_failed = false,
+        .max_files = STORAGE_MAX_FILES,
+        .allocation_unit_size = 16 * 1024,
+    };
+    err = esp_vfs_fat_sdspi_mount(STORAGE_MOUNT_POINT, &host, &slot, &mount, &s_card);
+    if (err != ESP_OK) {
+        ESP_LOGW(TAG, "No SD card mounted: %s", esp_err_to_name(err));
+        spi_bus_free(STORAGE_SPI_HOST);
+        s_card = NULL;
+        return false;
+    }
+
+    uint64_t bytes = (uint64_t)s_card->csd.capacity * (uint64_t)s_card->csd.sector_size;
+    ESP_LOGI(TAG, "SD card %s, %llu MB, mounted on %s",
+             s_card->cid.name, (unsigned long long)(bytes >> 20), STORAGE_MOUNT_POINT);
+    return true;
+}
+
+bool storage_is_mounted(void) {
+    return s_card != NULL;
+}
+
+bool storage_usage(uint64_t *total_bytes, uint64_t *free_bytes) {
+    if (s_card == NULL) {
+        return false;
+    }
+    return esp_vfs_fat_info(STORAGE_MOUNT_POINT, total_bytes, free_bytes) == ESP_OK;
+}
+
+size_t storage_list(storage_list_fn fn, void *ctx) {
+    if (s_card == NULL) {
+        return 0;
+    }
+    DIR *dir = opendir(STORAGE_MOUNT_POINT);
+    if (dir == NULL) {
+        return 0;
+    }
+
+    size_t count = 0;
+    struct dirent *ent;
+    while ((ent = readdir(dir)) != NULL) {
+        if (ent->d_type == DT_DIR) {
+            continue;
+        }
+        char path[STORAGE_PATH_MAX];
+        struct stat st;
+        if (!storage_path(path, sizeof(path), ent->d_name) || stat(path, &st) != 0) {
+            continue;
+        }
+        storage_file_t file = {
+            .name = ent->d_name,
+            .size = (uint32_t)st.st_size,
+        };
+        fn(&file, ctx);
+        count++;
+    }
+    closedir(dir);
+    return count;
+}
+
+bool storage_remove(const char *name) {
+    char path[STORAGE_PATH_MAX];
+    if (s_card == NULL || !storage_path(path, sizeof(path), name)) {
+        return false;
+    }
+    return unlink(path) == 0;
+}
+
+#else
+/* ============================================================================
+ * Host Stub Implementation (no card)
+ * ============================================================================ */
+
+bool storage_init(const storage_pins_t *pins) {
+    (void)pins;
+    return false;
+}
+
+bool storage_is_mounted(void) {
+    return false;
+}
+
+bool storage_usage(uint64_t *total_bytes, uint64_t *free_bytes) {
+    *total_bytes = 0;
+    *free_bytes = 0;
+    return false;
+}
+
+size_t storage_list(storage_list_fn fn, void *ctx) {
+    (void)fn;
+    (void)ctx;
+    return 0;
+}
+
+bool storage_remove(const char *name) {
+    (void)name;
+    return false;
+}
+
+#endif /* ESP_PLATFORM */
@@ -0,0 +1,132 @@
+/**
+ * @file abbrev.h
+ * @brief Keyboard mode abbreviations (;tu -> "TU 73 EE")
+ *
+ * A ';' typed in keyboard mode starts a shortcut: the letters and
+ * digits after it are held back until the next other character. If
+ * they name a stored abbreviation its text is queued instead, else
+ * they are queued as typed. ";;" queues a single ';'.
+ *
+ * Up to ABBREV_COUNT entries, kept in NVS. Changed from the console
+ * only; io_task writes them to NVS, so the keying path never does.
+ */
+
+#ifndef KEYER_ABBREV_H
+#define KEYER_ABBREV_H
+
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Number of abbreviations */
+#define ABBREV_COUNT 16
+
+/** Longest shortcut after the ';' */
+#define ABBREV_KEY_MAX 7
+
+/** Longest expansion */
+#define ABBREV_TEXT_MAX 47
+
+/** Starts a shortcut in keyboard mode */
+#define ABBREV_MARK ';'
+
+/**
+ * @brief One abbreviation
+ */
+typedef struct {
+    char key[ABBREV_KEY_MAX + 1];   /**< Uppercase letters/digits, "" = unused */
+    char text[ABBREV_TEXT_MAX + 1]; /**< Text queued instead */
+} abbrev_entry_t;
+
+/**
+ * @brief Shortcut being typed (keyboard session state)
+ */
+typedef struct {
+    bool active;                    /**< ';' typed, shortcut not ended yet */
+    char word[ABBREV_KEY_MAX + 1];  /**< Characters after the ';' */
+    size_t len;
+} abbrev_input_t;
+
+/**
+ * @brief Load the table from NVS (built-in defaults if nothing saved)
+ */
+void abbrev_init(void);
+
+/**
+ * @brief Find an abbreviation (case-insensitive)
+ *
+ * @return Expansion, NULL if key is not defined
+ */
+const char *abbrev_lookup(const char *key);
+
+/**
+ * @brief Define, replace or remove an abbreviation
+ *
+ * Saved to NVS by the next abbrev_save_tick().
+ *
+ * @param key Shortcut, 1-ABBREV_KEY_MAX letters/digits
+ * @param text Expansion (NULL or "" removes the key)
+ * @return 0 on success, -1 if invalid or full
+ */
+int abbrev_set(const char *key, const char *text);
+
+/**
+ * @brief Write the table to NVS if it changed since the last save
+ *
+ * Blocking; called from io_task only.
+ */
+void abbrev_save_tick(void);
+
+/**
+ * @brief Entry at a table position, for listing
+ *
+ * @return Entry, NULL if index is out of range or unused
+ */
+const abbrev_entry_t *abbrev_get(size_t index);
+
+/**
+ * @brief Reset a shortcut input (start of a keyboard session)
+ */
+void abbrev_input_init(abbrev_input_t *in);
+
+/**
+ * @brief Feed a typed character
+ *
+ * @param in Shortcut input
+ * @param c Typed character, uppercase
+ * @param out Characters to queue now (not NUL-terminated)
+ * @param out_len Size of out (ABBREV_TEXT_MAX + ABBREV_KEY_MAX + 2 fits any)
+ * @return Number of characters in out
+ */
+size_t abbrev_input_feed(abbrev_input_t *in, char c, char *out, size_t out_len);
+
+/**
+ * @brief Remove the last held character (or the ';')
+ *
+ * @return false if nothing is held: the backspace is for the queue
+ */
+bool abbrev_input_backspace(abbrev_input_t *in);
+
+/**
+ * @brief End a held shortcut (keyboard session ending)
+ *
+ * @return Number of characters in out, as abbrev_input_feed()
+ */
+size_t abbrev_input_flush(abbrev_input_t *in, char *out, size_t out_len);
+
+/**
+ * @brief Copy the held ";word" for display
+ *
+ * @return Number of characters copied (buf NUL-terminated)
+ */
+size_t abbrev_input_held(const abbrev_input_t *in, char *buf, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_ABBREV_H */
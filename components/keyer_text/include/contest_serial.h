@@ -0,0 +1,99 @@
+/**
+ * @file contest_serial.h
+ * @brief Contest serial number for %N message substitution
+ *
+ * Messages may contain:
+ * - %N  : current serial number; advances to the next one once sent
+ * - %N- : the number sent last (for repeats), does not advance
+ * - %N+ : advance first, then send the new number
+ *
+ * Numbers are sent with at least 3 digits (001). The number advances when
+ * it has been keyed, not when the message is queued: an aborted message
+ * does not use up a number. The next number is kept in NVS so a reboot in
+ * the middle of a contest does not restart at 001; io_task does the NVS
+ * write, never the keying path.
+ */
+
+#ifndef KEYER_CONTEST_SERIAL_H
+#define KEYER_CONTEST_SERIAL_H
+
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** First number of a contest */
+#define CONTEST_SERIAL_FIRST 1
+
+/** Largest number (wraps back to CONTEST_SERIAL_FIRST) */
+#define CONTEST_SERIAL_MAX 9999
+
+/**
+ * @brief Load the next serial number from NVS
+ */
+void contest_serial_init(void);
+
+/**
+ * @brief Next number %N will send
+ */
+uint32_t contest_serial_get(void);
+
+/**
+ * @brief Set the next number (saved by contest_serial_save_tick())
+ *
+ * @param serial CONTEST_SERIAL_FIRST..CONTEST_SERIAL_MAX
+ * @return 0 on success, -1 if out of range
+ */
+int contest_serial_set(uint32_t serial);
+
+/**
+ * @brief Expand %N, %N- and %N+ in a message
+ *
+ * Pure function: updates *serial in place, does not touch NVS.
+ * Output is truncated to fit out_len (always terminated).
+ *
+ * @param in Message text
+ * @param out Expanded text
+ * @param out_len Size of out
+ * @param serial Next number; advanced according to the codes used
+ * @return true if the message contained a serial code
+ */
+bool contest_serial_expand_with(const char *in, char *out, size_t out_len, uint32_t *serial);
+
+/**
+ * @brief Expand a message with the stored number, for keying
+ *
+ * Does not advance the number: each %N and %N+ is followed by a |Nn|
+ * command, and the text keyer calls contest_serial_sent() when it
+ * reaches it.
+ *
+ * @param in Message text
+ * @param out Expanded text
+ * @param out_len Size of out
+ */
+void contest_serial_expand(const char *in, char *out, size_t out_len);
+
+/**
+ * @brief A number has been keyed: store the next one
+ *
+ * Called from text_keyer_tick() (bg_task). Out-of-range values are ignored.
+ *
+ * @param next Number the next %N sends
+ */
+void contest_serial_sent(uint32_t next);
+
+/**
+ * @brief Write the number to NVS if it changed since the last save
+ *
+ * Blocking; called from io_task only.
+ */
+void contest_serial_save_tick(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_CONTEST_SERIAL_H */
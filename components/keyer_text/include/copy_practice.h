@@ -0,0 +1,85 @@
+/**
+ * @file copy_practice.h
+ * @brief Copy practice: trainer text echoed back on the paddle and scored
+ *
+ * A session plays one trainer item at a time through the sidetone, then
+ * listens to the decoder while the operator keys the item back on the
+ * paddle. The echo ends after a word gap or a short silence; the item is
+ * scored (see trainer_score_item()) and the next one follows.
+ *
+ * TX stays off for the whole session and the decoder is switched on
+ * for it (previous setting restored at the end).
+ *
+ * Started from the console, driven by copy_practice_tick() in bg_task.
+ * Core 1 only.
+ */
+
+#ifndef KEYER_COPY_PRACTICE_H
+#define KEYER_COPY_PRACTICE_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include "trainer.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Longest report line, including terminator */
+#define COPY_PRACTICE_LINE_MAX 80
+
+/**
+ * @brief Report sink: one line per item, then the session summary
+ *
+ * Called from bg_task. The line has no line ending.
+ */
+typedef void (*copy_practice_report_fn)(const char *line);
+
+/**
+ * @brief Session parameters
+ */
+typedef struct {
+    trainer_mode_t mode;            /**< Kind of items */
+    uint32_t items;                 /**< Items in the session */
+    uint32_t char_wpm;              /**< Character speed (0 = keyer.wpm) */
+    uint32_t effective_wpm;         /**< Farnsworth speed (0 = none) */
+    uint32_t seed;                  /**< Trainer PRNG seed */
+    copy_practice_report_fn report; /**< Result lines (can be NULL) */
+} copy_practice_config_t;
+
+/**
+ * @brief Start a session
+ *
+ * @param config Session parameters (copied)
+ * @return 0 on success, -1 if a session runs, the text keyer is busy
+ *         or items is 0
+ */
+int copy_practice_start(const copy_practice_config_t *config);
+
+/**
+ * @brief Stop the session after reporting the score so far
+ */
+void copy_practice_stop(void);
+
+/**
+ * @brief Check if a session is running
+ */
+bool copy_practice_is_active(void);
+
+/**
+ * @brief Score of the running or last session
+ */
+trainer_score_t copy_practice_get_score(void);
+
+/**
+ * @brief Advance the session (call from bg_task every tick)
+ *
+ * @param now_us Current time
+ */
+void copy_practice_tick(int64_t now_us);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_COPY_PRACTICE_H */
@@ -0,0 +1,89 @@
+/**
+ * @file memory_buttons.h
+ * @brief Debounced memory and function buttons
+ *
+ * Each button runs the action chosen in hardware.btnN_action: play a
+ * message memory, tune, speed up/down, or pause. Holding a memory button makes
+ * the message repeat. bg_task feeds the raw levels from
+ * hal_gpio_read_buttons() every tick; pure logic, host-testable.
+ */
+
+#ifndef KEYER_MEMORY_BUTTONS_H
+#define KEYER_MEMORY_BUTTONS_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Level must hold this long before a change is accepted */
+#define MEMORY_BUTTONS_DEBOUNCE_US 30000
+
+/** Held this long after the press: long press (repeat mode) */
+#define MEMORY_BUTTONS_LONG_US 800000
+
+/** Buttons per debouncer (one bit each) */
+#define MEMORY_BUTTONS_MAX 16
+
+/**
+ * @brief Button actions (hardware.btnN_action values)
+ */
+typedef enum {
+    MEMORY_BUTTON_MEM1 = 0,     /**< MEM1..MEM8: play memory slot 1..8 */
+    MEMORY_BUTTON_MEM8 = 7,
+    MEMORY_BUTTON_TUNE = 8,     /**< Key down while held */
+    MEMORY_BUTTON_FASTER = 9,   /**< +1 WPM */
+    MEMORY_BUTTON_SLOWER = 10,  /**< -1 WPM */
+    MEMORY_BUTTON_PAUSE = 11,   /**< Pause / resume the message */
+} memory_button_action_t;
+
+/**
+ * @brief Debouncer state
+ */
+typedef struct {
+    uint16_t stable;        /**< Debounced held mask */
+    uint16_t candidate;     /**< Last raw mask seen */
+    int64_t changed_us;     /**< When the raw mask last changed */
+    int8_t last;            /**< Last pressed button, -1 = none */
+    int64_t pressed_us;     /**< When it was pressed */
+    bool long_reported;     /**< Long press already returned for it */
+} memory_buttons_t;
+
+/**
+ * @brief Reset to "no button held"
+ */
+void memory_buttons_init(memory_buttons_t *buttons);
+
+/**
+ * @brief Feed the raw button mask
+ *
+ * @param buttons Debouncer state
+ * @param raw Bit i set while button i is held
+ * @param now_us Current time
+ * @return Index of a button that was just pressed (lowest if several), or -1
+ */
+int memory_buttons_update(memory_buttons_t *buttons, uint16_t raw, int64_t now_us);
+
+/**
+ * @brief Check for a long press (once per press)
+ *
+ * Only the last pressed button counts, and only while it is still held.
+ *
+ * @param buttons Debouncer state (after memory_buttons_update())
+ * @param now_us Current time
+ * @return Index of the button held for MEMORY_BUTTONS_LONG_US, or -1
+ */
+int memory_buttons_long_press(memory_buttons_t *buttons, int64_t now_us);
+
+/**
+ * @brief Check if a button is held (debounced)
+ */
+bool memory_buttons_is_held(const memory_buttons_t *buttons, int index);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_MEMORY_BUTTONS_H */
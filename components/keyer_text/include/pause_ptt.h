@@ -0,0 +1,59 @@
+/**
+ * @file pause_ptt.h
+ * @brief PTT hold while a message is paused (keyer.pause_ptt = HOLD)
+ *
+ * The rig stays in TX through the pause so the rest of the message goes
+ * out without a new PTT lead-in. The hold ends on resume, or after
+ * PAUSE_PTT_MAX_US: a pause left on must not keep the rig transmitting.
+ * PTT then drops after its normal tail. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_PAUSE_PTT_H
+#define KEYER_PAUSE_PTT_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Longest PTT hold in one pause */
+#define PAUSE_PTT_MAX_US (30LL * 1000 * 1000)
+
+/**
+ * @brief Hold state for one pause
+ */
+typedef struct {
+    bool held;          /**< PTT kept up */
+    int64_t start_us;   /**< When the pause was taken */
+} pause_ptt_t;
+
+/**
+ * @brief A message was paused
+ *
+ * @param p Hold state
+ * @param hold true to keep PTT up (HOLD mode, not practice)
+ * @param now_us Current time
+ */
+void pause_ptt_start(pause_ptt_t *p, bool hold, int64_t now_us);
+
+/**
+ * @brief Check the hold while paused
+ *
+ * @param p Hold state
+ * @param now_us Current time
+ * @return true while PTT must stay up, false once released or capped
+ */
+bool pause_ptt_tick(pause_ptt_t *p, int64_t now_us);
+
+/**
+ * @brief The pause ended (resume or abort): release the hold
+ */
+void pause_ptt_end(pause_ptt_t *p);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_PAUSE_PTT_H */
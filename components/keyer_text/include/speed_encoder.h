@@ -0,0 +1,56 @@
+/**
+ * @file speed_encoder.h
+ * @brief Rotary encoder speed control with acceleration
+ *
+ * bg_task feeds the detents counted by hal_encoder_take_detents() and
+ * applies the returned WPM change like a speed button: the message
+ * playing is nudged, otherwise keyer.wpm is set (picked up by the
+ * paddles at the next element through the config generation).
+ *
+ * Turning fast moves several WPM per detent; a change of direction
+ * starts again at one. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_SPEED_ENCODER_H
+#define KEYER_SPEED_ENCODER_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Detents closer than this: 2 WPM each */
+#define SPEED_ENCODER_QUICK_US 80000
+
+/** Detents closer than this: 4 WPM each */
+#define SPEED_ENCODER_FAST_US  30000
+
+/**
+ * @brief Acceleration state
+ */
+typedef struct {
+    int64_t last_us;        /**< Time of the previous detent, 0 = none yet */
+    int last_dir;           /**< Direction of the previous detent (+1/-1) */
+} speed_encoder_t;
+
+/**
+ * @brief Reset to "not turned yet"
+ */
+void speed_encoder_init(speed_encoder_t *encoder);
+
+/**
+ * @brief Turn detents into a WPM change
+ *
+ * @param encoder Acceleration state
+ * @param detents Detents since the last call (negative = slower)
+ * @param now_us Current time
+ * @return WPM to add (0 if detents is 0)
+ */
+int speed_encoder_steps(speed_encoder_t *encoder, int detents, int64_t now_us);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_SPEED_ENCODER_H */
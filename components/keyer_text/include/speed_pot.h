@@ -0,0 +1,61 @@
+/**
+ * @file speed_pot.h
+ * @brief Speed potentiometer: filtering, hysteresis, WPM mapping
+ *
+ * bg_task feeds the raw ADC reading every tick. The reading is smoothed,
+ * mapped linearly onto keyer.pot_wpm_min..max and only reported when the
+ * knob has clearly moved to another WPM, so ADC noise never rewrites
+ * keyer.wpm. Speed set from the console, WebUI or encoder stays until
+ * the knob is turned (last writer wins); the first reading after boot
+ * only primes the filter. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_SPEED_POT_H
+#define KEYER_SPEED_POT_H
+
+#include <stdint.h>
+#include <stdbool.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Full-scale raw reading (12-bit ADC) */
+#define SPEED_POT_RAW_MAX 4095
+
+/** Smoothing: each reading moves the filter 1/SPEED_POT_FILTER of the way */
+#define SPEED_POT_FILTER 8
+
+/** Extra travel, in 1/256 WPM, past the half-way point before a change */
+#define SPEED_POT_HYSTERESIS 64
+
+/**
+ * @brief Potentiometer state
+ */
+typedef struct {
+    int32_t filtered;       /**< Smoothed reading, raw x 16 */
+    bool primed;            /**< First reading taken */
+    uint16_t wpm;           /**< Last WPM reported or primed */
+} speed_pot_t;
+
+/**
+ * @brief Reset to "no reading yet"
+ */
+void speed_pot_init(speed_pot_t *pot);
+
+/**
+ * @brief Feed a raw reading
+ *
+ * @param pot Potentiometer state
+ * @param raw ADC reading, 0 to SPEED_POT_RAW_MAX
+ * @param min_wpm Speed at the bottom of the travel
+ * @param max_wpm Speed at the top (swapped with min_wpm if lower)
+ * @return New WPM when the knob moved to another speed, 0 otherwise
+ */
+uint16_t speed_pot_update(speed_pot_t *pot, uint16_t raw, uint16_t min_wpm, uint16_t max_wpm);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_SPEED_POT_H */
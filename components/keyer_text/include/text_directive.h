@@ -0,0 +1,70 @@
+/**
+ * @file text_directive.h
+ * @brief Embedded commands in message text
+ *
+ * Commands sit between bars inside the text and are executed by the
+ * playback engine instead of being sent:
+ *
+ *   |S30|  speed 30 WPM for the rest of the message (5-60)
+ *   |P2|   pause 2 seconds, key up (1-60)
+ *   |R|    repeat the message from the start (until aborted)
+ *   |M3|   continue with memory slot 3 (1-8); text after it is not sent
+ *   |N12|  next contest serial is 12 (1-9999); added after %N by the
+ *          serial expansion so the number advances once it is keyed
+ *
+ * Letters are case-insensitive. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_TEXT_DIRECTIVE_H
+#define KEYER_TEXT_DIRECTIVE_H
+
+#include <stdint.h>
+#include <stddef.h>
+#include "text_memory.h"
+#include "contest_serial.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Command delimiter */
+#define TEXT_DIRECTIVE_MARK '|'
+
+/** Longest command body between the bars ("N9999") */
+#define TEXT_DIRECTIVE_BODY_MAX 5
+
+/**
+ * @brief Embedded command kind
+ */
+typedef enum {
+    TEXT_DIRECTIVE_INVALID = 0,  /**< Bars found but body not understood (skipped) */
+    TEXT_DIRECTIVE_SPEED,        /**< value = WPM */
+    TEXT_DIRECTIVE_PAUSE,        /**< value = seconds */
+    TEXT_DIRECTIVE_REPEAT,       /**< value unused */
+    TEXT_DIRECTIVE_MEMORY,       /**< value = memory slot, 1-based */
+    TEXT_DIRECTIVE_SERIAL,       /**< value = next contest serial */
+} text_directive_type_t;
+
+/**
+ * @brief Parsed command
+ */
+typedef struct {
+    text_directive_type_t type;
+    uint32_t value;
+} text_directive_t;
+
+/**
+ * @brief Parse a command starting at an opening bar
+ *
+ * @param s Text at TEXT_DIRECTIVE_MARK
+ * @param out Parsed command
+ * @return Characters consumed including both bars, 0 if s is not a
+ *         command (no closing bar within TEXT_DIRECTIVE_BODY_MAX)
+ */
+size_t text_directive_parse(const char *s, text_directive_t *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_TEXT_DIRECTIVE_H */
@@ -7,9 +7,17 @@
  *
  * Features:
  * - Free-form text via send command
+ * - Embedded |S30| |P2| |R| |M3| commands (see text_directive.h)
+ * - %N contest serial substitution (see contest_serial.h)
+ * - %C last decoded callsign (see callsign.h)
+ * - Keyboard mode: typed characters queued and sent as they arrive,
+ *   ;shortcuts expanded (see abbrev.h)
+ * - Practice text (trainer): sidetone only, Farnsworth spacing
+ * - Copy practice sessions (see copy_practice.h)
  * - 8 memory slots in NVS
- * - Paddle abort via atomic flag
- * - Uses global WPM from config
+ * - Repeat mode (keyer.msg_repeat_s) and tune carrier, for the buttons
+ * - Paddle touch aborts or pauses playback (keyer.paddle_interrupt)
+ * - Uses global WPM from config; +/- and nudges shift it mid-message
  */
 
 #ifndef KEYER_TEXT_KEYER_H
@@ -36,11 +44,28 @@ typedef enum {
     TEXT_KEYER_PAUSED,        /**< Paused (can resume) */
 } text_keyer_state_t;
 
+/**
+ * @brief keyer.paddle_interrupt values
+ */
+typedef enum {
+    TEXT_KEYER_PADDLE_ABORT = 0,  /**< Paddle touch drops the message */
+    TEXT_KEYER_PADDLE_PAUSE = 1,  /**< Paddle touch pauses until resumed */
+} text_keyer_paddle_t;
+
+/**
+ * @brief keyer.pause_ptt values
+ */
+typedef enum {
+    TEXT_KEYER_PAUSE_PTT_RELEASE = 0,  /**< PTT drops after the tail */
+    TEXT_KEYER_PAUSE_PTT_HOLD = 1,     /**< PTT stays up until resumed, at most PAUSE_PTT_MAX_US */
+} text_keyer_pause_ptt_t;
+
 /**
  * @brief Text keyer configuration
  */
 typedef struct {
-    const atomic_bool *paddle_abort;  /**< Abort trigger (non-owning, can be NULL) */
+    /** Set by the RT task on any paddle closure, cleared here (non-owning, can be NULL) */
+    atomic_bool *paddle_touch;
 } text_keyer_config_t;
 
 /**
@@ -54,11 +79,157 @@ int text_keyer_init(const text_keyer_config_t *config);
 /**
  * @brief Send text as morse code
  *
+ * <AR>, <SK>, <BK>, <KN>... and any 2-4 characters in angle brackets
+ * are sent run together, without character gaps.
+ *
+ * '+' and '-' change the speed by 1 WPM from the next character on
+ * instead of being sent; <AR> sends '+', <DU> sends '-'.
+ *
+ * %N codes are expanded here, so the serial only advances for text
+ * that is actually accepted. %C becomes the last decoded callsign.
+ *
  * @param text Text to send (A-Z, 0-9, punctuation, prosigns, spaces)
  * @return 0 on success, -1 if already sending or invalid
  */
 int text_keyer_send(const char *text);
 
+/**
+ * @brief Send practice text through the sidetone only
+ *
+ * Never reaches the keying stream: not transmitted, not decoded, not
+ * forwarded. TX stays inhibited until the text is done or aborted.
+ * Paddles do not interrupt it. Text is sent as is (no %N, %C).
+ *
+ * @param text Text to send
+ * @param char_wpm Character speed (0 = keyer.wpm)
+ * @param effective_wpm Farnsworth overall speed (0 = same as char_wpm)
+ * @return 0 on success, -1 if already sending or invalid
+ */
+int text_keyer_send_practice(const char *text, uint32_t char_wpm, uint32_t effective_wpm);
+
+/**
+ * @brief Keep TX off between practice texts (copy practice session)
+ *
+ * @param hold true while the operator keys practice echoes on the paddle
+ */
+void text_keyer_hold_tx_off(bool hold);
+
+/**
+ * @brief Check if TX is inhibited (RT-safe)
+ *
+ * @return true while practice text plays, a session holds TX off or
+ *         system.practice_osc is on
+ */
+bool text_keyer_tx_inhibited(void);
+
+/**
+ * @brief Repeat the playing message until aborted
+ *
+ * At the end of the text the keyer waits keyer.msg_repeat_s seconds,
+ * then sends the text again from the start (%N is not advanced again).
+ * After a |Mn| jump the chained slot is what repeats.
+ *
+ * Repeating stops on a paddle touch (whatever keyer.paddle_interrupt
+ * says) and when the decoder hears keying during the wait.
+ *
+ * @return 0 on success, -1 if no message is playing or long
+ *         transmissions are blocked
+ */
+int text_keyer_repeat(void);
+
+/**
+ * @brief Key a steady carrier for tuning
+ *
+ * Key down until text_keyer_abort() or a paddle touch, at most 20 s.
+ *
+ * @return 0 on success, -1 if already sending or long transmissions
+ *         are blocked
+ */
+int text_keyer_tune(void);
+
+/**
+ * @brief Check if the tune carrier is on
+ */
+bool text_keyer_is_tuning(void);
+
+/**
+ * @brief Block long transmissions (thermal guard)
+ *
+ * While blocked, tune and repeat are refused, a tune carrier stops and
+ * a repeating message ends with the pass in progress. Single messages
+ * still go out. Safe from any task.
+ *
+ * @param block true to block
+ */
+void text_keyer_block_long_tx(bool block);
+
+/**
+ * @brief Check if long transmissions are blocked
+ */
+bool text_keyer_long_tx_blocked(void);
+
+/**
+ * @brief Change the playback speed mid-message
+ *
+ * Safe from any task. Steps of 1 WPM, applied when the next character
+ * starts; the result stays within 5-60 WPM. Lasts until the end of
+ * the message (keyer.wpm is not changed).
+ *
+ * @param steps WPM to add (negative = slower)
+ * @return 0 on success, -1 if nothing is playing
+ */
+int text_keyer_nudge_speed(int steps);
+
+/**
+ * @brief Current playback speed in WPM
+ */
+uint32_t text_keyer_get_wpm(void);
+
+/**
+ * @brief Get practice tone state (RT-safe, sidetone only)
+ */
+bool text_keyer_is_tone_down(void);
+
+/**
+ * @brief Queue a typed character (keyboard mode)
+ *
+ * Starts a keyboard session if idle. Characters are keyed in order with
+ * normal character spacing; space gives a word gap. A ;shortcut is held
+ * back until it ends, then queued as its abbreviation. The session stays
+ * open (key up, waiting) until text_keyer_type_end().
+ *
+ * @param c Character (lowercase accepted)
+ * @return 0 on success, -1 if a message is playing or the queue is full
+ */
+int text_keyer_type(char c);
+
+/**
+ * @brief Cancel the last typed character if it has not been keyed yet
+ *
+ * @return 0 on success, -1 if nothing is pending
+ */
+int text_keyer_backspace(void);
+
+/**
+ * @brief Close the keyboard session once the queue has been sent
+ */
+void text_keyer_type_end(void);
+
+/**
+ * @brief Check if a keyboard session is active
+ */
+bool text_keyer_is_typing(void);
+
+/**
+ * @brief Copy recently typed text for display (console task only)
+ *
+ * @param buf Output, NUL-terminated
+ * @param len Size of buf
+ * @param pending Output: trailing characters of buf not yet sent
+ * @return Number of characters copied
+ */
+size_t text_keyer_get_typed(char *buf, size_t len, size_t *pending);
+
 /**
  * @brief Abort current transmission
  */
@@ -66,11 +237,24 @@ void text_keyer_abort(void);
 
 /**
  * @brief Pause current transmission
+ *
+ * Safe from any task. The character being keyed is finished first;
+ * on resume the next one follows after a normal character gap.
+ * keyer.pause_ptt decides if PTT stays up meanwhile. (A paddle touch
+ * pauses at once instead and the cut character is sent again.)
  */
 void text_keyer_pause(void);
 
+/**
+ * @brief Check if PTT must stay up during a pause (RT-safe)
+ */
+bool text_keyer_holds_ptt(void);
+
 /**
  * @brief Resume paused transmission
+ *
+ * Also cancels a pause not taken yet. In keyboard mode typing a
+ * character also resumes.
  */
 void text_keyer_resume(void);
 
@@ -106,6 +290,20 @@ void text_keyer_tick(int64_t now_us);
  */
 bool text_keyer_is_key_down(void);
 
+/** Marker character for a <..> group that has no character of its own */
+#define TEXT_KEYER_MARK_GROUP '~'
+
+/**
+ * @brief Get the character being keyed (RT-safe)
+ *
+ * Called by the RT task with text_keyer_is_key_down() to mark the
+ * keying stream where each sent character starts (stream_mark_text()).
+ *
+ * @return Tag: low byte the character, upper bits change with every
+ *         character started, so the same letter twice is two tags
+ */
+uint32_t text_keyer_char_tag(void);
+
 #ifdef __cplusplus
 }
 #endif
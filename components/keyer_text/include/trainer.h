@@ -0,0 +1,94 @@
+/**
+ * @file trainer.h
+ * @brief Morse trainer practice text generator
+ *
+ * Random 5-character groups, callsign-like strings or common words for
+ * code practice. Played by text_keyer_send_practice() through the
+ * sidetone only. Pure logic (own PRNG), host-testable.
+ *
+ * Also scores copy practice: what the operator keyed back is compared
+ * with what was sent, character errors counted as edit distance.
+ */
+
+#ifndef KEYER_TRAINER_H
+#define KEYER_TRAINER_H
+
+#include <stdint.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Characters per random group */
+#define TRAINER_GROUP_LEN 5
+
+/** Longest item compared when scoring (longer text is cut) */
+#define TRAINER_SCORE_LEN_MAX 32
+
+/**
+ * @brief Kind of practice text
+ */
+typedef enum {
+    TRAINER_GROUPS = 0,     /**< Random letters and digits, 5 per group */
+    TRAINER_CALLS,          /**< Callsign-shaped strings (IU3QEZ, K1ABC) */
+    TRAINER_WORDS,          /**< Common words and CW abbreviations */
+} trainer_mode_t;
+
+/**
+ * @brief Copy practice score for one session
+ */
+typedef struct {
+    uint32_t items;         /**< Items scored */
+    uint32_t perfect;       /**< Items copied without error */
+    uint32_t chars;         /**< Characters sent */
+    uint32_t errors;        /**< Character errors (at most chars) */
+} trainer_score_t;
+
+/**
+ * @brief Parse a mode name ("groups", "calls", "words")
+ *
+ * @return 0 on success, -1 if unknown
+ */
+int trainer_mode_from_str(const char *name, trainer_mode_t *mode);
+
+/**
+ * @brief Generate practice text
+ *
+ * Items are separated by single spaces. Stops early rather than cut an
+ * item when buf is full.
+ *
+ * @param mode Kind of text
+ * @param seed PRNG state (non-zero), advanced
+ * @param count Number of items wanted
+ * @param buf Output, NUL-terminated
+ * @param len Size of buf
+ * @return Number of items generated
+ */
+size_t trainer_generate(trainer_mode_t mode, uint32_t *seed, size_t count,
+                        char *buf, size_t len);
+
+/**
+ * @brief Score one item
+ *
+ * Errors are the edit distance between sent and copied (a wrong,
+ * missing or extra character counts one), capped at the length of the
+ * sent item so a garbled copy cannot cost more than the item is worth.
+ *
+ * @param score Session score, updated
+ * @param sent Item as sent
+ * @param copied Item as decoded ("" if nothing was keyed)
+ * @return Errors in this item
+ */
+uint32_t trainer_score_item(trainer_score_t *score, const char *sent, const char *copied);
+
+/**
+ * @brief Character accuracy in percent (100 if nothing scored yet)
+ */
+uint32_t trainer_score_percent(const trainer_score_t *score);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_TRAINER_H */
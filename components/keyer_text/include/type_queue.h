@@ -0,0 +1,109 @@
+/**
+ * @file type_queue.h
+ * @brief Keyboard keying queue (typed, not yet sent characters)
+ *
+ * One producer (the console typing in keyboard mode) and one consumer
+ * (text_keyer_tick on bg_task). Typed and sent counters share one atomic
+ * word with a backspace count, so backspace can only remove a character
+ * the keyer has not claimed yet, and the keyer can claim a group only as
+ * it saw it, without locks. Pure logic, host-testable.
+ */
+
+#ifndef KEYER_TYPE_QUEUE_H
+#define KEYER_TYPE_QUEUE_H
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdatomic.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** Ring size (power of two, at most TYPE_QUEUE_SIZE - 1 chars pending) */
+#define TYPE_QUEUE_SIZE 128
+
+/**
+ * @brief Typed character queue
+ */
+typedef struct {
+    atomic_uint_fast32_t pos;       /**< typed count | sent count (mod 2^12) | backspace count */
+    uint16_t history;               /**< Producer only: chars held for type_queue_tail() */
+    char ring[TYPE_QUEUE_SIZE];
+} type_queue_t;
+
+/**
+ * @brief Empty the queue
+ */
+void type_queue_init(type_queue_t *q);
+
+/**
+ * @brief Append a typed character (producer)
+ *
+ * @return false if TYPE_QUEUE_SIZE - 1 characters are already pending
+ */
+bool type_queue_push(type_queue_t *q, char c);
+
+/**
+ * @brief Remove the last typed character if it is still pending (producer)
+ *
+ * @return false if nothing is pending (already being keyed)
+ */
+bool type_queue_backspace(type_queue_t *q);
+
+/**
+ * @brief Claim the next pending character for keying (consumer)
+ *
+ * @return false if nothing is pending
+ */
+bool type_queue_pop(type_queue_t *q, char *c);
+
+/**
+ * @brief Claim the next n pending characters only if they are expect (consumer)
+ *
+ * Check and claim are one atomic step: a group read with
+ * type_queue_peek() is claimed exactly as seen, or not at all if the
+ * producer edited it meanwhile.
+ *
+ * @param expect Characters the caller saw
+ * @param n Number of characters
+ * @return false if fewer than n are pending or they differ from expect
+ */
+bool type_queue_pop_if(type_queue_t *q, const char *expect, size_t n);
+
+/**
+ * @brief Look at a pending character without claiming it (consumer)
+ *
+ * Only a hint: the producer may still backspace it. Claim with
+ * type_queue_pop_if() before acting on it.
+ *
+ * @param offset 0 = next character to be claimed
+ * @return false if fewer than offset + 1 characters are pending
+ */
+bool type_queue_peek(type_queue_t *q, size_t offset, char *c);
+
+/**
+ * @brief Number of typed characters not yet claimed by the keyer
+ */
+size_t type_queue_pending(type_queue_t *q);
+
+/**
+ * @brief Copy the most recently typed characters (producer)
+ *
+ * The last *pending characters of buf are still waiting to be sent,
+ * the ones before them are already keyed.
+ *
+ * @param q Queue
+ * @param buf Output, NUL-terminated
+ * @param len Size of buf
+ * @param pending Output: pending characters at the end of buf
+ * @return Number of characters copied
+ */
+size_t type_queue_tail(type_queue_t *q, char *buf, size_t len, size_t *pending);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_TYPE_QUEUE_H */
@@ -0,0 +1,281 @@
+/**
+ * @file abbrev.c
+ * @brief Keyboard mode abbreviations implementation (NVS persistence)
+ */
+
+#include "abbrev.h"
+#include <ctype.h>
+#include <stdio.h>
+#include <string.h>
+#include <stdatomic.h>
+
+#ifdef ESP_PLATFORM
+#include "nvs.h"
+#include "esp_log.h"
+static const char *TAG = "abbrev";
+#define NVS_NAMESPACE "abbrev"
+#define NVS_KEY_TABLE "table"
+#endif
+
+/* ============================================================================
+ * Module State
+ * ============================================================================ */
+
+static abbrev_entry_t s_table[ABBREV_COUNT];
+
+/* Set by abbrev_set() after the table change, cleared by io_task */
+static atomic_bool s_dirty = ATOMIC_VAR_INIT(false);
+
+static const abbrev_entry_t DEFAULT_TABLE[] = {
+    { .key = "TU", .text = "TU 73 EE" },
+    { .key = "RR", .text = "RR TU" },
+    { .key = "AGN", .text = "AGN PSE" },
+};
+
+/* ============================================================================
+ * NVS Helpers
+ * ============================================================================ */
+
+#ifdef ESP_PLATFORM
+static void load_from_nvs(void) {
+    nvs_handle_t handle;
+    if (nvs_open(NVS_NAMESPACE, NVS_READONLY, &handle) != ESP_OK) {
+        return;  /* Nothing saved yet: keep defaults */
+    }
+    abbrev_entry_t table[ABBREV_COUNT];
+    size_t len = sizeof(table);
+    if (nvs_get_blob(handle, NVS_KEY_TABLE, table, &len) == ESP_OK && len == sizeof(table)) {
+        for (size_t i = 0; i < ABBREV_COUNT; i++) {
+            table[i].key[ABBREV_KEY_MAX] = '\0';
+            table[i].text[ABBREV_TEXT_MAX] = '\0';
+        }
+        memcpy(s_table, table, sizeof(s_table));
+    }
+    nvs_close(handle);
+}
+
+static int save_to_nvs(const abbrev_entry_t *table) {
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err == ESP_OK) {
+        err = nvs_set_blob(handle, NVS_KEY_TABLE, table, sizeof(s_table));
+        if (err == ESP_OK) {
+            err = nvs_commit(handle);
+        }
+        nvs_close(handle);
+    }
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to save abbreviations: %s", esp_err_to_name(err));
+        return -1;
+    }
+    return 0;
+}
+#else
+/* Host stubs */
+static void load_from_nvs(void) {}
+static int save_to_nvs(const abbrev_entry_t *table) { (void)table; return 0; }
+#endif
+
+/* ============================================================================
+ * Table
+ * ============================================================================ */
+
+static bool is_key_char(char c) {
+    return isalnum((unsigned char)c) != 0;
+}
+
+/**
+ * @brief Uppercase copy of a valid key
+ * @return false if empty, too long or not letters/digits
+ */
+static bool normalize_key(const char *key, char *out) {
+    size_t len = (key != NULL) ? strlen(key) : 0;
+    if (len == 0 || len > ABBREV_KEY_MAX) {
+        return false;
+    }
+    for (size_t i = 0; i < len; i++) {
+        if (!is_key_char(key[i])) {
+            return false;
+        }
+        out[i] = (char)toupper((unsigned char)key[i]);
+    }
+    out[len] = '\0';
+    return true;
+}
+
+static abbrev_entry_t *find(const char *upper_key) {
+    for (size_t i = 0; i < ABBREV_COUNT; i++) {
+        if (s_table[i].key[0] != '\0' && strcmp(s_table[i].key, upper_key) == 0) {
+            return &s_table[i];
+        }
+    }
+    return NULL;
+}
+
+void abbrev_init(void) {
+    memset(s_table, 0, sizeof(s_table));
+    memcpy(s_table, DEFAULT_TABLE, sizeof(DEFAULT_TABLE));
+    atomic_store(&s_dirty, false);
+    load_from_nvs();
+}
+
+const char *abbrev_lookup(const char *key) {
+    char upper[ABBREV_KEY_MAX + 1];
+    if (!normalize_key(key, upper)) {
+        return NULL;
+    }
+    const abbrev_entry_t *e = find(upper);
+    return (e != NULL) ? e->text : NULL;
+}
+
+int abbrev_set(const char *key, const char *text) {
+    char upper[ABBREV_KEY_MAX + 1];
+    if (!normalize_key(key, upper) ||
+        (text != NULL && strlen(text) > ABBREV_TEXT_MAX)) {
+        return -1;
+    }
+
+    abbrev_entry_t *e = find(upper);
+    if (text == NULL || text[0] == '\0') {
+        if (e == NULL) {
+            return 0;
+        }
+        memset(e, 0, sizeof(*e));
+        atomic_store(&s_dirty, true);
+        return 0;
+    }
+
+    for (size_t i = 0; e == NULL && i < ABBREV_COUNT; i++) {
+        if (s_table[i].key[0] == '\0') {
+            e = &s_table[i];
+        }
+    }
+    if (e == NULL) {
+        return -1;  /* Table full */
+    }
+    strcpy(e->key, upper);
+    size_t i;
+    for (i = 0; text[i] != '\0'; i++) {
+        e->text[i] = (char)toupper((unsigned char)text[i]);
+    }
+    e->text[i] = '\0';
+    atomic_store(&s_dirty, true);
+    return 0;
+}
+
+void abbrev_save_tick(void) {
+    if (!atomic_exchange(&s_dirty, false)) {
+        return;
+    }
+    /* A change racing the copy sets s_dirty again: saved next tick */
+    abbrev_entry_t table[ABBREV_COUNT];
+    memcpy(table, s_table, sizeof(table));
+    (void)save_to_nvs(table);
+}
+
+const abbrev_entry_t *abbrev_get(size_t index) {
+    if (index >= ABBREV_COUNT || s_table[index].key[0] == '\0') {
+        return NULL;
+    }
+    return &s_table[index];
+}
+
+/* ============================================================================
+ * Keyboard Input
+ * ============================================================================ */
+
+/**
+ * @brief Append a string to out, as much as fits
+ */
+static size_t put(char *out, size_t pos, size_t out_len, const char *s) {
+    while (*s != '\0' && pos < out_len) {
+        out[pos++] = *s++;
+    }
+    return pos;
+}
+
+/**
+ * @brief End the held shortcut: its expansion, or the text as typed
+ */
+static size_t end_word(abbrev_input_t *in, char *out, size_t out_len) {
+    in->active = false;
+    in->word[in->len] = '\0';
+
+    const char *text = abbrev_lookup(in->word);
+    if (text != NULL) {
+        return put(out, 0, out_len, text);
+    }
+    size_t pos = put(out, 0, out_len, ";");
+    return put(out, pos, out_len, in->word);
+}
+
+void abbrev_input_init(abbrev_input_t *in) {
+    in->active = false;
+    in->word[0] = '\0';
+    in->len = 0;
+}
+
+size_t abbrev_input_feed(abbrev_input_t *in, char c, char *out, size_t out_len) {
+    if (out_len == 0) {
+        return 0;
+    }
+    if (!in->active) {
+        if (c == ABBREV_MARK) {
+            in->active = true;
+            in->len = 0;
+            return 0;
+        }
+        out[0] = c;
+        return 1;
+    }
+
+    if (is_key_char(c) && in->len < ABBREV_KEY_MAX) {
+        in->word[in->len++] = c;
+        return 0;
+    }
+    if (c == ABBREV_MARK && in->len == 0) {
+        in->active = false;
+        out[0] = ABBREV_MARK;  /* ";;": a plain ';' */
+        return 1;
+    }
+
+    size_t n = end_word(in, out, out_len);
+    if (n < out_len) {
+        out[n++] = c;
+    }
+    return n;
+}
+
+bool abbrev_input_backspace(abbrev_input_t *in) {
+    if (!in->active) {
+        return false;
+    }
+    if (in->len > 0) {
+        in->len--;
+    } else {
+        in->active = false;  /* The ';' itself */
+    }
+    return true;
+}
+
+size_t abbrev_input_flush(abbrev_input_t *in, char *out, size_t out_len) {
+    if (!in->active || out_len == 0) {
+        return 0;
+    }
+    return end_word(in, out, out_len);
+}
+
+size_t abbrev_input_held(const abbrev_input_t *in, char *buf, size_t len) {
+    if (len == 0) {
+        return 0;
+    }
+    size_t n = 0;
+    if (in->active && len > 1) {
+        buf[n++] = ABBREV_MARK;
+        for (size_t i = 0; i < in->len && n + 1 < len; i++) {
+            buf[n++] = in->word[i];
+        }
+    }
+    buf[n] = '\0';
+    return n;
+}
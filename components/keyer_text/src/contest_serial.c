@@ -0,0 +1,197 @@
+/**
+ * @file contest_serial.c
+ * @brief Contest serial number implementation (NVS persistence)
+ */
+
+#include "contest_serial.h"
+#include "text_directive.h"
+#include <stdio.h>
+#include <string.h>
+#include <stdatomic.h>
+
+#ifdef ESP_PLATFORM
+#include "nvs.h"
+#include "esp_log.h"
+static const char *TAG = "serial";
+#define NVS_NAMESPACE "contest"
+#define NVS_KEY_NEXT  "next_nr"
+#endif
+
+/* ============================================================================
+ * Module State
+ * ============================================================================ */
+
+/* Written by the console and by text_keyer_tick(), saved by io_task */
+static atomic_uint s_serial = ATOMIC_VAR_INIT(CONTEST_SERIAL_FIRST);
+static atomic_bool s_dirty = ATOMIC_VAR_INIT(false);
+
+/* ============================================================================
+ * NVS Helpers
+ * ============================================================================ */
+
+#ifdef ESP_PLATFORM
+static void load_from_nvs(void) {
+    nvs_handle_t handle;
+    if (nvs_open(NVS_NAMESPACE, NVS_READONLY, &handle) != ESP_OK) {
+        return;  /* Nothing saved yet: start at 001 */
+    }
+    uint32_t serial = 0;
+    if (nvs_get_u32(handle, NVS_KEY_NEXT, &serial) == ESP_OK &&
+        serial >= CONTEST_SERIAL_FIRST && serial <= CONTEST_SERIAL_MAX) {
+        atomic_store(&s_serial, serial);
+    }
+    nvs_close(handle);
+}
+
+static int save_to_nvs(uint32_t serial) {
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err == ESP_OK) {
+        err = nvs_set_u32(handle, NVS_KEY_NEXT, serial);
+        if (err == ESP_OK) {
+            err = nvs_commit(handle);
+        }
+        nvs_close(handle);
+    }
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Failed to save serial: %s", esp_err_to_name(err));
+        return -1;
+    }
+    return 0;
+}
+#else
+/* Host stubs */
+static void load_from_nvs(void) {}
+static int save_to_nvs(uint32_t serial) { (void)serial; return 0; }
+#endif
+
+/* ============================================================================
+ * Expansion
+ * ============================================================================ */
+
+static uint32_t next_serial(uint32_t serial) {
+    return (serial >= CONTEST_SERIAL_MAX) ? CONTEST_SERIAL_FIRST : serial + 1;
+}
+
+static uint32_t prev_serial(uint32_t serial) {
+    return (serial > CONTEST_SERIAL_FIRST) ? serial - 1 : CONTEST_SERIAL_FIRST;
+}
+
+/**
+ * @brief Append the |Nn| command that stores n as the next number
+ * @return New position, unchanged if the command does not fit
+ */
+static size_t put_marker(char *out, size_t pos, size_t out_len, uint32_t next) {
+    int n = snprintf(&out[pos], out_len - pos, "%cN%lu%c",
+                     TEXT_DIRECTIVE_MARK, (unsigned long)next, TEXT_DIRECTIVE_MARK);
+    if (n < 0 || (size_t)n >= out_len - pos) {
+        out[pos] = '\0';
+        return pos;
+    }
+    return pos + (size_t)n;
+}
+
+/**
+ * @brief Expand serial codes, optionally marking where the number advances
+ *
+ * With mark, every %N and %N+ is followed by |Nn| carrying the number
+ * the store would hold if the message ended there.
+ */
+static bool expand(const char *in, char *out, size_t out_len, uint32_t *serial, bool mark) {
+    if (out == NULL || out_len == 0) {
+        return false;
+    }
+    out[0] = '\0';
+    if (in == NULL || serial == NULL) {
+        return false;
+    }
+
+    bool found = false;
+    bool advance = false;
+    size_t pos = 0;
+
+    while (*in != '\0' && pos + 1 < out_len) {
+        if (in[0] != '%' || (in[1] != 'N' && in[1] != 'n')) {
+            out[pos++] = *in++;
+            continue;
+        }
+
+        uint32_t value;
+        bool moves = true;
+        if (in[2] == '+') {
+            *serial = next_serial(*serial);
+            value = *serial;
+            in += 3;
+        } else if (in[2] == '-') {
+            value = prev_serial(*serial);
+            moves = false;
+            in += 3;
+        } else {
+            value = *serial;
+            advance = true;
+            in += 2;
+        }
+        found = true;
+
+        int n = snprintf(&out[pos], out_len - pos, "%03lu", (unsigned long)value);
+        if (n < 0 || (size_t)n >= out_len - pos) {
+            break;  /* Does not fit: drop the partial number */
+        }
+        pos += (size_t)n;
+
+        if (mark && moves) {
+            pos = put_marker(out, pos, out_len, advance ? next_serial(*serial) : *serial);
+        }
+    }
+    out[pos] = '\0';
+
+    if (advance) {
+        *serial = next_serial(*serial);
+    }
+    return found;
+}
+
+bool contest_serial_expand_with(const char *in, char *out, size_t out_len, uint32_t *serial) {
+    return expand(in, out, out_len, serial, false);
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void contest_serial_init(void) {
+    atomic_store(&s_serial, CONTEST_SERIAL_FIRST);
+    atomic_store(&s_dirty, false);
+    load_from_nvs();
+}
+
+uint32_t contest_serial_get(void) {
+    return atomic_load(&s_serial);
+}
+
+int contest_serial_set(uint32_t serial) {
+    if (serial < CONTEST_SERIAL_FIRST || serial > CONTEST_SERIAL_MAX) {
+        return -1;
+    }
+    atomic_store(&s_serial, serial);
+    atomic_store(&s_dirty, true);
+    return 0;
+}
+
+void contest_serial_expand(const char *in, char *out, size_t out_len) {
+    uint32_t serial = atomic_load(&s_serial);
+    expand(in, out, out_len, &serial, true);
+}
+
+void contest_serial_sent(uint32_t next) {
+    if (next >= CONTEST_SERIAL_FIRST && next <= CONTEST_SERIAL_MAX &&
+        atomic_exchange(&s_serial, next) != next) {
+        atomic_store(&s_dirty, true);
+    }
+}
+
+void contest_serial_save_tick(void) {
+    if (atomic_exchange(&s_dirty, false)) {
+        (void)save_to_nvs(atomic_load(&s_serial));
+    }
+}
@@ -0,0 +1,234 @@
+/**
+ * @file copy_practice.c
+ * @brief Copy practice session implementation
+ */
+
+#include "copy_practice.h"
+#include "text_keyer.h"
+#include "decoder.h"
+#include <stdio.h>
+#include <stdatomic.h>
+#include <string.h>
+
+/** Pause between the end of an echo and the next item */
+#define ITEM_GAP_US      (1000 * 1000)
+
+/** Nothing keyed back for this long: item counted as missed */
+#define ECHO_START_US    (10 * 1000 * 1000)
+
+/** Silence after the last decoded character that ends the echo */
+#define ECHO_END_US      (2 * 1000 * 1000)
+
+/* ============================================================================
+ * Internal Types
+ * ============================================================================ */
+
+typedef enum {
+    PHASE_IDLE = 0,
+    PHASE_NEXT,             /**< Waiting to play the next item */
+    PHASE_PLAYING,          /**< Item playing through the sidetone */
+    PHASE_LISTENING,        /**< Collecting the operator's echo */
+} phase_t;
+
+/* ============================================================================
+ * Module State
+ * ============================================================================ */
+
+/* Written by the console (start/stop), advanced by bg_task */
+static atomic_int s_phase = ATOMIC_VAR_INIT(PHASE_IDLE);
+static atomic_bool s_stop = ATOMIC_VAR_INIT(false);
+
+static copy_practice_config_t s_cfg;
+static trainer_score_t s_score;
+static uint32_t s_seed;
+static bool s_decoder_was_enabled;
+
+static char s_item[16];
+static char s_copied[TRAINER_SCORE_LEN_MAX + 1];
+static size_t s_copied_len;
+static uint32_t s_cursor;
+static int64_t s_phase_start_us;
+static int64_t s_last_char_us;
+
+/* ============================================================================
+ * Helpers
+ * ============================================================================ */
+
+static void report(const char *line) {
+    if (s_cfg.report != NULL) {
+        s_cfg.report(line);
+    }
+}
+
+static void report_summary(void) {
+    char line[COPY_PRACTICE_LINE_MAX];
+    snprintf(line, sizeof(line), "Score: %lu/%lu items perfect, %lu/%lu chars (%lu%%)",
+             (unsigned long)s_score.perfect, (unsigned long)s_score.items,
+             (unsigned long)(s_score.chars - s_score.errors), (unsigned long)s_score.chars,
+             (unsigned long)trainer_score_percent(&s_score));
+    report(line);
+}
+
+static void set_phase(phase_t phase, int64_t now_us) {
+    s_phase_start_us = now_us;
+    atomic_store_explicit(&s_phase, (int)phase, memory_order_release);
+}
+
+/**
+ * @brief End the session: summary, TX and decoder back as they were
+ */
+static void finish_session(void) {
+    report_summary();
+    text_keyer_hold_tx_off(false);
+    decoder_set_enabled(s_decoder_was_enabled);
+    atomic_store_explicit(&s_stop, false, memory_order_relaxed);
+    atomic_store_explicit(&s_phase, PHASE_IDLE, memory_order_release);
+}
+
+static void play_next(int64_t now_us) {
+    trainer_generate(s_cfg.mode, &s_seed, 1, s_item, sizeof(s_item));
+    if (text_keyer_send_practice(s_item, s_cfg.char_wpm, s_cfg.effective_wpm) != 0) {
+        report("Stopped: text keyer busy");
+        finish_session();
+        return;
+    }
+    set_phase(PHASE_PLAYING, now_us);
+}
+
+static void start_listening(int64_t now_us) {
+    s_copied[0] = '\0';
+    s_copied_len = 0;
+    s_last_char_us = 0;
+    s_cursor = decoder_text_seq();  /* Only what is keyed from now on */
+    set_phase(PHASE_LISTENING, now_us);
+}
+
+static void score_echo(int64_t now_us) {
+    uint32_t errors = trainer_score_item(&s_score, s_item, s_copied);
+
+    char result[16];
+    if (errors == 0) {
+        strcpy(result, "OK");
+    } else {
+        snprintf(result, sizeof(result), "%lu error%s",
+                 (unsigned long)errors, (errors == 1) ? "" : "s");
+    }
+
+    char line[COPY_PRACTICE_LINE_MAX];
+    snprintf(line, sizeof(line), "%2lu/%lu  sent %-8s copied %-8s %s",
+             (unsigned long)s_score.items, (unsigned long)s_cfg.items, s_item,
+             (s_copied_len > 0) ? s_copied : "-", result);
+    report(line);
+
+    if (s_score.items >= s_cfg.items) {
+        finish_session();
+    } else {
+        set_phase(PHASE_NEXT, now_us);
+    }
+}
+
+/**
+ * @brief Collect decoded characters; true once the echo is complete
+ */
+static bool collect_echo(int64_t now_us) {
+    decoded_char_t chars[8];
+    size_t n;
+    while ((n = decoder_read_since(&s_cursor, chars, sizeof(chars) / sizeof(chars[0]))) > 0) {
+        for (size_t i = 0; i < n; i++) {
+            char c = chars[i].character;
+            if (c == ' ') {
+                if (s_copied_len > 0) {
+                    return true;  /* Word gap after the echo */
+                }
+                continue;
+            }
+            if (s_copied_len < TRAINER_SCORE_LEN_MAX) {
+                s_copied[s_copied_len++] = c;
+                s_copied[s_copied_len] = '\0';
+            }
+            s_last_char_us = now_us;
+        }
+    }
+
+    if (s_copied_len > 0) {
+        return now_us - s_last_char_us > ECHO_END_US;
+    }
+    return now_us - s_phase_start_us > ECHO_START_US;
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+int copy_practice_start(const copy_practice_config_t *config) {
+    if (config == NULL || config->items == 0 || copy_practice_is_active() ||
+        text_keyer_get_state() != TEXT_KEYER_IDLE) {
+        return -1;
+    }
+
+    s_cfg = *config;
+    s_seed = config->seed;
+    memset(&s_score, 0, sizeof(s_score));
+
+    s_decoder_was_enabled = decoder_is_enabled();
+    decoder_set_enabled(true);
+    text_keyer_hold_tx_off(true);
+
+    atomic_store_explicit(&s_stop, false, memory_order_relaxed);
+    s_phase_start_us = 0;  /* First item plays on the next tick */
+    atomic_store_explicit(&s_phase, PHASE_NEXT, memory_order_release);
+    return 0;
+}
+
+void copy_practice_stop(void) {
+    if (copy_practice_is_active()) {
+        atomic_store_explicit(&s_stop, true, memory_order_release);
+    }
+}
+
+bool copy_practice_is_active(void) {
+    return atomic_load_explicit(&s_phase, memory_order_acquire) != PHASE_IDLE;
+}
+
+trainer_score_t copy_practice_get_score(void) {
+    return s_score;
+}
+
+void copy_practice_tick(int64_t now_us) {
+    phase_t phase = (phase_t)atomic_load_explicit(&s_phase, memory_order_acquire);
+    if (phase == PHASE_IDLE) {
+        return;
+    }
+
+    if (atomic_load_explicit(&s_stop, memory_order_acquire)) {
+        if (phase == PHASE_PLAYING && text_keyer_tx_inhibited()) {
+            text_keyer_abort();
+        }
+        report("Stopped");
+        finish_session();
+        return;
+    }
+
+    switch (phase) {
+        case PHASE_NEXT:
+            if (now_us - s_phase_start_us >= ITEM_GAP_US) {
+                play_next(now_us);
+            }
+            break;
+
+        case PHASE_PLAYING:
+            if (text_keyer_get_state() == TEXT_KEYER_IDLE) {
+                start_listening(now_us);
+            }
+            break;
+
+        case PHASE_LISTENING:
+            if (collect_echo(now_us)) {
+                score_echo(now_us);
+            }
+            break;
+
+        default:
+            break;
+    }
+}
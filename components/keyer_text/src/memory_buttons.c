@@ -0,0 +1,53 @@
+/**
+ * @file memory_buttons.c
+ * @brief Memory and function button debouncing
+ */
+
+#include "memory_buttons.h"
+
+void memory_buttons_init(memory_buttons_t *buttons) {
+    buttons->stable = 0;
+    buttons->candidate = 0;
+    buttons->changed_us = 0;
+    buttons->last = -1;
+    buttons->pressed_us = 0;
+    buttons->long_reported = false;
+}
+
+int memory_buttons_update(memory_buttons_t *buttons, uint16_t raw, int64_t now_us) {
+    if (raw != buttons->candidate) {
+        buttons->candidate = raw;
+        buttons->changed_us = now_us;
+        return -1;
+    }
+    if (raw == buttons->stable || now_us - buttons->changed_us < MEMORY_BUTTONS_DEBOUNCE_US) {
+        return -1;
+    }
+
+    /* Only press edges count; releases just update the state */
+    uint16_t pressed = (uint16_t)(raw & ~buttons->stable);
+    buttons->stable = raw;
+    for (int i = 0; i < MEMORY_BUTTONS_MAX; i++) {
+        if ((pressed & (1U << i)) != 0) {
+            buttons->last = (int8_t)i;
+            buttons->pressed_us = now_us;
+            buttons->long_reported = false;
+            return i;
+        }
+    }
+    return -1;
+}
+
+int memory_buttons_long_press(memory_buttons_t *buttons, int64_t now_us) {
+    int i = buttons->last;
+    if (i < 0 || buttons->long_reported || !memory_buttons_is_held(buttons, i) ||
+        now_us - buttons->pressed_us < MEMORY_BUTTONS_LONG_US) {
+        return -1;
+    }
+    buttons->long_reported = true;
+    return i;
+}
+
+bool memory_buttons_is_held(const memory_buttons_t *buttons, int index) {
+    return index >= 0 && index < MEMORY_BUTTONS_MAX && (buttons->stable & (1U << index)) != 0;
+}
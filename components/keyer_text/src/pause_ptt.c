@@ -0,0 +1,22 @@
+/**
+ * @file pause_ptt.c
+ * @brief PTT hold while a message is paused
+ */
+
+#include "pause_ptt.h"
+
+void pause_ptt_start(pause_ptt_t *p, bool hold, int64_t now_us) {
+    p->held = hold;
+    p->start_us = now_us;
+}
+
+bool pause_ptt_tick(pause_ptt_t *p, int64_t now_us) {
+    if (p->held && now_us - p->start_us >= PAUSE_PTT_MAX_US) {
+        p->held = false;
+    }
+    return p->held;
+}
+
+void pause_ptt_end(pause_ptt_t *p) {
+    p->held = false;
+}
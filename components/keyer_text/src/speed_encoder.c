@@ -0,0 +1,35 @@
+/**
+ * @file speed_encoder.c
+ * @brief Rotary encoder speed control implementation
+ */
+
+#include "speed_encoder.h"
+
+void speed_encoder_init(speed_encoder_t *encoder) {
+    encoder->last_us = 0;
+    encoder->last_dir = 0;
+}
+
+int speed_encoder_steps(speed_encoder_t *encoder, int detents, int64_t now_us) {
+    if (detents == 0) {
+        return 0;
+    }
+
+    int dir = (detents > 0) ? 1 : -1;
+    int count = detents * dir;
+
+    /* Several detents in one poll share the time since the last one */
+    int per_step = 1;
+    if (encoder->last_us != 0 && dir == encoder->last_dir) {
+        int64_t interval_us = (now_us - encoder->last_us) / count;
+        if (interval_us < SPEED_ENCODER_FAST_US) {
+            per_step = 4;
+        } else if (interval_us < SPEED_ENCODER_QUICK_US) {
+            per_step = 2;
+        }
+    }
+
+    encoder->last_us = now_us;
+    encoder->last_dir = dir;
+    return detents * per_step;
+}
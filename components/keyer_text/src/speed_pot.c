@@ -0,0 +1,58 @@
+/**
+ * @file speed_pot.c
+ * @brief Speed potentiometer implementation
+ */
+
+#include "speed_pot.h"
+
+/** Filter full scale (raw x 16) */
+#define FILTERED_MAX ((int32_t)SPEED_POT_RAW_MAX * 16)
+
+void speed_pot_init(speed_pot_t *pot) {
+    pot->filtered = 0;
+    pot->primed = false;
+    pot->wpm = 0;
+}
+
+uint16_t speed_pot_update(speed_pot_t *pot, uint16_t raw, uint16_t min_wpm, uint16_t max_wpm) {
+    if (raw > SPEED_POT_RAW_MAX) {
+        raw = SPEED_POT_RAW_MAX;
+    }
+    if (min_wpm > max_wpm) {
+        uint16_t t = min_wpm;
+        min_wpm = max_wpm;
+        max_wpm = t;
+    }
+
+    int32_t sample = (int32_t)raw * 16;
+    if (!pot->primed) {
+        pot->filtered = sample;
+    } else {
+        pot->filtered += (sample - pot->filtered) / SPEED_POT_FILTER;
+    }
+
+    /* Position above min_wpm in 1/256 WPM */
+    int32_t span = (int32_t)(max_wpm - min_wpm);
+    int32_t pos = (int32_t)(((int64_t)pot->filtered * span * 256) / FILTERED_MAX);
+    uint16_t wpm = (uint16_t)(min_wpm + (pos + 128) / 256);
+
+    if (!pot->primed) {
+        pot->primed = true;
+        pot->wpm = wpm;
+        return 0;
+    }
+    if (wpm == pot->wpm) {
+        return 0;
+    }
+
+    /* Limits changed: pot->wpm may be out of range, take the new value */
+    if (pot->wpm >= min_wpm && pot->wpm <= max_wpm) {
+        int32_t current = (int32_t)(pot->wpm - min_wpm) * 256;
+        int32_t moved = (pos > current) ? pos - current : current - pos;
+        if (moved < 128 + SPEED_POT_HYSTERESIS) {
+            return 0;
+        }
+    }
+    pot->wpm = wpm;
+    return wpm;
+}
@@ -0,0 +1,85 @@
+/**
+ * @file text_directive.c
+ * @brief Embedded message command parser
+ */
+
+#include "text_directive.h"
+#include <stdbool.h>
+#include <ctype.h>
+
+/**
+ * @brief Parse the decimal argument of a command body
+ * @return false if missing, not a number or outside [min, max]
+ */
+static bool parse_arg(const char *s, size_t len, uint32_t min, uint32_t max, uint32_t *out) {
+    if (len == 0) {
+        return false;
+    }
+    uint32_t v = 0;
+    for (size_t i = 0; i < len; i++) {
+        if (!isdigit((unsigned char)s[i])) {
+            return false;
+        }
+        v = v * 10U + (uint32_t)(s[i] - '0');
+    }
+    if (v < min || v > max) {
+        return false;
+    }
+    *out = v;
+    return true;
+}
+
+size_t text_directive_parse(const char *s, text_directive_t *out) {
+    if (s == NULL || out == NULL || s[0] != TEXT_DIRECTIVE_MARK) {
+        return 0;
+    }
+
+    /* Find the closing bar */
+    size_t len = 0;
+    while (len <= TEXT_DIRECTIVE_BODY_MAX && s[1 + len] != TEXT_DIRECTIVE_MARK) {
+        if (s[1 + len] == '\0') {
+            return 0;
+        }
+        len++;
+    }
+    if (len > TEXT_DIRECTIVE_BODY_MAX) {
+        return 0;
+    }
+
+    out->type = TEXT_DIRECTIVE_INVALID;
+    out->value = 0;
+    const char *body = s + 1;
+    const char *arg = body + 1;
+    size_t arg_len = (len > 0) ? len - 1 : 0;
+
+    switch ((len > 0) ? toupper((unsigned char)body[0]) : 0) {
+        case 'S':
+            if (parse_arg(arg, arg_len, 5, 60, &out->value)) {
+                out->type = TEXT_DIRECTIVE_SPEED;
+            }
+            break;
+        case 'P':
+            if (parse_arg(arg, arg_len, 1, 60, &out->value)) {
+                out->type = TEXT_DIRECTIVE_PAUSE;
+            }
+            break;
+        case 'R':
+            if (arg_len == 0) {
+                out->type = TEXT_DIRECTIVE_REPEAT;
+            }
+            break;
+        case 'M':
+            if (parse_arg(arg, arg_len, 1, TEXT_MEMORY_SLOTS, &out->value)) {
+                out->type = TEXT_DIRECTIVE_MEMORY;
+            }
+            break;
+        case 'N':
+            if (parse_arg(arg, arg_len, CONTEST_SERIAL_FIRST, CONTEST_SERIAL_MAX, &out->value)) {
+                out->type = TEXT_DIRECTIVE_SERIAL;
+            }
+            break;
+        default:
+            break;
+    }
+    return len + 2;
+}
@@ -4,6 +4,14 @@
  */
 
 #include "text_keyer.h"
+#include "text_directive.h"
+#include "contest_serial.h"
+#include "callsign.h"
+#include "decoder.h"
+#include "type_queue.h"
+#include "abbrev.h"
+#include "text_memory.h"
+#include "pause_ptt.h"
 #include "morse_table.h"
 #include "config.h"
 #include <string.h>
@@ -19,6 +27,8 @@ typedef enum {
     ELEMENT_INTRA_GAP,
     ELEMENT_CHAR_GAP,
     ELEMENT_WORD_GAP,
+    ELEMENT_PAUSE,          /**< |Pn| embedded pause */
+    ELEMENT_WAIT,           /**< Keyboard mode, nothing typed yet */
 } element_type_t;
 
 typedef struct {
@@ -30,60 +40,377 @@ typedef struct {
     element_type_t element;
     int64_t element_end_us;
     bool key_down;
+    uint32_t wpm;           /**< |Sn| speed, 0 = global WPM */
+    int64_t pause_us;       /**< Length of the pending |Pn| pause */
+    bool emitted;           /**< Something sent since the start (or last |R|, |Mn|) */
+    uint8_t chain_hops;     /**< |Mn| jumps taken */
+    bool keyboard;          /**< Sending from the typed queue, not text[] */
+    char prosign[MORSE_PROSIGN_PATTERN_MAX];  /**< Current <XY> pattern */
+    bool practice;          /**< Trainer text: sidetone only */
+    uint32_t effective_wpm; /**< Farnsworth overall speed, 0 = none */
+    bool repeat;            /**< Start over after keyer.msg_repeat_s */
+    bool rx_wait;           /**< In the pause between repeats */
+    bool rx_quiet;          /**< Decoder seen idle during that pause */
+    bool tune;              /**< Steady carrier, no text */
 } send_state_t;
 
 /* ============================================================================
  * Module State
  * ============================================================================ */
 
-static const atomic_bool *s_paddle_abort = NULL;
+static atomic_bool *s_paddle_touch = NULL;
 static text_keyer_state_t s_state = TEXT_KEYER_IDLE;
 static send_state_t s_send = {0};
 
 /* Atomic key state for RT task polling (Core 0 reads, Core 1 writes) */
 static atomic_bool s_key_down = ATOMIC_VAR_INIT(false);
 
+/* Character being keyed (see text_keyer_char_tag()), read by the RT task */
+static atomic_uint_fast32_t s_char_tag = ATOMIC_VAR_INIT(0);
+
+/* Practice text keys the sidetone only; TX is inhibited for the session */
+static atomic_bool s_tone_down = ATOMIC_VAR_INIT(false);
+static atomic_bool s_practice = ATOMIC_VAR_INIT(false);
+static atomic_bool s_tx_hold = ATOMIC_VAR_INIT(false);
+
+/* Thermal guard: no tune, no repeats (set by bg_task) */
+static atomic_bool s_long_tx_blocked = ATOMIC_VAR_INIT(false);
+
+/* Pause requested (console, WebUI, button), taken at the next character */
+static atomic_bool s_pause_request = ATOMIC_VAR_INIT(false);
+
+/* PTT kept up while paused (keyer.pause_ptt), read by the RT task */
+static atomic_bool s_ptt_hold = ATOMIC_VAR_INIT(false);
+static pause_ptt_t s_pause_ptt;
+
+/* Speed nudges (console, WebUI), applied at the next character */
+static atomic_int s_speed_nudge = ATOMIC_VAR_INIT(0);
+
+/* Keyboard mode: console types into the queue, tick keys it out */
+static type_queue_t s_typed;
+static atomic_bool s_typing_open = ATOMIC_VAR_INIT(false);
+
+/* ;shortcut being typed (console task only) */
+static abbrev_input_t s_abbrev;
+
 /* ============================================================================
  * Timing Helpers
  * ============================================================================ */
 
+/** Playback speed limits (as |Sn|) */
+#define WPM_MIN 5
+#define WPM_MAX 60
+
+/**
+ * @brief Current playback speed (|Sn|, +/- and nudges, else keyer.wpm)
+ */
+static uint32_t playback_wpm(void) {
+    uint32_t wpm = (s_send.wpm != 0) ? s_send.wpm : CONFIG_GET_WPM();
+    if (wpm < WPM_MIN) wpm = WPM_MIN;
+    if (wpm > WPM_MAX) wpm = WPM_MAX;
+    return wpm;
+}
+
 static int64_t dit_duration_us(void) {
-    uint32_t wpm = CONFIG_GET_WPM();
-    if (wpm < 5) wpm = 5;
-    if (wpm > 60) wpm = 60;
-    return 1200000 / (int64_t)wpm;
+    return 1200000 / (int64_t)playback_wpm();
+}
+
+/**
+ * @brief Shift the playback speed by whole WPM steps (clamped)
+ */
+static void shift_speed(int steps) {
+    int wpm = (int)playback_wpm() + steps;
+    if (wpm < WPM_MIN) wpm = WPM_MIN;
+    if (wpm > WPM_MAX) wpm = WPM_MAX;
+    s_send.wpm = (uint32_t)wpm;
+}
+
+/**
+ * @brief Gap between characters (3 dits, or Farnsworth-stretched)
+ *
+ * ARRL Farnsworth: total spacing delay ta = (60c - 37.2s) / (sc) seconds
+ * for character speed c and overall speed s, split 3/19 per character
+ * gap and 7/19 per word gap.
+ */
+static int64_t gap_us(int64_t dit_us, int64_t units) {
+    uint32_t c = (uint32_t)(1200000 / dit_us);
+    uint32_t s = s_send.effective_wpm;
+    if (s == 0 || s >= c) {
+        return dit_us * units;
+    }
+    int64_t ta_us = (60000000LL * c - 37200000LL * s) / ((int64_t)s * c);
+    return ta_us * units / 19;
 }
 
 /* ============================================================================
  * Key State Management
  * ============================================================================ */
 
+/**
+ * @brief Publish the character whose first element is about to key
+ *
+ * Stored before the key goes down, so the RT task sees the new tag
+ * with the key-down that starts the character.
+ */
+static void publish_char(const char *pattern) {
+    char c = morse_table_lookup(pattern);
+    if (c == '\0') {
+        c = TEXT_KEYER_MARK_GROUP;
+    }
+    uint32_t count = (uint32_t)(atomic_load_explicit(&s_char_tag, memory_order_relaxed) >> 8);
+    atomic_store_explicit(&s_char_tag, ((count + 1U) << 8) | (uint8_t)c,
+                          memory_order_release);
+}
+
 static void set_key_down(bool key_down) {
-    atomic_store_explicit(&s_key_down, key_down, memory_order_release);
+    atomic_store_explicit(s_send.practice ? &s_tone_down : &s_key_down,
+                          key_down, memory_order_release);
+}
+
+/**
+ * @brief Back to IDLE with both key outputs released
+ */
+static void finish_send(void) {
+    s_state = TEXT_KEYER_IDLE;
+    atomic_store_explicit(&s_pause_request, false, memory_order_relaxed);
+    pause_ptt_end(&s_pause_ptt);
+    atomic_store_explicit(&s_ptt_hold, false, memory_order_release);
+    atomic_store_explicit(&s_key_down, false, memory_order_release);
+    atomic_store_explicit(&s_tone_down, false, memory_order_release);
+    atomic_store_explicit(&s_practice, false, memory_order_release);
+}
+
+/**
+ * @brief Consume the paddle touch latch
+ * @return true if a paddle was closed since the last call
+ */
+static bool take_paddle_touch(void) {
+    return s_paddle_touch != NULL &&
+           atomic_exchange_explicit(s_paddle_touch, false, memory_order_acq_rel);
 }
 
 /* ============================================================================
  * Pattern Navigation
  * ============================================================================ */
 
+/** Most |Mn| jumps in one message: a chain that loops back is stopped */
+#define CHAIN_MAX 8
+
+/**
+ * @brief Expand %C, then %N (a decoded call never contains %N)
+ */
+static void expand_message(const char *text, char *out, size_t out_len) {
+    char call[CALLSIGN_MAX_LEN + 1];
+    char with_call[TEXT_KEYER_MAX_LEN];
+    decoder_get_last_callsign(call, sizeof(call));
+    callsign_expand(text, call, with_call, sizeof(with_call));
+    contest_serial_expand(with_call, out, out_len);
+}
+
+/**
+ * @brief |Mn|: continue with memory slot n
+ *
+ * Stops the message instead if the slot is empty or the chain is
+ * already CHAIN_MAX jumps long. Speed set by |Sn| carries over.
+ */
+static void chain_to_memory(uint32_t slot) {
+    text_memory_slot_t mem;
+    if (s_send.chain_hops >= CHAIN_MAX ||
+        text_memory_get((uint8_t)(slot - 1), &mem) != 0) {
+        s_send.char_index = s_send.text_len;
+        return;
+    }
+    expand_message(mem.text, s_send.text, sizeof(s_send.text));
+    s_send.text_len = strlen(s_send.text);
+    s_send.char_index = 0;
+    s_send.emitted = false;  /* |R| in the new slot repeats that slot */
+    s_send.chain_hops++;
+}
+
+/** Marker patterns returned by get_next_pattern() */
+#define PATTERN_WORD_GAP " "
+#define PATTERN_PAUSE    "|"
+
+/** Longest tune carrier: a stuck button must not hold TX forever */
+#define TUNE_MAX_US (20 * 1000 * 1000)
+
+/**
+ * @brief Check for keying heard between repeats (someone answered)
+ *
+ * Our own last character is still being decoded when the pause starts,
+ * so only a decoder that went idle and then busy again counts.
+ */
+static bool rx_heard(void) {
+    if (decoder_get_state() == DECODER_STATE_IDLE) {
+        s_send.rx_quiet = true;
+        return false;
+    }
+    return s_send.rx_quiet;
+}
+
+/**
+ * @brief Repeat mode: pause, then the text again from the start
+ *
+ * Like |R|, nothing is repeated if nothing was sent.
+ */
+static const char *repeat_message(void) {
+    if (!s_send.repeat || !s_send.emitted) {
+        return NULL;
+    }
+    s_send.char_index = 0;
+    s_send.wpm = 0;
+    s_send.emitted = false;
+    s_send.pause_us = (int64_t)CONFIG_GET_MSG_REPEAT_S() * 1000000;
+    s_send.rx_wait = true;
+    s_send.rx_quiet = false;
+    return PATTERN_PAUSE;
+}
+
+/**
+ * @brief Execute an embedded command at the current position
+ *
+ * @param pattern Set to PATTERN_PAUSE for |Pn|
+ * @return false if the text there is not a command (send it as text)
+ */
+static bool run_directive(const char **pattern) {
+    text_directive_t d;
+    size_t len = text_directive_parse(&s_send.text[s_send.char_index], &d);
+    if (len == 0) {
+        return false;
+    }
+    s_send.char_index += len;
+
+    switch (d.type) {
+        case TEXT_DIRECTIVE_SPEED:
+            s_send.wpm = d.value;
+            break;
+        case TEXT_DIRECTIVE_PAUSE:
+            s_send.pause_us = (int64_t)d.value * 1000000;
+            *pattern = PATTERN_PAUSE;
+            break;
+        case TEXT_DIRECTIVE_REPEAT:
+            /* Nothing sent since the start: stop instead of spinning */
+            if (s_send.emitted) {
+                s_send.char_index = 0;
+                s_send.wpm = 0;
+                s_send.emitted = false;
+            } else {
+                s_send.char_index = s_send.text_len;
+            }
+            break;
+        case TEXT_DIRECTIVE_MEMORY:
+            chain_to_memory(d.value);
+            break;
+        case TEXT_DIRECTIVE_SERIAL:
+            contest_serial_sent(d.value);
+            break;
+        case TEXT_DIRECTIVE_INVALID:
+        default:
+            break;  /* Skipped */
+    }
+    return true;
+}
+
+/** Longest typed "<...>" group held back until its '>' arrives */
+#define TYPED_GROUP_MAX (MORSE_PROSIGN_CHARS_MAX + 2)
+
+/**
+ * @brief Next pattern from the typed queue
+ *
+ * A typed '<' waits for its '>' so the group is sent run together;
+ * embedded |x| commands are not interpreted.
+ */
+static const char *get_next_typed_pattern(void) {
+    char c;
+    while (type_queue_peek(&s_typed, 0, &c)) {
+        if (c == '<') {
+            char group[TYPED_GROUP_MAX + 1];
+            size_t n = 0;
+            while (n < TYPED_GROUP_MAX && type_queue_peek(&s_typed, n, &group[n])) {
+                if (group[n++] == '>') {
+                    break;
+                }
+            }
+            group[n] = '\0';
+
+            if (group[n - 1] == '>') {
+                if (!type_queue_pop_if(&s_typed, group, n)) {
+                    continue;  /* Edited since the peek: look again */
+                }
+                if (morse_build_prosign(group, s_send.prosign, sizeof(s_send.prosign)) == n) {
+                    return s_send.prosign;
+                }
+                continue;  /* Not a valid group: dropped */
+            }
+            if (n < TYPED_GROUP_MAX &&
+                atomic_load_explicit(&s_typing_open, memory_order_acquire)) {
+                return NULL;  /* Still being typed */
+            }
+            /* Never closed: the '<' alone is dropped below */
+        }
+
+        if (!type_queue_pop_if(&s_typed, &c, 1)) {
+            continue;  /* Edited since the peek */
+        }
+        if (c == ' ') {
+            return PATTERN_WORD_GAP;
+        }
+        const char *pattern = morse_table_reverse(c);
+        if (pattern != NULL) {
+            return pattern;
+        }
+    }
+    return NULL;
+}
+
 static const char *get_next_pattern(void) {
+    /* Character boundary: never stretch or squeeze a character midway */
+    int nudge = atomic_exchange_explicit(&s_speed_nudge, 0, memory_order_acq_rel);
+    if (nudge != 0) {
+        shift_speed(nudge);
+    }
+
+    if (s_send.keyboard) {
+        return get_next_typed_pattern();
+    }
+
     while (s_send.char_index < s_send.text_len) {
         char c = s_send.text[s_send.char_index];
 
-        /* Check for prosign */
-        if (c == '<') {
+        /* Speed step: 1 WPM faster/slower from the next character */
+        if (c == '+' || c == '-') {
+            shift_speed((c == '+') ? 1 : -1);
+            s_send.char_index++;
+            continue;
+        }
+
+        /* Embedded command */
+        if (c == TEXT_DIRECTIVE_MARK) {
             const char *pattern = NULL;
-            size_t len = morse_match_prosign(&s_send.text[s_send.char_index], &pattern);
-            if (len > 0 && pattern != NULL) {
+            if (run_directive(&pattern)) {
+                if (pattern != NULL) {
+                    s_send.emitted = true;
+                    return pattern;
+                }
+                continue;
+            }
+        }
+
+        /* Prosign or other run-together group */
+        if (c == '<') {
+            size_t len = morse_build_prosign(&s_send.text[s_send.char_index],
+                                             s_send.prosign, sizeof(s_send.prosign));
+            if (len > 0) {
                 s_send.char_index += len;
-                return pattern;
+                s_send.emitted = true;
+                return s_send.prosign;
             }
         }
 
         /* Space = word gap */
         if (c == ' ') {
             s_send.char_index++;
-            return " ";  /* Special marker for word gap */
+            return PATTERN_WORD_GAP;
         }
 
         /* Regular character */
@@ -91,11 +418,12 @@ static const char *get_next_pattern(void) {
         s_send.char_index++;
 
         if (pattern != NULL) {
+            s_send.emitted = true;
             return pattern;
         }
         /* Skip unknown characters */
     }
-    return NULL;
+    return repeat_message();
 }
 
 static bool start_next_element(int64_t now_us) {
@@ -105,11 +433,12 @@ static bool start_next_element(int64_t now_us) {
     if (s_send.current_pattern == NULL ||
         s_send.current_pattern[s_send.pattern_index] == '\0') {
 
-        /* Char gap before next character (unless first or word gap) */
+        /* Char gap before next character (unless first, word gap or pause) */
         if (s_send.current_pattern != NULL &&
-            s_send.current_pattern[0] != ' ') {
+            s_send.current_pattern[0] != ' ' &&
+            s_send.current_pattern[0] != TEXT_DIRECTIVE_MARK) {
             s_send.element = ELEMENT_CHAR_GAP;
-            s_send.element_end_us = now_us + (dit_us * 3);
+            s_send.element_end_us = now_us + gap_us(dit_us, 3);
             s_send.key_down = false;
             set_key_down(false);
         }
@@ -118,19 +447,37 @@ static bool start_next_element(int64_t now_us) {
         s_send.pattern_index = 0;
 
         if (s_send.current_pattern == NULL) {
+            /* Keyboard mode: wait for typing, a char gap set above still runs */
+            if (s_send.keyboard &&
+                atomic_load_explicit(&s_typing_open, memory_order_acquire)) {
+                if (s_send.element_end_us == 0) {
+                    s_send.element = ELEMENT_WAIT;
+                }
+                return true;
+            }
             return false;  /* Done */
         }
 
         /* Word gap */
         if (s_send.current_pattern[0] == ' ') {
             s_send.element = ELEMENT_WORD_GAP;
-            s_send.element_end_us = now_us + (dit_us * 7);
+            s_send.element_end_us = now_us + gap_us(dit_us, 7);
             s_send.key_down = false;
             set_key_down(false);
             s_send.current_pattern = NULL;  /* Force get next pattern */
             return true;
         }
 
+        /* Embedded pause: key up, then continue like after a word gap */
+        if (s_send.current_pattern[0] == TEXT_DIRECTIVE_MARK) {
+            s_send.element = ELEMENT_PAUSE;
+            s_send.element_end_us = now_us + s_send.pause_us;
+            s_send.key_down = false;
+            set_key_down(false);
+            s_send.current_pattern = NULL;
+            return true;
+        }
+
         /* Skip char gap if we just set one */
         if (s_send.element == ELEMENT_CHAR_GAP) {
             return true;
@@ -138,6 +485,9 @@ static bool start_next_element(int64_t now_us) {
     }
 
     /* Send dit or dah */
+    if (s_send.pattern_index == 0) {
+        publish_char(s_send.current_pattern);
+    }
     char elem = s_send.current_pattern[s_send.pattern_index++];
 
     if (elem == '.') {
@@ -183,10 +533,12 @@ int text_keyer_init(const text_keyer_config_t *config) {
         return -1;
     }
 
-    s_paddle_abort = config->paddle_abort;
+    s_paddle_touch = config->paddle_touch;
     s_state = TEXT_KEYER_IDLE;
     set_key_down(false);
     memset(&s_send, 0, sizeof(s_send));
+    type_queue_init(&s_typed);
+    atomic_store_explicit(&s_typing_open, false, memory_order_relaxed);
 
     return 0;
 }
@@ -200,15 +552,12 @@ int text_keyer_send(const char *text) {
         return -1;
     }
 
-    size_t len = strlen(text);
-    if (len >= TEXT_KEYER_MAX_LEN) {
-        len = TEXT_KEYER_MAX_LEN - 1;
-    }
-
     memset(&s_send, 0, sizeof(s_send));
-    memcpy(s_send.text, text, len);
-    s_send.text[len] = '\0';
-    s_send.text_len = len;
+    expand_message(text, s_send.text, sizeof(s_send.text));
+    s_send.text_len = strlen(s_send.text);
+    (void)take_paddle_touch();  /* Only touches from now on interrupt */
+    atomic_store_explicit(&s_speed_nudge, 0, memory_order_relaxed);
+    atomic_store_explicit(&s_pause_request, false, memory_order_relaxed);
 
     /* Count actual characters (excluding spaces and prosign brackets) */
     s_send.char_index = 0;
@@ -217,30 +566,219 @@ int text_keyer_send(const char *text) {
     return 0;
 }
 
+/**
+ * @brief Queue typed (or abbreviation) characters
+ * @return false if the queue filled up
+ */
+static bool queue_typed(const char *chars, size_t n) {
+    for (size_t i = 0; i < n; i++) {
+        if (!type_queue_push(&s_typed, chars[i])) {
+            return false;
+        }
+    }
+    return true;
+}
+
+int text_keyer_type(char c) {
+    if (s_state != TEXT_KEYER_IDLE && !s_send.keyboard) {
+        return -1;  /* A message is playing */
+    }
+
+    if (s_state == TEXT_KEYER_IDLE) {
+        memset(&s_send, 0, sizeof(s_send));
+        s_send.keyboard = true;
+        type_queue_init(&s_typed);
+        abbrev_input_init(&s_abbrev);
+        atomic_store_explicit(&s_typing_open, true, memory_order_release);
+        (void)take_paddle_touch();
+        s_state = TEXT_KEYER_SENDING;
+    }
+
+    char out[ABBREV_TEXT_MAX + ABBREV_KEY_MAX + 2];
+    size_t n = abbrev_input_feed(&s_abbrev, (char)toupper((unsigned char)c), out, sizeof(out));
+    if (!queue_typed(out, n)) {
+        return -1;
+    }
+    text_keyer_resume();  /* Typing after a paddle pause carries on */
+    return 0;
+}
+
+int text_keyer_backspace(void) {
+    if (s_state == TEXT_KEYER_IDLE || !s_send.keyboard) {
+        return -1;
+    }
+    if (abbrev_input_backspace(&s_abbrev)) {
+        return 0;
+    }
+    return type_queue_backspace(&s_typed) ? 0 : -1;
+}
+
+void text_keyer_type_end(void) {
+    if (text_keyer_is_typing()) {
+        char out[ABBREV_TEXT_MAX + ABBREV_KEY_MAX + 2];
+        (void)queue_typed(out, abbrev_input_flush(&s_abbrev, out, sizeof(out)));
+    }
+    atomic_store_explicit(&s_typing_open, false, memory_order_release);
+}
+
+bool text_keyer_is_typing(void) {
+    return s_state != TEXT_KEYER_IDLE && s_send.keyboard;
+}
+
+size_t text_keyer_get_typed(char *buf, size_t len, size_t *pending) {
+    size_t n = type_queue_tail(&s_typed, buf, len, pending);
+    if (text_keyer_is_typing() && n < len) {
+        /* A ;shortcut still being typed shows as pending */
+        size_t held = abbrev_input_held(&s_abbrev, &buf[n], len - n);
+        if (pending != NULL) {
+            *pending += held;
+        }
+        n += held;
+    }
+    return n;
+}
+
+int text_keyer_send_practice(const char *text, uint32_t char_wpm, uint32_t effective_wpm) {
+    if (text == NULL || text[0] == '\0' || s_state != TEXT_KEYER_IDLE) {
+        return -1;
+    }
+
+    /* Copied as is: no %N, the serial must not advance for practice */
+    memset(&s_send, 0, sizeof(s_send));
+    strncpy(s_send.text, text, sizeof(s_send.text) - 1);
+    s_send.text_len = strlen(s_send.text);
+    s_send.wpm = char_wpm;
+    s_send.effective_wpm = effective_wpm;
+    s_send.practice = true;
+
+    atomic_store_explicit(&s_practice, true, memory_order_release);
+    s_state = TEXT_KEYER_SENDING;
+    return 0;
+}
+
+void text_keyer_hold_tx_off(bool hold) {
+    atomic_store_explicit(&s_tx_hold, hold, memory_order_release);
+}
+
+bool text_keyer_tx_inhibited(void) {
+    return atomic_load_explicit(&s_practice, memory_order_acquire) ||
+           atomic_load_explicit(&s_tx_hold, memory_order_acquire) ||
+           CONFIG_GET_PRACTICE_OSC();
+}
+
+int text_keyer_repeat(void) {
+    if (s_state == TEXT_KEYER_IDLE || s_send.keyboard || s_send.practice || s_send.tune ||
+        text_keyer_long_tx_blocked()) {
+        return -1;
+    }
+    s_send.repeat = true;
+    return 0;
+}
+
+int text_keyer_tune(void) {
+    if (s_state != TEXT_KEYER_IDLE || text_keyer_long_tx_blocked()) {
+        return -1;
+    }
+
+    memset(&s_send, 0, sizeof(s_send));
+    s_send.tune = true;
+    (void)take_paddle_touch();
+    s_state = TEXT_KEYER_SENDING;
+    return 0;
+}
+
+bool text_keyer_is_tuning(void) {
+    return s_state != TEXT_KEYER_IDLE && s_send.tune;
+}
+
+void text_keyer_block_long_tx(bool block) {
+    atomic_store_explicit(&s_long_tx_blocked, block, memory_order_release);
+}
+
+bool text_keyer_long_tx_blocked(void) {
+    return atomic_load_explicit(&s_long_tx_blocked, memory_order_acquire);
+}
+
+int text_keyer_nudge_speed(int steps) {
+    if (s_state == TEXT_KEYER_IDLE) {
+        return -1;
+    }
+    atomic_fetch_add_explicit(&s_speed_nudge, steps, memory_order_acq_rel);
+    return 0;
+}
+
+uint32_t text_keyer_get_wpm(void) {
+    return playback_wpm();
+}
+
+bool text_keyer_is_tone_down(void) {
+    return atomic_load_explicit(&s_tone_down, memory_order_acquire);
+}
+
 void text_keyer_abort(void) {
     if (s_state == TEXT_KEYER_IDLE) return;
 
     /* Always ensure key is released on abort */
-    set_key_down(false);
-
-    s_state = TEXT_KEYER_IDLE;
+    atomic_store_explicit(&s_typing_open, false, memory_order_release);
+    finish_send();
     memset(&s_send, 0, sizeof(s_send));
 }
 
-void text_keyer_pause(void) {
-    if (s_state != TEXT_KEYER_SENDING) return;
+/**
+ * @brief Stop keying now (paddle touch, or a pause reaching a boundary)
+ */
+static void pause_now(int64_t now_us) {
+    /* Character cut short: send it again from its first element */
+    if (s_send.current_pattern != NULL &&
+        (s_send.key_down || s_send.current_pattern[s_send.pattern_index] != '\0')) {
+        s_send.pattern_index = 0;
+    }
 
     if (s_send.key_down) {
         set_key_down(false);
         s_send.key_down = false;
     }
 
+    atomic_store_explicit(&s_pause_request, false, memory_order_relaxed);
+    pause_ptt_start(&s_pause_ptt,
+                    !s_send.practice && CONFIG_GET_PAUSE_PTT() == TEXT_KEYER_PAUSE_PTT_HOLD,
+                    now_us);
+    atomic_store_explicit(&s_ptt_hold, s_pause_ptt.held, memory_order_release);
     s_state = TEXT_KEYER_PAUSED;
 }
 
+/**
+ * @brief Take a requested pause once the character in progress is done
+ *
+ * Call between elements. On resume the next character gets its normal
+ * character gap first, so spacing is as if there was no pause.
+ */
+static bool pause_at_boundary(int64_t now_us) {
+    if (!atomic_load_explicit(&s_pause_request, memory_order_acquire) ||
+        (s_send.current_pattern != NULL &&
+         s_send.current_pattern[s_send.pattern_index] != '\0')) {
+        return false;
+    }
+    pause_now(now_us);
+    return true;
+}
+
+void text_keyer_pause(void) {
+    if (s_state != TEXT_KEYER_SENDING || s_send.tune) return;
+    atomic_store_explicit(&s_pause_request, true, memory_order_release);
+}
+
+bool text_keyer_holds_ptt(void) {
+    return atomic_load_explicit(&s_ptt_hold, memory_order_acquire);
+}
+
 void text_keyer_resume(void) {
+    atomic_store_explicit(&s_pause_request, false, memory_order_release);  /* Not taken yet */
     if (s_state != TEXT_KEYER_PAUSED) return;
 
+    pause_ptt_end(&s_pause_ptt);
+    atomic_store_explicit(&s_ptt_hold, false, memory_order_release);
+    (void)take_paddle_touch();  /* Touches while paused do not count */
     s_send.element_end_us = 0;  /* Restart timing */
     s_state = TEXT_KEYER_SENDING;
 }
@@ -259,31 +797,72 @@ void text_keyer_get_progress(size_t *sent, size_t *total) {
 }
 
 void text_keyer_tick(int64_t now_us) {
+    /* Paused: PTT hold ends at PAUSE_PTT_MAX_US even if never resumed */
+    if (s_state == TEXT_KEYER_PAUSED && !pause_ptt_tick(&s_pause_ptt, now_us)) {
+        atomic_store_explicit(&s_ptt_hold, false, memory_order_release);
+    }
     if (s_state != TEXT_KEYER_SENDING) return;
 
-    /* Check paddle abort */
-    if (s_paddle_abort != NULL &&
-        atomic_load_explicit(s_paddle_abort, memory_order_acquire)) {
-        text_keyer_abort();
+    /* Paddle touched: the operator takes over (practice keeps going) */
+    if (take_paddle_touch() && !s_send.practice) {
+        if (CONFIG_GET_PADDLE_INTERRUPT() == TEXT_KEYER_PADDLE_PAUSE &&
+            !s_send.tune && !s_send.repeat) {
+            pause_now(now_us);  /* At once: the operator is keying */
+        } else {
+            text_keyer_abort();
+        }
+        return;
+    }
+
+    /* Thermal guard: tune and the wait for a repeat stop, a pass finishes */
+    if (text_keyer_long_tx_blocked()) {
+        if (s_send.tune || s_send.rx_wait) {
+            text_keyer_abort();
+            return;
+        }
+        s_send.repeat = false;
+    }
+
+    /* Tune: key down until aborted or TUNE_MAX_US */
+    if (s_send.tune) {
+        if (s_send.element_end_us == 0) {
+            s_send.key_down = true;
+            set_key_down(true);
+            s_send.element_end_us = now_us + TUNE_MAX_US;
+        } else if (now_us >= s_send.element_end_us) {
+            text_keyer_abort();
+        }
         return;
     }
 
     /* First tick - start first element */
     if (s_send.element_end_us == 0) {
+        if (pause_at_boundary(now_us)) {
+            return;
+        }
         if (!start_next_element(now_us)) {
-            s_state = TEXT_KEYER_IDLE;
+            finish_send();
         }
         return;
     }
 
+    /* Calling CQ: stop as soon as anyone is heard */
+    if (s_send.rx_wait && rx_heard()) {
+        text_keyer_abort();
+        return;
+    }
+
     /* Check if current element finished */
     if (now_us >= s_send.element_end_us) {
+        s_send.rx_wait = false;
         finish_element(now_us);
 
         if (s_send.element_end_us == 0) {
+            if (pause_at_boundary(now_us)) {
+                return;
+            }
             if (!start_next_element(now_us)) {
-                s_state = TEXT_KEYER_IDLE;
-                set_key_down(false);  /* Ensure key released when done */
+                finish_send();  /* Ensure key released when done */
             }
         }
     }
@@ -292,3 +871,7 @@ void text_keyer_tick(int64_t now_us) {
 bool text_keyer_is_key_down(void) {
     return atomic_load_explicit(&s_key_down, memory_order_acquire);
 }
+
+uint32_t text_keyer_char_tag(void) {
+    return (uint32_t)atomic_load_explicit(&s_char_tag, memory_order_acquire);
+}
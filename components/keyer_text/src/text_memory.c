@@ -8,7 +8,7 @@
 #include <stdbool.h>
 #include <stdio.h>
 
-#ifdef CONFIG_IDF_TARGET
+#ifdef ESP_PLATFORM
 #include "nvs_flash.h"
 #include "nvs.h"
 #include "esp_log.h"
@@ -39,7 +39,7 @@ static const text_memory_slot_t DEFAULT_SLOTS[] = {
  * NVS Helpers
  * ============================================================================ */
 
-#ifdef CONFIG_IDF_TARGET
+#ifdef ESP_PLATFORM
 static void load_from_nvs(void) {
     nvs_handle_t handle;
     esp_err_t err = nvs_open(NVS_NAMESPACE, NVS_READONLY, &handle);
@@ -0,0 +1,209 @@
+/**
+ * @file trainer.c
+ * @brief Morse trainer practice text generator implementation
+ */
+
+#include "trainer.h"
+#include <string.h>
+
+/* ============================================================================
+ * Word Lists
+ * ============================================================================ */
+
+static const char GROUP_CHARS[] = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+static const char LETTERS[] = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+static const char *const WORDS[] = {
+    "THE", "AND", "FOR", "ARE", "BUT", "NOT", "YOU", "ALL", "ANY", "CAN",
+    "HAD", "HER", "WAS", "ONE", "OUR", "OUT", "DAY", "GET", "HAS", "HIM",
+    "HOW", "MAN", "NEW", "NOW", "OLD", "SEE", "TWO", "WAY", "WHO", "DID",
+    "NAME", "WORK", "GOOD", "HERE", "WILL", "WITH", "HAVE", "THIS", "FROM",
+    "RIG", "ANT", "WX", "QTH", "RST", "TNX", "FB", "OM", "HR", "ES",
+    "UR", "CQ", "DE", "PSE", "AGN", "CUL", "GM", "GA", "GE", "73",
+};
+
+#define WORD_COUNT (sizeof(WORDS) / sizeof(WORDS[0]))
+
+/* ============================================================================
+ * Random Helpers
+ * ============================================================================ */
+
+static uint32_t next_random(uint32_t *seed) {
+    /* xorshift32 */
+    uint32_t x = (*seed != 0) ? *seed : 0x2545F491u;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    *seed = x;
+    return x;
+}
+
+static size_t random_below(uint32_t *seed, size_t n) {
+    return (size_t)(next_random(seed) % (uint32_t)n);
+}
+
+static char random_from(uint32_t *seed, const char *set) {
+    return set[random_below(seed, strlen(set))];
+}
+
+/* ============================================================================
+ * Item Generators
+ * ============================================================================ */
+
+static size_t make_group(uint32_t *seed, char *item) {
+    for (size_t i = 0; i < TRAINER_GROUP_LEN; i++) {
+        item[i] = random_from(seed, GROUP_CHARS);
+    }
+    return TRAINER_GROUP_LEN;
+}
+
+/**
+ * @brief Prefix of 1-2 letters (or letter + digit), one digit, 1-3 letters
+ */
+static size_t make_call(uint32_t *seed, char *item) {
+    size_t n = 0;
+    item[n++] = random_from(seed, LETTERS);
+    switch (random_below(seed, 3)) {
+        case 0:
+            break;
+        case 1:
+            item[n++] = random_from(seed, LETTERS);
+            break;
+        default:
+            item[n++] = (char)('0' + random_below(seed, 10));
+            item[n++] = random_from(seed, LETTERS);
+            break;
+    }
+    item[n++] = (char)('0' + random_below(seed, 10));
+    size_t suffix = 1 + random_below(seed, 3);
+    for (size_t i = 0; i < suffix; i++) {
+        item[n++] = random_from(seed, LETTERS);
+    }
+    return n;
+}
+
+static size_t make_word(uint32_t *seed, char *item) {
+    const char *word = WORDS[random_below(seed, WORD_COUNT)];
+    size_t n = strlen(word);
+    memcpy(item, word, n);
+    return n;
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+int trainer_mode_from_str(const char *name, trainer_mode_t *mode) {
+    if (name == NULL || mode == NULL) {
+        return -1;
+    }
+    if (strcmp(name, "groups") == 0) {
+        *mode = TRAINER_GROUPS;
+    } else if (strcmp(name, "calls") == 0) {
+        *mode = TRAINER_CALLS;
+    } else if (strcmp(name, "words") == 0) {
+        *mode = TRAINER_WORDS;
+    } else {
+        return -1;
+    }
+    return 0;
+}
+
+size_t trainer_generate(trainer_mode_t mode, uint32_t *seed, size_t count,
+                        char *buf, size_t len) {
+    if (buf == NULL || len == 0 || seed == NULL) {
+        return 0;
+    }
+
+    size_t pos = 0;
+    size_t items = 0;
+    while (items < count) {
+        char item[8];  /* Longest: 7-character call */
+        size_t n;
+        switch (mode) {
+            case TRAINER_CALLS: n = make_call(seed, item); break;
+            case TRAINER_WORDS: n = make_word(seed, item); break;
+            case TRAINER_GROUPS:
+            default:            n = make_group(seed, item); break;
+        }
+
+        size_t sep = (items > 0) ? 1 : 0;
+        if (pos + sep + n >= len) {
+            break;
+        }
+        if (sep != 0) {
+            buf[pos++] = ' ';
+        }
+        memcpy(&buf[pos], item, n);
+        pos += n;
+        items++;
+    }
+    buf[pos] = '\0';
+    return items;
+}
+
+/* ============================================================================
+ * Copy Practice Scoring
+ * ============================================================================ */
+
+/**
+ * @brief Levenshtein distance, two rows (strings cut at TRAINER_SCORE_LEN_MAX)
+ */
+static uint32_t edit_distance(const char *a, size_t a_len, const char *b, size_t b_len) {
+    uint16_t prev[TRAINER_SCORE_LEN_MAX + 1];
+    uint16_t cur[TRAINER_SCORE_LEN_MAX + 1];
+
+    for (size_t j = 0; j <= b_len; j++) {
+        prev[j] = (uint16_t)j;
+    }
+    for (size_t i = 1; i <= a_len; i++) {
+        cur[0] = (uint16_t)i;
+        for (size_t j = 1; j <= b_len; j++) {
+            uint16_t best = (uint16_t)(prev[j - 1] + ((a[i - 1] != b[j - 1]) ? 1 : 0));
+            if (prev[j] + 1 < best) {
+                best = (uint16_t)(prev[j] + 1);
+            }
+            if (cur[j - 1] + 1 < best) {
+                best = (uint16_t)(cur[j - 1] + 1);
+            }
+            cur[j] = best;
+        }
+        memcpy(prev, cur, (b_len + 1) * sizeof(prev[0]));
+    }
+    return prev[b_len];
+}
+
+static size_t score_len(const char *s) {
+    size_t n = strlen(s);
+    return (n > TRAINER_SCORE_LEN_MAX) ? TRAINER_SCORE_LEN_MAX : n;
+}
+
+uint32_t trainer_score_item(trainer_score_t *score, const char *sent, const char *copied) {
+    if (score == NULL || sent == NULL) {
+        return 0;
+    }
+    if (copied == NULL) {
+        copied = "";
+    }
+
+    size_t sent_len = score_len(sent);
+    uint32_t errors = edit_distance(sent, sent_len, copied, score_len(copied));
+    if (errors > sent_len) {
+        errors = (uint32_t)sent_len;
+    }
+
+    score->items++;
+    score->chars += (uint32_t)sent_len;
+    score->errors += errors;
+    if (errors == 0) {
+        score->perfect++;
+    }
+    return errors;
+}
+
+uint32_t trainer_score_percent(const trainer_score_t *score) {
+    if (score == NULL || score->chars == 0) {
+        return 100;
+    }
+    return (score->chars - score->errors) * 100u / score->chars;
+}
@@ -0,0 +1,142 @@
+/**
+ * @file type_queue.c
+ * @brief Keyboard keying queue implementation
+ */
+
+#include "type_queue.h"
+
+/* pos = typed count (12 bits) | sent count (12 bits) | edit count (8 bits).
+ * The counts wrap at a multiple of the ring size. Backspace bumps the
+ * edit count, so backspace + retype never brings back an old pos */
+#define POS_COUNT_MASK 0xFFFu
+#define POS_TYPED(p)  ((uint16_t)(((p) >> 20) & POS_COUNT_MASK))
+#define POS_SENT(p)   ((uint16_t)(((p) >> 8) & POS_COUNT_MASK))
+#define POS_EDITS(p)  ((uint8_t)((p) & 0xFFu))
+#define POS_PACK(typed, sent, edits) \
+    (((((uint_fast32_t)(typed)) & POS_COUNT_MASK) << 20) | \
+     ((((uint_fast32_t)(sent)) & POS_COUNT_MASK) << 8) | \
+     (uint_fast32_t)(uint8_t)(edits))
+#define RING_SLOT(n)  ((size_t)(n) & (TYPE_QUEUE_SIZE - 1))
+
+static uint16_t pending_of(uint_fast32_t p) {
+    return (uint16_t)((unsigned)(POS_TYPED(p) - POS_SENT(p)) & POS_COUNT_MASK);
+}
+
+void type_queue_init(type_queue_t *q) {
+    atomic_init(&q->pos, 0);
+    q->history = 0;
+}
+
+bool type_queue_push(type_queue_t *q, char c) {
+    uint_fast32_t p = atomic_load_explicit(&q->pos, memory_order_acquire);
+    if (pending_of(p) >= TYPE_QUEUE_SIZE - 1) {
+        return false;
+    }
+
+    /* Slot is past the consumer's reach until the typed count moves */
+    uint16_t typed = POS_TYPED(p);
+    q->ring[RING_SLOT(typed)] = c;
+    while (!atomic_compare_exchange_weak_explicit(&q->pos, &p,
+                                                  POS_PACK(typed + 1, POS_SENT(p), POS_EDITS(p)),
+                                                  memory_order_release,
+                                                  memory_order_acquire)) {
+        /* Only the sent count can move under us */
+    }
+
+    if (q->history < TYPE_QUEUE_SIZE - 1) {
+        q->history++;
+    }
+    return true;
+}
+
+bool type_queue_backspace(type_queue_t *q) {
+    uint_fast32_t p = atomic_load_explicit(&q->pos, memory_order_acquire);
+    do {
+        if (pending_of(p) == 0) {
+            return false;
+        }
+    } while (!atomic_compare_exchange_weak_explicit(&q->pos, &p,
+                                                    POS_PACK(POS_TYPED(p) - 1, POS_SENT(p),
+                                                             POS_EDITS(p) + 1),
+                                                    memory_order_acq_rel,
+                                                    memory_order_acquire));
+
+    if (q->history > 0) {
+        q->history--;
+    }
+    return true;
+}
+
+bool type_queue_pop(type_queue_t *q, char *c) {
+    uint_fast32_t p = atomic_load_explicit(&q->pos, memory_order_acquire);
+    do {
+        if (pending_of(p) == 0) {
+            return false;
+        }
+    } while (!atomic_compare_exchange_weak_explicit(&q->pos, &p,
+                                                    POS_PACK(POS_TYPED(p), POS_SENT(p) + 1,
+                                                             POS_EDITS(p)),
+                                                    memory_order_acq_rel,
+                                                    memory_order_acquire));
+
+    /* Claimed: backspace can no longer take this slot back */
+    *c = q->ring[RING_SLOT(POS_SENT(p))];
+    return true;
+}
+
+bool type_queue_pop_if(type_queue_t *q, const char *expect, size_t n) {
+    uint_fast32_t p = atomic_load_explicit(&q->pos, memory_order_acquire);
+    do {
+        if (n == 0 || pending_of(p) < n) {
+            return false;
+        }
+        /* A pending slot only changes after a backspace, which moves pos */
+        for (size_t i = 0; i < n; i++) {
+            if (q->ring[RING_SLOT(POS_SENT(p) + i)] != expect[i]) {
+                return false;
+            }
+        }
+    } while (!atomic_compare_exchange_weak_explicit(&q->pos, &p,
+                                                    POS_PACK(POS_TYPED(p), POS_SENT(p) + n,
+                                                             POS_EDITS(p)),
+                                                    memory_order_acq_rel,
+                                                    memory_order_acquire));
+    return true;
+}
+
+bool type_queue_peek(type_queue_t *q, size_t offset, char *c) {
+    uint_fast32_t p = atomic_load_explicit(&q->pos, memory_order_acquire);
+    if (offset >= pending_of(p)) {
+        return false;
+    }
+    *c = q->ring[RING_SLOT(POS_SENT(p) + offset)];
+    return true;
+}
+
+size_t type_queue_pending(type_queue_t *q) {
+    return pending_of(atomic_load_explicit(&q->pos, memory_order_acquire));
+}
+
+size_t type_queue_tail(type_queue_t *q, char *buf, size_t len, size_t *pending) {
+    if (buf == NULL || len == 0) {
+        return 0;
+    }
+
+    uint_fast32_t p = atomic_load_explicit(&q->pos, memory_order_acquire);
+    size_t n = q->history;
+    if (n > len - 1) {
+        n = len - 1;
+    }
+
+    uint16_t start = (uint16_t)(POS_TYPED(p) - n);
+    for (size_t i = 0; i < n; i++) {
+        buf[i] = q->ring[RING_SLOT(start + i)];
+    }
+    buf[n] = '\0';
+
+    if (pending != NULL) {
+        size_t waiting = pending_of(p);
+        *pending = (waiting < n) ? waiting : n;
+    }
+    return n;
+}
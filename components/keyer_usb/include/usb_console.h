@@ -11,6 +11,7 @@
 
 #include "esp_err.h"
 #include <stdbool.h>
+#include <stddef.h>
 
 #ifdef __cplusplus
 extern "C" {
@@ -32,6 +33,16 @@ esp_err_t usb_console_init(void);
  */
 void usb_console_print(const char *str);
 
+/**
+ * @brief Write raw bytes to console (CDC0)
+ *
+ * Output sink for the console fan-out writer (see console_output.h).
+ *
+ * @param data Bytes to write
+ * @param len Number of bytes
+ */
+void usb_console_write(const char *data, size_t len);
+
 /**
  * @brief Print formatted string to console (CDC0)
  *
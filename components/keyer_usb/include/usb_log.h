@@ -1,9 +1,9 @@
 /**
  * @file usb_log.h
- * @brief CDC1 log drain with filtering
+ * @brief CDC1 log sink with filtering
  *
- * Drains RT-safe log ring buffer to CDC1.
- * Supports tag/level filtering via console commands.
+ * Log drain sink (log_drain.h) writing to CDC1, with a level filter of
+ * its own. Per-module levels are set with log_tag_set_level().
  */
 
 #ifndef KEYER_USB_LOG_H
@@ -18,22 +18,12 @@ extern "C" {
 #endif
 
 /**
- * @brief Initialize USB log on CDC1
+ * @brief Initialize USB log on CDC1 (attaches the "usb" log drain sink)
  *
- * @return ESP_OK on success
+ * @return ESP_OK on success, ESP_ERR_NO_MEM if the sink table is full
  */
 esp_err_t usb_log_init(void);
 
-/**
- * @brief USB log drain task
- *
- * Drains RT and BG log streams to CDC1.
- * Runs on Core 1, low priority.
- *
- * @param arg Unused
- */
-void usb_log_task(void *arg);
-
 /**
  * @brief Set global log level filter
  *
@@ -41,15 +31,6 @@ void usb_log_task(void *arg);
  */
 void usb_log_set_level(log_level_t level);
 
-/**
- * @brief Set log level for specific tag
- *
- * @param tag Tag name (max 15 chars)
- * @param level Minimum level for this tag
- * @return ESP_OK on success, ESP_ERR_NO_MEM if too many tags
- */
-esp_err_t usb_log_set_tag_level(const char *tag, log_level_t level);
-
 /**
  * @brief Get current global log level
  *
@@ -57,11 +38,6 @@ esp_err_t usb_log_set_tag_level(const char *tag, log_level_t level);
  */
 log_level_t usb_log_get_level(void);
 
-/**
- * @brief Clear all tag-specific filters
- */
-void usb_log_clear_filters(void);
-
 #ifdef __cplusplus
 }
 #endif
@@ -98,6 +98,14 @@ void usb_console_print(const char *str) {
     tinyusb_cdcacm_write_flush(TINYUSB_CDC_ACM_0, 0);
 }
 
+void usb_console_write(const char *data, size_t len) {
+    if (data == NULL || len == 0) {
+        return;
+    }
+    tinyusb_cdcacm_write_queue(TINYUSB_CDC_ACM_0, (const uint8_t *)data, len);
+    tinyusb_cdcacm_write_flush(TINYUSB_CDC_ACM_0, 0);
+}
+
 void usb_console_printf(const char *fmt, ...) {
     char buf[256];
     va_list args;
@@ -1,137 +1,78 @@
 /**
  * @file usb_log.c
- * @brief CDC1 log drain with filtering
+ * @brief CDC1 log sink with filtering
  */
 
 #include "usb_log.h"
 #include "usb_cdc.h"
 #include "rt_log.h"
+#include "log_drain.h"
 
+#include "tusb.h"
 #include "tusb_cdc_acm.h"
-#include "freertos/FreeRTOS.h"
-#include "freertos/task.h"
 #include "esp_log.h"
-#include <string.h>
 #include <stdio.h>
 
 static const char *TAG = "usb_log";
 
-/* Filter configuration */
-#define MAX_TAG_FILTERS 16
-#define MAX_TAG_LEN 16
-
-typedef struct {
-    char tag[MAX_TAG_LEN];
-    log_level_t level;
-} tag_filter_t;
-
+/* Per-module levels are applied before formatting (log_tag_set_level());
+ * this one only limits what reaches CDC1 */
 static log_level_t s_global_level = LOG_LEVEL_INFO;
-static tag_filter_t s_tag_filters[MAX_TAG_FILTERS];
-static size_t s_tag_filter_count = 0;
 
 /**
- * @brief Check if entry passes filter
+ * @brief Log drain sink: filter, format (colored by level) and queue on CDC1
+ *
+ * Busy while the CDC1 TX FIFO cannot take the whole line; dropped while
+ * no host is connected.
  */
-static bool filter_pass(const log_entry_t *entry, const char *tag) {
-    /* Check tag-specific filter first */
-    for (size_t i = 0; i < s_tag_filter_count; i++) {
-        if (strcmp(s_tag_filters[i].tag, tag) == 0) {
-            return entry->level <= s_tag_filters[i].level;
-        }
+static bool usb_log_sink(const log_entry_t *entry, const char *stream) {
+    (void)stream;
+    if (!usb_cdc_connected(CDC_ITF_LOG) || entry->level > s_global_level) {
+        return true;
     }
 
-    /* Fall back to global level */
-    return entry->level <= s_global_level;
-}
-
-esp_err_t usb_log_init(void) {
-    ESP_LOGI(TAG, "Initializing USB log on CDC1");
-    return ESP_OK;
-}
-
-void usb_log_task(void *arg) {
-    (void)arg;
-
-    log_entry_t entry;
-    char line[160];
-
-    ESP_LOGI(TAG, "USB log drain task started");
-
-    for (;;) {
-        /* Drain RT log stream */
-        while (log_stream_drain(&g_rt_log_stream, &entry)) {
-            if (!filter_pass(&entry, "RT")) {
-                continue;
-            }
-
-            int len = snprintf(line, sizeof(line),
-                "[%lld] %s: %.*s\r\n",
-                entry.timestamp_us,
-                log_level_str(entry.level),
-                (int)entry.len,
-                entry.msg);
-
-            if (len > 0 && usb_cdc_connected(CDC_ITF_LOG)) {
-                tinyusb_cdcacm_write_queue(TINYUSB_CDC_ACM_1, (uint8_t *)line, (size_t)len);
-            }
-        }
-
-        /* Drain BG log stream */
-        while (log_stream_drain(&g_bg_log_stream, &entry)) {
-            if (!filter_pass(&entry, "BG")) {
-                continue;
-            }
-
-            int len = snprintf(line, sizeof(line),
-                "[%lld] %s: %.*s\r\n",
-                entry.timestamp_us,
-                log_level_str(entry.level),
-                (int)entry.len,
-                entry.msg);
-
-            if (len > 0 && usb_cdc_connected(CDC_ITF_LOG)) {
-                tinyusb_cdcacm_write_queue(TINYUSB_CDC_ACM_1, (uint8_t *)line, (size_t)len);
-            }
-        }
+    char text[LOG_LINE_MAX];
+    size_t text_len = log_entry_format(entry, text, sizeof(text));
+    if (text_len < 2) {
+        return true;
+    }
 
-        /* Flush and yield */
-        if (usb_cdc_connected(CDC_ITF_LOG)) {
-            tinyusb_cdcacm_write_flush(TINYUSB_CDC_ACM_1, 0);
-        }
-        vTaskDelay(pdMS_TO_TICKS(10));
+    /* Color up to the CR/LF, reset before it */
+    char line[LOG_LINE_MAX + 16];
+    const char *color = log_level_color(entry->level);
+    int len = snprintf(line, sizeof(line), "%s%.*s%s\r\n",
+        color, (int)(text_len - 2), text,
+        (color[0] != '\0') ? LOG_COLOR_RESET : "");
+    if (len <= 0) {
+        return true;
     }
-}
 
-void usb_log_set_level(log_level_t level) {
-    s_global_level = level;
+    size_t n = ((size_t)len < sizeof(line)) ? (size_t)len : sizeof(line) - 1;
+    if (tud_cdc_n_write_available(TINYUSB_CDC_ACM_1) < n) {
+        return false;
+    }
+    tinyusb_cdcacm_write_queue(TINYUSB_CDC_ACM_1, (uint8_t *)line, n);
+    return true;
 }
 
-esp_err_t usb_log_set_tag_level(const char *tag, log_level_t level) {
-    /* Check if tag already exists */
-    for (size_t i = 0; i < s_tag_filter_count; i++) {
-        if (strcmp(s_tag_filters[i].tag, tag) == 0) {
-            s_tag_filters[i].level = level;
-            return ESP_OK;
-        }
+static void usb_log_flush(void) {
+    if (usb_cdc_connected(CDC_ITF_LOG)) {
+        tinyusb_cdcacm_write_flush(TINYUSB_CDC_ACM_1, 0);
     }
+}
 
-    /* Add new tag filter */
-    if (s_tag_filter_count >= MAX_TAG_FILTERS) {
+esp_err_t usb_log_init(void) {
+    ESP_LOGI(TAG, "Initializing USB log on CDC1");
+    if (log_drain_add_sink("usb", usb_log_sink, usb_log_flush) < 0) {
         return ESP_ERR_NO_MEM;
     }
-
-    strncpy(s_tag_filters[s_tag_filter_count].tag, tag, MAX_TAG_LEN - 1);
-    s_tag_filters[s_tag_filter_count].tag[MAX_TAG_LEN - 1] = '\0';
-    s_tag_filters[s_tag_filter_count].level = level;
-    s_tag_filter_count++;
-
     return ESP_OK;
 }
 
-log_level_t usb_log_get_level(void) {
-    return s_global_level;
+void usb_log_set_level(log_level_t level) {
+    s_global_level = level;
 }
 
-void usb_log_clear_filters(void) {
-    s_tag_filter_count = 0;
+log_level_t usb_log_get_level(void) {
+    return s_global_level;
 }
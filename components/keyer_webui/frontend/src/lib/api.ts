@@ -61,8 +61,9 @@ class ApiClient {
   }
 
   // Decoder
-  async getDecoderStatus(): Promise<DecoderStatus> {
-    return this.fetchJson('/api/decoder/status');
+  async getDecoderStatus(since?: number): Promise<DecoderStatus> {
+    const url = since === undefined ? '/api/decoder/status' : `/api/decoder/status?since=${since}`;
+    return this.fetchJson(url);
   }
 
   async setDecoderEnabled(enabled: boolean): Promise<void> {
@@ -103,6 +104,14 @@ class ApiClient {
     await this.fetchJson('/api/text/resume', { method: 'POST' });
   }
 
+  async nudgeTextSpeed(delta: number): Promise<void> {
+    await this.fetchJson('/api/text/speed', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ delta })
+    });
+  }
+
   async getMemorySlots(): Promise<{ slots: MemorySlot[] }> {
     return this.fetchJson('/api/text/memory');
   }
@@ -115,11 +124,11 @@ class ApiClient {
     });
   }
 
-  async playMemorySlot(slot: number): Promise<void> {
+  async playMemorySlot(slot: number, repeat = false): Promise<void> {
     await this.fetchJson('/api/text/play', {
       method: 'POST',
       headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify({ slot })
+      body: JSON.stringify({ slot, repeat })
     });
   }
 
@@ -210,7 +219,7 @@ class ApiClient {
 
     switch (msg.type) {
       case 'decoded':
-        this.wsCallbacks.onDecodedChar?.(msg.char, msg.wpm);
+        this.wsCallbacks.onDecodedText?.(msg.text, msg.wpm, msg.seq, ts, msg.sent);
         break;
       case 'word':
         this.wsCallbacks.onWord?.();
@@ -253,8 +262,11 @@ class ApiClient {
 // WebSocket message types
 interface WSMessageDecoded {
   type: 'decoded';
-  char: string;
+  text: string;
+  sent: string;  // text keyer character per position of text, ' ' where hand keyed
   wpm: number;
+  ts: number;
+  seq: number;  // decoder cursor of text[0]
 }
 
 interface WSMessageWord {
@@ -289,7 +301,7 @@ interface WSMessageGap {
 type WSMessage = WSMessageDecoded | WSMessageWord | WSMessagePattern | WSMessagePaddle | WSMessageKeying | WSMessageGap;
 
 export interface WSCallbacks {
-  onDecodedChar?: (char: string, wpm: number) => void;
+  onDecodedText?: (text: string, wpm: number, seq: number, ts: number, sent: string) => void;
   onWord?: () => void;
   onPattern?: (pattern: string) => void;
   onPaddle?: (ts: number, paddle: number, state: number) => void;
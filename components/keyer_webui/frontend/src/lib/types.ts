@@ -3,11 +3,24 @@ export interface CWNetStatus {
   latency_ms: number;
 }
 
+export interface RaisedFault {
+  code: string;
+  severity: 'INFO' | 'WARNING' | 'CRITICAL';
+}
+
+export interface FaultStatus {
+  shown: string | null;
+  tx_stopped: boolean;
+  count: number;
+  raised: RaisedFault[];
+}
+
 export interface DeviceStatus {
   mode: string;
   ip: string;
   ready: boolean;
   cwnet?: CWNetStatus;
+  fault?: FaultStatus;
 }
 
 export interface SystemUptime {
@@ -31,9 +44,18 @@ export interface TaskInfo {
   stack_hwm: number;
 }
 
+export interface CpuLoad {
+  core: number;
+  load: number | null;  // percent, null until the first sample
+  avg: number | null;
+  peak: number | null;
+}
+
 export interface SystemStats {
   uptime: SystemUptime;
   heap: HeapInfo;
+  cpu: CpuLoad[];
+  cpu_window_s: number;
   tasks: TaskInfo[];
 }
 
@@ -42,6 +64,8 @@ export interface DecoderStatus {
   wpm: number;
   pattern: string;
   text: string;
+  sent: string;  // text keyer character per position of text, ' ' where hand keyed
+  next: number;  // cursor for the next getDecoderStatus(since)
 }
 
 export interface TimelineConfig {
@@ -56,6 +80,8 @@ export interface ParameterMeta {
   min?: number;
   max?: number;
   unit?: string;
+  readonly?: boolean;
+  enabled_by?: string;
   description: string;
   values?: Array<{ name: string; description: string }>;
 }
@@ -73,6 +99,7 @@ export interface TextKeyerStatus {
   sent: number;
   total: number;
   progress: number;
+  wpm: number;
 }
 
 export interface MemorySlot {
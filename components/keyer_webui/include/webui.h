@@ -13,7 +13,8 @@ esp_err_t webui_start(void);
 esp_err_t webui_stop(void);
 
 void webui_timeline_push(const char *event_type, const char *json_data);
-void webui_decoder_push_char(char c, uint8_t wpm);
+void webui_decoder_push_text(const char *text, const char *sent, uint32_t seq,
+                             int64_t ts_ms, uint8_t wpm);
 void webui_decoder_push_word(void);
 void webui_decoder_push_pattern(const char *pattern);
 
@@ -23,6 +24,14 @@ void webui_decoder_push_pattern(const char *pattern);
  */
 int webui_get_ws_client_count(void);
 
+/**
+ * @brief Get the number of HTTP requests served so far (wraps)
+ *
+ * Counts every request, API and static files alike, so a page that only
+ * polls the REST API still shows up as activity.
+ */
+uint32_t webui_get_request_count(void);
+
 #ifdef __cplusplus
 }
 #endif
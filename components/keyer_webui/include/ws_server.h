@@ -47,11 +47,21 @@ esp_err_t ws_handler(httpd_req_t *req);
 void ws_broadcast(const char *message);
 
 /**
- * @brief Broadcast decoder character event
- * @param c Decoded character
+ * @brief Broadcast decoded text event
+ *
+ * Sends {"type":"decoded","text":...,"sent":...,"wpm":...,"ts":...,"seq":...}.
+ * seq is the decoder cursor of the first character: a client that sees
+ * a jump can fetch the missing text with GET /api/decoder/status?since=.
+ *
+ * @param text Decoded characters (spaces separate words)
+ * @param sent Same length as text: what the text keyer sent at each
+ *             position, ' ' where hand keyed
+ * @param seq Decoder sequence number of text[0]
+ * @param ts_ms Time the text was decoded (esp_timer, ms)
  * @param wpm Current WPM
  */
-void ws_broadcast_decoder_char(char c, uint8_t wpm);
+void ws_broadcast_decoder_text(const char *text, const char *sent, uint32_t seq,
+                               int64_t ts_ms, uint8_t wpm);
 
 /**
  * @brief Broadcast decoder word separator event
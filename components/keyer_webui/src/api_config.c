@@ -6,10 +6,47 @@
 #include "config_console.h"
 #include "config_nvs.h"
 #include "config_schema.h"
+#include "config_audit.h"
+#include "lwip/sockets.h"
 #include <string.h>
 
 static const char *TAG = "api_config";
 
+/* Recent parameter changes (defined in main.c) */
+extern config_audit_t g_config_audit;
+
+/* Peer address of a request ("" if unavailable) */
+static void client_addr(httpd_req_t *req, char *buf, size_t len) {
+    struct sockaddr_storage addr;
+    socklen_t addr_len = sizeof(addr);
+    buf[0] = '\0';
+    if (getpeername(httpd_req_to_sockfd(req), (struct sockaddr *)&addr, &addr_len) != 0) {
+        return;
+    }
+    if (addr.ss_family == AF_INET) {
+        inet_ntop(AF_INET, &((struct sockaddr_in *)&addr)->sin_addr, buf, len);
+    } else if (addr.ss_family == AF_INET6) {
+        /* IPv4 clients arrive as v4-mapped addresses on the dual-stack socket */
+        struct sockaddr_in6 *a6 = (struct sockaddr_in6 *)&addr;
+        inet_ntop(AF_INET, &a6->sin6_addr.un.u32_addr[3], buf, len);
+    }
+}
+
+/* Add a WebUI change to the history (secrets masked, no-ops skipped) */
+static void audit_change(httpd_req_t *req, const char *name, const char *old_value) {
+    const param_descriptor_t *p = config_find_param(name);
+    char new_value[72];
+    if (p == NULL || config_get_param_str(p->full_path, new_value, sizeof(new_value)) != 0 ||
+        strcmp(old_value, new_value) == 0) {
+        return;
+    }
+    char who[CONFIG_AUDIT_WHO_MAX];
+    client_addr(req, who, sizeof(who));
+    config_audit_record(&g_config_audit, CONFIG_AUDIT_WEB, who, p->full_path,
+                        p->sensitive ? "***" : old_value,
+                        p->sensitive ? "***" : new_value, esp_timer_get_time());
+}
+
 /* GET /api/config/schema */
 esp_err_t api_config_schema_handler(httpd_req_t *req) {
     httpd_resp_set_type(req, "application/json");
@@ -49,16 +86,21 @@ esp_err_t api_config_get_handler(httpd_req_t *req) {
             case PARAM_TYPE_U32:
                 cJSON_AddNumberToObject(family_obj, p->name, val.u32);
                 break;
+            case PARAM_TYPE_I8:
+                cJSON_AddNumberToObject(family_obj, p->name, val.i8);
+                break;
+            case PARAM_TYPE_I16:
+                cJSON_AddNumberToObject(family_obj, p->name, val.i16);
+                break;
             case PARAM_TYPE_ENUM:
                 cJSON_AddNumberToObject(family_obj, p->name, val.u8);
                 break;
-            case PARAM_TYPE_STRING:
-                if (val.str != NULL) {
-                    cJSON_AddStringToObject(family_obj, p->name, val.str);
-                } else {
-                    cJSON_AddStringToObject(family_obj, p->name, "");
-                }
+            case PARAM_TYPE_STRING: {
+                char str[CONFIG_STR_MAX_LEN + 1];
+                p->copy_fn(str, sizeof(str));
+                cJSON_AddStringToObject(family_obj, p->name, str);
                 break;
+            }
         }
     }
 
@@ -122,6 +164,8 @@ esp_err_t api_parameter_set_handler(httpd_req_t *req) {
 
     cJSON_Delete(json);
 
+    char old_value[72] = "";
+    config_get_param_str(param_name, old_value, sizeof(old_value));
     int result = config_set_param_str(param_name, value_str);
     if (result != 0) {
         ESP_LOGW(TAG, "Failed to set %s = %s (error=%d)", param_name, value_str, result);
@@ -130,6 +174,8 @@ esp_err_t api_parameter_set_handler(httpd_req_t *req) {
             case -1: snprintf(err_msg, sizeof(err_msg), "Parameter not found: %.60s", param_name); break;
             case -2: snprintf(err_msg, sizeof(err_msg), "Invalid value format"); break;
             case -4: snprintf(err_msg, sizeof(err_msg), "Value out of range"); break;
+            case -5: snprintf(err_msg, sizeof(err_msg), "%s", config_constraint_error()); break;
+            case -6: snprintf(err_msg, sizeof(err_msg), "Parameter is read-only: %.60s", param_name); break;
             default: snprintf(err_msg, sizeof(err_msg), "Unknown error: %d", result); break;
         }
         httpd_resp_send_err(req, HTTPD_400_BAD_REQUEST, err_msg);
@@ -137,6 +183,7 @@ esp_err_t api_parameter_set_handler(httpd_req_t *req) {
     }
 
     ESP_LOGI(TAG, "Set %s = %s", param_name, value_str);
+    audit_change(req, param_name, old_value);
 
     /* Return success response */
     httpd_resp_set_type(req, "application/json");
@@ -2,10 +2,39 @@
 #include "esp_log.h"
 #include "cJSON.h"
 #include "decoder.h"
+#include <stdlib.h>
 
 static const char *TAG = "api_decoder";
 
-/* GET /api/decoder/status */
+/** Most text returned per status request */
+#define STATUS_TEXT_MAX 128
+
+/**
+ * @brief Read the optional ?since=<seq> cursor
+ */
+static bool get_since(httpd_req_t *req, uint32_t *since) {
+    char query[32];
+    char value[12];
+    if (httpd_req_get_url_query_str(req, query, sizeof(query)) != ESP_OK ||
+        httpd_query_key_value(query, "since", value, sizeof(value)) != ESP_OK) {
+        return false;
+    }
+    char *end;
+    unsigned long v = strtoul(value, &end, 10);
+    if (*end != '\0') {
+        return false;
+    }
+    *since = (uint32_t)v;
+    return true;
+}
+
+/* GET /api/decoder/status[?since=<seq>]
+ *
+ * Without since: the last decoded text. With since: only text decoded
+ * after that cursor. "next" is the cursor to pass on the next poll, so
+ * each client follows the transcript on its own. "sent" runs parallel
+ * to "text": the character the text keyer sent at each position, or a
+ * space where the text was hand keyed. */
 esp_err_t api_decoder_status_handler(httpd_req_t *req) {
     cJSON *root = cJSON_CreateObject();
     if (root == NULL) {
@@ -20,9 +49,36 @@ esp_err_t api_decoder_status_handler(httpd_req_t *req) {
     decoder_get_current_pattern(pattern, sizeof(pattern));
     cJSON_AddStringToObject(root, "pattern", pattern);
 
-    char text[128];
-    decoder_get_text(text, sizeof(text));
+    char text[STATUS_TEXT_MAX + 1];
+    char sent[STATUS_TEXT_MAX + 1];
+    uint32_t since;
+    if (!get_since(req, &since)) {
+        since = decoder_text_seq() - STATUS_TEXT_MAX;  /* Clamped to the oldest held */
+    }
+
+    /* Small chunks: this runs on the httpd task stack */
+    decoded_char_t chars[16];
+    size_t len = 0;
+    while (len < STATUS_TEXT_MAX) {
+        size_t want = STATUS_TEXT_MAX - len;
+        if (want > sizeof(chars) / sizeof(chars[0])) {
+            want = sizeof(chars) / sizeof(chars[0]);
+        }
+        size_t n = decoder_read_since(&since, chars, want);
+        if (n == 0) {
+            break;
+        }
+        for (size_t i = 0; i < n; i++) {
+            text[len] = chars[i].character;
+            sent[len] = (chars[i].sent != '\0') ? chars[i].sent : ' ';
+            len++;
+        }
+    }
+    text[len] = '\0';
+    sent[len] = '\0';
     cJSON_AddStringToObject(root, "text", text);
+    cJSON_AddStringToObject(root, "sent", sent);
+    cJSON_AddNumberToObject(root, "next", (double)since);
 
     char *json_str = cJSON_PrintUnformatted(root);
     cJSON_Delete(root);
@@ -95,6 +95,7 @@ esp_err_t api_text_status_handler(httpd_req_t *req) {
     cJSON_AddNumberToObject(root, "sent", (int)sent);
     cJSON_AddNumberToObject(root, "total", (int)total);
     cJSON_AddNumberToObject(root, "progress", progress);
+    cJSON_AddNumberToObject(root, "wpm", (int)text_keyer_get_wpm());
 
     char *json_str = cJSON_PrintUnformatted(root);
     cJSON_Delete(root);
@@ -134,6 +135,40 @@ esp_err_t api_text_resume_handler(httpd_req_t *req) {
     return httpd_resp_send(req, "{\"success\":true}", HTTPD_RESP_USE_STRLEN);
 }
 
+/* POST /api/text/speed - Nudge playback speed: {"delta": +/-n} */
+esp_err_t api_text_speed_handler(httpd_req_t *req) {
+    char buf[64];
+    if (read_post_body(req, buf, sizeof(buf)) < 0) {
+        httpd_resp_send_err(req, HTTPD_400_BAD_REQUEST, "Invalid body");
+        return ESP_FAIL;
+    }
+
+    cJSON *json = cJSON_Parse(buf);
+    if (json == NULL) {
+        httpd_resp_send_err(req, HTTPD_400_BAD_REQUEST, "Invalid JSON");
+        return ESP_FAIL;
+    }
+
+    cJSON *delta_obj = cJSON_GetObjectItem(json, "delta");
+    if (!cJSON_IsNumber(delta_obj) || delta_obj->valueint == 0 ||
+        delta_obj->valueint < -20 || delta_obj->valueint > 20) {
+        cJSON_Delete(json);
+        httpd_resp_send_err(req, HTTPD_400_BAD_REQUEST, "'delta' must be -20..20, not 0");
+        return ESP_FAIL;
+    }
+    int delta = delta_obj->valueint;
+    cJSON_Delete(json);
+
+    if (text_keyer_nudge_speed(delta) != 0) {
+        httpd_resp_send_err(req, HTTPD_400_BAD_REQUEST, "Not sending");
+        return ESP_FAIL;
+    }
+
+    ESP_LOGI(TAG, "Text speed nudged %+d WPM", delta);
+    httpd_resp_set_type(req, "application/json");
+    return httpd_resp_send(req, "{\"success\":true}", HTTPD_RESP_USE_STRLEN);
+}
+
 /* GET /api/text/memory - List all memory slots */
 esp_err_t api_text_memory_list_handler(httpd_req_t *req) {
     cJSON *root = cJSON_CreateObject();
@@ -235,7 +270,7 @@ esp_err_t api_text_memory_set_handler(httpd_req_t *req) {
     return httpd_resp_send(req, "{\"success\":true}", HTTPD_RESP_USE_STRLEN);
 }
 
-/* POST /api/text/play - Play memory slot */
+/* POST /api/text/play - Play memory slot: {"slot": n, "repeat": bool} */
 esp_err_t api_text_play_handler(httpd_req_t *req) {
     char buf[64];
     if (read_post_body(req, buf, sizeof(buf)) < 0) {
@@ -257,6 +292,7 @@ esp_err_t api_text_play_handler(httpd_req_t *req) {
     }
 
     int slot = slot_obj->valueint;
+    bool repeat = cJSON_IsTrue(cJSON_GetObjectItem(json, "repeat"));
     cJSON_Delete(json);
 
     if (slot < 0 || slot >= TEXT_MEMORY_SLOTS) {
@@ -275,8 +311,11 @@ esp_err_t api_text_play_handler(httpd_req_t *req) {
         httpd_resp_send_err(req, HTTPD_400_BAD_REQUEST, "Keyer busy");
         return ESP_FAIL;
     }
+    if (repeat) {
+        text_keyer_repeat();
+    }
 
-    ESP_LOGI(TAG, "Playing memory slot %d", slot);
+    ESP_LOGI(TAG, "Playing memory slot %d%s", slot, repeat ? " (repeat)" : "");
     httpd_resp_set_type(req, "application/json");
     return httpd_resp_send(req, "{\"success\":true}", HTTPD_RESP_USE_STRLEN);
 }
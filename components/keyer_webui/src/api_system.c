@@ -8,6 +8,12 @@
 #include "cJSON.h"
 #include "wifi.h"
 #include "cwnet_socket.h"
+#include "fault_indicator.h"
+#include "cpu_load.h"
+
+extern fault_state_t g_fault_state;
+extern fault_indicator_t g_fault_indicator;
+extern cpu_load_t g_cpu_load;
 
 static const char *TAG = "api_system";
 
@@ -48,6 +54,30 @@ esp_err_t api_status_handler(httpd_req_t *req) {
     cJSON_AddNumberToObject(cwnet, "latency_ms", cwnet_socket_get_latency_ms());
     cJSON_AddItemToObject(root, "cwnet", cwnet);
 
+    /* Fault status: the one shown on the keyer, then every raised code */
+    cJSON *fault = cJSON_CreateObject();
+    const char *banner = fault_indicator_banner(&g_fault_indicator);
+    if (banner != NULL) {
+        cJSON_AddStringToObject(fault, "shown", banner);
+    } else {
+        cJSON_AddNullToObject(fault, "shown");
+    }
+    cJSON_AddBoolToObject(fault, "tx_stopped", fault_is_active(&g_fault_state));
+    cJSON_AddNumberToObject(fault, "count", fault_get_count(&g_fault_state));
+    cJSON *raised = cJSON_CreateArray();
+    uint32_t mask = fault_get_raised(&g_fault_state);
+    for (int code = 1; code < FAULT_CODE_COUNT; code++) {
+        if (mask & FAULT_BIT(code)) {
+            cJSON *item = cJSON_CreateObject();
+            cJSON_AddStringToObject(item, "code", fault_code_str((fault_code_t)code));
+            cJSON_AddStringToObject(item, "severity",
+                fault_severity_str(fault_code_severity((fault_code_t)code)));
+            cJSON_AddItemToArray(raised, item);
+        }
+    }
+    cJSON_AddItemToObject(fault, "raised", raised);
+    cJSON_AddItemToObject(root, "fault", fault);
+
     char *json_str = cJSON_PrintUnformatted(root);
     cJSON_Delete(root);
 
@@ -91,6 +121,25 @@ esp_err_t api_system_stats_handler(httpd_req_t *req) {
     cJSON_AddNumberToObject(heap, "largest_free_block", (int)heap_info.largest_free_block);
     cJSON_AddItemToObject(root, "heap", heap);
 
+    /* CPU load per core (percent; null until the first sample) */
+    cJSON *cpu = cJSON_CreateArray();
+    for (uint32_t core = 0; core < CPU_LOAD_CORES; core++) {
+        cJSON *item = cJSON_CreateObject();
+        cJSON_AddNumberToObject(item, "core", (int)core);
+        if (cpu_load_valid(&g_cpu_load)) {
+            cJSON_AddNumberToObject(item, "load", (int)cpu_load_now(&g_cpu_load, core));
+            cJSON_AddNumberToObject(item, "avg", (int)cpu_load_avg(&g_cpu_load, core));
+            cJSON_AddNumberToObject(item, "peak", (int)cpu_load_peak(&g_cpu_load, core));
+        } else {
+            cJSON_AddNullToObject(item, "load");
+            cJSON_AddNullToObject(item, "avg");
+            cJSON_AddNullToObject(item, "peak");
+        }
+        cJSON_AddItemToArray(cpu, item);
+    }
+    cJSON_AddItemToObject(root, "cpu", cpu);
+    cJSON_AddNumberToObject(root, "cpu_window_s", CPU_LOAD_WINDOW);
+
     /* Tasks */
     cJSON *tasks = cJSON_CreateArray();
     UBaseType_t task_count = uxTaskGetNumberOfTasks();
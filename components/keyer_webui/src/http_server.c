@@ -3,10 +3,12 @@
 #include "ws_server.h"
 #include "esp_http_server.h"
 #include "esp_log.h"
+#include <stdatomic.h>
 #include <string.h>
 
 static const char *TAG = "webui";
 static httpd_handle_t s_server = NULL;
+static atomic_uint s_request_count = ATOMIC_VAR_INIT(0);
 
 /* API handlers (implemented in api_*.c) */
 extern esp_err_t api_config_schema_handler(httpd_req_t *req);
@@ -24,6 +26,7 @@ extern esp_err_t api_text_status_handler(httpd_req_t *req);
 extern esp_err_t api_text_abort_handler(httpd_req_t *req);
 extern esp_err_t api_text_pause_handler(httpd_req_t *req);
 extern esp_err_t api_text_resume_handler(httpd_req_t *req);
+extern esp_err_t api_text_speed_handler(httpd_req_t *req);
 extern esp_err_t api_text_memory_list_handler(httpd_req_t *req);
 extern esp_err_t api_text_memory_set_handler(httpd_req_t *req);
 extern esp_err_t api_text_play_handler(httpd_req_t *req);
@@ -41,6 +44,17 @@ static const char *SPA_ROUTES[] = {
     NULL
 };
 
+/* Every request passes the URI matcher: count it there (possibly more than
+ * once, it is tried per handler; only changes of the count matter) */
+static bool match_and_count(const char *template, const char *uri, size_t len) {
+    atomic_fetch_add_explicit(&s_request_count, 1, memory_order_relaxed);
+    return httpd_uri_match_wildcard(template, uri, len);
+}
+
+uint32_t webui_get_request_count(void) {
+    return atomic_load_explicit(&s_request_count, memory_order_relaxed);
+}
+
 static bool is_spa_route(const char *uri) {
     for (int i = 0; SPA_ROUTES[i] != NULL; i++) {
         if (strcmp(uri, SPA_ROUTES[i]) == 0) {
@@ -249,6 +263,14 @@ static void register_api_routes(httpd_handle_t server) {
     };
     httpd_register_uri_handler(server, &text_resume);
 
+    httpd_uri_t text_speed = {
+        .uri = "/api/text/speed",
+        .method = HTTP_POST,
+        .handler = api_text_speed_handler,
+        .user_ctx = NULL,
+    };
+    httpd_register_uri_handler(server, &text_speed);
+
     httpd_uri_t text_memory_list = {
         .uri = "/api/text/memory",
         .method = HTTP_GET,
@@ -288,7 +310,7 @@ esp_err_t webui_start(void) {
     httpd_config_t config = HTTPD_DEFAULT_CONFIG();
     config.max_uri_handlers = 32;
     config.stack_size = 8192;
-    config.uri_match_fn = httpd_uri_match_wildcard;
+    config.uri_match_fn = match_and_count;
 
     esp_err_t ret = httpd_start(&s_server, &config);
     if (ret != ESP_OK) {
@@ -322,9 +344,11 @@ void webui_timeline_push(const char *event_type, const char *json_data) {
     ws_broadcast_timeline(event_type, json_data);
 }
 
-void webui_decoder_push_char(char c, uint8_t wpm) {
-    ESP_LOGI(TAG, "Push char '%c' wpm=%u clients=%d", c, wpm, ws_get_client_count());
-    ws_broadcast_decoder_char(c, wpm);
+void webui_decoder_push_text(const char *text, const char *sent, uint32_t seq,
+                             int64_t ts_ms, uint8_t wpm) {
+    ESP_LOGD(TAG, "Push text '%s' seq=%lu clients=%d", text, (unsigned long)seq,
+             ws_get_client_count());
+    ws_broadcast_decoder_text(text, sent, seq, ts_ms, wpm);
 }
 
 void webui_decoder_push_word(void) {
@@ -199,18 +199,31 @@ void ws_broadcast(const char *message) {
     }
 }
 
-void ws_broadcast_decoder_char(char c, uint8_t wpm) {
-    char json[64];
-
-    /* Handle special characters that need JSON escaping */
-    if (c == '"') {
-        snprintf(json, sizeof(json), "{\"type\":\"decoded\",\"char\":\"\\\"\",\"wpm\":%u}", wpm);
-    } else if (c == '\\') {
-        snprintf(json, sizeof(json), "{\"type\":\"decoded\",\"char\":\"\\\\\",\"wpm\":%u}", wpm);
-    } else {
-        snprintf(json, sizeof(json), "{\"type\":\"decoded\",\"char\":\"%c\",\"wpm\":%u}", c, wpm);
+/**
+ * @brief Escape for JSON: the morse table has '"' but no control characters
+ */
+static void json_escape(const char *in, char *out, size_t out_size) {
+    size_t len = 0;
+    for (const char *p = in; *p != '\0' && len + 2 < out_size; p++) {
+        if (*p == '"' || *p == '\\') {
+            out[len++] = '\\';
+        }
+        out[len++] = *p;
     }
+    out[len] = '\0';
+}
 
+void ws_broadcast_decoder_text(const char *text, const char *sent, uint32_t seq,
+                               int64_t ts_ms, uint8_t wpm) {
+    char escaped[64];
+    char escaped_sent[64];
+    json_escape(text, escaped, sizeof(escaped));
+    json_escape(sent, escaped_sent, sizeof(escaped_sent));
+
+    char json[224];
+    snprintf(json, sizeof(json),
+             "{\"type\":\"decoded\",\"text\":\"%s\",\"sent\":\"%s\",\"wpm\":%u,\"ts\":%lld,\"seq\":%lu}",
+             escaped, escaped_sent, wpm, (long long)ts_ms, (unsigned long)seq);
     ws_broadcast(json);
 }
 
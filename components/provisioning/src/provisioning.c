@@ -3,7 +3,9 @@
  * @brief Main provisioning logic and NVS operations
  *
  * Provides check functions and main entry point for provisioning mode.
- * Uses hardcoded NVS keys for isolation from generated config code.
+ * Uses hardcoded NVS keys for isolation from generated config code; the
+ * only call into keyer_config is config_nvs_reseal() after a direct write,
+ * which keeps the namespace CRC valid for the next normal boot.
  */
 
 #include "provisioning.h"
@@ -17,6 +19,7 @@
 #include "nvs.h"
 #include "driver/gpio.h"
 #include "led.h"
+#include "config_nvs.h"
 
 /* Internal declarations */
 extern void prov_wifi_start_ap(void);
@@ -128,6 +131,9 @@ bool provisioning_check_factory_reset(gpio_num_t dit_gpio, gpio_num_t dah_gpio,
         nvs_erase_key(handle, PROV_NVS_WIFI_PASS);
         nvs_commit(handle);
         nvs_close(handle);
+        if (config_nvs_reseal() != ESP_OK) {
+            ESP_LOGW(TAG, "Failed to reseal config namespace");
+        }
         ESP_LOGI(TAG, "WiFi credentials cleared from NVS");
     } else {
         ESP_LOGE(TAG, "Failed to open NVS for reset: %s", esp_err_to_name(err));
@@ -195,6 +201,13 @@ esp_err_t prov_save_config(const char *ssid, const char *password,
         return err;
     }
 
+    /* The normal boot checks the whole namespace against its CRC */
+    err = config_nvs_reseal();
+    if (err != ESP_OK) {
+        ESP_LOGE(TAG, "Config reseal failed: %s", esp_err_to_name(err));
+        return err;
+    }
+
     ESP_LOGI(TAG, "Configuration saved: SSID=%s, callsign=%s, theme=%d",
              ssid, callsign, theme);
     return ESP_OK;
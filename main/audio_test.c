@@ -10,6 +10,7 @@
 #include "freertos/task.h"
 #include "esp_log.h"
 #include "hal_audio.h"
+#include "tasks.h"
 #include <math.h>
 
 static const char *TAG = "audio_test";
@@ -55,13 +56,5 @@ void audio_test_task(void *arg) {
 }
 
 void start_audio_test(void) {
-    xTaskCreatePinnedToCore(
-        audio_test_task,
-        "audio_test",
-        4096,
-        NULL,
-        5,  /* Medium priority */
-        NULL,
-        1   /* Core 1 (not RT core) */
-    );
+    tasks_spawn_bg(audio_test_task, "audio_test", 4096, 5);  /* Medium priority */
 }
\ No newline at end of file
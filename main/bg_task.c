@@ -4,39 +4,70 @@
  *
  * Best-effort processing:
  * - LED status feedback
+ * - Memory buttons (GPIO and I2C expander)
  * - WiFi connectivity
  * - Remote CW forwarder
  * - Morse decoder
- * - Diagnostics
+ * - Copy practice sessions (trainer + decoder)
+ * - Diagnostics (diag.* read-only parameters)
+ * - Supply voltage monitor (low-voltage fault)
+ * - CWNet link monitor (link-loss warning)
+ * - Fault indication (LED blink code, buzzer chirp, OLED banner)
+ * - Thermal guard (no tune or repeats when hot)
+ * - Codec input selection and gain
  *
  * Runs on Core 1 with normal priority.
  */
 
+#define RT_LOG_TAG LOG_TAG_BG
+
 #include "freertos/FreeRTOS.h"
 #include "freertos/task.h"
 #include "esp_timer.h"
+#include "esp_system.h"
 #include <inttypes.h>
+#include <stdatomic.h>
 #include <string.h>
+#include <sys/time.h>
 
 #include "keyer_core.h"
 #include "consumer.h"
 #include "rt_log.h"
+#include "crash_log.h"
 #include "decoder.h"
+#include "console.h"
 #include "text_keyer.h"
+#include "copy_practice.h"
 #include "text_memory.h"
+#include "memory_buttons.h"
+#include "speed_encoder.h"
+#include "speed_pot.h"
+#include "supply_monitor.h"
+#include "straight_key.h"
 #include "led.h"
 #include "wifi.h"
 #include "vpn.h"
 #include "hal_gpio.h"
+#include "hal_audio.h"
+#include "hal_buzzer.h"
+#include "hal_encoder.h"
+#include "hal_expander.h"
+#include "hal_pot.h"
+#include "hal_supply.h"
+#include "hal_temp.h"
 #include "config.h"
 #include "webui.h"
 #include "cwnet_socket.h"
+#include "power.h"
 
 #include <stdio.h>
 
 /* External globals */
 extern keying_stream_t g_keying_stream;
 extern fault_state_t g_fault_state;
+extern fault_indicator_t g_fault_indicator;
+extern cpu_load_t g_cpu_load;
+extern atomic_bool g_ptt_in_held;
 
 /* ============================================================================
  * Timeline Consumer (best-effort, for WebUI visualization)
@@ -49,6 +80,390 @@ static bool s_timeline_initialized = false;
 static gpio_state_t s_tl_prev_gpio = {0};
 static uint8_t s_tl_prev_local_key = 0;
 
+/* ============================================================================
+ * Memory Buttons
+ * ============================================================================ */
+
+static memory_buttons_t s_mem_buttons;
+
+/**
+ * @brief hardware.btnN_action of a button
+ */
+static memory_button_action_t button_action(int button) {
+    switch (button) {
+        case 0: return (memory_button_action_t)CONFIG_GET_BTN1_ACTION();
+        case 1: return (memory_button_action_t)CONFIG_GET_BTN2_ACTION();
+        case 2: return (memory_button_action_t)CONFIG_GET_BTN3_ACTION();
+        default: return (memory_button_action_t)CONFIG_GET_BTN4_ACTION();
+    }
+}
+
+/**
+ * @brief Speed button or encoder: nudge the message playing, else keyer.wpm
+ */
+static void button_speed(int steps) {
+    if (text_keyer_nudge_speed(steps) == 0) {
+        return;
+    }
+    int wpm = (int)CONFIG_GET_WPM() + steps;
+    if (wpm < 5) wpm = 5;
+    if (wpm > 100) wpm = 100;
+    if (wpm != (int)CONFIG_GET_WPM()) {
+        CONFIG_SET_WPM((uint16_t)wpm);  /* Paddles pick it up when idle */
+    }
+}
+
+/**
+ * @brief Run the action of a pressed button
+ *
+ * Any press while a message plays stops it; a speed button changes
+ * its speed and a pause button pauses or resumes it instead.
+ */
+static void button_pressed(memory_button_action_t action) {
+    if (action == MEMORY_BUTTON_FASTER || action == MEMORY_BUTTON_SLOWER) {
+        button_speed((action == MEMORY_BUTTON_FASTER) ? 1 : -1);
+        return;
+    }
+    if (action == MEMORY_BUTTON_PAUSE) {
+        if (text_keyer_get_state() == TEXT_KEYER_PAUSED) {
+            text_keyer_resume();
+        } else {
+            text_keyer_pause();
+        }
+        return;
+    }
+    if (text_keyer_get_state() != TEXT_KEYER_IDLE) {
+        text_keyer_abort();
+        return;
+    }
+    if (action == MEMORY_BUTTON_TUNE) {
+        text_keyer_tune();
+        return;
+    }
+    text_memory_slot_t mem;
+    if (action <= MEMORY_BUTTON_MEM8 && text_memory_get((uint8_t)action, &mem) == 0) {
+        text_keyer_send(mem.text);
+    }
+}
+
+/**
+ * @brief Poll one set of buttons: press, long press (repeat), tune release
+ */
+static void buttons_tick(memory_buttons_t *buttons, uint16_t raw,
+                         memory_button_action_t (*action)(int), int64_t now_us) {
+    int button = memory_buttons_update(buttons, raw, now_us);
+    if (button >= 0) {
+        button_pressed(action(button));
+        return;
+    }
+
+    button = memory_buttons_long_press(buttons, now_us);
+    if (button >= 0 && action(button) <= MEMORY_BUTTON_MEM8) {
+        text_keyer_repeat();
+    }
+
+    /* Tune lasts while its button is held */
+    if (text_keyer_is_tuning() && buttons->last >= 0 &&
+        action(buttons->last) == MEMORY_BUTTON_TUNE &&
+        !memory_buttons_is_held(buttons, buttons->last)) {
+        text_keyer_abort();
+    }
+}
+
+static void memory_buttons_tick(int64_t now_us) {
+    buttons_tick(&s_mem_buttons, hal_gpio_read_buttons(), button_action, now_us);
+}
+
+/* ============================================================================
+ * I2C Expander Inputs
+ * ============================================================================ */
+
+static memory_buttons_t s_exp_buttons;
+static uint16_t s_exp_inputs = 0;
+
+/**
+ * @brief Expander input n: button n runs action n (MEM1..MEM8, TUNE, ...)
+ */
+static memory_button_action_t expander_action(int button) {
+    return (memory_button_action_t)button;
+}
+
+/**
+ * @brief Poll the expander: the first hardware.exp_buttons inputs are
+ * buttons, the rest sense levels for diag.exp_inputs
+ */
+static void expander_tick(int64_t now_us) {
+    if (hal_expander_input_count() == 0) {
+        return;
+    }
+
+    uint16_t closed;
+    hal_expander_read(&closed);  /* Zero on a bus error: nothing held */
+    s_exp_inputs = closed;
+
+    uint16_t button_mask = (uint16_t)((1U << CONFIG_GET_EXP_BUTTONS()) - 1U);
+    buttons_tick(&s_exp_buttons, closed & button_mask, expander_action, now_us);
+}
+
+/* ============================================================================
+ * Speed Encoder
+ * ============================================================================ */
+
+static speed_encoder_t s_speed_encoder;
+
+static void speed_encoder_tick(int64_t now_us) {
+    int steps = speed_encoder_steps(&s_speed_encoder, hal_encoder_take_detents(), now_us);
+    if (steps != 0) {
+        button_speed(steps);
+    }
+}
+
+/* ============================================================================
+ * Speed Pot
+ * ============================================================================ */
+
+static speed_pot_t s_speed_pot;
+
+/**
+ * @brief Sample the pot; a turned knob sets keyer.wpm (last writer wins)
+ */
+static void speed_pot_tick(void) {
+    int raw = hal_pot_read();
+    if (raw < 0) {
+        return;  /* Not fitted */
+    }
+    uint16_t wpm = speed_pot_update(&s_speed_pot, (uint16_t)raw,
+                                    CONFIG_GET_POT_WPM_MIN(), CONFIG_GET_POT_WPM_MAX());
+    if (wpm != 0 && wpm != CONFIG_GET_WPM()) {
+        CONFIG_SET_WPM(wpm);  /* Paddles pick it up when idle */
+    }
+}
+
+/* ============================================================================
+ * Supply Voltage
+ * ============================================================================ */
+
+static supply_monitor_t s_supply;
+
+/**
+ * @brief Measure the supply (once a second); critical raises a fault
+ *
+ * The fault latches like the RT faults: TX stays off until reboot.
+ */
+static void supply_tick(int64_t now_us) {
+    int pin_mv = hal_supply_read_mv();
+    if (pin_mv < 0) {
+        return;  /* Not fitted */
+    }
+
+    supply_level_t prev = s_supply.level;
+    uint32_t mv = supply_monitor_scale((uint32_t)pin_mv, CONFIG_GET_SUPPLY_DIVIDER_X100());
+    supply_level_t level = supply_monitor_update(&s_supply, mv, CONFIG_GET_SUPPLY_LOW_MV(),
+                                                 CONFIG_GET_SUPPLY_CRITICAL_MV());
+    CONFIG_UPDATE_SUPPLY_MV((uint16_t)((s_supply.filtered_mv > UINT16_MAX) ?
+                                       UINT16_MAX : s_supply.filtered_mv));
+
+    if (level == prev) {
+        return;
+    }
+    switch (level) {
+        case SUPPLY_CRITICAL:
+            RT_ERROR(&g_bg_log_stream, now_us, "Supply critical: %" PRIu32 " mV, TX off",
+                     s_supply.filtered_mv);
+            if (!fault_is_active(&g_fault_state)) {
+                fault_set(&g_fault_state, FAULT_LOW_VOLTAGE, s_supply.filtered_mv);
+            }
+            break;
+        case SUPPLY_LOW:
+            RT_WARN(&g_bg_log_stream, now_us, "Supply low: %" PRIu32 " mV",
+                    s_supply.filtered_mv);
+            break;
+        default:
+            RT_INFO(&g_bg_log_stream, now_us, "Supply ok: %" PRIu32 " mV",
+                    s_supply.filtered_mv);
+            break;
+    }
+}
+
+/* ============================================================================
+ * Link Monitor
+ * ============================================================================ */
+
+static bool s_link_up = false;
+
+/**
+ * @brief Raise a link-loss warning when CWNet drops, resolve it on reconnect
+ */
+static void link_tick(int64_t now_us) {
+    cwnet_socket_state_t state = cwnet_socket_get_state();
+    bool up = (state == CWNET_SOCK_READY);
+    if (up == s_link_up) {
+        return;
+    }
+    s_link_up = up;
+    if (up || state == CWNET_SOCK_DISABLED) {
+        fault_resolve(&g_fault_state, FAULT_LINK_LOSS);
+    } else {
+        RT_LOG_T(&g_bg_log_stream, LOG_TAG_CWNET, LOG_LEVEL_WARN, now_us, "CWNet link lost");
+        fault_set(&g_fault_state, FAULT_LINK_LOSS, 0);
+    }
+}
+
+/* ============================================================================
+ * Fault Indication
+ * ============================================================================ */
+
+/** Post-mortem of the active critical fault already saved */
+static bool s_crash_saved = false;
+
+/**
+ * @brief Follow the fault state: LED blink code, buzzer chirp (RT), banner
+ *
+ * The OLED and /api/status read the banner from g_fault_indicator. A
+ * critical fault is also saved to the crash log once (TX is stopped, so
+ * the flash write stalling Core 0 does not matter).
+ */
+static void fault_indication_tick(int64_t now_us) {
+    bool active = fault_is_active(&g_fault_state);
+    if (active && !s_crash_saved) {
+        if (!crash_log_save(CRASH_CAUSE_FAULT, &g_fault_state, now_us)) {
+            RT_WARN(&g_bg_log_stream, now_us, "Crash log not saved");
+        }
+    }
+    s_crash_saved = active;
+
+    if (fault_indicator_update(&g_fault_indicator, &g_fault_state, now_us,
+                               CONFIG_GET_FAULT_CHIRP())) {
+        const char *banner = fault_indicator_banner(&g_fault_indicator);
+        RT_INFO(&g_bg_log_stream, now_us, "Fault shown: %s", banner != NULL ? banner : "none");
+    }
+
+    led_fault_t level = LED_FAULT_NONE;
+    switch (g_fault_indicator.severity) {
+        case FAULT_SEVERITY_CRITICAL: level = LED_FAULT_CRITICAL; break;
+        case FAULT_SEVERITY_WARNING:  level = LED_FAULT_WARNING; break;
+        default: break;
+    }
+    led_set_fault(level, fault_indicator_blink(&g_fault_indicator, now_us));
+}
+
+/* ============================================================================
+ * Wall Clock
+ * ============================================================================ */
+
+/**
+ * @brief Anchor the log timestamps once the system time is set (once a second)
+ *
+ * Follows any later change of the system time (SNTP, manual set).
+ */
+static void wall_clock_tick(int64_t now_us) {
+    struct timeval tv;
+    if (gettimeofday(&tv, NULL) != 0 || tv.tv_sec < LOG_CLOCK_VALID_EPOCH) {
+        return;
+    }
+    int64_t boot_us = (int64_t)tv.tv_sec * 1000000 + tv.tv_usec - esp_timer_get_time();
+    bool first = (log_clock_boot_epoch() == 0);
+    log_clock_set_boot_epoch(boot_us);
+    if (first) {
+        RT_INFO(&g_bg_log_stream, now_us, "Wall clock set: log times in local time from now");
+    }
+}
+
+/* ============================================================================
+ * CPU Load
+ * ============================================================================ */
+
+/**
+ * @brief Sample the idle tasks' run time (once a second)
+ *
+ * Run-time stats count in esp_timer microseconds, the same clock as now_us.
+ */
+static void cpu_load_tick(int64_t now_us) {
+    uint32_t idle_us[CPU_LOAD_CORES];
+    for (BaseType_t core = 0; core < CPU_LOAD_CORES; core++) {
+        idle_us[core] = (uint32_t)ulTaskGetRunTimeCounter(xTaskGetIdleTaskHandleForCore(core));
+    }
+    cpu_load_update(&g_cpu_load, now_us, idle_us);
+}
+
+/* ============================================================================
+ * Thermal Guard
+ * ============================================================================ */
+
+/** Cooling needed below hardware.thermal_limit_c to lift the guard */
+#define THERMAL_HYSTERESIS_C 5
+
+/**
+ * @brief Read the chip temperature (once a second); hot blocks long TX
+ */
+static void thermal_tick(int64_t now_us) {
+    float celsius;
+    if (!hal_temp_read(&celsius)) {
+        return;
+    }
+    int temp_c = (int)(celsius + ((celsius < 0.0f) ? -0.5f : 0.5f));
+    CONFIG_UPDATE_CHIP_TEMP_C((int16_t)temp_c);
+
+    int limit_c = CONFIG_GET_THERMAL_LIMIT_C();
+    bool blocked = text_keyer_long_tx_blocked();
+    bool hot = limit_c != 0 &&
+               temp_c >= (blocked ? limit_c - THERMAL_HYSTERESIS_C : limit_c);
+    if (hot == blocked) {
+        return;
+    }
+    text_keyer_block_long_tx(hot);
+    if (hot) {
+        RT_WARN(&g_bg_log_stream, now_us, "Chip %d C: tune and repeats blocked", temp_c);
+    } else {
+        RT_INFO(&g_bg_log_stream, now_us, "Chip %d C: thermal guard lifted", temp_c);
+    }
+}
+
+/* ============================================================================
+ * Codec Input
+ * ============================================================================ */
+
+/**
+ * @brief Apply audio.input_* and mic_bias (every tick, I2C only on change)
+ *
+ * With audio.ptt_mic_route, the analog mic input is selected while the
+ * PTT input is held.
+ */
+static void audio_input_tick(void) {
+    if (!hal_audio_is_available()) {
+        return;
+    }
+    hal_audio_input_t source = (hal_audio_input_t)CONFIG_GET_INPUT_SOURCE();
+    if (CONFIG_GET_PTT_MIC_ROUTE() &&
+        atomic_load_explicit(&g_ptt_in_held, memory_order_relaxed)) {
+        source = HAL_AUDIO_INPUT_ANALOG;
+    }
+    hal_audio_set_input(source);
+    hal_audio_set_input_gain(CONFIG_GET_INPUT_GAIN_DB());
+    hal_audio_set_mic_bias(CONFIG_GET_MIC_BIAS());
+}
+
+/* ============================================================================
+ * Headphone Detect
+ * ============================================================================ */
+
+/** Jack switch bounce on insertion and removal */
+#define HP_DETECT_DEBOUNCE_US 50000
+
+static straight_key_t s_hp_detect;
+
+/**
+ * @brief Route the codec output from the jack switch (every tick)
+ */
+static void audio_output_tick(int64_t now_us) {
+    if (!hal_audio_is_available() || !hal_audio_hp_detect_fitted()) {
+        return;
+    }
+    bool plugged = straight_key_update(&s_hp_detect, hal_audio_read_hp_detect(), now_us);
+    hal_audio_set_output(plugged ? HAL_AUDIO_OUTPUT_HEADPHONE : HAL_AUDIO_OUTPUT_SPEAKER,
+                         CONFIG_GET_HP_MUTE_SPEAKER());
+}
+
 /**
  * @brief Map WiFi state to LED state
  */
@@ -69,6 +484,22 @@ static led_state_t wifi_to_led_state(wifi_state_t ws) {
     }
 }
 
+/**
+ * @brief Publish live values to the read-only diag.* parameters
+ */
+static void update_diag_params(int64_t now_us) {
+    CONFIG_UPDATE_UPTIME_S((uint32_t)(now_us / 1000000));
+    CONFIG_UPDATE_FAULT_COUNT(fault_get_count(&g_fault_state));
+    CONFIG_UPDATE_HEAP_FREE(esp_get_free_heap_size());
+    CONFIG_UPDATE_EXP_INPUTS(s_exp_inputs);
+
+    int32_t rtt = cwnet_socket_get_latency_ms();
+    if (rtt > INT16_MAX) {
+        rtt = INT16_MAX;
+    }
+    CONFIG_UPDATE_LINK_RTT_MS((int16_t)((rtt < 0) ? -1 : rtt));
+}
+
 void bg_task(void *arg) {
     (void)arg;
 
@@ -76,6 +507,7 @@ void bg_task(void *arg) {
 
     /* Initialize timeline consumer (skip_threshold=0: never auto-skip) */
     best_effort_consumer_init(&s_timeline_consumer, &g_keying_stream, 0);
+    best_effort_consumer_track(&s_timeline_consumer, consumer_lag_register("remote"));
     s_timeline_initialized = true;
 
     /* Initialize CWNet client (reads config, connects if enabled) */
@@ -84,6 +516,12 @@ void bg_task(void *arg) {
     /* Log startup */
     int64_t now_us = esp_timer_get_time();
     RT_INFO(&g_bg_log_stream, now_us, "BG task started (text keyer ready)");
+    memory_buttons_init(&s_mem_buttons);
+    memory_buttons_init(&s_exp_buttons);
+    speed_encoder_init(&s_speed_encoder);
+    speed_pot_init(&s_speed_pot);
+    supply_monitor_init(&s_supply);
+    straight_key_init(&s_hp_detect, HP_DETECT_DEBOUNCE_US);
 
     uint32_t stats_counter = 0;
     wifi_state_t prev_wifi_state = WIFI_STATE_DISABLED;
@@ -93,6 +531,23 @@ void bg_task(void *arg) {
     for (;;) {
         now_us = esp_timer_get_time();
 
+        /* Fault blink code, chirp and banner (before the LED tick) */
+        fault_indication_tick(now_us);
+
+        /* UART and USB log colors follow system.log_color */
+        log_set_color(CONFIG_GET_LOG_COLOR());
+
+        /* Buzzer tone follows audio.sidetone_freq_hz (LEDC timer
+         * reprogramming stays out of the RT task) */
+        {
+            static uint32_t buzzer_freq = 0;
+            uint32_t freq = CONFIG_GET_SIDETONE_FREQ_HZ();
+            if (freq != buzzer_freq) {
+                hal_buzzer_set_frequency(freq);
+                buzzer_freq = freq;
+            }
+        }
+
         /* Update LED state from WiFi */
         if (led_is_initialized()) {
             wifi_state_t ws = wifi_get_state();
@@ -106,13 +561,16 @@ void bg_task(void *arg) {
                 if (ws == WIFI_STATE_CONNECTED) {
                     char ip_buf[16];
                     if (wifi_get_ip(ip_buf, sizeof(ip_buf))) {
-                        RT_INFO(&g_bg_log_stream, now_us, "WiFi connected: %s", ip_buf);
+                        RT_LOG_T(&g_bg_log_stream, LOG_TAG_WIFI, LOG_LEVEL_INFO, now_us,
+                                 "WiFi connected: %s", ip_buf);
                     }
                     wifi_connected_flash_done = false;
                 } else if (ws == WIFI_STATE_AP_MODE) {
-                    RT_INFO(&g_bg_log_stream, now_us, "WiFi AP mode active");
+                    RT_LOG_T(&g_bg_log_stream, LOG_TAG_WIFI, LOG_LEVEL_INFO, now_us,
+                             "WiFi AP mode active");
                 } else if (ws == WIFI_STATE_FAILED) {
-                    RT_WARN(&g_bg_log_stream, now_us, "WiFi connection failed");
+                    RT_LOG_T(&g_bg_log_stream, LOG_TAG_WIFI, LOG_LEVEL_WARN, now_us,
+                             "WiFi connection failed");
                 }
 
                 prev_wifi_state = ws;
@@ -123,19 +581,24 @@ void bg_task(void *arg) {
             if (vs != prev_vpn_state) {
                 switch (vs) {
                     case VPN_STATE_WAITING_WIFI:
-                        RT_INFO(&g_bg_log_stream, now_us, "VPN: waiting for WiFi");
+                        RT_LOG_T(&g_bg_log_stream, LOG_TAG_VPN, LOG_LEVEL_INFO, now_us,
+                                 "VPN: waiting for WiFi");
                         break;
                     case VPN_STATE_WAITING_TIME:
-                        RT_INFO(&g_bg_log_stream, now_us, "VPN: syncing time (NTP)");
+                        RT_LOG_T(&g_bg_log_stream, LOG_TAG_VPN, LOG_LEVEL_INFO, now_us,
+                                 "VPN: syncing time (NTP)");
                         break;
                     case VPN_STATE_CONNECTING:
-                        RT_INFO(&g_bg_log_stream, now_us, "VPN: WireGuard handshake");
+                        RT_LOG_T(&g_bg_log_stream, LOG_TAG_VPN, LOG_LEVEL_INFO, now_us,
+                                 "VPN: WireGuard handshake");
                         break;
                     case VPN_STATE_CONNECTED:
-                        RT_INFO(&g_bg_log_stream, now_us, "VPN: tunnel established");
+                        RT_LOG_T(&g_bg_log_stream, LOG_TAG_VPN, LOG_LEVEL_INFO, now_us,
+                                 "VPN: tunnel established");
                         break;
                     case VPN_STATE_FAILED:
-                        RT_WARN(&g_bg_log_stream, now_us, "VPN: connection failed");
+                        RT_LOG_T(&g_bg_log_stream, LOG_TAG_VPN, LOG_LEVEL_WARN, now_us,
+                                 "VPN: connection failed");
                         break;
                     default:
                         break;
@@ -156,16 +619,39 @@ void bg_task(void *arg) {
 
         /* Process CWNet socket (connection, send/receive) */
         cwnet_socket_process();
-
-        /* Process decoder (reads from keying_stream) */
+        link_tick(now_us);
+
+        /* Process decoder (reads from keying_stream); our own call is
+         * decoded from every CQ and must not become the last callsign */
+        char own_call[CONFIG_STR_MAX_LEN + 1];
+        CONFIG_COPY_CALLSIGN(own_call, sizeof(own_call));
+        decoder_set_own_callsign(own_call);
+        decoder_set_glitch_filter_ms(CONFIG_GET_DECODE_GLITCH_MS());
         decoder_process();
 
-        /* Push decoded characters to WebUI */
-        decoded_char_t ch;
-        while ((ch = decoder_pop_char()).character != '\0') {
-            webui_decoder_push_char(ch.character, (uint8_t)decoder_get_wpm());
-            if (ch.character == ' ') {
-                webui_decoder_push_word();
+        /* Push decoded text to WebUI clients (timestamped) and console */
+        {
+            static uint32_t decode_cursor = 0;
+            decoded_char_t chars[16];
+            size_t n;
+            while ((n = decoder_read_since(&decode_cursor, chars,
+                                           sizeof(chars) / sizeof(chars[0]))) > 0) {
+                char text[sizeof(chars) / sizeof(chars[0]) + 1];
+                char sent[sizeof(chars) / sizeof(chars[0]) + 1];
+                bool word = false;
+                uint32_t seq = decode_cursor - (uint32_t)n;  /* Skips ahead if we lagged */
+                for (size_t i = 0; i < n; i++) {
+                    text[i] = chars[i].character;
+                    sent[i] = (chars[i].sent != '\0') ? chars[i].sent : ' ';
+                    console_decode_echo(chars[i].character);
+                    word = word || (chars[i].character == ' ');
+                }
+                text[n] = '\0';
+                sent[n] = '\0';
+                webui_decoder_push_text(text, sent, seq, now_us / 1000, (uint8_t)decoder_get_wpm());
+                if (word) {
+                    webui_decoder_push_word();
+                }
             }
         }
 
@@ -218,8 +704,11 @@ void bg_task(void *arg) {
                         sample.local_key ? 1 : 0);
                     webui_timeline_push("keying", json);
 
-                    /* Forward key event to CWNet */
-                    cwnet_socket_send_key_event(sample.local_key != 0);
+                    /* Forward key event to CWNet (not trainer echoes; key-up
+                     * always, so the remote never keeps a key down) */
+                    if (sample.local_key == 0 || !text_keyer_tx_inhibited()) {
+                        cwnet_socket_send_key_event(sample.local_key != 0);
+                    }
                 }
 
                 /* Update previous state */
@@ -228,9 +717,30 @@ void bg_task(void *arg) {
             }
         }
 
-        /* Tick text keyer */
+        /* Memory buttons, then tick text keyer */
+        memory_buttons_tick(now_us);
+        expander_tick(now_us);
+        speed_encoder_tick(now_us);
+        speed_pot_tick();
+        copy_practice_tick(now_us);
         text_keyer_tick(now_us);
 
+        /* Codec input follows the PTT input without waiting a second */
+        audio_input_tick();
+        audio_output_tick(now_us);
+
+        /* Light sleep when idle (system.sleep_idle_min, 0 = off) */
+        power_tick(now_us);
+
+        /* Read-only diag.* parameters, once a second */
+        if (stats_counter % 100 == 0) {
+            update_diag_params(now_us);
+            supply_tick(now_us);
+            thermal_tick(now_us);
+            wall_clock_tick(now_us);
+            cpu_load_tick(now_us);
+        }
+
         /* Periodic stats logging */
         stats_counter++;
         if (stats_counter >= 1000) {  /* Every ~10 seconds at 10ms tick */
@@ -256,17 +766,23 @@ void bg_task(void *arg) {
             if (cwnet_state != CWNET_SOCK_DISABLED) {
                 int32_t latency = cwnet_socket_get_latency_ms();
                 if (latency >= 0) {
-                    RT_INFO(&g_bg_log_stream, now_us, "CWNet: %s, latency=%"PRId32"ms",
-                            cwnet_socket_state_str(cwnet_state), latency);
+                    RT_LOG_T(&g_bg_log_stream, LOG_TAG_CWNET, LOG_LEVEL_INFO, now_us,
+                             "CWNet: %s, latency=%"PRId32"ms",
+                             cwnet_socket_state_str(cwnet_state), latency);
                 } else {
-                    RT_INFO(&g_bg_log_stream, now_us, "CWNet: %s",
-                            cwnet_socket_state_str(cwnet_state));
+                    RT_LOG_T(&g_bg_log_stream, LOG_TAG_CWNET, LOG_LEVEL_INFO, now_us,
+                             "CWNet: %s", cwnet_socket_state_str(cwnet_state));
                 }
             }
 
             stats_counter = 0;
         }
 
-        vTaskDelay(pdMS_TO_TICKS(10));  /* 100Hz - adequate for LED animations */
+        /* 100Hz - adequate for LED animations; slower in light sleep */
+        if (power_is_asleep()) {
+            power_sleep_wait();
+        } else {
+            vTaskDelay(pdMS_TO_TICKS(10));
+        }
     }
 }
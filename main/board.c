@@ -0,0 +1,177 @@
+/**
+ * @file board.c
+ * @brief Pin map of the active board implementation
+ */
+
+#include "board.h"
+#include "sdkconfig.h"
+#include "esp_log.h"
+#include "config.h"
+
+static const char *TAG = "board";
+
+#ifdef CONFIG_KEYER_LOG_UART_TX_GPIO
+#define BOARD_LOG_TX_PIN CONFIG_KEYER_LOG_UART_TX_GPIO
+#else
+#define BOARD_LOG_TX_PIN 0
+#endif
+
+/** One named GPIO of the map, for the clash check */
+typedef struct {
+    const char *name;
+    uint8_t *pin;
+} board_pin_use_t;
+
+/** Entries in board_pin_use_t form (every field but the expander pin) */
+#define BOARD_PIN_USES (32 + HAL_GPIO_BUTTON_MAX)
+
+/* ============================================================================
+ * Helpers
+ * ============================================================================ */
+
+static size_t list_pins(board_pins_t *p, board_pin_use_t *uses) {
+    static const char *const mem_names[HAL_GPIO_BUTTON_MAX] = {
+        "mem1", "mem2", "mem3", "mem4",
+    };
+    size_t n = 0;
+
+    /* Claimed by the boot log UART before the map is read: always wins */
+    uses[n++] = (board_pin_use_t){ "log_tx", &p->log_tx };
+    uses[n++] = (board_pin_use_t){ "dit", &p->dit };
+    uses[n++] = (board_pin_use_t){ "dah", &p->dah };
+    uses[n++] = (board_pin_use_t){ "key_in", &p->key_in };
+    uses[n++] = (board_pin_use_t){ "ptt_in", &p->ptt_in };
+    uses[n++] = (board_pin_use_t){ "tx", &p->tx };
+    uses[n++] = (board_pin_use_t){ "ptt", &p->ptt };
+    uses[n++] = (board_pin_use_t){ "tx2", &p->tx2 };
+    uses[n++] = (board_pin_use_t){ "ptt2", &p->ptt2 };
+    uses[n++] = (board_pin_use_t){ "antenna", &p->antenna };
+    uses[n++] = (board_pin_use_t){ "amp", &p->amp };
+    uses[n++] = (board_pin_use_t){ "loop_tx", &p->loop_tx };
+    uses[n++] = (board_pin_use_t){ "loop_ptt", &p->loop_ptt };
+    for (size_t i = 0; i < HAL_GPIO_BUTTON_MAX; i++) {
+        uses[n++] = (board_pin_use_t){ mem_names[i], &p->mem[i] };
+    }
+    uses[n++] = (board_pin_use_t){ "enc_a", &p->enc_a };
+    uses[n++] = (board_pin_use_t){ "enc_b", &p->enc_b };
+    uses[n++] = (board_pin_use_t){ "speed_pot", &p->speed_pot };
+    uses[n++] = (board_pin_use_t){ "supply", &p->supply };
+    uses[n++] = (board_pin_use_t){ "i2c_sda", &p->i2c_sda };
+    uses[n++] = (board_pin_use_t){ "i2c_scl", &p->i2c_scl };
+    uses[n++] = (board_pin_use_t){ "i2s_mclk", &p->i2s_mclk };
+    uses[n++] = (board_pin_use_t){ "i2s_bclk", &p->i2s_bclk };
+    uses[n++] = (board_pin_use_t){ "i2s_lrck", &p->i2s_lrck };
+    uses[n++] = (board_pin_use_t){ "i2s_dout", &p->i2s_dout };
+    uses[n++] = (board_pin_use_t){ "i2s_din", &p->i2s_din };
+    uses[n++] = (board_pin_use_t){ "mic_bias", &p->mic_bias };
+    uses[n++] = (board_pin_use_t){ "hp_detect", &p->hp_detect };
+    uses[n++] = (board_pin_use_t){ "sd_sck", &p->sd_sck };
+    uses[n++] = (board_pin_use_t){ "sd_mosi", &p->sd_mosi };
+    uses[n++] = (board_pin_use_t){ "sd_miso", &p->sd_miso };
+    uses[n++] = (board_pin_use_t){ "sd_cs", &p->sd_cs };
+    uses[n++] = (board_pin_use_t){ "leds", &p->leds };
+    uses[n++] = (board_pin_use_t){ "buzzer", &p->buzzer };
+    return n;
+}
+
+/** Not fitted is 0 in the map, -1 for the audio HAL */
+static int audio_pin(uint8_t pin) {
+    return pin != 0 ? (int)pin : -1;
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void board_pins_load(board_pins_t *pins) {
+    *pins = (board_pins_t){
+        .dit = CONFIG_GET_GPIO_DIT(),
+        .dah = CONFIG_GET_GPIO_DAH(),
+        .key_in = CONFIG_GET_GPIO_KEY_IN(),
+        .ptt_in = CONFIG_GET_GPIO_PTT_IN(),
+        .tx = CONFIG_GET_GPIO_TX(),
+        .ptt = CONFIG_GET_GPIO_PTT(),
+        .tx2 = CONFIG_GET_GPIO_TX2(),
+        .ptt2 = CONFIG_GET_GPIO_PTT2(),
+        .antenna = CONFIG_GET_GPIO_ANTENNA(),
+        .amp = CONFIG_GET_GPIO_AMP(),
+        .loop_tx = CONFIG_GET_GPIO_LOOP_TX(),
+        .loop_ptt = CONFIG_GET_GPIO_LOOP_PTT(),
+        .mem = {
+            CONFIG_GET_GPIO_MEM1(), CONFIG_GET_GPIO_MEM2(),
+            CONFIG_GET_GPIO_MEM3(), CONFIG_GET_GPIO_MEM4(),
+        },
+        .enc_a = CONFIG_GET_GPIO_ENC_A(),
+        .enc_b = CONFIG_GET_GPIO_ENC_B(),
+        .speed_pot = CONFIG_GET_GPIO_SPEED_POT(),
+        .supply = CONFIG_GET_GPIO_SUPPLY(),
+        .i2c_sda = CONFIG_GET_GPIO_I2C_SDA(),
+        .i2c_scl = CONFIG_GET_GPIO_I2C_SCL(),
+        .i2s_mclk = CONFIG_GET_GPIO_I2S_MCLK(),
+        .i2s_bclk = CONFIG_GET_GPIO_I2S_BCLK(),
+        .i2s_lrck = CONFIG_GET_GPIO_I2S_LRCK(),
+        .i2s_dout = CONFIG_GET_GPIO_I2S_DOUT(),
+        .i2s_din = CONFIG_GET_GPIO_I2S_DIN(),
+        .pa_expander_pin = CONFIG_GET_CODEC_PA_PIN(),
+        .mic_bias = CONFIG_GET_GPIO_MIC_BIAS(),
+        .hp_detect = CONFIG_GET_GPIO_HP_DETECT(),
+        .sd_sck = CONFIG_GET_GPIO_SD_SCK(),
+        .sd_mosi = CONFIG_GET_GPIO_SD_MOSI(),
+        .sd_miso = CONFIG_GET_GPIO_SD_MISO(),
+        .sd_cs = CONFIG_GET_GPIO_SD_CS(),
+        .leds = CONFIG_GET_GPIO_DATA(),
+        .buzzer = CONFIG_GET_GPIO_BUZZER(),
+        .log_tx = BOARD_LOG_TX_PIN,
+    };
+
+    /* No codec: its pins are free for other uses */
+    if (!config_board_active()->has_codec) {
+        pins->i2s_mclk = 0;
+        pins->i2s_bclk = 0;
+        pins->i2s_lrck = 0;
+        pins->i2s_dout = 0;
+        pins->i2s_din = 0;
+    }
+}
+
+esp_err_t board_pins_check(board_pins_t *pins, uint8_t *clash) {
+    board_pin_use_t uses[BOARD_PIN_USES];
+    size_t n = list_pins(pins, uses);
+
+    ESP_LOGI(TAG, "Pins: DIT=%d DAH=%d TX=%d PTT=%d I2C=%d/%d LED=%d log=%d",
+             pins->dit, pins->dah, pins->tx, pins->ptt,
+             pins->i2c_sda, pins->i2c_scl, pins->leds, pins->log_tx);
+
+    esp_err_t result = ESP_OK;
+    for (size_t i = 0; i < n; i++) {
+        if (*uses[i].pin == 0) {
+            continue;
+        }
+        for (size_t j = i + 1; j < n; j++) {
+            if (*uses[j].pin == *uses[i].pin) {
+                ESP_LOGE(TAG, "GPIO%d assigned to both %s and %s: %s not started",
+                         *uses[i].pin, uses[i].name, uses[j].name, uses[j].name);
+                if (result == ESP_OK && clash != NULL) {
+                    *clash = *uses[i].pin;
+                }
+                *uses[j].pin = 0;
+                result = ESP_ERR_INVALID_STATE;
+            }
+        }
+    }
+    return result;
+}
+
+void board_audio_pins(const board_pins_t *pins, hal_audio_config_t *cfg) {
+    cfg->i2c_sda_pin = audio_pin(pins->i2c_sda);
+    cfg->i2c_scl_pin = audio_pin(pins->i2c_scl);
+    cfg->i2s_mclk_pin = audio_pin(pins->i2s_mclk);
+    cfg->i2s_bclk_pin = audio_pin(pins->i2s_bclk);
+    cfg->i2s_lrck_pin = audio_pin(pins->i2s_lrck);
+    cfg->i2s_dout_pin = audio_pin(pins->i2s_dout);
+    cfg->i2s_din_pin = audio_pin(pins->i2s_din);
+    cfg->pa_pin = pins->pa_expander_pin;
+    cfg->mic_bias_pin = audio_pin(pins->mic_bias);
+    cfg->hp_detect_pin = audio_pin(pins->hp_detect);
+    cfg->hp_detect_high = CONFIG_GET_HP_DETECT_HIGH();
+}
@@ -0,0 +1,116 @@
+/**
+ * @file board.h
+ * @brief Pin map of the active board
+ *
+ * Every GPIO the firmware drives or reads, gathered in one place. The
+ * board variant (NVS board id or Kconfig KEYER_BOARD, see
+ * config_board_active()) supplies the defaults, stored hardware.* values
+ * override them, and the drivers get their pins from here instead of
+ * carrying their own numbers. The boot log UART pin is a build option
+ * (Kconfig KEYER_LOG_UART_TX_GPIO): it is needed before NVS is read.
+ */
+
+#ifndef KEYER_BOARD_H
+#define KEYER_BOARD_H
+
+#include <stdint.h>
+#include <stddef.h>
+#include "esp_err.h"
+#include "hal_gpio.h"
+#include "hal_audio.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Board pin assignments (GPIO numbers, 0 = not fitted)
+ */
+typedef struct {
+    /* Keying inputs */
+    uint8_t dit;
+    uint8_t dah;
+    uint8_t key_in;
+    uint8_t ptt_in;
+
+    /* Rig outputs */
+    uint8_t tx;
+    uint8_t ptt;
+    uint8_t tx2;
+    uint8_t ptt2;
+    uint8_t antenna;
+    uint8_t amp;
+
+    /* Boot self-test sense inputs (jumpered to TX and PTT) */
+    uint8_t loop_tx;
+    uint8_t loop_ptt;
+
+    /* Front panel */
+    uint8_t mem[HAL_GPIO_BUTTON_MAX];
+    uint8_t enc_a;
+    uint8_t enc_b;
+    uint8_t speed_pot;
+    uint8_t supply;
+
+    /* Shared I2C bus (codec, IO expander, OLED) */
+    uint8_t i2c_sda;
+    uint8_t i2c_scl;
+
+    /* Codec */
+    uint8_t i2s_mclk;
+    uint8_t i2s_bclk;
+    uint8_t i2s_lrck;
+    uint8_t i2s_dout;
+    uint8_t i2s_din;
+    uint8_t pa_expander_pin;   /**< TCA9555 pin, not a GPIO */
+    uint8_t mic_bias;
+    uint8_t hp_detect;
+
+    /* SD card (SPI) */
+    uint8_t sd_sck;
+    uint8_t sd_mosi;
+    uint8_t sd_miso;
+    uint8_t sd_cs;
+
+    /* Indicators */
+    uint8_t leds;
+    uint8_t buzzer;
+
+    /* Boot log UART TX (build option) */
+    uint8_t log_tx;
+} board_pins_t;
+
+/**
+ * @brief Resolve the pin map from the configuration
+ *
+ * Call after the board defaults and NVS are loaded.
+ *
+ * @param pins Filled in
+ */
+void board_pins_load(board_pins_t *pins);
+
+/**
+ * @brief Log the map and drop GPIOs assigned twice
+ *
+ * The first user in map order keeps a shared GPIO (paddles and keying
+ * outputs come first); every later user is set to 0 so it is not started.
+ *
+ * @param pins Pin map, clashing entries cleared
+ * @param clash First GPIO found assigned twice (may be NULL)
+ * @return ESP_OK, or ESP_ERR_INVALID_STATE if any GPIO was assigned twice
+ */
+esp_err_t board_pins_check(board_pins_t *pins, uint8_t *clash);
+
+/**
+ * @brief Copy the codec pins into an audio HAL configuration
+ *
+ * @param pins Pin map
+ * @param cfg Updated (I2C, I2S, PA, mic bias and headphone detect only)
+ */
+void board_audio_pins(const board_pins_t *pins, hal_audio_config_t *cfg);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_BOARD_H */
@@ -0,0 +1,51 @@
+/**
+ * @file io_task.c
+ * @brief Slow peripheral I/O task (Core 1)
+ *
+ * Work that can wait on a bus for milliseconds runs here, below bg_task,
+ * so text keying, the decoder and the WebUI never wait for it:
+ * - OLED status display (I2C page writes)
+ * - Deferred config save (NVS write, flash erase)
+ * - Contest serial number and abbreviation saves (NVS)
+ * - SD card recordings (file writes and flushes)
+ *
+ * Runs on Core 1 at the log drain priority.
+ */
+
+#include "freertos/FreeRTOS.h"
+#include "freertos/task.h"
+#include "esp_timer.h"
+
+#include "display.h"
+#include "config_nvs.h"
+#include "recorder.h"
+#include "contest_serial.h"
+#include "abbrev.h"
+
+/** Loop period: the display drains its stream consumer every pass */
+#define IO_TASK_PERIOD_MS 20
+
+void io_task(void *arg) {
+    (void)arg;
+
+    for (;;) {
+        int64_t now_us = esp_timer_get_time();
+
+        /* OLED: redraws at most every DISPLAY_REFRESH_US */
+        display_tick(now_us);
+
+        /* SD card recordings: keying stream, decoded text, log flush */
+        recorder_tick(now_us);
+
+        /* Deferred config save (system.autosave_sec, 0 = off) */
+        config_nvs_autosave_tick(now_us);
+
+        /* Contest serial advanced by keying or set from the console */
+        contest_serial_save_tick();
+
+        /* Keyboard abbreviations changed from the console */
+        abbrev_save_tick();
+
+        vTaskDelay(pdMS_TO_TICKS(IO_TASK_PERIOD_MS));
+    }
+}
@@ -10,6 +10,7 @@
 #include "freertos/task.h"
 #include "esp_log.h"
 #include "esp_timer.h"
+#include "esp_system.h"
 #include "esp_netif.h"
 #include "esp_event.h"
 #include "nvs_flash.h"
@@ -18,39 +19,52 @@
 #include "iambic.h"
 #include "audio.h"
 #include "rt_log.h"
+#include "crash_log.h"
+#include "log_drain.h"
 #include "console.h"
+#include "console_output.h"
 #include "config.h"
 #include "config_nvs.h"
 #include "hal_gpio.h"
+#include "hal_encoder.h"
+#include "hal_pot.h"
+#include "hal_supply.h"
+#include "hal_temp.h"
+#include "hal_touch.h"
 #include "hal_audio.h"
+#include "hal_buzzer.h"
+#include "hal_i2c.h"
+#include "hal_expander.h"
 #include "usb_cdc.h"
+#include "usb_console.h"
 #include "usb_log.h"
 #include "wifi.h"
 #include "vpn.h"
 #include "webui.h"
 #include "led.h"
+#include "display.h"
 #include "decoder.h"
 #include "text_keyer.h"
 #include "text_memory.h"
+#include "contest_serial.h"
+#include "abbrev.h"
 #include "provisioning.h"
+#include "tasks.h"
+#include "power.h"
+#include "board.h"
+#include "selftest.h"
+#include "storage.h"
+#include "recorder.h"
 
 static const char *TAG = "main";
 
-/* Default GPIO pins for factory reset check (before config loaded) */
-#define DEFAULT_GPIO_DIT  3
-#define DEFAULT_GPIO_DAH  4
 #define FACTORY_RESET_HOLD_MS  5000
 
-/* External task functions */
-extern void rt_task(void *arg);
-extern void bg_task(void *arg);
-extern void start_audio_test(void);  /* Audio test task */
+/* Audio test task (audio_test.c, not in the boot path) */
+extern void start_audio_test(void);
 
-/* Paddle state for text keyer abort (from rt_task.c) */
-extern atomic_bool g_paddle_active;
-
-/* UART logger task handle (for stopping after USB CDC ready) */
-static TaskHandle_t s_uart_log_task_handle = NULL;
+/* Paddle touch latch for text keyer interrupt (from rt_task.c) */
+extern atomic_bool g_paddle_touched;
 
 /* Stream buffer in PSRAM */
 #define STREAM_BUFFER_SIZE 4096
@@ -61,6 +75,46 @@ keying_stream_t g_keying_stream;
 
 /* Global fault state */
 fault_state_t g_fault_state = FAULT_STATE_INIT;
+fault_indicator_t g_fault_indicator;
+
+/* RT loop timing statistics (written by rt_task, read by console) */
+rt_stats_t g_rt_stats;
+
+/* Per-core CPU load (written by bg_task, read by console and web UI) */
+cpu_load_t g_cpu_load;
+
+/* Recent parameter changes (written by console and HTTP tasks) */
+config_audit_t g_config_audit;
+
+/**
+ * @brief SD card log sink (system.sd_log)
+ */
+static bool sd_log_sink(const log_entry_t *entry, const char *stream) {
+    (void)stream;
+    static char line[LOG_LINE_MAX];
+    return recorder_log_write(line, log_entry_format(entry, line, sizeof(line)));
+}
+
+/**
+ * @brief Record a watchdog or brownout end of the previous boot (INFO faults)
+ */
+static void report_reset_reason(void) {
+    esp_reset_reason_t reason = esp_reset_reason();
+    switch (reason) {
+        case ESP_RST_INT_WDT:
+        case ESP_RST_TASK_WDT:
+        case ESP_RST_WDT:
+            ESP_LOGW(TAG, "Previous boot ended in a watchdog reset (%d)", (int)reason);
+            fault_set(&g_fault_state, FAULT_WATCHDOG, (uint32_t)reason);
+            break;
+        case ESP_RST_BROWNOUT:
+            ESP_LOGW(TAG, "Previous boot ended in a brownout reset");
+            fault_set(&g_fault_state, FAULT_BROWNOUT, (uint32_t)reason);
+            break;
+        default:
+            break;
+    }
+}
 
 void app_main(void) {
     /* Minimal early debug - use printf since ESP_LOG may not be ready */
@@ -68,18 +122,29 @@ void app_main(void) {
 
     ESP_LOGI(TAG, "keyer_c starting...");
 
+    /* Time source for modules that read the clock themselves (keyer_clock.h) */
+    keyer_clock_set_source(esp_timer_get_time);
+
     printf(">>> log_stream_init...\n");
     /* Initialize log streams FIRST (before any RT_* logging) */
     log_stream_init(&g_rt_log_stream);
     log_stream_init(&g_bg_log_stream);
     printf(">>> log_stream_init OK\n");
 
+    /* Initialize fault state (boot checks below may raise faults) */
+    fault_init(&g_fault_state);
+    fault_indicator_init(&g_fault_indicator);
+    report_reset_reason();
+
     /* Enable RT diagnostics for boot debugging */
     atomic_store_explicit(&g_rt_diag_enabled, true, memory_order_relaxed);
 
-    /* Initialize UART logger early for boot logs (GPIO6, 115200) */
+    /* Initialize UART logger early for boot logs (Kconfig pin, 115200) */
     uart_logger_init();
 
+    /* Recent log lines for the post-mortem ("log crash") */
+    log_drain_add_sink("crash", crash_log_sink, NULL);
+
     /* Initialize NVS */
     printf(">>> NVS init...\n");
     esp_err_t ret = nvs_flash_init();
@@ -90,11 +155,27 @@ void app_main(void) {
     ESP_ERROR_CHECK(ret);
     printf(">>> NVS init OK\n");
 
+    /* Keep the post-mortem of a panic in the previous boot ("log crash") */
+    if (crash_log_boot()) {
+        ESP_LOGW(TAG, "Previous boot panicked: post-mortem saved, see 'log crash'");
+    }
+
+    /* Schema defaults, then the board's own (pins, LED strip) */
+    printf(">>> config_init_defaults...\n");
+    config_init_defaults(&g_config);
+    config_board_init();
+    const config_board_t *board = config_board_active();
+    ESP_LOGI(TAG, "Board: %s (codec %s, PSRAM %s)", board->name,
+             board->has_codec ? "yes" : "no", config_psram_str(board->psram));
+    printf(">>> config_init_defaults OK\n");
+
     /* ===== PROVISIONING CHECK (before normal boot) ===== */
 
-    /* Check for factory reset request (both paddles held 5s) */
+    /* Check for factory reset request (both paddles held 5s, board default pins) */
     printf(">>> Checking factory reset...\n");
-    if (provisioning_check_factory_reset(DEFAULT_GPIO_DIT, DEFAULT_GPIO_DAH, FACTORY_RESET_HOLD_MS)) {
+    if (provisioning_check_factory_reset((gpio_num_t)CONFIG_GET_GPIO_DIT(),
+                                         (gpio_num_t)CONFIG_GET_GPIO_DAH(),
+                                         FACTORY_RESET_HOLD_MS)) {
         ESP_LOGW(TAG, "Factory reset triggered - rebooting to provisioning mode");
         esp_restart();
     }
@@ -109,16 +190,15 @@ void app_main(void) {
 
     /* ===== NORMAL BOOT CONTINUES ===== */
 
-    /* Initialize config with defaults, then load from NVS */
-    printf(">>> config_init_defaults...\n");
-    config_init_defaults(&g_config);
-    printf(">>> config_init_defaults OK\n");
-
+    /* Stored values override the board defaults applied above */
     printf(">>> config_load_from_nvs...\n");
     int loaded = config_load_from_nvs();
     printf(">>> config_load_from_nvs OK (loaded=%d)\n", loaded);
     if (loaded > 0) {
         ESP_LOGI(TAG, "Loaded %d parameters from NVS", loaded);
+    } else if (config_nvs_integrity_failed()) {
+        ESP_LOGW(TAG, "Stored configuration failed CRC check, using defaults");
+        fault_set(&g_fault_state, FAULT_NVS, 0);
     } else {
         ESP_LOGI(TAG, "Using default configuration");
     }
@@ -127,21 +207,69 @@ void app_main(void) {
     ESP_ERROR_CHECK(esp_netif_init());
     ESP_ERROR_CHECK(esp_event_loop_create_default());
 
+    /* Board pin map: board defaults, then stored hardware.* values */
+    board_pins_t pins;
+    board_pins_load(&pins);
+    uint8_t pin_clash = 0;
+    if (board_pins_check(&pins, &pin_clash) != ESP_OK) {
+        fault_set(&g_fault_state, FAULT_PIN_CONFLICT, pin_clash);
+    }
+
     /* Initialize HAL GPIO using values from g_config (loaded from NVS or defaults) */
     printf(">>> hal_gpio_init...\n");
     hal_gpio_config_t gpio_cfg = {
-        .dit_pin = CONFIG_GET_GPIO_DIT(),
-        .dah_pin = CONFIG_GET_GPIO_DAH(),
-        .tx_pin = CONFIG_GET_GPIO_TX(),
-        .active_low = true,        /* Paddles are active low (internal pull-up) */
-        .tx_active_high = true,    /* TX output is active high */
+        .dit_pin = pins.dit,
+        .dah_pin = pins.dah,
+        .tx_pin = pins.tx,
+        .ptt_pin = pins.ptt,
+        .tx2_pin = pins.tx2,
+        .ptt2_pin = pins.ptt2,
+        .antenna_pin = pins.antenna,
+        .amp_pin = pins.amp,
+        .active_low = CONFIG_GET_PADDLE_ACTIVE_LOW(),
+        .tx_active_high = !CONFIG_GET_TX_ACTIVE_LOW(),
+        .tx_open_drain = CONFIG_GET_TX_OPEN_DRAIN(),
+        .tx_min_key_up_us = (uint32_t)CONFIG_GET_TX_MIN_KEY_UP_MS() * 1000,
         .isr_blanking_us = 1500,   /* ISR blanking period for debounce (0 = polling only) */
+        .glitch_filter_ns = CONFIG_GET_PADDLE_GLITCH_NS(),
+        .touch_paddles = CONFIG_GET_TOUCH_PADDLES(),
+        .key_in_pin = pins.key_in,
+        .key_in_active_low = CONFIG_GET_KEY_IN_ACTIVE_LOW(),
+        .ptt_in_pin = pins.ptt_in,
+        .ptt_in_active_low = CONFIG_GET_PTT_IN_ACTIVE_LOW(),
     };
     ESP_LOGI(TAG, "GPIO config from g_config: DIT=%d, DAH=%d, TX=%d",
              gpio_cfg.dit_pin, gpio_cfg.dah_pin, gpio_cfg.tx_pin);
+    if (gpio_cfg.touch_paddles) {
+        hal_touch_init(gpio_cfg.dit_pin, gpio_cfg.dah_pin, CONFIG_GET_TOUCH_SENSITIVITY_PCT());
+    }
     hal_gpio_init(&gpio_cfg);
+    uint8_t button_refused = hal_gpio_buttons_init(pins.mem, HAL_GPIO_BUTTON_MAX);
+    if (button_refused != 0) {
+        fault_set(&g_fault_state, FAULT_PIN_CONFLICT, button_refused);
+    }
+    hal_encoder_init(pins.enc_a, pins.enc_b);
+    hal_pot_init(pins.speed_pot);
+    hal_supply_init(pins.supply);
+    hal_temp_init();
     printf(">>> hal_gpio_init OK\n");
 
+    /* TX pad follows the output: a line stuck keyed stops all keying */
+    if (pins.tx != 0 && !hal_gpio_tx_loopback()) {
+        ESP_LOGE(TAG, "TX GPIO%d loopback FAIL: line does not follow the output", pins.tx);
+        fault_set(&g_fault_state, FAULT_GPIO_SELFTEST, pins.tx);
+    }
+
+    /* Output stage check through the test jumpers (RT task not keying yet) */
+    char loop_detail[SELFTEST_DETAIL_MAX];
+    selftest_status_t loop_status = selftest_loopback(loop_detail, sizeof(loop_detail));
+    if (loop_status == SELFTEST_FAIL) {
+        ESP_LOGE(TAG, "Output loopback FAIL: %s", loop_detail);
+        fault_set(&g_fault_state, FAULT_GPIO_SELFTEST, 0);
+    } else if (loop_status == SELFTEST_PASS) {
+        ESP_LOGI(TAG, "Output loopback PASS: %s", loop_detail);
+    }
+
     /* Initialize USB CDC (before console) */
     printf(">>> usb_cdc_init...\n");
     ESP_ERROR_CHECK(usb_cdc_init());
@@ -149,7 +277,7 @@ void app_main(void) {
 
     /* Initialize LED strip */
     led_config_t led_cfg = {
-        .gpio_data = atomic_load_explicit(&g_config.leds.gpio_data, memory_order_relaxed),
+        .gpio_data = pins.leds,
         .led_count = atomic_load_explicit(&g_config.leds.count, memory_order_relaxed),
         .brightness = atomic_load_explicit(&g_config.leds.brightness, memory_order_relaxed),
         .brightness_dim = atomic_load_explicit(&g_config.leds.brightness_dim, memory_order_relaxed),
@@ -169,12 +297,12 @@ void app_main(void) {
             .timeout_sec = atomic_load_explicit(&g_config.wifi.timeout_sec, memory_order_relaxed),
             .use_static_ip = atomic_load_explicit(&g_config.wifi.use_static_ip, memory_order_relaxed),
         };
-        strncpy(wifi_cfg.ssid, g_config.wifi.ssid, sizeof(wifi_cfg.ssid) - 1);
-        strncpy(wifi_cfg.password, g_config.wifi.password, sizeof(wifi_cfg.password) - 1);
-        strncpy(wifi_cfg.ip_address, g_config.wifi.ip_address, sizeof(wifi_cfg.ip_address) - 1);
-        strncpy(wifi_cfg.netmask, g_config.wifi.netmask, sizeof(wifi_cfg.netmask) - 1);
-        strncpy(wifi_cfg.gateway, g_config.wifi.gateway, sizeof(wifi_cfg.gateway) - 1);
-        strncpy(wifi_cfg.dns, g_config.wifi.dns, sizeof(wifi_cfg.dns) - 1);
+        CONFIG_COPY_SSID(wifi_cfg.ssid, sizeof(wifi_cfg.ssid));
+        CONFIG_COPY_PASSWORD(wifi_cfg.password, sizeof(wifi_cfg.password));
+        CONFIG_COPY_IP_ADDRESS(wifi_cfg.ip_address, sizeof(wifi_cfg.ip_address));
+        CONFIG_COPY_NETMASK(wifi_cfg.netmask, sizeof(wifi_cfg.netmask));
+        CONFIG_COPY_GATEWAY(wifi_cfg.gateway, sizeof(wifi_cfg.gateway));
+        CONFIG_COPY_DNS(wifi_cfg.dns, sizeof(wifi_cfg.dns));
 
         ret = wifi_app_init(&wifi_cfg);
         if (ret == ESP_OK) {
@@ -189,7 +317,8 @@ void app_main(void) {
         led_set_state(LED_STATE_IDLE);
     }
 
-    /* Initialize VPN if enabled (requires WiFi) */
+    /* Initialize VPN if enabled (requires WiFi), started with the tasks */
+    tasks_plan_t task_plan = { .vpn = false };
     if (atomic_load_explicit(&g_config.vpn.enabled, memory_order_relaxed)) {
         ESP_LOGI(TAG, "VPN enabled, initializing...");
         vpn_config_app_t vpn_cfg = {
@@ -197,15 +326,15 @@ void app_main(void) {
             .server_port = atomic_load_explicit(&g_config.vpn.server_port, memory_order_relaxed),
             .persistent_keepalive = atomic_load_explicit(&g_config.vpn.persistent_keepalive, memory_order_relaxed),
         };
-        strncpy(vpn_cfg.server_endpoint, g_config.vpn.server_endpoint, sizeof(vpn_cfg.server_endpoint) - 1);
-        strncpy(vpn_cfg.server_public_key, g_config.vpn.server_public_key, sizeof(vpn_cfg.server_public_key) - 1);
-        strncpy(vpn_cfg.client_private_key, g_config.vpn.client_private_key, sizeof(vpn_cfg.client_private_key) - 1);
-        strncpy(vpn_cfg.client_address, g_config.vpn.client_address, sizeof(vpn_cfg.client_address) - 1);
-        strncpy(vpn_cfg.allowed_ips, g_config.vpn.allowed_ips, sizeof(vpn_cfg.allowed_ips) - 1);
+        CONFIG_COPY_SERVER_ENDPOINT(vpn_cfg.server_endpoint, sizeof(vpn_cfg.server_endpoint));
+        CONFIG_COPY_SERVER_PUBLIC_KEY(vpn_cfg.server_public_key, sizeof(vpn_cfg.server_public_key));
+        CONFIG_COPY_CLIENT_PRIVATE_KEY(vpn_cfg.client_private_key, sizeof(vpn_cfg.client_private_key));
+        CONFIG_COPY_CLIENT_ADDRESS(vpn_cfg.client_address, sizeof(vpn_cfg.client_address));
+        CONFIG_COPY_ALLOWED_IPS(vpn_cfg.allowed_ips, sizeof(vpn_cfg.allowed_ips));
 
         ret = vpn_app_init(&vpn_cfg);
         if (ret == ESP_OK) {
-            vpn_app_start();  /* Non-blocking, spawns task on Core 1 */
+            task_plan.vpn = true;
         } else {
             ESP_LOGE(TAG, "VPN init failed: %s", esp_err_to_name(ret));
         }
@@ -217,18 +346,78 @@ void app_main(void) {
     ESP_LOGI(TAG, "Initializing keying stream (%d samples)", STREAM_BUFFER_SIZE);
     stream_init(&g_keying_stream, s_stream_buffer, STREAM_BUFFER_SIZE);
 
-    /* Initialize fault state */
-    fault_init(&g_fault_state);
+    /* A panic releases the outputs and keeps the stream tail ("log crash") */
+    crash_log_panic_setup(&g_keying_stream, hal_gpio_panic_off);
 
-    hal_audio_config_t audio_cfg = HAL_AUDIO_CONFIG_DEFAULT;
-    hal_audio_init(&audio_cfg);
+    /* Initialize RT loop statistics (1ms nominal period) */
+    rt_stats_init(&g_rt_stats, 1000);
+    cpu_load_init(&g_cpu_load);
 
-    /* Enable PA for sidetone output (TODO: integrate with PTT for proper control) */
-    hal_audio_set_pa(true);
+    config_audit_init(&g_config_audit);
+
+    /* Shared I2C bus: the OLED needs it on boards without a codec too */
+    if (pins.i2c_sda != 0 && pins.i2c_scl != 0) {
+        ret = hal_i2c_init(pins.i2c_sda, pins.i2c_scl);
+        if (ret != ESP_OK) {
+            ESP_LOGW(TAG, "I2C init failed (non-fatal): %s", esp_err_to_name(ret));
+        }
+    }
+
+    /* Extra buttons and sense inputs on the I2C bus (hardware.expander_type) */
+    if (CONFIG_GET_EXPANDER_TYPE() != HAL_EXPANDER_NONE) {
+        hal_expander_init((hal_expander_type_t)CONFIG_GET_EXPANDER_TYPE(),
+                          CONFIG_GET_EXPANDER_ADDR());
+    }
+
+    if (board->has_codec) {
+        hal_audio_config_t audio_cfg = HAL_AUDIO_CONFIG_DEFAULT;
+        board_audio_pins(&pins, &audio_cfg);
+        ret = hal_audio_init(&audio_cfg);  /* Input settings follow from bg_task */
+        if (ret != ESP_OK || !hal_audio_is_available()) {
+            /* Init does not block boot: it returns ESP_OK with audio off */
+            fault_set(&g_fault_state, FAULT_CODEC, (uint32_t)((ret != ESP_OK) ? ret : ESP_FAIL));
+        }
+
+        /* Enable PA for sidetone output (TODO: integrate with PTT for proper control) */
+        hal_audio_set_pa(true);
+    } else {
+        ESP_LOGI(TAG, "No codec on this board, sidetone disabled");
+    }
+
+    /* Buzzer sidetone, independent of the codec (audio.buzzer) */
+    hal_buzzer_init(pins.buzzer, CONFIG_GET_SIDETONE_FREQ_HZ());
+
+    /* OLED on the shared I2C bus */
+    if (CONFIG_GET_DISPLAY_ENABLED()) {
+        display_config_t display_cfg = {
+            .i2c_addr = CONFIG_GET_I2C_ADDR(),
+            .flip = CONFIG_GET_FLIP(),
+        };
+        ret = display_init(&display_cfg);
+        if (ret != ESP_OK) {
+            ESP_LOGW(TAG, "Display init failed (non-fatal): %s", esp_err_to_name(ret));
+        }
+    }
+
+    /* SD card recordings (system.sd_record, sd_transcript, sd_log) */
+    storage_pins_t sd_pins = {
+        .sck = pins.sd_sck,
+        .mosi = pins.sd_mosi,
+        .miso = pins.sd_miso,
+        .cs = pins.sd_cs,
+    };
+    if (storage_init(&sd_pins)) {
+        recorder_init(&g_keying_stream);
+        log_drain_add_sink("sd", sd_log_sink, NULL);
+    }
 
     /* Initialize console */
     console_init();
 
+    /* Console responses and prompt go to USB CDC0 and the log UART */
+    console_output_add_sink("usb", usb_console_write, false);
+    console_output_add_sink("uart", uart_logger_write, false);
+
     /* Initialize WebUI (requires WiFi to be connected) */
     ESP_LOGI(TAG, "Initializing WebUI...");
     webui_init();
@@ -236,70 +425,24 @@ void app_main(void) {
 
     /* Initialize decoder (creates its own stream consumer) */
     decoder_init();
+    if (CONFIG_GET_PRACTICE_OSC()) {
+        decoder_set_enabled(true);  /* Practice oscillator: show what is keyed */
+    }
 
     /* Initialize text keyer */
     text_keyer_config_t text_cfg = {
-        .paddle_abort = &g_paddle_active,
+        .paddle_touch = &g_paddle_touched,
     };
     text_keyer_init(&text_cfg);
     text_memory_init();
+    contest_serial_init();
+    abbrev_init();
 
-    ESP_LOGI(TAG, "Creating tasks...");
-
-    /* Create RT task on Core 0 (highest priority) */
-    xTaskCreatePinnedToCore(
-        rt_task,
-        "rt_task",
-        4096,
-        NULL,
-        configMAX_PRIORITIES - 1,
-        NULL,
-        0  /* Core 0 */
-    );
-
-    /* Create BG task on Core 1 */
-    xTaskCreatePinnedToCore(
-        bg_task,
-        "bg_task",
-        4096,
-        NULL,
-        tskIDLE_PRIORITY + 2,
-        NULL,
-        1  /* Core 1 */
-    );
-
-    /* Create USB log drain task on Core 1 */
-    xTaskCreatePinnedToCore(
-        usb_log_task,
-        "usb_log",
-        4096,
-        NULL,
-        tskIDLE_PRIORITY + 1,
-        NULL,
-        1  /* Core 1 */
-    );
-
-    /* Create UART log drain task on Core 1 (for boot logs, stopped after USB ready) */
-    xTaskCreatePinnedToCore(
-        uart_logger_task,
-        "uart_log",
-        2048,
-        NULL,
-        tskIDLE_PRIORITY + 1,
-        &s_uart_log_task_handle,
-        1  /* Core 1 */
-    );
-
-    /* Wait for USB CDC to be ready, then stop UART logger */
-    ESP_LOGI(TAG, "Waiting for USB CDC...");
-    while (!usb_cdc_connected(CDC_ITF_CONSOLE)) {
-        vTaskDelay(pdMS_TO_TICKS(100));
-    }
-    ESP_LOGI(TAG, "USB CDC connected, stopping UART logger");
-    if (s_uart_log_task_handle != NULL) {
-        vTaskDelete(s_uart_log_task_handle);
-        s_uart_log_task_handle = NULL;
-    }
+    /* Light sleep when idle (system.sleep_idle_min) */
+    power_init();
+
+    tasks_start(&task_plan);
+    tasks_handover_logs();
 
     ESP_LOGI(TAG, "keyer_c started successfully");
 }
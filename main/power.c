@@ -0,0 +1,181 @@
+/**
+ * @file power.c
+ * @brief Light sleep when idle implementation
+ */
+
+#define RT_LOG_TAG LOG_TAG_POWER
+
+#include "power.h"
+#include "freertos/FreeRTOS.h"
+#include "freertos/task.h"
+#include "esp_attr.h"
+#include "esp_timer.h"
+#include <stdatomic.h>
+
+#include "keyer_core.h"
+#include "idle_sleep.h"
+#include "rt_log.h"
+#include "config.h"
+#include "hal_gpio.h"
+#include "hal_audio.h"
+#include "hal_encoder.h"
+#include "hal_power.h"
+#include "usb_cdc.h"
+#include "webui.h"
+#include "cwnet_socket.h"
+#include "text_keyer.h"
+
+extern keying_stream_t g_keying_stream;
+
+static bool s_available = false;
+static idle_sleep_t s_idle;
+static size_t s_stream_pos = 0;
+static uint32_t s_http_requests = 0;
+static int64_t s_asleep_at_us = 0;
+
+/* Wakeup inputs armed and the awake lock released */
+static bool s_sleeping = false;
+
+/* RT task handshake: bg_task requests, the RT task parks and blocks */
+static atomic_bool s_park_request = ATOMIC_VAR_INIT(false);
+static atomic_bool s_parked = ATOMIC_VAR_INIT(false);
+static TaskHandle_t s_rt_task = NULL;
+
+/* bg_task, woken by the paddle ISR while asleep */
+static TaskHandle_t s_bg_task = NULL;
+
+/* ============================================================================
+ * Activity
+ * ============================================================================ */
+
+/**
+ * @brief Check for anything that keeps the keyer awake
+ */
+static bool has_activity(void) {
+    /* Keying: the stream only grows on a change (text keyer included) */
+    size_t pos = stream_write_position(&g_keying_stream);
+    bool active = pos != s_stream_pos;
+    s_stream_pos = pos;
+
+    /* Contacts (RT task parked: read here) */
+    active = active || !gpio_is_idle(hal_gpio_read_paddles());
+    active = active || hal_gpio_read_ptt_in();
+    active = active || hal_gpio_read_buttons() != 0;
+
+    /* Console and network: USB does not survive light sleep either */
+    uint32_t requests = webui_get_request_count();
+    active = active || requests != s_http_requests;
+    s_http_requests = requests;
+    active = active || usb_cdc_connected(CDC_ITF_CONSOLE);
+    active = active || webui_get_ws_client_count() > 0;
+    active = active || cwnet_socket_get_state() == CWNET_SOCK_READY;
+    active = active || text_keyer_get_state() != TEXT_KEYER_IDLE;
+    return active;
+}
+
+/* ============================================================================
+ * Sleep and Wakeup
+ * ============================================================================ */
+
+/* Paddle closure while armed: run power_tick now instead of at the next poll */
+static void IRAM_ATTR paddle_wake(void) {
+    BaseType_t woken = pdFALSE;
+    if (s_bg_task != NULL) {
+        vTaskNotifyGiveFromISR(s_bg_task, &woken);
+    }
+    portYIELD_FROM_ISR(woken);
+}
+
+static void enter_sleep(void) {
+    s_bg_task = xTaskGetCurrentTaskHandle();
+    hal_gpio_sleep_wakeup(true);
+    hal_encoder_sleep(true);
+    hal_power_allow_sleep(true);
+    s_sleeping = true;
+}
+
+static void wake_up(int64_t now_us) {
+    if (s_sleeping) {
+        hal_power_allow_sleep(false);
+        hal_encoder_sleep(false);
+        hal_gpio_sleep_wakeup(false);
+        s_sleeping = false;
+    }
+
+    /* The RT task checks the request after marking itself parked: it
+     * either sees it dropped or gets the notification */
+    atomic_store(&s_park_request, false);
+    if (atomic_load(&s_parked)) {
+        xTaskNotifyGive(s_rt_task);
+    }
+    RT_INFO(&g_bg_log_stream, now_us, "Awake after %lu s idle sleep",
+            (unsigned long)((now_us - s_asleep_at_us) / 1000000));
+}
+
+/* ============================================================================
+ * Public API
+ * ============================================================================ */
+
+void power_init(void) {
+    if (hal_gpio_get_config().touch_paddles) {
+        return;  /* Touch pads cannot wake the chip: never sleep */
+    }
+    s_available = hal_power_init();
+    idle_sleep_init(&s_idle, esp_timer_get_time());
+    if (s_available) {
+        hal_gpio_set_wake_callback(paddle_wake);
+    }
+}
+
+void power_tick(int64_t now_us) {
+    if (!s_available) {
+        return;
+    }
+
+    uint32_t timeout_s = (uint32_t)CONFIG_GET_SLEEP_IDLE_MIN() * 60U;
+    switch (idle_sleep_update(&s_idle, has_activity(), timeout_s, now_us)) {
+        case IDLE_SLEEP_ENTER:
+            s_asleep_at_us = now_us;
+            RT_INFO(&g_bg_log_stream, now_us, "Idle %lu min: light sleep",
+                    (unsigned long)CONFIG_GET_SLEEP_IDLE_MIN());
+            atomic_store(&s_park_request, true);
+            break;
+
+        case IDLE_SLEEP_WAKE:
+            wake_up(now_us);
+            break;
+
+        case IDLE_SLEEP_NONE:
+            break;
+    }
+
+    /* Sleep only once the RT task is parked (it waits for key-up) */
+    if (idle_sleep_is_asleep(&s_idle) && !s_sleeping && atomic_load(&s_parked)) {
+        enter_sleep();
+    }
+}
+
+bool power_is_asleep(void) {
+    return s_sleeping;
+}
+
+void power_sleep_wait(void) {
+    ulTaskNotifyTake(pdTRUE, pdMS_TO_TICKS(POWER_SLEEP_POLL_MS));
+}
+
+bool power_rt_park_requested(void) {
+    return atomic_load_explicit(&s_park_request, memory_order_relaxed);
+}
+
+void power_rt_park(void) {
+    hal_audio_stop();
+
+    s_rt_task = xTaskGetCurrentTaskHandle();
+    atomic_store(&s_parked, true);
+    while (atomic_load(&s_park_request)) {
+        ulTaskNotifyTake(pdTRUE, portMAX_DELAY);
+    }
+    atomic_store(&s_parked, false);
+
+    hal_audio_start();
+}
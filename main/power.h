@@ -0,0 +1,75 @@
+/**
+ * @file power.h
+ * @brief Light sleep when idle (system.sleep_idle_min)
+ *
+ * After the configured minutes with no keying, paddle or button
+ * contact, USB console, WebUI request or WebSocket client, or CWNet
+ * connection, bg_task asks the RT task to park. Once it is parked (TX,
+ * PTT and sequencer off, I2S stopped), the wakeup inputs are armed and
+ * the chip light sleeps whenever every task is blocked. bg_task keeps
+ * polling, more slowly, and wakes everything on the first activity:
+ *
+ * 1. Take the awake lock back, disarm the inputs
+ * 2. Unpark the RT task: I2S restarts, the 1ms tick starts over
+ *
+ * A paddle closure also interrupts bg_task's wait (paddle ISR notify),
+ * so the RT task is back within a millisecond or two and the element
+ * starts while the paddle is still closed. Straight key, PTT input and
+ * buttons are only polled: hold them for at least POWER_SLEEP_POLL_MS.
+ */
+
+#ifndef KEYER_POWER_H
+#define KEYER_POWER_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** bg_task period while asleep */
+#define POWER_SLEEP_POLL_MS 50
+
+/**
+ * @brief Set up light sleep (app_main, after GPIO and audio init)
+ */
+void power_init(void);
+
+/**
+ * @brief Track activity, enter and leave sleep (bg_task, every tick)
+ *
+ * @param now_us Current time
+ */
+void power_tick(int64_t now_us);
+
+/**
+ * @brief Check if asleep (RT task parked)
+ */
+bool power_is_asleep(void);
+
+/**
+ * @brief Wait between bg_task ticks while asleep (bg_task)
+ *
+ * Returns after POWER_SLEEP_POLL_MS, or at once on a paddle closure.
+ */
+void power_sleep_wait(void);
+
+/**
+ * @brief Check if the RT task should park (RT task, every tick)
+ */
+bool power_rt_park_requested(void);
+
+/**
+ * @brief Park the RT task until wakeup (RT task)
+ *
+ * Stops I2S, blocks, then restarts I2S. Call only with TX, PTT and the
+ * sequencer off.
+ */
+void power_rt_park(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_POWER_H */
@@ -12,6 +12,8 @@
  * - No blocking calls, no heap allocation
  */
 
+#define RT_LOG_TAG LOG_TAG_RT
+
 #include "freertos/FreeRTOS.h"
 #include "freertos/task.h"
 #include "esp_timer.h"
@@ -20,9 +22,13 @@
 #include "iambic.h"
 #include "sidetone.h"
 #include "ptt.h"
+#include "sequencer.h"
+#include "straight_key.h"
+#include "power.h"
 #include "rt_log.h"
 #include "hal_gpio.h"
 #include "hal_audio.h"
+#include "hal_buzzer.h"
 #include "config.h"
 #include "text_keyer.h"
 
@@ -35,9 +41,14 @@
 /* External globals */
 extern keying_stream_t g_keying_stream;
 extern fault_state_t g_fault_state;
+extern fault_indicator_t g_fault_indicator;
+extern rt_stats_t g_rt_stats;
+
+/* Paddle touched, latched until the text keyer (Core 1) consumes it */
+atomic_bool g_paddle_touched = ATOMIC_VAR_INIT(false);
 
-/* Paddle state for text keyer abort (Core 1 reads this) */
-atomic_bool g_paddle_active = ATOMIC_VAR_INIT(false);
+/* PTT input held (debounced), for the mic routing on Core 1 */
+atomic_bool g_ptt_in_held = ATOMIC_VAR_INIT(false);
 
 /* ============================================================================
  * Diagnostic State Tracking
@@ -84,17 +95,17 @@ static void rt_diag_log(rt_diag_state_t *diag,
         diag->expected_duration_us = get_expected_duration(iambic);
 
         if (iambic->state == IAMBIC_STATE_SEND_DIT) {
-            RT_DIAG_INFO(&g_rt_log_stream, now_us, "KEY DIT down");
+            RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_INFO, now_us, KEY_DIT_DOWN);
         } else if (iambic->state == IAMBIC_STATE_SEND_DAH) {
-            RT_DIAG_INFO(&g_rt_log_stream, now_us, "KEY DAH down");
+            RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_INFO, now_us, KEY_DAH_DOWN);
         }
     }
 
     /* Key up transition */
     if (!key_down && diag->prev_key_down) {
         int64_t actual_duration = now_us - diag->element_start_us;
-        RT_DIAG_INFO(&g_rt_log_stream, now_us, "KEY up %lldus",
-                     (long long)actual_duration);
+        RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_INFO, now_us, KEY_UP,
+                    (uint32_t)actual_duration);
 
         /* Check for timing drift */
         if (diag->expected_duration_us > 0) {
@@ -103,21 +114,20 @@ static void rt_diag_log(rt_diag_state_t *diag,
             int64_t drift_pct = (diff * 100) / diag->expected_duration_us;
 
             if (drift_pct > DIAG_DRIFT_THRESHOLD_PCT) {
-                RT_DIAG_WARN(&g_rt_log_stream, now_us,
-                             "DRIFT %lld%% (exp=%lld act=%lld)",
-                             (long long)drift_pct,
-                             (long long)diag->expected_duration_us,
-                             (long long)actual_duration);
+                RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_WARN, now_us, DRIFT,
+                            (uint32_t)drift_pct,
+                            (uint32_t)diag->expected_duration_us,
+                            (uint32_t)actual_duration);
             }
         }
     }
 
     /* Memory armed (detect via iambic state) */
     if (iambic->dit_memory && diag->prev_iambic_state != iambic->state) {
-        RT_DIAG_DEBUG(&g_rt_log_stream, now_us, "MEM DIT");
+        RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_DEBUG, now_us, MEM_DIT);
     }
     if (iambic->dah_memory && diag->prev_iambic_state != iambic->state) {
-        RT_DIAG_DEBUG(&g_rt_log_stream, now_us, "MEM DAH");
+        RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_DEBUG, now_us, MEM_DAH);
     }
 
     /* Sidetone fade state transitions */
@@ -139,28 +149,139 @@ static void rt_diag_log(rt_diag_state_t *diag,
     diag->prev_iambic_state = iambic->state;
 }
 
+/* ============================================================================
+ * Element Pulses
+ *
+ * With the RMT key output a paddle element is handed to the HAL as one
+ * timed pulse, so its length does not depend on when the 1ms loop runs.
+ * Elements are remembered here until the key down they belong to comes
+ * out of the stream and the sequencer delay.
+ * ============================================================================ */
+
+/** Elements remembered (the sequencer lead covers at most a few) */
+#define RT_ELEMENTS 4
+
+/** Slack between element start and its delayed key down */
+#define RT_ELEMENT_MATCH_US 3000
+
+typedef struct {
+    int64_t start_us[RT_ELEMENTS];
+    uint32_t duration_us[RT_ELEMENTS];  /**< 0 = free or used */
+    uint8_t next;
+    int64_t last_start_us;
+} rt_elements_t;
+
+/**
+ * @brief Remember the element the iambic FSM just started
+ */
+static void rt_elements_record(rt_elements_t *e, const iambic_processor_t *iambic) {
+    if ((iambic->state != IAMBIC_STATE_SEND_DIT && iambic->state != IAMBIC_STATE_SEND_DAH) ||
+        iambic->element_start_us == e->last_start_us) {
+        return;
+    }
+    e->last_start_us = iambic->element_start_us;
+    e->start_us[e->next] = iambic->element_start_us;
+    e->duration_us[e->next] = (uint32_t)iambic->element_duration_us;
+    e->next = (uint8_t)((e->next + 1U) % RT_ELEMENTS);
+}
+
+/**
+ * @brief Take the element that started at key_at_us
+ *
+ * @return Element duration, 0 if none matches (text keyer, straight key)
+ */
+static uint32_t rt_elements_take(rt_elements_t *e, int64_t key_at_us) {
+    for (uint8_t i = 0; i < RT_ELEMENTS; i++) {
+        int64_t diff = e->start_us[i] - key_at_us;
+        if (e->duration_us[i] != 0 &&
+            diff <= RT_ELEMENT_MATCH_US && diff >= -RT_ELEMENT_MATCH_US) {
+            uint32_t duration = e->duration_us[i];
+            e->duration_us[i] = 0;
+            return duration;
+        }
+    }
+    return 0;
+}
+
+/* ============================================================================
+ * Config Snapshot
+ * ============================================================================ */
+
+/**
+ * @brief Build the iambic FSM config from a plain snapshot
+ */
+static void rt_iambic_config(const config_snapshot_t *snap, iambic_config_t *out) {
+    out->wpm = snap->keyer.wpm;
+    out->mode = (iambic_mode_t)snap->keyer.iambic_mode;
+    out->memory_mode = (memory_mode_t)snap->keyer.memory_mode;
+    out->squeeze_mode = (squeeze_mode_t)snap->keyer.squeeze_mode;
+    out->mem_window_start_pct = snap->keyer.mem_window_start_pct;
+    out->mem_window_end_pct = snap->keyer.mem_window_end_pct;
+}
+
+/** Parameters that feed the iambic FSM */
+static const config_param_id_t RT_IAMBIC_IDS[] = {
+    CONFIG_ID_KEYER_WPM,
+    CONFIG_ID_KEYER_IAMBIC_MODE,
+    CONFIG_ID_KEYER_MEMORY_MODE,
+    CONFIG_ID_KEYER_SQUEEZE_MODE,
+    CONFIG_ID_KEYER_MEM_WINDOW_START_PCT,
+    CONFIG_ID_KEYER_MEM_WINDOW_END_PCT,
+};
+
+/**
+ * @brief Sequencer step delays from a plain snapshot
+ */
+static void rt_sequencer_delays(const config_snapshot_t *snap, uint32_t out[SEQUENCER_STEPS - 1]) {
+    out[0] = snap->timing.seq_ant_amp_ms;
+    out[1] = snap->timing.seq_amp_ptt_ms;
+    out[2] = snap->timing.seq_ptt_key_ms;
+}
+
+/** Parameters that feed the TX sequencer */
+static const config_param_id_t RT_SEQUENCER_IDS[] = {
+    CONFIG_ID_TIMING_SEQ_ANT_AMP_MS,
+    CONFIG_ID_TIMING_SEQ_AMP_PTT_MS,
+    CONFIG_ID_TIMING_SEQ_PTT_KEY_MS,
+};
+
+/**
+ * @brief Check if any parameter in a set changed after a generation
+ */
+static bool rt_config_changed(const config_param_id_t *ids, size_t count, uint16_t since_gen) {
+    for (size_t i = 0; i < count; i++) {
+        if (config_changed_since(&g_config, ids[i], since_gen)) {
+            return true;
+        }
+    }
+    return false;
+}
+
 void rt_task(void *arg) {
     (void)arg;
 
-    /* Initialize iambic processor from g_config */
+    /* Initial config snapshot: start one generation behind so the first
+     * call copies (nothing else is writing yet at boot) */
+    uint16_t config_gen = (uint16_t)(atomic_load_explicit(&g_config.generation,
+                                                          memory_order_acquire) - 1U);
+    config_snapshot_t cfg;
+    config_snapshot_if_changed(&g_config, &cfg, &config_gen);
+
+    /* Initialize iambic processor from snapshot */
     iambic_config_t iambic_cfg;
-    iambic_cfg.wpm = (uint32_t)CONFIG_GET_WPM();
-    iambic_cfg.mode = (iambic_mode_t)CONFIG_GET_IAMBIC_MODE();
-    iambic_cfg.memory_mode = (memory_mode_t)CONFIG_GET_MEMORY_MODE();
-    iambic_cfg.squeeze_mode = (squeeze_mode_t)CONFIG_GET_SQUEEZE_MODE();
-    iambic_cfg.mem_window_start_pct = CONFIG_GET_MEM_WINDOW_START_PCT();
-    iambic_cfg.mem_window_end_pct = CONFIG_GET_MEM_WINDOW_END_PCT();
+    rt_iambic_config(&cfg, &iambic_cfg);
     iambic_processor_t iambic;
     iambic_init(&iambic, &iambic_cfg);
 
     /* Initialize hard RT consumer */
     hard_rt_consumer_t consumer;
     hard_rt_consumer_init(&consumer, &g_keying_stream, &g_fault_state, 2);
+    hard_rt_consumer_track(&consumer, consumer_lag_register("audio+tx"));
 
     /* Initialize sidetone generator from config */
     sidetone_gen_t sidetone;
-    uint32_t sidetone_freq = CONFIG_GET_SIDETONE_FREQ_HZ();
-    uint32_t fade_ms = CONFIG_GET_FADE_DURATION_MS();
+    uint32_t sidetone_freq = cfg.audio.sidetone_freq_hz;
+    uint32_t fade_ms = cfg.audio.fade_duration_ms;
     if (fade_ms > UINT16_MAX / SAMPLES_PER_TICK) {
         fade_ms = UINT16_MAX / SAMPLES_PER_TICK;
     }
@@ -170,7 +291,31 @@ void rt_task(void *arg) {
 
     /* Initialize PTT controller from config */
     ptt_controller_t ptt;
-    ptt_init(&ptt, CONFIG_GET_PTT_TAIL_MS());
+    ptt_init(&ptt, cfg.timing.ptt_tail_ms);
+
+    /* TX sequencer: relays, amp and radio PTT follow PTT in order */
+    sequencer_t seq;
+    uint32_t seq_delays[SEQUENCER_STEPS - 1];
+    rt_sequencer_delays(&cfg, seq_delays);
+    sequencer_init(&seq, seq_delays);
+
+    /* Key output as last decided on the consumer side */
+    bool tx_key = false;
+    bool fault_stopped = false;  /* Outputs already stopped for the latched fault */
+    rt_elements_t elements = {0};
+
+    /* Straight key / external keyer input (GPIO_KEY_BIT) */
+    straight_key_t skey;
+    straight_key_init(&skey, (uint32_t)cfg.timing.key_in_debounce_ms * 1000U);
+
+    /* PTT input (footswitch, mic PTT): same debounce as the key */
+    straight_key_t ptt_in;
+    straight_key_init(&ptt_in, skey.debounce_us);
+
+    /* Radio the outputs are routed to (hal_gpio starts on radio 1) */
+    uint8_t radio = 1;
+    /* Last keyer.radio hal_gpio refused (0 = none), so it warns once */
+    uint8_t radio_refused = 0;
 
     TickType_t last_wake = xTaskGetTickCount();
     const TickType_t period = pdMS_TO_TICKS(1);  /* 1ms tick */
@@ -179,92 +324,162 @@ void rt_task(void *arg) {
     int64_t now_us = esp_timer_get_time();
     RT_INFO(&g_rt_log_stream, now_us, "RT task started");
 
-    /* Track config generation for hot-reload */
-    uint16_t last_config_gen = atomic_load_explicit(&g_config.generation, memory_order_acquire);
+    /* Iambic reload waits for IDLE so no element is cut short */
+    bool iambic_reload_pending = false;
+
+    /* PTT tail reload waits for the flagged sample on the consumer side */
+    bool ptt_reload_pending = false;
+
+    /* Sequencer delay reload waits for the sequencer to go idle */
+    bool seq_reload_pending = false;
+
+    /* Loop start of previous iteration (for period statistics) */
+    int64_t prev_loop_us = 0;
+
+    /* Last text character marked in the stream */
+    uint32_t text_char_tag = text_keyer_char_tag();
 
     for (;;) {
         now_us = esp_timer_get_time();
 
-        /* Check for config changes and hot-reload during IDLE */
-        uint16_t current_gen = atomic_load_explicit(&g_config.generation, memory_order_acquire);
-        if (current_gen != last_config_gen && iambic.state == IAMBIC_STATE_IDLE) {
-            /* Reload iambic config from g_config (safe during IDLE only) */
-            iambic_cfg.wpm = (uint32_t)CONFIG_GET_WPM();
-            iambic_cfg.mode = (iambic_mode_t)CONFIG_GET_IAMBIC_MODE();
-            iambic_cfg.memory_mode = (memory_mode_t)CONFIG_GET_MEMORY_MODE();
-            iambic_cfg.squeeze_mode = (squeeze_mode_t)CONFIG_GET_SQUEEZE_MODE();
-            iambic_cfg.mem_window_start_pct = CONFIG_GET_MEM_WINDOW_START_PCT();
-            iambic_cfg.mem_window_end_pct = CONFIG_GET_MEM_WINDOW_END_PCT();
-
-            /* Verify generation didn't change mid-read (optimistic read) */
-            uint16_t gen_after = atomic_load_explicit(&g_config.generation, memory_order_acquire);
-            if (gen_after != current_gen) {
-                continue;  /* Torn read - retry next tick */
-            }
-
-            iambic_set_config(&iambic, &iambic_cfg);
+        /* 1. Poll GPIO paddles: one input snapshot at the top of the tick */
+        gpio_state_t gpio = hal_gpio_read_paddles();
 
-            /* Reload sidetone frequency */
-            uint32_t new_freq = CONFIG_GET_SIDETONE_FREQ_HZ();
-            if (new_freq != sidetone_freq) {
-                sidetone_set_frequency(&sidetone, new_freq);
-                sidetone_freq = new_freq;
+        /* Record loop period */
+        if (prev_loop_us != 0) {
+            rt_stats_record_period(&g_rt_stats, (uint32_t)(now_us - prev_loop_us));
+        }
+        prev_loop_us = now_us;
+
+        /* Pick up a new config generation (console/WebUI bumped it) */
+        uint16_t prev_gen = config_gen;
+        if (config_snapshot_if_changed(&g_config, &cfg, &config_gen)) {
+            /* Only reload what actually changed */
+            if (rt_config_changed(RT_IAMBIC_IDS,
+                                  sizeof(RT_IAMBIC_IDS) / sizeof(RT_IAMBIC_IDS[0]),
+                                  prev_gen)) {
+                iambic_reload_pending = true;
+            }
+            if (config_changed_since(&g_config, CONFIG_ID_TIMING_PTT_TAIL_MS, prev_gen)) {
+                ptt_reload_pending = true;
+            }
+            if (rt_config_changed(RT_SEQUENCER_IDS,
+                                  sizeof(RT_SEQUENCER_IDS) / sizeof(RT_SEQUENCER_IDS[0]),
+                                  prev_gen)) {
+                seq_reload_pending = true;
             }
+        }
 
-            /* Reload PTT tail */
-            ptt_set_tail(&ptt, CONFIG_GET_PTT_TAIL_MS());
+        /* Producer side: iambic reloads once the FSM is IDLE */
+        if (iambic_reload_pending && iambic.state == IAMBIC_STATE_IDLE) {
+            rt_iambic_config(&cfg, &iambic_cfg);
+            iambic_set_config(&iambic, &iambic_cfg);
+            iambic_reload_pending = false;
+        }
 
-            RT_INFO(&g_rt_log_stream, now_us, "Config updated: WPM=%lu freq=%lu",
-                    (unsigned long)iambic_cfg.wpm, (unsigned long)sidetone_freq);
-            last_config_gen = current_gen;
+        /* 1b. Override with ISR-latched presses: a closure shorter than a
+         *     tick, or one during a slipped tick, is not missed */
+        hal_gpio_presses_t presses;
+        if (hal_gpio_take_presses(now_us, &presses)) {
+            gpio.bits |= presses.paddles.bits;
+            rt_stats_record_press_age(&g_rt_stats, presses.age_us);
         }
 
-        /* 1. Poll GPIO paddles */
-        gpio_state_t gpio = hal_gpio_read_paddles();
+        /* 1c. Straight key: debounced here, then keys in parallel with the paddles */
+        skey.debounce_us = (uint32_t)cfg.timing.key_in_debounce_ms * 1000U;
+        bool skey_down = straight_key_update(&skey, gpio_key(gpio), now_us);
+        gpio.bits = (uint8_t)((gpio.bits & ~GPIO_KEY_BIT) | (skey_down ? GPIO_KEY_BIT : 0));
 
-        /* 1b. Override with ISR-detected presses (low latency path) */
-        if (hal_gpio_consume_dit_press()) {
-            gpio.bits |= GPIO_DIT_BIT;
-        }
-        if (hal_gpio_consume_dah_press()) {
-            gpio.bits |= GPIO_DAH_BIT;
-        }
+        /* 1d. PTT input: a level in the stream (FLAG_PTT_IN), holds PTT below */
+        ptt_in.debounce_us = skey.debounce_us;
+        bool ptt_in_held = straight_key_update(&ptt_in, hal_gpio_read_ptt_in(), now_us);
+        atomic_store_explicit(&g_ptt_in_held, ptt_in_held, memory_order_relaxed);
 
-        /* Update paddle active flag for text keyer abort (Core 1) */
+        /* Latch paddle touches: even a 1ms closure stops a message (Core 1) */
         bool paddle_active = !gpio_is_idle(gpio);
-        atomic_store_explicit(&g_paddle_active, paddle_active, memory_order_release);
+        if (paddle_active) {
+            atomic_store_explicit(&g_paddle_touched, true, memory_order_release);
+        }
 
         /* 2. Tick iambic FSM */
         stream_sample_t sample = iambic_tick(&iambic, now_us, gpio);
+        rt_elements_record(&elements, &iambic);
+        if (skey_down) {
+            sample.local_key = 1;  /* OR-ed with the iambic output */
+        }
+        if (ptt_in_held) {
+            sample.flags |= FLAG_PTT_IN;
+        }
 
-        /* 2b. Override with text keyer state if active (mutually exclusive with paddle) */
-        if (text_keyer_is_key_down()) {
+        /* 2b. Text keyer keys only while the paddles are free. A closure takes
+         *     over at once; the text keyer stops on its next tick. */
+        bool text_keyed = text_keyer_is_key_down() && !paddle_active &&
+                          iambic.state == IAMBIC_STATE_IDLE;
+        if (text_keyed) {
             sample.local_key = 1;
         }
 
+        /* 2c. Stamp config generation (stream sets FLAG_CONFIG_CHANGE on change) */
+        sample.config_gen = config_gen;
+
         /* 3. Push to stream */
+        int64_t t_push = esp_timer_get_time();
         if (!stream_push(&g_keying_stream, sample)) {
             fault_set(&g_fault_state, FAULT_PRODUCER_OVERRUN, 0);
         }
+        int64_t t_consume = esp_timer_get_time();
+        rt_stats_record_push(&g_rt_stats, (uint32_t)(t_consume - t_push));
+
+        /* 3b. Sent-text marker on the first key-down of each text character */
+        if (text_keyed) {
+            uint32_t tag = text_keyer_char_tag();
+            if (tag != text_char_tag) {
+                text_char_tag = tag;
+                stream_mark_text(&g_keying_stream, (char)(tag & 0xFFU));
+            }
+        }
 
         /* 4. Consume for audio/TX (co-located, no context switch) */
         stream_sample_t out;
         hard_rt_result_t result = hard_rt_consumer_tick(&consumer, &out);
+        rt_stats_record_consume(&g_rt_stats, (uint32_t)(esp_timer_get_time() - t_consume));
 
         /* Handle consumer result */
         switch (result) {
             case HARD_RT_OK:
-                /* Update TX output */
-                hal_gpio_set_tx(out.local_key != 0);
+                fault_stopped = false;
+                /* Consumer side: reload at the stream position of the change */
+                if (sample_has_config_change(&out)) {
+                    if (cfg.audio.sidetone_freq_hz != sidetone_freq) {
+                        sidetone_freq = cfg.audio.sidetone_freq_hz;
+                        sidetone_set_frequency(&sidetone, sidetone_freq);
+                    }
+                    if (ptt_reload_pending) {
+                        ptt_set_tail(&ptt, cfg.timing.ptt_tail_ms);
+                        ptt_reload_pending = false;
+                    }
+                    RT_INFO(&g_rt_log_stream, now_us, "Config gen %u: WPM=%lu freq=%lu",
+                            (unsigned)out.config_gen, (unsigned long)cfg.keyer.wpm,
+                            (unsigned long)sidetone_freq);
+                }
+
+                /* TX output, through the sequencer (inhibited while the trainer runs) */
+                tx_key = out.local_key != 0 && !text_keyer_tx_inhibited();
                 break;
 
             case HARD_RT_FAULT:
-                /* FAULT - stop TX/audio immediately */
-                hal_gpio_set_tx(false);
+                /* FAULT - stop TX/audio immediately; the RMT channel is
+                 * reset once, on entry, not on every tick of the fault */
+                tx_key = false;
+                sequencer_key_off(&seq);
                 sidetone_reset(&sidetone);
                 ptt_force_off(&ptt);
-                RT_ERROR(&g_rt_log_stream, now_us, "FAULT: %s",
-                         fault_code_str(fault_get_code(&g_fault_state)));
+                if (!fault_stopped) {
+                    fault_stopped = true;
+                    hal_gpio_tx_stop();
+                    RT_ERROR(&g_rt_log_stream, now_us, "FAULT: %s",
+                             fault_code_str(fault_get_code(&g_fault_state)));
+                }
                 break;
 
             case HARD_RT_NO_DATA:
@@ -274,10 +489,11 @@ void rt_task(void *arg) {
 
         /* Generate and write audio ALWAYS (even when stream empty) to maintain I2S sync */
         bool key_down = (out.local_key != 0);
+        bool tone_on = key_down || text_keyer_is_tone_down();  /* Trainer: sidetone only */
         int16_t audio_samples[SAMPLES_PER_TICK];
-        uint8_t volume = CONFIG_GET_SIDETONE_VOLUME();  /* 1-100 */
+        uint8_t volume = cfg.audio.sidetone_volume;  /* 1-100 */
         for (int i = 0; i < SAMPLES_PER_TICK; i++) {
-            int32_t sample = sidetone_next_sample(&sidetone, key_down);
+            int32_t sample = sidetone_next_sample(&sidetone, tone_on);
             audio_samples[i] = (int16_t)((sample * volume) / 100);
         }
 
@@ -292,16 +508,58 @@ void rt_task(void *arg) {
         prev_key = key_down;
 
         /* ALWAYS write to I2S (even silence) to keep codec/I2S synchronized */
-        hal_audio_write(audio_samples, SAMPLES_PER_TICK);
+        if (hal_audio_write(audio_samples, SAMPLES_PER_TICK) < SAMPLES_PER_TICK &&
+            !fault_has(&g_fault_state, FAULT_AUDIO_UNDERRUN)) {
+            /* Sidetone only: a warning, keying goes on */
+            fault_set(&g_fault_state, FAULT_AUDIO_UNDERRUN, 0);
+        }
+
+        /* Buzzer follows the sidetone (no fade: the piezo clicks anyway),
+         * or chirps for a new fault */
+        hal_buzzer_set((tone_on && cfg.audio.buzzer) ||
+                       fault_indicator_chirping(&g_fault_indicator));
 
-        /* Update PTT on key down */
-        if (key_down) {
+        /* Update PTT on key down, and keep it up through a message pause */
+        if ((key_down && !text_keyer_tx_inhibited()) ||
+            (text_keyer_holds_ptt() && ptt_is_on(&ptt))) {
             ptt_audio_sample(&ptt, (uint64_t)now_us);
         }
 
+        /* PTT input holds PTT on, except on a fault or while TX is inhibited */
+        ptt_set_manual(&ptt, ptt_in_held && result != HARD_RT_FAULT &&
+                             !text_keyer_tx_inhibited());
+
         /* 5. Update PTT */
         ptt_tick(&ptt, (uint64_t)now_us);
 
+        /* 5b. Sequencer: antenna relay, amp, radio PTT, then the delayed key */
+        if (seq_reload_pending) {
+            rt_sequencer_delays(&cfg, seq_delays);
+            seq_reload_pending = !sequencer_set_delays(&seq, seq_delays);
+        }
+        bool tx_out = sequencer_tick(&seq, ptt_is_on(&ptt), tx_key, now_us);
+        uint32_t pulse_us = (tx_out && !hal_gpio_get_tx())
+            ? rt_elements_take(&elements, now_us - (int64_t)seq.lead_us) : 0;
+        if (pulse_us == 0 || !hal_gpio_tx_pulse(pulse_us)) {
+            hal_gpio_set_tx(tx_out);  /* Key up after a pulse waits for its end */
+        }
+        hal_gpio_set_antenna(sequencer_is_on(&seq, SEQUENCER_ANTENNA));
+        hal_gpio_set_amp(sequencer_is_on(&seq, SEQUENCER_AMP));
+        hal_gpio_set_ptt(sequencer_is_on(&seq, SEQUENCER_RADIO));
+
+        /* 5c. Radio switch waits for the sequence to end (keyer.radio) */
+        if (cfg.keyer.radio != radio && cfg.keyer.radio != radio_refused &&
+            !sequencer_is_active(&seq)) {
+            if (hal_gpio_select_radio((uint8_t)(cfg.keyer.radio - 1U))) {
+                radio = cfg.keyer.radio;
+                radio_refused = 0;
+                RT_LOG_BIN(&g_rt_log_stream, LOG_LEVEL_INFO, now_us, RADIO_SELECT, radio);
+            } else {
+                radio_refused = cfg.keyer.radio;
+                RT_LOG_BIN(&g_rt_log_stream, LOG_LEVEL_WARN, now_us, RADIO_NO_TX, radio_refused);
+            }
+        }
+
         /* 6. Diagnostic logging (zero overhead if disabled) */
         rt_diag_log(&s_diag, &iambic, &sidetone,
                     out.local_key != 0, now_us);
@@ -309,6 +567,19 @@ void rt_task(void *arg) {
         /* 7. ISR blanking timer management (must be in task context) */
         hal_gpio_isr_tick(now_us);
 
+        /* Loop body duration (excludes the wait below) */
+        rt_stats_record_work(&g_rt_stats, (uint32_t)(esp_timer_get_time() - now_us));
+
+        /* 8. Idle sleep: park once nothing is keyed, until bg_task wakes us */
+        if (power_rt_park_requested() && !tx_key && !ptt_is_on(&ptt) &&
+            !sequencer_is_active(&seq) && iambic.state == IAMBIC_STATE_IDLE) {
+            power_rt_park();
+            sidetone_reset(&sidetone);
+            last_wake = xTaskGetTickCount();
+            prev_loop_us = 0;  /* The parked time is no loop period */
+            continue;
+        }
+
         /* Wait for next tick */
         vTaskDelayUntil(&last_wake, period);
     }
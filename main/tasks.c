@@ -0,0 +1,74 @@
+/**
+ * @file tasks.c
+ * @brief Task spawning and startup order implementation
+ */
+
+#include "tasks.h"
+#include "esp_log.h"
+#include "rt_log.h"
+#include "log_drain.h"
+#include "console_output.h"
+#include "usb_cdc.h"
+#include "vpn.h"
+
+static const char *TAG = "tasks";
+
+/* Task functions (rt_task.c, bg_task.c, io_task.c) */
+extern void rt_task(void *arg);
+extern void bg_task(void *arg);
+extern void io_task(void *arg);
+
+/* ============================================================================
+ * Spawning
+ * ============================================================================ */
+
+static TaskHandle_t spawn(TaskFunction_t fn, const char *name, uint32_t stack,
+                          UBaseType_t prio, BaseType_t core) {
+    TaskHandle_t handle = NULL;
+    if (xTaskCreatePinnedToCore(fn, name, stack, NULL, prio, &handle, core) != pdPASS) {
+        ESP_LOGE(TAG, "Failed to create %s (stack %lu)", name, (unsigned long)stack);
+        return NULL;
+    }
+    return handle;
+}
+
+TaskHandle_t tasks_spawn_rt(TaskFunction_t fn, const char *name, uint32_t stack, UBaseType_t prio) {
+    return spawn(fn, name, stack, prio, 0);
+}
+
+TaskHandle_t tasks_spawn_bg(TaskFunction_t fn, const char *name, uint32_t stack, UBaseType_t prio) {
+    return spawn(fn, name, stack, prio, 1);
+}
+
+/* ============================================================================
+ * Startup
+ * ============================================================================ */
+
+void tasks_start(const tasks_plan_t *plan) {
+    ESP_LOGI(TAG, "Creating tasks...");
+
+    /* Keying first: paddles work as soon as anything runs */
+    tasks_spawn_rt(rt_task, "rt_task", 4096, TASKS_PRIO_RT);
+    tasks_spawn_bg(bg_task, "bg_task", 4096, TASKS_PRIO_BG);
+    tasks_spawn_bg(io_task, "io_task", 4096, TASKS_PRIO_IO);
+
+    /* Log drain (UART sink for boot logs, detached after USB ready) */
+    tasks_spawn_bg(log_drain_task, "log_drain", 4096, TASKS_PRIO_LOG);
+
+    /* Console output posted by bg_task (live decode, copy practice) */
+    tasks_spawn_bg(console_output_task, "console_out", 3072, TASKS_PRIO_LOG);
+
+    /* Network last: nothing local waits for it */
+    if (plan != NULL && plan->vpn) {
+        vpn_app_start();  /* Non-blocking, spawns task on Core 1 */
+    }
+}
+
+void tasks_handover_logs(void) {
+    ESP_LOGI(TAG, "Waiting for USB CDC...");
+    while (!usb_cdc_connected(CDC_ITF_CONSOLE)) {
+        vTaskDelay(pdMS_TO_TICKS(100));
+    }
+    ESP_LOGI(TAG, "USB CDC connected, detaching UART log sink");
+    uart_logger_detach();
+}
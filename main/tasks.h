@@ -0,0 +1,90 @@
+/**
+ * @file tasks.h
+ * @brief Task spawning and startup order
+ *
+ * Every firmware task is created through tasks_spawn_rt() (Core 0) or
+ * tasks_spawn_bg() (Core 1), so core pinning and priorities are set in
+ * one place. tasks_start() launches them in order once app_main has
+ * initialized all components:
+ *
+ * 1. rt_task: the 1ms keying loop, including sidetone audio (Core 0)
+ * 2. bg_task: console polling, decoder, LEDs, stats (Core 1)
+ *    io_task: OLED display, config autosave, SD recordings: anything that
+ *    waits on a bus or on flash (Core 1)
+ * 3. log_drain: log streams to the UART, USB, SD and crash log sinks (Core 1)
+ *    console_out: console output posted by bg_task (Core 1)
+ * 4. Network: VPN tunnel (WiFi connects on its own from wifi_app_start())
+ *
+ * Only the RT loop runs on Core 0.
+ */
+
+#ifndef KEYER_TASKS_H
+#define KEYER_TASKS_H
+
+#include <stdbool.h>
+#include <stdint.h>
+#include "freertos/FreeRTOS.h"
+#include "freertos/task.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/** RT loop: above everything else on Core 0 */
+#define TASKS_PRIO_RT       (configMAX_PRIORITIES - 1)
+
+/** Background loop */
+#define TASKS_PRIO_BG       (tskIDLE_PRIORITY + 2)
+
+/** Log drains (behind bg_task) */
+#define TASKS_PRIO_LOG      (tskIDLE_PRIORITY + 1)
+
+/** Slow peripheral I/O (behind bg_task) */
+#define TASKS_PRIO_IO       (tskIDLE_PRIORITY + 1)
+
+/**
+ * @brief What tasks_start() launches besides the fixed tasks
+ */
+typedef struct {
+    bool vpn;               /**< Start the VPN task (vpn_app_init() succeeded) */
+} tasks_plan_t;
+
+/**
+ * @brief Create a task on Core 0 (RT path)
+ *
+ * @param fn Task function (argument NULL)
+ * @param name Task name
+ * @param stack Stack size in bytes
+ * @param prio Priority
+ * @return Task handle, NULL if it could not be created (logged)
+ */
+TaskHandle_t tasks_spawn_rt(TaskFunction_t fn, const char *name, uint32_t stack, UBaseType_t prio);
+
+/**
+ * @brief Create a task on Core 1 (best effort)
+ *
+ * Same parameters as tasks_spawn_rt().
+ */
+TaskHandle_t tasks_spawn_bg(TaskFunction_t fn, const char *name, uint32_t stack, UBaseType_t prio);
+
+/**
+ * @brief Launch the firmware tasks in startup order
+ *
+ * Call once from app_main, after every component is initialized.
+ *
+ * @param plan Optional tasks
+ */
+void tasks_start(const tasks_plan_t *plan);
+
+/**
+ * @brief Wait for USB CDC, then detach the boot UART log sink
+ *
+ * Logs go to USB from then on. Blocks the caller (app_main).
+ */
+void tasks_handover_logs(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* KEYER_TASKS_H */
@@ -16,6 +16,7 @@ Example:
 """
 
 import yaml
+import re
 import sys
 from pathlib import Path
 from typing import Dict, List, Any
@@ -34,6 +35,7 @@ def parse_v2_schema(schema: Dict) -> tuple:
             'label': family_data.get('label', {'en': family_name}),
             'description': family_data.get('description', {'en': ''}),
             'aliases': family_data.get('aliases', []),
+            'enabled_by': family_data.get('enabled_by'),
         }
         families.append(family_meta)
 
@@ -69,6 +71,7 @@ def generate_config_schema_json(params, families, output_dir: Path):
     import json
 
     schema = {"parameters": []}
+    gates = {f['name']: f['enabled_by'] for f in families or [] if f.get('enabled_by')}
 
     for param in params:
         p = {
@@ -81,6 +84,13 @@ def generate_config_schema_json(params, families, output_dir: Path):
         if 'unit' in param:
             p['unit'] = param['unit']
 
+        if is_readonly(param):
+            p['readonly'] = True
+
+        gate = gates.get(param.get('family'))
+        if gate and gate != param['full_path']:
+            p['enabled_by'] = gate
+
         if 'range' in param:
             p['min'] = param['range'][0]
             p['max'] = param['range'][1]
@@ -140,26 +150,87 @@ def main():
         families = None  # v1 has no family metadata
 
     print(f"Found {len(params)} parameters")
+
+    nvs_schema = schema.get('nvs_schema', 1)
+    for p in params:
+        if is_readonly(p) and ('nvs_key' in p or 'old_nvs_keys' in p):
+            print(f"ERROR: read-only parameter {p['full_path']} can't have an nvs_key",
+                  file=sys.stderr)
+            sys.exit(1)
+        if is_readonly(p) and is_string_type(p):
+            print(f"ERROR: read-only parameter {p['full_path']} must be numeric",
+                  file=sys.stderr)
+            sys.exit(1)
+    nvs_params = [p for p in params if not is_readonly(p)]
+
+    seen_keys = {NVS_SCHEMA_KEY: '(schema version)', NVS_CRC_KEY: '(integrity CRC)'}
+    for p in nvs_params:
+        key = p['nvs_key']
+        path = f"{p.get('family', '')}.{p['name']}"
+        if len(key) > 15:
+            print(f"ERROR: nvs_key '{key}' of {path} exceeds 15 chars", file=sys.stderr)
+            sys.exit(1)
+        if key in seen_keys:
+            print(f"ERROR: nvs_key '{key}' of {path} already used by {seen_keys[key]}", file=sys.stderr)
+            sys.exit(1)
+        seen_keys[key] = path
+    for p in nvs_params:
+        path = f"{p.get('family', '')}.{p['name']}"
+        for key in p.get('old_nvs_keys', []):
+            if len(key) > 15:
+                print(f"ERROR: old_nvs_key '{key}' of {path} exceeds 15 chars", file=sys.stderr)
+                sys.exit(1)
+            if key in seen_keys:
+                print(f"ERROR: old_nvs_key '{key}' of {path} already used by {seen_keys[key]}", file=sys.stderr)
+                sys.exit(1)
+            seen_keys[key] = f"{path} (old key)"
+
+    seen_names = {}
+    for p in params:
+        seen_names[p['full_path']] = p['full_path']
+        seen_names.setdefault(p['name'], p['full_path'])
+    for p in params:
+        for old_name in p.get('old_names', []):
+            if old_name in seen_names:
+                print(f"ERROR: old_name '{old_name}' of {p['full_path']} already used by {seen_names[old_name]}",
+                      file=sys.stderr)
+                sys.exit(1)
+            seen_names[old_name] = p['full_path']
+
+    constraints = parse_constraints(schema.get('constraints', []), params)
+    if constraints:
+        print(f"Found {len(constraints)} constraints")
+    boards = parse_boards(schema.get('boards', []), params)
+    for fam in families or []:
+        gate = fam.get('enabled_by')
+        if gate is None:
+            continue
+        gp = next((p for p in params if p['full_path'] == gate), None)
+        if gp is None or gp['type'] != 'bool' or is_readonly(gp):
+            print(f"ERROR: family {fam['name']}: enabled_by '{gate}' is not a settable bool parameter",
+                  file=sys.stderr)
+            sys.exit(1)
+    print(f"Found {len(boards)} boards: {[b['name'] for b in boards]}")
     if families:
         print(f"Found {len(families)} families: {[f['name'] for f in families]}")
 
     print("Generating config.h...")
-    generate_config_h(params, families, output_dir)
+    generate_config_h(params, families, output_dir, boards)
 
     print("Generating config_meta.h...")
     generate_config_meta_h(params, output_dir)
 
     print("Generating config_nvs.h...")
-    generate_config_nvs_h(params, output_dir)
+    generate_config_nvs_h(nvs_params, output_dir, nvs_schema)
 
     print("Generating config_nvs.c...")
-    generate_config_nvs_c(params, families, output_dir)
+    generate_config_nvs_c(nvs_params, families, output_dir)
 
     print("Generating config_console.h...")
     generate_config_console_h(params, families, output_dir)
 
     print("Generating config_console.c...")
-    generate_config_console_c(params, families, output_dir)
+    generate_config_console_c(params, families, output_dir, constraints, boards)
 
     print("Generating config_schema.h...")
     generate_config_schema_json(params, families, output_dir)
@@ -167,11 +238,94 @@ def main():
     print(f"✓ Code generation complete: {output_dir}")
 
 
+CONSTRAINT_RE = re.compile(r'^\s*(\S+)\s*(<=|>=|==|!=|<|>)\s*(\S+)\s*$')
+
+
+def parse_constraints(raw: List[Dict], params: List[Dict]) -> List[Dict]:
+    """Parse cross-parameter constraints ("a.b < c.d") into operand lists"""
+    by_path = {f"{p.get('family', '')}.{p['name']}" if p.get('family') else p['name']: i
+               for i, p in enumerate(params)}
+    parsed = []
+    for c in raw:
+        m = CONSTRAINT_RE.match(c['check'])
+        if not m:
+            print(f"ERROR: bad constraint '{c['check']}' (expected 'a OP b')", file=sys.stderr)
+            sys.exit(1)
+        operands = []
+        for tok in (m.group(1), m.group(3)):
+            if tok.lstrip('-').isdigit():
+                operands.append(('const', int(tok)))
+            elif (tok in by_path and not is_string_type(params[by_path[tok]])
+                  and not is_readonly(params[by_path[tok]])):
+                operands.append(('param', by_path[tok]))
+            else:
+                print(f"ERROR: constraint '{c['check']}': '{tok}' is not a settable numeric parameter",
+                      file=sys.stderr)
+                sys.exit(1)
+        if all(kind == 'const' for kind, _ in operands):
+            print(f"ERROR: constraint '{c['check']}' references no parameter", file=sys.stderr)
+            sys.exit(1)
+        parsed.append({'check': c['check'], 'op': m.group(2),
+                       'operands': operands, 'message': c['message']})
+    return parsed
+
+
+BOARD_PSRAM = ['none', 'quad', 'octal']
+
+
+def parse_boards(raw: List[Dict], params: List[Dict]) -> List[Dict]:
+    """Parse board variants; resolve default overrides to parameter entries"""
+    by_path = {p['full_path']: p for p in params}
+    boards = []
+    seen = set()
+    for b in raw:
+        name = b.get('name', '')
+        if not re.match(r'^[a-z0-9_]{1,15}$', name) or name in seen:
+            print(f"ERROR: board name '{name}' invalid or duplicated ([a-z0-9_], 1-15 chars)",
+                  file=sys.stderr)
+            sys.exit(1)
+        seen.add(name)
+        psram = b.get('psram', 'none')
+        if psram not in BOARD_PSRAM:
+            print(f"ERROR: board {name}: psram must be one of {BOARD_PSRAM}", file=sys.stderr)
+            sys.exit(1)
+        overrides = []
+        for path, value in (b.get('defaults') or {}).items():
+            p = by_path.get(path)
+            if p is None or is_string_type(p) or is_readonly(p):
+                print(f"ERROR: board {name}: '{path}' is not a settable numeric parameter",
+                      file=sys.stderr)
+                sys.exit(1)
+            q = dict(p, default=value)
+            if p['type'] == 'enum' and value not in p['enum_values']:
+                print(f"ERROR: board {name}: {path}={value} is not a valid enum value",
+                      file=sys.stderr)
+                sys.exit(1)
+            if 'range' in p and not (p['range'][0] <= value <= p['range'][1]):
+                print(f"ERROR: board {name}: {path}={value} outside {p['range']}", file=sys.stderr)
+                sys.exit(1)
+            overrides.append({'param': p, 'c_value': get_default_value(q)[0],
+                              'str_value': get_default_str(q)})
+        boards.append({'name': name, 'description': b.get('description', ''),
+                       'codec': bool(b.get('codec', True)), 'psram': psram,
+                       'overrides': overrides})
+    if not boards:
+        # Schema without variants: one implicit board, the yaml defaults
+        boards.append({'name': 'default', 'description': 'Schema defaults',
+                       'codec': True, 'psram': 'none', 'overrides': []})
+    return boards
+
+
 def is_string_type(param: Dict) -> bool:
     """Check if parameter is a string type"""
     return param['type'] == 'string'
 
 
+def is_readonly(param: Dict) -> bool:
+    """Read-only (diagnostic) parameter: firmware-updated, never set or stored"""
+    return bool(param.get('readonly', False))
+
+
 def get_string_max_length(param: Dict) -> int:
     """Get max_length for string parameter (includes null terminator space)"""
     return param.get('max_length', 32)
@@ -188,6 +342,8 @@ def get_c_atomic_type(param: Dict) -> str:
         'u8': 'atomic_uchar',
         'u16': 'atomic_ushort',
         'u32': 'atomic_uint',
+        'i8': 'atomic_schar',
+        'i16': 'atomic_short',
         'bool': 'atomic_bool',
         'enum': 'atomic_uchar',
     }
@@ -208,6 +364,8 @@ def get_c_storage_type(param: Dict) -> str:
         'u8': 'uint8_t',
         'u16': 'uint16_t',
         'u32': 'uint32_t',
+        'i8': 'int8_t',
+        'i16': 'int16_t',
         'bool': 'bool',
         'enum': 'uint8_t',
     }
@@ -230,6 +388,26 @@ def get_default_value(param: Dict) -> tuple:
         return (str(param['default']), None)
 
 
+def get_default_str(param: Dict) -> str:
+    """Get default value formatted the way config_get_param_str() prints it"""
+    if param['type'] == 'bool':
+        return 'true' if param['default'] else 'false'
+    elif param['type'] == 'enum':
+        return str(param['enum_values'].index(param['default']))
+    elif param['type'] == 'string':
+        return str(param.get('default', ''))
+    else:
+        return str(param['default'])
+
+
+def get_param_id(param: Dict) -> str:
+    """C enum name identifying a parameter (always family-qualified)"""
+    family = param.get('family')
+    if family:
+        return f"CONFIG_ID_{family.upper()}_{param['name'].upper()}"
+    return f"CONFIG_ID_{param['name'].upper()}"
+
+
 def get_field_comment(param: Dict) -> str:
     """Generate field documentation comment"""
     gui = param['gui']
@@ -240,7 +418,8 @@ def get_field_comment(param: Dict) -> str:
     return f"{label}{range_str}"
 
 
-def generate_config_h(params: List[Dict], families: List[Dict], output_dir: Path):
+def generate_config_h(params: List[Dict], families: List[Dict], output_dir: Path,
+                      boards: List[Dict]):
     """Generate config.h with per-family structs (v2) or flat struct (v1)"""
 
     # Group params by family
@@ -272,6 +451,75 @@ def generate_config_h(params: List[Dict], families: List[Dict], output_dir: Path
 extern "C" {
 #endif
 
+/* ============================================================================
+ * Bounded String Parameters
+ * ============================================================================ */
+
+/**
+ * @brief Double-buffered string storage of max length n
+ *
+ * Writers (console, WebUI, NVS load, provisioning) serialize on `seq`:
+ * a writer moves it from even to odd with a CAS, fills the idle buffer,
+ * flips `active` and makes `seq` even again. CONFIG_STR_COPY() rechecks
+ * `seq` after copying and retries on a change, so its copy is never torn.
+ * The pointer from CONFIG_STR_GET() is only good while no second write
+ * reuses its buffer: use it for an immediate printf, copy anything kept.
+ */
+#define CONFIG_STR_T(n) struct { char buf[2][(n) + 1]; atomic_uchar active; atomic_uint seq; }
+
+/** Current value of a CONFIG_STR_T field (const char *), for immediate use */
+#define CONFIG_STR_GET(s) \\
+    ((const char *)(s).buf[atomic_load_explicit(&(s).active, memory_order_acquire) & 1U])
+
+/** Copy the current value of a CONFIG_STR_T field into out[len] */
+#define CONFIG_STR_COPY(s, out, len) \\
+    config_str_load(&(s).buf[0][0], &(s).active, &(s).seq, sizeof((s).buf[0]), (out), (len))
+
+/** Store a new value (truncated to the field's max length) */
+#define CONFIG_STR_SET(s, v) \\
+    config_str_store(&(s).buf[0][0], &(s).active, &(s).seq, sizeof((s).buf[0]), (v))
+
+/**
+ * @brief Write the idle buffer of a CONFIG_STR_T and publish it
+ *
+ * Waits for a concurrent writer of the same field. Use CONFIG_STR_SET().
+ *
+ * @param bufs Start of buf[0] (buf[1] follows at bufs + size)
+ * @param active Active buffer index
+ * @param seq Write sequence (odd while a write is in progress)
+ * @param size Size of one buffer including terminator
+ * @param v New value (NULL stores "")
+ */
+void config_str_store(char *bufs, atomic_uchar *active, atomic_uint *seq, size_t size,
+                      const char *v);
+
+/**
+ * @brief Copy the active buffer of a CONFIG_STR_T, retrying if a write overlapped
+ *
+ * Use CONFIG_STR_COPY().
+ *
+ * @param bufs Start of buf[0]
+ * @param active Active buffer index
+ * @param seq Write sequence
+ * @param size Size of one buffer including terminator
+ * @param out Destination (always terminated)
+ * @param len Size of out
+ */
+void config_str_load(const char *bufs, atomic_uchar *active, atomic_uint *seq, size_t size,
+                     char *out, size_t len);
+
+/* ============================================================================
+ * Parameter IDs
+ * ============================================================================ */
+
+/** @brief Parameter index (same order as CONSOLE_PARAMS) */
+typedef enum {
+"""
+    for p in params:
+        code += f"    {get_param_id(p)},\n"
+    code += """    CONFIG_ID_COUNT
+} config_param_id_t;
+
 """
 
     if families:
@@ -288,7 +536,7 @@ extern "C" {
                 comment = get_field_comment(p)
                 if is_string_type(p):
                     max_len = get_string_max_length(p)
-                    code += f"    char {p['name']}[{max_len + 1}];  /**< {comment} */\n"
+                    code += f"    CONFIG_STR_T({max_len}) {p['name']};  /**< {comment} */\n"
                 else:
                     atomic_type = get_c_atomic_type(p)
                     code += f"    {atomic_type} {p['name']};  /**< {comment} */\n"
@@ -302,6 +550,8 @@ extern "C" {
             if fname in by_family and by_family[fname]:
                 code += f"    config_{fname}_t {fname};\n"
         code += "    atomic_ushort generation;  /**< Config change counter */\n"
+        code += "    atomic_bool batch;  /**< Batch write in progress (config_batch_begin()) */\n"
+        code += "    atomic_ushort changed_gen[CONFIG_ID_COUNT];  /**< Generation of each parameter's last change */\n"
         code += "} keyer_config_t;\n\n"
     else:
         # V1: Flat struct
@@ -314,13 +564,21 @@ typedef struct {
             comment = get_field_comment(p)
             if is_string_type(p):
                 max_len = get_string_max_length(p)
-                code += f"    char {p['name']}[{max_len + 1}];  /**< {comment} */\n"
+                code += f"    CONFIG_STR_T({max_len}) {p['name']};  /**< {comment} */\n"
             else:
                 atomic_type = get_c_atomic_type(p)
                 code += f"    {atomic_type} {p['name']};  /**< {comment} */\n"
         code += "    atomic_ushort generation;  /**< Config generation counter */\n"
+        code += "    atomic_bool batch;  /**< Batch write in progress (config_batch_begin()) */\n"
+        code += "    atomic_ushort changed_gen[CONFIG_ID_COUNT];  /**< Generation of each parameter's last change */\n"
         code += "} keyer_config_t;\n\n"
 
+    str_max = max((get_string_max_length(p) for p in params if is_string_type(p)), default=0)
+    code += f"/** Longest string parameter (excluding terminator) */\n"
+    code += f"#define CONFIG_STR_MAX_LEN {str_max}\n\n"
+
+    code += generate_snapshot_types(params, families, by_family)
+
     code += """/** Global configuration instance */
 extern keyer_config_t g_config;
 
@@ -331,11 +589,132 @@ extern keyer_config_t g_config;
 void config_init_defaults(keyer_config_t *cfg);
 
 /**
- * @brief Increment generation counter to signal config change
+ * @brief Signal a bulk config change (every parameter counts as changed)
  * @param cfg Configuration
  */
 void config_bump_generation(keyer_config_t *cfg);
 
+/**
+ * @brief Signal that one parameter changed
+ *
+ * Stamps the parameter with the next generation, then publishes that
+ * generation. Called by the CONFIG_SET_* macros and console setters.
+ *
+ * @param cfg Configuration
+ * @param id Parameter that changed
+ */
+void config_mark_changed(keyer_config_t *cfg, config_param_id_t id);
+
+/**
+ * @brief Start a multi-parameter change published as one generation
+ *
+ * Until config_batch_commit(), setters stamp their parameters but do not
+ * publish a generation, and snapshot readers treat the config as torn
+ * (config_batch_active()). Keeps related values (e.g. WPM, weight and
+ * ratio) from being picked up half-applied. Console task only, not nested.
+ *
+ * @param cfg Configuration
+ */
+void config_batch_begin(keyer_config_t *cfg);
+
+/**
+ * @brief Publish every change made since config_batch_begin() at once
+ * @param cfg Configuration
+ */
+void config_batch_commit(keyer_config_t *cfg);
+
+/**
+ * @brief Check if a batch write is in progress
+ *
+ * Snapshot readers test this after reading, together with the
+ * generation, and retry if either says the values may be mixed.
+ */
+static inline bool config_batch_active(keyer_config_t *cfg) {
+    return atomic_load_explicit(&cfg->batch, memory_order_acquire);
+}
+
+/**
+ * @brief Check if a parameter changed after a given generation
+ *
+ * Lets a subsystem react only to its own parameters: remember the
+ * generation last handled, and on a new generation test just the IDs
+ * it cares about instead of rereading everything.
+ *
+ * @param cfg Configuration
+ * @param id Parameter to test
+ * @param since_gen Generation the caller last handled
+ * @return true if the parameter changed after since_gen
+ */
+static inline bool config_changed_since(keyer_config_t *cfg, config_param_id_t id,
+                                        uint16_t since_gen) {
+    uint16_t stamp = atomic_load_explicit(&cfg->changed_gen[id], memory_order_acquire);
+    return (int16_t)(uint16_t)(stamp - since_gen) > 0;
+}
+
+/**
+ * @brief Copy every numeric parameter into a plain snapshot on a new generation
+ *
+ * For hot loops: call once per tick, then read plain fields from the
+ * snapshot instead of loading atomics. Does nothing while the generation
+ * is unchanged; a read torn by a concurrent write or an open batch is
+ * discarded and retried on the next call.
+ *
+ * @param cfg Configuration
+ * @param snap Snapshot, only overwritten with a consistent copy
+ * @param gen In: generation of snap. Out: generation now held
+ * @return true if snap was refreshed
+ */
+bool config_snapshot_if_changed(keyer_config_t *cfg, config_snapshot_t *snap, uint16_t *gen);
+
+/* ============================================================================
+ * Board Variants
+ * ============================================================================ */
+
+/** External PSRAM fitted on a board */
+typedef enum {
+    CONFIG_PSRAM_NONE = 0,
+    CONFIG_PSRAM_QUAD,
+    CONFIG_PSRAM_OCTAL,
+} config_psram_t;
+
+/** Hardware revision, from parameters.yaml `boards:` */
+typedef struct {
+    const char *name;
+    const char *description;
+    bool has_codec;            /**< ES8311 codec fitted (I2S sidetone) */
+    config_psram_t psram;
+} config_board_t;
+
+#define CONFIG_BOARD_COUNT @BOARD_COUNT@
+
+extern const config_board_t CONFIG_BOARDS[CONFIG_BOARD_COUNT];
+
+/**
+ * @brief Find a board by name
+ * @return Index into CONFIG_BOARDS, or -1 if unknown
+ */
+int config_board_find(const char *name);
+
+/**
+ * @brief Make a board active and store its parameter defaults
+ *
+ * Boot only: call after config_init_defaults() and before loading NVS,
+ * so stored values still override the board defaults.
+ *
+ * @param cfg Configuration
+ * @param index Index into CONFIG_BOARDS (out of range selects board 0)
+ */
+void config_board_apply(keyer_config_t *cfg, size_t index);
+
+/** Board applied at boot (board 0 until config_board_apply()) */
+const config_board_t *config_board_active(void);
+
+/** Index of the active board in CONFIG_BOARDS */
+size_t config_board_active_index(void);
+
+/** PSRAM type as text: "none", "quad" or "octal" */
+const char *config_psram_str(config_psram_t psram);
+
 /* ============================================================================
  * Parameter Access Macros
  * ============================================================================ */
@@ -360,25 +739,23 @@ void config_bump_generation(keyer_config_t *cfg);
             macro_upper = upper
 
         if is_string_type(p):
-            max_len = get_string_max_length(p)
-            if families and family:
-                # V2: nested access for strings
-                code += f"#define CONFIG_GET_{macro_upper}() \\\n"
-                code += f"    (g_config.{family}.{name})\n\n"
-                code += f"#define CONFIG_SET_{macro_upper}(v) do {{ \\\n"
-                code += f"    strncpy(g_config.{family}.{name}, (v), {max_len}); \\\n"
-                code += f"    g_config.{family}.{name}[{max_len}] = '\\0'; \\\n"
-                code += f"    config_bump_generation(&g_config); \\\n"
-                code += f"}} while(0)\n\n"
-            else:
-                # V1: flat access for strings
-                code += f"#define CONFIG_GET_{macro_upper}() \\\n"
-                code += f"    (g_config.{name})\n\n"
-                code += f"#define CONFIG_SET_{macro_upper}(v) do {{ \\\n"
-                code += f"    strncpy(g_config.{name}, (v), {max_len}); \\\n"
-                code += f"    g_config.{name}[{max_len}] = '\\0'; \\\n"
-                code += f"    config_bump_generation(&g_config); \\\n"
-                code += f"}} while(0)\n\n"
+            path = f"{family}.{name}" if families and family else name
+            code += f"#define CONFIG_GET_{macro_upper}() \\\n"
+            code += f"    CONFIG_STR_GET(g_config.{path})\n\n"
+            code += f"#define CONFIG_COPY_{macro_upper}(out, len) \\\n"
+            code += f"    CONFIG_STR_COPY(g_config.{path}, (out), (len))\n\n"
+            code += f"#define CONFIG_SET_{macro_upper}(v) do {{ \\\n"
+            code += f"    CONFIG_STR_SET(g_config.{path}, (v)); \\\n"
+            code += f"    config_mark_changed(&g_config, {get_param_id(p)}); \\\n"
+            code += f"}} while(0)\n\n"
+        elif is_readonly(p):
+            # Diagnostic value: published by firmware, not a config change
+            path = f"{family}.{name}" if families and family else name
+            code += f"#define CONFIG_GET_{macro_upper}() \\\n"
+            code += f"    atomic_load_explicit(&g_config.{path}, memory_order_relaxed)\n\n"
+            code += f"/** Read-only parameter: update without bumping the generation */\n"
+            code += f"#define CONFIG_UPDATE_{macro_upper}(v) \\\n"
+            code += f"    atomic_store_explicit(&g_config.{path}, (v), memory_order_relaxed)\n\n"
         else:
             if families and family:
                 # V2: nested access
@@ -386,7 +763,7 @@ void config_bump_generation(keyer_config_t *cfg);
                 code += f"    atomic_load_explicit(&g_config.{family}.{name}, memory_order_relaxed)\n\n"
                 code += f"#define CONFIG_SET_{macro_upper}(v) do {{ \\\n"
                 code += f"    atomic_store_explicit(&g_config.{family}.{name}, (v), memory_order_relaxed); \\\n"
-                code += f"    config_bump_generation(&g_config); \\\n"
+                code += f"    config_mark_changed(&g_config, {get_param_id(p)}); \\\n"
                 code += f"}} while(0)\n\n"
             else:
                 # V1: flat access
@@ -394,7 +771,7 @@ void config_bump_generation(keyer_config_t *cfg);
                 code += f"    atomic_load_explicit(&g_config.{name}, memory_order_relaxed)\n\n"
                 code += f"#define CONFIG_SET_{macro_upper}(v) do {{ \\\n"
                 code += f"    atomic_store_explicit(&g_config.{name}, (v), memory_order_relaxed); \\\n"
-                code += f"    config_bump_generation(&g_config); \\\n"
+                code += f"    config_mark_changed(&g_config, {get_param_id(p)}); \\\n"
                 code += f"}} while(0)\n\n"
 
     code += """#ifdef __cplusplus
@@ -404,14 +781,160 @@ void config_bump_generation(keyer_config_t *cfg);
 #endif /* KEYER_CONFIG_H */
 """
 
+    code = code.replace('@BOARD_COUNT@', str(len(boards)))
     with open(output_dir / "config.h", "w") as f:
         f.write(code)
 
     # Also generate config.c with initialization
-    generate_config_c(params, families, output_dir)
+    generate_config_c(params, families, output_dir, boards)
+
+
+def generate_snapshot_types(params: List[Dict], families: List[Dict], by_family: Dict) -> str:
+    """Plain (non-atomic) mirror of keyer_config_t, numeric parameters only"""
+    code = """/* ============================================================================
+ * Plain Snapshot
+ * ============================================================================ */
+
+"""
+    def fields(plist: List[Dict]) -> str:
+        out = ""
+        for p in plist:
+            if not is_string_type(p) and not is_readonly(p):
+                out += f"    {get_c_storage_type(p)} {p['name']};  /**< {get_field_comment(p)} */\n"
+        return out
+
+    members = ""
+    if families:
+        for family in families:
+            fname = family['name']
+            plain = [p for p in by_family.get(fname, [])
+                     if not is_string_type(p) and not is_readonly(p)]
+            if not plain:
+                continue
+            code += f"/** @brief {fname.title()} values (plain copy) */\n"
+            code += "typedef struct {\n"
+            code += fields(plain)
+            code += f"}} config_{fname}_snap_t;\n\n"
+            members += f"    config_{fname}_snap_t {fname};\n"
+    else:
+        members = fields(params)
 
+    code += """/**
+ * @brief Plain copy of the numeric parameters, taken at one generation
+ *
+ * Strings are left out: they are not read from hot loops and would make
+ * the copy large. Read-only (diagnostic) parameters are left out too.
+ * Filled by config_snapshot_if_changed().
+ */
+typedef struct {
+"""
+    code += members
+    code += "} config_snapshot_t;\n\n"
+    return code
+
+
+def generate_snapshot_c(params: List[Dict], families: List[Dict]) -> str:
+    """config_snapshot_if_changed(): optimistic read into a local copy"""
+    code = """
+bool config_snapshot_if_changed(keyer_config_t *cfg, config_snapshot_t *snap, uint16_t *gen) {
+    uint16_t current = atomic_load_explicit(&cfg->generation, memory_order_acquire);
+    if (current == *gen) {
+        return false;
+    }
 
-def generate_config_c(params: List[Dict], families: List[Dict], output_dir: Path):
+    config_snapshot_t tmp;
+"""
+    for p in params:
+        if is_string_type(p) or is_readonly(p):
+            continue
+        family = p.get('family', None)
+        path = f"{family}.{p['name']}" if families and family else p['name']
+        code += f"    tmp.{path} = atomic_load_explicit(&cfg->{path}, memory_order_relaxed);\n"
+    code += """
+    /* Generation moved or batch open mid-read: values may be mixed */
+    atomic_thread_fence(memory_order_acquire);
+    if (atomic_load_explicit(&cfg->generation, memory_order_relaxed) != current ||
+        atomic_load_explicit(&cfg->batch, memory_order_relaxed)) {
+        return false;
+    }
+
+    *snap = tmp;
+    *gen = current;
+    return true;
+}
+"""
+    return code
+
+
+def generate_boards_c(boards: List[Dict]) -> str:
+    """Generate the board table and the per-board default overrides"""
+    code = """
+/* ============================================================================
+ * Board Variants
+ * ============================================================================ */
+
+const config_board_t CONFIG_BOARDS[CONFIG_BOARD_COUNT] = {
+"""
+    psram_enum = {'none': 'CONFIG_PSRAM_NONE', 'quad': 'CONFIG_PSRAM_QUAD',
+                  'octal': 'CONFIG_PSRAM_OCTAL'}
+    for b in boards:
+        codec = 'true' if b['codec'] else 'false'
+        code += f'    {{ "{b["name"]}", "{b["description"]}", {codec}, {psram_enum[b["psram"]]} }},\n'
+    code += """};
+
+static size_t s_board = 0;
+
+int config_board_find(const char *name) {
+    if (name == NULL) return -1;
+    for (size_t i = 0; i < CONFIG_BOARD_COUNT; i++) {
+        if (strcmp(CONFIG_BOARDS[i].name, name) == 0) {
+            return (int)i;
+        }
+    }
+    return -1;
+}
+
+void config_board_apply(keyer_config_t *cfg, size_t index) {
+    s_board = (index < CONFIG_BOARD_COUNT) ? index : 0;
+"""
+    if not any(b['overrides'] for b in boards):
+        code += "    (void)cfg;\n"
+    code += """    switch (s_board) {
+"""
+    for i, b in enumerate(boards):
+        if not b['overrides']:
+            continue
+        code += f"        case {i}:  /* {b['name']} */\n"
+        for o in b['overrides']:
+            path = f"{o['param']['family']}.{o['param']['name']}"
+            code += f"            atomic_store_explicit(&cfg->{path}, {o['c_value']}, memory_order_relaxed);\n"
+        code += "            break;\n"
+    code += """        default:
+            break;  /* Schema defaults as they are */
+    }
+}
+
+const config_board_t *config_board_active(void) {
+    return &CONFIG_BOARDS[s_board];
+}
+
+size_t config_board_active_index(void) {
+    return s_board;
+}
+
+const char *config_psram_str(config_psram_t psram) {
+    switch (psram) {
+        case CONFIG_PSRAM_QUAD:  return "quad";
+        case CONFIG_PSRAM_OCTAL: return "octal";
+        default:                 return "none";
+    }
+}
+"""
+    return code
+
+
+def generate_config_c(params: List[Dict], families: List[Dict], output_dir: Path,
+                      boards: List[Dict]):
     """Generate config.c with nested initialization (v2) or flat (v1)"""
 
     # Output to parent's src directory
@@ -427,6 +950,11 @@ def generate_config_c(params: List[Dict], families: List[Dict], output_dir: Path
 #include "config.h"
 #include <string.h>
 
+#ifdef ESP_PLATFORM
+#include "freertos/FreeRTOS.h"
+#include "freertos/task.h"
+#endif
+
 keyer_config_t g_config;
 
 void config_init_defaults(keyer_config_t *cfg) {
@@ -445,12 +973,9 @@ void config_init_defaults(keyer_config_t *cfg) {
             path = name
 
         if is_string_type(p):
-            max_len = get_string_max_length(p)
-            if comment:
-                code += f"    strncpy(cfg->{path}, {default_val}, {max_len});  /* {comment} */\n"
-            else:
-                code += f"    strncpy(cfg->{path}, {default_val}, {max_len});\n"
-            code += f"    cfg->{path}[{max_len}] = '\\0';\n"
+            code += f"    atomic_init(&cfg->{path}.active, 0);\n"
+            code += f"    atomic_init(&cfg->{path}.seq, 0);\n"
+            code += f"    CONFIG_STR_SET(cfg->{path}, {default_val});\n"
         else:
             if comment:
                 code += f"    atomic_init(&cfg->{path}, {default_val});  /* {comment} */\n"
@@ -458,12 +983,107 @@ void config_init_defaults(keyer_config_t *cfg) {
                 code += f"    atomic_init(&cfg->{path}, {default_val});\n"
 
     code += """    atomic_init(&cfg->generation, 0);
+    atomic_init(&cfg->batch, false);
+    for (size_t i = 0; i < CONFIG_ID_COUNT; i++) {
+        atomic_init(&cfg->changed_gen[i], 0);
+    }
+}
+
+/**
+ * @brief Publish generation + 1 once the stamps carry it
+ *
+ * Stamp first: a reader that sees the new generation also sees the
+ * stamp. A writer that loses the CAS to another one stamps again with
+ * the newer value, so no stamp ends up behind the generation that
+ * publishes it (two plain increments could publish a stamp the reader
+ * already considers handled).
+ */
+static void publish_stamped(keyer_config_t *cfg, size_t first, size_t count) {
+    uint16_t gen = atomic_load_explicit(&cfg->generation, memory_order_relaxed);
+    do {
+        for (size_t i = first; i < first + count; i++) {
+            atomic_store_explicit(&cfg->changed_gen[i], (uint16_t)(gen + 1U), memory_order_relaxed);
+        }
+    } while (!atomic_compare_exchange_weak_explicit(&cfg->generation, &gen, (uint16_t)(gen + 1U),
+                                                    memory_order_release, memory_order_relaxed));
 }
 
 void config_bump_generation(keyer_config_t *cfg) {
+    publish_stamped(cfg, 0, CONFIG_ID_COUNT);
+}
+
+void config_mark_changed(keyer_config_t *cfg, config_param_id_t id) {
+    if (atomic_load_explicit(&cfg->batch, memory_order_relaxed)) {
+        /* Published by config_batch_commit() */
+        uint16_t gen = (uint16_t)(atomic_load_explicit(&cfg->generation, memory_order_relaxed) + 1U);
+        atomic_store_explicit(&cfg->changed_gen[id], gen, memory_order_relaxed);
+        return;
+    }
+    publish_stamped(cfg, (size_t)id, 1);
+}
+
+void config_batch_begin(keyer_config_t *cfg) {
+    atomic_store_explicit(&cfg->batch, true, memory_order_seq_cst);
+}
+
+void config_batch_commit(keyer_config_t *cfg) {
+    /* Publish before clearing: a reader never sees neither flag nor new generation */
     atomic_fetch_add_explicit(&cfg->generation, 1, memory_order_release);
+    atomic_store_explicit(&cfg->batch, false, memory_order_release);
+}
+
+/* Let a preempted writer finish (it may run on this core at lower priority) */
+static void str_wait(void) {
+#ifdef ESP_PLATFORM
+    vTaskDelay(1);
+#endif
+}
+
+void config_str_store(char *bufs, atomic_uchar *active, atomic_uint *seq, size_t size,
+                      const char *v) {
+    unsigned s = atomic_load_explicit(seq, memory_order_relaxed);
+    while ((s & 1U) != 0 ||
+           !atomic_compare_exchange_weak_explicit(seq, &s, s + 1U, memory_order_acquire,
+                                                  memory_order_relaxed)) {
+        if ((s & 1U) != 0) {
+            str_wait();
+            s = atomic_load_explicit(seq, memory_order_relaxed);
+        }
+    }
+    /* Odd seq is visible before the buffer changes */
+    atomic_thread_fence(memory_order_release);
+
+    unsigned idle = (atomic_load_explicit(active, memory_order_relaxed) & 1U) ^ 1U;
+    char *dst = bufs + idle * size;
+    strncpy(dst, (v != NULL) ? v : "", size - 1);
+    dst[size - 1] = '\\0';
+    atomic_store_explicit(active, (unsigned char)idle, memory_order_release);
+    atomic_store_explicit(seq, s + 2U, memory_order_release);
 }
-"""
+
+void config_str_load(const char *bufs, atomic_uchar *active, atomic_uint *seq, size_t size,
+                     char *out, size_t len) {
+    if (len == 0) {
+        return;
+    }
+    size_t n = (len < size) ? len : size;
+    for (;;) {
+        unsigned before = atomic_load_explicit(seq, memory_order_acquire);
+        if ((before & 1U) != 0) {
+            str_wait();
+            continue;
+        }
+        const char *src = bufs + (atomic_load_explicit(active, memory_order_acquire) & 1U) * size;
+        memcpy(out, src, n);
+        atomic_thread_fence(memory_order_acquire);
+        if (atomic_load_explicit(seq, memory_order_relaxed) == before) {
+            break;
+        }
+    }
+    out[n - 1] = '\\0';
+}"""
+    code += generate_snapshot_c(params, families)
+    code += generate_boards_c(boards)
 
     with open(src_dir / "config.c", "w") as f:
         f.write(code)
@@ -537,7 +1157,14 @@ const param_meta_t *config_get_meta(const char *name);
         f.write(code)
 
 
-def generate_config_nvs_h(params: List[Dict], output_dir: Path):
+NVS_SCHEMA_KEY = "schema_ver"
+
+# Value limits of signed parameter types (range defaults to these)
+SIGNED_LIMITS = {'i8': (-128, 127), 'i16': (-32768, 32767)}
+NVS_CRC_KEY = "cfg_crc"
+
+
+def generate_config_nvs_h(params: List[Dict], output_dir: Path, nvs_schema: int):
     """Generate config_nvs.h - NVS persistence declarations"""
 
     code = """/* Auto-generated from parameters.yaml - DO NOT EDIT MANUALLY */
@@ -549,6 +1176,9 @@ def generate_config_nvs_h(params: List[Dict], output_dir: Path):
 #ifndef KEYER_CONFIG_NVS_H
 #define KEYER_CONFIG_NVS_H
 
+#include <stddef.h>
+#include <stdint.h>
+#include <stdbool.h>
 #include "esp_err.h"
 
 #ifdef __cplusplus
@@ -558,20 +1188,178 @@ extern "C" {
 /** NVS namespace for keyer configuration */
 #define CONFIG_NVS_NAMESPACE "keyer_cfg"
 
+/**
+ * Stored layout version (nvs_schema in parameters.yaml)
+ *
+ * Bumped when an existing nvs_key changes type or meaning. Data written
+ * under another version is ignored on load and replaced on next save.
+ * Adding or removing parameters does not need a bump: missing keys keep
+ * their defaults and stale keys are never read.
+ */
+#define CONFIG_NVS_SCHEMA_VERSION @NVS_SCHEMA@
+
+/** NVS key holding CONFIG_NVS_SCHEMA_VERSION */
+#define CONFIG_NVS_KEY_SCHEMA "@NVS_SCHEMA_KEY@"
+
+/** NVS key holding the CRC over every other entry of the namespace */
+#define CONFIG_NVS_KEY_CRC "@NVS_CRC_KEY@"
+
+/** config_load_from_nvs() result: stored CRC doesn't match, nothing loaded */
+#define CONFIG_NVS_ERR_CRC (-2)
+
 /**
  * @brief Load all parameters from NVS
- * @return Number of parameters loaded, or negative on error
+ *
+ * Out-of-range numeric values (e.g. after a range change in the schema)
+ * are skipped and keep their defaults. Values found only under a
+ * parameter's old_nvs_keys are loaded and the namespace is rewritten
+ * under the current keys.
+ *
+ * The namespace is checked against the CRC written by the last save
+ * first. On mismatch (flash corruption) nothing is loaded, so the
+ * defaults stay in effect, and config_nvs_integrity_failed() reports it.
+ * Data saved before CRCs were added is accepted as is.
+ *
+ * @return Number of parameters loaded (0 if nothing stored or stored
+ *         under another schema version), CONFIG_NVS_ERR_CRC, or
+ *         negative on other errors
  */
 int config_load_from_nvs(void);
 
+/**
+ * @brief Check if the boot load rejected the stored config (CRC mismatch)
+ */
+bool config_nvs_integrity_failed(void);
+
 /**
  * @brief Save all parameters to NVS
+ *
+ * Only entries whose stored value differs are written, then everything
+ * is committed at once. Unchanged parameters cost a read, not flash wear.
+ *
  * @return Number of parameters saved, or negative on error
  */
 int config_save_to_nvs(void);
 
+/**
+ * @brief Recompute the integrity CRC of CONFIG_NVS_NAMESPACE
+ *
+ * The CRC covers every entry of the namespace, not only the parameters.
+ * Code that writes or erases keys there without config_save_to_nvs()
+ * (provisioning) must call this afterwards, or the next boot rejects
+ * the whole namespace as corrupted.
+ *
+ * @return ESP_OK, or the NVS error
+ */
+esp_err_t config_nvs_reseal(void);
+
+/**
+ * @brief Load all parameters from another NVS namespace (e.g. a profile)
+ *
+ * Same rules as config_load_from_nvs(). Does not bump the generation.
+ *
+ * @param ns NVS namespace (max 15 chars)
+ * @return Number of parameters loaded, or negative on error
+ */
+int config_load_from_nvs_ns(const char *ns);
+
+/**
+ * @brief Save all parameters to another NVS namespace (e.g. a profile)
+ * @param ns NVS namespace (max 15 chars)
+ * @return Number of parameters saved, or negative on error
+ */
+int config_save_to_nvs_ns(const char *ns);
+
+/**
+ * @brief Read the value stored in NVS for one parameter
+ *
+ * Formats the stored value the same way as config_get_param_str(),
+ * so the two can be compared directly.
+ *
+ * @param name Parameter name or full path
+ * @param buf Output buffer
+ * @param len Buffer size
+ * @return 0 if found, 1 if not stored in NVS, -1 on error
+ */
+int config_nvs_get_param_str(const char *name, char *buf, size_t len);
+
+/**
+ * @brief Check if a parameter has an unsaved change
+ *
+ * True when the running value differs from the one stored in the
+ * default namespace (or from the default, if never stored). The result
+ * is cached per parameter and recomputed only after the parameter
+ * changes or the namespace is loaded/saved, so repeated calls are cheap.
+ *
+ * @param name Parameter name or full path
+ * @return true if a reboot would lose the current value
+ */
+bool config_nvs_param_dirty(const char *name);
+
+/**
+ * @brief Number of parameters with unsaved changes
+ */
+size_t config_nvs_dirty_count(void);
+
+/**
+ * @brief NVS commit statistics (since boot, all namespaces)
+ */
+typedef struct {
+    uint32_t commits;       /**< Successful save/commit cycles */
+    uint32_t failures;      /**< Saves that returned an error */
+    uint32_t written;       /**< Entries actually written to flash */
+    uint32_t unchanged;     /**< Entries skipped (stored value already equal) */
+    uint32_t autosaves;     /**< Commits triggered by config_nvs_autosave_tick() */
+    uint32_t last_us;       /**< Duration of the last save (us) */
+} config_nvs_stats_t;
+
+/**
+ * @brief Copy the commit statistics
+ * @param out Output
+ */
+void config_nvs_get_stats(config_nvs_stats_t *out);
+
+/**
+ * @brief Deferred save of unsaved changes
+ *
+ * Call periodically from a task that may block for a flash write and
+ * erase (io_task, never bg_task or the RT task). Once the configuration
+ * has been quiet for system.autosave_sec seconds (0 = off) and something
+ * is unsaved, all changes are flushed with one commit. A burst of edits
+ * (slider drags, scripts) then costs one flash write per parameter
+ * instead of one per step.
+ *
+ * @param now_us Current time (esp_timer_get_time())
+ */
+void config_nvs_autosave_tick(int64_t now_us);
+
+/** NVS location of the board selection (kept outside the config namespace) */
+#define CONFIG_BOARD_NVS_NAMESPACE "board"
+#define CONFIG_BOARD_NVS_KEY "id"
+
+/**
+ * @brief Pick the board variant and apply its parameter defaults
+ *
+ * Uses the board stored in NVS, else Kconfig KEYER_BOARD, else the first
+ * board in parameters.yaml. Call after config_init_defaults() and before
+ * config_load_from_nvs().
+ *
+ * @return Index of the applied board in CONFIG_BOARDS
+ */
+size_t config_board_init(void);
+
+/**
+ * @brief Store the board variant used from the next boot
+ * @param name Board name from CONFIG_BOARDS
+ * @return 0 on success, -1 unknown board, -2 NVS error
+ */
+int config_board_save(const char *name);
+
 /* NVS key definitions */
 """
+    code = code.replace('@NVS_SCHEMA@', str(nvs_schema))
+    code = code.replace('@NVS_SCHEMA_KEY@', NVS_SCHEMA_KEY)
+    code = code.replace('@NVS_CRC_KEY@', NVS_CRC_KEY)
 
     for p in params:
         family = p.get('family', '')
@@ -593,10 +1381,91 @@ int config_save_to_nvs(void);
         f.write(code)
 
 
+def nvs_range_check(param: Dict, var: str) -> str:
+    """Extra load condition rejecting stored values outside the schema range"""
+    if param['type'] == 'enum':
+        return f" &&\n            {var} < {len(param['enum_values'])}U"
+    if 'range' not in param:
+        return ""
+    lo, hi = param['range']
+    if param['type'] in SIGNED_LIMITS:
+        type_min, type_max = SIGNED_LIMITS[param['type']]
+        suffix = ""
+    else:
+        type_min = 0
+        type_max = {'u8': 0xFF, 'u16': 0xFFFF, 'u32': 0xFFFFFFFF}[param['type']]
+        suffix = "U"
+    checks = []
+    if lo > type_min:
+        checks.append(f"{var} >= {lo}{suffix}")
+    if hi < type_max:
+        checks.append(f"{var} <= {hi}{suffix}")
+    if not checks:
+        return ""
+    return " &&\n            " + " && ".join(checks)
+
+
+def nvs_load_block(param: Dict) -> str:
+    """Load code for one parameter, falling back to its old_nvs_keys"""
+    family = param.get('family', '')
+    pname = param['name']
+    ptype = param['type']
+    if family:
+        nvs_key = f"NVS_{family.upper()}_{pname.upper()}"
+        config_path = f"g_config.{family}.{pname}"
+    else:
+        nvs_key = f"NVS_{pname.upper()}"
+        config_path = f"g_config.{pname}"
+
+    if ptype == 'string':
+        reset = f"str_len = sizeof({config_path}.buf[0]);"
+        get, var, store = "nvs_get_str", "str_val", f"CONFIG_STR_SET({config_path}, str_val);"
+        cond = ""
+    else:
+        reset = None
+        get, var = {
+            'u8': ("nvs_get_u8", "u8_val"),
+            'enum': ("nvs_get_u8", "u8_val"),
+            'bool': ("nvs_get_u8", "u8_val"),
+            'u16': ("nvs_get_u16", "u16_val"),
+            'u32': ("nvs_get_u32", "u32_val"),
+            'i8': ("nvs_get_i8", "i8_val"),
+            'i16': ("nvs_get_i16", "i16_val"),
+        }[ptype]
+        value = f"{var} != 0" if ptype == 'bool' else var
+        store = f"atomic_store_explicit(&{config_path}, {value}, memory_order_relaxed);"
+        cond = nvs_range_check(param, var)
+
+    def attempt(key: str) -> str:
+        if ptype == 'string':
+            return f"{get}(handle, {key}, str_val, &str_len) == ESP_OK{cond}"
+        return f"{get}(handle, {key}, &{var}) == ESP_OK{cond}"
+
+    code = f"    /* Load {family}.{pname} */\n"
+    if reset:
+        code += f"    {reset}\n"
+    code += f"""    if ({attempt(nvs_key)}) {{
+        {store}
+        loaded++;
+    }}"""
+    for old_key in param.get('old_nvs_keys', []):
+        old_attempt = attempt('"' + old_key + '"')
+        if reset:
+            code += f" else if (({reset[:-1]},\n                {old_attempt})) {{\n"
+        else:
+            code += f" else if ({old_attempt}) {{\n"
+        code += f"""        {store}
+        loaded++;
+        migrated++;
+    }}"""
+    code += "\n\n"
+    return code
+
+
 def generate_config_nvs_c(params: List[Dict], families: List[Dict], output_dir: Path):
     """Generate config_nvs.c - NVS persistence implementation"""
 
-    src_dir = output_dir / "src"
+    src_dir = output_dir.parent / "src"
     src_dir.mkdir(parents=True, exist_ok=True)
 
     code = """/* Auto-generated from parameters.yaml - DO NOT EDIT MANUALLY */
@@ -607,13 +1476,268 @@ def generate_config_nvs_c(params: List[Dict], families: List[Dict], output_dir:
 
 #include "config_nvs.h"
 #include "config.h"
+#include "config_console.h"
 #include "nvs_flash.h"
 #include "nvs.h"
+#include "esp_rom_crc.h"
+#include "esp_timer.h"
+#include "sdkconfig.h"
+#include <stdio.h>
 #include <string.h>
 
+/* ============================================================================
+ * Unsaved-Change Cache
+ * ============================================================================ */
+
+enum {
+    DIRTY_UNKNOWN = 0,      /**< Not computed since last load/save */
+    DIRTY_CLEAN,
+    DIRTY_SET,
+};
+
+/** Per-parameter state, valid while changed_gen matches s_dirty_gen */
+static atomic_uchar s_dirty[CONFIG_ID_COUNT];
+static atomic_ushort s_dirty_gen[CONFIG_ID_COUNT];
+
+static void dirty_invalidate(void) {
+    for (size_t i = 0; i < CONFIG_ID_COUNT; i++) {
+        atomic_store_explicit(&s_dirty[i], DIRTY_UNKNOWN, memory_order_relaxed);
+    }
+}
+
+bool config_nvs_param_dirty(const char *name) {
+    const param_descriptor_t *p = config_find_param(name);
+    if (p == NULL || p->readonly) {
+        return false;
+    }
+    size_t id = (size_t)(p - CONSOLE_PARAMS);
+
+    /* Read the stamp first: a set racing with us leaves a stale stamp */
+    uint16_t gen = atomic_load_explicit(&g_config.changed_gen[id], memory_order_acquire);
+    uint8_t state = atomic_load_explicit(&s_dirty[id], memory_order_relaxed);
+    if (state != DIRTY_UNKNOWN &&
+        atomic_load_explicit(&s_dirty_gen[id], memory_order_relaxed) == gen) {
+        return state == DIRTY_SET;
+    }
+
+    char current[CONFIG_STR_MAX_LEN + 1];
+    char saved[CONFIG_STR_MAX_LEN + 1];
+    bool dirty = false;
+    config_get_param_str(p->full_path, current, sizeof(current));
+    int ret = config_nvs_get_param_str(p->full_path, saved, sizeof(saved));
+    if (ret == 0) {
+        dirty = (strcmp(current, saved) != 0);
+    } else if (ret == 1) {
+        dirty = !config_param_is_default(p);
+    }
+
+    atomic_store_explicit(&s_dirty_gen[id], gen, memory_order_relaxed);
+    atomic_store_explicit(&s_dirty[id], dirty ? DIRTY_SET : DIRTY_CLEAN, memory_order_relaxed);
+    return dirty;
+}
+
+size_t config_nvs_dirty_count(void) {
+    size_t count = 0;
+    for (size_t i = 0; i < CONSOLE_PARAM_COUNT; i++) {
+        if (config_nvs_param_dirty(CONSOLE_PARAMS[i].full_path)) {
+            count++;
+        }
+    }
+    return count;
+}
+
+/* ============================================================================
+ * Integrity CRC
+ * ============================================================================ */
+
+static bool s_integrity_failed = false;
+
+bool config_nvs_integrity_failed(void) {
+    return s_integrity_failed;
+}
+
+/**
+ * @brief CRC over every entry of a namespace except CONFIG_NVS_KEY_CRC
+ *
+ * Walks what is actually stored, not the parameter table, so keys of
+ * parameters added or removed by a firmware update don't matter. Entry
+ * CRCs (key, type, value) are summed: iteration order is unspecified.
+ */
+static esp_err_t namespace_crc(nvs_handle_t handle, const char *ns, uint32_t *out) {
+    nvs_iterator_t it = NULL;
+    uint32_t total = 0;
+    esp_err_t err = nvs_entry_find(NVS_DEFAULT_PART_NAME, ns, NVS_TYPE_ANY, &it);
+    while (err == ESP_OK) {
+        nvs_entry_info_t info;
+        nvs_entry_info(it, &info);
+        if (strcmp(info.key, CONFIG_NVS_KEY_CRC) != 0) {
+            uint8_t value[CONFIG_STR_MAX_LEN + 1];
+            size_t len = 0;
+            uint8_t type = (uint8_t)info.type;
+            memset(value, 0, sizeof(value));
+            switch (info.type) {
+                case NVS_TYPE_U8:
+                    len = 1;
+                    nvs_get_u8(handle, info.key, value);
+                    break;
+                case NVS_TYPE_I8: {
+                    int8_t v = 0;
+                    nvs_get_i8(handle, info.key, &v);
+                    len = sizeof(v);
+                    memcpy(value, &v, len);
+                    break;
+                }
+                case NVS_TYPE_U16: {
+                    uint16_t v = 0;
+                    nvs_get_u16(handle, info.key, &v);
+                    len = sizeof(v);
+                    memcpy(value, &v, len);
+                    break;
+                }
+                case NVS_TYPE_I16: {
+                    int16_t v = 0;
+                    nvs_get_i16(handle, info.key, &v);
+                    len = sizeof(v);
+                    memcpy(value, &v, len);
+                    break;
+                }
+                case NVS_TYPE_U32: {
+                    uint32_t v = 0;
+                    nvs_get_u32(handle, info.key, &v);
+                    len = sizeof(v);
+                    memcpy(value, &v, len);
+                    break;
+                }
+                case NVS_TYPE_I32: {
+                    int32_t v = 0;
+                    nvs_get_i32(handle, info.key, &v);
+                    len = sizeof(v);
+                    memcpy(value, &v, len);
+                    break;
+                }
+                case NVS_TYPE_STR:
+                    len = sizeof(value);
+                    if (nvs_get_str(handle, info.key, (char *)value, &len) != ESP_OK) {
+                        len = 0;  /* Longer than any parameter: key and type only */
+                    }
+                    break;
+                default:
+                    break;  /* Not written by this module: key and type only */
+            }
+            uint32_t crc = esp_rom_crc32_le(0, (const uint8_t *)info.key, (uint32_t)strlen(info.key));
+            crc = esp_rom_crc32_le(crc, &type, 1);
+            total += esp_rom_crc32_le(crc, value, (uint32_t)len);
+        }
+        err = nvs_entry_next(&it);
+    }
+    nvs_release_iterator(it);
+    if (err != ESP_ERR_NVS_NOT_FOUND) {
+        return err;
+    }
+    *out = total;
+    return ESP_OK;
+}
+
+/* ============================================================================
+ * Write-Back
+ * ============================================================================ */
+
+/* Updated by whichever task saves (console, HTTP, io_task autosave), each
+ * save blocking that task for the flash write and any page erase; plain
+ * counters, so concurrent saves or readers may see a mix of two saves */
+static config_nvs_stats_t s_stats;
+
+void config_nvs_get_stats(config_nvs_stats_t *out) {
+    *out = s_stats;
+}
+
+/* Set an entry only if the stored value differs (a read costs no flash wear) */
+@PUT_HELPERS@
+static esp_err_t put_str(nvs_handle_t handle, const char *key, const char *v) {
+    char old[CONFIG_STR_MAX_LEN + 1];
+    size_t len = sizeof(old);
+    if (nvs_get_str(handle, key, old, &len) == ESP_OK && strcmp(old, v) == 0) {
+        s_stats.unchanged++;
+        return ESP_OK;
+    }
+    esp_err_t err = nvs_set_str(handle, key, v);
+    if (err == ESP_OK) {
+        s_stats.written++;
+    }
+    return err;
+}
+
+/** Store the CRC of what the namespace holds now (caller commits) */
+static esp_err_t seal(nvs_handle_t handle, const char *ns) {
+    uint32_t crc = 0;
+    esp_err_t err = namespace_crc(handle, ns, &crc);
+    if (err == ESP_OK) {
+        err = put_u32(handle, CONFIG_NVS_KEY_CRC, crc);
+    }
+    return err;
+}
+
+esp_err_t config_nvs_reseal(void) {
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(CONFIG_NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err != ESP_OK) {
+        return err;
+    }
+    err = seal(handle, CONFIG_NVS_NAMESPACE);
+    if (err == ESP_OK) {
+        err = nvs_commit(handle);
+    }
+    nvs_close(handle);
+    return err;
+}
+
+/** Generation last seen by the autosave tick and when it appeared */
+static uint16_t s_autosave_gen = 0;
+static int64_t s_autosave_since_us = 0;
+static bool s_autosave_armed = false;
+
+void config_nvs_autosave_tick(int64_t now_us) {
+    uint32_t delay_s = @AUTOSAVE_DELAY@;
+    uint16_t gen = atomic_load_explicit(&g_config.generation, memory_order_acquire);
+    if (gen != s_autosave_gen) {
+        /* Restart the quiet period on every change */
+        s_autosave_gen = gen;
+        s_autosave_since_us = now_us;
+        s_autosave_armed = true;
+        return;
+    }
+    if (!s_autosave_armed || delay_s == 0 || config_batch_active(&g_config) ||
+        now_us - s_autosave_since_us < (int64_t)delay_s * 1000000) {
+        return;
+    }
+
+    s_autosave_armed = false;
+    if (config_nvs_dirty_count() > 0 && config_save_to_nvs() >= 0) {
+        s_stats.autosaves++;
+        printf("config: autosaved\\n");
+    }
+}
+
+/* ============================================================================
+ * Load / Save
+ * ============================================================================ */
+
 int config_load_from_nvs(void) {
+    int ret = config_load_from_nvs_ns(CONFIG_NVS_NAMESPACE);
+    s_integrity_failed = (ret == CONFIG_NVS_ERR_CRC);
+    dirty_invalidate();
+    return ret;
+}
+
+int config_save_to_nvs(void) {
+    int ret = config_save_to_nvs_ns(CONFIG_NVS_NAMESPACE);
+    dirty_invalidate();
+    return ret;
+}
+
+int config_load_from_nvs_ns(const char *ns) {
     nvs_handle_t handle;
-    esp_err_t err = nvs_open(CONFIG_NVS_NAMESPACE, NVS_READONLY, &handle);
+    esp_err_t err = nvs_open(ns, NVS_READONLY, &handle);
     if (err == ESP_ERR_NVS_NOT_FOUND) {
         return 0;  /* Namespace doesn't exist yet, use defaults */
     }
@@ -621,85 +1745,109 @@ int config_load_from_nvs(void) {
         return -1;
     }
 
+    /* Data saved before versioning has the schema 1 layout */
+    uint16_t schema = 1;
+    nvs_get_u16(handle, CONFIG_NVS_KEY_SCHEMA, &schema);
+    if (schema != CONFIG_NVS_SCHEMA_VERSION) {
+        printf("config: %s schema %u != %u, ignored\\n",
+               ns, (unsigned)schema, (unsigned)CONFIG_NVS_SCHEMA_VERSION);
+        nvs_close(handle);
+        return 0;
+    }
+
+    /* Verify before applying anything: corruption must not mix into the config */
+    uint32_t stored_crc;
+    if (nvs_get_u32(handle, CONFIG_NVS_KEY_CRC, &stored_crc) == ESP_OK) {
+        uint32_t crc = 0;
+        if (namespace_crc(handle, ns, &crc) != ESP_OK || crc != stored_crc) {
+            printf("config: %s CRC %08lx != %08lx, using defaults\\n",
+                   ns, (unsigned long)crc, (unsigned long)stored_crc);
+            nvs_close(handle);
+            return CONFIG_NVS_ERR_CRC;
+        }
+    }
+
     int loaded = 0;
+    int migrated = 0;
     uint8_t u8_val;
     uint16_t u16_val;
     uint32_t u32_val;
-    size_t str_len;
-
-"""
-
-    # Generate load code for each parameter
-    for p in params:
-        family = p.get('family', '')
-        ptype = p['type']
-        pname = p['name']
-
-        # Build NVS key macro name
-        if family:
-            nvs_key = f"NVS_{family.upper()}_{pname.upper()}"
-            config_path = f"g_config.{family}.{pname}"
-        else:
-            nvs_key = f"NVS_{pname.upper()}"
-            config_path = f"g_config.{pname}"
-
-        if ptype == 'u8' or ptype == 'enum':
-            code += f"""    /* Load {family}.{pname} */
-    if (nvs_get_u8(handle, {nvs_key}, &u8_val) == ESP_OK) {{
-        atomic_store_explicit(&{config_path}, u8_val, memory_order_relaxed);
-        loaded++;
-    }}
+@SIGNED_VARS@    size_t str_len;
+    char str_val[CONFIG_STR_MAX_LEN + 1];
 
 """
-        elif ptype == 'u16':
-            code += f"""    /* Load {family}.{pname} */
-    if (nvs_get_u16(handle, {nvs_key}, &u16_val) == ESP_OK) {{
-        atomic_store_explicit(&{config_path}, u16_val, memory_order_relaxed);
-        loaded++;
+    types = {p['type'] for p in params}
+    signed_vars = "".join(f"    int{t[1:]}_t {t}_val;\n" for t in ('i8', 'i16') if t in types)
+    code = code.replace('@SIGNED_VARS@', signed_vars)
+
+    # put_<type>() for every NVS integer type in use (schema/CRC need u16/u32)
+    put_types = ['u8', 'u16', 'u32'] + [t for t in ('i8', 'i16') if t in types]
+    put_helpers = ""
+    for t in put_types:
+        ctype = f"{'int' if t[0] == 'i' else 'uint'}{t[1:]}_t"
+        put_helpers += f"""static esp_err_t put_{t}(nvs_handle_t handle, const char *key, {ctype} v) {{
+    {ctype} old;
+    if (nvs_get_{t}(handle, key, &old) == ESP_OK && old == v) {{
+        s_stats.unchanged++;
+        return ESP_OK;
     }}
-
-"""
-        elif ptype == 'u32':
-            code += f"""    /* Load {family}.{pname} */
-    if (nvs_get_u32(handle, {nvs_key}, &u32_val) == ESP_OK) {{
-        atomic_store_explicit(&{config_path}, u32_val, memory_order_relaxed);
-        loaded++;
+    esp_err_t err = nvs_set_{t}(handle, key, v);
+    if (err == ESP_OK) {{
+        s_stats.written++;
     }}
+    return err;
+}}
 
 """
-        elif ptype == 'bool':
-            code += f"""    /* Load {family}.{pname} */
-    if (nvs_get_u8(handle, {nvs_key}, &u8_val) == ESP_OK) {{
-        atomic_store_explicit(&{config_path}, u8_val != 0, memory_order_relaxed);
-        loaded++;
-    }}
+    code = code.replace('@PUT_HELPERS@\n', put_helpers)
 
-"""
-        elif ptype == 'string':
-            max_len = p.get('max_length', 32)
-            code += f"""    /* Load {family}.{pname} */
-    str_len = sizeof({config_path});
-    if (nvs_get_str(handle, {nvs_key}, {config_path}, &str_len) == ESP_OK) {{
-        loaded++;
-    }}
+    autosave = next((p for p in params if p['name'] == 'autosave_sec'), None)
+    if autosave is not None:
+        fam = autosave.get('family')
+        path = f"{fam}.autosave_sec" if fam else "autosave_sec"
+        delay = f"atomic_load_explicit(&g_config.{path}, memory_order_relaxed);"
+    else:
+        delay = "0;  /* No autosave_sec parameter in the schema */"
+    code = code.replace('@AUTOSAVE_DELAY@;', delay)
 
-"""
+    # Generate load code for each parameter
+    for p in params:
+        code += nvs_load_block(p)
 
     code += """    nvs_close(handle);
+
+    /* Rewrite under the current keys so the old ones can be dropped */
+    if (migrated > 0) {
+        printf("config: %s migrated %d renamed key(s)\\n", ns, migrated);
+        config_save_to_nvs_ns(ns);
+    }
     return loaded;
 }
 
-int config_save_to_nvs(void) {
+int config_save_to_nvs_ns(const char *ns) {
     nvs_handle_t handle;
-    esp_err_t err = nvs_open(CONFIG_NVS_NAMESPACE, NVS_READWRITE, &handle);
+    esp_err_t err = nvs_open(ns, NVS_READWRITE, &handle);
     if (err != ESP_OK) {
+        s_stats.failures++;
         return -1;
     }
 
     int saved = 0;
+    int64_t start_us = esp_timer_get_time();
+@SAVE_STR_BUF@
+    if (put_u16(handle, CONFIG_NVS_KEY_SCHEMA, CONFIG_NVS_SCHEMA_VERSION) != ESP_OK) {
+        nvs_close(handle);
+        s_stats.failures++;
+        return -1;
+    }
 
 """
 
+    if any(is_string_type(p) for p in params):
+        code = code.replace('@SAVE_STR_BUF@\n', "    char str_val[CONFIG_STR_MAX_LEN + 1];\n\n")
+    else:
+        code = code.replace('@SAVE_STR_BUF@\n', "\n")
+
     # Generate save code for each parameter
     for p in params:
         family = p.get('family', '')
@@ -714,50 +1862,168 @@ int config_save_to_nvs(void) {
             nvs_key = f"NVS_{pname.upper()}"
             config_path = f"g_config.{pname}"
 
-        if ptype == 'u8' or ptype == 'enum':
-            code += f"""    /* Save {family}.{pname} */
-    if (nvs_set_u8(handle, {nvs_key},
-            atomic_load_explicit(&{config_path}, memory_order_relaxed)) == ESP_OK) {{
+        load = f"atomic_load_explicit(&{config_path}, memory_order_relaxed)"
+        copy = ""
+        if ptype == 'string':
+            copy = f"    CONFIG_STR_COPY({config_path}, str_val, sizeof(str_val));\n"
+            call = f"put_str(handle, {nvs_key}, str_val)"
+        elif ptype == 'bool':
+            call = f"put_u8(handle, {nvs_key},\n            {load} ? 1 : 0)"
+        else:
+            ctype = 'u8' if ptype == 'enum' else ptype
+            call = f"put_{ctype}(handle, {nvs_key},\n            {load})"
+        code += f"""    /* Save {family}.{pname} */
+{copy}    if ({call} == ESP_OK) {{
         saved++;
     }}
 
 """
-        elif ptype == 'u16':
-            code += f"""    /* Save {family}.{pname} */
-    if (nvs_set_u16(handle, {nvs_key},
-            atomic_load_explicit(&{config_path}, memory_order_relaxed)) == ESP_OK) {{
-        saved++;
-    }}
 
-"""
-        elif ptype == 'u32':
-            code += f"""    /* Save {family}.{pname} */
-    if (nvs_set_u32(handle, {nvs_key},
-            atomic_load_explicit(&{config_path}, memory_order_relaxed)) == ESP_OK) {{
-        saved++;
-    }}
+    old_keys = [k for p in params for k in p.get('old_nvs_keys', [])]
+    if old_keys:
+        code += "    /* Drop keys of renamed parameters (see old_nvs_keys) */\n"
+        for k in old_keys:
+            code += f"    nvs_erase_key(handle, \"{k}\");\n"
+        code += "\n"
+
+    code += """    /* Seal what is now stored */
+    err = seal(handle, ns);
+    if (err == ESP_OK) {
+        err = nvs_commit(handle);
+    }
+    nvs_close(handle);
 
-"""
-        elif ptype == 'bool':
-            code += f"""    /* Save {family}.{pname} */
-    if (nvs_set_u8(handle, {nvs_key},
-            atomic_load_explicit(&{config_path}, memory_order_relaxed) ? 1 : 0) == ESP_OK) {{
-        saved++;
-    }}
+    s_stats.last_us = (uint32_t)(esp_timer_get_time() - start_us);
+    if (err != ESP_OK) {
+        s_stats.failures++;
+        return -1;
+    }
+    s_stats.commits++;
+    return saved;
+}
 
-"""
-        elif ptype == 'string':
-            code += f"""    /* Save {family}.{pname} */
-    if (nvs_set_str(handle, {nvs_key}, {config_path}) == ESP_OK) {{
-        saved++;
-    }}
+int config_nvs_get_param_str(const char *name, char *buf, size_t len) {
+    const param_descriptor_t *p = config_find_param(name);
+    if (p == NULL || buf == NULL || len == 0) {
+        return -1;
+    }
+    if (p->readonly) {
+        return 1;  /* Never stored */
+    }
 
-"""
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(CONFIG_NVS_NAMESPACE, NVS_READONLY, &handle);
+    if (err == ESP_ERR_NVS_NOT_FOUND) {
+        return 1;  /* Nothing saved yet */
+    }
+    if (err != ESP_OK) {
+        return -1;
+    }
+
+    uint8_t u8_val;
+    uint16_t u16_val;
+    uint32_t u32_val;
+    int8_t i8_val;
+    int16_t i16_val;
+    size_t str_len = len;
 
-    code += """    err = nvs_commit(handle);
+    switch (p->type) {
+        case PARAM_TYPE_U8:
+        case PARAM_TYPE_ENUM:
+            err = nvs_get_u8(handle, p->nvs_key, &u8_val);
+            if (err == ESP_OK) {
+                snprintf(buf, len, "%u", u8_val);
+            }
+            break;
+        case PARAM_TYPE_U16:
+            err = nvs_get_u16(handle, p->nvs_key, &u16_val);
+            if (err == ESP_OK) {
+                snprintf(buf, len, "%u", u16_val);
+            }
+            break;
+        case PARAM_TYPE_U32:
+            err = nvs_get_u32(handle, p->nvs_key, &u32_val);
+            if (err == ESP_OK) {
+                snprintf(buf, len, "%lu", (unsigned long)u32_val);
+            }
+            break;
+        case PARAM_TYPE_I8:
+            err = nvs_get_i8(handle, p->nvs_key, &i8_val);
+            if (err == ESP_OK) {
+                snprintf(buf, len, "%d", i8_val);
+            }
+            break;
+        case PARAM_TYPE_I16:
+            err = nvs_get_i16(handle, p->nvs_key, &i16_val);
+            if (err == ESP_OK) {
+                snprintf(buf, len, "%d", i16_val);
+            }
+            break;
+        case PARAM_TYPE_BOOL:
+            err = nvs_get_u8(handle, p->nvs_key, &u8_val);
+            if (err == ESP_OK) {
+                snprintf(buf, len, "%s", u8_val ? "true" : "false");
+            }
+            break;
+        case PARAM_TYPE_STRING:
+            err = nvs_get_str(handle, p->nvs_key, buf, &str_len);
+            break;
+        default:
+            err = ESP_FAIL;
+            break;
+    }
     nvs_close(handle);
 
-    return (err == ESP_OK) ? saved : -1;
+    if (err == ESP_ERR_NVS_NOT_FOUND) {
+        return 1;
+    }
+    return (err == ESP_OK) ? 0 : -1;
+}
+
+/* ============================================================================
+ * Board Selection
+ * ============================================================================ */
+
+size_t config_board_init(void) {
+    char name[16] = "";
+    nvs_handle_t handle;
+    if (nvs_open(CONFIG_BOARD_NVS_NAMESPACE, NVS_READONLY, &handle) == ESP_OK) {
+        size_t len = sizeof(name);
+        if (nvs_get_str(handle, CONFIG_BOARD_NVS_KEY, name, &len) != ESP_OK) {
+            name[0] = '\\0';
+        }
+        nvs_close(handle);
+    }
+
+    int index = config_board_find(name);
+    if (index < 0 && name[0] != '\\0') {
+        printf("config: unknown board '%s' in NVS\\n", name);
+    }
+#ifdef CONFIG_KEYER_BOARD
+    if (index < 0) {
+        index = config_board_find(CONFIG_KEYER_BOARD);
+    }
+#endif
+    size_t board = (index >= 0) ? (size_t)index : 0;
+    config_board_apply(&g_config, board);
+    return board;
+}
+
+int config_board_save(const char *name) {
+    if (config_board_find(name) < 0) {
+        return -1;
+    }
+    nvs_handle_t handle;
+    esp_err_t err = nvs_open(CONFIG_BOARD_NVS_NAMESPACE, NVS_READWRITE, &handle);
+    if (err != ESP_OK) {
+        return -2;
+    }
+    err = nvs_set_str(handle, CONFIG_BOARD_NVS_KEY, name);
+    if (err == ESP_OK) {
+        err = nvs_commit(handle);
+    }
+    nvs_close(handle);
+    return (err == ESP_OK) ? 0 : -2;
 }
 
 """
@@ -800,8 +2066,10 @@ typedef union {
     uint8_t  u8;
     uint16_t u16;
     uint32_t u32;
+    int8_t   i8;
+    int16_t  i16;
     bool     b;
-    const char *str;  /**< For string parameters (points to config buffer) */
+    const char *str;  /**< For string parameters (points to config buffer, see CONFIG_STR_GET) */
 } param_value_t;
 
 /** Parameter type with validation */
@@ -812,6 +2080,8 @@ typedef enum {
     PARAM_TYPE_BOOL = 3,
     PARAM_TYPE_ENUM = 4,
     PARAM_TYPE_STRING = 5,
+    PARAM_TYPE_I8 = 6,
+    PARAM_TYPE_I16 = 7,
 } param_type_t;
 
 /** Family descriptor */
@@ -820,6 +2090,7 @@ typedef struct {
     const char *aliases;      /**< Comma-separated: "k" or "a,snd" */
     const char *description;
     uint8_t order;
+    const char *enabled_by;   /**< Bool parameter gating the family (NULL = always shown) */
 } family_descriptor_t;
 
 /** Parameter descriptor */
@@ -828,10 +2099,15 @@ typedef struct {
     const char *family;
     const char *full_path;    /**< "keyer.wpm" */
     param_type_t type;
-    uint32_t min;
+    uint32_t min;             /**< Signed types hold the int32_t bit pattern */
     uint32_t max;
     param_value_t (*get_fn)(void);
-    void (*set_fn)(param_value_t);
+    void (*set_fn)(param_value_t);  /**< NULL for read-only parameters */
+    void (*copy_fn)(char *, size_t);  /**< Strings: consistent copy (NULL otherwise) */
+    const char *default_str;  /**< Default, formatted as config_get_param_str() */
+    const char *nvs_key;      /**< NVS storage key (NULL if read-only) */
+    bool readonly;            /**< Diagnostic value: shown, never set or saved */
+    bool sensitive;           /**< Secret (password, key): never logged */
 } param_descriptor_t;
 
 """
@@ -853,12 +2129,47 @@ const family_descriptor_t *config_find_family(const char *name);
 /** Find parameter by name or full path */
 const param_descriptor_t *config_find_param(const char *name);
 
+/**
+ * @brief Check a family's gate (its enabled_by parameter)
+ * @return true if the family has no gate or the gate is on
+ */
+bool config_family_enabled(const family_descriptor_t *family);
+
+/**
+ * @brief Check whether listings should show a parameter
+ *
+ * False for parameters of a gated-off family ("wifi.ssid" while
+ * wifi.enabled=false). The gate parameter itself stays visible. Hidden
+ * parameters can still be read and set by name.
+ */
+bool config_param_visible(const param_descriptor_t *param);
+
 /** Get parameter value as string */
 int config_get_param_str(const char *name, char *buf, size_t len);
 
-/** Set parameter from string */
+/**
+ * @brief Set parameter from string
+ * @return 0 on success, -1 unknown parameter, -2 out of range,
+ *         -3 invalid boolean, -5 rejected by a cross-parameter constraint
+ *         (see config_constraint_error()), -6 read-only parameter
+ */
 int config_set_param_str(const char *name, const char *value);
 
+/**
+ * @brief Message of the constraint that rejected the last set
+ * @return Description (never NULL)
+ */
+const char *config_constraint_error(void);
+
+/**
+ * @brief Default of a parameter on the active board
+ * @return Board override if the board has one, else param->default_str
+ */
+const char *config_param_default(const param_descriptor_t *param);
+
+/** Check if parameter currently holds its (board) default value (always true if read-only) */
+bool config_param_is_default(const param_descriptor_t *param);
+
 /** Pattern matching visitor callback */
 typedef void (*param_visitor_fn)(const param_descriptor_t *param, void *ctx);
 
@@ -869,9 +2180,16 @@ typedef void (*param_visitor_fn)(const param_descriptor_t *param, void *ctx);
  * - "keyer.wpm"     exact match
  * - "keyer.*"       all direct params in keyer
  * - "keyer.**"      all params in keyer and subfamilies
+ * - "*.gpio_*"      '*' matches within a path segment
+ * - "*", "**"       all parameters
+ * - "*wpm*"         no '.': wildcard on the bare name, any family
  * - "hw.*"          alias expansion + wildcard
+ * - "@audio"        whole family (name or alias)
+ * - "keyer.w"       prefix of full path or bare name
+ *
+ * @return Number of parameters visited
  */
-void config_foreach_matching(const char *pattern, param_visitor_fn visitor, void *ctx);
+size_t config_foreach_matching(const char *pattern, param_visitor_fn visitor, void *ctx);
 
 #ifdef __cplusplus
 }
@@ -884,7 +2202,124 @@ void config_foreach_matching(const char *pattern, param_visitor_fn visitor, void
         f.write(code)
 
 
-def generate_config_console_c(params: List[Dict], families: List[Dict], output_dir: Path):
+def generate_constraints_c(params: List[Dict], constraints: List[Dict]) -> str:
+    """Generate config_check_constraints() for config_set_param_str()"""
+    code = """/* ============================================================================
+ * Cross-Parameter Constraints
+ * ============================================================================ */
+
+static const char *s_constraint_error = "";
+
+const char *config_constraint_error(void) {
+    return s_constraint_error;
+}
+
+/**
+ * @brief Numeric value of a non-string parameter (signed types sign-extended)
+ */
+static int64_t param_numeric(const param_descriptor_t *p, param_value_t v) {
+    switch (p->type) {
+        case PARAM_TYPE_U8:
+        case PARAM_TYPE_ENUM:
+            return v.u8;
+        case PARAM_TYPE_U16:
+            return v.u16;
+        case PARAM_TYPE_I8:
+            return v.i8;
+        case PARAM_TYPE_I16:
+            return v.i16;
+        case PARAM_TYPE_BOOL:
+            return v.b ? 1 : 0;
+        default:
+            return v.u32;
+    }
+}
+
+/**
+ * @brief Current numeric value of a parameter, or the proposed one if it is being set
+ */
+static int64_t constraint_value(size_t idx, size_t changed, int64_t proposed) {
+    if (idx == changed) {
+        return proposed;
+    }
+    return param_numeric(&CONSOLE_PARAMS[idx], CONSOLE_PARAMS[idx].get_fn());
+}
+
+/**
+ * @brief Check constraints involving a parameter against a proposed value
+ * @return NULL if all hold, else the message of the first violated one
+ */
+static const char *config_check_constraints(size_t changed, int64_t proposed) {
+"""
+    if not constraints:
+        code += "    (void)constraint_value;\n    (void)changed;\n    (void)proposed;\n"
+    for c in constraints:
+        ids = [get_param_id(params[v]) for kind, v in c['operands'] if kind == 'param']
+        guard = " || ".join(f"changed == {i}" for i in ids)
+        terms = []
+        for kind, v in c['operands']:
+            if kind == 'param':
+                terms.append(f"constraint_value({get_param_id(params[v])}, changed, proposed)")
+            else:
+                terms.append(str(v))
+        code += f"    /* {c['check']} */\n"
+        code += f"    if (({guard}) &&\n"
+        code += f"        !({terms[0]} {c['op']}\n"
+        code += f"          {terms[1]})) {{\n"
+        code += f"        return \"{c['message']}\";\n"
+        code += "    }\n"
+    code += "    return NULL;\n}\n\n"
+    return code
+
+
+def generate_board_defaults_c(boards: List[Dict]) -> str:
+    """Generate config_param_default(): board overrides as console strings"""
+    code = """
+/* ============================================================================
+ * Board Defaults
+ * ============================================================================ */
+
+typedef struct {
+    const char *full_path;
+    const char *value;
+} board_default_t;
+
+"""
+    for i, b in enumerate(boards):
+        if b['overrides']:
+            code += f"static const board_default_t BOARD_DEFAULTS_{i}[] = {{\n"
+            for o in b['overrides']:
+                code += f'    {{ "{o["param"]["full_path"]}", "{o["str_value"]}" }},\n'
+            code += "};\n\n"
+    code += """static const struct {
+    const board_default_t *list;
+    size_t count;
+} BOARD_DEFAULTS[CONFIG_BOARD_COUNT] = {
+"""
+    for i, b in enumerate(boards):
+        if b['overrides']:
+            code += f"    {{ BOARD_DEFAULTS_{i}, {len(b['overrides'])} }},  /* {b['name']} */\n"
+        else:
+            code += f"    {{ NULL, 0 }},  /* {b['name']} */\n"
+    code += """};
+
+const char *config_param_default(const param_descriptor_t *param) {
+    if (param == NULL) return "";
+    size_t board = config_board_active_index();
+    for (size_t i = 0; i < BOARD_DEFAULTS[board].count; i++) {
+        if (strcmp(BOARD_DEFAULTS[board].list[i].full_path, param->full_path) == 0) {
+            return BOARD_DEFAULTS[board].list[i].value;
+        }
+    }
+    return param->default_str;
+}
+
+"""
+    return code
+
+
+def generate_config_console_c(params: List[Dict], families: List[Dict], output_dir: Path,
+                              constraints: List[Dict], boards: List[Dict]):
     """Generate config_console.c - Console parameter registry implementation"""
 
     src_dir = output_dir.parent / "src"
@@ -914,7 +2349,8 @@ def generate_config_console_c(params: List[Dict], families: List[Dict], output_d
             aliases = ','.join(f.get('aliases', []))
             desc = f.get('description', {}).get('en', f['name'])
             order = f.get('order', 0)
-            code += f'    {{ "{f["name"]}", "{aliases}", "{desc}", {order} }},\n'
+            gate = f'"{f["enabled_by"]}"' if f.get('enabled_by') else 'NULL'
+            code += f'    {{ "{f["name"]}", "{aliases}", "{desc}", {order}, {gate} }},\n'
         code += "};\n\n"
 
     # Generate get/set functions for each parameter
@@ -944,7 +2380,7 @@ def generate_config_console_c(params: List[Dict], families: List[Dict], output_d
         code += "    param_value_t v;\n"
 
         if ptype == 'string':
-            code += f"    v.str = {config_path};\n"
+            code += f"    v.str = CONFIG_STR_GET({config_path});\n"
         elif ptype == 'bool':
             code += f"    v.b = atomic_load_explicit(&{config_path}, memory_order_relaxed);\n"
         elif ptype in ('u8', 'enum'):
@@ -953,17 +2389,25 @@ def generate_config_console_c(params: List[Dict], families: List[Dict], output_d
             code += f"    v.u16 = atomic_load_explicit(&{config_path}, memory_order_relaxed);\n"
         elif ptype == 'u32':
             code += f"    v.u32 = atomic_load_explicit(&{config_path}, memory_order_relaxed);\n"
+        elif ptype in ('i8', 'i16'):
+            code += f"    v.{ptype} = atomic_load_explicit(&{config_path}, memory_order_relaxed);\n"
 
         code += "    return v;\n"
         code += "}\n\n"
 
+        if ptype == 'string':
+            code += f"static void copy_{func_name}(char *buf, size_t len) {{\n"
+            code += f"    CONFIG_STR_COPY({config_path}, buf, len);\n"
+            code += "}\n\n"
+
+        if is_readonly(p):
+            continue
+
         # Generate setter
         code += f"static void set_{func_name}(param_value_t v) {{\n"
 
         if ptype == 'string':
-            max_len = p.get('max_length', 32)
-            code += f"    strncpy({config_path}, v.str, {max_len});\n"
-            code += f"    {config_path}[{max_len}] = '\\0';\n"
+            code += f"    CONFIG_STR_SET({config_path}, v.str);\n"
         elif ptype == 'bool':
             code += f"    atomic_store_explicit(&{config_path}, v.b, memory_order_relaxed);\n"
         elif ptype in ('u8', 'enum'):
@@ -972,8 +2416,10 @@ def generate_config_console_c(params: List[Dict], families: List[Dict], output_d
             code += f"    atomic_store_explicit(&{config_path}, v.u16, memory_order_relaxed);\n"
         elif ptype == 'u32':
             code += f"    atomic_store_explicit(&{config_path}, v.u32, memory_order_relaxed);\n"
+        elif ptype in ('i8', 'i16'):
+            code += f"    atomic_store_explicit(&{config_path}, v.{ptype}, memory_order_relaxed);\n"
 
-        code += "    config_bump_generation(&g_config);\n"
+        code += f"    config_mark_changed(&g_config, {get_param_id(p)});\n"
         code += "}\n\n"
 
     # Generate CONSOLE_PARAMS array
@@ -1000,6 +2446,8 @@ def generate_config_console_c(params: List[Dict], families: List[Dict], output_d
             'u8': 'PARAM_TYPE_U8',
             'u16': 'PARAM_TYPE_U16',
             'u32': 'PARAM_TYPE_U32',
+            'i8': 'PARAM_TYPE_I8',
+            'i16': 'PARAM_TYPE_I16',
             'bool': 'PARAM_TYPE_BOOL',
             'enum': 'PARAM_TYPE_ENUM',
             'string': 'PARAM_TYPE_STRING',
@@ -1019,14 +2467,39 @@ def generate_config_console_c(params: List[Dict], families: List[Dict], output_d
         elif ptype == 'string':
             min_val = 0
             max_val = p.get('max_length', 32)
+        elif ptype in SIGNED_LIMITS:
+            min_val, max_val = SIGNED_LIMITS[ptype]
         else:
             min_val = 0
             max_val = 0xFFFFFFFF
-
-        code += f'    {{ "{pname}", "{family}", "{full_path}", {param_type}, {min_val}, {max_val}, get_{func_name}, set_{func_name} }},\n'
+        if ptype in SIGNED_LIMITS:
+            # Descriptor bounds are uint32_t: store the int32_t bit pattern
+            min_val = f"(uint32_t)INT32_C({min_val})"
+            max_val = f"(uint32_t)INT32_C({max_val})"
+
+        default_str = get_default_str(p)
+        if is_readonly(p):
+            setter, nvs_key, readonly = "NULL", "NULL", "true"
+        else:
+            setter, nvs_key, readonly = f"set_{func_name}", f'"{p["nvs_key"]}"', "false"
+        sensitive = "true" if p.get('sensitive', False) else "false"
+        copier = f"copy_{func_name}" if ptype == 'string' else "NULL"
+        code += f'    {{ "{pname}", "{family}", "{full_path}", {param_type}, {min_val}, {max_val}, get_{func_name}, {setter}, {copier}, "{default_str}", {nvs_key}, {readonly}, {sensitive} }},\n'
 
     code += "};\n\n"
 
+    renames = [(old, get_param_id(p)) for p in params for old in p.get('old_names', [])]
+    if renames:
+        code += "/** Previous names of renamed parameters (old_names in parameters.yaml) */\n"
+        code += "static const struct {\n    const char *old_name;\n    size_t idx;\n}"
+        code += f" CONFIG_RENAMES[{len(renames)}] = {{\n"
+        for old_name, pid in renames:
+            code += f'    {{ "{old_name}", {pid} }},\n'
+        code += "};\n\n"
+
+    code += generate_constraints_c(params, constraints)
+    code += generate_board_defaults_c(boards)
+
     # Generate helper functions
     code += """/* ============================================================================
  * Helper Functions
@@ -1072,7 +2545,7 @@ const param_descriptor_t *config_find_param(const char *name) {
             return p;
         }
     }
-    return NULL;
+@RENAME_LOOKUP@    return NULL;
 }
 
 int config_get_param_str(const char *name, char *buf, size_t len) {
@@ -1081,6 +2554,11 @@ int config_get_param_str(const char *name, char *buf, size_t len) {
         return -1;
     }
 
+    if (p->type == PARAM_TYPE_STRING) {
+        p->copy_fn(buf, len);
+        return 0;
+    }
+
     param_value_t v = p->get_fn();
 
     switch (p->type) {
@@ -1094,12 +2572,15 @@ int config_get_param_str(const char *name, char *buf, size_t len) {
         case PARAM_TYPE_U32:
             snprintf(buf, len, "%lu", (unsigned long)v.u32);
             break;
+        case PARAM_TYPE_I8:
+            snprintf(buf, len, "%d", v.i8);
+            break;
+        case PARAM_TYPE_I16:
+            snprintf(buf, len, "%d", v.i16);
+            break;
         case PARAM_TYPE_BOOL:
             snprintf(buf, len, "%s", v.b ? "true" : "false");
             break;
-        case PARAM_TYPE_STRING:
-            snprintf(buf, len, "%s", v.str ? v.str : "");
-            break;
         default:
             return -1;
     }
@@ -1111,9 +2592,13 @@ int config_set_param_str(const char *name, const char *value) {
     if (p == NULL || value == NULL) {
         return -1;
     }
+    if (p->readonly) {
+        return -6;
+    }
 
     param_value_t v;
     unsigned long parsed;
+    long sparsed;
 
     switch (p->type) {
         case PARAM_TYPE_U8:
@@ -1138,6 +2623,18 @@ int config_set_param_str(const char *name, const char *value) {
             }
             v.u32 = (uint32_t)parsed;
             break;
+        case PARAM_TYPE_I8:
+        case PARAM_TYPE_I16:
+            sparsed = strtol(value, NULL, 0);
+            if (sparsed < (int32_t)p->min || sparsed > (int32_t)p->max) {
+                return -2;
+            }
+            if (p->type == PARAM_TYPE_I8) {
+                v.i8 = (int8_t)sparsed;
+            } else {
+                v.i16 = (int16_t)sparsed;
+            }
+            break;
         case PARAM_TYPE_BOOL:
             if (strcmp(value, "true") == 0 || strcmp(value, "1") == 0 ||
                 strcmp(value, "on") == 0 || strcmp(value, "yes") == 0) {
@@ -1156,69 +2653,142 @@ int config_set_param_str(const char *name, const char *value) {
             return -1;
     }
 
+    if (p->type != PARAM_TYPE_STRING) {
+        int64_t proposed = param_numeric(p, v);
+        const char *why = config_check_constraints((size_t)(p - CONSOLE_PARAMS), proposed);
+        if (why != NULL) {
+            s_constraint_error = why;
+            return -5;
+        }
+    }
+
     p->set_fn(v);
     return 0;
 }
 
-void config_foreach_matching(const char *pattern, param_visitor_fn visitor, void *ctx) {
-    if (pattern == NULL || visitor == NULL) return;
+bool config_param_is_default(const param_descriptor_t *param) {
+    if (param == NULL) return false;
+    if (param->readonly) return true;  /* Live value, no default to differ from */
+
+    char buf[72];
+    if (config_get_param_str(param->full_path, buf, sizeof(buf)) != 0) {
+        return false;
+    }
+    return strcmp(buf, config_param_default(param)) == 0;
+}
+
+bool config_family_enabled(const family_descriptor_t *family) {
+    if (family == NULL || family->enabled_by == NULL) return true;
+    const param_descriptor_t *gate = config_find_param(family->enabled_by);
+    return gate == NULL || gate->get_fn().b;
+}
 
-    /* Check for wildcard patterns */
-    const char *star = strchr(pattern, '*');
+bool config_param_visible(const param_descriptor_t *param) {
+    if (param == NULL) return false;
+    const family_descriptor_t *f = config_find_family(param->family);
+    if (f == NULL || f->enabled_by == NULL || strcmp(f->enabled_by, param->full_path) == 0) {
+        return true;
+    }
+    return config_family_enabled(f);
+}
 
-    if (star == NULL) {
-        /* Exact match */
-        const param_descriptor_t *p = config_find_param(pattern);
-        if (p != NULL) {
-            visitor(p, ctx);
+/**
+ * @brief Glob match on a dotted path: '*' stops at '.', '**' crosses it
+ */
+static bool path_glob_match(const char *pat, const char *str) {
+    while (*pat != '\\0') {
+        if (pat[0] == '*') {
+            bool deep = (pat[1] == '*');
+            const char *rest = pat + (deep ? 2 : 1);
+            for (const char *s = str; ; s++) {
+                if (path_glob_match(rest, s)) return true;
+                if (*s == '\\0' || (!deep && *s == '.')) return false;
+            }
         }
-        return;
+        if (*pat != *str) return false;
+        pat++;
+        str++;
+    }
+    return *str == '\\0';
+}
+
+size_t config_foreach_matching(const char *pattern, param_visitor_fn visitor, void *ctx) {
+    if (pattern == NULL || visitor == NULL) return 0;
+
+    size_t count = 0;
+
+    /* "@audio" - whole family (name or alias) */
+    if (pattern[0] == '@') {
+        const family_descriptor_t *f = config_find_family(pattern + 1);
+        if (f == NULL) return 0;
+        for (int i = 0; i < CONSOLE_PARAM_COUNT; i++) {
+            if (strcmp(CONSOLE_PARAMS[i].family, f->name) == 0) {
+                visitor(&CONSOLE_PARAMS[i], ctx);
+                count++;
+            }
+        }
+        return count;
     }
 
-    /* Get prefix (everything before the wildcard) */
-    size_t prefix_len = (size_t)(star - pattern);
-    char prefix[64];
-    if (prefix_len >= sizeof(prefix)) {
-        prefix_len = sizeof(prefix) - 1;
+    /* Expand family alias in first segment: "hw.*" -> "hardware.*" */
+    char expanded[64];
+    size_t seg_len = strcspn(pattern, ".");
+    if (seg_len > 0 && seg_len < 16 && memchr(pattern, '*', seg_len) == NULL) {
+        char seg[16];
+        memcpy(seg, pattern, seg_len);
+        seg[seg_len] = '\\0';
+        const family_descriptor_t *f = config_find_family(seg);
+        if (f != NULL) {
+            snprintf(expanded, sizeof(expanded), "%s%s", f->name, pattern + seg_len);
+            pattern = expanded;
+        }
     }
-    strncpy(prefix, pattern, prefix_len);
-    prefix[prefix_len] = '\\0';
 
-    /* Remove trailing dot from prefix if present */
-    if (prefix_len > 0 && prefix[prefix_len - 1] == '.') {
-        prefix[prefix_len - 1] = '\\0';
-        prefix_len--;
+    /* Wildcards; a single segment matches the bare name ("*" = everything) */
+    if (strchr(pattern, '*') != NULL) {
+        bool bare = (strchr(pattern, '.') == NULL);
+        for (int i = 0; i < CONSOLE_PARAM_COUNT; i++) {
+            const param_descriptor_t *p = &CONSOLE_PARAMS[i];
+            if (path_glob_match(pattern, bare ? p->name : p->full_path)) {
+                visitor(p, ctx);
+                count++;
+            }
+        }
+        return count;
     }
 
-    /* Check if double-star (recursive) */
-    bool recursive = (star[1] == '*');
+    /* Exact match */
+    const param_descriptor_t *exact = config_find_param(pattern);
+    if (exact != NULL) {
+        visitor(exact, ctx);
+        return 1;
+    }
 
-    /* Iterate all params */
+    /* Prefix match on full path or bare name: "keyer.w", "sidetone" */
+    size_t len = strlen(pattern);
     for (int i = 0; i < CONSOLE_PARAM_COUNT; i++) {
         const param_descriptor_t *p = &CONSOLE_PARAMS[i];
-
-        /* Check if family matches prefix */
-        if (prefix_len == 0) {
-            /* Empty prefix matches all */
+        if (strncmp(p->full_path, pattern, len) == 0 ||
+            strncmp(p->name, pattern, len) == 0) {
             visitor(p, ctx);
-        } else if (strncmp(p->full_path, prefix, prefix_len) == 0) {
-            /* Prefix matches */
-            if (recursive) {
-                /* ** matches all under this family */
-                visitor(p, ctx);
-            } else {
-                /* * only matches direct children (no more dots after prefix) */
-                const char *rest = p->full_path + prefix_len;
-                if (rest[0] == '.') rest++;  /* Skip the dot */
-                if (strchr(rest, '.') == NULL) {
-                    visitor(p, ctx);
-                }
-            }
+            count++;
         }
     }
+    return count;
 }
 """
 
+    rename_lookup = ""
+    if renames:
+        rename_lookup = """    /* Old names keep working in scripts written before a rename */
+    for (size_t i = 0; i < sizeof(CONFIG_RENAMES) / sizeof(CONFIG_RENAMES[0]); i++) {
+        if (strcmp(CONFIG_RENAMES[i].old_name, name) == 0) {
+            return &CONSOLE_PARAMS[CONFIG_RENAMES[i].idx];
+        }
+    }
+"""
+    code = code.replace('@RENAME_LOOKUP@', rename_lookup)
+
     with open(src_dir / "config_console.c", "w") as f:
         f.write(code)
 
@@ -0,0 +1,455 @@
+/**
+ * @file keyer_sim.c
+ * @brief Host simulator: the keying pipeline on a PC terminal
+ *
+ * Runs the same 1 ms pipeline as rt_task on the real components:
+ *
+ *   keyboard paddles ──► iambic FSM ──► KeyingStream ──┬──► hard RT consumer ──► sidetone, PTT
+ *                                                     └──► decoder (best effort) ──► text
+ *
+ * Paddles: 'z' or ',' = dit, 'x' or '.' = dah. A terminal only reports
+ * key presses, so each press closes the paddle for about one element;
+ * holding a key sends repeats through the terminal's auto-repeat.
+ * ':' opens the command line (wpm, mode, tone, tail, help, quit). It is the
+ * simulator's own small command set on the shared parser, not the device
+ * console: commands.c needs the HAL, NVS and network and is not host-built.
+ *
+ * Sidetone goes to an external player reading raw 16-bit mono PCM on
+ * stdin (default: aplay). POSIX terminals only.
+ *
+ * Usage: keyer_sim [--wpm N] [--mode a|b] [--tone HZ] [--audio CMD | --no-audio]
+ */
+
+#include "stream.h"
+#include "consumer.h"
+#include "fault.h"
+#include "iambic.h"
+#include "sidetone.h"
+#include "ptt.h"
+#include "decoder.h"
+#include "console.h"
+#include "keyer_clock.h"
+
+#include <errno.h>
+#include <signal.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <termios.h>
+#include <time.h>
+#include <unistd.h>
+
+/* Host-only hooks of the decoder (decoder.c, !ESP_PLATFORM) */
+void decoder_set_test_stream(keying_stream_t *stream);
+
+/* ============================================================================
+ * Configuration
+ * ============================================================================ */
+
+#define SIM_TICK_US         1000
+#define SIM_SAMPLE_RATE     8000
+#define SIM_SAMPLES_PER_TICK (SIM_SAMPLE_RATE / 1000)
+#define SIM_FADE_SAMPLES    (5 * SIM_SAMPLES_PER_TICK)
+#define SIM_STREAM_SIZE     4096
+#define SIM_DEFAULT_AUDIO   "aplay -q -t raw -f S16_LE -r 8000 -c 1"
+
+typedef struct {
+    iambic_config_t iambic;
+    uint32_t tone_hz;
+    uint32_t ptt_tail_ms;
+    const char *audio_cmd;  /**< NULL = no audio */
+} sim_config_t;
+
+/* ============================================================================
+ * Pipeline State
+ * ============================================================================ */
+
+static stream_sample_t s_stream_buffer[SIM_STREAM_SIZE];
+static keying_stream_t s_stream;
+static fault_state_t s_fault = FAULT_STATE_INIT;
+static iambic_processor_t s_iambic;
+static hard_rt_consumer_t s_consumer;
+static sidetone_gen_t s_sidetone;
+static ptt_controller_t s_ptt;
+static stream_sample_t s_out;
+
+static sim_config_t s_config;
+static FILE *s_audio = NULL;
+static volatile sig_atomic_t s_quit = 0;
+
+/* Paddle closures from key presses (absolute µs) */
+static int64_t s_dit_until_us = 0;
+static int64_t s_dah_until_us = 0;
+
+static uint32_t s_decoder_cursor = 0;
+
+/* ============================================================================
+ * Clock and Terminal
+ * ============================================================================ */
+
+static struct timespec s_start;
+
+static int64_t sim_now_us(void) {
+    struct timespec ts;
+    clock_gettime(CLOCK_MONOTONIC, &ts);
+    return (int64_t)(ts.tv_sec - s_start.tv_sec) * 1000000 +
+           (ts.tv_nsec - s_start.tv_nsec) / 1000;
+}
+
+static struct termios s_saved_termios;
+static bool s_raw = false;
+
+static void term_raw(bool on) {
+    if (on == s_raw) {
+        return;
+    }
+    if (on) {
+        struct termios raw = s_saved_termios;
+        raw.c_lflag &= ~(tcflag_t)(ICANON | ECHO);
+        raw.c_cc[VMIN] = 0;
+        raw.c_cc[VTIME] = 0;
+        tcsetattr(STDIN_FILENO, TCSANOW, &raw);
+    } else {
+        tcsetattr(STDIN_FILENO, TCSANOW, &s_saved_termios);
+    }
+    s_raw = on;
+}
+
+static void restore_terminal(void) {
+    term_raw(false);
+}
+
+static void on_signal(int sig) {
+    (void)sig;
+    s_quit = 1;
+}
+
+/* ============================================================================
+ * Audio
+ * ============================================================================ */
+
+static void audio_open(void) {
+    if (s_config.audio_cmd == NULL) {
+        return;
+    }
+    s_audio = popen(s_config.audio_cmd, "w");
+    if (s_audio == NULL) {
+        fprintf(stderr, "audio: cannot start '%s', running silent\n", s_config.audio_cmd);
+    }
+}
+
+static void audio_write(const int16_t *samples, size_t count) {
+    if (s_audio == NULL) {
+        return;
+    }
+    if (fwrite(samples, sizeof(int16_t), count, s_audio) != count) {
+        fprintf(stderr, "\r\naudio: player gone (%s), running silent\r\n", strerror(errno));
+        pclose(s_audio);
+        s_audio = NULL;
+    }
+}
+
+/* ============================================================================
+ * Commands
+ * ============================================================================ */
+
+static void apply_config(void) {
+    iambic_set_config(&s_iambic, &s_config.iambic);
+    sidetone_set_frequency(&s_sidetone, s_config.tone_hz);
+    ptt_set_tail(&s_ptt, s_config.ptt_tail_ms);
+}
+
+static void print_status(void) {
+    printf("wpm %u, mode %s, tone %u Hz, ptt tail %u ms (ptt %s), audio %s, decoder %u wpm\n",
+           (unsigned)s_config.iambic.wpm,
+           (s_config.iambic.mode == IAMBIC_MODE_A) ? "A" : "B",
+           (unsigned)s_config.tone_hz, (unsigned)s_config.ptt_tail_ms,
+           ptt_is_on(&s_ptt) ? "on" : "off",
+           (s_audio != NULL) ? "on" : "off",
+           (unsigned)decoder_get_wpm());
+}
+
+static void print_help(void) {
+    printf("Paddles: z or , = dit   x or . = dah   : = command line   q = quit\n"
+           "Commands:\n"
+           "  wpm <5-60>      Speed\n"
+           "  mode <a|b>      Iambic mode\n"
+           "  tone <hz>       Sidetone frequency\n"
+           "  tail <ms>       PTT tail\n"
+           "  status          Current settings\n"
+           "  quit            Exit\n");
+}
+
+/* console_error_message() lives in commands.c, which is not host-built */
+static const char *error_str(console_error_t err) {
+    switch (err) {
+        case CONSOLE_ERR_UNKNOWN_CMD:   return "unknown command (try 'help')";
+        case CONSOLE_ERR_INVALID_VALUE: return "invalid value";
+        case CONSOLE_ERR_MISSING_ARG:   return "missing argument";
+        case CONSOLE_ERR_OUT_OF_RANGE:  return "out of range";
+        default:                        return "error";
+    }
+}
+
+static bool parse_u32(const char *s, uint32_t min, uint32_t max, uint32_t *out) {
+    char *end;
+    unsigned long v = strtoul(s, &end, 10);
+    if (*s == '\0' || *end != '\0' || v < min || v > max) {
+        return false;
+    }
+    *out = (uint32_t)v;
+    return true;
+}
+
+static console_error_t run_command(const console_parsed_cmd_t *cmd) {
+    uint32_t value;
+    if (cmd->command == NULL) {
+        return CONSOLE_OK;
+    }
+    if (strcmp(cmd->command, "quit") == 0 || strcmp(cmd->command, "exit") == 0) {
+        s_quit = 1;
+    } else if (strcmp(cmd->command, "help") == 0 || strcmp(cmd->command, "?") == 0) {
+        print_help();
+    } else if (strcmp(cmd->command, "status") == 0) {
+        print_status();
+    } else if (cmd->argc < 1 && (strcmp(cmd->command, "wpm") == 0 ||
+                                 strcmp(cmd->command, "mode") == 0 ||
+                                 strcmp(cmd->command, "tone") == 0 ||
+                                 strcmp(cmd->command, "tail") == 0)) {
+        return CONSOLE_ERR_MISSING_ARG;
+    } else if (strcmp(cmd->command, "wpm") == 0) {
+        if (!parse_u32(cmd->args[0], 5, 60, &value)) {
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+        s_config.iambic.wpm = value;
+    } else if (strcmp(cmd->command, "mode") == 0) {
+        if (strcmp(cmd->args[0], "a") == 0 || strcmp(cmd->args[0], "A") == 0) {
+            s_config.iambic.mode = IAMBIC_MODE_A;
+        } else if (strcmp(cmd->args[0], "b") == 0 || strcmp(cmd->args[0], "B") == 0) {
+            s_config.iambic.mode = IAMBIC_MODE_B;
+        } else {
+            return CONSOLE_ERR_INVALID_VALUE;
+        }
+    } else if (strcmp(cmd->command, "tone") == 0) {
+        if (!parse_u32(cmd->args[0], 200, 2000, &value)) {
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+        s_config.tone_hz = value;
+    } else if (strcmp(cmd->command, "tail") == 0) {
+        if (!parse_u32(cmd->args[0], 0, 10000, &value)) {
+            return CONSOLE_ERR_OUT_OF_RANGE;
+        }
+        s_config.ptt_tail_ms = value;
+    } else {
+        return CONSOLE_ERR_UNKNOWN_CMD;
+    }
+    apply_config();
+    return CONSOLE_OK;
+}
+
+/**
+ * @brief Read one command line (keying pauses meanwhile)
+ */
+static void command_line(void) {
+    term_raw(false);
+    printf("\n> ");
+    fflush(stdout);
+
+    char line[128];
+    if (fgets(line, sizeof(line), stdin) == NULL) {
+        s_quit = 1;
+        return;
+    }
+    line[strcspn(line, "\r\n")] = '\0';
+
+    console_parsed_cmd_t cmd;
+    console_parse_line(line, &cmd);
+    console_error_t err = run_command(&cmd);
+    if (err != CONSOLE_OK) {
+        printf("%s\n", error_str(err));
+    }
+    if (!s_quit) {
+        term_raw(true);
+    }
+}
+
+/* ============================================================================
+ * Pipeline
+ * ============================================================================ */
+
+static void read_keys(int64_t now_us) {
+    /* A closure a bit longer than one element: one press, one element */
+    int64_t hold_us = iambic_dit_duration_us(&s_config.iambic) * 3 / 2;
+    char c;
+    while (read(STDIN_FILENO, &c, 1) == 1) {
+        switch (c) {
+            case 'z': case 'Z': case ',':
+                s_dit_until_us = now_us + hold_us;
+                break;
+            case 'x': case 'X': case '.':
+                s_dah_until_us = now_us + hold_us;
+                break;
+            case ':':
+                command_line();
+                return;
+            case 'q': case 'Q':
+                s_quit = 1;
+                return;
+            default:
+                break;
+        }
+    }
+}
+
+/**
+ * @brief One RT tick: paddles → iambic → stream → audio/PTT
+ */
+static void pipeline_tick(int64_t now_us) {
+    gpio_state_t gpio = gpio_from_paddles(now_us < s_dit_until_us, now_us < s_dah_until_us);
+    stream_sample_t sample = iambic_tick(&s_iambic, now_us, gpio);
+    if (!stream_push(&s_stream, sample)) {
+        fault_set(&s_fault, FAULT_PRODUCER_OVERRUN, 0);
+    }
+
+    if (hard_rt_consumer_tick(&s_consumer, &s_out) == HARD_RT_FAULT) {
+        printf("\r\nFAULT: %s, resync\r\n", fault_code_str(fault_get_code(&s_fault)));
+        fault_clear(&s_fault);
+        hard_rt_consumer_resync(&s_consumer);
+        sidetone_reset(&s_sidetone);
+        ptt_force_off(&s_ptt);
+        s_out = STREAM_SAMPLE_EMPTY;
+    }
+
+    bool key_down = s_out.local_key != 0;
+    if (key_down) {
+        ptt_audio_sample(&s_ptt, (uint64_t)now_us);
+    }
+    ptt_tick(&s_ptt, (uint64_t)now_us);
+
+    int16_t audio[SIM_SAMPLES_PER_TICK];
+    for (int i = 0; i < SIM_SAMPLES_PER_TICK; i++) {
+        audio[i] = sidetone_next_sample(&s_sidetone, key_down);
+    }
+    audio_write(audio, SIM_SAMPLES_PER_TICK);
+}
+
+static void decoder_tick(void) {
+    decoder_process();
+
+    decoded_char_t chars[16];
+    size_t count;
+    while ((count = decoder_read_since(&s_decoder_cursor, chars, 16)) > 0) {
+        for (size_t i = 0; i < count; i++) {
+            putchar(chars[i].character);
+        }
+    }
+    fflush(stdout);
+}
+
+/* ============================================================================
+ * Main
+ * ============================================================================ */
+
+static void usage(const char *prog) {
+    fprintf(stderr, "Usage: %s [--wpm N] [--mode a|b] [--tone HZ] [--audio CMD | --no-audio]\n",
+            prog);
+}
+
+static bool parse_args(int argc, char **argv) {
+    s_config.iambic = (iambic_config_t)IAMBIC_CONFIG_DEFAULT;
+    s_config.tone_hz = 600;
+    s_config.ptt_tail_ms = 300;
+    s_config.audio_cmd = SIM_DEFAULT_AUDIO;
+
+    for (int i = 1; i < argc; i++) {
+        const char *arg = argv[i];
+        const char *val = (i + 1 < argc) ? argv[i + 1] : NULL;
+        console_parsed_cmd_t cmd = { .command = arg + 2, .args = { val }, .argc = 1 };
+        if (strcmp(arg, "--no-audio") == 0) {
+            s_config.audio_cmd = NULL;
+        } else if (strcmp(arg, "--audio") == 0 && val != NULL) {
+            s_config.audio_cmd = val;
+            i++;
+        } else if ((strcmp(arg, "--wpm") == 0 || strcmp(arg, "--mode") == 0 ||
+                    strcmp(arg, "--tone") == 0) && val != NULL) {
+            if (run_command(&cmd) != CONSOLE_OK) {
+                fprintf(stderr, "bad value for %s: %s\n", arg, val);
+                return false;
+            }
+            i++;
+        } else {
+            usage(argv[0]);
+            return false;
+        }
+    }
+    return true;
+}
+
+int main(int argc, char **argv) {
+    /* Every module reads the terminal's monotonic clock */
+    clock_gettime(CLOCK_MONOTONIC, &s_start);
+    keyer_clock_set_source(sim_now_us);
+
+    stream_init(&s_stream, s_stream_buffer, SIM_STREAM_SIZE);
+    iambic_init(&s_iambic, &(iambic_config_t)IAMBIC_CONFIG_DEFAULT);
+    hard_rt_consumer_init(&s_consumer, &s_stream, &s_fault, 2);
+    sidetone_init(&s_sidetone, 600, SIM_SAMPLE_RATE, SIM_FADE_SAMPLES);
+    ptt_init(&s_ptt, 300);
+    s_out = STREAM_SAMPLE_EMPTY;
+
+    if (!parse_args(argc, argv)) {
+        return 2;
+    }
+
+    decoder_set_test_stream(&s_stream);
+    decoder_init();
+
+    signal(SIGINT, on_signal);
+    signal(SIGTERM, on_signal);
+    signal(SIGPIPE, SIG_IGN);
+    audio_open();
+
+    if (tcgetattr(STDIN_FILENO, &s_saved_termios) != 0) {
+        fprintf(stderr, "stdin is not a terminal\n");
+        return 1;
+    }
+    atexit(restore_terminal);
+
+    printf("keyer_sim - ");
+    print_status();
+    print_help();
+    term_raw(true);
+
+    struct timespec next;
+    clock_gettime(CLOCK_MONOTONIC, &next);
+    uint32_t tick = 0;
+
+    while (!s_quit) {
+        int64_t now_us = keyer_clock_now_us();
+        read_keys(now_us);
+        pipeline_tick(now_us);
+        if (++tick % 10 == 0) {
+            decoder_tick();
+        }
+
+        /* Absolute deadlines: no drift; after a stall (command line) catch up */
+        next.tv_nsec += SIM_TICK_US * 1000;
+        if (next.tv_nsec >= 1000000000L) {
+            next.tv_nsec -= 1000000000L;
+            next.tv_sec++;
+        }
+        struct timespec now;
+        clock_gettime(CLOCK_MONOTONIC, &now);
+        if (now.tv_sec > next.tv_sec + 1) {
+            next = now;
+        }
+        clock_nanosleep(CLOCK_MONOTONIC, TIMER_ABSTIME, &next, NULL);
+    }
+
+    term_raw(false);
+    if (s_audio != NULL) {
+        pclose(s_audio);
+    }
+    printf("\n");
+    return 0;
+}
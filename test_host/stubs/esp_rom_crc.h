@@ -0,0 +1,23 @@
+/**
+ * @file esp_rom_crc.h
+ * @brief Host version of the ROM CRC32 (same results as the target)
+ */
+
+#ifndef ESP_ROM_CRC_H
+#define ESP_ROM_CRC_H
+
+#include <stdint.h>
+
+/* CRC-32 (IEEE 802.3), little endian; crc is the previous result, 0 to start */
+static inline uint32_t esp_rom_crc32_le(uint32_t crc, const uint8_t *buf, uint32_t len) {
+    crc = ~crc;
+    for (uint32_t i = 0; i < len; i++) {
+        crc ^= buf[i];
+        for (int bit = 0; bit < 8; bit++) {
+            crc = (crc >> 1) ^ (0xEDB88320U & (0U - (crc & 1U)));
+        }
+    }
+    return ~crc;
+}
+
+#endif /* ESP_ROM_CRC_H */
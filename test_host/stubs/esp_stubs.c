@@ -4,14 +4,13 @@
  */
 
 #include "esp_stubs.h"
+#include "keyer_clock.h"
 
-/* Simulated time for testing */
-static int64_t s_simulated_time_us = 0;
-
+/* Simulated time for testing: the same clock keyer_clock_now_us() reads */
 int64_t esp_timer_get_time(void) {
-    return s_simulated_time_us;
+    return keyer_clock_now_us();
 }
 
 void esp_timer_set_time(int64_t time_us) {
-    s_simulated_time_us = time_us;
+    keyer_clock_sim_set(time_us);
 }
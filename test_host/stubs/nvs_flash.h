@@ -0,0 +1,11 @@
+/**
+ * @file nvs_flash.h
+ * @brief Stub for nvs_flash.h during host testing
+ */
+
+#ifndef NVS_FLASH_H
+#define NVS_FLASH_H
+
+#include "nvs.h"
+
+#endif /* NVS_FLASH_H */
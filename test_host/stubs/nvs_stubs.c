@@ -0,0 +1,306 @@
+/**
+ * @file nvs_stubs.c
+ * @brief In-memory NVS for host testing
+ *
+ * A flat table of entries tagged with their namespace. A handle is the
+ * index of its namespace plus one, with the read-only flag in bit 16.
+ */
+
+#include "nvs.h"
+#include <string.h>
+
+#define NVS_STUB_MAX_NS 8
+#define NVS_STUB_MAX_ENTRIES 512
+#define NVS_STUB_STR_MAX 4000
+
+#define HANDLE_READONLY 0x10000U
+
+typedef struct {
+    bool used;
+    uint8_t ns;
+    nvs_type_t type;
+    char key[NVS_KEY_NAME_MAX_SIZE];
+    int64_t num;
+    char str[NVS_STUB_STR_MAX];
+} nvs_stub_entry_t;
+
+struct nvs_stub_iterator {
+    uint8_t ns;
+    nvs_type_t type;
+    size_t next;
+};
+
+static char s_ns[NVS_STUB_MAX_NS][NVS_NS_NAME_MAX_SIZE];
+static size_t s_ns_count;
+static nvs_stub_entry_t s_entries[NVS_STUB_MAX_ENTRIES];
+static struct nvs_stub_iterator s_iter;
+
+void nvs_stub_reset(void) {
+    memset(s_ns, 0, sizeof(s_ns));
+    s_ns_count = 0;
+    memset(s_entries, 0, sizeof(s_entries));
+}
+
+static int find_ns(const char *ns) {
+    for (size_t i = 0; i < s_ns_count; i++) {
+        if (strcmp(s_ns[i], ns) == 0) {
+            return (int)i;
+        }
+    }
+    return -1;
+}
+
+size_t nvs_stub_count(const char *ns) {
+    int index = find_ns(ns);
+    size_t count = 0;
+    for (size_t i = 0; index >= 0 && i < NVS_STUB_MAX_ENTRIES; i++) {
+        if (s_entries[i].used && s_entries[i].ns == (uint8_t)index) {
+            count++;
+        }
+    }
+    return count;
+}
+
+/* ============================================================================
+ * Handles
+ * ============================================================================ */
+
+esp_err_t nvs_open(const char *ns, nvs_open_mode_t mode, nvs_handle_t *out) {
+    if (strlen(ns) >= NVS_NS_NAME_MAX_SIZE) {
+        return ESP_ERR_NVS_KEY_TOO_LONG;
+    }
+    int index = find_ns(ns);
+    if (index < 0) {
+        if (mode == NVS_READONLY) {
+            return ESP_ERR_NVS_NOT_FOUND;
+        }
+        if (s_ns_count >= NVS_STUB_MAX_NS) {
+            return ESP_ERR_NVS_NOT_ENOUGH_SPACE;
+        }
+        strcpy(s_ns[s_ns_count], ns);
+        index = (int)s_ns_count++;
+    }
+    *out = (nvs_handle_t)index + 1U;
+    if (mode == NVS_READONLY) {
+        *out |= HANDLE_READONLY;
+    }
+    return ESP_OK;
+}
+
+void nvs_close(nvs_handle_t handle) {
+    (void)handle;
+}
+
+static bool handle_valid(nvs_handle_t handle) {
+    uint32_t index = handle & ~HANDLE_READONLY;
+    return index >= 1 && index <= s_ns_count;
+}
+
+static uint8_t handle_ns(nvs_handle_t handle) {
+    return (uint8_t)((handle & ~HANDLE_READONLY) - 1U);
+}
+
+esp_err_t nvs_commit(nvs_handle_t handle) {
+    return handle_valid(handle) ? ESP_OK : ESP_ERR_NVS_INVALID_HANDLE;
+}
+
+/* ============================================================================
+ * Entries
+ * ============================================================================ */
+
+static nvs_stub_entry_t *find_entry(nvs_handle_t handle, const char *key) {
+    for (size_t i = 0; i < NVS_STUB_MAX_ENTRIES; i++) {
+        nvs_stub_entry_t *e = &s_entries[i];
+        if (e->used && e->ns == handle_ns(handle) && strcmp(e->key, key) == 0) {
+            return e;
+        }
+    }
+    return NULL;
+}
+
+/** Entry to overwrite: the existing one for key (any type) or a free one */
+static esp_err_t writable_entry(nvs_handle_t handle, const char *key, nvs_stub_entry_t **out) {
+    if (!handle_valid(handle)) {
+        return ESP_ERR_NVS_INVALID_HANDLE;
+    }
+    if (handle & HANDLE_READONLY) {
+        return ESP_ERR_NVS_READ_ONLY;
+    }
+    if (strlen(key) >= NVS_KEY_NAME_MAX_SIZE) {
+        return ESP_ERR_NVS_KEY_TOO_LONG;
+    }
+    nvs_stub_entry_t *e = find_entry(handle, key);
+    for (size_t i = 0; e == NULL && i < NVS_STUB_MAX_ENTRIES; i++) {
+        if (!s_entries[i].used) {
+            e = &s_entries[i];
+        }
+    }
+    if (e == NULL) {
+        return ESP_ERR_NVS_NOT_ENOUGH_SPACE;
+    }
+    memset(e, 0, sizeof(*e));
+    e->used = true;
+    e->ns = handle_ns(handle);
+    strcpy(e->key, key);
+    *out = e;
+    return ESP_OK;
+}
+
+esp_err_t nvs_erase_key(nvs_handle_t handle, const char *key) {
+    if (!handle_valid(handle)) {
+        return ESP_ERR_NVS_INVALID_HANDLE;
+    }
+    if (handle & HANDLE_READONLY) {
+        return ESP_ERR_NVS_READ_ONLY;
+    }
+    nvs_stub_entry_t *e = find_entry(handle, key);
+    if (e == NULL) {
+        return ESP_ERR_NVS_NOT_FOUND;
+    }
+    e->used = false;
+    return ESP_OK;
+}
+
+esp_err_t nvs_erase_all(nvs_handle_t handle) {
+    if (!handle_valid(handle)) {
+        return ESP_ERR_NVS_INVALID_HANDLE;
+    }
+    if (handle & HANDLE_READONLY) {
+        return ESP_ERR_NVS_READ_ONLY;
+    }
+    for (size_t i = 0; i < NVS_STUB_MAX_ENTRIES; i++) {
+        if (s_entries[i].ns == handle_ns(handle)) {
+            s_entries[i].used = false;
+        }
+    }
+    return ESP_OK;
+}
+
+static esp_err_t set_num(nvs_handle_t handle, const char *key, nvs_type_t type, int64_t value) {
+    nvs_stub_entry_t *e;
+    esp_err_t err = writable_entry(handle, key, &e);
+    if (err == ESP_OK) {
+        e->type = type;
+        e->num = value;
+    }
+    return err;
+}
+
+static esp_err_t get_num(nvs_handle_t handle, const char *key, nvs_type_t type, int64_t *out) {
+    if (!handle_valid(handle)) {
+        return ESP_ERR_NVS_INVALID_HANDLE;
+    }
+    const nvs_stub_entry_t *e = find_entry(handle, key);
+    if (e == NULL) {
+        return ESP_ERR_NVS_NOT_FOUND;
+    }
+    if (e->type != type) {
+        return ESP_ERR_NVS_TYPE_MISMATCH;
+    }
+    *out = e->num;
+    return ESP_OK;
+}
+
+#define NVS_STUB_NUM(suffix, ctype, nvs_type) \
+    esp_err_t nvs_set_##suffix(nvs_handle_t handle, const char *key, ctype value) { \
+        return set_num(handle, key, nvs_type, (int64_t)value); \
+    } \
+    esp_err_t nvs_get_##suffix(nvs_handle_t handle, const char *key, ctype *out) { \
+        int64_t value = 0; \
+        esp_err_t err = get_num(handle, key, nvs_type, &value); \
+        if (err == ESP_OK) { \
+            *out = (ctype)value; \
+        } \
+        return err; \
+    }
+
+NVS_STUB_NUM(u8, uint8_t, NVS_TYPE_U8)
+NVS_STUB_NUM(i8, int8_t, NVS_TYPE_I8)
+NVS_STUB_NUM(u16, uint16_t, NVS_TYPE_U16)
+NVS_STUB_NUM(i16, int16_t, NVS_TYPE_I16)
+NVS_STUB_NUM(u32, uint32_t, NVS_TYPE_U32)
+NVS_STUB_NUM(i32, int32_t, NVS_TYPE_I32)
+
+esp_err_t nvs_set_str(nvs_handle_t handle, const char *key, const char *value) {
+    if (strlen(value) >= NVS_STUB_STR_MAX) {
+        return ESP_ERR_NVS_INVALID_LENGTH;
+    }
+    nvs_stub_entry_t *e;
+    esp_err_t err = writable_entry(handle, key, &e);
+    if (err == ESP_OK) {
+        e->type = NVS_TYPE_STR;
+        strcpy(e->str, value);
+    }
+    return err;
+}
+
+esp_err_t nvs_get_str(nvs_handle_t handle, const char *key, char *out, size_t *length) {
+    if (!handle_valid(handle)) {
+        return ESP_ERR_NVS_INVALID_HANDLE;
+    }
+    const nvs_stub_entry_t *e = find_entry(handle, key);
+    if (e == NULL) {
+        return ESP_ERR_NVS_NOT_FOUND;
+    }
+    if (e->type != NVS_TYPE_STR) {
+        return ESP_ERR_NVS_TYPE_MISMATCH;
+    }
+    size_t needed = strlen(e->str) + 1;
+    if (out == NULL) {
+        *length = needed;
+        return ESP_OK;
+    }
+    if (*length < needed) {
+        return ESP_ERR_NVS_INVALID_LENGTH;
+    }
+    memcpy(out, e->str, needed);
+    *length = needed;
+    return ESP_OK;
+}
+
+/* ============================================================================
+ * Iteration (one iterator at a time)
+ * ============================================================================ */
+
+static esp_err_t advance(nvs_iterator_t *it) {
+    for (size_t i = (*it)->next; i < NVS_STUB_MAX_ENTRIES; i++) {
+        const nvs_stub_entry_t *e = &s_entries[i];
+        if (e->used && e->ns == (*it)->ns &&
+            ((*it)->type == NVS_TYPE_ANY || e->type == (*it)->type)) {
+            (*it)->next = i;
+            return ESP_OK;
+        }
+    }
+    *it = NULL;
+    return ESP_ERR_NVS_NOT_FOUND;
+}
+
+esp_err_t nvs_entry_find(const char *part, const char *ns, nvs_type_t type, nvs_iterator_t *it) {
+    (void)part;
+    *it = NULL;
+    int index = find_ns(ns);
+    if (index < 0) {
+        return ESP_ERR_NVS_NOT_FOUND;
+    }
+    s_iter = (struct nvs_stub_iterator){ .ns = (uint8_t)index, .type = type, .next = 0 };
+    *it = &s_iter;
+    return advance(it);
+}
+
+esp_err_t nvs_entry_next(nvs_iterator_t *it) {
+    (*it)->next++;
+    return advance(it);
+}
+
+esp_err_t nvs_entry_info(const nvs_iterator_t it, nvs_entry_info_t *out) {
+    const nvs_stub_entry_t *e = &s_entries[it->next];
+    memset(out, 0, sizeof(*out));
+    strcpy(out->namespace_name, s_ns[e->ns]);
+    strcpy(out->key, e->key);
+    out->type = e->type;
+    return ESP_OK;
+}
+
+void nvs_release_iterator(nvs_iterator_t it) {
+    (void)it;
+}
@@ -0,0 +1,9 @@
+/**
+ * @file sdkconfig.h
+ * @brief Empty Kconfig output for host testing (every option at its default)
+ */
+
+#ifndef SDKCONFIG_H
+#define SDKCONFIG_H
+
+#endif /* SDKCONFIG_H */
@@ -0,0 +1,81 @@
+/**
+ * @file test_abbrev.c
+ * @brief Unit tests for keyboard mode abbreviations
+ */
+
+#include "unity.h"
+#include "abbrev.h"
+#include <string.h>
+
+/**
+ * @brief Type a string through the shortcut input, collect what is queued
+ */
+static void type_text(abbrev_input_t *in, const char *typed, char *queued, size_t len) {
+    size_t pos = 0;
+    for (const char *p = typed; *p != '\0'; p++) {
+        char out[ABBREV_TEXT_MAX + ABBREV_KEY_MAX + 2];
+        size_t n = abbrev_input_feed(in, *p, out, sizeof(out));
+        for (size_t i = 0; i < n && pos + 1 < len; i++) {
+            queued[pos++] = out[i];
+        }
+    }
+    queued[pos] = '\0';
+}
+
+void test_abbrev_table(void) {
+    abbrev_init();
+
+    TEST_ASSERT_EQUAL_STRING("TU 73 EE", abbrev_lookup("tu"));
+    TEST_ASSERT_NULL(abbrev_lookup("QRZ"));
+
+    TEST_ASSERT_EQUAL(0, abbrev_set("qrz", "qrz?"));
+    TEST_ASSERT_EQUAL_STRING("QRZ?", abbrev_lookup("QRZ"));
+    TEST_ASSERT_EQUAL(0, abbrev_set("qrz", "QRZ DE IU3QEZ"));
+    TEST_ASSERT_EQUAL_STRING("QRZ DE IU3QEZ", abbrev_lookup("qrz"));
+
+    TEST_ASSERT_EQUAL(0, abbrev_set("qrz", NULL));
+    TEST_ASSERT_NULL(abbrev_lookup("qrz"));
+
+    TEST_ASSERT_EQUAL(-1, abbrev_set("", "X"));
+    TEST_ASSERT_EQUAL(-1, abbrev_set("TOOLONGKEY", "X"));
+    TEST_ASSERT_EQUAL(-1, abbrev_set("A B", "X"));
+}
+
+void test_abbrev_expands_typed_shortcut(void) {
+    abbrev_input_t in;
+    char queued[128];
+    abbrev_init();
+    abbrev_input_init(&in);
+
+    type_text(&in, "R ;TU ", queued, sizeof(queued));
+    TEST_ASSERT_EQUAL_STRING("R TU 73 EE ", queued);
+
+    /* Unknown shortcut goes out as typed */
+    type_text(&in, ";XYZ?", queued, sizeof(queued));
+    TEST_ASSERT_EQUAL_STRING(";XYZ?", queued);
+
+    /* ";;" is a plain ';' */
+    type_text(&in, ";;", queued, sizeof(queued));
+    TEST_ASSERT_EQUAL_STRING(";", queued);
+}
+
+void test_abbrev_backspace_and_flush(void) {
+    abbrev_input_t in;
+    char queued[64];
+    char held[16];
+    abbrev_init();
+    abbrev_input_init(&in);
+
+    type_text(&in, ";TUX", queued, sizeof(queued));
+    TEST_ASSERT_EQUAL_STRING("", queued);
+    TEST_ASSERT_EQUAL(4, abbrev_input_held(&in, held, sizeof(held)));
+    TEST_ASSERT_EQUAL_STRING(";TUX", held);
+
+    TEST_ASSERT_TRUE(abbrev_input_backspace(&in));
+    size_t n = abbrev_input_flush(&in, queued, sizeof(queued));
+    queued[n] = '\0';
+    TEST_ASSERT_EQUAL_STRING("TU 73 EE", queued);
+
+    /* Nothing held: backspace belongs to the queue */
+    TEST_ASSERT_FALSE(abbrev_input_backspace(&in));
+}
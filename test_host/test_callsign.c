@@ -0,0 +1,104 @@
+/**
+ * @file test_callsign.c
+ * @brief Unit tests for callsign recognition, capture and %C substitution
+ */
+
+#include "unity.h"
+#include "callsign.h"
+#include "decoder.h"
+#include "morse_table.h"
+#include <string.h>
+
+/**
+ * @brief Feed text to the decoder as dit/dah events (' ' = word gap)
+ */
+static void key_text(const char *text) {
+    int64_t t = 1000;
+    for (const char *c = text; *c != '\0'; c++) {
+        if (*c == ' ') {
+            decoder_handle_event(KEY_EVENT_WORD_GAP, t);
+            continue;
+        }
+        const char *pattern = morse_table_reverse(*c);
+        for (const char *e = pattern; *e != '\0'; e++) {
+            decoder_handle_event((*e == '.') ? KEY_EVENT_DIT : KEY_EVENT_DAH, t);
+            t += 1000;
+            if (e[1] != '\0') {
+                decoder_handle_event(KEY_EVENT_INTRA_GAP, t);
+            }
+        }
+        if (c[1] != ' ' && c[1] != '\0') {
+            decoder_handle_event(KEY_EVENT_CHAR_GAP, t);
+        }
+    }
+}
+
+void test_callsign_shapes(void) {
+    TEST_ASSERT_TRUE(callsign_is_plausible("K1ABC"));
+    TEST_ASSERT_TRUE(callsign_is_plausible("IU3QEZ"));
+    TEST_ASSERT_TRUE(callsign_is_plausible("2E0ABC"));
+    TEST_ASSERT_TRUE(callsign_is_plausible("4U1ITU"));
+    TEST_ASSERT_TRUE(callsign_is_plausible("W1AW"));
+    TEST_ASSERT_TRUE(callsign_is_plausible("IU3QEZ/P"));
+    TEST_ASSERT_TRUE(callsign_is_plausible("DL/K1ABC"));
+
+    TEST_ASSERT_FALSE(callsign_is_plausible("5NN"));
+    TEST_ASSERT_FALSE(callsign_is_plausible("599"));
+    TEST_ASSERT_FALSE(callsign_is_plausible("TU"));
+    TEST_ASSERT_FALSE(callsign_is_plausible("73"));
+    TEST_ASSERT_FALSE(callsign_is_plausible("TEST"));
+    TEST_ASSERT_FALSE(callsign_is_plausible("R2"));
+    TEST_ASSERT_FALSE(callsign_is_plausible("K1ABCDE"));     /* Suffix too long */
+    TEST_ASSERT_FALSE(callsign_is_plausible("K1ABC//P"));
+    TEST_ASSERT_FALSE(callsign_is_plausible("K1ABC/PORTABLE"));
+
+    TEST_ASSERT_TRUE(callsign_same_station("iu3qez", "IU3QEZ/P"));
+    TEST_ASSERT_FALSE(callsign_same_station("IU3QEZ", "K1ABC"));
+}
+
+void test_callsign_expand(void) {
+    char out[32];
+
+    TEST_ASSERT_TRUE(callsign_expand("%C 5NN %N", "K1ABC", out, sizeof(out)));
+    TEST_ASSERT_EQUAL_STRING("K1ABC 5NN %N", out);  /* %N left for contest_serial */
+
+    TEST_ASSERT_TRUE(callsign_expand("%c TU", "", out, sizeof(out)));
+    TEST_ASSERT_EQUAL_STRING(" TU", out);
+
+    TEST_ASSERT_FALSE(callsign_expand("CQ 100%", "K1ABC", out, sizeof(out)));
+    TEST_ASSERT_EQUAL_STRING("CQ 100%", out);
+
+    /* No room: the call is dropped, not cut */
+    TEST_ASSERT_TRUE(callsign_expand("TU %C", "K1ABC", out, 7));
+    TEST_ASSERT_EQUAL_STRING("TU ", out);
+}
+
+void test_callsign_capture(void) {
+    char call[CALLSIGN_MAX_LEN + 1];
+
+    decoder_reset();
+    decoder_set_own_callsign("IU3QEZ");
+    TEST_ASSERT_TRUE(decoder_set_last_callsign(""));
+    TEST_ASSERT_EQUAL(0, decoder_get_last_callsign(call, sizeof(call)));
+
+    /* Exchange words and our own call leave it alone */
+    key_text("CQ TEST IU3QEZ ");
+    TEST_ASSERT_EQUAL(0, decoder_get_last_callsign(call, sizeof(call)));
+
+    key_text("K1ABC 5NN 001 ");
+    TEST_ASSERT_EQUAL(5, decoder_get_last_callsign(call, sizeof(call)));
+    TEST_ASSERT_EQUAL_STRING("K1ABC", call);
+
+    key_text("TU IU3QEZ/P ");
+    decoder_get_last_callsign(call, sizeof(call));
+    TEST_ASSERT_EQUAL_STRING("K1ABC", call);
+
+    /* Manual correction */
+    TEST_ASSERT_FALSE(decoder_set_last_callsign("5NN"));
+    TEST_ASSERT_TRUE(decoder_set_last_callsign("k1abd"));
+    decoder_get_last_callsign(call, sizeof(call));
+    TEST_ASSERT_EQUAL_STRING("K1ABD", call);
+
+    decoder_set_own_callsign("");
+    TEST_ASSERT_TRUE(decoder_set_last_callsign(""));
+}
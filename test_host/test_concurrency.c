@@ -0,0 +1,386 @@
+/**
+ * @file test_concurrency.c
+ * @brief Threaded tests of the lock-free buffers and the sink tables
+ *
+ * The unit tests in test_runner exercise the buffers from one thread,
+ * which says nothing about interleavings. Here real threads hammer each
+ * buffer and every value that comes out is checked against what went in.
+ * The buffer sources are compiled into this executable with
+ * -fsanitize=thread (see CMakeLists.txt), so a missing happens-before
+ * between a slot write and its read is reported by ThreadSanitizer even
+ * when the run happens to produce the right values.
+ *
+ * Built as its own executable: POSIX threads only, and the sanitizer
+ * must not leak into test_runner.
+ *
+ * KeyingStream: one producer, two consumers. The producer is held back
+ * so it never laps a consumer; a lapped slot is discarded by
+ * stream_read() but its copy is still a race by construction.
+ *
+ * LogStream: three producers (like the Core 1 tasks on g_bg_log_stream)
+ * and the drain. Entries may be dropped when the buffer is full, never
+ * duplicated, reordered within a producer or torn.
+ *
+ * Console output and log drain: output flows while a session attaches
+ * and detaches its sink. Once remove returns, the sink must not be
+ * entered again.
+ */
+
+#include "unity.h"
+#include "stream.h"
+#include "rt_log.h"
+#include "console_output.h"
+#include "log_drain.h"
+
+#include <pthread.h>
+#include <sched.h>
+#include <stdio.h>
+#include <string.h>
+
+#define CONC_STREAM_SIZE 256
+#define CONC_STREAM_SAMPLES 200000U
+#define CONC_STREAM_CONSUMERS 2
+
+#define CONC_LOG_PRODUCERS 3
+#define CONC_LOG_MESSAGES 20000U
+
+#define CONC_SINK_WRITERS 2
+#define CONC_SINK_WRITES 20000U
+#define CONC_SINK_SESSIONS 2000U
+#define CONC_DRAIN_MESSAGES 50000U
+
+void setUp(void) {}
+void tearDown(void) {}
+
+/* ============================================================================
+ * KeyingStream: 1 producer, N consumers
+ * ============================================================================ */
+
+static stream_sample_t s_buffer[CONC_STREAM_SIZE];
+static keying_stream_t s_stream;
+
+/* Samples each consumer has taken (test harness only, for backpressure) */
+static atomic_size_t s_taken[CONC_STREAM_CONSUMERS];
+static atomic_uint s_stream_errors;
+
+/** Spread a sample number over the fields, so a stale or torn slot shows */
+static stream_sample_t encode_sample(uint32_t n) {
+    stream_sample_t s = STREAM_SAMPLE_EMPTY;
+    s.local_key = (uint8_t)(n & 0xFFU);
+    s.audio_level = (uint8_t)((n >> 8) & 0xFFU);
+    s.config_gen = (uint16_t)(n >> 16);
+    s.flags = (uint8_t)(~n & 0xFFU);
+    return s;
+}
+
+static bool decode_sample(const stream_sample_t *s, uint32_t *n) {
+    *n = (uint32_t)s->local_key | ((uint32_t)s->audio_level << 8) |
+         ((uint32_t)s->config_gen << 16);
+    return s->flags == (uint8_t)(~*n & 0xFFU);
+}
+
+static size_t slowest_consumer(void) {
+    size_t min = SIZE_MAX;
+    for (size_t i = 0; i < CONC_STREAM_CONSUMERS; i++) {
+        size_t taken = atomic_load_explicit(&s_taken[i], memory_order_acquire);
+        if (taken < min) {
+            min = taken;
+        }
+    }
+    return min;
+}
+
+static void *stream_producer(void *arg) {
+    (void)arg;
+    for (uint32_t n = 0; n < CONC_STREAM_SAMPLES; n++) {
+        /* Stay within the readable window of the slowest consumer */
+        while (n - slowest_consumer() >= CONC_STREAM_SIZE - 1) {
+            sched_yield();
+        }
+        stream_push_raw(&s_stream, encode_sample(n));
+    }
+    return NULL;
+}
+
+static void *stream_reader(void *arg) {
+    size_t id = (size_t)arg;
+    stream_consumer_t consumer;
+    consumer_init_at(&consumer, &s_stream, 0);
+
+    uint32_t expect = 0;
+    while (expect < CONC_STREAM_SAMPLES) {
+        stream_sample_t s;
+        if (!consumer_next(&consumer, &s)) {
+            if (consumer_is_overrun(&consumer)) {
+                atomic_fetch_add(&s_stream_errors, 1);
+                return NULL;
+            }
+            sched_yield();
+            continue;
+        }
+        uint32_t n;
+        if (!decode_sample(&s, &n) || n != expect) {
+            printf("  consumer %zu: sample %u read as %u\n", id, (unsigned)expect, (unsigned)n);
+            atomic_fetch_add(&s_stream_errors, 1);
+            return NULL;
+        }
+        expect++;
+        atomic_store_explicit(&s_taken[id], expect, memory_order_release);
+    }
+    return NULL;
+}
+
+void test_concurrency_stream_spmc(void) {
+    stream_init(&s_stream, s_buffer, CONC_STREAM_SIZE);
+    atomic_init(&s_stream_errors, 0);
+    for (size_t i = 0; i < CONC_STREAM_CONSUMERS; i++) {
+        atomic_init(&s_taken[i], 0);
+    }
+
+    pthread_t readers[CONC_STREAM_CONSUMERS];
+    pthread_t producer;
+    for (size_t i = 0; i < CONC_STREAM_CONSUMERS; i++) {
+        TEST_ASSERT_EQUAL(0, pthread_create(&readers[i], NULL, stream_reader, (void *)i));
+    }
+    TEST_ASSERT_EQUAL(0, pthread_create(&producer, NULL, stream_producer, NULL));
+
+    pthread_join(producer, NULL);
+    for (size_t i = 0; i < CONC_STREAM_CONSUMERS; i++) {
+        pthread_join(readers[i], NULL);
+    }
+
+    TEST_ASSERT_EQUAL_UINT(0, atomic_load(&s_stream_errors));
+    TEST_ASSERT_EQUAL(CONC_STREAM_SAMPLES, stream_write_position(&s_stream));
+}
+
+/* ============================================================================
+ * LogStream: N producers, 1 consumer
+ * ============================================================================ */
+
+static log_stream_t s_log;
+static atomic_uint s_producers_done;
+
+static void *log_producer(void *arg) {
+    unsigned id = (unsigned)(size_t)arg;
+    for (uint32_t seq = 0; seq < CONC_LOG_MESSAGES; seq++) {
+        char msg[32];
+        int len = snprintf(msg, sizeof(msg), "p%u %u", id, (unsigned)seq);
+        /* Timestamp and tag repeat the payload: a torn entry disagrees */
+        log_stream_push(&s_log, (int64_t)seq, LOG_LEVEL_INFO, (log_tag_t)id,
+                        msg, (size_t)len);
+        if ((seq & 0x3FU) == 0) {
+            sched_yield();
+        }
+    }
+    atomic_fetch_add_explicit(&s_producers_done, 1, memory_order_release);
+    return NULL;
+}
+
+void test_concurrency_log_mpsc(void) {
+    log_stream_init(&s_log);
+    atomic_init(&s_producers_done, 0);
+
+    pthread_t producers[CONC_LOG_PRODUCERS];
+    for (size_t i = 0; i < CONC_LOG_PRODUCERS; i++) {
+        TEST_ASSERT_EQUAL(0, pthread_create(&producers[i], NULL, log_producer, (void *)i));
+    }
+
+    /* Drain on this thread, as the log drain task does */
+    uint32_t received = 0;
+    int64_t last[CONC_LOG_PRODUCERS] = {-1, -1, -1};
+    bool ok = true;
+    for (;;) {
+        bool done = atomic_load_explicit(&s_producers_done, memory_order_acquire) ==
+                    CONC_LOG_PRODUCERS;
+        log_entry_t entry;
+        if (!log_stream_drain(&s_log, &entry)) {
+            if (done && !log_stream_has_entries(&s_log)) {
+                break;
+            }
+            sched_yield();
+            continue;
+        }
+        received++;
+
+        unsigned id;
+        unsigned seq;
+        char text[LOG_MAX_MSG_LEN + 1];
+        memcpy(text, entry.msg, entry.len);
+        text[entry.len] = '\0';
+        if (sscanf(text, "p%u %u", &id, &seq) != 2 || id >= CONC_LOG_PRODUCERS ||
+            entry.tag != id || entry.timestamp_us != (int64_t)seq ||
+            entry.level != LOG_LEVEL_INFO) {
+            printf("  torn entry: \"%s\" tag %u ts %lld\n", text, (unsigned)entry.tag,
+                   (long long)entry.timestamp_us);
+            ok = false;
+            break;
+        }
+        if ((int64_t)seq <= last[id]) {
+            printf("  producer %u: %u after %lld\n", id, seq, (long long)last[id]);
+            ok = false;
+            break;
+        }
+        last[id] = seq;
+    }
+
+    for (size_t i = 0; i < CONC_LOG_PRODUCERS; i++) {
+        pthread_join(producers[i], NULL);
+    }
+
+    TEST_ASSERT_TRUE(ok);
+    TEST_ASSERT_EQUAL_UINT32(CONC_LOG_PRODUCERS * CONC_LOG_MESSAGES,
+                             received + log_stream_dropped(&s_log));
+    TEST_ASSERT_TRUE(received > 0);
+}
+
+/* ============================================================================
+ * Console output: writers vs attach/detach
+ * ============================================================================ */
+
+static atomic_bool s_session_open;
+static atomic_uint s_sink_errors;
+static atomic_uint s_writers_done;
+
+static void session_sink(const char *data, size_t len) {
+    (void)data;
+    (void)len;
+    if (!atomic_load(&s_session_open)) {
+        atomic_fetch_add(&s_sink_errors, 1);
+    }
+}
+
+static void null_sink(const char *data, size_t len) {
+    (void)data;
+    (void)len;
+}
+
+static void *sink_writer(void *arg) {
+    (void)arg;
+    for (uint32_t n = 0; n < CONC_SINK_WRITES; n++) {
+        console_write("x\n", 2);
+    }
+    atomic_fetch_add(&s_writers_done, 1);
+    return NULL;
+}
+
+void test_concurrency_console_sinks(void) {
+    atomic_init(&s_session_open, false);
+    atomic_init(&s_sink_errors, 0);
+    atomic_init(&s_writers_done, 0);
+    int base = console_output_add_sink("base", null_sink, false);
+    TEST_ASSERT_TRUE(base >= 0);
+
+    pthread_t writers[CONC_SINK_WRITERS];
+    for (size_t i = 0; i < CONC_SINK_WRITERS; i++) {
+        TEST_ASSERT_EQUAL(0, pthread_create(&writers[i], NULL, sink_writer, NULL));
+    }
+
+    /* The session transport "closes" right after its sink is removed */
+    for (uint32_t n = 0; n < CONC_SINK_SESSIONS &&
+                         atomic_load(&s_writers_done) < CONC_SINK_WRITERS; n++) {
+        atomic_store(&s_session_open, true);
+        int slot = console_output_add_sink("session", session_sink, true);
+        TEST_ASSERT_TRUE(slot >= 0);
+        sched_yield();
+        console_output_remove_sink(slot);
+        atomic_store(&s_session_open, false);
+    }
+
+    for (size_t i = 0; i < CONC_SINK_WRITERS; i++) {
+        pthread_join(writers[i], NULL);
+    }
+    console_output_remove_sink(base);
+
+    TEST_ASSERT_EQUAL_UINT(0, atomic_load(&s_sink_errors));
+    TEST_ASSERT_EQUAL(0, console_output_sink_count());
+}
+
+/* ============================================================================
+ * Log drain: drain task vs attach/detach
+ * ============================================================================ */
+
+static atomic_bool s_log_session_open;
+static atomic_uint s_log_sink_errors;
+static atomic_bool s_drain_stop;
+
+static bool log_session_sink(const log_entry_t *entry, const char *stream) {
+    (void)entry;
+    (void)stream;
+    if (!atomic_load(&s_log_session_open)) {
+        atomic_fetch_add(&s_log_sink_errors, 1);
+    }
+    return true;
+}
+
+static void log_session_flush(void) {
+    if (!atomic_load(&s_log_session_open)) {
+        atomic_fetch_add(&s_log_sink_errors, 1);
+    }
+}
+
+static void *drain_producer(void *arg) {
+    (void)arg;
+    for (uint32_t n = 0; n < CONC_DRAIN_MESSAGES; n++) {
+        log_stream_push(&g_bg_log_stream, (int64_t)n, LOG_LEVEL_INFO, LOG_TAG_MAIN, "m", 1);
+        if ((n & 0x3FU) == 0) {
+            sched_yield();
+        }
+    }
+    return NULL;
+}
+
+static void *drain_task(void *arg) {
+    (void)arg;
+    while (!atomic_load(&s_drain_stop)) {
+        if (log_drain_poll() == 0) {
+            sched_yield();
+        }
+    }
+    return NULL;
+}
+
+void test_concurrency_log_drain_sinks(void) {
+    log_stream_init(&g_rt_log_stream);
+    log_stream_init(&g_bg_log_stream);
+    atomic_init(&s_log_session_open, false);
+    atomic_init(&s_log_sink_errors, 0);
+    atomic_init(&s_drain_stop, false);
+
+    pthread_t producer;
+    pthread_t drain;
+    TEST_ASSERT_EQUAL(0, pthread_create(&drain, NULL, drain_task, NULL));
+    TEST_ASSERT_EQUAL(0, pthread_create(&producer, NULL, drain_producer, NULL));
+
+    for (uint32_t n = 0; n < CONC_SINK_SESSIONS; n++) {
+        atomic_store(&s_log_session_open, true);
+        int slot = log_drain_add_sink("session", log_session_sink, log_session_flush);
+        TEST_ASSERT_TRUE(slot >= 0);
+        sched_yield();
+        log_drain_remove_sink(slot);
+        atomic_store(&s_log_session_open, false);
+    }
+
+    pthread_join(producer, NULL);
+    atomic_store(&s_drain_stop, true);
+    pthread_join(drain, NULL);
+
+    TEST_ASSERT_EQUAL_UINT(0, atomic_load(&s_log_sink_errors));
+}
+
+int main(void) {
+    UNITY_BEGIN();
+
+    printf("\n=== KeyingStream Concurrency Tests ===\n");
+    RUN_TEST(test_concurrency_stream_spmc);
+
+    printf("\n=== LogStream Concurrency Tests ===\n");
+    RUN_TEST(test_concurrency_log_mpsc);
+
+    printf("\n=== Console Output Concurrency Tests ===\n");
+    RUN_TEST(test_concurrency_console_sinks);
+
+    printf("\n=== Log Drain Concurrency Tests ===\n");
+    RUN_TEST(test_concurrency_log_drain_sinks);
+
+    return UNITY_END();
+}
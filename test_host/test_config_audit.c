@@ -0,0 +1,64 @@
+/**
+ * @file test_config_audit.c
+ * @brief Unit tests for the configuration change ring
+ */
+
+#include "unity.h"
+#include "config_audit.h"
+#include <stdio.h>
+#include <string.h>
+
+static config_audit_t s_audit;
+
+void test_config_audit_record_and_get(void) {
+    config_audit_init(&s_audit);
+    TEST_ASSERT_EQUAL(0, config_audit_count(&s_audit));
+
+    config_audit_record(&s_audit, CONFIG_AUDIT_CONSOLE, NULL, "keyer.wpm", "25", "30", 1000);
+    config_audit_record(&s_audit, CONFIG_AUDIT_WEB, "192.168.1.20", "audio.sidetone_freq_hz",
+                        "600", "700", 2000);
+    TEST_ASSERT_EQUAL(2, config_audit_count(&s_audit));
+
+    config_audit_entry_t e;
+    TEST_ASSERT_TRUE(config_audit_get(&s_audit, 0, &e));  /* Newest first */
+    TEST_ASSERT_EQUAL(CONFIG_AUDIT_WEB, e.source);
+    TEST_ASSERT_EQUAL_STRING("192.168.1.20", e.who);
+    TEST_ASSERT_EQUAL_STRING("audio.sidetone_freq_hz", e.path);
+    TEST_ASSERT_EQUAL_STRING("600", e.old_value);
+    TEST_ASSERT_EQUAL_STRING("700", e.new_value);
+
+    TEST_ASSERT_TRUE(config_audit_get(&s_audit, 1, &e));
+    TEST_ASSERT_EQUAL(CONFIG_AUDIT_CONSOLE, e.source);
+    TEST_ASSERT_EQUAL_STRING("", e.who);
+    TEST_ASSERT_EQUAL(1000, e.time_us);
+
+    TEST_ASSERT_FALSE(config_audit_get(&s_audit, 2, &e));
+}
+
+void test_config_audit_wraps(void) {
+    config_audit_init(&s_audit);
+    char value[8];
+    for (int i = 0; i < CONFIG_AUDIT_SIZE + 5; i++) {
+        snprintf(value, sizeof(value), "%d", i);
+        config_audit_record(&s_audit, CONFIG_AUDIT_CONSOLE, "", "keyer.wpm", "", value, i);
+    }
+    TEST_ASSERT_EQUAL(CONFIG_AUDIT_SIZE, config_audit_count(&s_audit));
+
+    config_audit_entry_t e;
+    TEST_ASSERT_TRUE(config_audit_get(&s_audit, 0, &e));
+    snprintf(value, sizeof(value), "%d", CONFIG_AUDIT_SIZE + 4);
+    TEST_ASSERT_EQUAL_STRING(value, e.new_value);
+
+    TEST_ASSERT_TRUE(config_audit_get(&s_audit, CONFIG_AUDIT_SIZE - 1, &e));
+    TEST_ASSERT_EQUAL_STRING("5", e.new_value);  /* Oldest kept */
+}
+
+void test_config_audit_truncates(void) {
+    config_audit_init(&s_audit);
+    config_audit_record(&s_audit, CONFIG_AUDIT_CONSOLE, NULL, "system.station_name",
+                        "", "a very long station name well past the limit", 0);
+
+    config_audit_entry_t e;
+    TEST_ASSERT_TRUE(config_audit_get(&s_audit, 0, &e));
+    TEST_ASSERT_EQUAL(CONFIG_AUDIT_VALUE_MAX - 1, strlen(e.new_value));
+}
@@ -0,0 +1,41 @@
+/**
+ * @file test_config_match.c
+ * @brief Unit tests for parameter pattern matching (config_foreach_matching)
+ *
+ * Uses the generated test schema (test_host/config/test_params.yaml):
+ * keyer.wpm, keyer.sidetone, system.callsign, system.serial.
+ */
+
+#include "unity.h"
+#include "config_console.h"
+#include <string.h>
+
+static void count_visitor(const param_descriptor_t *param, void *ctx) {
+    (void)param;
+    (*(size_t *)ctx)++;
+}
+
+static size_t visit_count(const char *pattern) {
+    size_t visited = 0;
+    size_t returned = config_foreach_matching(pattern, count_visitor, &visited);
+    TEST_ASSERT_EQUAL(visited, returned);
+    return returned;
+}
+
+void test_config_match_star_is_all(void) {
+    TEST_ASSERT_EQUAL(CONSOLE_PARAM_COUNT, visit_count("*"));
+    TEST_ASSERT_EQUAL(CONSOLE_PARAM_COUNT, visit_count("**"));
+}
+
+void test_config_match_family_glob(void) {
+    TEST_ASSERT_EQUAL(2, visit_count("keyer.*"));
+    TEST_ASSERT_EQUAL(2, visit_count("system.**"));
+    TEST_ASSERT_EQUAL(1, visit_count("*.call*"));
+    TEST_ASSERT_EQUAL(0, visit_count("keyer.call*"));
+}
+
+void test_config_match_bare_name_glob(void) {
+    TEST_ASSERT_EQUAL(1, visit_count("w*"));
+    TEST_ASSERT_EQUAL(2, visit_count("s*"));
+    TEST_ASSERT_EQUAL(0, visit_count("x*"));
+}
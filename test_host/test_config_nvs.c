@@ -0,0 +1,170 @@
+/**
+ * @file test_config_nvs.c
+ * @brief Unit tests for config persistence (generated config_nvs.c)
+ *
+ * Runs the code gen_config_c.py generates for test_host/config/test_params.yaml
+ * against the in-memory NVS stub (stubs/nvs_stubs.c).
+ */
+
+#include "unity.h"
+#include "config.h"
+#include "config_console.h"
+#include "config_nvs.h"
+#include "nvs.h"
+#include <string.h>
+
+static void reset_all(void) {
+    nvs_stub_reset();
+    config_init_defaults(&g_config);
+}
+
+/** Write a key of the config namespace directly, as provisioning does */
+static void foreign_write_str(const char *key, const char *value) {
+    nvs_handle_t handle;
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_open(CONFIG_NVS_NAMESPACE, NVS_READWRITE, &handle));
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_set_str(handle, key, value));
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_commit(handle));
+    nvs_close(handle);
+}
+
+void test_config_nvs_roundtrip(void) {
+    reset_all();
+    TEST_ASSERT_EQUAL(0, config_set_param_str("keyer.wpm", "32"));
+    TEST_ASSERT_EQUAL(0, config_set_param_str("system.callsign", "IU3QEZ"));
+    TEST_ASSERT_TRUE(config_save_to_nvs() > 0);
+
+    config_init_defaults(&g_config);
+    TEST_ASSERT_TRUE(config_load_from_nvs() > 0);
+    TEST_ASSERT_FALSE(config_nvs_integrity_failed());
+
+    char buf[32];
+    config_get_param_str("keyer.wpm", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("32", buf);
+    config_get_param_str("system.callsign", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("IU3QEZ", buf);
+}
+
+void test_config_nvs_foreign_write_unsealed(void) {
+    reset_all();
+    TEST_ASSERT_EQUAL(0, config_set_param_str("keyer.wpm", "32"));
+    TEST_ASSERT_TRUE(config_save_to_nvs() > 0);
+
+    /* A direct write without reseal looks like corruption */
+    foreign_write_str("callsign", "IK3XYZ");
+
+    config_init_defaults(&g_config);
+    TEST_ASSERT_EQUAL(CONFIG_NVS_ERR_CRC, config_load_from_nvs());
+    TEST_ASSERT_TRUE(config_nvs_integrity_failed());
+}
+
+void test_config_nvs_foreign_write_resealed(void) {
+    reset_all();
+    TEST_ASSERT_EQUAL(0, config_set_param_str("keyer.wpm", "32"));
+    TEST_ASSERT_TRUE(config_save_to_nvs() > 0);
+
+    foreign_write_str("callsign", "IK3XYZ");
+    TEST_ASSERT_EQUAL(ESP_OK, config_nvs_reseal());
+
+    config_init_defaults(&g_config);
+    TEST_ASSERT_TRUE(config_load_from_nvs() > 0);
+    TEST_ASSERT_FALSE(config_nvs_integrity_failed());
+
+    /* Both the saved value and the foreign one are in effect */
+    char buf[32];
+    config_get_param_str("keyer.wpm", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("32", buf);
+    config_get_param_str("system.callsign", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("IK3XYZ", buf);
+}
+
+/** Data written before keyer.wpm and system.callsign were renamed (no CRC yet) */
+static void write_pre_rename(void) {
+    nvs_handle_t handle;
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_open(CONFIG_NVS_NAMESPACE, NVS_READWRITE, &handle));
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_set_u16(handle, "speed", 32));
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_set_str(handle, "call", "IU3QEZ"));
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_commit(handle));
+    nvs_close(handle);
+}
+
+/** Whether the config namespace still holds key (u16 or string) */
+static bool stored(const char *key) {
+    nvs_handle_t handle;
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_open(CONFIG_NVS_NAMESPACE, NVS_READONLY, &handle));
+    uint16_t u16_val;
+    size_t len = 0;
+    bool found = nvs_get_u16(handle, key, &u16_val) == ESP_OK ||
+                 nvs_get_str(handle, key, NULL, &len) == ESP_OK;
+    nvs_close(handle);
+    return found;
+}
+
+void test_config_nvs_old_keys_migrate(void) {
+    reset_all();
+    write_pre_rename();
+
+    TEST_ASSERT_EQUAL(2, config_load_from_nvs());
+    char buf[32];
+    config_get_param_str("keyer.wpm", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("32", buf);
+    config_get_param_str("system.callsign", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("IU3QEZ", buf);
+
+    /* The load rewrote both under the current keys and dropped the old ones */
+    TEST_ASSERT_TRUE(stored("wpm"));
+    TEST_ASSERT_TRUE(stored("callsign"));
+    TEST_ASSERT_FALSE(stored("speed"));
+    TEST_ASSERT_FALSE(stored("call"));
+
+    /* And sealed the result */
+    config_init_defaults(&g_config);
+    TEST_ASSERT_TRUE(config_load_from_nvs() > 0);
+    TEST_ASSERT_FALSE(config_nvs_integrity_failed());
+}
+
+void test_config_nvs_old_keys_erased_on_save(void) {
+    reset_all();
+    write_pre_rename();
+    TEST_ASSERT_EQUAL(0, config_set_param_str("keyer.wpm", "20"));
+
+    /* A save without a load first still drops the old keys */
+    TEST_ASSERT_TRUE(config_save_to_nvs() > 0);
+    TEST_ASSERT_FALSE(stored("speed"));
+    TEST_ASSERT_FALSE(stored("call"));
+
+    /* The current value wins over the stale old key */
+    config_init_defaults(&g_config);
+    TEST_ASSERT_TRUE(config_load_from_nvs() > 0);
+    char buf[32];
+    config_get_param_str("keyer.wpm", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("20", buf);
+}
+
+void test_config_nvs_old_name_accepted(void) {
+    reset_all();
+    TEST_ASSERT_EQUAL(0, config_set_param_str("keyer.speed", "18"));
+    char buf[32];
+    config_get_param_str("keyer.wpm", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("18", buf);
+}
+
+void test_config_nvs_foreign_erase_resealed(void) {
+    reset_all();
+    TEST_ASSERT_EQUAL(0, config_set_param_str("system.callsign", "IU3QEZ"));
+    TEST_ASSERT_TRUE(config_save_to_nvs() > 0);
+
+    /* Factory reset path: erase, then reseal */
+    nvs_handle_t handle;
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_open(CONFIG_NVS_NAMESPACE, NVS_READWRITE, &handle));
+    TEST_ASSERT_EQUAL(ESP_OK, nvs_erase_key(handle, "callsign"));
+    nvs_close(handle);
+    TEST_ASSERT_EQUAL(ESP_OK, config_nvs_reseal());
+
+    config_init_defaults(&g_config);
+    TEST_ASSERT_TRUE(config_load_from_nvs() >= 0);
+    TEST_ASSERT_FALSE(config_nvs_integrity_failed());
+
+    char buf[32];
+    config_get_param_str("system.callsign", buf, sizeof(buf));
+    TEST_ASSERT_EQUAL_STRING("", buf);
+}
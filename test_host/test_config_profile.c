@@ -0,0 +1,59 @@
+/**
+ * @file test_config_profile.c
+ * @brief Tests for configuration profile registry
+ */
+
+#include "unity.h"
+#include "config_profile.h"
+
+void test_config_profile_name_valid(void) {
+    TEST_ASSERT_TRUE(config_profile_name_valid("home"));
+    TEST_ASSERT_TRUE(config_profile_name_valid("contest_2"));
+    TEST_ASSERT_TRUE(config_profile_name_valid("abcdefghij"));
+    TEST_ASSERT_FALSE(config_profile_name_valid("abcdefghijk"));
+    TEST_ASSERT_FALSE(config_profile_name_valid(""));
+    TEST_ASSERT_FALSE(config_profile_name_valid(NULL));
+    TEST_ASSERT_FALSE(config_profile_name_valid("Home"));
+    TEST_ASSERT_FALSE(config_profile_name_valid("a,b"));
+}
+
+void test_config_profile_save_load(void) {
+    config_profile_init();
+    TEST_ASSERT_EQUAL(0, config_profile_count());
+    TEST_ASSERT_EQUAL_STRING("", config_profile_active());
+
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("home"));
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("portable"));
+    TEST_ASSERT_EQUAL(2, config_profile_count());
+    TEST_ASSERT_EQUAL_STRING("portable", config_profile_active());
+
+    /* Overwrite keeps a single entry */
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("home"));
+    TEST_ASSERT_EQUAL(2, config_profile_count());
+
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_load("portable"));
+    TEST_ASSERT_EQUAL_STRING("portable", config_profile_active());
+    TEST_ASSERT_EQUAL(CONSOLE_ERR_INVALID_VALUE, config_profile_load("contest"));
+    TEST_ASSERT_EQUAL(CONSOLE_ERR_INVALID_VALUE, config_profile_save("Bad Name"));
+}
+
+void test_config_profile_full_and_delete(void) {
+    config_profile_init();
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("p1"));
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("p2"));
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("p3"));
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("p4"));
+    TEST_ASSERT_EQUAL(CONSOLE_ERR_OUT_OF_RANGE, config_profile_save("p5"));
+
+    /* Deleting the active profile clears it; order is kept */
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_delete(config_profile_name(3)));
+    TEST_ASSERT_EQUAL_STRING("", config_profile_active());
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_delete("p1"));
+    TEST_ASSERT_EQUAL(2, config_profile_count());
+    TEST_ASSERT_EQUAL_STRING("p2", config_profile_name(0));
+    TEST_ASSERT_EQUAL_STRING("p3", config_profile_name(1));
+    TEST_ASSERT_NULL(config_profile_name(2));
+    TEST_ASSERT_EQUAL(CONSOLE_ERR_INVALID_VALUE, config_profile_delete("p1"));
+
+    TEST_ASSERT_EQUAL(CONSOLE_OK, config_profile_save("p5"));
+}
@@ -0,0 +1,74 @@
+/**
+ * @file test_console_lock.c
+ * @brief Tests for console passphrase lock
+ */
+
+#include "unity.h"
+#include "console_lock.h"
+
+void test_console_lock_init_unlocked(void) {
+    console_lock_init();
+    TEST_ASSERT_FALSE(console_lock_has_passphrase());
+    TEST_ASSERT_FALSE(console_lock_is_locked());
+    TEST_ASSERT_FALSE(console_lock_engage());
+    TEST_ASSERT_TRUE(console_lock_allows("set"));
+}
+
+void test_console_lock_set_and_unlock(void) {
+    console_lock_init();
+    TEST_ASSERT_EQUAL(CONSOLE_ERR_OUT_OF_RANGE, console_lock_set_passphrase("abc"));
+    TEST_ASSERT_EQUAL(CONSOLE_OK, console_lock_set_passphrase("secret"));
+    TEST_ASSERT_TRUE(console_lock_has_passphrase());
+
+    TEST_ASSERT_TRUE(console_lock_engage());
+    TEST_ASSERT_TRUE(console_lock_is_locked());
+    TEST_ASSERT_FALSE(console_lock_try_unlock("Secret"));
+    TEST_ASSERT_TRUE(console_lock_is_locked());
+    TEST_ASSERT_TRUE(console_lock_try_unlock("secret"));
+    TEST_ASSERT_FALSE(console_lock_is_locked());
+
+    TEST_ASSERT_EQUAL(CONSOLE_OK, console_lock_set_passphrase(NULL));
+    TEST_ASSERT_FALSE(console_lock_has_passphrase());
+}
+
+void test_console_lock_allows_when_locked(void) {
+    console_lock_init();
+    console_lock_set_passphrase("secret");
+    console_lock_engage();
+
+    TEST_ASSERT_TRUE(console_lock_allows("unlock"));
+    TEST_ASSERT_TRUE(console_lock_allows("help"));
+    TEST_ASSERT_TRUE(console_lock_allows("version"));
+    TEST_ASSERT_FALSE(console_lock_allows("set"));
+    TEST_ASSERT_FALSE(console_lock_allows("factory-reset"));
+    TEST_ASSERT_FALSE(console_lock_allows(NULL));
+
+    console_lock_set_passphrase(NULL);
+}
+
+void test_console_lock_idle_timeout(void) {
+    console_lock_init();
+    console_lock_set_passphrase("secret");
+    console_lock_touch(1000);
+
+    /* Disabled timeout never locks */
+    TEST_ASSERT_FALSE(console_lock_check_idle(1000000, 0));
+
+    TEST_ASSERT_FALSE(console_lock_check_idle(60999, 60000));
+    console_lock_touch(50000);
+    TEST_ASSERT_FALSE(console_lock_check_idle(109999, 60000));
+    TEST_ASSERT_TRUE(console_lock_check_idle(110000, 60000));
+    TEST_ASSERT_TRUE(console_lock_is_locked());
+
+    /* Already locked: no second notification */
+    TEST_ASSERT_FALSE(console_lock_check_idle(200000, 60000));
+
+    console_lock_set_passphrase(NULL);
+}
+
+void test_console_lock_idle_without_passphrase(void) {
+    console_lock_init();
+    console_lock_touch(0);
+    TEST_ASSERT_FALSE(console_lock_check_idle(1000000, 60000));
+    TEST_ASSERT_FALSE(console_lock_is_locked());
+}
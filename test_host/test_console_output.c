@@ -0,0 +1,125 @@
+/**
+ * @file test_console_output.c
+ * @brief Tests for console output fan-out writer
+ */
+
+#include "unity.h"
+#include "console_output.h"
+#include <string.h>
+
+static char s_buf_a[128];
+static size_t s_len_a;
+static char s_buf_b[128];
+static size_t s_len_b;
+
+static void sink_a(const char *data, size_t len) {
+    if (s_len_a + len < sizeof(s_buf_a)) {
+        memcpy(&s_buf_a[s_len_a], data, len);
+        s_len_a += len;
+        s_buf_a[s_len_a] = '\0';
+    }
+}
+
+static void sink_b(const char *data, size_t len) {
+    if (s_len_b + len < sizeof(s_buf_b)) {
+        memcpy(&s_buf_b[s_len_b], data, len);
+        s_len_b += len;
+        s_buf_b[s_len_b] = '\0';
+    }
+}
+
+static void reset_buffers(void) {
+    s_len_a = 0;
+    s_buf_a[0] = '\0';
+    s_len_b = 0;
+    s_buf_b[0] = '\0';
+}
+
+void test_console_output_fanout(void) {
+    reset_buffers();
+    int a = console_output_add_sink("a", sink_a, false);
+    int b = console_output_add_sink("b", sink_b, false);
+    TEST_ASSERT_TRUE(a >= 0);
+    TEST_ASSERT_TRUE(b >= 0);
+    TEST_ASSERT_EQUAL(2, console_output_sink_count());
+
+    console_printf("wpm=%d\r\n", 25);
+    TEST_ASSERT_EQUAL_STRING("wpm=25\r\n", s_buf_a);
+    TEST_ASSERT_EQUAL_STRING("wpm=25\r\n", s_buf_b);
+
+    console_output_remove_sink(a);
+    console_output_remove_sink(b);
+    TEST_ASSERT_EQUAL(0, console_output_sink_count());
+}
+
+void test_console_output_crlf(void) {
+    reset_buffers();
+    int a = console_output_add_sink("raw", sink_a, false);
+    int b = console_output_add_sink("crlf", sink_b, true);
+
+    console_write("one\ntwo\r\n", 9);
+    TEST_ASSERT_EQUAL_STRING("one\ntwo\r\n", s_buf_a);
+    TEST_ASSERT_EQUAL_STRING("one\r\ntwo\r\n", s_buf_b);
+
+    console_output_remove_sink(a);
+    console_output_remove_sink(b);
+}
+
+void test_console_output_remove(void) {
+    reset_buffers();
+    int a = console_output_add_sink("a", sink_a, false);
+    int b = console_output_add_sink("b", sink_b, false);
+
+    console_output_remove_sink(b);
+    console_write("x", 1);
+    TEST_ASSERT_EQUAL_STRING("x", s_buf_a);
+    TEST_ASSERT_EQUAL(0, s_len_b);
+
+    console_output_remove_sink(a);
+}
+
+void test_console_output_table_full(void) {
+    int slots[CONSOLE_MAX_SINKS];
+    for (int i = 0; i < CONSOLE_MAX_SINKS; i++) {
+        slots[i] = console_output_add_sink("a", sink_a, false);
+        TEST_ASSERT_TRUE(slots[i] >= 0);
+    }
+    TEST_ASSERT_EQUAL(-1, console_output_add_sink("b", sink_b, false));
+    TEST_ASSERT_EQUAL(-1, console_output_add_sink("c", NULL, false));
+
+    for (int i = 0; i < CONSOLE_MAX_SINKS; i++) {
+        console_output_remove_sink(slots[i]);
+    }
+    TEST_ASSERT_EQUAL(0, console_output_sink_count());
+}
+
+void test_console_output_post(void) {
+    reset_buffers();
+    int a = console_output_add_sink("a", sink_a, false);
+
+    /* Nothing reaches the sink until the pump runs */
+    TEST_ASSERT_TRUE(console_post("CQ ", 3));
+    TEST_ASSERT_TRUE(console_post("TEST", 4));
+    TEST_ASSERT_EQUAL(0, s_len_a);
+    console_output_pump();
+    TEST_ASSERT_EQUAL_STRING("CQ TEST", s_buf_a);
+
+    /* A post that does not fit is dropped whole */
+    reset_buffers();
+    static char fill[CONSOLE_POST_RING];
+    memset(fill, 'x', sizeof(fill));
+    TEST_ASSERT_TRUE(console_post(fill, sizeof(fill) - 2));
+    TEST_ASSERT_FALSE(console_post("abc", 3));
+    TEST_ASSERT_TRUE(console_post("ab", 2));
+    console_output_pump();
+
+    /* Wraps around the end of the ring (7 + 512 + 504 = 1023) */
+    TEST_ASSERT_TRUE(console_post(fill, CONSOLE_POST_RING - 8));
+    console_output_pump();
+    reset_buffers();
+    TEST_ASSERT_TRUE(console_post("de", 2));
+    console_output_pump();
+    TEST_ASSERT_EQUAL_STRING("de", s_buf_a);
+
+    console_output_remove_sink(a);
+}
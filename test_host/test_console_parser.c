@@ -64,6 +64,16 @@ void test_parse_extra_args_ignored(void) {
     TEST_ASSERT_EQUAL_STRING("c", cmd.args[2]);
 }
 
+void test_parse_rest_of_line(void) {
+    console_parsed_cmd_t cmd;
+    console_parse_line("msg set 1 CQ CQ DE N0CALL K  ", &cmd);
+
+    TEST_ASSERT_EQUAL_INT(3, cmd.argc);
+    TEST_ASSERT_EQUAL_STRING("CQ", cmd.args[2]);
+    TEST_ASSERT_EQUAL_STRING("CQ CQ DE N0CALL K", cmd.rest[2]);
+    TEST_ASSERT_EQUAL_STRING("1 CQ CQ DE N0CALL K", cmd.rest[1]);
+}
+
 void test_parse_leading_whitespace(void) {
     console_parsed_cmd_t cmd;
     console_parse_line("   help", &cmd);
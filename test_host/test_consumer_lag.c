@@ -0,0 +1,68 @@
+/**
+ * @file test_consumer_lag.c
+ * @brief Unit tests for per-consumer lag telemetry
+ */
+
+#include "unity.h"
+#include "stream.h"
+#include "sample.h"
+#include "consumer.h"
+#include "consumer_lag.h"
+
+#define TEST_BUFFER_SIZE 64
+static stream_sample_t s_buffer[TEST_BUFFER_SIZE];
+static keying_stream_t s_stream;
+
+static void push_edges(size_t n) {
+    for (size_t i = 0; i < n; i++) {
+        stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+        sample.local_key = (uint8_t)(i & 1U);
+        stream_push(&s_stream, sample);
+    }
+}
+
+void test_consumer_lag_registry(void) {
+    consumer_lag_t *a = consumer_lag_register("test_a");
+    TEST_ASSERT_NOT_NULL(a);
+    TEST_ASSERT_EQUAL_PTR(a, consumer_lag_register("test_a"));
+    TEST_ASSERT_TRUE(consumer_lag_count() >= 1);
+
+    /* Mean over records, max kept */
+    consumer_lag_request_reset();
+    consumer_lag_record(a, 1);
+    consumer_lag_record(a, 2);
+    consumer_lag_record(a, 4);
+    TEST_ASSERT_EQUAL_UINT32(4, atomic_load(&a->lag_max));
+    TEST_ASSERT_EQUAL_UINT32(233, consumer_lag_mean_x100(a));
+
+    /* Reset is applied by the writer on its next record */
+    consumer_lag_drop(a, 3);
+    consumer_lag_request_reset();
+    TEST_ASSERT_EQUAL_UINT32(3, atomic_load(&a->dropped));
+    consumer_lag_record(a, 1);
+    TEST_ASSERT_EQUAL_UINT32(0, atomic_load(&a->dropped));
+    TEST_ASSERT_EQUAL_UINT32(1, atomic_load(&a->lag_max));
+
+    /* Untracked consumers pass NULL */
+    consumer_lag_record(NULL, 5);
+    consumer_lag_drop(NULL, 5);
+}
+
+void test_consumer_lag_best_effort(void) {
+    stream_init(&s_stream, s_buffer, TEST_BUFFER_SIZE);
+    consumer_lag_t *lag = consumer_lag_register("test_be");
+    consumer_lag_request_reset();
+
+    best_effort_consumer_t consumer;
+    best_effort_consumer_init(&consumer, &s_stream, 8);
+    best_effort_consumer_track(&consumer, lag);
+
+    /* Behind by 20 with a threshold of 8: the skip counts as drops */
+    push_edges(20);
+    stream_sample_t out;
+    TEST_ASSERT_TRUE(best_effort_consumer_tick(&consumer, &out));
+    TEST_ASSERT_EQUAL_UINT32(20, atomic_load(&lag->lag_max));
+    TEST_ASSERT_EQUAL_UINT32((uint32_t)best_effort_consumer_dropped(&consumer),
+                             atomic_load(&lag->dropped));
+    TEST_ASSERT_TRUE(atomic_load(&lag->dropped) > 0);
+}
@@ -0,0 +1,77 @@
+/**
+ * @file test_contest_serial.c
+ * @brief Unit tests for %N serial substitution
+ */
+
+#include "unity.h"
+#include "contest_serial.h"
+
+void test_contest_serial_expand_codes(void) {
+    char out[64];
+    uint32_t serial = 7;
+
+    /* %N sends the current number, then advances */
+    TEST_ASSERT_TRUE(contest_serial_expand_with("5NN %N", out, sizeof(out), &serial));
+    TEST_ASSERT_EQUAL_STRING("5NN 007", out);
+    TEST_ASSERT_EQUAL_UINT32(8, serial);
+
+    /* %N- repeats the last one sent */
+    TEST_ASSERT_TRUE(contest_serial_expand_with("NR %N- %N-", out, sizeof(out), &serial));
+    TEST_ASSERT_EQUAL_STRING("NR 007 007", out);
+    TEST_ASSERT_EQUAL_UINT32(8, serial);
+
+    /* %N+ skips a number */
+    TEST_ASSERT_TRUE(contest_serial_expand_with("%n+", out, sizeof(out), &serial));
+    TEST_ASSERT_EQUAL_STRING("009", out);
+    TEST_ASSERT_EQUAL_UINT32(9, serial);
+
+    /* No code: text unchanged, serial untouched */
+    TEST_ASSERT_FALSE(contest_serial_expand_with("100% CQ", out, sizeof(out), &serial));
+    TEST_ASSERT_EQUAL_STRING("100% CQ", out);
+    TEST_ASSERT_EQUAL_UINT32(9, serial);
+}
+
+void test_contest_serial_wraps_and_truncates(void) {
+    char out[8];
+    uint32_t serial = CONTEST_SERIAL_MAX;
+
+    contest_serial_expand_with("%N", out, sizeof(out), &serial);
+    TEST_ASSERT_EQUAL_STRING("9999", out);
+    TEST_ASSERT_EQUAL_UINT32(CONTEST_SERIAL_FIRST, serial);
+
+    /* Number does not fit: cut at the buffer end, still terminated */
+    serial = 1234;
+    contest_serial_expand_with("TU 5NN %N", out, sizeof(out), &serial);
+    TEST_ASSERT_EQUAL_STRING("TU 5NN ", out);
+    contest_serial_expand_with("5NN %N", out, sizeof(out), &serial);
+    TEST_ASSERT_EQUAL_STRING("5NN ", out);
+}
+
+void test_contest_serial_advances_when_keyed(void) {
+    char out[16];
+    contest_serial_init();
+    TEST_ASSERT_EQUAL(0, contest_serial_set(7));
+
+    /* Expansion marks the advance but does not apply it */
+    contest_serial_expand("5NN %N", out, sizeof(out));
+    TEST_ASSERT_EQUAL_STRING("5NN 007|N8|", out);
+    TEST_ASSERT_EQUAL_UINT32(7, contest_serial_get());
+
+    /* Keyer reached the mark */
+    contest_serial_sent(8);
+    TEST_ASSERT_EQUAL_UINT32(8, contest_serial_get());
+
+    /* %N- never advances; %N+ stores the number it sent */
+    contest_serial_expand("%N- %N+", out, sizeof(out));
+    TEST_ASSERT_EQUAL_STRING("007 009|N9|", out);
+
+    /* No room for the mark: number sent, serial left alone */
+    char small[11];
+    contest_serial_expand("TU 5NN %N", small, sizeof(small));
+    TEST_ASSERT_EQUAL_STRING("TU 5NN 008", small);
+
+    contest_serial_sent(0);
+    TEST_ASSERT_EQUAL_UINT32(8, contest_serial_get());
+    TEST_ASSERT_EQUAL(-1, contest_serial_set(CONTEST_SERIAL_MAX + 1));
+    contest_serial_save_tick();
+}
@@ -0,0 +1,62 @@
+/**
+ * @file test_cpu_load.c
+ * @brief Unit tests for per-core CPU load
+ */
+
+#include "unity.h"
+#include "cpu_load.h"
+
+static cpu_load_t s_load;
+
+void test_cpu_load_first_sample_is_reference(void) {
+    cpu_load_init(&s_load);
+    uint32_t idle[CPU_LOAD_CORES] = {500000, 900000};
+    cpu_load_update(&s_load, 1000000, idle);
+
+    TEST_ASSERT_FALSE(cpu_load_valid(&s_load));
+    TEST_ASSERT_EQUAL(0, cpu_load_now(&s_load, 0));
+
+    /* Core 0 idle 750 ms of 1 s, core 1 idle 400 ms */
+    idle[0] += 750000;
+    idle[1] += 400000;
+    cpu_load_update(&s_load, 2000000, idle);
+
+    TEST_ASSERT_TRUE(cpu_load_valid(&s_load));
+    TEST_ASSERT_EQUAL(25, cpu_load_now(&s_load, 0));
+    TEST_ASSERT_EQUAL(60, cpu_load_now(&s_load, 1));
+    TEST_ASSERT_EQUAL(0, cpu_load_now(&s_load, CPU_LOAD_CORES));
+}
+
+void test_cpu_load_window(void) {
+    cpu_load_init(&s_load);
+    /* Counter about to wrap */
+    uint32_t idle[CPU_LOAD_CORES] = {UINT32_MAX - 100000, 0};
+    int64_t now = 1000000;
+    cpu_load_update(&s_load, now, idle);
+
+    /* One busy second, then a window of idle ones */
+    now += 1000000;
+    idle[0] += 100000;
+    cpu_load_update(&s_load, now, idle);
+    TEST_ASSERT_EQUAL(90, cpu_load_now(&s_load, 0));
+    TEST_ASSERT_EQUAL(100, cpu_load_now(&s_load, 1));
+
+    for (int i = 0; i < CPU_LOAD_WINDOW - 1; i++) {
+        now += 1000000;
+        idle[0] += 1000000;
+        idle[1] += 1000000;
+        cpu_load_update(&s_load, now, idle);
+    }
+    TEST_ASSERT_EQUAL(0, cpu_load_now(&s_load, 0));
+    TEST_ASSERT_EQUAL(9, cpu_load_avg(&s_load, 0));
+    TEST_ASSERT_EQUAL(90, cpu_load_peak(&s_load, 0));
+    TEST_ASSERT_EQUAL(10, cpu_load_avg(&s_load, 1));
+
+    /* The busy second leaves the window */
+    now += 1000000;
+    idle[0] += 1000000;
+    idle[1] += 1000000;
+    cpu_load_update(&s_load, now, idle);
+    TEST_ASSERT_EQUAL(0, cpu_load_avg(&s_load, 0));
+    TEST_ASSERT_EQUAL(0, cpu_load_peak(&s_load, 0));
+}
@@ -0,0 +1,80 @@
+/**
+ * @file test_crash_log.c
+ * @brief Tests for the post-mortem log record
+ */
+
+#include "unity.h"
+#include "crash_log.h"
+#include <stdio.h>
+#include <string.h>
+
+void test_crash_log_keeps_last_lines(void) {
+    char line[32];
+    for (int i = 0; i < CRASH_LOG_LINES + 3; i++) {
+        int len = snprintf(line, sizeof(line), "[%d] INFO main: line %d\r\n", i, i);
+        crash_log_capture(line, (size_t)len);
+    }
+
+    fault_state_t fault = FAULT_STATE_INIT;
+    fault_set(&fault, FAULT_OVERRUN, 42);
+
+    TEST_ASSERT_TRUE(crash_log_save(CRASH_CAUSE_FAULT, &fault, 5000000));
+
+    crash_record_t rec;
+    TEST_ASSERT_TRUE(crash_log_read(&rec));
+    TEST_ASSERT_EQUAL(CRASH_CAUSE_FAULT, rec.cause);
+    TEST_ASSERT_EQUAL(5000000, rec.uptime_us);
+    TEST_ASSERT_EQUAL(FAULT_OVERRUN, rec.fault_code);
+    TEST_ASSERT_EQUAL(42, rec.fault_data);
+    TEST_ASSERT_EQUAL(1, rec.fault_active);
+
+    /* Oldest kept line first, CR/LF dropped */
+    TEST_ASSERT_EQUAL(CRASH_LOG_LINES, rec.line_count);
+    TEST_ASSERT_EQUAL_STRING("[3] INFO main: line 3", rec.lines[0]);
+    snprintf(line, sizeof(line), "[%d] INFO main: line %d", CRASH_LOG_LINES + 2,
+             CRASH_LOG_LINES + 2);
+    TEST_ASSERT_EQUAL_STRING(line, rec.lines[CRASH_LOG_LINES - 1]);
+}
+
+void test_crash_log_rejects_damaged_record(void) {
+    fault_state_t fault = FAULT_STATE_INIT;
+    crash_record_t rec;
+    crash_log_snapshot(&rec, CRASH_CAUSE_PANIC, &fault, 1);
+    TEST_ASSERT_TRUE(crash_log_valid(&rec));
+    TEST_ASSERT_EQUAL_STRING("panic", crash_cause_str((crash_cause_t)rec.cause));
+
+    rec.lines[0][0] ^= 0x01;
+    TEST_ASSERT_FALSE(crash_log_valid(&rec));
+
+    TEST_ASSERT_TRUE(crash_log_clear());
+    TEST_ASSERT_FALSE(crash_log_read(&rec));
+}
+
+void test_crash_log_keeps_stream_tail(void) {
+    static stream_sample_t buffer[256];
+    keying_stream_t stream;
+    stream_init(&stream, buffer, 256);
+    for (int i = 0; i < CRASH_LOG_SAMPLES + 50; i++) {
+        stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+        sample.local_key = (uint8_t)(i % 2);
+        sample.audio_level = (uint8_t)i;
+        stream_push_raw(&stream, sample);
+    }
+    crash_log_panic_setup(&stream, NULL);
+
+    fault_state_t fault = FAULT_STATE_INIT;
+    crash_record_t rec;
+    crash_log_snapshot(&rec, CRASH_CAUSE_PANIC, &fault, 1);
+    crash_log_panic_setup(NULL, NULL);
+
+    TEST_ASSERT_TRUE(crash_log_valid(&rec));
+    TEST_ASSERT_EQUAL(CRASH_LOG_SAMPLES + 50, rec.sample_end);
+    TEST_ASSERT_EQUAL(CRASH_LOG_SAMPLES, rec.sample_count);
+
+    /* Oldest kept sample first */
+    TEST_ASSERT_EQUAL(50, rec.samples[0].audio_level);
+    TEST_ASSERT_EQUAL(0, rec.samples[0].local_key);
+    TEST_ASSERT_EQUAL((uint8_t)(CRASH_LOG_SAMPLES + 49),
+                      rec.samples[CRASH_LOG_SAMPLES - 1].audio_level);
+    TEST_ASSERT_EQUAL(1, rec.samples[CRASH_LOG_SAMPLES - 1].local_key);
+}
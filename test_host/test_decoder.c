@@ -6,6 +6,7 @@
 #include "unity.h"
 #include "decoder.h"
 #include "timing_classifier.h"
+#include "stream.h"
 
 void test_decoder_init(void) {
     decoder_init();
@@ -13,7 +14,7 @@ void test_decoder_init(void) {
     TEST_ASSERT_TRUE(decoder_is_enabled());
     TEST_ASSERT_EQUAL(DECODER_STATE_IDLE, decoder_get_state());
     TEST_ASSERT_EQUAL(0, decoder_get_buffer_count());
-    TEST_ASSERT_EQUAL(128, decoder_get_buffer_capacity());
+    TEST_ASSERT_EQUAL(256, decoder_get_buffer_capacity());
 }
 
 void test_decoder_decode_letter_a(void) {
@@ -216,13 +217,13 @@ void test_decoder_buffer_circular(void) {
     decoder_reset();
 
     /* Fill buffer with characters beyond capacity */
-    for (int i = 0; i < 150; i++) {
+    for (int i = 0; i < 300; i++) {
         decoder_handle_event(KEY_EVENT_DIT, (int64_t)(i * 1000));
         decoder_handle_event(KEY_EVENT_CHAR_GAP, (int64_t)(i * 1000 + 500));
     }
 
     /* Buffer should be at capacity */
-    TEST_ASSERT_EQUAL(128, decoder_get_buffer_count());
+    TEST_ASSERT_EQUAL(256, decoder_get_buffer_count());
 
     /* Should still be able to get text */
     char buf[64];
@@ -249,3 +250,116 @@ void test_decoder_get_text_with_timestamps(void) {
     TEST_ASSERT_EQUAL_CHAR('T', chars[1].character);
     TEST_ASSERT_EQUAL(4000, chars[1].timestamp_us);
 }
+
+void test_decoder_pop_after_overrun(void) {
+    decoder_reset();
+
+    /* Nothing popped while 258 characters arrive: E x 257, then T */
+    for (int i = 0; i < 257; i++) {
+        decoder_handle_event(KEY_EVENT_DIT, (int64_t)(i * 1000));
+        decoder_handle_event(KEY_EVENT_CHAR_GAP, (int64_t)(i * 1000 + 500));
+    }
+    decoder_handle_event(KEY_EVENT_DAH, 300000);
+    decoder_handle_event(KEY_EVENT_CHAR_GAP, 301000);
+
+    /* Oldest two were overwritten; the rest is still readable in order */
+    size_t popped = 0;
+    decoded_char_t ch;
+    decoded_char_t last = { 0 };
+    while ((ch = decoder_pop_char()).character != '\0') {
+        last = ch;
+        popped++;
+    }
+    TEST_ASSERT_EQUAL(256, popped);
+    TEST_ASSERT_EQUAL_CHAR('T', last.character);
+    TEST_ASSERT_EQUAL_CHAR('\0', decoder_pop_char().character);
+}
+
+void test_decoder_read_since_independent(void) {
+    decoder_reset();
+    decoder_handle_event(KEY_EVENT_DIT, 1000);       /* E */
+    decoder_handle_event(KEY_EVENT_CHAR_GAP, 2000);
+
+    uint32_t console = 0;
+    uint32_t web = decoder_text_seq();  /* New text only */
+    TEST_ASSERT_EQUAL_UINT32(1, web);
+
+    decoded_char_t buf[4];
+    TEST_ASSERT_EQUAL(1, decoder_read_since(&console, buf, 4));
+    TEST_ASSERT_EQUAL_CHAR('E', buf[0].character);
+    TEST_ASSERT_EQUAL(0, decoder_read_since(&web, buf, 4));
+
+    decoder_handle_event(KEY_EVENT_DAH, 3000);       /* T */
+    decoder_handle_event(KEY_EVENT_CHAR_GAP, 4000);
+
+    /* Both readers see T, and pop_char is not affected by either */
+    TEST_ASSERT_EQUAL(1, decoder_read_since(&console, buf, 4));
+    TEST_ASSERT_EQUAL_CHAR('T', buf[0].character);
+    TEST_ASSERT_EQUAL(1, decoder_read_since(&web, buf, 4));
+    TEST_ASSERT_EQUAL_CHAR('T', buf[0].character);
+    TEST_ASSERT_EQUAL(4000, buf[0].timestamp_us);
+    TEST_ASSERT_EQUAL_UINT32(2, web);
+    TEST_ASSERT_EQUAL_CHAR('E', decoder_pop_char().character);
+}
+
+void test_decoder_read_since_lapped(void) {
+    decoder_reset();
+    for (int i = 0; i < 300; i++) {
+        decoder_handle_event(KEY_EVENT_DIT, (int64_t)(i * 1000));
+        decoder_handle_event(KEY_EVENT_CHAR_GAP, (int64_t)(i * 1000 + 500));
+    }
+
+    /* Cursor 10 was overwritten: reader resumes at the oldest held */
+    uint32_t cursor = 10;
+    decoded_char_t buf[8];
+    TEST_ASSERT_EQUAL(8, decoder_read_since(&cursor, buf, 8));
+    TEST_ASSERT_EQUAL(44500, buf[0].timestamp_us);
+    TEST_ASSERT_EQUAL_UINT32(300 - 256 + 8, cursor);
+
+    /* Cursor from before a reset restarts from the beginning */
+    decoder_reset();
+    decoder_handle_event(KEY_EVENT_DIT, 1000);
+    decoder_handle_event(KEY_EVENT_CHAR_GAP, 2000);
+    TEST_ASSERT_EQUAL(1, decoder_read_since(&cursor, buf, 8));
+    TEST_ASSERT_EQUAL_UINT32(1, cursor);
+}
+
+void decoder_set_test_stream(keying_stream_t *stream);
+
+/* One sample per ms, read as it goes: the decoder skips ahead if it lags */
+static void push_key(keying_stream_t *stream, uint8_t key, int ms) {
+    stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+    sample.local_key = key;
+    for (int i = 0; i < ms; i++) {
+        stream_push_raw(stream, sample);
+        decoder_process();
+    }
+}
+
+void test_decoder_sent_markers(void) {
+    static stream_sample_t buffer[1024];
+    static keying_stream_t stream;
+    stream_init(&stream, buffer, 1024);
+    decoder_set_test_stream(&stream);
+    decoder_init();
+
+    /* 20 WPM: 'E' from the text keyer, then a hand keyed 'T' */
+    push_key(&stream, 1, 1);
+    stream_mark_text(&stream, 'E');
+    push_key(&stream, 1, 59);
+    push_key(&stream, 0, 180);
+    push_key(&stream, 1, 180);
+    push_key(&stream, 0, 180);
+    push_key(&stream, 1, 20);  /* Ends the gap after 'T' */
+
+    uint32_t cursor = 0;
+    decoded_char_t buf[4];
+    TEST_ASSERT_EQUAL(2, decoder_read_since(&cursor, buf, 4));
+    TEST_ASSERT_EQUAL_CHAR('E', buf[0].character);
+    TEST_ASSERT_EQUAL_CHAR('E', buf[0].sent);
+    TEST_ASSERT_EQUAL_CHAR('T', buf[1].character);
+    TEST_ASSERT_EQUAL_CHAR('\0', buf[1].sent);
+
+    decoder_set_test_stream(NULL);
+    decoder_init();
+}
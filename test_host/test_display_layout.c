@@ -0,0 +1,66 @@
+/**
+ * @file test_display_layout.c
+ * @brief Unit tests for the OLED status screen layout
+ */
+
+#include "unity.h"
+#include "display_layout.h"
+#include <string.h>
+
+static uint8_t s_fb[DISPLAY_FB_SIZE];
+
+static display_status_t idle_status(void) {
+    display_status_t status;
+    memset(&status, 0, sizeof(status));
+    status.wpm = 25;
+    status.preset_name = "Contest";
+    status.iambic_mode = 'B';
+    return status;
+}
+
+static bool page_blank(int page, int from_x, int to_x) {
+    for (int x = from_x; x < to_x; x++) {
+        if (s_fb[page * DISPLAY_WIDTH + x] != 0) {
+            return false;
+        }
+    }
+    return true;
+}
+
+void test_display_text_append_scrolls(void) {
+    char text[DISPLAY_TEXT_LEN + 1] = "";
+
+    display_text_append(text, 'C');
+    display_text_append(text, 'Q');
+    TEST_ASSERT_EQUAL_STRING("CQ", text);
+
+    for (int i = 0; i < DISPLAY_TEXT_LEN; i++) {
+        display_text_append(text, (char)('A' + i % 26));
+    }
+    TEST_ASSERT_EQUAL(DISPLAY_TEXT_LEN, strlen(text));
+    TEST_ASSERT_EQUAL('A', text[0]);
+    TEST_ASSERT_EQUAL('A' + (DISPLAY_TEXT_LEN - 1) % 26, text[DISPLAY_TEXT_LEN - 1]);
+}
+
+void test_display_render_layout(void) {
+    display_status_t status = idle_status();
+    strcpy(status.text, "k");
+
+    display_render(&status, s_fb);
+
+    /* Decoded text on the first text row, lower case drawn as upper */
+    static const uint8_t K[] = {0x7F, 0x08, 0x14, 0x22, 0x41};
+    TEST_ASSERT_EQUAL_UINT8_ARRAY(K, &s_fb[(DISPLAY_PAGES - DISPLAY_TEXT_ROWS) * DISPLAY_WIDTH], 5);
+
+    /* Idle: no TX in the top right corner, no fault */
+    TEST_ASSERT_TRUE(page_blank(0, 100, DISPLAY_WIDTH));
+    TEST_ASSERT_TRUE(page_blank(3, 96, DISPLAY_WIDTH));
+    TEST_ASSERT_FALSE(page_blank(0, 0, 40));
+
+    status.tx = true;
+    status.fault = "LINK_LOSS";
+    display_render(&status, s_fb);
+    TEST_ASSERT_FALSE(page_blank(0, 100, DISPLAY_WIDTH));
+    TEST_ASSERT_FALSE(page_blank(1, 100, DISPLAY_WIDTH));
+    TEST_ASSERT_FALSE(page_blank(3, 96, DISPLAY_WIDTH));
+}
@@ -0,0 +1,82 @@
+/**
+ * @file test_edge_filter.c
+ * @brief Unit tests for the decoder glitch filter
+ */
+
+#include "unity.h"
+#include "edge_filter.h"
+
+/**
+ * @brief Feed a level for a span of 1 ms samples, return accepted edges
+ */
+static int feed(edge_filter_t *f, bool mark, int64_t *now_us, int ms,
+                edge_filter_edge_t *last) {
+    int edges = 0;
+    for (int i = 0; i < ms; i++) {
+        *now_us += 1000;
+        if (edge_filter_update(f, mark, *now_us, last)) {
+            edges++;
+        }
+    }
+    return edges;
+}
+
+void test_edge_filter_off_passes_every_edge(void) {
+    edge_filter_t f;
+    edge_filter_edge_t e;
+    int64_t now = 0;
+    edge_filter_init(&f, 0);
+
+    feed(&f, false, &now, 10, &e);
+    TEST_ASSERT_EQUAL(1, feed(&f, true, &now, 2, &e));
+    TEST_ASSERT_EQUAL(1, feed(&f, false, &now, 5, &e));
+    TEST_ASSERT_TRUE(e.was_mark);
+    TEST_ASSERT_EQUAL_INT64(2000, e.duration_us);
+    TEST_ASSERT_EQUAL_INT64(13000, e.at_us);
+    TEST_ASSERT_EQUAL_UINT32(0, f.glitches);
+}
+
+void test_edge_filter_drops_short_mark(void) {
+    edge_filter_t f;
+    edge_filter_edge_t e;
+    int64_t now = 0;
+    edge_filter_init(&f, 5000);
+
+    feed(&f, false, &now, 20, &e);
+    TEST_ASSERT_EQUAL(0, feed(&f, true, &now, 3, &e));
+    TEST_ASSERT_EQUAL(0, feed(&f, false, &now, 20, &e));
+    TEST_ASSERT_EQUAL_UINT32(1, f.glitches);
+}
+
+void test_edge_filter_merges_bounce_into_mark(void) {
+    edge_filter_t f;
+    edge_filter_edge_t e;
+    int64_t now = 0;
+    edge_filter_init(&f, 5000);
+
+    /* Key down at 1 ms, accepted once held 5 ms, timed from its start */
+    TEST_ASSERT_EQUAL(1, feed(&f, true, &now, 30, &e));
+    TEST_ASSERT_FALSE(e.was_mark);
+    TEST_ASSERT_EQUAL_INT64(1000, e.at_us);
+
+    /* 2 ms contact bounce inside the mark */
+    TEST_ASSERT_EQUAL(0, feed(&f, false, &now, 2, &e));
+    TEST_ASSERT_EQUAL(0, feed(&f, true, &now, 30, &e));
+
+    /* Real key up: one mark of 62 ms, bounce included */
+    TEST_ASSERT_EQUAL(1, feed(&f, false, &now, 10, &e));
+    TEST_ASSERT_TRUE(e.was_mark);
+    TEST_ASSERT_EQUAL_INT64(62000, e.duration_us);
+    TEST_ASSERT_EQUAL_UINT32(1, f.glitches);
+}
+
+void test_edge_filter_silence_confirms_change(void) {
+    edge_filter_t f;
+    edge_filter_edge_t e;
+    edge_filter_init(&f, 5000);
+
+    TEST_ASSERT_FALSE(edge_filter_update(&f, true, 1000, &e));
+    /* No more regular samples: a silence marker moves time on */
+    TEST_ASSERT_TRUE(edge_filter_update(&f, true, 50000, &e));
+    TEST_ASSERT_EQUAL_INT64(1000, e.at_us);
+}
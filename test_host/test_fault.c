@@ -6,6 +6,7 @@
 #include "unity.h"
 #include "fault.h"
 #include "stubs/esp_stubs.h"
+#include <string.h>
 
 static fault_state_t s_fault;
 
@@ -55,3 +56,53 @@ void test_fault_count(void) {
     fault_set(&s_fault, FAULT_OVERRUN, 100);
     TEST_ASSERT_EQUAL(1, fault_get_count(&s_fault));
 }
+
+void test_fault_severity(void) {
+    TEST_ASSERT_EQUAL(FAULT_SEVERITY_NONE, fault_code_severity(FAULT_NONE));
+    TEST_ASSERT_EQUAL(FAULT_SEVERITY_CRITICAL, fault_code_severity(FAULT_LATENCY_EXCEEDED));
+    TEST_ASSERT_EQUAL(FAULT_SEVERITY_CRITICAL, fault_code_severity(FAULT_LOW_VOLTAGE));
+    TEST_ASSERT_EQUAL(FAULT_SEVERITY_CRITICAL, fault_code_severity(FAULT_GPIO_SELFTEST));
+    TEST_ASSERT_EQUAL(FAULT_SEVERITY_WARNING, fault_code_severity(FAULT_LINK_LOSS));
+    TEST_ASSERT_EQUAL(FAULT_SEVERITY_INFO, fault_code_severity(FAULT_BROWNOUT));
+
+    /* Every code has a name and a severity */
+    for (int code = 1; code < FAULT_CODE_COUNT; code++) {
+        TEST_ASSERT_NOT_EQUAL(FAULT_SEVERITY_NONE, fault_code_severity((fault_code_t)code));
+        TEST_ASSERT_TRUE(strcmp("UNKNOWN", fault_code_str((fault_code_t)code)) != 0);
+    }
+}
+
+void test_fault_warning_keeps_output(void) {
+    fault_init(&s_fault);
+
+    fault_set(&s_fault, FAULT_LINK_LOSS, 0);
+    TEST_ASSERT_FALSE(fault_is_active(&s_fault));
+    TEST_ASSERT_EQUAL(FAULT_LINK_LOSS, fault_get_code(&s_fault));
+    TEST_ASSERT_EQUAL(FAULT_SEVERITY_WARNING, fault_get_severity(&s_fault));
+
+    /* Link back: gone from code and raised mask */
+    fault_resolve(&s_fault, FAULT_LINK_LOSS);
+    TEST_ASSERT_EQUAL(FAULT_NONE, fault_get_code(&s_fault));
+    TEST_ASSERT_FALSE(fault_has(&s_fault, FAULT_LINK_LOSS));
+    TEST_ASSERT_EQUAL(1, fault_get_count(&s_fault));
+}
+
+void test_fault_warning_does_not_hide_critical(void) {
+    fault_init(&s_fault);
+
+    fault_set(&s_fault, FAULT_OVERRUN, 7);
+    fault_set(&s_fault, FAULT_CODEC, 0);
+    TEST_ASSERT_TRUE(fault_is_active(&s_fault));
+    TEST_ASSERT_EQUAL(FAULT_OVERRUN, fault_get_code(&s_fault));
+    TEST_ASSERT_EQUAL(7, fault_get_data(&s_fault));
+    TEST_ASSERT_EQUAL(FAULT_BIT(FAULT_OVERRUN) | FAULT_BIT(FAULT_CODEC),
+                      fault_get_raised(&s_fault));
+
+    /* Only fault_clear() ends a critical fault */
+    fault_resolve(&s_fault, FAULT_OVERRUN);
+    TEST_ASSERT_TRUE(fault_is_active(&s_fault));
+
+    fault_clear(&s_fault);
+    TEST_ASSERT_FALSE(fault_is_active(&s_fault));
+    TEST_ASSERT_EQUAL(0, fault_get_raised(&s_fault));
+}
@@ -0,0 +1,80 @@
+/**
+ * @file test_fault_indicator.c
+ * @brief Unit tests for fault blink codes, chirps and banners
+ */
+
+#include "unity.h"
+#include "fault_indicator.h"
+#include <string.h>
+
+void test_fault_indicator_pick(void) {
+    fault_state_t fault = FAULT_STATE_INIT;
+    TEST_ASSERT_EQUAL(FAULT_NONE, fault_indicator_pick(&fault));
+
+    /* Warning beats info, critical beats both */
+    fault_set(&fault, FAULT_WATCHDOG, 0);
+    TEST_ASSERT_EQUAL(FAULT_WATCHDOG, fault_indicator_pick(&fault));
+    fault_set(&fault, FAULT_CODEC, 0);
+    fault_set(&fault, FAULT_LINK_LOSS, 0);
+    TEST_ASSERT_EQUAL(FAULT_LINK_LOSS, fault_indicator_pick(&fault));
+    fault_set(&fault, FAULT_LOW_VOLTAGE, 11000);
+    TEST_ASSERT_EQUAL(FAULT_LOW_VOLTAGE, fault_indicator_pick(&fault));
+}
+
+void test_fault_indicator_blink_code(void) {
+    fault_state_t fault = FAULT_STATE_INIT;
+    fault_indicator_t ind;
+    fault_indicator_init(&ind);
+    TEST_ASSERT_FALSE(fault_indicator_update(&ind, &fault, 0, true));
+    TEST_ASSERT_FALSE(fault_indicator_blink(&ind, 0));
+    TEST_ASSERT_NULL(fault_indicator_banner(&ind));
+
+    /* FAULT_LINK_LOSS (6): six blinks, then the gap */
+    fault_set(&fault, FAULT_LINK_LOSS, 0);
+    TEST_ASSERT_TRUE(fault_indicator_update(&ind, &fault, 1000000, true));
+    TEST_ASSERT_EQUAL_STRING("LINK_LOSS", fault_indicator_banner(&ind));
+    int blinks = 0;
+    bool was_on = false;
+    int64_t cycle_us = 6 * (FAULT_BLINK_ON_US + FAULT_BLINK_OFF_US) + FAULT_BLINK_GAP_US;
+    for (int64_t t = 0; t < cycle_us; t += 10000) {
+        bool on = fault_indicator_blink(&ind, 1000000 + t);
+        if (on && !was_on) {
+            blinks++;
+        }
+        was_on = on;
+    }
+    TEST_ASSERT_EQUAL(6, blinks);
+    TEST_ASSERT_TRUE(fault_indicator_blink(&ind, 1000000 + cycle_us));
+
+    /* Info faults show a banner but do not blink */
+    fault_resolve(&fault, FAULT_LINK_LOSS);
+    fault_set(&fault, FAULT_BROWNOUT, 0);
+    TEST_ASSERT_TRUE(fault_indicator_update(&ind, &fault, 9000000, true));
+    TEST_ASSERT_EQUAL_STRING("BROWNOUT", fault_indicator_banner(&ind));
+    TEST_ASSERT_FALSE(fault_indicator_blink(&ind, 9000000));
+}
+
+void test_fault_indicator_chirp(void) {
+    fault_state_t fault = FAULT_STATE_INIT;
+    fault_indicator_t ind;
+    fault_indicator_init(&ind);
+
+    /* Info: no chirp */
+    fault_set(&fault, FAULT_WATCHDOG, 0);
+    fault_indicator_update(&ind, &fault, 0, true);
+    TEST_ASSERT_FALSE(fault_indicator_chirping(&ind));
+
+    /* New warning: one short chirp */
+    fault_set(&fault, FAULT_NVS, 0);
+    fault_indicator_update(&ind, &fault, 100000, true);
+    TEST_ASSERT_TRUE(fault_indicator_chirping(&ind));
+    fault_indicator_update(&ind, &fault, 100000 + FAULT_CHIRP_US, true);
+    TEST_ASSERT_FALSE(fault_indicator_chirping(&ind));
+
+    /* Resolved and raised again: chirps again; disabled: silent */
+    fault_resolve(&fault, FAULT_NVS);
+    fault_indicator_update(&ind, &fault, 300000, true);
+    fault_set(&fault, FAULT_NVS, 0);
+    fault_indicator_update(&ind, &fault, 400000, false);
+    TEST_ASSERT_FALSE(fault_indicator_chirping(&ind));
+}
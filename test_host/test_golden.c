@@ -0,0 +1,333 @@
+/**
+ * @file test_golden.c
+ * @brief Golden-trace keying regression tests
+ *
+ * Each test_host/golden/<name>.trace file holds a keyer configuration,
+ * a paddle script and the key-edge timeline it produced when it was
+ * recorded. The script runs through iambic FSM → KeyingStream at 1 ms
+ * per tick, the stream is read back (silence markers expanded) and the
+ * resulting timeline must match the stored one line by line.
+ *
+ * Trace file:
+ *
+ *   # comment
+ *   wpm 20
+ *   mode b                 (a | b)
+ *   memory both            (none | dit | dah | both)
+ *   squeeze off            (off | on)
+ *   window 0 100           (memory window start/end %)
+ *   at 0 both              (from t ms: none | dit | dah | both)
+ *   at 150 none
+ *   run 600                (total ms)
+ *   == timeline
+ *        0 both down       (t ms, paddles, key: one line per change)
+ *
+ * To record a new trace write the script part and end it with the
+ * "== timeline" line; to accept an intended behavior change, rerun with
+ * KEYER_GOLDEN_UPDATE=1 and review the diff of the .trace files.
+ */
+
+#include "unity.h"
+#include "iambic.h"
+#include "stream.h"
+#include "sample.h"
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#ifndef GOLDEN_DIR
+#define GOLDEN_DIR "golden"
+#endif
+
+#define GOLDEN_MAX_STEPS 64
+#define GOLDEN_MAX_LINES 256
+#define GOLDEN_LINE_LEN 128
+#define GOLDEN_HEADER_LEN 2048
+#define GOLDEN_STREAM_SIZE 4096
+#define GOLDEN_TIMELINE_MARK "== timeline"
+
+/* ============================================================================
+ * Script
+ * ============================================================================ */
+
+typedef struct {
+    uint32_t at_ms;
+    bool dit;
+    bool dah;
+} golden_step_t;
+
+typedef struct {
+    iambic_config_t config;
+    golden_step_t steps[GOLDEN_MAX_STEPS];
+    size_t step_count;
+    uint32_t run_ms;
+    char header[GOLDEN_HEADER_LEN];     /**< Script part, kept for rewriting */
+    char expected[GOLDEN_MAX_LINES][GOLDEN_LINE_LEN];
+    size_t expected_count;
+} golden_trace_t;
+
+typedef struct {
+    char lines[GOLDEN_MAX_LINES][GOLDEN_LINE_LEN];
+    size_t count;
+} golden_timeline_t;
+
+static golden_trace_t s_trace;
+static golden_timeline_t s_actual;
+static stream_sample_t s_buffer[GOLDEN_STREAM_SIZE];
+static keying_stream_t s_stream;
+
+static bool parse_paddles(const char *word, bool *dit, bool *dah) {
+    if (strcmp(word, "none") == 0) { *dit = false; *dah = false; return true; }
+    if (strcmp(word, "dit") == 0)  { *dit = true;  *dah = false; return true; }
+    if (strcmp(word, "dah") == 0)  { *dit = false; *dah = true;  return true; }
+    if (strcmp(word, "both") == 0) { *dit = true;  *dah = true;  return true; }
+    return false;
+}
+
+static const char *paddles_str(gpio_state_t gpio) {
+    if (gpio_both_pressed(gpio)) {
+        return "both";
+    }
+    if (gpio_dit(gpio)) {
+        return "dit";
+    }
+    return gpio_dah(gpio) ? "dah" : "none";
+}
+
+static bool parse_line(golden_trace_t *trace, const char *line) {
+    char word[16];
+    unsigned a;
+    unsigned b;
+
+    if (sscanf(line, "wpm %u", &a) == 1) {
+        trace->config.wpm = a;
+    } else if (sscanf(line, "mode %15s", word) == 1) {
+        if (strcmp(word, "a") != 0 && strcmp(word, "b") != 0) {
+            return false;
+        }
+        trace->config.mode = (word[0] == 'a') ? IAMBIC_MODE_A : IAMBIC_MODE_B;
+    } else if (sscanf(line, "memory %15s", word) == 1) {
+        if (strcmp(word, "none") == 0)      trace->config.memory_mode = MEMORY_MODE_NONE;
+        else if (strcmp(word, "dit") == 0)  trace->config.memory_mode = MEMORY_MODE_DOT_ONLY;
+        else if (strcmp(word, "dah") == 0)  trace->config.memory_mode = MEMORY_MODE_DAH_ONLY;
+        else if (strcmp(word, "both") == 0) trace->config.memory_mode = MEMORY_MODE_DOT_AND_DAH;
+        else return false;
+    } else if (sscanf(line, "squeeze %15s", word) == 1) {
+        if (strcmp(word, "off") != 0 && strcmp(word, "on") != 0) {
+            return false;
+        }
+        trace->config.squeeze_mode = (strcmp(word, "on") == 0)
+                                         ? SQUEEZE_MODE_LATCH_ON : SQUEEZE_MODE_LATCH_OFF;
+    } else if (sscanf(line, "window %u %u", &a, &b) == 2) {
+        if (a > 100 || b > 100) {
+            return false;
+        }
+        trace->config.mem_window_start_pct = (uint8_t)a;
+        trace->config.mem_window_end_pct = (uint8_t)b;
+    } else if (sscanf(line, "at %u %15s", &a, word) == 2) {
+        if (trace->step_count >= GOLDEN_MAX_STEPS) {
+            return false;
+        }
+        golden_step_t *step = &trace->steps[trace->step_count++];
+        step->at_ms = a;
+        return parse_paddles(word, &step->dit, &step->dah);
+    } else if (sscanf(line, "run %u", &a) == 1) {
+        trace->run_ms = a;
+    } else {
+        return false;
+    }
+    return true;
+}
+
+/**
+ * @brief Load a trace file (script and stored timeline)
+ */
+static bool load_trace(golden_trace_t *trace, const char *path) {
+    FILE *f = fopen(path, "r");
+    if (f == NULL) {
+        return false;
+    }
+
+    memset(trace, 0, sizeof(*trace));
+    trace->config = (iambic_config_t)IAMBIC_CONFIG_DEFAULT;
+
+    char line[GOLDEN_LINE_LEN];
+    bool in_timeline = false;
+    bool ok = true;
+    size_t header_len = 0;
+
+    while (ok && fgets(line, sizeof(line), f) != NULL) {
+        if (!in_timeline) {
+            size_t len = strlen(line);
+            if (header_len + len < GOLDEN_HEADER_LEN) {
+                memcpy(&trace->header[header_len], line, len + 1);
+                header_len += len;
+            }
+        }
+        line[strcspn(line, "\r\n")] = '\0';
+
+        if (strcmp(line, GOLDEN_TIMELINE_MARK) == 0) {
+            in_timeline = true;
+        } else if (in_timeline) {
+            if (trace->expected_count >= GOLDEN_MAX_LINES) {
+                ok = false;
+            } else {
+                snprintf(trace->expected[trace->expected_count++], GOLDEN_LINE_LEN, "%s", line);
+            }
+        } else if (line[0] != '#' && line[0] != '\0') {
+            ok = parse_line(trace, line);
+            if (!ok) {
+                printf("  %s: bad line \"%s\"\n", path, line);
+            }
+        }
+    }
+    fclose(f);
+    return ok && in_timeline && trace->run_ms > 0;
+}
+
+/* ============================================================================
+ * Run
+ * ============================================================================ */
+
+static void timeline_add(golden_timeline_t *tl, uint32_t t_ms, gpio_state_t gpio, bool key) {
+    if (tl->count < GOLDEN_MAX_LINES) {
+        snprintf(tl->lines[tl->count++], GOLDEN_LINE_LEN, "%6u %-4s %s",
+                 (unsigned)t_ms, paddles_str(gpio), key ? "down" : "up");
+    }
+}
+
+/**
+ * @brief Run the script through the FSM and stream, read back the timeline
+ */
+static void run_trace(const golden_trace_t *trace, golden_timeline_t *tl) {
+    iambic_processor_t proc;
+    iambic_init(&proc, &trace->config);
+    stream_init(&s_stream, s_buffer, GOLDEN_STREAM_SIZE);
+
+    bool dit = false;
+    bool dah = false;
+    size_t next = 0;
+    for (uint32_t t = 0; t < trace->run_ms; t++) {
+        while (next < trace->step_count && trace->steps[next].at_ms <= t) {
+            dit = trace->steps[next].dit;
+            dah = trace->steps[next].dah;
+            next++;
+        }
+        stream_sample_t sample = iambic_tick(&proc, (int64_t)t * 1000, gpio_from_paddles(dit, dah));
+        TEST_ASSERT_TRUE_MESSAGE(stream_push(&s_stream, sample), "golden stream full");
+    }
+    stream_flush(&s_stream);
+
+    /* Read back as a consumer would: one tick per sample, silence expanded */
+    memset(tl, 0, sizeof(*tl));
+    gpio_state_t gpio = GPIO_IDLE;
+    bool key = false;
+    uint32_t t = 0;
+    size_t end = stream_write_position(&s_stream);
+    for (size_t idx = 0; idx < end; idx++) {
+        stream_sample_t s;
+        TEST_ASSERT_TRUE(stream_read(&s_stream, idx, &s));
+        if (sample_is_silence(&s)) {
+            t += sample_silence_ticks(&s);
+            continue;
+        }
+        if (s.gpio.bits != gpio.bits || (s.local_key != 0) != key) {
+            gpio = s.gpio;
+            key = s.local_key != 0;
+            timeline_add(tl, t, gpio, key);
+        }
+        t++;
+    }
+}
+
+/* ============================================================================
+ * Compare / Record
+ * ============================================================================ */
+
+static bool write_trace(const char *path, const golden_trace_t *trace, const golden_timeline_t *tl) {
+    FILE *f = fopen(path, "w");
+    if (f == NULL) {
+        return false;
+    }
+    fputs(trace->header, f);
+    for (size_t i = 0; i < tl->count; i++) {
+        fprintf(f, "%s\n", tl->lines[i]);
+    }
+    return fclose(f) == 0;
+}
+
+static void print_diff(const golden_trace_t *trace, const golden_timeline_t *tl) {
+    size_t n = (trace->expected_count > tl->count) ? trace->expected_count : tl->count;
+    printf("  timeline differs (- golden, + now):\n");
+    for (size_t i = 0; i < n; i++) {
+        const char *want = (i < trace->expected_count) ? trace->expected[i] : NULL;
+        const char *got = (i < tl->count) ? tl->lines[i] : NULL;
+        if (want != NULL && got != NULL && strcmp(want, got) == 0) {
+            printf("    %s\n", want);
+            continue;
+        }
+        if (want != NULL) {
+            printf("  - %s\n", want);
+        }
+        if (got != NULL) {
+            printf("  + %s\n", got);
+        }
+    }
+}
+
+static void check_golden(const char *name) {
+    char path[256];
+    snprintf(path, sizeof(path), "%s/%s.trace", GOLDEN_DIR, name);
+    TEST_ASSERT_TRUE_MESSAGE(load_trace(&s_trace, path), "cannot load golden trace");
+
+    run_trace(&s_trace, &s_actual);
+
+    const char *update = getenv("KEYER_GOLDEN_UPDATE");
+    if (update != NULL && strcmp(update, "1") == 0) {
+        TEST_ASSERT_TRUE_MESSAGE(write_trace(path, &s_trace, &s_actual), "cannot write golden trace");
+        return;
+    }
+
+    bool same = s_actual.count == s_trace.expected_count;
+    for (size_t i = 0; same && i < s_actual.count; i++) {
+        same = strcmp(s_actual.lines[i], s_trace.expected[i]) == 0;
+    }
+    if (!same) {
+        printf("\n  %s\n", path);
+        print_diff(&s_trace, &s_actual);
+    }
+    TEST_ASSERT_TRUE_MESSAGE(same, "keying differs from golden trace");
+}
+
+/* ============================================================================
+ * Traces
+ * ============================================================================ */
+
+void test_golden_single_elements(void) {
+    check_golden("single_elements");
+}
+
+void test_golden_mode_a_squeeze(void) {
+    check_golden("mode_a_squeeze");
+}
+
+void test_golden_mode_b_squeeze(void) {
+    check_golden("mode_b_squeeze");
+}
+
+void test_golden_dit_memory(void) {
+    check_golden("dit_memory");
+}
+
+void test_golden_no_memory(void) {
+    check_golden("no_memory");
+}
+
+void test_golden_memory_window(void) {
+    check_golden("memory_window");
+}
+
+void test_golden_squeeze_latch(void) {
+    check_golden("squeeze_latch");
+}
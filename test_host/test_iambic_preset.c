@@ -7,6 +7,13 @@
 #include "iambic_preset.h"
 #include <string.h>
 
+/** Copy of a preset's name (valid until the next call) */
+static const char *preset_name(const iambic_preset_t *p) {
+    static char name[IAMBIC_PRESET_NAME_MAX];
+    iambic_preset_get_name(p, name, sizeof(name));
+    return name;
+}
+
 void test_preset_init(void) {
     iambic_preset_init();
 
@@ -16,12 +23,12 @@ void test_preset_init(void) {
     /* Check that preset 0 has default name */
     const iambic_preset_t *p0 = iambic_preset_get(0);
     TEST_ASSERT_NOT_NULL(p0);
-    TEST_ASSERT_EQUAL_STRING("Default", p0->name);
+    TEST_ASSERT_EQUAL_STRING("Default", preset_name(p0));
 
     /* Check preset 1 "Contest" has higher WPM */
     const iambic_preset_t *p1 = iambic_preset_get(1);
     TEST_ASSERT_NOT_NULL(p1);
-    TEST_ASSERT_EQUAL_STRING("Contest", p1->name);
+    TEST_ASSERT_EQUAL_STRING("Contest", preset_name(p1));
     TEST_ASSERT_EQUAL(35, iambic_preset_get_wpm(p1));
 }
 
@@ -35,7 +42,7 @@ void test_preset_activate(void) {
     /* Active preset should be "Slow" */
     const iambic_preset_t *active = iambic_preset_active();
     TEST_ASSERT_NOT_NULL(active);
-    TEST_ASSERT_EQUAL_STRING("Slow", active->name);
+    TEST_ASSERT_EQUAL_STRING("Slow", preset_name(active));
     TEST_ASSERT_EQUAL(15, iambic_preset_get_wpm(active));
 
     /* Invalid index should fail */
@@ -103,7 +110,7 @@ void test_preset_copy(void) {
 
     /* Verify preset 5 has same values */
     const iambic_preset_t *p5 = iambic_preset_get(5);
-    TEST_ASSERT_EQUAL_STRING("Custom", p5->name);
+    TEST_ASSERT_EQUAL_STRING("Custom", preset_name(p5));
     TEST_ASSERT_EQUAL(42, iambic_preset_get_wpm(p5));
     TEST_ASSERT_EQUAL(IAMBIC_MODE_A, iambic_preset_get_mode(p5));
 
@@ -127,7 +134,7 @@ void test_preset_reset(void) {
     TEST_ASSERT_TRUE(iambic_preset_reset(0));
 
     /* Verify it's back to defaults */
-    TEST_ASSERT_EQUAL_STRING("Default", p0->name);
+    TEST_ASSERT_EQUAL_STRING("Default", preset_name(p0));
     TEST_ASSERT_EQUAL(25, iambic_preset_get_wpm(p0));
 
     /* Invalid index should fail */
@@ -140,17 +147,18 @@ void test_preset_set_name(void) {
     /* Set a short name */
     TEST_ASSERT_TRUE(iambic_preset_set_name(4, "Test"));
     const iambic_preset_t *p4 = iambic_preset_get(4);
-    TEST_ASSERT_EQUAL_STRING("Test", p4->name);
+    TEST_ASSERT_EQUAL_STRING("Test", preset_name(p4));
 
     /* Set a long name (should be truncated) */
     char long_name[64];
     memset(long_name, 'X', 63);
     long_name[63] = '\0';
     TEST_ASSERT_TRUE(iambic_preset_set_name(4, long_name));
-    TEST_ASSERT_EQUAL(IAMBIC_PRESET_NAME_MAX - 1, strlen(p4->name));
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_NAME_MAX - 1, strlen(preset_name(p4)));
 
     /* NULL name should fail */
     TEST_ASSERT_FALSE(iambic_preset_set_name(4, NULL));
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_NAME_MAX - 1, strlen(preset_name(p4)));
 
     /* Invalid index should fail */
     TEST_ASSERT_FALSE(iambic_preset_set_name(10, "Test"));
@@ -185,3 +193,96 @@ void test_preset_null_safety(void) {
     /* Active preset should never be NULL */
     TEST_ASSERT_NOT_NULL(iambic_preset_active());
 }
+
+void test_preset_name_double_buffer(void) {
+    iambic_preset_init();
+
+    /* Each rename moves the sequence by two and leaves it even */
+    const iambic_preset_t *p1 = iambic_preset_get(1);
+    unsigned seq = atomic_load(&p1->name.seq);
+    TEST_ASSERT_TRUE(iambic_preset_set_name(1, "Sprint"));
+    TEST_ASSERT_EQUAL_UINT(seq + 2U, atomic_load(&p1->name.seq));
+    TEST_ASSERT_EQUAL_STRING("Sprint", preset_name(p1));
+
+    /* Copies are truncated to the caller's buffer */
+    char small[4];
+    iambic_preset_get_name(p1, small, sizeof(small));
+    TEST_ASSERT_EQUAL_STRING("Spr", small);
+
+    /* Renames alternate buffers */
+    TEST_ASSERT_TRUE(iambic_preset_set_name(1, "CQWW"));
+    TEST_ASSERT_EQUAL_STRING("CQWW", preset_name(p1));
+    TEST_ASSERT_TRUE(iambic_preset_set_name(1, "Contest"));
+    TEST_ASSERT_EQUAL_STRING("Contest", preset_name(p1));
+}
+
+void test_preset_share_code(void) {
+    iambic_preset_init();
+
+    iambic_preset_t *p1 = iambic_preset_get_mut(1);
+    iambic_preset_set_mode(p1, IAMBIC_MODE_A);
+    iambic_preset_set_memory_mode(p1, MEMORY_MODE_DAH_ONLY);
+    iambic_preset_set_squeeze_mode(p1, SQUEEZE_MODE_LATCH_ON);
+    iambic_preset_set_mem_start(p1, 60);
+    iambic_preset_set_mem_end(p1, 99);
+    iambic_preset_set_weight(p1, 45);
+    iambic_preset_set_sidetone(p1, 600);
+    iambic_preset_set_ptt_tail(p1, 200);
+
+    char code[IAMBIC_PRESET_CODE_MAX];
+    size_t len = iambic_preset_export(1, code, sizeof(code));
+    TEST_ASSERT_TRUE(len > 0);
+    TEST_ASSERT_EQUAL(len, strlen(code));
+
+    /* Round trip into another slot */
+    TEST_ASSERT_TRUE(iambic_preset_import(7, code));
+    const iambic_preset_t *p7 = iambic_preset_get(7);
+    TEST_ASSERT_EQUAL_STRING("Contest", preset_name(p7));
+    TEST_ASSERT_EQUAL(35, iambic_preset_get_wpm(p7));
+    TEST_ASSERT_EQUAL(IAMBIC_MODE_A, iambic_preset_get_mode(p7));
+    TEST_ASSERT_EQUAL(MEMORY_MODE_DAH_ONLY, iambic_preset_get_memory_mode(p7));
+    TEST_ASSERT_EQUAL(SQUEEZE_MODE_LATCH_ON, iambic_preset_get_squeeze_mode(p7));
+    TEST_ASSERT_EQUAL(60, iambic_preset_get_mem_start(p7));
+    TEST_ASSERT_EQUAL(99, iambic_preset_get_mem_end(p7));
+    TEST_ASSERT_EQUAL(45, iambic_preset_get_weight(p7));
+    TEST_ASSERT_EQUAL(600, iambic_preset_get_sidetone(p7));
+    TEST_ASSERT_EQUAL(200, iambic_preset_get_ptt_tail(p7));
+
+    /* Format 1 code (no weight or overrides): standard weight, no overrides */
+    TEST_ASSERT_TRUE(iambic_preset_import(7, "AR4HPGNPbGRl"));
+    TEST_ASSERT_EQUAL_STRING("Old", preset_name(p7));
+    TEST_ASSERT_EQUAL(30, iambic_preset_get_wpm(p7));
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_WEIGHT_DEFAULT, iambic_preset_get_weight(p7));
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_NO_OVERRIDE, iambic_preset_get_sidetone(p7));
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_NO_OVERRIDE, iambic_preset_get_ptt_tail(p7));
+
+    /* Longest name fits the documented buffer size */
+    char long_name[IAMBIC_PRESET_NAME_MAX];
+    memset(long_name, 'N', sizeof(long_name) - 1);
+    long_name[sizeof(long_name) - 1] = '\0';
+    iambic_preset_set_name(2, long_name);
+    TEST_ASSERT_TRUE(iambic_preset_export(2, code, sizeof(code)) > 0);
+    TEST_ASSERT_EQUAL(0, iambic_preset_export(2, code, 10));  /* Too small */
+}
+
+void test_preset_share_code_rejects_bad_input(void) {
+    iambic_preset_init();
+
+    char code[IAMBIC_PRESET_CODE_MAX];
+    TEST_ASSERT_TRUE(iambic_preset_export(1, code, sizeof(code)) > 0);
+
+    /* One changed character breaks the CRC; slot 4 stays untouched */
+    code[3] = (code[3] == 'A') ? 'B' : 'A';
+    TEST_ASSERT_FALSE(iambic_preset_import(4, code));
+    TEST_ASSERT_EQUAL_STRING("", preset_name(iambic_preset_get(4)));
+
+    /* Valid CRC but an empty or inverted memory window (start 99/60, end 60) */
+    TEST_ASSERT_FALSE(iambic_preset_import(4, "AhkHYzwyAAAAAEJhZIE"));
+    TEST_ASSERT_FALSE(iambic_preset_import(4, "AhkHPDwyAAAAAEJhZAM"));
+    TEST_ASSERT_EQUAL_STRING("", preset_name(iambic_preset_get(4)));
+
+    TEST_ASSERT_FALSE(iambic_preset_import(4, "not a code!"));
+    TEST_ASSERT_FALSE(iambic_preset_import(4, "AB"));
+    TEST_ASSERT_FALSE(iambic_preset_import(4, NULL));
+    TEST_ASSERT_EQUAL(0, iambic_preset_export(10, code, sizeof(code)));
+}
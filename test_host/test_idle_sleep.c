@@ -0,0 +1,36 @@
+/**
+ * @file test_idle_sleep.c
+ * @brief Unit tests for the idle sleep timer
+ */
+
+#include "unity.h"
+#include "idle_sleep.h"
+
+void test_idle_sleep_enter_and_wake(void) {
+    idle_sleep_t idle;
+    idle_sleep_init(&idle, 0);
+
+    /* Activity restarts the timer */
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_NONE, idle_sleep_update(&idle, false, 60, 59000000));
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_NONE, idle_sleep_update(&idle, true, 60, 59000000));
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_NONE, idle_sleep_update(&idle, false, 60, 118999999));
+
+    /* Timeout reached: ENTER once */
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_ENTER, idle_sleep_update(&idle, false, 60, 119000000));
+    TEST_ASSERT_TRUE(idle_sleep_is_asleep(&idle));
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_NONE, idle_sleep_update(&idle, false, 60, 200000000));
+
+    /* First activity wakes, the timer starts over */
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_WAKE, idle_sleep_update(&idle, true, 60, 210000000));
+    TEST_ASSERT_FALSE(idle_sleep_is_asleep(&idle));
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_NONE, idle_sleep_update(&idle, false, 60, 269999999));
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_ENTER, idle_sleep_update(&idle, false, 60, 270000000));
+}
+
+void test_idle_sleep_disabled(void) {
+    idle_sleep_t idle;
+    idle_sleep_init(&idle, 0);
+
+    TEST_ASSERT_EQUAL(IDLE_SLEEP_NONE, idle_sleep_update(&idle, false, 0, INT64_C(86400000000)));
+    TEST_ASSERT_FALSE(idle_sleep_is_asleep(&idle));
+}
@@ -0,0 +1,43 @@
+/**
+ * @file test_keyer_clock.c
+ * @brief Unit tests for the clock source
+ */
+
+#include "unity.h"
+#include "keyer_clock.h"
+#include "esp_stubs.h"
+
+static int64_t s_source_us = 0;
+
+static int64_t test_source(void) {
+    return s_source_us;
+}
+
+void test_keyer_clock_simulated(void) {
+    keyer_clock_set_source(NULL);
+    keyer_clock_sim_set(1000);
+    TEST_ASSERT_EQUAL_INT64(1000, keyer_clock_now_us());
+
+    keyer_clock_sim_advance(250);
+    TEST_ASSERT_EQUAL_INT64(1250, keyer_clock_now_us());
+
+    /* The esp_timer stub reads the same clock */
+    TEST_ASSERT_EQUAL_INT64(1250, esp_timer_get_time());
+    esp_timer_set_time(5000);
+    TEST_ASSERT_EQUAL_INT64(5000, keyer_clock_now_us());
+}
+
+void test_keyer_clock_source(void) {
+    keyer_clock_sim_set(0);
+    s_source_us = 42000000;
+    keyer_clock_set_source(test_source);
+    TEST_ASSERT_EQUAL_INT64(42000000, keyer_clock_now_us());
+
+    /* The simulated clock is not touched while a source is installed */
+    keyer_clock_sim_advance(10);
+    TEST_ASSERT_EQUAL_INT64(42000000, keyer_clock_now_us());
+
+    keyer_clock_set_source(NULL);
+    TEST_ASSERT_EQUAL_INT64(10, keyer_clock_now_us());
+    keyer_clock_sim_set(0);
+}
@@ -0,0 +1,56 @@
+/**
+ * @file test_log_bin.c
+ * @brief Tests for binary (deferred-formatting) log messages
+ */
+
+#define RT_LOG_TAG LOG_TAG_RT
+
+#include "unity.h"
+#include "rt_log.h"
+#include <string.h>
+
+void test_log_bin_formats_on_drain(void) {
+    log_stream_init(&g_rt_log_stream);
+
+    RT_LOG_BIN(&g_rt_log_stream, LOG_LEVEL_INFO, 100, RADIO_SELECT, 2);
+
+    /* The ring holds the id and the raw argument, not text */
+    const log_entry_t *raw = &g_rt_log_stream.entries[0];
+    TEST_ASSERT_EQUAL(LOG_MSG_RADIO_SELECT, raw->msg_id);
+    TEST_ASSERT_EQUAL(1, raw->len);
+
+    log_entry_t entry;
+    TEST_ASSERT_TRUE(log_stream_drain(&g_rt_log_stream, &entry));
+    TEST_ASSERT_EQUAL(LOG_MSG_NONE, entry.msg_id);
+    TEST_ASSERT_EQUAL(LOG_TAG_RT, entry.tag);
+    TEST_ASSERT_EQUAL(100, entry.timestamp_us);
+    TEST_ASSERT_EQUAL_STRING_LEN("Radio 2 selected", entry.msg, entry.len);
+    TEST_ASSERT_EQUAL(strlen("Radio 2 selected"), entry.len);
+
+    /* Several arguments, and none */
+    atomic_store(&g_rt_diag_enabled, true);
+    RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_WARN, 200, DRIFT, 12, 60000, 67200);
+    RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_DEBUG, 300, MEM_DIT);
+    atomic_store(&g_rt_diag_enabled, false);
+
+    TEST_ASSERT_TRUE(log_stream_drain(&g_rt_log_stream, &entry));
+    TEST_ASSERT_EQUAL_STRING_LEN("DRIFT 12% (exp=60000 act=67200)", entry.msg, entry.len);
+    TEST_ASSERT_TRUE(log_stream_drain(&g_rt_log_stream, &entry));
+    TEST_ASSERT_EQUAL_STRING_LEN("MEM DIT", entry.msg, entry.len);
+}
+
+void test_log_bin_respects_levels(void) {
+    log_stream_init(&g_rt_log_stream);
+
+    /* Below the tag level: nothing pushed */
+    RT_LOG_BIN(&g_rt_log_stream, LOG_LEVEL_DEBUG, 1, RADIO_SELECT, 1);
+    TEST_ASSERT_EQUAL(0, log_stream_count(&g_rt_log_stream));
+
+    /* Diag switch off: nothing pushed */
+    RT_DIAG_BIN(&g_rt_log_stream, LOG_LEVEL_INFO, 2, KEY_DIT_DOWN);
+    TEST_ASSERT_EQUAL(0, log_stream_count(&g_rt_log_stream));
+
+    TEST_ASSERT_EQUAL_STRING("KEY up %uus", log_msg_format(LOG_MSG_KEY_UP));
+    TEST_ASSERT_NULL(log_msg_format(LOG_MSG_NONE));
+    TEST_ASSERT_NULL(log_msg_format(LOG_MSG_COUNT));
+}
@@ -0,0 +1,93 @@
+/**
+ * @file test_log_drain.c
+ * @brief Tests for the log drain sinks
+ */
+
+#include "unity.h"
+#include "log_drain.h"
+#include <string.h>
+
+static int s_fast_count;
+static int s_slow_count;
+static bool s_slow_busy;
+static char s_fast_last[LOG_LINE_MAX];
+
+static bool fast_sink(const log_entry_t *entry, const char *stream) {
+    (void)stream;
+    log_entry_format(entry, s_fast_last, sizeof(s_fast_last));
+    s_fast_count++;
+    return true;
+}
+
+static bool slow_sink(const log_entry_t *entry, const char *stream) {
+    (void)entry;
+    (void)stream;
+    if (s_slow_busy) {
+        return false;
+    }
+    s_slow_count++;
+    return true;
+}
+
+void test_log_drain_fans_out(void) {
+    log_stream_init(&g_rt_log_stream);
+    log_stream_init(&g_bg_log_stream);
+    s_slow_busy = false;
+
+    int fast = log_drain_add_sink("fast", fast_sink, NULL);
+    int slow = log_drain_add_sink("slow", slow_sink, NULL);
+    TEST_ASSERT_TRUE(fast >= 0 && slow >= 0);
+
+    /* New sinks first get what is left in the ring */
+    log_drain_poll();
+    s_fast_count = 0;
+    s_slow_count = 0;
+
+    RT_LOG_T(&g_bg_log_stream, LOG_TAG_BG, LOG_LEVEL_WARN, 7, "hello %d", 1);
+    TEST_ASSERT_EQUAL(1, log_drain_poll());
+    TEST_ASSERT_EQUAL(1, s_fast_count);
+    TEST_ASSERT_EQUAL(1, s_slow_count);
+    TEST_ASSERT_EQUAL_STRING("[7] WARN bg: hello 1\r\n", s_fast_last);
+
+    log_drain_remove_sink(fast);
+    log_drain_remove_sink(slow);
+}
+
+void test_log_drain_busy_sink_falls_behind(void) {
+    log_stream_init(&g_rt_log_stream);
+    log_stream_init(&g_bg_log_stream);
+    s_slow_busy = false;
+
+    int fast = log_drain_add_sink("fast", fast_sink, NULL);
+    int slow = log_drain_add_sink("slow", slow_sink, NULL);
+    log_drain_poll();
+    s_fast_count = 0;
+    s_slow_count = 0;
+
+    /* Busy sink keeps its place while the other gets everything */
+    s_slow_busy = true;
+    for (int i = 0; i < LOG_DRAIN_RING + 5; i++) {
+        RT_LOG_T(&g_rt_log_stream, LOG_TAG_RT, LOG_LEVEL_ERROR, i, "line %d", i);
+    }
+    TEST_ASSERT_EQUAL(LOG_DRAIN_RING + 5, log_drain_poll());
+    TEST_ASSERT_EQUAL(LOG_DRAIN_RING + 5, s_fast_count);
+    TEST_ASSERT_EQUAL(0, s_slow_count);
+
+    /* Back: it gets the last LOG_DRAIN_RING entries, the rest count as dropped */
+    s_slow_busy = false;
+    RT_LOG_T(&g_rt_log_stream, LOG_TAG_RT, LOG_LEVEL_ERROR, 99, "last");
+    log_drain_poll();
+    TEST_ASSERT_EQUAL(LOG_DRAIN_RING, s_slow_count);
+
+    const char *name;
+    uint32_t dropped;
+    TEST_ASSERT_TRUE(log_drain_sink_info(slow, &name, &dropped));
+    TEST_ASSERT_EQUAL_STRING("slow", name);
+    TEST_ASSERT_EQUAL(6, dropped);
+    TEST_ASSERT_TRUE(log_drain_sink_info(fast, &name, &dropped));
+    TEST_ASSERT_EQUAL(0, dropped);
+
+    log_drain_remove_sink(fast);
+    log_drain_remove_sink(slow);
+    TEST_ASSERT_FALSE(log_drain_sink_info(slow, &name, &dropped));
+}
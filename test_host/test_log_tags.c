@@ -0,0 +1,106 @@
+/**
+ * @file test_log_tags.c
+ * @brief Tests for per-tag runtime log levels
+ */
+
+#define RT_LOG_TAG LOG_TAG_WIFI
+
+#include "unity.h"
+#include "rt_log.h"
+#include <stdlib.h>
+#include <time.h>
+
+static int s_formatted;
+
+/** Counts how often the macro arguments are evaluated */
+static int count_format(void) {
+    return ++s_formatted;
+}
+
+void test_log_tag_names(void) {
+    log_tag_t tag;
+    TEST_ASSERT_TRUE(log_tag_from_str("WiFi", &tag));
+    TEST_ASSERT_EQUAL(LOG_TAG_WIFI, tag);
+    TEST_ASSERT_EQUAL_STRING("cwnet", log_tag_str(LOG_TAG_CWNET));
+    TEST_ASSERT_FALSE(log_tag_from_str("nope", &tag));
+
+    log_level_t level;
+    TEST_ASSERT_TRUE(log_level_from_str("debug", &level));
+    TEST_ASSERT_EQUAL(LOG_LEVEL_DEBUG, level);
+    TEST_ASSERT_TRUE(log_level_from_str("W", &level));
+    TEST_ASSERT_EQUAL(LOG_LEVEL_WARN, level);
+    TEST_ASSERT_TRUE(log_level_from_str("t", &level));
+    TEST_ASSERT_EQUAL(LOG_LEVEL_TRACE, level);
+    TEST_ASSERT_FALSE(log_level_from_str("loud", &level));
+}
+
+void test_log_tag_filters_before_format(void) {
+    log_stream_init(&g_bg_log_stream);
+    s_formatted = 0;
+    TEST_ASSERT_EQUAL(LOG_TAG_DEFAULT_LEVEL, log_tag_get_level(LOG_TAG_WIFI));
+
+    /* Default INFO: debug is dropped without formatting */
+    RT_DEBUG(&g_bg_log_stream, 1, "scan %d", count_format());
+    TEST_ASSERT_EQUAL(0, s_formatted);
+    TEST_ASSERT_EQUAL(0, log_stream_count(&g_bg_log_stream));
+
+    /* "log level wifi debug" */
+    log_tag_set_level(LOG_TAG_WIFI, LOG_LEVEL_DEBUG);
+    RT_DEBUG(&g_bg_log_stream, 2, "scan %d", count_format());
+    TEST_ASSERT_EQUAL(1, s_formatted);
+    TEST_ASSERT_EQUAL(1, log_stream_count(&g_bg_log_stream));
+
+    /* The entry carries the module it came from */
+    log_entry_t entry;
+    TEST_ASSERT_TRUE(log_stream_drain(&g_bg_log_stream, &entry));
+    TEST_ASSERT_EQUAL(LOG_TAG_WIFI, entry.tag);
+    TEST_ASSERT_EQUAL_STRING("scan 1", entry.msg);
+
+    /* Other tags keep their own level */
+    RT_LOG_T(&g_bg_log_stream, LOG_TAG_VPN, LOG_LEVEL_DEBUG, 3, "vpn %d", count_format());
+    TEST_ASSERT_EQUAL(1, s_formatted);
+
+    /* Silenced: even errors are dropped */
+    log_tag_set_level(LOG_TAG_VPN, LOG_LEVEL_ERROR);
+    RT_LOG_T(&g_bg_log_stream, LOG_TAG_VPN, LOG_LEVEL_WARN, 4, "vpn");
+    TEST_ASSERT_EQUAL(0, log_stream_count(&g_bg_log_stream));
+
+    log_tag_set_level(LOG_TAG_WIFI, LOG_TAG_DEFAULT_LEVEL);
+    log_tag_set_level(LOG_TAG_VPN, LOG_TAG_DEFAULT_LEVEL);
+}
+
+void test_log_level_colors(void) {
+    /* Plain by default */
+    TEST_ASSERT_EQUAL_STRING("", log_level_color(LOG_LEVEL_ERROR));
+
+    log_set_color(true);
+    TEST_ASSERT_EQUAL_STRING("\033[1;31m", log_level_color(LOG_LEVEL_ERROR));
+    TEST_ASSERT_EQUAL_STRING("", log_level_color(LOG_LEVEL_INFO));
+    log_set_color(false);
+}
+
+void test_log_wall_clock_format(void) {
+    log_entry_t entry = {
+        .timestamp_us = 2500000,
+        .level = LOG_LEVEL_INFO,
+        .tag = LOG_TAG_WIFI,
+        .len = 2,
+        .msg = "up",
+    };
+    char line[LOG_LINE_MAX];
+
+    /* Not synced: uptime only */
+    log_entry_format(&entry, line, sizeof(line));
+    TEST_ASSERT_EQUAL_STRING("[2500000] INFO wifi: up\r\n", line);
+
+    /* Boot at 2026-10-16 12:00:00.000100 UTC: earlier entries convert too,
+     * printed in UTC whatever TZ says */
+    setenv("TZ", "CET-1", 1);
+    tzset();
+    log_clock_set_boot_epoch(1792152000000100LL);
+    log_entry_format(&entry, line, sizeof(line));
+    TEST_ASSERT_EQUAL_STRING("[2026-10-16 12:00:02.500100Z 2500000] INFO wifi: up\r\n", line);
+    unsetenv("TZ");
+    tzset();
+    log_clock_set_boot_epoch(0);
+}
@@ -11,7 +11,12 @@ void test_stream_init(void);
 void test_stream_push_pop(void);
 void test_stream_wrap_around(void);
 void test_stream_overrun_detection(void);
+void test_stream_lapped_slot(void);
 void test_stream_multiple_consumers(void);
+void test_stream_config_change_flag(void);
+void test_stream_ptt_in_flag(void);
+void test_stream_gpio_from_levels(void);
+void test_stream_text_markers(void);
 
 void test_iambic_init(void);
 void test_iambic_dit(void);
@@ -21,6 +26,21 @@ void test_iambic_mode_b_squeeze(void);
 void test_iambic_memory(void);
 void test_iambic_squeeze_prolonged(void);
 
+/* Stress tests */
+void test_stress_reproducible(void);
+void test_stress_run_without_stalls(void);
+void test_stress_run_stalls_recover(void);
+void test_stress_run_overrun(void);
+
+/* Golden keying trace tests */
+void test_golden_single_elements(void);
+void test_golden_mode_a_squeeze(void);
+void test_golden_mode_b_squeeze(void);
+void test_golden_dit_memory(void);
+void test_golden_no_memory(void);
+void test_golden_memory_window(void);
+void test_golden_squeeze_latch(void);
+
 void test_preset_init(void);
 void test_preset_activate(void);
 void test_preset_get_set_values(void);
@@ -29,14 +49,74 @@ void test_preset_reset(void);
 void test_preset_set_name(void);
 void test_preset_timing_helpers(void);
 void test_preset_null_safety(void);
+void test_preset_name_double_buffer(void);
+void test_preset_share_code(void);
+void test_preset_share_code_rejects_bad_input(void);
+void test_preset_nvs_migrates_v2_slot(void);
+void test_preset_nvs_loads_current_slot(void);
+void test_preset_nvs_rejects_bad_slot(void);
 
 void test_sidetone_init(void);
 void test_sidetone_keying(void);
 void test_sidetone_fade(void);
+void test_sidetone_lut_checksum(void);
 
 void test_fault_init(void);
 void test_fault_set_clear(void);
 void test_fault_count(void);
+void test_fault_severity(void);
+void test_fault_warning_keeps_output(void);
+void test_fault_warning_does_not_hide_critical(void);
+
+void test_rt_stats_init(void);
+void test_rt_stats_bucket(void);
+void test_rt_stats_period(void);
+void test_rt_stats_period_sum_no_wrap(void);
+void test_rt_stats_worst_case(void);
+void test_rt_stats_reset_request(void);
+void test_cpu_load_first_sample_is_reference(void);
+void test_cpu_load_window(void);
+
+/* Clock source tests */
+void test_keyer_clock_simulated(void);
+void test_keyer_clock_source(void);
+
+void test_config_audit_record_and_get(void);
+void test_config_audit_wraps(void);
+void test_config_audit_truncates(void);
+
+void test_config_nvs_roundtrip(void);
+void test_config_nvs_foreign_write_unsealed(void);
+void test_config_nvs_foreign_write_resealed(void);
+void test_config_nvs_foreign_erase_resealed(void);
+void test_config_nvs_old_keys_migrate(void);
+void test_config_nvs_old_keys_erased_on_save(void);
+void test_config_nvs_old_name_accepted(void);
+
+void test_config_match_star_is_all(void);
+void test_config_match_family_glob(void);
+void test_config_match_bare_name_glob(void);
+
+/* Touch paddle tests */
+void test_touch_paddle_threshold_hysteresis(void);
+void test_touch_paddle_sensitivity_clamped(void);
+
+/* Straight key tests */
+void test_straight_key_edge_then_lockout(void);
+void test_straight_key_no_debounce(void);
+
+/* Idle sleep tests */
+void test_idle_sleep_enter_and_wake(void);
+void test_idle_sleep_disabled(void);
+
+/* Supply monitor tests */
+void test_supply_monitor_scale(void);
+void test_supply_monitor_levels(void);
+void test_fault_indicator_pick(void);
+void test_fault_indicator_blink_code(void);
+void test_fault_indicator_chirp(void);
+void test_consumer_lag_registry(void);
+void test_consumer_lag_best_effort(void);
 
 void test_parse_empty_line(void);
 void test_parse_simple_command(void);
@@ -44,10 +124,28 @@ void test_parse_command_with_one_arg(void);
 void test_parse_command_with_two_args(void);
 void test_parse_command_with_three_args(void);
 void test_parse_extra_args_ignored(void);
+void test_parse_rest_of_line(void);
 void test_parse_leading_whitespace(void);
 void test_parse_trailing_whitespace(void);
 void test_parse_multiple_spaces(void);
 
+void test_console_output_fanout(void);
+void test_console_output_crlf(void);
+void test_console_output_remove(void);
+void test_console_output_table_full(void);
+void test_console_output_post(void);
+
+void test_console_lock_init_unlocked(void);
+void test_console_lock_set_and_unlock(void);
+void test_console_lock_allows_when_locked(void);
+void test_console_lock_idle_timeout(void);
+void test_console_lock_idle_without_passphrase(void);
+
+/* Config profile tests */
+void test_config_profile_name_valid(void);
+void test_config_profile_save_load(void);
+void test_config_profile_full_and_delete(void);
+
 void test_config_find_param_wpm(void);
 void test_config_find_param_unknown(void);
 void test_config_get_param_str_wpm(void);
@@ -78,6 +176,17 @@ void test_diag_disabled_by_default(void);
 void test_diag_enable_disable(void);
 void test_diag_macro_does_not_crash_when_disabled(void);
 void test_diag_macro_logs_when_enabled(void);
+void test_log_tag_names(void);
+void test_log_tag_filters_before_format(void);
+void test_log_level_colors(void);
+void test_log_wall_clock_format(void);
+void test_log_bin_formats_on_drain(void);
+void test_log_bin_respects_levels(void);
+void test_crash_log_keeps_last_lines(void);
+void test_crash_log_rejects_damaged_record(void);
+void test_crash_log_keeps_stream_tail(void);
+void test_log_drain_fans_out(void);
+void test_log_drain_busy_sink_falls_behind(void);
 
 /* Morse table tests */
 void test_morse_lookup_letters(void);
@@ -87,8 +196,10 @@ void test_morse_lookup_prosigns(void);
 void test_morse_lookup_invalid(void);
 void test_morse_reverse_lookup(void);
 void test_morse_table_count(void);
+void test_morse_table_round_trip(void);
 void test_morse_match_prosign(void);
 void test_morse_get_prosign_tag(void);
+void test_morse_build_prosign(void);
 
 /* Timing classifier tests */
 void test_timing_init(void);
@@ -102,6 +213,12 @@ void test_timing_ignore_short_durations(void);
 void test_timing_ignore_long_durations(void);
 void test_timing_reset(void);
 void test_timing_null_safety(void);
+
+/* Edge filter tests */
+void test_edge_filter_off_passes_every_edge(void);
+void test_edge_filter_drops_short_mark(void);
+void test_edge_filter_merges_bounce_into_mark(void);
+void test_edge_filter_silence_confirms_change(void);
 void test_key_event_str(void);
 
 /* Decoder tests */
@@ -120,6 +237,73 @@ void test_decoder_timing_access(void);
 void test_decoder_state_str(void);
 void test_decoder_buffer_circular(void);
 void test_decoder_get_text_with_timestamps(void);
+void test_decoder_pop_after_overrun(void);
+void test_decoder_read_since_independent(void);
+void test_decoder_read_since_lapped(void);
+void test_decoder_sent_markers(void);
+
+/* Callsign tests */
+void test_callsign_shapes(void);
+void test_callsign_expand(void);
+void test_callsign_capture(void);
+
+/* Memory button tests */
+void test_memory_buttons_press_after_debounce(void);
+void test_memory_buttons_ignores_bounce(void);
+void test_memory_buttons_long_press_once(void);
+void test_memory_buttons_short_press_is_not_long(void);
+void test_memory_buttons_expander_width(void);
+
+/* Speed encoder tests */
+void test_speed_encoder_slow_turn(void);
+void test_speed_encoder_accelerates(void);
+
+/* Speed pot tests */
+void test_speed_pot_maps_range(void);
+void test_speed_pot_hysteresis(void);
+
+/* Display layout tests */
+void test_display_text_append_scrolls(void);
+void test_display_render_layout(void);
+
+/* SD card recording format tests */
+void test_record_session_names(void);
+void test_record_name_is_safe(void);
+void test_record_format_sample(void);
+
+/* Sequencer tests */
+void test_sequencer_disabled_passes_key(void);
+void test_sequencer_order_and_key_delay(void);
+void test_sequencer_key_off_drops_pending(void);
+
+/* Text directive tests */
+void test_text_directive_parse_commands(void);
+void test_text_directive_rejects_bad_input(void);
+
+/* Contest serial tests */
+void test_contest_serial_expand_codes(void);
+void test_contest_serial_wraps_and_truncates(void);
+void test_contest_serial_advances_when_keyed(void);
+
+/* Type queue tests */
+void test_type_queue_backspace_only_unsent(void);
+void test_type_queue_tail_marks_pending(void);
+void test_type_queue_full_and_wrap(void);
+void test_type_queue_pop_if_matches(void);
+
+/* Abbreviation tests */
+void test_abbrev_table(void);
+void test_abbrev_expands_typed_shortcut(void);
+void test_abbrev_backspace_and_flush(void);
+
+/* Trainer tests */
+void test_trainer_groups_shape(void);
+void test_trainer_calls_and_limits(void);
+void test_trainer_score(void);
+
+/* Pause PTT hold tests */
+void test_pause_ptt_holds_until_resume(void);
+void test_pause_ptt_capped(void);
 
 /* CWNet Timestamp tests */
 void test_timestamp_encode_zero(void);
@@ -260,7 +444,12 @@ int main(void) {
     RUN_TEST(test_stream_push_pop);
     RUN_TEST(test_stream_wrap_around);
     RUN_TEST(test_stream_overrun_detection);
+    RUN_TEST(test_stream_lapped_slot);
     RUN_TEST(test_stream_multiple_consumers);
+    RUN_TEST(test_stream_config_change_flag);
+    RUN_TEST(test_stream_ptt_in_flag);
+    RUN_TEST(test_stream_gpio_from_levels);
+    RUN_TEST(test_stream_text_markers);
 
     /* Iambic tests */
     printf("\n=== Iambic Tests ===\n");
@@ -272,6 +461,23 @@ int main(void) {
     RUN_TEST(test_iambic_memory);
     RUN_TEST(test_iambic_squeeze_prolonged);
 
+    /* Stress input */
+    printf("\n=== Stress Tests ===\n");
+    RUN_TEST(test_stress_reproducible);
+    RUN_TEST(test_stress_run_without_stalls);
+    RUN_TEST(test_stress_run_stalls_recover);
+    RUN_TEST(test_stress_run_overrun);
+
+    /* Golden keying traces */
+    printf("\n=== Golden Trace Tests ===\n");
+    RUN_TEST(test_golden_single_elements);
+    RUN_TEST(test_golden_mode_a_squeeze);
+    RUN_TEST(test_golden_mode_b_squeeze);
+    RUN_TEST(test_golden_dit_memory);
+    RUN_TEST(test_golden_no_memory);
+    RUN_TEST(test_golden_memory_window);
+    RUN_TEST(test_golden_squeeze_latch);
+
     /* Iambic Preset tests */
     printf("\n=== Iambic Preset Tests ===\n");
     RUN_TEST(test_preset_init);
@@ -282,18 +488,91 @@ int main(void) {
     RUN_TEST(test_preset_set_name);
     RUN_TEST(test_preset_timing_helpers);
     RUN_TEST(test_preset_null_safety);
+    RUN_TEST(test_preset_name_double_buffer);
+    RUN_TEST(test_preset_share_code);
+    RUN_TEST(test_preset_share_code_rejects_bad_input);
+    RUN_TEST(test_preset_nvs_migrates_v2_slot);
+    RUN_TEST(test_preset_nvs_loads_current_slot);
+    RUN_TEST(test_preset_nvs_rejects_bad_slot);
 
     /* Sidetone tests */
     printf("\n=== Sidetone Tests ===\n");
     RUN_TEST(test_sidetone_init);
     RUN_TEST(test_sidetone_keying);
     RUN_TEST(test_sidetone_fade);
+    RUN_TEST(test_sidetone_lut_checksum);
 
     /* Fault tests */
     printf("\n=== Fault Tests ===\n");
     RUN_TEST(test_fault_init);
     RUN_TEST(test_fault_set_clear);
     RUN_TEST(test_fault_count);
+    RUN_TEST(test_fault_severity);
+    RUN_TEST(test_fault_warning_keeps_output);
+    RUN_TEST(test_fault_warning_does_not_hide_critical);
+
+    /* RT stats tests */
+    printf("\n=== RT Stats Tests ===\n");
+    RUN_TEST(test_rt_stats_init);
+    RUN_TEST(test_rt_stats_bucket);
+    RUN_TEST(test_rt_stats_period);
+    RUN_TEST(test_rt_stats_period_sum_no_wrap);
+    RUN_TEST(test_rt_stats_worst_case);
+    RUN_TEST(test_rt_stats_reset_request);
+
+    /* CPU load tests */
+    printf("\n=== CPU Load Tests ===\n");
+    RUN_TEST(test_cpu_load_first_sample_is_reference);
+    RUN_TEST(test_cpu_load_window);
+
+    printf("\n=== Clock Source Tests ===\n");
+    RUN_TEST(test_keyer_clock_simulated);
+    RUN_TEST(test_keyer_clock_source);
+
+    /* Config audit tests */
+    printf("\n=== Config Audit Tests ===\n");
+    RUN_TEST(test_config_audit_record_and_get);
+    RUN_TEST(test_config_audit_wraps);
+    RUN_TEST(test_config_audit_truncates);
+
+    printf("\n=== Config NVS Tests ===\n");
+    RUN_TEST(test_config_nvs_roundtrip);
+    RUN_TEST(test_config_nvs_foreign_write_unsealed);
+    RUN_TEST(test_config_nvs_foreign_write_resealed);
+    RUN_TEST(test_config_nvs_foreign_erase_resealed);
+    RUN_TEST(test_config_nvs_old_keys_migrate);
+    RUN_TEST(test_config_nvs_old_keys_erased_on_save);
+    RUN_TEST(test_config_nvs_old_name_accepted);
+
+    printf("\n=== Config Match Tests ===\n");
+    RUN_TEST(test_config_match_star_is_all);
+    RUN_TEST(test_config_match_family_glob);
+    RUN_TEST(test_config_match_bare_name_glob);
+
+    /* Touch paddle tests */
+    printf("\n=== Touch Paddle Tests ===\n");
+    RUN_TEST(test_touch_paddle_threshold_hysteresis);
+    RUN_TEST(test_touch_paddle_sensitivity_clamped);
+
+    /* Straight key tests */
+    printf("\n=== Straight Key Tests ===\n");
+    RUN_TEST(test_straight_key_edge_then_lockout);
+    RUN_TEST(test_straight_key_no_debounce);
+
+    /* Idle sleep tests */
+    printf("\n=== Idle Sleep Tests ===\n");
+    RUN_TEST(test_idle_sleep_enter_and_wake);
+    RUN_TEST(test_idle_sleep_disabled);
+
+    /* Supply monitor tests */
+    printf("\n=== Supply Monitor Tests ===\n");
+    RUN_TEST(test_supply_monitor_scale);
+    RUN_TEST(test_supply_monitor_levels);
+    RUN_TEST(test_fault_indicator_pick);
+    RUN_TEST(test_fault_indicator_blink_code);
+    RUN_TEST(test_fault_indicator_chirp);
+    RUN_TEST(test_consumer_lag_registry);
+    RUN_TEST(test_consumer_lag_best_effort);
 
     /* Console parser tests */
     printf("\n=== Console Parser Tests ===\n");
@@ -303,10 +582,33 @@ int main(void) {
     RUN_TEST(test_parse_command_with_two_args);
     RUN_TEST(test_parse_command_with_three_args);
     RUN_TEST(test_parse_extra_args_ignored);
+    RUN_TEST(test_parse_rest_of_line);
     RUN_TEST(test_parse_leading_whitespace);
     RUN_TEST(test_parse_trailing_whitespace);
     RUN_TEST(test_parse_multiple_spaces);
 
+    /* Console output tests */
+    printf("\n=== Console Output Tests ===\n");
+    RUN_TEST(test_console_output_fanout);
+    RUN_TEST(test_console_output_crlf);
+    RUN_TEST(test_console_output_remove);
+    RUN_TEST(test_console_output_table_full);
+    RUN_TEST(test_console_output_post);
+
+    /* Console lock tests */
+    printf("\n=== Console Lock Tests ===\n");
+    RUN_TEST(test_console_lock_init_unlocked);
+    RUN_TEST(test_console_lock_set_and_unlock);
+    RUN_TEST(test_console_lock_allows_when_locked);
+    RUN_TEST(test_console_lock_idle_timeout);
+    RUN_TEST(test_console_lock_idle_without_passphrase);
+
+    /* Config profile tests */
+    printf("\n=== Config Profile Tests ===\n");
+    RUN_TEST(test_config_profile_name_valid);
+    RUN_TEST(test_config_profile_save_load);
+    RUN_TEST(test_config_profile_full_and_delete);
+
     /* Config console tests - TEMPORARILY DISABLED (requires full console system) */
     /* printf("\n=== Config Console Tests ===\n");
     RUN_TEST(test_config_find_param_wpm);
@@ -345,6 +647,17 @@ int main(void) {
     RUN_TEST(test_diag_enable_disable);
     RUN_TEST(test_diag_macro_does_not_crash_when_disabled);
     RUN_TEST(test_diag_macro_logs_when_enabled);
+    RUN_TEST(test_log_tag_names);
+    RUN_TEST(test_log_tag_filters_before_format);
+    RUN_TEST(test_log_level_colors);
+    RUN_TEST(test_log_wall_clock_format);
+    RUN_TEST(test_log_bin_formats_on_drain);
+    RUN_TEST(test_log_bin_respects_levels);
+    RUN_TEST(test_crash_log_keeps_last_lines);
+    RUN_TEST(test_crash_log_rejects_damaged_record);
+    RUN_TEST(test_crash_log_keeps_stream_tail);
+    RUN_TEST(test_log_drain_fans_out);
+    RUN_TEST(test_log_drain_busy_sink_falls_behind);
 
     /* Morse table tests */
     printf("\n=== Morse Table Tests ===\n");
@@ -355,8 +668,10 @@ int main(void) {
     RUN_TEST(test_morse_lookup_invalid);
     RUN_TEST(test_morse_reverse_lookup);
     RUN_TEST(test_morse_table_count);
+    RUN_TEST(test_morse_table_round_trip);
     RUN_TEST(test_morse_match_prosign);
     RUN_TEST(test_morse_get_prosign_tag);
+    RUN_TEST(test_morse_build_prosign);
 
     /* Timing classifier tests */
     printf("\n=== Timing Classifier Tests ===\n");
@@ -373,6 +688,13 @@ int main(void) {
     RUN_TEST(test_timing_null_safety);
     RUN_TEST(test_key_event_str);
 
+    /* Edge filter tests */
+    printf("\n=== Edge Filter Tests ===\n");
+    RUN_TEST(test_edge_filter_off_passes_every_edge);
+    RUN_TEST(test_edge_filter_drops_short_mark);
+    RUN_TEST(test_edge_filter_merges_bounce_into_mark);
+    RUN_TEST(test_edge_filter_silence_confirms_change);
+
     /* Decoder tests */
     printf("\n=== Decoder Tests ===\n");
     RUN_TEST(test_decoder_init);
@@ -390,6 +712,75 @@ int main(void) {
     RUN_TEST(test_decoder_state_str);
     RUN_TEST(test_decoder_buffer_circular);
     RUN_TEST(test_decoder_get_text_with_timestamps);
+    RUN_TEST(test_decoder_pop_after_overrun);
+    RUN_TEST(test_decoder_read_since_independent);
+    RUN_TEST(test_decoder_read_since_lapped);
+    RUN_TEST(test_decoder_sent_markers);
+
+    /* Callsign tests */
+    printf("\n=== Callsign Tests ===\n");
+    RUN_TEST(test_callsign_shapes);
+    RUN_TEST(test_callsign_expand);
+    RUN_TEST(test_callsign_capture);
+
+    printf("\n=== Memory Button Tests ===\n");
+    RUN_TEST(test_memory_buttons_press_after_debounce);
+    RUN_TEST(test_memory_buttons_ignores_bounce);
+    RUN_TEST(test_memory_buttons_long_press_once);
+    RUN_TEST(test_memory_buttons_short_press_is_not_long);
+    RUN_TEST(test_memory_buttons_expander_width);
+
+    printf("\n=== Speed Encoder Tests ===\n");
+    RUN_TEST(test_speed_encoder_slow_turn);
+    RUN_TEST(test_speed_encoder_accelerates);
+
+    printf("\n=== Speed Pot Tests ===\n");
+    RUN_TEST(test_speed_pot_maps_range);
+    RUN_TEST(test_speed_pot_hysteresis);
+
+    printf("\n=== Display Layout Tests ===\n");
+    RUN_TEST(test_display_text_append_scrolls);
+    RUN_TEST(test_display_render_layout);
+
+    printf("\n=== Record Format Tests ===\n");
+    RUN_TEST(test_record_session_names);
+    RUN_TEST(test_record_name_is_safe);
+    RUN_TEST(test_record_format_sample);
+
+    printf("\n=== Sequencer Tests ===\n");
+    RUN_TEST(test_sequencer_disabled_passes_key);
+    RUN_TEST(test_sequencer_order_and_key_delay);
+    RUN_TEST(test_sequencer_key_off_drops_pending);
+
+    printf("\n=== Text Directive Tests ===\n");
+    RUN_TEST(test_text_directive_parse_commands);
+    RUN_TEST(test_text_directive_rejects_bad_input);
+
+    printf("\n=== Contest Serial Tests ===\n");
+    RUN_TEST(test_contest_serial_expand_codes);
+    RUN_TEST(test_contest_serial_wraps_and_truncates);
+    RUN_TEST(test_contest_serial_advances_when_keyed);
+
+    printf("\n=== Type Queue Tests ===\n");
+    RUN_TEST(test_type_queue_backspace_only_unsent);
+    RUN_TEST(test_type_queue_tail_marks_pending);
+    RUN_TEST(test_type_queue_full_and_wrap);
+    RUN_TEST(test_type_queue_pop_if_matches);
+
+    /* Abbreviation tests */
+    printf("\n=== Abbreviation Tests ===\n");
+    RUN_TEST(test_abbrev_table);
+    RUN_TEST(test_abbrev_expands_typed_shortcut);
+    RUN_TEST(test_abbrev_backspace_and_flush);
+
+    printf("\n=== Trainer Tests ===\n");
+    RUN_TEST(test_trainer_groups_shape);
+    RUN_TEST(test_trainer_calls_and_limits);
+    RUN_TEST(test_trainer_score);
+
+    /* Pause PTT hold tests */
+    RUN_TEST(test_pause_ptt_holds_until_resume);
+    RUN_TEST(test_pause_ptt_capped);
 
     /* CWNet Timestamp tests */
     printf("\n=== CWNet Timestamp Tests ===\n");
@@ -0,0 +1,80 @@
+/**
+ * @file test_memory_buttons.c
+ * @brief Unit tests for message memory button debouncing
+ */
+
+#include "unity.h"
+#include "memory_buttons.h"
+
+void test_memory_buttons_press_after_debounce(void) {
+    memory_buttons_t b;
+    memory_buttons_init(&b);
+
+    TEST_ASSERT_EQUAL(-1, memory_buttons_update(&b, 0x04, 1000));
+    TEST_ASSERT_EQUAL(-1, memory_buttons_update(&b, 0x04, 1000 + MEMORY_BUTTONS_DEBOUNCE_US - 1));
+    TEST_ASSERT_EQUAL(2, memory_buttons_update(&b, 0x04, 1000 + MEMORY_BUTTONS_DEBOUNCE_US));
+
+    /* Held: reported once */
+    TEST_ASSERT_EQUAL(-1, memory_buttons_update(&b, 0x04, 200000));
+
+    /* Release, then press again */
+    memory_buttons_update(&b, 0x00, 300000);
+    TEST_ASSERT_EQUAL(-1, memory_buttons_update(&b, 0x00, 400000));
+    memory_buttons_update(&b, 0x04, 500000);
+    TEST_ASSERT_EQUAL(2, memory_buttons_update(&b, 0x04, 600000));
+}
+
+void test_memory_buttons_ignores_bounce(void) {
+    memory_buttons_t b;
+    memory_buttons_init(&b);
+
+    /* Contact chatter restarts the debounce window */
+    memory_buttons_update(&b, 0x01, 0);
+    memory_buttons_update(&b, 0x00, 5000);
+    memory_buttons_update(&b, 0x01, 10000);
+    TEST_ASSERT_EQUAL(-1, memory_buttons_update(&b, 0x01, 35000));
+    TEST_ASSERT_EQUAL(0, memory_buttons_update(&b, 0x01, 40000));
+}
+
+void test_memory_buttons_long_press_once(void) {
+    memory_buttons_t b;
+    memory_buttons_init(&b);
+
+    memory_buttons_update(&b, 0x02, 0);
+    TEST_ASSERT_EQUAL(1, memory_buttons_update(&b, 0x02, MEMORY_BUTTONS_DEBOUNCE_US));
+    int64_t pressed = MEMORY_BUTTONS_DEBOUNCE_US;
+
+    TEST_ASSERT_EQUAL(-1, memory_buttons_long_press(&b, pressed + MEMORY_BUTTONS_LONG_US - 1));
+    TEST_ASSERT_EQUAL(1, memory_buttons_long_press(&b, pressed + MEMORY_BUTTONS_LONG_US));
+    TEST_ASSERT_EQUAL(-1, memory_buttons_long_press(&b, pressed + 2 * MEMORY_BUTTONS_LONG_US));
+    TEST_ASSERT_TRUE(memory_buttons_is_held(&b, 1));
+    TEST_ASSERT_FALSE(memory_buttons_is_held(&b, 0));
+}
+
+void test_memory_buttons_short_press_is_not_long(void) {
+    memory_buttons_t b;
+    memory_buttons_init(&b);
+
+    memory_buttons_update(&b, 0x01, 0);
+    TEST_ASSERT_EQUAL(0, memory_buttons_update(&b, 0x01, 40000));
+
+    /* Released before the long press time */
+    memory_buttons_update(&b, 0x00, 200000);
+    memory_buttons_update(&b, 0x00, 240000);
+    TEST_ASSERT_FALSE(memory_buttons_is_held(&b, 0));
+    TEST_ASSERT_EQUAL(-1, memory_buttons_long_press(&b, 40000 + MEMORY_BUTTONS_LONG_US));
+}
+
+void test_memory_buttons_expander_width(void) {
+    memory_buttons_t b;
+    memory_buttons_init(&b);
+
+    /* Expander inputs above the first eight */
+    memory_buttons_update(&b, 0x0800, 0);
+    TEST_ASSERT_EQUAL(11, memory_buttons_update(&b, 0x0800, MEMORY_BUTTONS_DEBOUNCE_US));
+    TEST_ASSERT_TRUE(memory_buttons_is_held(&b, 11));
+    TEST_ASSERT_FALSE(memory_buttons_is_held(&b, MEMORY_BUTTONS_MAX));
+
+    memory_buttons_update(&b, 0x8800, 100000);
+    TEST_ASSERT_EQUAL(15, memory_buttons_update(&b, 0x8800, 100000 + MEMORY_BUTTONS_DEBOUNCE_US));
+}
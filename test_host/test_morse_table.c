@@ -63,6 +63,19 @@ void test_morse_table_count(void) {
     TEST_ASSERT_LESS_THAN(100, count);
 }
 
+void test_morse_table_round_trip(void) {
+    /* Every entry decodes and encodes back to itself */
+    for (unsigned i = 0; i < morse_table_count(); i++) {
+        const char *pattern = NULL;
+        char c = morse_table_at(i, &pattern);
+        TEST_ASSERT_NOT_NULL(pattern);
+        TEST_ASSERT_EQUAL_CHAR(c, morse_table_lookup(pattern));
+        TEST_ASSERT_EQUAL_STRING(pattern, morse_table_reverse(c));
+    }
+    TEST_ASSERT_EQUAL_CHAR('\0', morse_table_at(morse_table_count(), NULL));
+    TEST_ASSERT_NULL(morse_table_reverse((char)0xC8));  /* Non-ASCII */
+}
+
 void test_morse_match_prosign(void) {
     const char *pattern = NULL;
 
@@ -98,3 +111,24 @@ void test_morse_get_prosign_tag(void) {
     /* NULL input */
     TEST_ASSERT_NULL(morse_get_prosign_tag(NULL));
 }
+
+void test_morse_build_prosign(void) {
+    char pattern[MORSE_PROSIGN_PATTERN_MAX];
+
+    /* Named prosigns match the table */
+    TEST_ASSERT_EQUAL(4, morse_build_prosign("<BK> TU", pattern, sizeof(pattern)));
+    TEST_ASSERT_EQUAL_STRING("-...-.-", pattern);
+    TEST_ASSERT_EQUAL(4, morse_build_prosign("<kn>", pattern, sizeof(pattern)));
+    TEST_ASSERT_EQUAL_STRING("-.--.", pattern);
+
+    /* Anything run together, up to 4 characters */
+    TEST_ASSERT_EQUAL(5, morse_build_prosign("<SOS>", pattern, sizeof(pattern)));
+    TEST_ASSERT_EQUAL_STRING("...---...", pattern);
+
+    /* Not closed, too short, too long, unknown character */
+    TEST_ASSERT_EQUAL(0, morse_build_prosign("<AR", pattern, sizeof(pattern)));
+    TEST_ASSERT_EQUAL(0, morse_build_prosign("<A>", pattern, sizeof(pattern)));
+    TEST_ASSERT_EQUAL(0, morse_build_prosign("<HELLO>", pattern, sizeof(pattern)));
+    TEST_ASSERT_EQUAL(0, morse_build_prosign("<A B>", pattern, sizeof(pattern)));
+    TEST_ASSERT_EQUAL(0, morse_build_prosign("<A^>", pattern, sizeof(pattern)));
+}
@@ -0,0 +1,37 @@
+/**
+ * @file test_pause_ptt.c
+ * @brief Unit tests for the PTT hold while a message is paused
+ */
+
+#include "unity.h"
+#include "pause_ptt.h"
+
+void test_pause_ptt_holds_until_resume(void) {
+    pause_ptt_t p;
+    pause_ptt_start(&p, true, 1000);
+
+    TEST_ASSERT_TRUE(pause_ptt_tick(&p, 1000));
+    TEST_ASSERT_TRUE(pause_ptt_tick(&p, 1000 + PAUSE_PTT_MAX_US - 1));
+
+    /* Resumed: released at once */
+    pause_ptt_end(&p);
+    TEST_ASSERT_FALSE(pause_ptt_tick(&p, 2000));
+
+    /* RELEASE mode (or practice): never held */
+    pause_ptt_start(&p, false, 5000);
+    TEST_ASSERT_FALSE(pause_ptt_tick(&p, 5000));
+}
+
+void test_pause_ptt_capped(void) {
+    pause_ptt_t p;
+    pause_ptt_start(&p, true, 1000);
+
+    TEST_ASSERT_FALSE(pause_ptt_tick(&p, 1000 + PAUSE_PTT_MAX_US));
+
+    /* Stays released for the rest of the pause */
+    TEST_ASSERT_FALSE(pause_ptt_tick(&p, 1000 + PAUSE_PTT_MAX_US + 1));
+
+    /* A new pause holds again */
+    pause_ptt_start(&p, true, 2 * PAUSE_PTT_MAX_US);
+    TEST_ASSERT_TRUE(pause_ptt_tick(&p, 2 * PAUSE_PTT_MAX_US + 1));
+}
@@ -0,0 +1,97 @@
+/**
+ * @file test_preset_nvs.c
+ * @brief Unit tests for preset slot loading and schema migration
+ */
+
+#include "unity.h"
+#include "preset_nvs.h"
+#include "iambic_preset.h"
+#include <string.h>
+
+/** Schema 2 slot as written by older firmware: name[32], wpm (LE), 5 x u8 */
+#define V2_SLOT_LEN 39
+
+/** Schema 3 slot: V2 plus sidetone (u16), weight and PTT tail (u16) */
+#define V3_SLOT_LEN 44
+
+/** Copy of a preset's name (valid until the next call) */
+static const char *preset_name(const iambic_preset_t *p) {
+    static char name[IAMBIC_PRESET_NAME_MAX];
+    iambic_preset_get_name(p, name, sizeof(name));
+    return name;
+}
+
+static void make_v2_slot(uint8_t *slot, const char *name) {
+    memset(slot, 0, V2_SLOT_LEN);
+    strcpy((char *)slot, name);
+    slot[32] = 28;   /* wpm low byte */
+    slot[33] = 0;    /* wpm high byte */
+    slot[34] = IAMBIC_MODE_A;
+    slot[35] = MEMORY_MODE_DOT_ONLY;
+    slot[36] = SQUEEZE_MODE_LATCH_ON;
+    slot[37] = 40;   /* window start */
+    slot[38] = 90;   /* window end */
+}
+
+void test_preset_nvs_migrates_v2_slot(void) {
+    iambic_preset_init();
+    iambic_preset_t *p = iambic_preset_get_mut(5);
+    iambic_preset_set_sidetone(p, 750);
+    iambic_preset_set_weight(p, 60);
+
+    uint8_t slot[V2_SLOT_LEN];
+    make_v2_slot(slot, "Field Day");
+    TEST_ASSERT_TRUE(preset_nvs_apply_slot(5, 2, slot, sizeof(slot)));
+
+    /* Existing values carry over */
+    TEST_ASSERT_EQUAL_STRING("Field Day", preset_name(p));
+    TEST_ASSERT_EQUAL(28, iambic_preset_get_wpm(p));
+    TEST_ASSERT_EQUAL(IAMBIC_MODE_A, iambic_preset_get_mode(p));
+    TEST_ASSERT_EQUAL(MEMORY_MODE_DOT_ONLY, iambic_preset_get_memory_mode(p));
+    TEST_ASSERT_EQUAL(SQUEEZE_MODE_LATCH_ON, iambic_preset_get_squeeze_mode(p));
+    TEST_ASSERT_EQUAL(40, iambic_preset_get_mem_start(p));
+    TEST_ASSERT_EQUAL(90, iambic_preset_get_mem_end(p));
+
+    /* Newer fields get their defaults: standard weight, no overrides */
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_NO_OVERRIDE, iambic_preset_get_sidetone(p));
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_WEIGHT_DEFAULT, iambic_preset_get_weight(p));
+    TEST_ASSERT_EQUAL(IAMBIC_PRESET_NO_OVERRIDE, iambic_preset_get_ptt_tail(p));
+}
+
+void test_preset_nvs_loads_current_slot(void) {
+    iambic_preset_init();
+    iambic_preset_t *p = iambic_preset_get_mut(6);
+
+    /* Schema 3: v2 layout + sidetone (LE u16) + weight + PTT tail (LE u16) */
+    uint8_t slot[V3_SLOT_LEN];
+    make_v2_slot(slot, "Contest");
+    slot[39] = 0xBC; /* 700 Hz */
+    slot[40] = 0x02;
+    slot[41] = 55;
+    slot[42] = 0x2C; /* 300 ms */
+    slot[43] = 0x01;
+    TEST_ASSERT_TRUE(preset_nvs_apply_slot(6, IAMBIC_PRESET_SCHEMA_VERSION, slot, sizeof(slot)));
+
+    TEST_ASSERT_EQUAL_STRING("Contest", preset_name(p));
+    TEST_ASSERT_EQUAL(28, iambic_preset_get_wpm(p));
+    TEST_ASSERT_EQUAL(55, iambic_preset_get_weight(p));
+    TEST_ASSERT_EQUAL(700, iambic_preset_get_sidetone(p));
+    TEST_ASSERT_EQUAL(300, iambic_preset_get_ptt_tail(p));
+}
+
+void test_preset_nvs_rejects_bad_slot(void) {
+    iambic_preset_init();
+    uint8_t slot[V2_SLOT_LEN];
+    make_v2_slot(slot, "Bogus");
+
+    /* v2 data tagged as current schema has the wrong length */
+    TEST_ASSERT_FALSE(preset_nvs_apply_slot(5, IAMBIC_PRESET_SCHEMA_VERSION, slot, sizeof(slot)));
+    /* Unknown schema, short blob, bad index */
+    TEST_ASSERT_FALSE(preset_nvs_apply_slot(5, 1, slot, sizeof(slot)));
+    TEST_ASSERT_FALSE(preset_nvs_apply_slot(5, 2, slot, sizeof(slot) - 1));
+    TEST_ASSERT_FALSE(preset_nvs_apply_slot(IAMBIC_PRESET_COUNT, 2, slot, sizeof(slot)));
+
+    /* Slot untouched */
+    TEST_ASSERT_EQUAL_STRING("", preset_name(iambic_preset_get(5)));
+    TEST_ASSERT_EQUAL(25, iambic_preset_get_wpm(iambic_preset_get(5)));
+}
@@ -0,0 +1,60 @@
+/**
+ * @file test_record_format.c
+ * @brief Unit tests for the SD card recording names and line format
+ */
+
+#include "unity.h"
+#include "record_format.h"
+
+void test_record_session_names(void) {
+    char name[RECORD_NAME_LEN];
+
+    TEST_ASSERT_TRUE(record_session_name(name, sizeof(name), "KEY", "CSV", 7));
+    TEST_ASSERT_EQUAL_STRING("KEY0007.CSV", name);
+    TEST_ASSERT_EQUAL_UINT16(7, record_session_from_name(name));
+    TEST_ASSERT_EQUAL_UINT16(9999, record_session_from_name("log9999.txt"));
+
+    TEST_ASSERT_FALSE(record_session_name(name, sizeof(name), "KEY", "CSV", 0));
+    TEST_ASSERT_FALSE(record_session_name(name, sizeof(name), "KEY", "CSV", 10000));
+    TEST_ASSERT_FALSE(record_session_name(name, RECORD_NAME_LEN - 1, "KEY", "CSV", 1));
+
+    /* Anything else on the card is not a recording */
+    TEST_ASSERT_EQUAL_UINT16(0, record_session_from_name("KEY12.CSV"));
+    TEST_ASSERT_EQUAL_UINT16(0, record_session_from_name("KEY00A1.CSV"));
+    TEST_ASSERT_EQUAL_UINT16(0, record_session_from_name("1230001.CSV"));
+    TEST_ASSERT_EQUAL_UINT16(0, record_session_from_name("NOTES.TXT"));
+}
+
+void test_record_name_is_safe(void) {
+    TEST_ASSERT_TRUE(record_name_is_safe("KEY0001.CSV"));
+    TEST_ASSERT_TRUE(record_name_is_safe("README"));
+
+    TEST_ASSERT_FALSE(record_name_is_safe(""));
+    TEST_ASSERT_FALSE(record_name_is_safe(".."));
+    TEST_ASSERT_FALSE(record_name_is_safe("../nvs"));
+    TEST_ASSERT_FALSE(record_name_is_safe("DIR/KEY0001.CSV"));
+    TEST_ASSERT_FALSE(record_name_is_safe("KEY.0001.CSV"));
+    TEST_ASSERT_FALSE(record_name_is_safe("LONGNAME1.CSV"));
+    TEST_ASSERT_FALSE(record_name_is_safe("KEY0001.CSVX"));
+}
+
+void test_record_format_sample(void) {
+    stream_sample_t s = STREAM_SAMPLE_EMPTY;
+    s.gpio = GPIO_BOTH;
+    s.local_key = 1;
+    s.audio_level = 255;
+    s.flags = FLAG_GPIO_EDGE | FLAG_LOCAL_EDGE;
+
+    char line[RECORD_LINE_MAX];
+    size_t len = record_format_sample(line, sizeof(line), 1520, &s);
+    TEST_ASSERT_EQUAL_STRING("1520,3,1,255,33\n", line);
+    TEST_ASSERT_EQUAL(16, len);
+
+    /* Widest values still fit */
+    s.gpio.bits = 0xFF;
+    s.flags = 0xFF;
+    len = record_format_sample(line, sizeof(line), UINT32_MAX, &s);
+    TEST_ASSERT_EQUAL_STRING("4294967295,255,1,255,255\n", line);
+
+    TEST_ASSERT_EQUAL(0, record_format_sample(line, 8, 1520, &s));
+}
@@ -0,0 +1,91 @@
+/**
+ * @file test_rt_stats.c
+ * @brief Unit tests for RT loop timing statistics
+ */
+
+#include "unity.h"
+#include "rt_stats.h"
+
+static rt_stats_t s_stats;
+
+void test_rt_stats_init(void) {
+    rt_stats_init(&s_stats, 1000);
+
+    TEST_ASSERT_EQUAL(1000, s_stats.nominal_us);
+    TEST_ASSERT_EQUAL(0, rt_stats_avg_period_us(&s_stats));
+    TEST_ASSERT_EQUAL(0, atomic_load(&s_stats.missed_ticks));
+    TEST_ASSERT_EQUAL(0, atomic_load(&s_stats.period_max_us));
+}
+
+void test_rt_stats_bucket(void) {
+    TEST_ASSERT_EQUAL(0, rt_stats_bucket(0));
+    TEST_ASSERT_EQUAL(0, rt_stats_bucket(9));
+    TEST_ASSERT_EQUAL(1, rt_stats_bucket(10));
+    TEST_ASSERT_EQUAL(3, rt_stats_bucket(99));
+    TEST_ASSERT_EQUAL(RT_STATS_BUCKETS - 1, rt_stats_bucket(1000));
+    TEST_ASSERT_EQUAL(RT_STATS_BUCKETS - 1, rt_stats_bucket(UINT32_MAX));
+}
+
+void test_rt_stats_period(void) {
+    rt_stats_init(&s_stats, 1000);
+
+    rt_stats_record_period(&s_stats, 1000);
+    rt_stats_record_period(&s_stats, 980);
+    rt_stats_record_period(&s_stats, 1060);
+    rt_stats_record_period(&s_stats, 2000);  /* Missed tick */
+
+    TEST_ASSERT_EQUAL(980, atomic_load(&s_stats.period_min_us));
+    TEST_ASSERT_EQUAL(2000, atomic_load(&s_stats.period_max_us));
+    TEST_ASSERT_EQUAL(1260, rt_stats_avg_period_us(&s_stats));
+    TEST_ASSERT_EQUAL(1, atomic_load(&s_stats.missed_ticks));
+
+    TEST_ASSERT_EQUAL(1, atomic_load(&s_stats.hist[0]));  /* 0us */
+    TEST_ASSERT_EQUAL(1, atomic_load(&s_stats.hist[1]));  /* 20us */
+    TEST_ASSERT_EQUAL(1, atomic_load(&s_stats.hist[3]));  /* 60us */
+    TEST_ASSERT_EQUAL(1, atomic_load(&s_stats.hist[RT_STATS_BUCKETS - 1]));  /* 1000us */
+}
+
+void test_rt_stats_period_sum_no_wrap(void) {
+    rt_stats_init(&s_stats, 1000);
+
+    /* Sum exceeds 32 bits */
+    rt_stats_record_period(&s_stats, 3000000000u);
+    rt_stats_record_period(&s_stats, 3000000000u);
+
+    TEST_ASSERT_EQUAL_UINT32(3000000000u, rt_stats_avg_period_us(&s_stats));
+}
+
+void test_rt_stats_worst_case(void) {
+    rt_stats_init(&s_stats, 1000);
+
+    rt_stats_record_push(&s_stats, 3);
+    rt_stats_record_push(&s_stats, 7);
+    rt_stats_record_push(&s_stats, 2);
+    rt_stats_record_consume(&s_stats, 5);
+    rt_stats_record_work(&s_stats, 120);
+    rt_stats_record_press_age(&s_stats, 850);
+    rt_stats_record_press_age(&s_stats, 40);
+
+    TEST_ASSERT_EQUAL(7, atomic_load(&s_stats.push_max_us));
+    TEST_ASSERT_EQUAL(5, atomic_load(&s_stats.consume_max_us));
+    TEST_ASSERT_EQUAL(120, atomic_load(&s_stats.work_max_us));
+    TEST_ASSERT_EQUAL(850, atomic_load(&s_stats.press_age_max_us));
+}
+
+void test_rt_stats_reset_request(void) {
+    rt_stats_init(&s_stats, 1000);
+
+    rt_stats_record_period(&s_stats, 3000);
+    rt_stats_record_push(&s_stats, 50);
+    rt_stats_request_reset(&s_stats);
+
+    /* Reset is applied by the writer on its next period record */
+    TEST_ASSERT_EQUAL(1, atomic_load(&s_stats.missed_ticks));
+    rt_stats_record_period(&s_stats, 1000);
+
+    TEST_ASSERT_FALSE(atomic_load(&s_stats.reset_request));
+    TEST_ASSERT_EQUAL(0, atomic_load(&s_stats.missed_ticks));
+    TEST_ASSERT_EQUAL(0, atomic_load(&s_stats.push_max_us));
+    TEST_ASSERT_EQUAL(1000, atomic_load(&s_stats.period_max_us));
+    TEST_ASSERT_EQUAL(1, atomic_load(&s_stats.period_count));
+}
@@ -0,0 +1,91 @@
+/**
+ * @file test_sequencer.c
+ * @brief Unit tests for the TX sequencer
+ */
+
+#include "unity.h"
+#include "sequencer.h"
+
+#define MS 1000
+
+/** Outputs on, one bit per step */
+static unsigned outputs(const sequencer_t *seq) {
+    unsigned mask = 0;
+    for (int i = 0; i < SEQUENCER_STEPS; i++) {
+        if (sequencer_is_on(seq, (sequencer_step_t)i)) {
+            mask |= 1U << i;
+        }
+    }
+    return mask;
+}
+
+void test_sequencer_disabled_passes_key(void) {
+    static const uint32_t none[SEQUENCER_STEPS - 1] = {0, 0, 0};
+    sequencer_t seq;
+    sequencer_init(&seq, none);
+
+    TEST_ASSERT_TRUE(sequencer_tick(&seq, true, true, 0));
+    TEST_ASSERT_EQUAL_HEX8(0xF, outputs(&seq));
+
+    TEST_ASSERT_FALSE(sequencer_tick(&seq, true, false, 1 * MS));
+    TEST_ASSERT_FALSE(sequencer_tick(&seq, false, false, 2 * MS));
+    TEST_ASSERT_EQUAL_HEX8(0x0, outputs(&seq));
+    TEST_ASSERT_FALSE(sequencer_is_active(&seq));
+}
+
+void test_sequencer_order_and_key_delay(void) {
+    static const uint32_t delays[SEQUENCER_STEPS - 1] = {5, 10, 15};
+    sequencer_t seq;
+    sequencer_init(&seq, delays);
+    bool key_seen = false;
+
+    /* Key down at 0 for 100 ms, PTT until 200 ms */
+    for (int64_t t = 0; t <= 300; t++) {
+        bool ptt = t < 200;
+        bool key = t < 100;
+        bool out = sequencer_tick(&seq, ptt, key, t * MS);
+        unsigned on = outputs(&seq);
+
+        if (t == 0) {
+            TEST_ASSERT_EQUAL_HEX8(0x1, on);             /* Antenna first */
+        } else if (t == 5) {
+            TEST_ASSERT_EQUAL_HEX8(0x3, on);             /* Then amp */
+        } else if (t == 15) {
+            TEST_ASSERT_EQUAL_HEX8(0x7, on);             /* Then radio PTT */
+        } else if (t == 29) {
+            TEST_ASSERT_FALSE(out);
+        } else if (t == 30) {
+            TEST_ASSERT_EQUAL_HEX8(0xF, on);             /* Key after the lead */
+        } else if (t == 200) {
+            TEST_ASSERT_EQUAL_HEX8(0x7, on);             /* Reverse: key off */
+        } else if (t == 215) {
+            TEST_ASSERT_EQUAL_HEX8(0x3, on);
+        } else if (t == 225) {
+            TEST_ASSERT_EQUAL_HEX8(0x1, on);
+        } else if (t == 230) {
+            TEST_ASSERT_EQUAL_HEX8(0x0, on);
+        }
+
+        /* The whole 100 ms element comes out, shifted by the lead */
+        TEST_ASSERT_EQUAL(t >= 30 && t < 130, out);
+        key_seen |= out;
+    }
+    TEST_ASSERT_TRUE(key_seen);
+    TEST_ASSERT_FALSE(sequencer_is_active(&seq));
+}
+
+void test_sequencer_key_off_drops_pending(void) {
+    static const uint32_t delays[SEQUENCER_STEPS - 1] = {5, 5, 5};
+    sequencer_t seq;
+    sequencer_init(&seq, delays);
+
+    sequencer_tick(&seq, true, true, 0);
+    TEST_ASSERT_FALSE(sequencer_set_delays(&seq, delays));  /* Busy */
+
+    sequencer_key_off(&seq);
+    for (int64_t t = 1; t <= 40; t++) {
+        TEST_ASSERT_FALSE(sequencer_tick(&seq, false, false, t * MS));
+    }
+    TEST_ASSERT_FALSE(sequencer_is_active(&seq));
+    TEST_ASSERT_TRUE(sequencer_set_delays(&seq, delays));
+}
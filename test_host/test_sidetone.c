@@ -72,3 +72,7 @@ void test_sidetone_fade(void) {
     sample = sidetone_next_sample(&s_sidetone, false);
     TEST_ASSERT_EQUAL(0, sample);
 }
+
+void test_sidetone_lut_checksum(void) {
+    TEST_ASSERT_EQUAL_HEX32(SINE_LUT_CHECKSUM, sidetone_lut_checksum());
+}
@@ -0,0 +1,34 @@
+/**
+ * @file test_speed_encoder.c
+ * @brief Unit tests for rotary encoder speed acceleration
+ */
+
+#include "unity.h"
+#include "speed_encoder.h"
+
+void test_speed_encoder_slow_turn(void) {
+    speed_encoder_t e;
+    speed_encoder_init(&e);
+
+    TEST_ASSERT_EQUAL(0, speed_encoder_steps(&e, 0, 1000));
+    TEST_ASSERT_EQUAL(1, speed_encoder_steps(&e, 1, 1000000));
+    TEST_ASSERT_EQUAL(1, speed_encoder_steps(&e, 1, 1000000 + SPEED_ENCODER_QUICK_US));
+    TEST_ASSERT_EQUAL(-2, speed_encoder_steps(&e, -2, 2000000));
+}
+
+void test_speed_encoder_accelerates(void) {
+    speed_encoder_t e;
+    speed_encoder_init(&e);
+
+    /* First detent is always 1 WPM */
+    TEST_ASSERT_EQUAL(1, speed_encoder_steps(&e, 1, 1000000));
+    TEST_ASSERT_EQUAL(2, speed_encoder_steps(&e, 1, 1050000));
+    TEST_ASSERT_EQUAL(4, speed_encoder_steps(&e, 1, 1070000));
+
+    /* Two detents in one 40ms poll: 20ms each */
+    TEST_ASSERT_EQUAL(8, speed_encoder_steps(&e, 2, 1110000));
+
+    /* Change of direction starts again at 1 */
+    TEST_ASSERT_EQUAL(-1, speed_encoder_steps(&e, -1, 1120000));
+    TEST_ASSERT_EQUAL(-4, speed_encoder_steps(&e, -1, 1130000));
+}
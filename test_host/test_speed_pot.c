@@ -0,0 +1,50 @@
+/**
+ * @file test_speed_pot.c
+ * @brief Unit tests for speed potentiometer filtering and mapping
+ */
+
+#include "unity.h"
+#include "speed_pot.h"
+
+/** Feed the same reading until the filter settles */
+static uint16_t settle(speed_pot_t *pot, uint16_t raw) {
+    uint16_t result = 0;
+    for (int i = 0; i < 100; i++) {
+        uint16_t wpm = speed_pot_update(pot, raw, 10, 40);
+        if (wpm != 0) {
+            result = wpm;
+        }
+    }
+    return result;
+}
+
+void test_speed_pot_maps_range(void) {
+    speed_pot_t pot;
+    speed_pot_init(&pot);
+
+    /* First reading only primes */
+    TEST_ASSERT_EQUAL(0, speed_pot_update(&pot, 0, 10, 40));
+    TEST_ASSERT_EQUAL(10, pot.wpm);
+
+    TEST_ASSERT_EQUAL(40, settle(&pot, SPEED_POT_RAW_MAX));
+    TEST_ASSERT_EQUAL(25, settle(&pot, SPEED_POT_RAW_MAX / 2));
+    TEST_ASSERT_EQUAL(10, settle(&pot, 0));
+}
+
+void test_speed_pot_hysteresis(void) {
+    speed_pot_t pot;
+    speed_pot_init(&pot);
+
+    /* 30 WPM span over 4095: one WPM is ~136 counts */
+    speed_pot_update(&pot, 1365, 10, 40);
+    TEST_ASSERT_EQUAL(20, pot.wpm);
+
+    /* Noise around the 20/21 boundary (20.5 WPM ~ 1433) changes nothing */
+    for (int i = 0; i < 200; i++) {
+        uint16_t raw = (i & 1) ? 1445 : 1425;
+        TEST_ASSERT_EQUAL(0, speed_pot_update(&pot, raw, 10, 40));
+    }
+
+    /* A real turn does */
+    TEST_ASSERT_EQUAL(21, settle(&pot, 1510));
+}
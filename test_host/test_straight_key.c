@@ -0,0 +1,34 @@
+/**
+ * @file test_straight_key.c
+ * @brief Unit tests for straight key debounce
+ */
+
+#include "unity.h"
+#include "straight_key.h"
+
+void test_straight_key_edge_then_lockout(void) {
+    straight_key_t key;
+    straight_key_init(&key, 5000);
+
+    /* First closure keys at once */
+    TEST_ASSERT_TRUE(straight_key_update(&key, true, 1000000));
+
+    /* Bounce inside the lockout is ignored */
+    TEST_ASSERT_TRUE(straight_key_update(&key, false, 1001000));
+    TEST_ASSERT_TRUE(straight_key_update(&key, true, 1002000));
+    TEST_ASSERT_TRUE(straight_key_update(&key, false, 1004999));
+
+    /* After it, the pin is followed again */
+    TEST_ASSERT_FALSE(straight_key_update(&key, false, 1005000));
+    TEST_ASSERT_FALSE(straight_key_update(&key, true, 1007000));
+    TEST_ASSERT_TRUE(straight_key_update(&key, true, 1010000));
+}
+
+void test_straight_key_no_debounce(void) {
+    straight_key_t key;
+    straight_key_init(&key, 0);
+
+    TEST_ASSERT_TRUE(straight_key_update(&key, true, 1000));
+    TEST_ASSERT_FALSE(straight_key_update(&key, false, 1000));
+    TEST_ASSERT_TRUE(straight_key_update(&key, true, 1001));
+}
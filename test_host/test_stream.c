@@ -88,6 +88,31 @@ void test_stream_overrun_detection(void) {
     TEST_ASSERT_EQUAL(5, lag);
 }
 
+void test_stream_lapped_slot(void) {
+    stream_init(&s_stream, s_test_buffer, TEST_BUFFER_SIZE);
+
+    for (size_t i = 0; i < TEST_BUFFER_SIZE; i++) {
+        stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+        sample.audio_level = (uint8_t)i;
+        stream_push_raw(&s_stream, sample);
+    }
+
+    /* Sample 0 shares its slot with the next write: no longer readable */
+    stream_sample_t out;
+    TEST_ASSERT_FALSE(stream_read(&s_stream, 0, &out));
+    TEST_ASSERT_TRUE(stream_is_overrun(&s_stream, 0));
+    TEST_ASSERT_TRUE(stream_read(&s_stream, 1, &out));
+    TEST_ASSERT_EQUAL(1, out.audio_level);
+    TEST_ASSERT_FALSE(stream_is_overrun(&s_stream, 1));
+
+    /* Resync lands on the oldest readable sample */
+    stream_consumer_t consumer;
+    consumer_init_at(&consumer, &s_stream, 0);
+    consumer_resync(&consumer);
+    TEST_ASSERT_TRUE(consumer_next(&consumer, &out));
+    TEST_ASSERT_EQUAL(1, out.audio_level);
+}
+
 void test_stream_multiple_consumers(void) {
     stream_init(&s_stream, s_test_buffer, TEST_BUFFER_SIZE);
 
@@ -116,3 +141,121 @@ void test_stream_multiple_consumers(void) {
     TEST_ASSERT_TRUE(ok);
     TEST_ASSERT_EQUAL(0, sample3.local_key);
 }
+
+void test_stream_config_change_flag(void) {
+    stream_init(&s_stream, s_test_buffer, TEST_BUFFER_SIZE);
+
+    /* Key down at generation 0, then idle ticks */
+    stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+    sample.local_key = 1;
+    stream_push(&s_stream, sample);
+    stream_push(&s_stream, sample);
+    stream_push(&s_stream, sample);
+    TEST_ASSERT_EQUAL(1, stream_write_position(&s_stream));
+
+    /* Same key state, new generation: not compressed, flagged */
+    sample.config_gen = 1;
+    stream_push(&s_stream, sample);
+    TEST_ASSERT_EQUAL(3, stream_write_position(&s_stream));
+
+    stream_sample_t out;
+    TEST_ASSERT_TRUE(stream_read(&s_stream, 0, &out));
+    TEST_ASSERT_FALSE(sample_has_config_change(&out));
+
+    /* Silence marker carries ticks in config_gen, never a config change */
+    TEST_ASSERT_TRUE(stream_read(&s_stream, 1, &out));
+    TEST_ASSERT_TRUE(sample_is_silence(&out));
+    TEST_ASSERT_FALSE(sample_has_config_change(&out));
+
+    TEST_ASSERT_TRUE(stream_read(&s_stream, 2, &out));
+    TEST_ASSERT_TRUE(sample_has_config_change(&out));
+    TEST_ASSERT_EQUAL(1, out.config_gen);
+    TEST_ASSERT_EQUAL(1, out.local_key);
+}
+
+void test_stream_ptt_in_flag(void) {
+    stream_init(&s_stream, s_test_buffer, TEST_BUFFER_SIZE);
+
+    /* Footswitch held with the key up: the level alone is a change */
+    stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+    sample.flags = FLAG_PTT_IN;
+    stream_push(&s_stream, sample);
+    stream_push(&s_stream, sample);
+    sample.flags = 0;
+    stream_push(&s_stream, sample);
+    TEST_ASSERT_EQUAL(3, stream_write_position(&s_stream));
+
+    stream_sample_t out;
+    TEST_ASSERT_TRUE(stream_read(&s_stream, 0, &out));
+    TEST_ASSERT_TRUE(sample_has_ptt_in(&out));
+    TEST_ASSERT_EQUAL(0, out.local_key);
+
+    /* Held through the silence marker, released after it */
+    TEST_ASSERT_TRUE(stream_read(&s_stream, 1, &out));
+    TEST_ASSERT_TRUE(sample_is_silence(&out));
+    TEST_ASSERT_FALSE(sample_has_ptt_in(&out));
+    TEST_ASSERT_TRUE(stream_read(&s_stream, 2, &out));
+    TEST_ASSERT_FALSE(sample_has_ptt_in(&out));
+}
+
+void test_stream_gpio_from_levels(void) {
+    /* Active low paddles on GPIO3/4, nothing pressed: both pins high */
+    uint64_t active_low = (1ULL << 3) | (1ULL << 4);
+    uint64_t levels = (1ULL << 3) | (1ULL << 4);
+    TEST_ASSERT_TRUE(gpio_is_idle(gpio_from_levels(levels, active_low, 3, 4, 0)));
+
+    /* DIT pulled low, other pins ignored */
+    gpio_state_t gs = gpio_from_levels(levels & ~(1ULL << 3), active_low, 3, 4, 0);
+    TEST_ASSERT_TRUE(gpio_dit(gs));
+    TEST_ASSERT_FALSE(gpio_dah(gs));
+    TEST_ASSERT_FALSE(gpio_key(gs));
+
+    /* Active high straight key in the second bank */
+    gs = gpio_from_levels(levels | (1ULL << 40), active_low, 3, 4, 40);
+    TEST_ASSERT_TRUE(gpio_key(gs));
+    TEST_ASSERT_FALSE(gpio_dit(gs));
+
+    /* Both paddles from the same snapshot */
+    gs = gpio_from_levels(0, active_low, 3, 4, 0);
+    TEST_ASSERT_TRUE(gpio_both_pressed(gs));
+}
+
+void test_stream_text_markers(void) {
+    stream_init(&s_stream, s_test_buffer, TEST_BUFFER_SIZE);
+    TEST_ASSERT_EQUAL(0, stream_marker_position(&s_stream));
+
+    /* Nothing written yet: nothing to mark */
+    stream_mark_text(&s_stream, 'A');
+    TEST_ASSERT_EQUAL(0, stream_marker_position(&s_stream));
+
+    /* Key down, silence, key down again: marks land on the edge samples */
+    stream_sample_t sample = STREAM_SAMPLE_EMPTY;
+    sample.local_key = 1;
+    stream_push(&s_stream, sample);
+    stream_mark_text(&s_stream, 'C');
+    sample.local_key = 0;
+    stream_push(&s_stream, sample);
+    stream_push(&s_stream, sample);
+    sample.local_key = 1;
+    stream_push(&s_stream, sample);
+    stream_mark_text(&s_stream, 'Q');
+
+    stream_marker_t marker;
+    TEST_ASSERT_TRUE(stream_read_marker(&s_stream, 0, &marker));
+    TEST_ASSERT_EQUAL(0, marker.sample_idx);
+    TEST_ASSERT_EQUAL_CHAR('C', marker.character);
+    TEST_ASSERT_TRUE(stream_read_marker(&s_stream, 1, &marker));
+    TEST_ASSERT_EQUAL(stream_write_position(&s_stream) - 1, marker.sample_idx);
+    TEST_ASSERT_EQUAL_CHAR('Q', marker.character);
+    TEST_ASSERT_FALSE(stream_read_marker(&s_stream, 2, &marker));
+
+    /* Lapped markers are reported as lost */
+    for (size_t i = 0; i < STREAM_MARKER_CAPACITY; i++) {
+        stream_mark_text(&s_stream, 'E');
+    }
+    TEST_ASSERT_FALSE(stream_read_marker(&s_stream, 1, &marker));
+    /* Marker 2 shares its slot with the next one to be written */
+    TEST_ASSERT_FALSE(stream_read_marker(&s_stream, 2, &marker));
+    TEST_ASSERT_TRUE(stream_read_marker(&s_stream, 3, &marker));
+    TEST_ASSERT_EQUAL_CHAR('E', marker.character);
+}
@@ -0,0 +1,118 @@
+/**
+ * @file test_stress.c
+ * @brief Unit tests for the stress generator and stress run
+ */
+
+#include "unity.h"
+#include "stress.h"
+#include "iambic_stress.h"
+#include <string.h>
+
+#define STRESS_TEST_TICKS 60000     /* One minute of keying */
+
+static stream_sample_t s_buffer[1024];
+static keying_stream_t s_stream;
+
+void test_stress_reproducible(void) {
+    stress_config_t config = STRESS_CONFIG_DEFAULT;
+    config.seed = 1234;
+    stress_gen_t a;
+    stress_gen_t b;
+    stress_init(&a, &config);
+    stress_init(&b, &config);
+
+    uint32_t patterns[4] = {0};
+    uint32_t speed_changes = 0;
+    uint32_t stalls = 0;
+    for (uint32_t i = 0; i < STRESS_TEST_TICKS; i++) {
+        stress_event_t ea;
+        stress_event_t eb;
+        stress_next(&a, &ea);
+        stress_next(&b, &eb);
+        TEST_ASSERT_EQUAL_UINT8(ea.paddles.bits, eb.paddles.bits);
+        TEST_ASSERT_EQUAL_UINT32(ea.wpm, eb.wpm);
+        TEST_ASSERT_EQUAL_UINT32(ea.stall_ticks, eb.stall_ticks);
+
+        patterns[ea.paddles.bits & 3U]++;
+        if (ea.wpm != 0) {
+            TEST_ASSERT_TRUE(ea.wpm >= config.wpm_min && ea.wpm <= config.wpm_max);
+            speed_changes++;
+        }
+        if (ea.stall_ticks != 0) {
+            TEST_ASSERT_TRUE(ea.stall_ticks <= config.stall_max_ticks);
+            stalls++;
+        }
+    }
+    for (int i = 0; i < 4; i++) {
+        TEST_ASSERT_TRUE(patterns[i] > 0);
+    }
+    TEST_ASSERT_TRUE(speed_changes > 10);
+    TEST_ASSERT_TRUE(stalls > 10);
+
+    /* Another seed, another sequence */
+    config.seed = 4321;
+    stress_init(&b, &config);
+    stress_init(&a, &(stress_config_t)STRESS_CONFIG_DEFAULT);
+    bool differs = false;
+    for (uint32_t i = 0; i < 1000 && !differs; i++) {
+        stress_event_t ea;
+        stress_event_t eb;
+        stress_next(&a, &ea);
+        stress_next(&b, &eb);
+        differs = ea.paddles.bits != eb.paddles.bits;
+    }
+    TEST_ASSERT_TRUE(differs);
+}
+
+void test_stress_run_without_stalls(void) {
+    stress_config_t config = STRESS_CONFIG_DEFAULT;
+    config.stall_permille = 0;
+    stress_report_t report;
+    stream_init(&s_stream, s_buffer, 1024);
+
+    iambic_stress_run(&config, STRESS_TEST_TICKS, &s_stream, 2, &report);
+
+    TEST_ASSERT_EQUAL_UINT32(STRESS_TEST_TICKS, report.ticks);
+    TEST_ASSERT_TRUE(report.key_edges > 100);
+    TEST_ASSERT_TRUE(report.speed_changes > 10);
+    TEST_ASSERT_EQUAL_UINT32(0, report.stalls);
+    TEST_ASSERT_EQUAL_UINT32(0, report.faults_latency + report.faults_overrun);
+    TEST_ASSERT_EQUAL_UINT32(0, report.best_effort_dropped);
+    TEST_ASSERT_TRUE(iambic_stress_ok(&report));
+}
+
+void test_stress_run_stalls_recover(void) {
+    stress_config_t config = STRESS_CONFIG_DEFAULT;
+    config.seed = 77;
+    config.stall_permille = 20;
+    config.stall_max_ticks = 40;
+    stress_report_t report;
+    stream_init(&s_stream, s_buffer, 1024);
+
+    iambic_stress_run(&config, STRESS_TEST_TICKS, &s_stream, 2, &report);
+
+    TEST_ASSERT_TRUE(report.stalls > 0);
+    TEST_ASSERT_TRUE(report.faults_latency > 0);
+    TEST_ASSERT_TRUE(iambic_stress_ok(&report));
+
+    /* Same seed, same run */
+    stress_report_t again;
+    iambic_stress_run(&config, STRESS_TEST_TICKS, &s_stream, 2, &again);
+    TEST_ASSERT_EQUAL_MEMORY(&report, &again, sizeof(report));
+}
+
+void test_stress_run_overrun(void) {
+    /* Lag limit beyond the buffer: long stalls lap the hard RT consumer */
+    stress_config_t config = STRESS_CONFIG_DEFAULT;
+    config.seed = 99;
+    config.stall_permille = 20;
+    config.stall_max_ticks = 400;
+    stress_report_t report;
+    stream_init(&s_stream, s_buffer, 16);
+
+    iambic_stress_run(&config, STRESS_TEST_TICKS, &s_stream, 64, &report);
+
+    TEST_ASSERT_TRUE(report.faults_overrun > 0);
+    TEST_ASSERT_TRUE(report.best_effort_dropped > 0);
+    TEST_ASSERT_TRUE(iambic_stress_ok(&report));
+}
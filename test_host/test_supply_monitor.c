@@ -0,0 +1,43 @@
+/**
+ * @file test_supply_monitor.c
+ * @brief Unit tests for supply voltage levels
+ */
+
+#include "unity.h"
+#include "supply_monitor.h"
+
+void test_supply_monitor_scale(void) {
+    TEST_ASSERT_EQUAL_UINT32(3700, supply_monitor_scale(1850, 200));
+    TEST_ASSERT_EQUAL_UINT32(12045, supply_monitor_scale(2190, 550));
+    TEST_ASSERT_EQUAL_UINT32(1000, supply_monitor_scale(1000, 100));
+}
+
+void test_supply_monitor_levels(void) {
+    supply_monitor_t mon;
+    supply_monitor_init(&mon);
+
+    /* Already low at boot: reported on the first reading */
+    TEST_ASSERT_EQUAL(SUPPLY_LOW, supply_monitor_update(&mon, 11400, 11500, 10500));
+
+    /* Recovering needs the hysteresis margin */
+    for (int i = 0; i < 20; i++) {
+        supply_monitor_update(&mon, 11550, 11500, 10500);
+    }
+    TEST_ASSERT_EQUAL(SUPPLY_LOW, mon.level);
+    for (int i = 0; i < 20; i++) {
+        supply_monitor_update(&mon, 11700, 11500, 10500);
+    }
+    TEST_ASSERT_EQUAL(SUPPLY_OK, mon.level);
+
+    /* A one-second sag under TX load is smoothed out */
+    TEST_ASSERT_EQUAL(SUPPLY_OK, supply_monitor_update(&mon, 11000, 11500, 10500));
+
+    /* A collapsing battery goes critical */
+    for (int i = 0; i < 20; i++) {
+        supply_monitor_update(&mon, 10000, 11500, 10500);
+    }
+    TEST_ASSERT_EQUAL(SUPPLY_CRITICAL, mon.level);
+
+    /* Thresholds at 0 are off */
+    TEST_ASSERT_EQUAL(SUPPLY_OK, supply_monitor_update(&mon, 10000, 0, 0));
+}
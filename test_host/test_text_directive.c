@@ -0,0 +1,53 @@
+/**
+ * @file test_text_directive.c
+ * @brief Unit tests for embedded message commands
+ */
+
+#include "unity.h"
+#include "text_directive.h"
+
+void test_text_directive_parse_commands(void) {
+    text_directive_t d;
+
+    TEST_ASSERT_EQUAL(5, text_directive_parse("|S30|CQ", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_SPEED, d.type);
+    TEST_ASSERT_EQUAL_UINT32(30, d.value);
+
+    TEST_ASSERT_EQUAL(4, text_directive_parse("|p2| K", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_PAUSE, d.type);
+    TEST_ASSERT_EQUAL_UINT32(2, d.value);
+
+    TEST_ASSERT_EQUAL(3, text_directive_parse("|R|", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_REPEAT, d.type);
+
+    TEST_ASSERT_EQUAL(4, text_directive_parse("|m3|", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_MEMORY, d.type);
+    TEST_ASSERT_EQUAL_UINT32(3, d.value);
+
+    TEST_ASSERT_EQUAL(7, text_directive_parse("|n9999| TU", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_SERIAL, d.type);
+    TEST_ASSERT_EQUAL_UINT32(9999, d.value);
+}
+
+void test_text_directive_rejects_bad_input(void) {
+    text_directive_t d;
+
+    /* Closed but not understood: consumed, marked invalid */
+    TEST_ASSERT_EQUAL(5, text_directive_parse("|S99|", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_INVALID, d.type);
+    TEST_ASSERT_EQUAL(4, text_directive_parse("|R1|", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_INVALID, d.type);
+    TEST_ASSERT_EQUAL(4, text_directive_parse("|M9|", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_INVALID, d.type);
+    TEST_ASSERT_EQUAL(3, text_directive_parse("|M|", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_INVALID, d.type);
+    TEST_ASSERT_EQUAL(4, text_directive_parse("|N0|", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_INVALID, d.type);
+    TEST_ASSERT_EQUAL(2, text_directive_parse("||", &d));
+    TEST_ASSERT_EQUAL(TEXT_DIRECTIVE_INVALID, d.type);
+
+    /* No closing bar (or too far away): not a command */
+    TEST_ASSERT_EQUAL(0, text_directive_parse("|S30", &d));
+    TEST_ASSERT_EQUAL(0, text_directive_parse("|CQ TEST|", &d));
+    TEST_ASSERT_EQUAL(0, text_directive_parse("S30|", &d));
+}
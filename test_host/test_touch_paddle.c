@@ -0,0 +1,37 @@
+/**
+ * @file test_touch_paddle.c
+ * @brief Unit tests for touch paddle detection
+ */
+
+#include "unity.h"
+#include "touch_paddle.h"
+
+void test_touch_paddle_threshold_hysteresis(void) {
+    touch_paddle_t pad;
+    touch_paddle_calibrate(&pad, 20000, 20);  /* Touch at +4000, release below +2000 */
+
+    TEST_ASSERT_FALSE(touch_paddle_update(&pad, 20000));
+    TEST_ASSERT_FALSE(touch_paddle_update(&pad, 23999));
+    TEST_ASSERT_TRUE(touch_paddle_update(&pad, 24000));
+
+    /* Finger easing off stays touched down to half the rise */
+    TEST_ASSERT_TRUE(touch_paddle_update(&pad, 22500));
+    TEST_ASSERT_TRUE(touch_paddle_update(&pad, 22000));
+    TEST_ASSERT_FALSE(touch_paddle_update(&pad, 21999));
+
+    /* And needs the full rise again */
+    TEST_ASSERT_FALSE(touch_paddle_update(&pad, 23000));
+}
+
+void test_touch_paddle_sensitivity_clamped(void) {
+    touch_paddle_t pad;
+
+    touch_paddle_calibrate(&pad, 10000, 0);
+    TEST_ASSERT_EQUAL_UINT32(500, pad.on_delta);
+
+    touch_paddle_calibrate(&pad, 10000, 250);
+    TEST_ASSERT_EQUAL_UINT32(10000, pad.on_delta);
+
+    /* Readings below the baseline (drift, noise) are never a touch */
+    TEST_ASSERT_FALSE(touch_paddle_update(&pad, 0));
+}
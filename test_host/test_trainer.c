@@ -0,0 +1,74 @@
+/**
+ * @file test_trainer.c
+ * @brief Unit tests for the Morse trainer text generator
+ */
+
+#include "unity.h"
+#include "trainer.h"
+#include <ctype.h>
+#include <string.h>
+
+void test_trainer_groups_shape(void) {
+    char buf[128];
+    uint32_t seed = 12345;
+
+    TEST_ASSERT_EQUAL(4, trainer_generate(TRAINER_GROUPS, &seed, 4, buf, sizeof(buf)));
+    TEST_ASSERT_EQUAL(4 * TRAINER_GROUP_LEN + 3, strlen(buf));
+    for (size_t i = 0; buf[i] != '\0'; i++) {
+        if (i % (TRAINER_GROUP_LEN + 1) == TRAINER_GROUP_LEN) {
+            TEST_ASSERT_EQUAL_CHAR(' ', buf[i]);
+        } else {
+            TEST_ASSERT_TRUE(isupper((unsigned char)buf[i]) || isdigit((unsigned char)buf[i]));
+        }
+    }
+
+    /* Same seed, same text */
+    char again[128];
+    seed = 12345;
+    trainer_generate(TRAINER_GROUPS, &seed, 4, again, sizeof(again));
+    TEST_ASSERT_EQUAL_STRING(buf, again);
+}
+
+void test_trainer_calls_and_limits(void) {
+    char buf[16];
+    uint32_t seed = 7;
+
+    /* Every call has exactly one digit after its first letter run */
+    for (int round = 0; round < 50; round++) {
+        TEST_ASSERT_EQUAL(1, trainer_generate(TRAINER_CALLS, &seed, 1, buf, sizeof(buf)));
+        size_t len = strlen(buf);
+        TEST_ASSERT_TRUE(len >= 3 && len <= 7);
+        TEST_ASSERT_TRUE(isalpha((unsigned char)buf[0]));
+        TEST_ASSERT_TRUE(isalpha((unsigned char)buf[len - 1]));
+    }
+
+    /* Buffer too small: whole items only */
+    TEST_ASSERT_EQUAL(2, trainer_generate(TRAINER_GROUPS, &seed, 5, buf, 12));
+    TEST_ASSERT_EQUAL(11, strlen(buf));
+
+    trainer_mode_t mode;
+    TEST_ASSERT_EQUAL(0, trainer_mode_from_str("words", &mode));
+    TEST_ASSERT_EQUAL(TRAINER_WORDS, mode);
+    TEST_ASSERT_EQUAL(-1, trainer_mode_from_str("qso", &mode));
+}
+
+void test_trainer_score(void) {
+    trainer_score_t score = {0};
+    TEST_ASSERT_EQUAL(100, trainer_score_percent(&score));
+
+    TEST_ASSERT_EQUAL(0, trainer_score_item(&score, "K1ABC", "K1ABC"));
+    TEST_ASSERT_EQUAL(1, trainer_score_item(&score, "K1ABC", "K1ABD"));   /* Wrong */
+    TEST_ASSERT_EQUAL(1, trainer_score_item(&score, "K1ABC", "K1AC"));    /* Missing */
+    TEST_ASSERT_EQUAL(1, trainer_score_item(&score, "K1ABC", "K1AEBC"));  /* Extra */
+    TEST_ASSERT_EQUAL(4, score.items);
+    TEST_ASSERT_EQUAL(1, score.perfect);
+    TEST_ASSERT_EQUAL(20, score.chars);
+    TEST_ASSERT_EQUAL(3, score.errors);
+    TEST_ASSERT_EQUAL(85, trainer_score_percent(&score));
+
+    /* Nothing or garbage keyed back costs the item, no more */
+    TEST_ASSERT_EQUAL(3, trainer_score_item(&score, "THE", ""));
+    TEST_ASSERT_EQUAL(3, trainer_score_item(&score, "THE", "EEEEEEEEEEEE"));
+    TEST_ASSERT_EQUAL(26, score.chars);
+    TEST_ASSERT_EQUAL(9, score.errors);
+}
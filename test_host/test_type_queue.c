@@ -0,0 +1,105 @@
+/**
+ * @file test_type_queue.c
+ * @brief Unit tests for the keyboard keying queue
+ */
+
+#include "unity.h"
+#include "type_queue.h"
+
+void test_type_queue_backspace_only_unsent(void) {
+    type_queue_t q;
+    type_queue_init(&q);
+    char c;
+
+    TEST_ASSERT_TRUE(type_queue_push(&q, 'C'));
+    TEST_ASSERT_TRUE(type_queue_push(&q, 'Q'));
+    TEST_ASSERT_TRUE(type_queue_pop(&q, &c));
+    TEST_ASSERT_EQUAL_CHAR('C', c);
+
+    /* Q is still pending: removed. C is already keyed: kept */
+    TEST_ASSERT_TRUE(type_queue_backspace(&q));
+    TEST_ASSERT_FALSE(type_queue_backspace(&q));
+    TEST_ASSERT_FALSE(type_queue_pop(&q, &c));
+
+    TEST_ASSERT_TRUE(type_queue_push(&q, 'X'));
+    TEST_ASSERT_TRUE(type_queue_pop(&q, &c));
+    TEST_ASSERT_EQUAL_CHAR('X', c);
+}
+
+void test_type_queue_tail_marks_pending(void) {
+    type_queue_t q;
+    type_queue_init(&q);
+    char buf[8];
+    size_t pending;
+    char c;
+
+    for (const char *s = "CQ TEST"; *s != '\0'; s++) {
+        type_queue_push(&q, *s);
+    }
+    type_queue_pop(&q, &c);
+    type_queue_pop(&q, &c);
+    type_queue_pop(&q, &c);
+
+    TEST_ASSERT_EQUAL(7, type_queue_tail(&q, buf, sizeof(buf), &pending));
+    TEST_ASSERT_EQUAL_STRING("CQ TEST", buf);
+    TEST_ASSERT_EQUAL(4, pending);
+    TEST_ASSERT_EQUAL(4, type_queue_pending(&q));
+
+    /* Peek looks ahead without claiming */
+    TEST_ASSERT_TRUE(type_queue_peek(&q, 1, &c));
+    TEST_ASSERT_EQUAL_CHAR('E', c);
+    TEST_ASSERT_FALSE(type_queue_peek(&q, 4, &c));
+    TEST_ASSERT_EQUAL(4, type_queue_pending(&q));
+
+    /* Shorter buffer keeps the newest characters */
+    TEST_ASSERT_EQUAL(3, type_queue_tail(&q, buf, 4, &pending));
+    TEST_ASSERT_EQUAL_STRING("EST", buf);
+    TEST_ASSERT_EQUAL(3, pending);
+}
+
+void test_type_queue_full_and_wrap(void) {
+    type_queue_t q;
+    type_queue_init(&q);
+    char c;
+
+    for (int i = 0; i < TYPE_QUEUE_SIZE - 1; i++) {
+        TEST_ASSERT_TRUE(type_queue_push(&q, 'E'));
+    }
+    TEST_ASSERT_FALSE(type_queue_push(&q, 'T'));
+
+    /* Keep going past the counter wrap */
+    for (int i = 0; i < 70000; i++) {
+        TEST_ASSERT_TRUE(type_queue_pop(&q, &c));
+        TEST_ASSERT_TRUE(type_queue_push(&q, (char)('A' + (i % 26))));
+    }
+    TEST_ASSERT_EQUAL(TYPE_QUEUE_SIZE - 1, type_queue_pending(&q));
+}
+
+void test_type_queue_pop_if_matches(void) {
+    type_queue_t q;
+    type_queue_init(&q);
+    char c;
+
+    for (const char *s = "<AR>E"; *s != '\0'; s++) {
+        type_queue_push(&q, *s);
+    }
+    TEST_ASSERT_FALSE(type_queue_pop_if(&q, "<AS>", 4));
+    TEST_ASSERT_TRUE(type_queue_pop_if(&q, "<AR>", 4));
+    TEST_ASSERT_EQUAL(1, type_queue_pending(&q));
+    TEST_ASSERT_FALSE(type_queue_pop_if(&q, "EE", 2));  /* Only one pending */
+
+    /* Group edited after the peek: nothing claimed */
+    type_queue_pop(&q, &c);
+    for (const char *s = "<KN>"; *s != '\0'; s++) {
+        type_queue_push(&q, *s);
+    }
+    char group[4];
+    for (size_t i = 0; i < 4; i++) {
+        TEST_ASSERT_TRUE(type_queue_peek(&q, i, &group[i]));
+    }
+    type_queue_backspace(&q);
+    type_queue_push(&q, 'X');
+    TEST_ASSERT_FALSE(type_queue_pop_if(&q, group, 4));
+    TEST_ASSERT_EQUAL(4, type_queue_pending(&q));
+    TEST_ASSERT_TRUE(type_queue_pop_if(&q, "<KNX", 4));
+}